@@ -0,0 +1,448 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Dir, Matrix3, OcctKrsError, Point3, Quaternion, Result, Transform, Vector3};
+
+/// 原点と方向からなる軸（OCCT の `gp_Ax1` 相当）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Axis1 {
+    pub location: Point3,
+    pub direction: Dir,
+}
+
+/// 原点・主方向・X方向からなる右手系の座標系（OCCT の `gp_Ax2` 相当）
+///
+/// Y方向は常に `direction × x_direction` として求めるため、右手系であることが保証される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Axis2 {
+    location: Point3,
+    direction: Dir,
+    x_direction: Dir,
+}
+
+/// 右手系・左手系のいずれも表せる座標系（OCCT の `gp_Ax3` 相当）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Axis3 {
+    location: Point3,
+    direction: Dir,
+    x_direction: Dir,
+    y_direction: Dir,
+}
+
+impl Axis1 {
+    /// 原点と方向から軸を生成する
+    pub fn new(location: Point3, direction: Dir) -> Self {
+        Self {
+            location,
+            direction,
+        }
+    }
+
+    /// 原点を通るX軸を返す
+    pub fn ox() -> Self {
+        Self::new(Point3::origin(), Dir::x_axis())
+    }
+
+    /// 原点を通るY軸を返す
+    pub fn oy() -> Self {
+        Self::new(Point3::origin(), Dir::y_axis())
+    }
+
+    /// 原点を通るZ軸を返す
+    pub fn oz() -> Self {
+        Self::new(Point3::origin(), Dir::z_axis())
+    }
+
+    /// 向きを反転した軸を返す
+    pub fn reversed(&self) -> Self {
+        Self::new(self.location, self.direction.reversed())
+    }
+
+    /// 変換を適用した軸を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self::new(
+            t.transform_point(self.location),
+            self.direction.transformed(t),
+        )
+    }
+}
+
+impl Axis2 {
+    /// 原点・主方向・X方向の目安から座標系を生成する
+    ///
+    /// X方向は `x_hint` を主方向に直交するよう射影して求める。
+    /// `x_hint` が主方向と平行な場合はエラーを返す。
+    pub fn new(location: Point3, direction: Dir, x_hint: Dir) -> Result<Self> {
+        let n = direction.to_vector();
+        let x_direction = Dir::from_vector(n.cross(x_hint.to_vector()).cross(n))
+            .map_err(|_| OcctKrsError::DegenerateGeometry("X方向が主方向と平行です".to_string()))?;
+        Ok(Self {
+            location,
+            direction,
+            x_direction,
+        })
+    }
+
+    /// 原点と主方向から座標系を生成する
+    /// X方向は OCCT の `gp_Ax2(P, V)` と同じ規則で自動的に決める
+    pub fn from_normal(location: Point3, direction: Dir) -> Self {
+        let (nx, ny, nz) = (direction.x(), direction.y(), direction.z());
+        let (a, b, c) = (nx.abs(), ny.abs(), nz.abs());
+        let x = if b <= a && b <= c {
+            if a > c {
+                Vector3::new(-nz, 0.0, nx)
+            } else {
+                Vector3::new(nz, 0.0, -nx)
+            }
+        } else if a <= b && a <= c {
+            if b > c {
+                Vector3::new(0.0, -nz, ny)
+            } else {
+                Vector3::new(0.0, nz, -ny)
+            }
+        } else if a > b {
+            Vector3::new(-ny, nx, 0.0)
+        } else {
+            Vector3::new(ny, -nx, 0.0)
+        };
+        // 単位ベクトルに直交する成分から作るので長さはゼロにならない
+        let x_direction = Dir::new_unchecked(x.normalized());
+        Self {
+            location,
+            direction,
+            x_direction,
+        }
+    }
+
+    /// 標準の座標系（原点, Z, X）を返す
+    pub fn world() -> Self {
+        Self {
+            location: Point3::origin(),
+            direction: Dir::z_axis(),
+            x_direction: Dir::x_axis(),
+        }
+    }
+
+    /// 原点を返す
+    pub fn location(&self) -> Point3 {
+        self.location
+    }
+
+    /// 主方向（Z方向）を返す
+    pub fn direction(&self) -> Dir {
+        self.direction
+    }
+
+    /// X方向を返す
+    pub fn x_direction(&self) -> Dir {
+        self.x_direction
+    }
+
+    /// Y方向を返す
+    pub fn y_direction(&self) -> Dir {
+        Dir::new_unchecked(
+            self.direction
+                .to_vector()
+                .cross(self.x_direction.to_vector()),
+        )
+    }
+
+    /// 主軸を返す
+    pub fn axis(&self) -> Axis1 {
+        Axis1::new(self.location, self.direction)
+    }
+
+    /// 原点を変更した座標系を返す
+    pub fn with_location(&self, location: Point3) -> Self {
+        Self { location, ..*self }
+    }
+
+    /// 局所座標 (x, y, z) に対応する点を返す
+    pub fn point_at(&self, x: f64, y: f64, z: f64) -> Point3 {
+        self.location
+            + self.x_direction.to_vector() * x
+            + self.y_direction().to_vector() * y
+            + self.direction.to_vector() * z
+    }
+
+    /// 変換を適用した座標系を返す
+    ///
+    /// 負のスケールを含む変換では左手系になるため、OCCT と同様に主方向を反転して右手系を保つ。
+    pub fn transformed(&self, t: &Transform) -> Self {
+        let x = self.x_direction.transformed(t);
+        let y = self.y_direction().transformed(t);
+        Self {
+            location: t.transform_point(self.location),
+            direction: Dir::new_unchecked(x.to_vector().cross(y.to_vector())),
+            x_direction: x,
+        }
+    }
+}
+
+impl Axis3 {
+    /// 原点・主方向・X方向の目安から右手系の座標系を生成する
+    /// `x_hint` が主方向と平行な場合はエラーを返す
+    pub fn new(location: Point3, direction: Dir, x_hint: Dir) -> Result<Self> {
+        Axis2::new(location, direction, x_hint).map(Self::from)
+    }
+
+    /// 原点と主方向から右手系の座標系を生成する
+    pub fn from_normal(location: Point3, direction: Dir) -> Self {
+        Self::from(Axis2::from_normal(location, direction))
+    }
+
+    /// 原点を返す
+    pub fn location(&self) -> Point3 {
+        self.location
+    }
+
+    /// 主方向（Z方向）を返す
+    pub fn direction(&self) -> Dir {
+        self.direction
+    }
+
+    /// X方向を返す
+    pub fn x_direction(&self) -> Dir {
+        self.x_direction
+    }
+
+    /// Y方向を返す
+    pub fn y_direction(&self) -> Dir {
+        self.y_direction
+    }
+
+    /// 主軸を返す
+    pub fn axis(&self) -> Axis1 {
+        Axis1::new(self.location, self.direction)
+    }
+
+    /// 右手系であれば `true` を返す
+    pub fn is_direct(&self) -> bool {
+        self.x_direction
+            .to_vector()
+            .cross(self.y_direction.to_vector())
+            .dot(self.direction.to_vector())
+            > 0.0
+    }
+
+    /// X方向を反転する（右手系と左手系が入れ替わる）
+    pub fn x_reverse(&mut self) {
+        self.x_direction = self.x_direction.reversed();
+    }
+
+    /// Y方向を反転する（右手系と左手系が入れ替わる）
+    pub fn y_reverse(&mut self) {
+        self.y_direction = self.y_direction.reversed();
+    }
+
+    /// 主方向を反転する（右手系と左手系が入れ替わる）
+    pub fn z_reverse(&mut self) {
+        self.direction = self.direction.reversed();
+    }
+
+    /// X・Y方向を共有する右手系の座標系を返す
+    /// 左手系の場合は主方向を反転したものになる
+    pub fn to_axis2(&self) -> Axis2 {
+        let direction = if self.is_direct() {
+            self.direction
+        } else {
+            self.direction.reversed()
+        };
+        Axis2 {
+            location: self.location,
+            direction,
+            x_direction: self.x_direction,
+        }
+    }
+
+    /// 局所座標 (x, y, z) に対応する点を返す
+    pub fn point_at(&self, x: f64, y: f64, z: f64) -> Point3 {
+        self.location
+            + self.x_direction.to_vector() * x
+            + self.y_direction.to_vector() * y
+            + self.direction.to_vector() * z
+    }
+
+    /// 変換を適用した座標系を返す
+    ///
+    /// 各方向をそのまま変換するため、負のスケールを含む変換では右手系と左手系が入れ替わる
+    /// （OCCT の `gp_Ax3::Transform` と同じ）。局所座標と点の対応は保たれる。
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            location: t.transform_point(self.location),
+            direction: self.direction.transformed(t),
+            x_direction: self.x_direction.transformed(t),
+            y_direction: self.y_direction.transformed(t),
+        }
+    }
+
+    /// 各軸を列とする行列（局所座標から全体座標への回転部分）を返す
+    fn basis(&self) -> Matrix3 {
+        Matrix3::from_columns(
+            self.x_direction.to_vector(),
+            self.y_direction.to_vector(),
+            self.direction.to_vector(),
+        )
+    }
+}
+
+impl From<Axis2> for Axis3 {
+    fn from(a: Axis2) -> Self {
+        Self {
+            location: a.location,
+            direction: a.direction,
+            x_direction: a.x_direction,
+            y_direction: a.y_direction(),
+        }
+    }
+}
+
+impl Transform {
+    /// 軸 `axis` まわりに角度 `angle`（ラジアン）だけ回転する変換を生成する
+    /// （OCCT の `gp_Trsf::SetRotation(gp_Ax1, angle)` 相当）
+    pub fn rotation(axis: &Axis1, angle: f64) -> Self {
+        let rotation = Quaternion::from_axis_angle(axis.direction.to_vector(), angle);
+        let c = axis.location.to_vector();
+        Self::new(rotation, c - rotation.rotate(c), 1.0)
+    }
+
+    /// 座標系 `from` に置かれた形状を座標系 `to` に移す変換を生成する
+    /// （OCCT の `gp_Trsf::SetDisplacement` 相当）
+    ///
+    /// 右手系と左手系の間の変換は負のスケールを伴う変換として表現する。
+    pub fn displacement(from: &Axis3, to: &Axis3) -> Self {
+        let m = to.basis() * from.basis().transpose();
+        let origin = to.location.to_vector() - m * from.location.to_vector();
+        Self::from_orthogonal(&m, origin)
+    }
+
+    /// 全体座標を座標系 `axis` における局所座標に変換する変換を生成する
+    /// （OCCT の `gp_Trsf::SetTransformation(ax3)` 相当）
+    pub fn to_local(axis: &Axis3) -> Self {
+        let m = axis.basis().transpose();
+        Self::from_orthogonal(&m, -(m * axis.location.to_vector()))
+    }
+
+    /// 座標系 `from` での局所座標を座標系 `to` での局所座標に変換する変換を生成する
+    /// （OCCT の `gp_Trsf::SetTransformation(from, to)` 相当）
+    pub fn coordinate_change(from: &Axis3, to: &Axis3) -> Self {
+        // 局所座標(from) → 全体座標 → 局所座標(to)
+        let m = to.basis().transpose() * from.basis();
+        let t = to.basis().transpose() * (from.location - to.location);
+        Self::from_orthogonal(&m, t)
+    }
+
+    /// 直交行列と平行移動から変換を生成する（行列式が負なら負のスケールで表す）
+    fn from_orthogonal(m: &Matrix3, translation: Vector3) -> Self {
+        if m.determinant() < 0.0 {
+            let rotation = Quaternion::from_matrix3(&(*m * -1.0));
+            Self::new(rotation, translation, -1.0)
+        } else {
+            Self::new(Quaternion::from_matrix3(m), translation, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_axis2_orthonormal() {
+        let n = Dir::new(1.0, 2.0, 3.0).unwrap();
+        let a = Axis2::new(Point3::origin(), n, Dir::x_axis()).unwrap();
+        assert!(a.x_direction().dot(n).abs() < 1e-12);
+        assert!(a.y_direction().dot(n).abs() < 1e-12);
+        assert!(a.x_direction().dot(a.y_direction()).abs() < 1e-12);
+        // X方向の目安が主方向と平行ならエラー
+        assert!(Axis2::new(Point3::origin(), n, n).is_err());
+    }
+
+    #[test]
+    fn test_axis2_from_normal_matches_occt() {
+        // gp_Ax2(P, gp::DZ()) の XDirection は (1, 0, 0)
+        let a = Axis2::from_normal(Point3::origin(), Dir::z_axis());
+        assert_eq!(a.x_direction(), Dir::x_axis());
+        assert_eq!(a.y_direction(), Dir::y_axis());
+        let n = Dir::new(1.0, 1.0, 1.0).unwrap();
+        let b = Axis2::from_normal(Point3::origin(), n);
+        assert!(b.x_direction().dot(n).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_axis3_handedness() {
+        let mut a = Axis3::from(Axis2::world());
+        assert!(a.is_direct());
+        a.y_reverse();
+        assert!(!a.is_direct());
+        let a2 = a.to_axis2();
+        assert_eq!(a2.direction(), Dir::z_axis().reversed());
+        assert_eq!(a2.y_direction(), a.y_direction());
+    }
+
+    #[test]
+    fn test_displacement() {
+        let from = Axis3::from(Axis2::world());
+        let to = Axis3::new(Point3::new(1.0, 2.0, 3.0), Dir::x_axis(), Dir::y_axis()).unwrap();
+        let t = Transform::displacement(&from, &to);
+        // 局所座標 (1, 2, 3) の点は移動先の座標系で同じ局所座標の点になる
+        assert_point_eq(t * from.point_at(1.0, 2.0, 3.0), to.point_at(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_displacement_to_left_handed() {
+        let from = Axis3::from(Axis2::world());
+        let mut to = Axis3::from_normal(Point3::new(0.0, 0.0, 1.0), Dir::z_axis());
+        to.z_reverse();
+        let t = Transform::displacement(&from, &to);
+        assert!(t.scale < 0.0);
+        assert_point_eq(
+            t * from.point_at(0.5, -1.0, 2.0),
+            to.point_at(0.5, -1.0, 2.0),
+        );
+    }
+
+    #[test]
+    fn test_coordinate_change() {
+        let to = Axis3::new(Point3::new(1.0, 0.0, 0.0), Dir::z_axis(), Dir::y_axis()).unwrap();
+        let world = Axis3::from(Axis2::world());
+        let p = to.point_at(2.0, 3.0, 4.0);
+        // 全体座標から局所座標へ
+        assert_point_eq(Transform::to_local(&to) * p, Point3::new(2.0, 3.0, 4.0));
+        assert_point_eq(
+            Transform::coordinate_change(&world, &to) * p,
+            Point3::new(2.0, 3.0, 4.0),
+        );
+    }
+
+    #[test]
+    fn test_transformed_axis() {
+        let a = Axis2::world();
+        let t = Transform::from_translation(Vector3::new(0.0, 0.0, 5.0));
+        let b = a.transformed(&t);
+        assert_point_eq(b.location(), Point3::new(0.0, 0.0, 5.0));
+        assert_eq!(b.direction(), Dir::z_axis());
+        let ax = Axis1::oz().transformed(&t);
+        assert_point_eq(ax.location, Point3::new(0.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn test_rotation_about_axis() {
+        let axis = Axis1::new(Point3::new(1.0, 0.0, 0.0), Dir::z_axis());
+        let t = Transform::rotation(&axis, std::f64::consts::FRAC_PI_2);
+        // 軸上の点は動かない
+        assert_point_eq(t * Point3::new(1.0, 0.0, 5.0), Point3::new(1.0, 0.0, 5.0));
+        assert_point_eq(t * Point3::new(2.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        // ベクトルの回転は rotated_about と一致する
+        let v = Vector3::new(0.5, 2.0, -1.0);
+        let r = t * v;
+        assert!((r - v.rotated_about(Dir::z_axis(), std::f64::consts::FRAC_PI_2)).length() < 1e-12);
+    }
+}
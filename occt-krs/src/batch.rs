@@ -0,0 +1,504 @@
+//! 大量の点・ベクトルをまとめて処理するためのバッチ演算
+//!
+//! 成分ごとに連続した配列（SoA: Structure of Arrays）で保持することで、
+//! テッセレーションや点群処理で数千〜数百万点を一括で変換・演算できるようにする。
+//! `simd` フィーチャーを有効にすると、x86_64 で AVX が利用可能な場合に明示的な SIMD 命令を用いる。
+
+use crate::{Matrix4, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 3次元ベクトル（または点）の列を成分ごとの配列で保持するバッファ
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vec3Buffer {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    zs: Vec<f64>,
+}
+
+impl Vec3Buffer {
+    /// 空のバッファを生成する
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 指定した容量を確保した空のバッファを生成する
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            xs: Vec::with_capacity(capacity),
+            ys: Vec::with_capacity(capacity),
+            zs: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// 成分ごとの配列からバッファを生成する
+    /// 配列の長さが揃っていない場合はエラーを返す
+    pub fn from_components(xs: Vec<f64>, ys: Vec<f64>, zs: Vec<f64>) -> Result<Self> {
+        if xs.len() != ys.len() || xs.len() != zs.len() {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "成分の配列の長さが一致しません: {}, {}, {}",
+                xs.len(),
+                ys.len(),
+                zs.len()
+            )));
+        }
+        Ok(Self { xs, ys, zs })
+    }
+
+    /// ベクトルの列からバッファを生成する
+    pub fn from_vectors(vectors: &[Vector3]) -> Self {
+        let mut b = Self::with_capacity(vectors.len());
+        for v in vectors {
+            b.push(*v);
+        }
+        b
+    }
+
+    /// 点の列からバッファを生成する
+    pub fn from_points(points: &[Point3]) -> Self {
+        let mut b = Self::with_capacity(points.len());
+        for p in points {
+            b.push(p.to_vector());
+        }
+        b
+    }
+
+    /// 要素数を返す
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// 要素が空であれば `true` を返す
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// 末尾にベクトルを追加する
+    pub fn push(&mut self, v: Vector3) {
+        self.xs.push(v.x);
+        self.ys.push(v.y);
+        self.zs.push(v.z);
+    }
+
+    /// i 番目の要素を取り出す
+    pub fn get(&self, i: usize) -> Option<Vector3> {
+        Some(Vector3::new(
+            *self.xs.get(i)?,
+            *self.ys.get(i)?,
+            *self.zs.get(i)?,
+        ))
+    }
+
+    /// x成分の配列を返す
+    pub fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    /// y成分の配列を返す
+    pub fn ys(&self) -> &[f64] {
+        &self.ys
+    }
+
+    /// z成分の配列を返す
+    pub fn zs(&self) -> &[f64] {
+        &self.zs
+    }
+
+    /// ベクトルの列に変換する
+    pub fn to_vectors(&self) -> Vec<Vector3> {
+        (0..self.len())
+            .map(|i| Vector3::new(self.xs[i], self.ys[i], self.zs[i]))
+            .collect()
+    }
+
+    /// 点の列に変換する
+    pub fn to_points(&self) -> Vec<Point3> {
+        (0..self.len())
+            .map(|i| Point3::new(self.xs[i], self.ys[i], self.zs[i]))
+            .collect()
+    }
+
+    /// 要素ごとの和を計算する
+    /// 要素数が異なる場合はエラーを返す
+    pub fn add(&self, other: &Vec3Buffer) -> Result<Vec3Buffer> {
+        self.check_len(other)?;
+        let mut out = Self::zeros(self.len());
+        kernel::add(&self.xs, &other.xs, &mut out.xs);
+        kernel::add(&self.ys, &other.ys, &mut out.ys);
+        kernel::add(&self.zs, &other.zs, &mut out.zs);
+        Ok(out)
+    }
+
+    /// 全要素に同じベクトルを加える（点群の平行移動）
+    pub fn translate(&mut self, v: Vector3) {
+        kernel::affine_in_place(&mut self.xs, 1.0, v.x);
+        kernel::affine_in_place(&mut self.ys, 1.0, v.y);
+        kernel::affine_in_place(&mut self.zs, 1.0, v.z);
+    }
+
+    /// 全要素をスカラー倍する
+    pub fn scale(&mut self, s: f64) {
+        kernel::affine_in_place(&mut self.xs, s, 0.0);
+        kernel::affine_in_place(&mut self.ys, s, 0.0);
+        kernel::affine_in_place(&mut self.zs, s, 0.0);
+    }
+
+    /// 要素ごとの内積を計算する
+    /// 要素数が異なる場合はエラーを返す
+    pub fn dot(&self, other: &Vec3Buffer) -> Result<Vec<f64>> {
+        self.check_len(other)?;
+        let mut out = vec![0.0; self.len()];
+        kernel::dot3(
+            [&self.xs, &self.ys, &self.zs],
+            [&other.xs, &other.ys, &other.zs],
+            &mut out,
+        );
+        Ok(out)
+    }
+
+    /// 要素ごとの外積を計算する
+    /// 要素数が異なる場合はエラーを返す
+    pub fn cross(&self, other: &Vec3Buffer) -> Result<Vec3Buffer> {
+        self.check_len(other)?;
+        let mut out = Self::zeros(self.len());
+        let (a, b) = (self, other);
+        kernel::cross_component(&a.ys, &b.zs, &a.zs, &b.ys, &mut out.xs);
+        kernel::cross_component(&a.zs, &b.xs, &a.xs, &b.zs, &mut out.ys);
+        kernel::cross_component(&a.xs, &b.ys, &a.ys, &b.xs, &mut out.zs);
+        Ok(out)
+    }
+
+    /// 各要素の長さを計算する
+    pub fn lengths(&self) -> Vec<f64> {
+        let mut out = vec![0.0; self.len()];
+        kernel::dot3(
+            [&self.xs, &self.ys, &self.zs],
+            [&self.xs, &self.ys, &self.zs],
+            &mut out,
+        );
+        for v in out.iter_mut() {
+            *v = v.sqrt();
+        }
+        out
+    }
+
+    /// 全要素を点として同次変換行列で変換する（射影成分は無視する）
+    pub fn transform_points(&mut self, m: &Matrix4) {
+        self.apply_affine(m, true);
+    }
+
+    /// 全要素をベクトルとして同次変換行列で変換する（平行移動は影響しない）
+    pub fn transform_vectors(&mut self, m: &Matrix4) {
+        self.apply_affine(m, false);
+    }
+
+    /// 全要素を点として変換する
+    pub fn apply_transform(&mut self, t: &Transform) {
+        self.transform_points(&t.to_matrix4());
+    }
+
+    fn apply_affine(&mut self, m: &Matrix4, with_translation: bool) {
+        let n = self.len();
+        let mut out = [vec![0.0; n], vec![0.0; n], vec![0.0; n]];
+        for (row, o) in out.iter_mut().enumerate() {
+            let r = m.m[row];
+            let c = [r[0], r[1], r[2], if with_translation { r[3] } else { 0.0 }];
+            kernel::lincomb3([&self.xs, &self.ys, &self.zs], c, o);
+        }
+        let [xs, ys, zs] = out;
+        self.xs = xs;
+        self.ys = ys;
+        self.zs = zs;
+    }
+
+    fn zeros(n: usize) -> Self {
+        Self {
+            xs: vec![0.0; n],
+            ys: vec![0.0; n],
+            zs: vec![0.0; n],
+        }
+    }
+
+    fn check_len(&self, other: &Vec3Buffer) -> Result<()> {
+        if self.len() != other.len() {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "バッファの要素数が一致しません: {} と {}",
+                self.len(),
+                other.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<Vector3> for Vec3Buffer {
+    fn from_iter<I: IntoIterator<Item = Vector3>>(iter: I) -> Self {
+        let mut b = Self::new();
+        for v in iter {
+            b.push(v);
+        }
+        b
+    }
+}
+
+/// スライス単位の計算カーネル
+///
+/// 呼び出し側で長さが揃っていることを保証する。
+mod kernel {
+    /// out = a + b
+    pub fn add(a: &[f64], b: &[f64], out: &mut [f64]) {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX が利用可能であることを実行時に確認済み
+            unsafe { avx::add(a, b, out) };
+            return;
+        }
+        for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+            *o = x + y;
+        }
+    }
+
+    /// v = v * s + t
+    pub fn affine_in_place(v: &mut [f64], s: f64, t: f64) {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX が利用可能であることを実行時に確認済み
+            unsafe { avx::affine_in_place(v, s, t) };
+            return;
+        }
+        for x in v.iter_mut() {
+            *x = *x * s + t;
+        }
+    }
+
+    /// out = a0*b0 + a1*b1 + a2*b2
+    pub fn dot3(a: [&[f64]; 3], b: [&[f64]; 3], out: &mut [f64]) {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX が利用可能であることを実行時に確認済み
+            unsafe { avx::dot3(a, b, out) };
+            return;
+        }
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = a[0][i] * b[0][i] + a[1][i] * b[1][i] + a[2][i] * b[2][i];
+        }
+    }
+
+    /// out = a1*b2 - a2*b1（外積の1成分）
+    pub fn cross_component(a1: &[f64], b2: &[f64], a2: &[f64], b1: &[f64], out: &mut [f64]) {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX が利用可能であることを実行時に確認済み
+            unsafe { avx::cross_component(a1, b2, a2, b1, out) };
+            return;
+        }
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = a1[i] * b2[i] - a2[i] * b1[i];
+        }
+    }
+
+    /// out = c0*x + c1*y + c2*z + c3
+    pub fn lincomb3(v: [&[f64]; 3], c: [f64; 4], out: &mut [f64]) {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if std::arch::is_x86_feature_detected!("avx") {
+            // SAFETY: AVX が利用可能であることを実行時に確認済み
+            unsafe { avx::lincomb3(v, c, out) };
+            return;
+        }
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = c[0] * v[0][i] + c[1] * v[1][i] + c[2] * v[2][i] + c[3];
+        }
+    }
+
+    /// AVX（256bit, f64 x 4）による実装
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    mod avx {
+        use std::arch::x86_64::*;
+
+        const LANES: usize = 4;
+
+        #[target_feature(enable = "avx")]
+        pub unsafe fn add(a: &[f64], b: &[f64], out: &mut [f64]) {
+            let n = out.len();
+            let mut i = 0;
+            while i + LANES <= n {
+                let va = _mm256_loadu_pd(a.as_ptr().add(i));
+                let vb = _mm256_loadu_pd(b.as_ptr().add(i));
+                _mm256_storeu_pd(out.as_mut_ptr().add(i), _mm256_add_pd(va, vb));
+                i += LANES;
+            }
+            for j in i..n {
+                out[j] = a[j] + b[j];
+            }
+        }
+
+        #[target_feature(enable = "avx")]
+        pub unsafe fn affine_in_place(v: &mut [f64], s: f64, t: f64) {
+            let n = v.len();
+            let vs = _mm256_set1_pd(s);
+            let vt = _mm256_set1_pd(t);
+            let mut i = 0;
+            while i + LANES <= n {
+                let p = v.as_mut_ptr().add(i);
+                let x = _mm256_loadu_pd(p);
+                _mm256_storeu_pd(p, _mm256_add_pd(_mm256_mul_pd(x, vs), vt));
+                i += LANES;
+            }
+            for x in &mut v[i..] {
+                *x = *x * s + t;
+            }
+        }
+
+        #[target_feature(enable = "avx")]
+        pub unsafe fn dot3(a: [&[f64]; 3], b: [&[f64]; 3], out: &mut [f64]) {
+            let n = out.len();
+            let mut i = 0;
+            while i + LANES <= n {
+                let mut acc = _mm256_setzero_pd();
+                for k in 0..3 {
+                    let va = _mm256_loadu_pd(a[k].as_ptr().add(i));
+                    let vb = _mm256_loadu_pd(b[k].as_ptr().add(i));
+                    acc = _mm256_add_pd(acc, _mm256_mul_pd(va, vb));
+                }
+                _mm256_storeu_pd(out.as_mut_ptr().add(i), acc);
+                i += LANES;
+            }
+            for j in i..n {
+                out[j] = a[0][j] * b[0][j] + a[1][j] * b[1][j] + a[2][j] * b[2][j];
+            }
+        }
+
+        #[target_feature(enable = "avx")]
+        pub unsafe fn cross_component(
+            a1: &[f64],
+            b2: &[f64],
+            a2: &[f64],
+            b1: &[f64],
+            out: &mut [f64],
+        ) {
+            let n = out.len();
+            let mut i = 0;
+            while i + LANES <= n {
+                let l = _mm256_mul_pd(
+                    _mm256_loadu_pd(a1.as_ptr().add(i)),
+                    _mm256_loadu_pd(b2.as_ptr().add(i)),
+                );
+                let r = _mm256_mul_pd(
+                    _mm256_loadu_pd(a2.as_ptr().add(i)),
+                    _mm256_loadu_pd(b1.as_ptr().add(i)),
+                );
+                _mm256_storeu_pd(out.as_mut_ptr().add(i), _mm256_sub_pd(l, r));
+                i += LANES;
+            }
+            for j in i..n {
+                out[j] = a1[j] * b2[j] - a2[j] * b1[j];
+            }
+        }
+
+        #[target_feature(enable = "avx")]
+        pub unsafe fn lincomb3(v: [&[f64]; 3], c: [f64; 4], out: &mut [f64]) {
+            let n = out.len();
+            let vc = [
+                _mm256_set1_pd(c[0]),
+                _mm256_set1_pd(c[1]),
+                _mm256_set1_pd(c[2]),
+            ];
+            let mut i = 0;
+            while i + LANES <= n {
+                let mut acc = _mm256_set1_pd(c[3]);
+                for k in 0..3 {
+                    let x = _mm256_loadu_pd(v[k].as_ptr().add(i));
+                    acc = _mm256_add_pd(acc, _mm256_mul_pd(x, vc[k]));
+                }
+                _mm256_storeu_pd(out.as_mut_ptr().add(i), acc);
+                i += LANES;
+            }
+            for j in i..n {
+                out[j] = c[0] * v[0][j] + c[1] * v[1][j] + c[2] * v[2][j] + c[3];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SIMD の端数処理も確認できるよう、レーン数の倍数でない要素数にする
+    fn sample(n: usize, seed: f64) -> Vec<Vector3> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64 + seed;
+                Vector3::new(t.sin(), (t * 0.7).cos(), t * 0.01 - 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let vs = sample(11, 0.0);
+        let b = Vec3Buffer::from_vectors(&vs);
+        assert_eq!(b.len(), 11);
+        assert_eq!(b.to_vectors(), vs);
+        assert_eq!(b.get(3), Some(vs[3]));
+        assert_eq!(b.get(11), None);
+        let collected: Vec3Buffer = vs.iter().copied().collect();
+        assert_eq!(collected, b);
+    }
+
+    #[test]
+    fn test_add_dot_cross_match_scalar() {
+        let a = sample(1003, 0.0);
+        let b = sample(1003, 0.5);
+        let ba = Vec3Buffer::from_vectors(&a);
+        let bb = Vec3Buffer::from_vectors(&b);
+
+        let sum = ba.add(&bb).unwrap().to_vectors();
+        let dot = ba.dot(&bb).unwrap();
+        let cross = ba.cross(&bb).unwrap().to_vectors();
+        let lengths = ba.lengths();
+        for i in 0..a.len() {
+            assert!((sum[i] - (a[i] + b[i])).length() < 1e-12);
+            assert!((dot[i] - a[i].dot(b[i])).abs() < 1e-12);
+            assert!((cross[i] - a[i].cross(b[i])).length() < 1e-12);
+            assert!((lengths[i] - a[i].length()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_scale_and_translate() {
+        let a = sample(7, 1.0);
+        let mut b = Vec3Buffer::from_vectors(&a);
+        b.scale(2.0);
+        b.translate(Vector3::new(1.0, 0.0, -1.0));
+        for (i, v) in b.to_vectors().into_iter().enumerate() {
+            assert!((v - (a[i] * 2.0 + Vector3::new(1.0, 0.0, -1.0))).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_transform_matches_scalar() {
+        let pts: Vec<Point3> = sample(1001, 2.0).into_iter().map(Point3::from).collect();
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+            * Matrix4::from_axis_angle(Vector3::new(1.0, 1.0, 0.0), 0.3);
+        let mut b = Vec3Buffer::from_points(&pts);
+        b.transform_points(&m);
+        for (p, q) in pts.iter().zip(b.to_points()) {
+            assert!(m.transform_point(*p).distance(q) < 1e-12);
+        }
+
+        let mut v = Vec3Buffer::from_points(&pts);
+        v.transform_vectors(&m);
+        for (p, q) in pts.iter().zip(v.to_vectors()) {
+            assert!((m.transform_vector(p.to_vector()) - q).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let a = Vec3Buffer::from_vectors(&sample(3, 0.0));
+        let b = Vec3Buffer::from_vectors(&sample(4, 0.0));
+        assert!(a.add(&b).is_err());
+        assert!(a.dot(&b).is_err());
+        assert!(a.cross(&b).is_err());
+        assert!(Vec3Buffer::from_components(vec![1.0], vec![], vec![]).is_err());
+    }
+}
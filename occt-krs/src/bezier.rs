@@ -0,0 +1,277 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::curve::{check_trim_range, split_ranges};
+use crate::{BSplineCurve, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 任意次数のベジェ曲線（OCCT の `Geom_BezierCurve` 相当、非有理のみ）
+///
+/// パラメータ範囲は `[0, 1]` で、次数は制御点の数 - 1 になる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BezierCurve {
+    control_points: Vec<Point3>,
+}
+
+impl BezierCurve {
+    /// 制御点から曲線を生成する
+    /// 制御点が空の場合はエラーを返す
+    pub fn new(control_points: Vec<Point3>) -> Result<Self> {
+        if control_points.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "ベジェ曲線には1つ以上の制御点が必要です".to_string(),
+            ));
+        }
+        Ok(Self { control_points })
+    }
+
+    /// 制御点を返す
+    pub fn control_points(&self) -> &[Point3] {
+        &self.control_points
+    }
+
+    /// 次数を返す
+    pub fn degree(&self) -> usize {
+        self.control_points.len() - 1
+    }
+
+    /// 始点を返す
+    pub fn start_point(&self) -> Point3 {
+        self.control_points[0]
+    }
+
+    /// 終点を返す
+    pub fn end_point(&self) -> Point3 {
+        self.control_points[self.degree()]
+    }
+
+    /// パラメータ `t` における点を de Casteljau のアルゴリズムで計算する
+    pub fn point_at(&self, t: f64) -> Point3 {
+        let pts: Vec<Vector3> = self.control_points.iter().map(|p| p.to_vector()).collect();
+        Point3::from(de_casteljau(&pts, t))
+    }
+
+    /// パラメータ `t` における1階微分を返す
+    pub fn derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 1)
+    }
+
+    /// パラメータ `t` における `order` 階微分を返す
+    /// 次数を超える階数の微分はゼロベクトルになる
+    pub fn nth_derivative_at(&self, t: f64, order: usize) -> Vector3 {
+        let n = self.degree();
+        if order > n {
+            return Vector3::ZERO;
+        }
+        // 差分を order 回とった制御点（ホドグラフ）を評価する
+        let mut d: Vec<Vector3> = self.control_points.iter().map(|p| p.to_vector()).collect();
+        let mut factor = 1.0;
+        for k in 0..order {
+            factor *= (n - k) as f64;
+            d = d.windows(2).map(|w| w[1] - w[0]).collect();
+        }
+        de_casteljau(&d, t) * factor
+    }
+
+    /// パラメータ `t` で2つのベジェ曲線に分割する
+    ///
+    /// 返り値の曲線はそれぞれ元の `[0, t]` と `[t, 1]` の部分を `[0, 1]` に再パラメータ化したもの。
+    pub fn subdivide(&self, t: f64) -> (BezierCurve, BezierCurve) {
+        let n = self.degree();
+        let mut work: Vec<Vector3> = self.control_points.iter().map(|p| p.to_vector()).collect();
+        let mut left = Vec::with_capacity(n + 1);
+        let mut right = Vec::with_capacity(n + 1);
+        left.push(Point3::from(work[0]));
+        right.push(Point3::from(work[n]));
+        for k in 1..=n {
+            for i in 0..=(n - k) {
+                work[i] = work[i] * (1.0 - t) + work[i + 1] * t;
+            }
+            left.push(Point3::from(work[0]));
+            right.push(Point3::from(work[n - k]));
+        }
+        right.reverse();
+        (
+            Self {
+                control_points: left,
+            },
+            Self {
+                control_points: right,
+            },
+        )
+    }
+
+    /// パラメータ範囲 `[u1, u2]` を切り出す（結果のパラメータは再び `[0, 1]`）
+    pub fn trim(&self, u1: f64, u2: f64) -> Result<BezierCurve> {
+        check_trim_range(0.0, 1.0, u1, u2)?;
+        let (left, _) = self.subdivide(u2);
+        let (_, mid) = left.subdivide(u1 / u2);
+        Ok(mid)
+    }
+
+    /// 指定したパラメータで分割する
+    pub fn split_at(&self, params: &[f64]) -> Result<Vec<BezierCurve>> {
+        split_ranges(0.0, 1.0, params)?
+            .into_iter()
+            .map(|(a, b)| self.trim(a, b))
+            .collect()
+    }
+
+    /// 形状を変えずに次数を1つ上げた曲線を返す
+    pub fn elevate_degree(&self) -> BezierCurve {
+        let n = self.degree();
+        let p = &self.control_points;
+        let mut q = Vec::with_capacity(n + 2);
+        q.push(p[0]);
+        for i in 1..=n {
+            let a = i as f64 / (n + 1) as f64;
+            q.push(Point3::from(
+                p[i - 1].to_vector() * a + p[i].to_vector() * (1.0 - a),
+            ));
+        }
+        q.push(p[n]);
+        Self { control_points: q }
+    }
+
+    /// 向きを反転した曲線を返す
+    pub fn reversed(&self) -> BezierCurve {
+        let mut control_points = self.control_points.clone();
+        control_points.reverse();
+        Self { control_points }
+    }
+
+    /// 変換を適用した曲線を返す
+    pub fn transformed(&self, t: &Transform) -> BezierCurve {
+        Self {
+            control_points: self
+                .control_points
+                .iter()
+                .map(|p| t.transform_point(*p))
+                .collect(),
+        }
+    }
+
+    /// 同じ次数・制御点の B-スプライン曲線に変換する（パラメータ範囲は `[0, 1]`）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        BSplineCurve::clamped_uniform(self.degree(), self.control_points.clone()).unwrap()
+    }
+}
+
+/// de Casteljau のアルゴリズムで多項式を評価する
+fn de_casteljau(points: &[Vector3], t: f64) -> Vector3 {
+    let mut work = points.to_vec();
+    let n = work.len();
+    for k in 1..n {
+        for i in 0..(n - k) {
+            work[i] = work[i] * (1.0 - t) + work[i + 1] * t;
+        }
+    }
+    work[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn sample() -> BezierCurve {
+        BezierCurve::new(vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(3.0, 2.0, 1.0),
+            Point3::new(4.0, 0.0, -1.0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_evaluation() {
+        let c = sample();
+        assert_eq!(c.degree(), 3);
+        assert_point_eq(c.point_at(0.0), c.start_point());
+        assert_point_eq(c.point_at(1.0), c.end_point());
+        // ベルンシュタイン基底での直接計算と比較する
+        let t: f64 = 0.3;
+        let b = [
+            (1.0 - t).powi(3),
+            3.0 * t * (1.0 - t).powi(2),
+            3.0 * t * t * (1.0 - t),
+            t.powi(3),
+        ];
+        let expected = c
+            .control_points()
+            .iter()
+            .zip(b)
+            .fold(Vector3::ZERO, |acc, (p, w)| acc + p.to_vector() * w);
+        assert_point_eq(c.point_at(t), Point3::from(expected));
+        assert!(BezierCurve::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let c = sample();
+        let h = 1e-6;
+        for t in [0.0, 0.25, 0.8] {
+            let numeric = (c.point_at(t + h) - c.point_at(t - h)) * (0.5 / h);
+            assert!((numeric - c.derivative_at(t)).length() < 1e-6);
+            let numeric2 = (c.derivative_at(t + h) - c.derivative_at(t - h)) * (0.5 / h);
+            assert!((numeric2 - c.nth_derivative_at(t, 2)).length() < 1e-6);
+        }
+        // 端点の接線は制御多角形の辺の n 倍
+        let p = c.control_points();
+        assert!((c.derivative_at(0.0) - (p[1] - p[0]) * 3.0).length() < 1e-12);
+        assert_eq!(c.nth_derivative_at(0.5, 4), Vector3::ZERO);
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let c = sample();
+        let (l, r) = c.subdivide(0.4);
+        assert_point_eq(l.end_point(), c.point_at(0.4));
+        assert_point_eq(r.start_point(), c.point_at(0.4));
+        for s in [0.0, 0.3, 0.7, 1.0] {
+            assert_point_eq(l.point_at(s), c.point_at(0.4 * s));
+            assert_point_eq(r.point_at(s), c.point_at(0.4 + 0.6 * s));
+        }
+    }
+
+    #[test]
+    fn test_elevate_degree() {
+        let c = sample();
+        let e = c.elevate_degree().elevate_degree();
+        assert_eq!(e.degree(), 5);
+        for t in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert_point_eq(e.point_at(t), c.point_at(t));
+        }
+        assert_point_eq(c.reversed().point_at(0.2), c.point_at(0.8));
+    }
+
+    #[test]
+    fn test_trim_and_split() {
+        let c = sample();
+        let t = c.trim(0.2, 0.7).unwrap();
+        for s in [0.0, 0.4, 1.0] {
+            assert_point_eq(t.point_at(s), c.point_at(0.2 + 0.5 * s));
+        }
+        let parts = c.split_at(&[0.3, 0.6]).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_point_eq(parts[1].start_point(), c.point_at(0.3));
+        assert_point_eq(parts[2].end_point(), c.end_point());
+        assert!(c.trim(0.5, 0.5).is_err());
+        assert!(c.split_at(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_to_nurbs() {
+        let b = sample();
+        let c = b.to_nurbs();
+        assert_eq!(c.degree(), 3);
+        assert_eq!(c.control_points(), b.control_points());
+        for t in [0.0, 0.3, 0.8, 1.0] {
+            assert_point_eq(c.point_at(t), b.point_at(t));
+        }
+    }
+}
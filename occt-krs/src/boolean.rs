@@ -0,0 +1,1200 @@
+//! 立体のブーリアン演算（OCCT の `BRepAlgoAPI_Fuse` など相当）
+//!
+//! 各引数の境界のフェイスを他の引数のフェイスとの交線で分割し、分割したフェイスが他の立体の
+//! 内側・外側・境界上のどこにあるかで残すフェイスを選んで、新しい立体を組み立てる。
+//! 平面と二次曲面（円柱・円錐・球・トーラス）のフェイスを主な対象とし、それ以外の曲面は
+//! 交線の追跡で求めた折れ線で分割する。
+
+use std::collections::HashMap;
+
+use crate::classify::{classify_point, signed_distance, FaceClassifier};
+use crate::fix::signed_volume;
+use crate::general_fuse::{Fragment, GeneralFuse, State};
+use crate::primitives::{make_cylinder, make_sphere, make_torus};
+use crate::sew::{coincidence, sew};
+use crate::shape_builder::union_find_root;
+use crate::topo::ShapeId;
+use crate::{
+    precision, Axis2, Axis3, BoundingBox, Compound, Edge, Face, GeomSurface, Line, OcctKrsError,
+    Orientation, Plane, Point3, PointClassification, Result, Shape, Shell, Solid, Surface, Vector2,
+    Vertex, Wire,
+};
+
+/// 2つの立体の和を返す（OCCT の `BRepAlgoAPI_Fuse` 相当）
+///
+/// 引数に含まれる立体（`Solid`、または立体を含む `Compound`）を対象とする。結果が1つの立体なら
+/// `Shape::Solid`、離れた複数の立体なら立体の `Shape::Compound` を返す。
+/// 引数に立体がない場合や、フェイスを分割できない場合、結果のシェルが閉じない場合はエラーを返す。
+pub fn fuse(a: &Shape, b: &Shape) -> Result<Shape> {
+    let general = GeneralFuse::new(&[solid_faces(a)?, solid_faces(b)?])?;
+    let faces = general
+        .fragments
+        .into_iter()
+        .filter(outside_others)
+        .map(|f| f.face)
+        .collect();
+    to_shape(assemble(faces)?)
+}
+
+/// 立体 `a` から立体 `b` を取り除いた差を返す（OCCT の `BRepAlgoAPI_Cut` 相当）
+///
+/// 結果の形は [`fuse`] と同じ。何も残らない場合はエラーを返す。
+pub fn cut(a: &Shape, b: &Shape) -> Result<Shape> {
+    cut_all(a, std::slice::from_ref(b))
+}
+
+/// 立体 `object` から複数の立体 `tools` をまとめて取り除いた差を返す
+///
+/// 道具ごとに [`cut`] を繰り返すより、フェイスの分割と分類が1回で済む。道具どうしが重なっていてもよい。
+/// 道具がない場合や、何も残らない場合はエラーを返す。
+pub fn cut_all(object: &Shape, tools: &[Shape]) -> Result<Shape> {
+    if tools.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "取り除く立体がありません".to_string(),
+        ));
+    }
+    let mut arguments = vec![solid_faces(object)?];
+    for tool in tools {
+        arguments.push(solid_faces(tool)?);
+    }
+    let general = GeneralFuse::new(&arguments)?;
+    let faces = general
+        .fragments
+        .into_iter()
+        .filter_map(|f| {
+            if f.argument == 0 {
+                // 道具のフェイスと逆向きに重なる部分は、道具に接する境界として残る
+                let keep = f.states[1..]
+                    .iter()
+                    .all(|&s| matches!(s, State::Out | State::On { same: false }));
+                keep.then_some(f.face)
+            } else {
+                // 道具の境界のうち対象の内側にある部分が、向きを反転して穴の壁になる
+                let keep = f.states[0] == State::In
+                    && f.states[1..]
+                        .iter()
+                        .enumerate()
+                        .all(|(k, &s)| keep_outside(s, k + 1, f.argument));
+                keep.then(|| f.face.reversed())
+            }
+        })
+        .collect();
+    to_shape(assemble(faces)?)
+}
+
+/// 同じフェイスで接する2つの立体を、交線を求めずにつないだ和を返す（OCCT の `BRepAlgoAPI_Fuse` で `BOPAlgo_GlueFull` を指定した場合に相当）
+///
+/// 境界のエッジが一致し、曲面が重なって外向きの法線が逆になるフェイスの組を取り除き、
+/// 残ったフェイスを共有するエッジで縫い合わせる。ブロックを積み上げたモデルなど、接する
+/// フェイスが同じ形だとわかっている場合に [`fuse`] より速い。結果は [`fuse`] と同じ体積を囲むが、
+/// 同じ曲面上に並んだ両側のフェイスは1枚にまとめず別々に残す。結果が1つの立体なら `Shape::Solid`、
+/// 離れた複数の立体なら立体の `Shape::Compound` を返す。
+///
+/// 引数に立体がない場合、空洞のある立体がある場合、同じフェイスの組がない場合、
+/// 接するフェイスの形が違うなどで縫い合わせた結果が閉じない場合はエラーを返す。
+pub fn glue(a: &Shape, b: &Shape) -> Result<Shape> {
+    let tolerance = precision::confusion();
+    for shape in [a, b] {
+        if shape
+            .explore(crate::ShapeKind::Solid)
+            .unique()
+            .any(|s| Solid::try_from(s).is_ok_and(|s| s.shells().len() > 1))
+        {
+            return Err(OcctKrsError::InvalidInput(
+                "空洞のある立体はつなげません".to_string(),
+            ));
+        }
+    }
+    let (faces_a, faces_b) = (solid_faces(a)?, solid_faces(b)?);
+    let classifiers_b = faces_b
+        .iter()
+        .map(FaceClassifier::new)
+        .collect::<Result<Vec<_>>>()?;
+    let mut glued_a = vec![false; faces_a.len()];
+    let mut glued_b = vec![false; faces_b.len()];
+    for (i, face) in faces_a.iter().enumerate() {
+        let classifier = FaceClassifier::new(face)?;
+        let samples = interior_samples(&classifier);
+        if samples.is_empty() {
+            continue;
+        }
+        let outward = |c: &FaceClassifier, f: &Face, p: Point3| {
+            c.normal_at(p).map(|n| {
+                if f.orientation() == Orientation::Reversed {
+                    n.reversed()
+                } else {
+                    n
+                }
+            })
+        };
+        let matched = (0..faces_b.len()).find(|&j| {
+            !glued_b[j]
+                && classifier
+                    .bounding_box
+                    .intersects(&classifiers_b[j].bounding_box)
+                && same_boundary(face, &faces_b[j], tolerance)
+                && samples.iter().all(|&q| {
+                    classifiers_b[j].touches(q, tolerance)
+                        && outward(&classifier, face, q)
+                            .zip(outward(&classifiers_b[j], &faces_b[j], q))
+                            .is_some_and(|(m, n)| m.dot(n) < 0.0)
+                })
+        });
+        if let Some(j) = matched {
+            glued_a[i] = true;
+            glued_b[j] = true;
+        }
+    }
+    if !glued_a.contains(&true) {
+        return Err(OcctKrsError::InvalidInput(
+            "つなげる立体に同じ形で接するフェイスがありません".to_string(),
+        ));
+    }
+    let faces: Vec<Face> = faces_a
+        .into_iter()
+        .zip(glued_a)
+        .chain(faces_b.into_iter().zip(glued_b))
+        .filter(|(_, glued)| !glued)
+        .map(|(f, _)| f)
+        .collect();
+    let sewing = sew(faces, tolerance)?;
+    if !sewing.is_closed() {
+        return Err(OcctKrsError::InvalidInput(
+            "接するフェイスの形が一致せず、つないだ境界が閉じません".to_string(),
+        ));
+    }
+    to_shape(sewing.solids()?)
+}
+
+/// フェイスの境界のエッジが、向きを問わず1本ずつ `tolerance` 以内で重なれば `true` を返す
+fn same_boundary(a: &Face, b: &Face, tolerance: f64) -> bool {
+    let edges = |f: &Face| -> Vec<Edge> {
+        f.wires()
+            .iter()
+            .flat_map(Wire::edges)
+            .filter(|e| !e.is_degenerate())
+            .collect()
+    };
+    let (edges_a, mut edges_b) = (edges(a), edges(b));
+    if edges_a.len() != edges_b.len() {
+        return false;
+    }
+    for e in &edges_a {
+        let Some(k) = edges_b.iter().position(|f| {
+            f.start_vertex()
+                .point()
+                .distance(e.start_vertex().point())
+                .min(f.start_vertex().point().distance(e.end_vertex().point()))
+                <= tolerance
+                && coincidence(e, f, tolerance).is_some()
+        }) else {
+            return false;
+        };
+        edges_b.swap_remove(k);
+    }
+    true
+}
+
+/// フェイスのパラメータ範囲を格子状に分けた点のうち、境界の内側にあるものを曲面上の点で返す
+fn interior_samples(classifier: &FaceClassifier) -> Vec<Point3> {
+    let (lo, hi) = classifier.uv_box();
+    let n = 8;
+    (0..n)
+        .flat_map(|i| (0..n).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            Vector2::new(
+                lo.x + (hi.x - lo.x) * (i as f64 + 0.5) / n as f64,
+                lo.y + (hi.y - lo.y) * (j as f64 + 0.5) / n as f64,
+            )
+        })
+        .filter(|&uv| [uv.x, uv.y].iter().all(|x| x.is_finite()) && classifier.contains_uv(uv))
+        .map(|uv| classifier.surface.point_at(uv.x, uv.y))
+        .collect()
+}
+
+/// 2つの立体の位置関係
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Contact {
+    /// 離れている
+    Disjoint,
+    /// 境界だけが接している（面・辺・点で接し、共通部分の体積がない）
+    Touching,
+    /// 内部が重なっている
+    Overlapping,
+}
+
+/// 共通部分の計算結果
+#[derive(Debug, Clone)]
+pub struct CommonReport {
+    /// 共通部分（重ならない場合は空の `Shape::Compound`）
+    pub shape: Shape,
+    /// 引数どうしの位置関係
+    pub contact: Contact,
+}
+
+/// 2つの立体の共通部分を返す（OCCT の `BRepAlgoAPI_Common` 相当）
+///
+/// 結果の形は [`fuse`] と同じ。重ならない場合はエラーを返す（空の結果を受け取るには [`common_report`] を使う）。
+pub fn common(a: &Shape, b: &Shape) -> Result<Shape> {
+    let report = common_report(a, b)?;
+    if report.contact != Contact::Overlapping {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "立体が重なっていません（{:?}）",
+            report.contact
+        )));
+    }
+    Ok(report.shape)
+}
+
+/// 2つの立体の共通部分と、引数どうしが離れている・接している・重なっているかを返す
+///
+/// 重ならない場合もエラーにせず、空の `Shape::Compound` を返す。
+pub fn common_report(a: &Shape, b: &Shape) -> Result<CommonReport> {
+    let general = GeneralFuse::new(&[solid_faces(a)?, solid_faces(b)?])?;
+    let on_boundary = general
+        .fragments
+        .iter()
+        .any(|f| f.states.iter().any(|s| matches!(s, State::On { .. })));
+    let faces: Vec<Face> = general
+        .fragments
+        .into_iter()
+        .filter(|f| {
+            f.states.iter().enumerate().all(|(k, &state)| match state {
+                State::In => true,
+                State::On { same: true } => k > f.argument,
+                State::Out => k == f.argument,
+                State::On { same: false } => false,
+            })
+        })
+        .map(|f| f.face)
+        .collect();
+    if faces.is_empty() {
+        let contact = if on_boundary || general.touching {
+            Contact::Touching
+        } else {
+            Contact::Disjoint
+        };
+        return Ok(CommonReport {
+            shape: Shape::from(Compound::new(Vec::new())),
+            contact,
+        });
+    }
+    Ok(CommonReport {
+        shape: to_shape(assemble(faces)?)?,
+        contact: Contact::Overlapping,
+    })
+}
+
+/// 2つの形状の交線をエッジの `Shape::Compound` で返す（OCCT の `BRepAlgoAPI_Section` 相当）
+///
+/// 立体に限らず、引数に含まれるすべてのフェイスを対象とする。フェイスの分割や分類を行わないので
+/// [`common`] などより軽い。交わらない場合は空の `Shape::Compound` を返し、フェイスがない場合はエラーを返す。
+pub fn section(a: &Shape, b: &Shape) -> Result<Shape> {
+    let edges = GeneralFuse::sections(&[shape_faces(a)?, shape_faces(b)?])?;
+    Ok(edge_compound(edges))
+}
+
+/// 形状と平面の交線（断面の輪郭）をエッジの `Shape::Compound` で返す
+///
+/// 平面は形状を覆う大きさの長方形のフェイスに置き換えて [`section`] と同じように求める。
+pub fn section_with_plane(shape: &Shape, plane: &Plane) -> Result<Shape> {
+    let faces = shape_faces(shape)?;
+    let rectangle = plane_face(plane, &face_bounds(&faces)?)?;
+    let edges = GeneralFuse::sections(&[faces, vec![rectangle]])?;
+    Ok(edge_compound(edges))
+}
+
+/// 曲面で区切られた空間の片側（OCCT の `BRepPrimAPI_MakeHalfSpace` 相当）
+///
+/// 平面の片側、または円柱面・球面・トーラス面の内側か外側を表す。有界な立体は持たず、
+/// [`cut_halfspace`] と [`common_halfspace`] で相手の立体を覆う大きさの立体に置き換えて使う。
+#[derive(Debug, Clone)]
+pub struct HalfSpace {
+    surface: GeomSurface,
+    /// 平面では法線の向く側、閉じた曲面では内側を表す場合に `true`
+    inside: bool,
+}
+
+/// 曲面 `surface` で区切られた空間のうち、点 `reference_point` を含む側を返す
+///
+/// 平面・円柱面・球面・トーラス面を受け付ける。それ以外の曲面の場合や、
+/// 点が曲面上にある場合はエラーを返す。
+pub fn make_halfspace(
+    surface: impl Into<GeomSurface>,
+    reference_point: Point3,
+) -> Result<HalfSpace> {
+    let surface = surface.into();
+    let d = signed_distance(&surface, reference_point);
+    let inside = match &surface {
+        GeomSurface::Plane(_) => d > 0.0,
+        // 位置の座標系の向きによらず、軸や中心からの距離で内外を決める
+        GeomSurface::Cylinder(_) | GeomSurface::Sphere(_) | GeomSurface::Torus(_) => d < 0.0,
+        other => {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "半空間を作れない曲面です: {:?}",
+                other
+            )))
+        }
+    };
+    if d.is_nan() || d.abs() <= precision::confusion() {
+        return Err(OcctKrsError::InvalidInput(
+            "半空間の基準点が曲面上にあります".to_string(),
+        ));
+    }
+    Ok(HalfSpace { surface, inside })
+}
+
+impl HalfSpace {
+    /// 境界の曲面を返す
+    pub fn surface(&self) -> &GeomSurface {
+        &self.surface
+    }
+
+    /// 点が半空間の内部にあれば `true` を返す
+    pub fn contains(&self, p: Point3) -> bool {
+        let d = signed_distance(&self.surface, p);
+        match self.surface {
+            GeomSurface::Plane(_) => (d > 0.0) == self.inside,
+            _ => (d < 0.0) == self.inside,
+        }
+    }
+
+    /// 範囲 `bounds` を覆う大きさの閉じた曲面の立体を返す（平面の場合は `None`）
+    fn closed_solid(&self, bounds: &BoundingBox) -> Result<Option<Solid>> {
+        let margin = 0.1 * bounds.size().length() + 1.0;
+        let axis2 =
+            |p: &Axis3, location: Point3| Axis2::new(location, p.direction(), p.x_direction());
+        match &self.surface {
+            GeomSurface::Cylinder(s) => {
+                let p = s.position();
+                let d = p.direction().to_vector();
+                // 範囲を囲む球を軸へ投影した区間の両側に余裕を取る
+                let z = (bounds.center() - p.location()).dot(d);
+                let half = 0.5 * bounds.size().length() + margin;
+                let position = axis2(&p, p.location() + d * (z - half))?;
+                make_cylinder(position, s.radius(), 2.0 * half).map(Some)
+            }
+            GeomSurface::Sphere(s) => {
+                let position = axis2(&s.position(), s.center())?;
+                make_sphere(position, s.radius()).map(Some)
+            }
+            GeomSurface::Torus(s) => {
+                let p = s.position();
+                make_torus(axis2(&p, p.location())?, s.major_radius(), s.minor_radius()).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// 立体から半空間を取り除いた差を返す
+///
+/// 平面の半空間は [`split_by_planes`] で分割して反対側の部分を残し、閉じた曲面の半空間は
+/// 立体を覆う大きさの立体に置き換えて [`cut`] か [`common`] で求める。結果の形は [`fuse`] と同じ。
+/// 何も残らない場合はエラーを返す。
+pub fn cut_halfspace(shape: &Shape, halfspace: &HalfSpace) -> Result<Shape> {
+    halfspace_boolean(shape, halfspace, false)
+}
+
+/// 立体と半空間の共通部分を返す（求め方と結果の形は [`cut_halfspace`] と同じ）
+pub fn common_halfspace(shape: &Shape, halfspace: &HalfSpace) -> Result<Shape> {
+    halfspace_boolean(shape, halfspace, true)
+}
+
+/// 立体のうち、半空間の内側（`keep_inside` が `false` なら外側）にある部分を返す
+fn halfspace_boolean(shape: &Shape, halfspace: &HalfSpace, keep_inside: bool) -> Result<Shape> {
+    let bounds = face_bounds(&solid_faces(shape)?)?;
+    match (halfspace.closed_solid(&bounds)?, &halfspace.surface) {
+        (Some(tool), _) => {
+            let tool = Shape::from(tool);
+            if keep_inside == halfspace.inside {
+                common(shape, &tool)
+            } else {
+                cut(shape, &tool)
+            }
+        }
+        (None, GeomSurface::Plane(plane)) => {
+            let side = if keep_inside == halfspace.inside {
+                Side::Positive
+            } else {
+                Side::Negative
+            };
+            let solids = split_by_planes(shape, std::slice::from_ref(plane))?
+                .into_iter()
+                .filter(|piece| piece.sides[0] == side)
+                .map(|piece| piece.solid)
+                .collect();
+            to_shape(solids)
+        }
+        (None, other) => Err(OcctKrsError::InvalidInput(format!(
+            "半空間を作れない曲面です: {:?}",
+            other
+        ))),
+    }
+}
+
+/// 分割した立体が道具のフェイスのどちら側にあるか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// フェイスの法線の向く側
+    Positive,
+    /// フェイスの法線と反対の側
+    Negative,
+}
+
+/// 分割した立体の1つ
+#[derive(Debug, Clone)]
+pub struct SplitPiece {
+    /// 分割した立体
+    pub solid: Solid,
+    /// 道具ごとに、立体がどちら側にあるか（道具と同じ順）
+    pub sides: Vec<Side>,
+}
+
+/// 立体を道具のフェイスで分割する（OCCT の `BRepAlgoAPI_Splitter` 相当）
+///
+/// 道具を順に適用し、道具のフェイス（立体を貫くシートや、閉じたシェルの境界）で立体を切り分けて、
+/// 各部分と、道具ごとにどちら側にあるかを返す。道具に触れない部分もそのまま含める。
+/// 道具やフェイスがない場合や、道具が立体を貫かず切り分けられない場合はエラーを返す。
+pub fn split(shape: &Shape, tools: &[Shape]) -> Result<Vec<SplitPiece>> {
+    let tools = tools.iter().map(shape_faces).collect::<Result<Vec<_>>>()?;
+    split_by_faces(shape, tools)
+}
+
+/// 立体を平面で分割する
+///
+/// 平面は立体を覆う大きさの長方形のフェイスに置き換えて [`split`] と同じように分割する。
+pub fn split_by_planes(shape: &Shape, planes: &[Plane]) -> Result<Vec<SplitPiece>> {
+    let bounds = face_bounds(&solid_faces(shape)?)?;
+    let tools = planes
+        .iter()
+        .map(|plane| Ok(vec![plane_face(plane, &bounds)?]))
+        .collect::<Result<Vec<_>>>()?;
+    split_by_faces(shape, tools)
+}
+
+fn split_by_faces(shape: &Shape, tools: Vec<Vec<Face>>) -> Result<Vec<SplitPiece>> {
+    if tools.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "分割する道具がありません".to_string(),
+        ));
+    }
+    solid_faces(shape)?;
+    let mut pieces: Vec<SplitPiece> = Vec::new();
+    for solid in shape.explore(crate::ShapeKind::Solid).unique() {
+        if let Shape::Solid(solid) = solid {
+            pieces.push(SplitPiece {
+                solid,
+                sides: Vec::new(),
+            });
+        }
+    }
+    for faces in tools {
+        let classifiers = faces
+            .iter()
+            .map(FaceClassifier::new)
+            .collect::<Result<Vec<_>>>()?;
+        let mut next = Vec::new();
+        for piece in pieces {
+            let general =
+                GeneralFuse::new(&[Shape::from(piece.solid.clone()).faces(), faces.clone()])?;
+            let mut groups: [Vec<Face>; 2] = [Vec::new(), Vec::new()];
+            for fragment in general.fragments {
+                if fragment.argument == 1 {
+                    // 立体の内側の道具のフェイスは、両側の部分の境界になる
+                    if fragment.states[0] == State::In {
+                        groups[0].push(fragment.face.reversed());
+                        groups[1].push(fragment.face);
+                    }
+                    continue;
+                }
+                let side = match fragment.states[1] {
+                    State::On { same } => {
+                        if same {
+                            Side::Negative
+                        } else {
+                            Side::Positive
+                        }
+                    }
+                    _ => side_of(&faces, &classifiers, fragment.point),
+                };
+                groups[usize::from(side == Side::Negative)].push(fragment.face);
+            }
+            for (side, group) in [Side::Positive, Side::Negative].into_iter().zip(groups) {
+                if group.is_empty() {
+                    continue;
+                }
+                for solid in assemble(group)? {
+                    if !solid.shells().iter().all(Shell::is_closed) {
+                        return Err(OcctKrsError::DegenerateGeometry(
+                            "道具が立体を貫いていないため分割できません".to_string(),
+                        ));
+                    }
+                    let mut sides = piece.sides.clone();
+                    sides.push(side);
+                    next.push(SplitPiece { solid, sides });
+                }
+            }
+        }
+        pieces = next;
+    }
+    Ok(pieces)
+}
+
+/// 点が道具のフェイスのどちら側にあるかを返す
+///
+/// 点を投影した位置がフェイスの内側になるもののうち、最も近いフェイスの法線で判定する。
+fn side_of(faces: &[Face], classifiers: &[FaceClassifier], p: Point3) -> Side {
+    let mut best: Option<(bool, f64, f64)> = None;
+    for (face, classifier) in faces.iter().zip(classifiers) {
+        let mut d = signed_distance(&classifier.surface, p);
+        if face.orientation() == Orientation::Reversed {
+            d = -d;
+        }
+        let inside = classifier.contains_uv(classifier.uv_of(p));
+        let better = best.is_none_or(|(i, a, _)| (inside, -d.abs()) > (i, -a));
+        if better {
+            best = Some((inside, d.abs(), d));
+        }
+    }
+    match best {
+        Some((_, _, d)) if d < 0.0 => Side::Negative,
+        _ => Side::Positive,
+    }
+}
+
+/// フェイスの境界ボックスを合わせた範囲を返す
+fn face_bounds(faces: &[Face]) -> Result<BoundingBox> {
+    let mut bounds: Option<BoundingBox> = None;
+    for face in faces {
+        let b = FaceClassifier::new(face)?.bounding_box;
+        bounds = Some(bounds.map_or(b, |a| a.union(&b)));
+    }
+    bounds.ok_or_else(|| OcctKrsError::InvalidInput("フェイスがありません".to_string()))
+}
+
+/// 範囲 `bounds` を覆う大きさの、平面上の長方形のフェイスを返す
+fn plane_face(plane: &Plane, bounds: &BoundingBox) -> Result<Face> {
+    let margin = 0.1 * bounds.size().length() + 1.0;
+    let (mut u0, mut v0) = (f64::INFINITY, f64::INFINITY);
+    let (mut u1, mut v1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for i in 0..8 {
+        let pick = |bit: usize, lo: f64, hi: f64| if i & bit == 0 { lo } else { hi };
+        let corner = Point3::new(
+            pick(1, bounds.min.x, bounds.max.x),
+            pick(2, bounds.min.y, bounds.max.y),
+            pick(4, bounds.min.z, bounds.max.z),
+        );
+        let (u, v) = plane.parameters_of(corner);
+        (u0, v0, u1, v1) = (u0.min(u), v0.min(v), u1.max(u), v1.max(v));
+    }
+    let corners = [
+        plane.point_at(u0 - margin, v0 - margin),
+        plane.point_at(u1 + margin, v0 - margin),
+        plane.point_at(u1 + margin, v1 + margin),
+        plane.point_at(u0 - margin, v1 + margin),
+    ];
+    let vertices: Vec<Vertex> = corners.iter().map(|&p| Vertex::new(p)).collect();
+    let mut edges = Vec::with_capacity(4);
+    for k in 0..4 {
+        let (a, b) = (corners[k], corners[(k + 1) % 4]);
+        edges.push(Edge::new(
+            Line::from_points(a, b)?,
+            (0.0, a.distance(b)),
+            vertices[k].clone(),
+            vertices[(k + 1) % 4].clone(),
+        )?);
+    }
+    Face::new(*plane, vec![Wire::new(edges)?])
+}
+
+/// 他のすべての引数の外側にあるフェイスなら `true` を返す
+///
+/// 同じ向きで重なるフェイスは、番号の小さい引数のものだけを残す。
+fn outside_others(fragment: &Fragment) -> bool {
+    fragment
+        .states
+        .iter()
+        .enumerate()
+        .all(|(k, &state)| keep_outside(state, k, fragment.argument))
+}
+
+/// 引数 `k` との位置関係が `state` の、引数 `argument` のフェイスを和の境界として残すか
+fn keep_outside(state: State, k: usize, argument: usize) -> bool {
+    match state {
+        State::Out => true,
+        State::On { same: true } => k > argument,
+        _ => false,
+    }
+}
+
+/// 形状に含まれる立体の境界のフェイス（立体が1つもなければエラー）
+fn solid_faces(shape: &Shape) -> Result<Vec<Face>> {
+    let solids = shape.explore(crate::ShapeKind::Solid).unique().count();
+    if solids == 0 {
+        return Err(OcctKrsError::InvalidInput(
+            "ブーリアン演算の引数に立体がありません".to_string(),
+        ));
+    }
+    Ok(shape
+        .explore(crate::ShapeKind::Solid)
+        .unique()
+        .flat_map(|s| s.faces())
+        .collect())
+}
+
+/// 形状に含まれるフェイスを返す（フェイスがない場合はエラー）
+fn shape_faces(shape: &Shape) -> Result<Vec<Face>> {
+    let faces = shape.faces();
+    if faces.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "交線を求める形状にフェイスがありません".to_string(),
+        ));
+    }
+    Ok(faces)
+}
+
+/// エッジを `Shape::Compound` にまとめる
+fn edge_compound(edges: Vec<Edge>) -> Shape {
+    Shape::from(Compound::new(edges.into_iter().map(Shape::from).collect()))
+}
+
+/// フェイスをエッジでつながった塊ごとにシェルにまとめ、外側のシェルと空洞から立体を組み立てる
+///
+/// 符号付き体積が正のシェルを外側の境界とし、負のシェルはそれを含む最も小さい外側の立体の空洞にする。
+/// 閉じていないシェルや、どの外側の立体にも含まれない空洞があればエラーを返す。
+fn assemble(faces: Vec<Face>) -> Result<Vec<Solid>> {
+    let mut parent: Vec<usize> = (0..faces.len()).collect();
+    let mut owner: HashMap<ShapeId, usize> = HashMap::new();
+    for (i, face) in faces.iter().enumerate() {
+        for edge in face.wires().iter().flat_map(Wire::edges) {
+            if edge.is_degenerate() {
+                continue;
+            }
+            if let Some(&j) = owner.get(&edge.id()) {
+                let (a, b) = (
+                    union_find_root(&mut parent, i),
+                    union_find_root(&mut parent, j),
+                );
+                parent[a] = b;
+            } else {
+                owner.insert(edge.id(), i);
+            }
+        }
+    }
+    let mut groups: HashMap<usize, Vec<Face>> = HashMap::new();
+    let mut order = Vec::new();
+    for (i, face) in faces.into_iter().enumerate() {
+        let root = union_find_root(&mut parent, i);
+        if !groups.contains_key(&root) {
+            order.push(root);
+        }
+        groups.entry(root).or_default().push(face);
+    }
+    let mut outers: Vec<(Shell, f64, Vec<FaceClassifier>)> = Vec::new();
+    let mut cavities = Vec::new();
+    for root in order {
+        let shell = Shell::new(groups.remove(&root).unwrap_or_default())?;
+        if !shell.is_closed() {
+            return Err(open_shell());
+        }
+        let volume = signed_volume(&shell);
+        if volume > 0.0 {
+            let classifiers = shell
+                .faces()
+                .iter()
+                .map(FaceClassifier::new)
+                .collect::<Result<Vec<_>>>()?;
+            outers.push((shell, volume, classifiers));
+        } else if volume < 0.0 {
+            cavities.push(shell);
+        }
+    }
+    let mut holes: Vec<Vec<Shell>> = vec![Vec::new(); outers.len()];
+    for cavity in cavities {
+        let Some(p) = cavity
+            .faces()
+            .first()
+            .and_then(|f| f.wires().first().and_then(|w| w.edges().first().cloned()))
+            .map(|e| {
+                let (a, b) = e.range();
+                e.point_at(0.5 * (a + b))
+            })
+        else {
+            return Err(orphan_cavity());
+        };
+        let owner = outers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, classifiers))| {
+                classify_point(classifiers, p, precision::confusion())
+                    == PointClassification::Inside
+            })
+            .min_by(|a, b| a.1 .1.total_cmp(&b.1 .1));
+        let Some((o, _)) = owner else {
+            return Err(orphan_cavity());
+        };
+        holes[o].push(cavity);
+    }
+    outers
+        .into_iter()
+        .zip(holes)
+        .map(|((outer, _, _), holes)| {
+            let mut shells = vec![outer];
+            shells.extend(holes);
+            Solid::new(shells)
+        })
+        .collect()
+}
+
+/// 立体が1つなら `Shape::Solid`、それ以外は立体の `Shape::Compound` にする
+///
+/// 立体がない場合や、閉じていないシェルがある場合はエラーを返す。
+fn to_shape(mut solids: Vec<Solid>) -> Result<Shape> {
+    if solids
+        .iter()
+        .flat_map(Solid::shells)
+        .any(|s| !s.is_closed())
+    {
+        return Err(open_shell());
+    }
+    match solids.len() {
+        0 => Err(OcctKrsError::DegenerateGeometry(
+            "ブーリアン演算の結果に立体がありません".to_string(),
+        )),
+        1 => Ok(Shape::from(solids.remove(0))),
+        _ => Ok(Shape::from(Compound::new(
+            solids.into_iter().map(Shape::from).collect(),
+        ))),
+    }
+}
+
+fn open_shell() -> OcctKrsError {
+    OcctKrsError::DegenerateGeometry("ブーリアン演算の結果のシェルが閉じていません".to_string())
+}
+
+fn orphan_cavity() -> OcctKrsError {
+    OcctKrsError::DegenerateGeometry(
+        "ブーリアン演算の結果に、どの立体にも含まれない空洞があります".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Dir, Point3, ShapeKind};
+    use std::collections::HashSet;
+
+    fn box_at(x: f64, y: f64, z: f64, size: f64) -> Shape {
+        let position = Axis2::new(Point3::new(x, y, z), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, size, size, size).unwrap())
+    }
+
+    #[test]
+    fn test_halfspace_with_plane() {
+        let block = box_at(0.0, 0.0, 0.0, 1.0);
+        let plane = Plane::new(Point3::new(0.0, 0.0, 0.25), Dir::Z);
+        let above = make_halfspace(plane, Point3::new(0.0, 0.0, 5.0)).unwrap();
+        assert!(above.contains(Point3::new(3.0, 3.0, 1.0)));
+        assert!(!above.contains(Point3::new(0.5, 0.5, 0.0)));
+        let lower = cut_halfspace(&block, &above).unwrap();
+        assert!((checked_volume(&lower) - 0.25).abs() < 1e-9);
+        let upper = common_halfspace(&block, &above).unwrap();
+        assert!((checked_volume(&upper) - 0.75).abs() < 1e-9);
+        // 基準点の側が逆なら結果も入れ替わる
+        let below = make_halfspace(plane, Point3::new(0.0, 0.0, -5.0)).unwrap();
+        assert!((checked_volume(&cut_halfspace(&block, &below).unwrap()) - 0.75).abs() < 1e-9);
+        // 立体と交わらない平面では、立体全体が片側に残る
+        let far = make_halfspace(
+            Plane::new(Point3::new(0.0, 0.0, 3.0), Dir::Z),
+            Point3::ORIGIN,
+        );
+        assert!(
+            (checked_volume(&common_halfspace(&block, &far.unwrap()).unwrap()) - 1.0).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_halfspace_with_closed_surface() {
+        use crate::{Axis3, CylindricalSurface};
+        let block = box_at(-1.0, -1.0, -1.0, 2.0);
+        let axis = Axis3::from_normal(Point3::ORIGIN, Dir::Z);
+        let cylinder = CylindricalSurface::new(axis, 0.5).unwrap();
+        let inside = make_halfspace(cylinder, Point3::ORIGIN).unwrap();
+        let core = common_halfspace(&block, &inside).unwrap();
+        let expected = std::f64::consts::PI * 0.25 * 2.0;
+        assert!((checked_volume(&core) / expected - 1.0).abs() < 1e-6);
+        // 外側の半空間を取り除くのは内側との共通部分と同じ
+        let outside = make_halfspace(cylinder, Point3::new(3.0, 0.0, 0.0)).unwrap();
+        let same = cut_halfspace(&block, &outside).unwrap();
+        assert!((checked_volume(&same) / expected - 1.0).abs() < 1e-6);
+        let drilled = common_halfspace(&block, &outside).unwrap();
+        assert!((checked_volume(&drilled) / (8.0 - expected) - 1.0).abs() < 1e-6);
+
+        let line = crate::Line::new(Point3::ORIGIN, Dir::X);
+        let extruded = crate::ExtrudedSurface::new(crate::GeomCurve::from(line), Dir::Z);
+        assert!(make_halfspace(extruded, Point3::new(0.0, 1.0, 0.0)).is_err());
+        let plane = Plane::new(Point3::ORIGIN, Dir::Z);
+        assert!(make_halfspace(plane, Point3::new(1.0, 1.0, 0.0)).is_err());
+    }
+
+    #[test]
+    fn test_fuse_overlapping_boxes() {
+        let result = fuse(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(0.5, 0.5, 0.5, 1.0)).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        assert!((volume / 1.875 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(result.faces().len(), 12);
+    }
+
+    #[test]
+    fn test_fuse_boxes_sharing_a_face() {
+        let result = fuse(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(1.0, 0.5, 0.0, 1.0)).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        assert!((volume / 2.0 - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_glue_blocks() {
+        let row = glue(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(1.0, 0.0, 0.0, 1.0)).unwrap();
+        assert_eq!(row.kind(), ShapeKind::Solid);
+        assert!((checked_volume(&row) - 2.0).abs() < 1e-9);
+        assert_eq!(row.faces().len(), 10);
+        // つないだ結果にさらにブロックを積む
+        let stack = glue(&row, &box_at(1.0, 0.0, 1.0, 1.0)).unwrap();
+        assert!((checked_volume(&stack) - 3.0).abs() < 1e-9);
+        assert_eq!(stack.faces().len(), 14);
+
+        // 円柱を円のフェイスで積み重ねる
+        let cylinder = |z: f64| {
+            let position = Axis2::new(Point3::new(0.0, 0.0, z), Dir::Z, Dir::X).unwrap();
+            Shape::from(make_cylinder(position, 0.5, 1.0).unwrap())
+        };
+        let column = glue(&cylinder(0.0), &cylinder(1.0)).unwrap();
+        let expected = std::f64::consts::PI * 0.25 * 2.0;
+        assert!((checked_volume(&column) / expected - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_glue_errors() {
+        // 一部だけ接するフェイスや離れた立体はつなげない
+        let a = box_at(0.0, 0.0, 0.0, 1.0);
+        assert!(glue(&a, &box_at(1.0, 0.5, 0.0, 1.0)).is_err());
+        assert!(glue(&a, &box_at(3.0, 0.0, 0.0, 1.0)).is_err());
+        // 重なった同じ形の立体はフェイスが一致しても外向きの法線がそろうのでつなげない
+        assert!(glue(&a, &box_at(0.0, 0.0, 0.0, 1.0)).is_err());
+        assert!(glue(&a, &Shape::from(Compound::new(Vec::new()))).is_err());
+    }
+
+    #[test]
+    fn test_fuse_box_and_cylinder() {
+        let position = Axis2::new(Point3::new(1.0, 0.5, 0.25), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.25, 1.0).unwrap());
+        let result = fuse(&box_at(0.0, 0.0, 0.0, 1.0), &cylinder).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        // 円柱の半分は箱の側面から、上の 0.25 は箱の上面から出る
+        let expected = 1.0 + std::f64::consts::PI * 0.0625 * (0.5 * 0.75 + 0.25);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_fuse_crossing_cylinders() {
+        // 円柱どうしの交線は追跡で求める
+        let a = make_cylinder(
+            Axis2::new(Point3::new(0.0, 0.0, -1.0), Dir::Z, Dir::X).unwrap(),
+            0.5,
+            2.0,
+        );
+        let b = make_cylinder(
+            Axis2::new(Point3::new(-1.0, 0.0, 0.0), Dir::X, Dir::Y).unwrap(),
+            0.3,
+            2.0,
+        );
+        let result = fuse(&Shape::from(a.unwrap()), &Shape::from(b.unwrap())).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        // 交線は近似曲線なので、体積もその誤差の分だけずれる
+        assert!((volume / 1.866913 - 1.0).abs() < 1e-4, "{}", volume);
+        // 細い円柱の側面は太い円柱の両側の2枚に分かれる
+        assert_eq!(result.faces().len(), 7);
+    }
+
+    #[test]
+    fn test_cut_corner() {
+        let result = cut(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(0.5, 0.5, 0.5, 1.0)).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        assert!((volume / 0.875 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(result.faces().len(), 9);
+    }
+
+    #[test]
+    fn test_cut_through_hole() {
+        let position = Axis2::new(Point3::new(0.5, 0.5, -0.5), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.25, 2.0).unwrap());
+        let result = cut(&box_at(0.0, 0.0, 0.0, 1.0), &cylinder).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        let expected = 1.0 - std::f64::consts::PI * 0.0625;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 上下の面に穴があき、円柱の側面が穴の壁になる
+        let holed = result
+            .faces()
+            .iter()
+            .filter(|f| f.inner_wires().len() == 1)
+            .count();
+        assert_eq!(holed, 2);
+        assert_eq!(result.faces().len(), 7);
+    }
+
+    #[test]
+    fn test_cut_all_tools() {
+        // 重なった2つの道具と、対象に触れない道具をまとめて取り除く
+        let tools = [
+            box_at(0.5, 0.5, 0.5, 1.0),
+            box_at(0.25, 0.5, 0.5, 1.0),
+            box_at(5.0, 5.0, 5.0, 1.0),
+        ];
+        let result = cut_all(&box_at(0.0, 0.0, 0.0, 1.0), &tools).unwrap();
+        let volume = checked_volume(&result);
+        assert!((volume / 0.8125 - 1.0).abs() < 1e-6, "{}", volume);
+        let two = cut(&box_at(0.0, 0.0, 0.0, 1.0), &tools[0]).unwrap();
+        let two = cut(&two, &tools[1]).unwrap();
+        assert!((checked_volume(&two) - volume).abs() < 1e-9);
+        // すべてを取り除く場合や道具がない場合はエラー
+        assert!(cut(&box_at(0.5, 0.5, 0.5, 1.0), &box_at(0.0, 0.0, 0.0, 2.0)).is_err());
+        assert!(cut_all(&box_at(0.0, 0.0, 0.0, 1.0), &[]).is_err());
+    }
+
+    #[test]
+    fn test_common_boxes() {
+        let report =
+            common_report(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(0.5, 0.5, 0.5, 1.0)).unwrap();
+        assert_eq!(report.contact, Contact::Overlapping);
+        assert_eq!(report.shape.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&report.shape);
+        assert!((volume / 0.125 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(report.shape.faces().len(), 6);
+        // 一方がもう一方を含む場合は小さい方になる
+        let inner = common(&box_at(0.0, 0.0, 0.0, 3.0), &box_at(1.0, 1.0, 1.0, 1.0)).unwrap();
+        assert!((checked_volume(&inner) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_common_box_and_cylinder() {
+        let position = Axis2::new(Point3::new(1.0, 0.5, 0.25), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.25, 1.0).unwrap());
+        let result = common(&box_at(0.0, 0.0, 0.0, 1.0), &cylinder).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        let volume = checked_volume(&result);
+        // 半円柱の高さ 0.75 の部分
+        let expected = 0.5 * std::f64::consts::PI * 0.0625 * 0.75;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_booleans_with_mirrored_solids() {
+        use crate::Plane;
+        use std::f64::consts::PI;
+        let cube = box_at(0.0, 0.0, 0.0, 1.0);
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.5, 1.0).unwrap());
+        let mirror = |shape: &Shape, x: f64, copy: bool| {
+            shape
+                .mirrored(&Plane::new(Point3::new(x, 0.0, 0.0), Dir::X), copy)
+                .unwrap()
+        };
+        // 半径 0.5 の円を中心間の距離 0.6 だけずらしたときに重なるレンズの面積
+        let lens = 0.5 * 0.6f64.acos() - 0.3 * 0.8;
+        for copy in [false, true] {
+            let image = mirror(&cube, 0.75, copy);
+            assert!((checked_volume(&image) - 1.0).abs() < 1e-9);
+            let cases = [
+                (fuse(&cube, &image), 1.5),
+                (cut(&cube, &image), 0.5),
+                (
+                    fuse(&cylinder, &mirror(&cylinder, 0.3, copy)),
+                    PI / 2.0 - lens,
+                ),
+                // 鏡映した円柱の 1/4 が立方体に食い込む
+                (cut(&cube, &mirror(&cylinder, 0.5, copy)), 1.0 - PI / 16.0),
+            ];
+            for (result, expected) in cases {
+                let result = result.unwrap();
+                assert_eq!(result.kind(), ShapeKind::Solid);
+                let volume = checked_volume(&result);
+                assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+            }
+        }
+    }
+
+    #[test]
+    fn test_booleans_with_sphere_through_vertex() {
+        // 球の中心が立体の頂点にあり、交線が球の極を通る
+        let block = box_at(0.0, 0.0, 0.0, 1.0);
+        let position = Axis2::new(Point3::new(1.0, 1.0, 1.0), Dir::Z, Dir::X).unwrap();
+        let ball = Shape::from(make_sphere(position, 0.5).unwrap());
+        let octant = std::f64::consts::PI / 48.0;
+        for (result, expected) in [
+            (fuse(&block, &ball).unwrap(), 1.0 + 7.0 * octant),
+            (cut(&block, &ball).unwrap(), 1.0 - octant),
+            (common(&block, &ball).unwrap(), octant),
+        ] {
+            let volume = checked_volume(&result);
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        }
+    }
+
+    #[test]
+    fn test_assemble_rejects_open_shells_and_orphan_cavities() {
+        let faces = box_at(0.0, 0.0, 0.0, 1.0).faces();
+        assert!(matches!(
+            assemble(faces[1..].to_vec()),
+            Err(OcctKrsError::DegenerateGeometry(_))
+        ));
+        // 外側の立体がない空洞
+        let reversed: Vec<Face> = faces.iter().map(Face::reversed).collect();
+        assert!(matches!(
+            assemble(reversed.clone()),
+            Err(OcctKrsError::DegenerateGeometry(_))
+        ));
+        let mut inside = box_at(-1.0, -1.0, -1.0, 3.0).faces();
+        inside.extend(reversed);
+        assert_eq!(assemble(inside).unwrap()[0].shells().len(), 2);
+    }
+
+    #[test]
+    fn test_common_contact() {
+        let a = box_at(0.0, 0.0, 0.0, 1.0);
+        // 面で接する
+        let report = common_report(&a, &box_at(1.0, 0.5, 0.0, 1.0)).unwrap();
+        assert_eq!(report.contact, Contact::Touching);
+        assert_eq!(report.shape.kind(), ShapeKind::Compound);
+        assert!(report.shape.faces().is_empty());
+        // 辺で接する
+        let report = common_report(&a, &box_at(1.0, 1.0, 0.0, 1.0)).unwrap();
+        assert_eq!(report.contact, Contact::Touching);
+        // 離れている
+        let report = common_report(&a, &box_at(3.0, 0.0, 0.0, 1.0)).unwrap();
+        assert_eq!(report.contact, Contact::Disjoint);
+        assert!(common(&a, &box_at(3.0, 0.0, 0.0, 1.0)).is_err());
+    }
+
+    /// 交線のエッジの長さの合計（折れ線による近似値）
+    fn section_length(shape: &Shape) -> f64 {
+        shape
+            .edges()
+            .iter()
+            .map(|e| {
+                let (t0, t1) = e.range();
+                (0..64)
+                    .map(|k| {
+                        let t = |k: usize| t0 + (t1 - t0) * k as f64 / 64.0;
+                        e.point_at(t(k)).distance(e.point_at(t(k + 1)))
+                    })
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_section_boxes() {
+        let result = section(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(0.5, 0.5, 0.5, 1.0)).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Compound);
+        // 重なった部分の稜線のうち、両方の境界が交わる6本
+        assert_eq!(result.edges().len(), 6);
+        assert!((section_length(&result) - 3.0).abs() < 1e-9);
+        let disjoint = section(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(3.0, 0.0, 0.0, 1.0)).unwrap();
+        assert!(disjoint.edges().is_empty());
+    }
+
+    #[test]
+    fn test_section_with_plane() {
+        let cube = box_at(0.0, 0.0, 0.0, 1.0);
+        let plane = Plane::new(Point3::new(0.0, 0.0, 0.25), Dir::Z);
+        let result = section_with_plane(&cube, &plane).unwrap();
+        assert_eq!(result.edges().len(), 4);
+        assert!((section_length(&result) - 4.0).abs() < 1e-9);
+        // 円柱を軸に垂直な平面で切ると円になる
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.5, 1.0).unwrap());
+        let circle = section_with_plane(&cylinder, &plane).unwrap();
+        let length = section_length(&circle);
+        assert!((length - std::f64::consts::PI).abs() < 1e-2, "{}", length);
+        // 形状から離れた平面
+        let far = Plane::new(Point3::new(0.0, 0.0, 5.0), Dir::Z);
+        assert!(section_with_plane(&cube, &far).unwrap().edges().is_empty());
+    }
+
+    #[test]
+    fn test_split_by_planes() {
+        let cube = box_at(0.0, 0.0, 0.0, 1.0);
+        let plane = Plane::new(Point3::new(0.0, 0.0, 0.25), Dir::Z);
+        let pieces = split_by_planes(&cube, &[plane]).unwrap();
+        assert_eq!(pieces.len(), 2);
+        for piece in &pieces {
+            let volume = checked_volume(&Shape::from(piece.solid.clone()));
+            let expected = match piece.sides[0] {
+                Side::Positive => 0.75,
+                Side::Negative => 0.25,
+            };
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        }
+        assert_ne!(pieces[0].sides, pieces[1].sides);
+        // 2つの平面で4つに分ける
+        let planes = [
+            Plane::new(Point3::new(0.5, 0.0, 0.0), Dir::X),
+            Plane::new(Point3::new(0.0, 0.5, 0.0), Dir::Y),
+        ];
+        let pieces = split_by_planes(&cube, &planes).unwrap();
+        assert_eq!(pieces.len(), 4);
+        let sides: HashSet<Vec<Side>> = pieces.iter().map(|p| p.sides.clone()).collect();
+        assert_eq!(sides.len(), 4);
+        for piece in &pieces {
+            let volume = checked_volume(&Shape::from(piece.solid.clone()));
+            assert!((volume / 0.25 - 1.0).abs() < 1e-6, "{}", volume);
+        }
+        // 立体に触れない平面では分割されない
+        let far = Plane::new(Point3::new(0.0, 0.0, 5.0), Dir::Z);
+        let pieces = split_by_planes(&cube, &[far]).unwrap();
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].sides, vec![Side::Negative]);
+        assert!(split_by_planes(&cube, &[]).is_err());
+    }
+
+    #[test]
+    fn test_split_by_faces() {
+        let cube = box_at(0.0, 0.0, 0.0, 1.0);
+        let position = Axis2::new(Point3::new(0.5, 0.5, -0.5), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 0.25, 2.0).unwrap());
+        let pieces = split(&cube, &[cylinder]).unwrap();
+        assert_eq!(pieces.len(), 2);
+        let inner = std::f64::consts::PI / 16.0;
+        for piece in &pieces {
+            let volume = checked_volume(&Shape::from(piece.solid.clone()));
+            // 円柱の内側は法線と反対の側
+            let expected = match piece.sides[0] {
+                Side::Positive => 1.0 - inner,
+                Side::Negative => inner,
+            };
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        }
+        // 立体の内側で閉じたフェイスでは切り分けられない
+        let face = box_at(0.25, 0.25, 0.25, 0.5).faces()[0].clone();
+        assert!(split(&cube, &[Shape::from(face)]).is_err());
+    }
+
+    #[test]
+    fn test_fuse_disjoint_and_errors() {
+        let result = fuse(&box_at(0.0, 0.0, 0.0, 1.0), &box_at(3.0, 0.0, 0.0, 1.0)).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Compound);
+        let volume = checked_volume(&result);
+        assert!((volume / 2.0 - 1.0).abs() < 1e-6, "{}", volume);
+        let face = box_at(0.0, 0.0, 0.0, 1.0).faces()[0].clone();
+        assert!(fuse(&Shape::from(face), &box_at(0.0, 0.0, 0.0, 1.0)).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Point3, Vector3};
+
+/// 座標軸に平行な境界ボックス（OCCT の `Bnd_Box` 相当）
+///
+/// 無限に延びる形状を表すため、成分に無限大を含んでもよい。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoundingBox {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl BoundingBox {
+    /// 2つの角の点から境界ボックスを生成する（成分ごとに大小を整える）
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Self {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    /// 点列を囲む最小の境界ボックスを返す
+    /// 点列が空の場合は `None` を返す
+    pub fn from_points<I: IntoIterator<Item = Point3>>(points: I) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        let mut b = Self::new(first, first);
+        for p in iter {
+            b.add_point(p);
+        }
+        Some(b)
+    }
+
+    /// 全空間を覆う境界ボックスを返す
+    pub fn infinite() -> Self {
+        let inf = f64::INFINITY;
+        Self::new(Point3::new(-inf, -inf, -inf), Point3::new(inf, inf, inf))
+    }
+
+    /// 点を含むよう拡張する
+    pub fn add_point(&mut self, p: Point3) {
+        self.min = self.min.component_min(p);
+        self.max = self.max.component_max(p);
+    }
+
+    /// 2つの境界ボックスを両方含む境界ボックスを返す
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        Self {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    /// 全方向に `tol` だけ広げた境界ボックスを返す
+    pub fn enlarged(&self, tol: f64) -> Self {
+        let d = Vector3::new(tol, tol, tol);
+        Self {
+            min: self.min - d,
+            max: self.max + d,
+        }
+    }
+
+    /// 中心を返す
+    pub fn center(&self) -> Point3 {
+        self.min.midpoint(self.max)
+    }
+
+    /// 各辺の長さを返す
+    pub fn size(&self) -> Vector3 {
+        self.max - self.min
+    }
+
+    /// 点が境界ボックスの内部（境界を含む）にあるか判定する
+    pub fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// 2つの境界ボックスが重なる（接する場合を含む）か判定する
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// いずれかの方向に無限に延びていれば `true` を返す
+    pub fn is_infinite(&self) -> bool {
+        !(self
+            .min
+            .to_vector()
+            .to_array()
+            .iter()
+            .all(|v| v.is_finite())
+            && self
+                .max
+                .to_vector()
+                .to_array()
+                .iter()
+                .all(|v| v.is_finite()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_points_and_queries() {
+        let b = BoundingBox::from_points([
+            Point3::new(1.0, -2.0, 0.0),
+            Point3::new(-1.0, 3.0, 2.0),
+            Point3::new(0.0, 0.0, 5.0),
+        ])
+        .unwrap();
+        assert_eq!(b.min, Point3::new(-1.0, -2.0, 0.0));
+        assert_eq!(b.max, Point3::new(1.0, 3.0, 5.0));
+        assert_eq!(b.size(), Vector3::new(2.0, 5.0, 5.0));
+        assert_eq!(b.center(), Point3::new(0.0, 0.5, 2.5));
+        assert!(b.contains(Point3::new(1.0, 3.0, 5.0)));
+        assert!(!b.contains(Point3::new(1.1, 0.0, 0.0)));
+        assert!(b.enlarged(0.2).contains(Point3::new(1.1, 0.0, 0.0)));
+        assert!(BoundingBox::from_points(Vec::new()).is_none());
+        assert!(!b.is_infinite());
+        assert!(BoundingBox::infinite().is_infinite());
+    }
+
+    #[test]
+    fn test_union_and_intersects() {
+        let a = BoundingBox::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        let b = BoundingBox::new(Point3::new(2.0, 2.0, 2.0), Point3::new(1.0, 1.0, 1.0));
+        let c = BoundingBox::new(Point3::new(3.0, 0.0, 0.0), Point3::new(4.0, 1.0, 1.0));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&c));
+        let u = a.union(&c);
+        assert_eq!(u.min, Point3::origin());
+        assert_eq!(u.max, Point3::new(4.0, 1.0, 1.0));
+    }
+}
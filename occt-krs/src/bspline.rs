@@ -0,0 +1,1205 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::f64::consts::TAU;
+
+use crate::curve::{check_trim_range, split_ranges};
+use crate::precision;
+use crate::{Arc, Axis2, Circle, Ellipse, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// B-スプライン曲線（OCCT の `Geom_BSplineCurve` 相当、非周期）
+///
+/// 重みを持つ場合は有理 B-スプライン（NURBS）となり、円や楕円を厳密に表せる。
+/// ノットは重複を展開した列（フラットノット）として保持する。
+/// 制御点の数を `n`、次数を `p` とすると、ノットの数は `n + p + 1` になる。
+/// パラメータ範囲は `[knots[p], knots[n]]`。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BSplineCurve {
+    degree: usize,
+    control_points: Vec<Point3>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    weights: Option<Vec<f64>>,
+    knots: Vec<f64>,
+}
+
+impl BSplineCurve {
+    /// 次数・制御点・異なるノット値とその多重度から曲線を生成する（OCCT と同じ指定方法）
+    ///
+    /// ノットが狭義単調増加でない、多重度の合計が `制御点数 + 次数 + 1` と一致しない、
+    /// 内部ノットの多重度が次数を超える場合などはエラーを返す。
+    pub fn new(
+        degree: usize,
+        control_points: Vec<Point3>,
+        knots: &[f64],
+        multiplicities: &[usize],
+    ) -> Result<Self> {
+        if knots.len() != multiplicities.len() {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "ノット数 {} と多重度の数 {} が一致しません",
+                knots.len(),
+                multiplicities.len()
+            )));
+        }
+        if knots.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(OcctKrsError::InvalidInput(
+                "ノットは狭義単調増加である必要があります".to_string(),
+            ));
+        }
+        let last = multiplicities.len().saturating_sub(1);
+        for (i, &m) in multiplicities.iter().enumerate() {
+            let max = if i == 0 || i == last {
+                degree + 1
+            } else {
+                degree
+            };
+            if m == 0 || m > max {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のノットの多重度 {} が不正です",
+                    i, m
+                )));
+            }
+        }
+        let flat = knots
+            .iter()
+            .zip(multiplicities)
+            .flat_map(|(&k, &m)| std::iter::repeat_n(k, m))
+            .collect();
+        Self::from_flat_knots(degree, control_points, flat)
+    }
+
+    /// 次数・制御点・フラットノットから曲線を生成する
+    /// ノット列が単調非減少でない、または長さが `制御点数 + 次数 + 1` と一致しない場合はエラーを返す
+    pub fn from_flat_knots(
+        degree: usize,
+        control_points: Vec<Point3>,
+        knots: Vec<f64>,
+    ) -> Result<Self> {
+        let n = control_points.len();
+        if degree == 0 || n < degree + 1 {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "次数 {} の曲線には {} 個以上の制御点が必要です（{} 個）",
+                degree,
+                degree + 1,
+                n
+            )));
+        }
+        if knots.len() != n + degree + 1 {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "ノット数 {} が 制御点数 + 次数 + 1 = {} と一致しません",
+                knots.len(),
+                n + degree + 1
+            )));
+        }
+        if knots.iter().any(|k| !k.is_finite()) || knots.windows(2).any(|w| w[0] > w[1]) {
+            return Err(OcctKrsError::InvalidInput(
+                "ノット列は有限かつ単調非減少である必要があります".to_string(),
+            ));
+        }
+        if knots[degree] >= knots[n] {
+            return Err(OcctKrsError::InvalidInput(
+                "パラメータ範囲が空です".to_string(),
+            ));
+        }
+        Ok(Self {
+            degree,
+            control_points,
+            weights: None,
+            knots,
+        })
+    }
+
+    /// 各制御点に重みを与えた有理曲線を返す
+    /// 重みの数が制御点の数と一致しない、または正の有限値でない重みがある場合はエラーを返す
+    pub fn with_weights(mut self, weights: Vec<f64>) -> Result<Self> {
+        if weights.len() != self.control_points.len() {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "重みの数 {} が制御点の数 {} と一致しません",
+                weights.len(),
+                self.control_points.len()
+            )));
+        }
+        if weights.iter().any(|w| !(*w > 0.0 && w.is_finite())) {
+            return Err(OcctKrsError::InvalidInput(
+                "重みは正の有限値である必要があります".to_string(),
+            ));
+        }
+        self.weights = Some(weights);
+        Ok(self)
+    }
+
+    /// 円を厳密に表す2次の有理曲線を生成する（パラメータは円の角度と両端で一致する）
+    pub fn from_circle(circle: &Circle) -> Self {
+        conic_arc(
+            &circle.position(),
+            circle.radius(),
+            circle.radius(),
+            0.0,
+            TAU,
+        )
+    }
+
+    /// 円弧を厳密に表す2次の有理曲線を生成する（パラメータは円弧の角度と両端で一致する）
+    pub fn from_arc(arc: &Arc) -> Self {
+        let c = arc.circle();
+        conic_arc(
+            &c.position(),
+            c.radius(),
+            c.radius(),
+            arc.start_angle(),
+            arc.end_angle(),
+        )
+    }
+
+    /// 楕円を厳密に表す2次の有理曲線を生成する
+    pub fn from_ellipse(ellipse: &Ellipse) -> Self {
+        conic_arc(
+            &ellipse.position(),
+            ellipse.major_radius(),
+            ellipse.minor_radius(),
+            0.0,
+            TAU,
+        )
+    }
+
+    /// 両端のノットを次数 + 1 重にした一様ノットの曲線を生成する（パラメータ範囲は `[0, 1]`）
+    pub fn clamped_uniform(degree: usize, control_points: Vec<Point3>) -> Result<Self> {
+        let n = control_points.len();
+        if n < degree + 1 {
+            return Self::from_flat_knots(degree, control_points, Vec::new());
+        }
+        let spans = n - degree;
+        let knots = (0..n + degree + 1)
+            .map(|i| (i.saturating_sub(degree).min(spans)) as f64 / spans as f64)
+            .collect();
+        Self::from_flat_knots(degree, control_points, knots)
+    }
+
+    /// 次数を返す
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// 制御点を返す
+    pub fn control_points(&self) -> &[Point3] {
+        &self.control_points
+    }
+
+    /// 重みを返す（非有理の場合は `None`）
+    pub fn weights(&self) -> Option<&[f64]> {
+        self.weights.as_deref()
+    }
+
+    /// 有理曲線（重みを持つ）であれば `true` を返す
+    pub fn is_rational(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// i 番目の制御点の重みを返す（非有理の場合は 1）
+    pub fn weight(&self, i: usize) -> f64 {
+        self.weights.as_ref().map_or(1.0, |w| w[i])
+    }
+
+    /// 同次座標 `(w P, w)` で表した制御点
+    fn homogeneous(&self, i: usize) -> Homogeneous {
+        let w = self.weight(i);
+        (self.control_points[i].to_vector() * w, w)
+    }
+
+    /// フラットノットを返す
+    pub fn flat_knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    /// 異なるノット値を返す
+    pub fn knots(&self) -> Vec<f64> {
+        let mut r: Vec<f64> = Vec::new();
+        for &k in &self.knots {
+            if r.last() != Some(&k) {
+                r.push(k);
+            }
+        }
+        r
+    }
+
+    /// 各ノット値の多重度を返す（[`knots`](Self::knots) と同じ順）
+    pub fn multiplicities(&self) -> Vec<usize> {
+        let mut r: Vec<usize> = Vec::new();
+        for (i, k) in self.knots.iter().enumerate() {
+            if i > 0 && self.knots[i - 1] == *k {
+                *r.last_mut().unwrap() += 1;
+            } else {
+                r.push(1);
+            }
+        }
+        r
+    }
+
+    /// パラメータ範囲の始まりを返す
+    pub fn first_parameter(&self) -> f64 {
+        self.knots[self.degree]
+    }
+
+    /// パラメータ範囲の終わりを返す
+    pub fn last_parameter(&self) -> f64 {
+        self.knots[self.control_points.len()]
+    }
+
+    /// 始点を返す
+    pub fn start_point(&self) -> Point3 {
+        self.point_at(self.first_parameter())
+    }
+
+    /// 終点を返す
+    pub fn end_point(&self) -> Point3 {
+        self.point_at(self.last_parameter())
+    }
+
+    /// パラメータ `u` が属するノット区間の番号を返す
+    fn find_span(&self, u: f64) -> usize {
+        find_span(&self.knots, self.control_points.len() - 1, self.degree, u)
+    }
+
+    /// パラメータ `u` における点を de Boor のアルゴリズムで計算する
+    pub fn point_at(&self, u: f64) -> Point3 {
+        let p = self.degree;
+        let span = self.find_span(u);
+        let k = &self.knots;
+        let mut d: Vec<Homogeneous> = (0..=p).map(|j| self.homogeneous(span - p + j)).collect();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let i = span - p + j;
+                let denom = k[i + p + 1 - r] - k[i];
+                let alpha = if denom == 0.0 {
+                    0.0
+                } else {
+                    (u - k[i]) / denom
+                };
+                d[j] = (
+                    d[j - 1].0 * (1.0 - alpha) + d[j].0 * alpha,
+                    d[j - 1].1 * (1.0 - alpha) + d[j].1 * alpha,
+                );
+            }
+        }
+        Point3::from(d[p].0 / d[p].1)
+    }
+
+    /// パラメータ `u` における1階微分を返す
+    pub fn derivative_at(&self, u: f64) -> Vector3 {
+        self.derivatives_at(u, 1)[1]
+    }
+
+    /// パラメータ `u` における 0 階から `order` 階までの微分を返す
+    ///
+    /// 返り値の `[0]` は曲線上の点の位置ベクトル、`[k]` は k 階微分。次数を超える階数はゼロになる。
+    pub fn derivatives_at(&self, u: f64, order: usize) -> Vec<Vector3> {
+        let p = self.degree;
+        let span = self.find_span(u);
+        let ders = basis_function_derivatives(&self.knots, span, u, p, order.min(p));
+        // 同次座標での微分
+        let mut a = vec![Vector3::ZERO; order + 1];
+        let mut w = vec![0.0; order + 1];
+        for (k, row) in ders.iter().enumerate() {
+            for (j, &b) in row.iter().enumerate() {
+                let (pw, wj) = self.homogeneous(span - p + j);
+                a[k] += pw * b;
+                w[k] += wj * b;
+            }
+        }
+        if !self.is_rational() {
+            return a;
+        }
+        // 有理曲線の微分（The NURBS Book の A4.2）
+        let mut r: Vec<Vector3> = Vec::with_capacity(order + 1);
+        for k in 0..=order {
+            let mut v = a[k];
+            let mut binom = 1.0;
+            for i in 1..=k {
+                binom = binom * (k + 1 - i) as f64 / i as f64;
+                v -= r[k - i] * (binom * w[i]);
+            }
+            r.push(v / w[0]);
+        }
+        r
+    }
+
+    /// ノット `u` を `times` 回挿入する（形状は変わらない、Boehm のアルゴリズム）
+    ///
+    /// `u` がパラメータ範囲外の場合や、挿入後の多重度が次数を超える場合はエラーを返す。
+    pub fn insert_knot(&mut self, u: f64, times: usize) -> Result<()> {
+        if !(u > self.first_parameter() && u < self.last_parameter()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "ノット {} がパラメータ範囲の内部にありません",
+                u
+            )));
+        }
+        let p = self.degree;
+        let s = self.knots.iter().filter(|&&k| k == u).count();
+        if s + times > p {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "挿入後の多重度 {} が次数 {} を超えます",
+                s + times,
+                p
+            )));
+        }
+        for _ in 0..times {
+            self.insert_knot_once(u);
+        }
+        Ok(())
+    }
+
+    fn insert_knot_once(&mut self, u: f64) {
+        let p = self.degree;
+        let k = self.find_span(u);
+        let n = self.control_points.len();
+        let mut pts: Vec<Homogeneous> = Vec::with_capacity(n + 1);
+        pts.extend((0..=k - p).map(|i| self.homogeneous(i)));
+        for i in (k - p + 1)..=k {
+            let alpha = (u - self.knots[i]) / (self.knots[i + p] - self.knots[i]);
+            let (a, wa) = self.homogeneous(i - 1);
+            let (b, wb) = self.homogeneous(i);
+            pts.push((
+                a * (1.0 - alpha) + b * alpha,
+                wa * (1.0 - alpha) + wb * alpha,
+            ));
+        }
+        pts.extend((k..n).map(|i| self.homogeneous(i)));
+        self.set_homogeneous(pts);
+        self.knots.insert(k + 1, u);
+    }
+
+    /// 同次座標の制御点から制御点と重みを設定する
+    fn set_homogeneous(&mut self, pts: Vec<Homogeneous>) {
+        self.control_points = pts.iter().map(|(p, w)| Point3::from(*p / *w)).collect();
+        if self.weights.is_some() {
+            self.weights = Some(pts.iter().map(|(_, w)| *w).collect());
+        }
+    }
+
+    /// 複数のノットをまとめて挿入する（ノットの細分化）
+    ///
+    /// いずれかのノットが範囲外、または挿入後の多重度が次数を超える場合は何も変更せずにエラーを返す。
+    pub fn refine_knots(&mut self, knots: &[f64]) -> Result<()> {
+        let mut refined = self.clone();
+        for &u in knots {
+            refined.insert_knot(u, 1)?;
+        }
+        *self = refined;
+        Ok(())
+    }
+
+    /// 内部ノット `u` を最大 `times` 回取り除き、実際に取り除いた回数を返す（The NURBS Book の A5.8）
+    ///
+    /// 形状の変化が `tolerance` を超える除去は行わない。
+    /// `u` が内部ノットでない場合はエラーを返す。
+    pub fn remove_knot(&mut self, u: f64, times: usize, tolerance: f64) -> Result<usize> {
+        let s = self.knots.iter().filter(|&&k| k == u).count();
+        if s == 0 || !(u > self.first_parameter() && u < self.last_parameter()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "{} は内部ノットではありません",
+                u
+            )));
+        }
+        // 同次座標での許容誤差に換算する
+        let w_min = (0..self.control_points.len())
+            .map(|i| self.weight(i))
+            .fold(f64::INFINITY, f64::min);
+        let p_max = self
+            .control_points
+            .iter()
+            .map(|p| p.to_vector().length())
+            .fold(0.0, f64::max);
+        let tol = tolerance * w_min / (1.0 + p_max);
+
+        let p = self.degree;
+        let n = self.control_points.len() - 1;
+        let r = self.knots.iter().rposition(|&k| k == u).unwrap();
+        let knots = &self.knots;
+        let mut pw: Vec<Homogeneous> = (0..=n).map(|i| self.homogeneous(i)).collect();
+        let mut temp = vec![(Vector3::ZERO, 0.0); 2 * p + 1];
+        let (mut first, mut last) = (r - p, r - s);
+        let mut removed = 0;
+        while removed < times.min(s) {
+            let t = removed;
+            let off = first - 1;
+            temp[0] = pw[off];
+            temp[last + 1 - off] = pw[last + 1];
+            let (mut i, mut j) = (first, last);
+            let (mut ii, mut jj) = (1, last - off);
+            while j > i + t {
+                let alfi = (u - knots[i]) / (knots[i + p + 1 + t] - knots[i]);
+                let alfj = (u - knots[j - t]) / (knots[j + p + 1] - knots[j - t]);
+                temp[ii] = combine(pw[i], 1.0 / alfi, temp[ii - 1], -(1.0 - alfi) / alfi);
+                temp[jj] = combine(
+                    pw[j],
+                    1.0 / (1.0 - alfj),
+                    temp[jj + 1],
+                    -alfj / (1.0 - alfj),
+                );
+                i += 1;
+                ii += 1;
+                j -= 1;
+                jj -= 1;
+            }
+            let deviation = if j < i + t {
+                distance4(temp[ii - 1], temp[jj + 1])
+            } else {
+                let alfi = (u - knots[i]) / (knots[i + p + 1 + t] - knots[i]);
+                distance4(
+                    pw[i],
+                    combine(temp[ii + t + 1], alfi, temp[ii - 1], 1.0 - alfi),
+                )
+            };
+            if deviation > tol {
+                break;
+            }
+            let (mut i, mut j) = (first, last);
+            while j > i + t {
+                pw[i] = temp[i - off];
+                pw[j] = temp[j - off];
+                i += 1;
+                j -= 1;
+            }
+            first -= 1;
+            last += 1;
+            removed += 1;
+        }
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        // 除去した分だけノットと制御点を詰める
+        let t = removed;
+        self.knots.drain(r + 1 - t..=r);
+        let fout = (2 * r - s - p) / 2;
+        let (mut i, mut j) = (fout, fout);
+        for k in 1..t {
+            if k % 2 == 1 {
+                i += 1;
+            } else {
+                j -= 1;
+            }
+        }
+        for k in i + 1..=n {
+            pw[j] = pw[k];
+            j += 1;
+        }
+        pw.truncate(n + 1 - t);
+        self.set_homogeneous(pw);
+        Ok(removed)
+    }
+
+    /// 次数を `times` だけ上げた曲線を返す（形状とパラメータは変わらない）
+    ///
+    /// ベジエ区間に分解して各区間の次数を上げ、内部ノットの多重度を元の多重度 + `times` まで戻す。
+    pub fn elevate_degree(&self, times: usize) -> BSplineCurve {
+        if times == 0 {
+            return self.clone();
+        }
+        let (mut segments, breaks, multiplicities) = self.bezier_segments();
+        for seg in &mut segments {
+            for _ in 0..times {
+                *seg = elevate_bezier(seg);
+            }
+        }
+        let mut c =
+            Self::from_bezier_segments(self.degree + times, &segments, &breaks, self.is_rational());
+        let q = c.degree;
+        for (u, m) in breaks[1..breaks.len() - 1].iter().zip(multiplicities) {
+            // 理論上は厳密に取り除けるので、丸め誤差程度の許容誤差で除去する
+            c.remove_knot(*u, q - (m + times), precision::confusion())
+                .unwrap();
+        }
+        c
+    }
+
+    /// 次数を1つ下げた曲線を返す（非有理曲線のみ）
+    ///
+    /// ベジエ区間ごとに両端から次数を下げた制御点を求めて近似する。
+    /// 近似誤差の上限が `tolerance` を超える場合は `ToleranceExceeded` を、
+    /// 次数が1または有理曲線の場合は `InvalidInput` を返す。
+    pub fn reduce_degree(&self, tolerance: f64) -> Result<BSplineCurve> {
+        if self.degree < 2 || self.is_rational() {
+            return Err(OcctKrsError::InvalidInput(
+                "次数を下げられるのは2次以上の非有理曲線のみです".to_string(),
+            ));
+        }
+        let p = self.degree;
+        let (segments, breaks, multiplicities) = self.bezier_segments();
+        let mut reduced = Vec::with_capacity(segments.len());
+        let mut deviation: f64 = 0.0;
+        for seg in &segments {
+            let r = reduce_bezier(seg);
+            // 次数を戻した制御点との差は曲線の差の上限になる
+            let back = elevate_bezier(&r);
+            for (a, b) in back.iter().zip(seg) {
+                deviation = deviation.max(distance4(*a, *b));
+            }
+            reduced.push(r);
+        }
+        if deviation > tolerance {
+            return Err(OcctKrsError::ToleranceExceeded {
+                tolerance,
+                deviation,
+            });
+        }
+        let mut c = Self::from_bezier_segments(p - 1, &reduced, &breaks, false);
+        for (u, m) in breaks[1..breaks.len() - 1].iter().zip(multiplicities) {
+            let target = m.saturating_sub(1).max(1);
+            c.remove_knot(*u, p - 1 - target, precision::confusion())?;
+        }
+        Ok(c)
+    }
+
+    /// ベジエ区間（同次座標の制御点）、区切りのパラメータ、元の内部ノットの多重度に分解する
+    fn bezier_segments(&self) -> (Vec<Vec<Homogeneous>>, Vec<f64>, Vec<usize>) {
+        let p = self.degree;
+        let mut c = self
+            .trim(self.first_parameter(), self.last_parameter())
+            .unwrap();
+        let breaks = c.knots();
+        let multiplicities = c.multiplicities()[1..breaks.len() - 1].to_vec();
+        for (u, m) in breaks[1..breaks.len() - 1].iter().zip(&multiplicities) {
+            for _ in *m..p {
+                c.insert_knot_once(*u);
+            }
+        }
+        let segments = (0..breaks.len() - 1)
+            .map(|k| (k * p..=k * p + p).map(|i| c.homogeneous(i)).collect())
+            .collect();
+        (segments, breaks, multiplicities)
+    }
+
+    /// ベジエ区間をつないだ曲線を生成する（内部ノットの多重度は次数と等しくなる）
+    fn from_bezier_segments(
+        degree: usize,
+        segments: &[Vec<Homogeneous>],
+        breaks: &[f64],
+        rational: bool,
+    ) -> BSplineCurve {
+        let mut pts = segments[0].clone();
+        for seg in &segments[1..] {
+            pts.extend_from_slice(&seg[1..]);
+        }
+        let mut knots = vec![breaks[0]; degree + 1];
+        for u in &breaks[1..breaks.len() - 1] {
+            knots.extend(std::iter::repeat_n(*u, degree));
+        }
+        knots.extend(std::iter::repeat_n(breaks[breaks.len() - 1], degree + 1));
+        let mut c = Self {
+            degree,
+            control_points: Vec::new(),
+            weights: rational.then(Vec::new),
+            knots,
+        };
+        c.set_homogeneous(pts);
+        c
+    }
+
+    /// パラメータ範囲 `[u1, u2]` を切り出す（パラメータは元の曲線と同じ値を保つ）
+    ///
+    /// 両端のノットを次数と同じ多重度まで挿入し、その間の制御点を取り出す。
+    pub fn trim(&self, u1: f64, u2: f64) -> Result<BSplineCurve> {
+        check_trim_range(self.first_parameter(), self.last_parameter(), u1, u2)?;
+        let p = self.degree;
+        let mut c = self.clone();
+        for u in [u1, u2] {
+            let m = c.knots.iter().filter(|&&k| k == u).count();
+            for _ in m..p {
+                c.insert_knot_once(u);
+            }
+        }
+        // u1 での点は (u1 の最後の位置 - p) 番目の制御点、u2 での点は (u2 の最初の位置 - 1) 番目
+        let last_u1 = c.knots.iter().rposition(|&k| k == u1).unwrap();
+        let first_u2 = c.knots.iter().position(|&k| k == u2).unwrap();
+        let (s, e) = (last_u1 - p, first_u2 - 1);
+        let mut knots = vec![u1; p + 1];
+        knots.extend(c.knots.iter().filter(|&&k| k > u1 && k < u2));
+        knots.extend(std::iter::repeat_n(u2, p + 1));
+        Ok(Self {
+            degree: p,
+            control_points: c.control_points[s..=e].to_vec(),
+            weights: c.weights.map(|w| w[s..=e].to_vec()),
+            knots,
+        })
+    }
+
+    /// 指定したパラメータで分割する
+    pub fn split_at(&self, params: &[f64]) -> Result<Vec<BSplineCurve>> {
+        split_ranges(self.first_parameter(), self.last_parameter(), params)?
+            .into_iter()
+            .map(|(a, b)| self.trim(a, b))
+            .collect()
+    }
+
+    /// パラメータ範囲が `[u1, u2]` になるようノットを線形に変換した曲線を返す（形状は変わらない）
+    /// `u1 < u2` でない、または有限でない場合はエラーを返す
+    pub fn reparametrized(&self, u1: f64, u2: f64) -> Result<BSplineCurve> {
+        if !(u1 < u2 && u1.is_finite() && u2.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "パラメータ範囲 [{}, {}] が不正です",
+                u1, u2
+            )));
+        }
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        let scale = (u2 - u1) / (b - a);
+        Ok(Self {
+            knots: self.knots.iter().map(|k| u1 + (k - a) * scale).collect(),
+            ..self.clone()
+        })
+    }
+
+    /// 向きを反転した曲線を返す（パラメータ範囲は保たれる）
+    pub fn reversed(&self) -> BSplineCurve {
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        let mut control_points = self.control_points.clone();
+        control_points.reverse();
+        let weights = self
+            .weights
+            .as_ref()
+            .map(|w| w.iter().rev().copied().collect());
+        let knots = self.knots.iter().rev().map(|k| a + b - k).collect();
+        Self {
+            degree: self.degree,
+            control_points,
+            weights,
+            knots,
+        }
+    }
+
+    /// 制御点を `f` で写し、ノットを `parameter_scale` 倍した曲線を返す
+    /// 呼び出し側で `parameter_scale` が正の有限値であることを保証すること
+    pub(crate) fn mapped(
+        &self,
+        f: impl Fn(Point3) -> Point3,
+        parameter_scale: f64,
+    ) -> BSplineCurve {
+        Self {
+            degree: self.degree,
+            control_points: self.control_points.iter().map(|&p| f(p)).collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.iter().map(|k| k * parameter_scale).collect(),
+        }
+    }
+
+    /// 変換を適用した曲線を返す
+    pub fn transformed(&self, t: &Transform) -> BSplineCurve {
+        Self {
+            degree: self.degree,
+            control_points: self
+                .control_points
+                .iter()
+                .map(|p| t.transform_point(*p))
+                .collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+        }
+    }
+}
+
+/// 楕円弧（`a = b` なら円弧）を2次の有理曲線で表す（The NURBS Book の A7.1）
+///
+/// 中心角が π/2 以下になるよう区間を分け、各区間の中間の制御点は両端の接線の交点とする。
+fn conic_arc(position: &Axis2, a: f64, b: f64, start: f64, end: f64) -> BSplineCurve {
+    let sweep = end - start;
+    let segments = ((sweep / std::f64::consts::FRAC_PI_2).ceil() as usize).max(1);
+    let delta = sweep / segments as f64;
+    let w1 = (delta * 0.5).cos();
+    let at = |angle: f64, scale: f64| {
+        let (s, c) = angle.sin_cos();
+        position.point_at(a * c * scale, b * s * scale, 0.0)
+    };
+
+    let mut control_points = vec![at(start, 1.0)];
+    let mut weights = vec![1.0];
+    let mut knots = vec![start; 3];
+    for i in 0..segments {
+        let a0 = start + delta * i as f64;
+        control_points.push(at(a0 + delta * 0.5, 1.0 / w1));
+        weights.push(w1);
+        control_points.push(at(a0 + delta, 1.0));
+        weights.push(1.0);
+        let k = if i + 1 == segments { end } else { a0 + delta };
+        let m = if i + 1 == segments { 3 } else { 2 };
+        knots.extend(std::iter::repeat_n(k, m));
+    }
+    BSplineCurve {
+        degree: 2,
+        control_points,
+        weights: Some(weights),
+        knots,
+    }
+}
+
+/// 同次座標 `(w P, w)` で表した制御点
+type Homogeneous = (Vector3, f64);
+
+/// 同次座標の線形結合 `a * ca + b * cb`
+fn combine(a: Homogeneous, ca: f64, b: Homogeneous, cb: f64) -> Homogeneous {
+    (a.0 * ca + b.0 * cb, a.1 * ca + b.1 * cb)
+}
+
+/// 同次座標での距離
+fn distance4(a: Homogeneous, b: Homogeneous) -> f64 {
+    let d = a.0 - b.0;
+    (d.dot(d) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// ベジエ曲線（同次座標）の次数を1つ上げる
+fn elevate_bezier(p: &[Homogeneous]) -> Vec<Homogeneous> {
+    let n = p.len() - 1;
+    let mut q = Vec::with_capacity(n + 2);
+    q.push(p[0]);
+    for i in 1..=n {
+        let a = i as f64 / (n + 1) as f64;
+        q.push(combine(p[i - 1], a, p[i], 1.0 - a));
+    }
+    q.push(p[n]);
+    q
+}
+
+/// ベジエ曲線（同次座標）の次数を1つ下げた近似を返す
+///
+/// 前半の制御点は始点側から、後半は終点側から次数上げの式を逆に解いて求める。
+fn reduce_bezier(p: &[Homogeneous]) -> Vec<Homogeneous> {
+    let n = p.len() - 1;
+    let mut r = vec![(Vector3::ZERO, 0.0); n];
+    let half = n / 2;
+    r[0] = p[0];
+    for i in 1..half {
+        // P_i = i/n R_(i-1) + (1 - i/n) R_i
+        let a = i as f64 / n as f64;
+        r[i] = combine(p[i], 1.0 / (1.0 - a), r[i - 1], -a / (1.0 - a));
+    }
+    r[n - 1] = p[n];
+    for i in (half..n - 1).rev() {
+        // P_(i+1) = (i+1)/n R_i + (1 - (i+1)/n) R_(i+1)
+        let a = (i + 1) as f64 / n as f64;
+        r[i] = combine(p[i + 1], 1.0 / a, r[i + 1], -(1.0 - a) / a);
+    }
+    r
+}
+
+/// パラメータ `u` が属するノット区間の番号 `i`（`knots[i] <= u < knots[i + 1]`）を返す
+/// 範囲外の `u` は端の区間に丸める（`n` は最後の制御点の番号、`p` は次数）
+pub(crate) fn find_span(k: &[f64], n: usize, p: usize, u: f64) -> usize {
+    if u >= k[n + 1] {
+        // 終端は値が終端と一致する最後の有効区間に含める
+        let mut i = n;
+        while i > p && k[i] == k[i + 1] {
+            i -= 1;
+        }
+        return i;
+    }
+    if u <= k[p] {
+        let mut i = p;
+        while i < n && k[i] == k[i + 1] {
+            i += 1;
+        }
+        return i;
+    }
+    let (mut low, mut high) = (p, n + 1);
+    let mut mid = (low + high) / 2;
+    while u < k[mid] || u >= k[mid + 1] {
+        if u < k[mid] {
+            high = mid;
+        } else {
+            low = mid;
+        }
+        mid = (low + high) / 2;
+    }
+    mid
+}
+
+/// 基底関数とその微分を計算する（The NURBS Book の A2.3）
+///
+/// 返り値 `ders[k][j]` は区間 `span` で非ゼロとなる j 番目の基底関数の k 階微分。
+pub(crate) fn basis_function_derivatives(
+    knots: &[f64],
+    span: usize,
+    u: f64,
+    p: usize,
+    n: usize,
+) -> Vec<Vec<f64>> {
+    let mut ndu = vec![vec![0.0; p + 1]; p + 1];
+    let mut left = vec![0.0; p + 1];
+    let mut right = vec![0.0; p + 1];
+    ndu[0][0] = 1.0;
+    for j in 1..=p {
+        left[j] = u - knots[span + 1 - j];
+        right[j] = knots[span + j] - u;
+        let mut saved = 0.0;
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut ders = vec![vec![0.0; p + 1]; n + 1];
+    for j in 0..=p {
+        ders[0][j] = ndu[j][p];
+    }
+    let mut a = [vec![0.0; p + 1], vec![0.0; p + 1]];
+    for r in 0..=p {
+        let (mut s1, mut s2) = (0, 1);
+        a[0][0] = 1.0;
+        for k in 1..=n {
+            let mut d = 0.0;
+            let rk = r as isize - k as isize;
+            let pk = p - k;
+            if r >= k {
+                let rk = rk as usize;
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk];
+                d = a[s2][0] * ndu[rk][pk];
+            }
+            let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+            let j2 = if r as isize - 1 <= pk as isize {
+                k - 1
+            } else {
+                p - r
+            };
+            for j in j1..=j2 {
+                let idx = (rk + j as isize) as usize;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][idx];
+                d += a[s2][j] * ndu[idx][pk];
+            }
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d += a[s2][k] * ndu[r][pk];
+            }
+            ders[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+    let mut factor = p as f64;
+    for (k, row) in ders.iter_mut().enumerate().skip(1) {
+        for v in row.iter_mut() {
+            *v *= factor;
+        }
+        factor *= (p - k) as f64;
+    }
+    ders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BezierCurve, Dir};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn control_points() -> Vec<Point3> {
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(2.0, -1.0, 1.0),
+            Point3::new(4.0, 1.0, 0.0),
+            Point3::new(5.0, 3.0, -2.0),
+            Point3::new(7.0, 0.0, 0.0),
+        ]
+    }
+
+    fn sample() -> BSplineCurve {
+        BSplineCurve::new(3, control_points(), &[0.0, 1.0, 2.5, 4.0], &[4, 1, 1, 4]).unwrap()
+    }
+
+    #[test]
+    fn test_knot_representation() {
+        let c = sample();
+        assert_eq!(c.flat_knots().len(), 10);
+        assert_eq!(c.knots(), vec![0.0, 1.0, 2.5, 4.0]);
+        assert_eq!(c.multiplicities(), vec![4, 1, 1, 4]);
+        assert_eq!((c.first_parameter(), c.last_parameter()), (0.0, 4.0));
+        // 両端を通る
+        assert_point_eq(c.start_point(), control_points()[0]);
+        assert_point_eq(c.end_point(), control_points()[5]);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(BSplineCurve::new(3, control_points(), &[0.0, 1.0, 4.0], &[4, 1, 4]).is_err());
+        assert!(
+            BSplineCurve::new(3, control_points(), &[0.0, 2.0, 1.0, 4.0], &[4, 1, 1, 4]).is_err()
+        );
+        assert!(BSplineCurve::new(2, control_points(), &[0.0, 1.0, 4.0], &[3, 3, 3]).is_err());
+        assert!(BSplineCurve::clamped_uniform(6, control_points()).is_err());
+        assert!(BSplineCurve::from_flat_knots(1, control_points(), vec![0.0; 8]).is_err());
+    }
+
+    #[test]
+    fn test_matches_bezier() {
+        // 内部ノットのない B-スプラインはベジェ曲線と一致する
+        let pts = control_points()[..4].to_vec();
+        let b = BSplineCurve::clamped_uniform(3, pts.clone()).unwrap();
+        let z = BezierCurve::new(pts).unwrap();
+        for t in [0.0, 0.2, 0.5, 0.77, 1.0] {
+            assert_point_eq(b.point_at(t), z.point_at(t));
+            assert!((b.derivative_at(t) - z.derivative_at(t)).length() < 1e-10);
+            assert!((b.derivatives_at(t, 2)[2] - z.nth_derivative_at(t, 2)).length() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let c = sample();
+        let h = 1e-6;
+        for u in [0.1, 0.9, 1.5, 2.5, 3.9] {
+            let d = c.derivatives_at(u, 4);
+            assert_point_eq(Point3::from(d[0]), c.point_at(u));
+            let numeric = (c.point_at(u + h) - c.point_at(u - h)) * (0.5 / h);
+            assert!((numeric - d[1]).length() < 1e-6);
+            let numeric2 =
+                (c.derivatives_at(u + h, 1)[1] - c.derivatives_at(u - h, 1)[1]) * (0.5 / h);
+            assert!((numeric2 - d[2]).length() < 1e-5);
+            assert_eq!(d[4], Vector3::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_insert_knot() {
+        let c = sample();
+        let mut refined = c.clone();
+        refined.insert_knot(1.7, 2).unwrap();
+        refined.insert_knot(1.0, 1).unwrap();
+        assert_eq!(refined.control_points().len(), c.control_points().len() + 3);
+        assert_eq!(refined.multiplicities(), vec![4, 2, 2, 1, 4]);
+        for u in [0.0, 0.5, 1.0, 1.7, 2.2, 3.3, 4.0] {
+            assert_point_eq(refined.point_at(u), c.point_at(u));
+        }
+        // 多重度が次数を超える挿入や範囲外の挿入はエラー
+        assert!(refined.insert_knot(1.7, 2).is_err());
+        assert!(refined.insert_knot(4.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_reversed() {
+        let c = sample();
+        let r = c.reversed();
+        assert_eq!(r.multiplicities(), vec![4, 1, 1, 4]);
+        for u in [0.0, 0.3, 2.0, 3.6] {
+            assert_point_eq(r.point_at(4.0 - u), c.point_at(u));
+        }
+    }
+
+    #[test]
+    fn test_exact_circle() {
+        let normal = Dir::new(1.0, 1.0, 0.0).unwrap();
+        let circle = Circle::from_center_normal(Point3::new(1.0, 2.0, 3.0), normal, 2.5).unwrap();
+        let c = BSplineCurve::from_circle(&circle);
+        assert!(c.is_rational());
+        assert_eq!(c.control_points().len(), 9);
+        assert_eq!((c.first_parameter(), c.last_parameter()), (0.0, TAU));
+        for i in 0..=40 {
+            let u = TAU * i as f64 / 40.0;
+            let p = c.point_at(u);
+            // 全ての点が厳密に円周上にある
+            assert!(circle.distance(p) < 1e-12);
+            assert!((p.distance(circle.center()) - 2.5).abs() < 1e-12);
+        }
+        // 区間の境界では円の角度パラメータと一致する
+        assert_point_eq(c.point_at(FRAC_PI_2), circle.point_at(FRAC_PI_2));
+        assert_point_eq(c.point_at(PI), circle.point_at(PI));
+    }
+
+    #[test]
+    fn test_exact_arc_and_ellipse() {
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 1.0).unwrap();
+        let arc = Arc::new(circle, 0.3, 2.3).unwrap();
+        let c = BSplineCurve::from_arc(&arc);
+        assert_eq!(c.multiplicities(), vec![3, 2, 3]);
+        assert_point_eq(c.start_point(), arc.start_point());
+        assert_point_eq(c.end_point(), arc.end_point());
+        for i in 0..=20 {
+            let u = 0.3 + 2.0 * i as f64 / 20.0;
+            assert!((c.point_at(u).to_vector().length() - 1.0).abs() < 1e-12);
+        }
+
+        let e = Ellipse::new(Axis2::world(), 3.0, 2.0).unwrap();
+        let c = BSplineCurve::from_ellipse(&e);
+        for i in 0..=20 {
+            let p = c.point_at(TAU * i as f64 / 20.0);
+            assert!(((p.x / 3.0).powi(2) + (p.y / 2.0).powi(2) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rational_derivatives_and_insertion() {
+        let c = BSplineCurve::clamped_uniform(2, control_points()[..5].to_vec())
+            .unwrap()
+            .with_weights(vec![1.0, 0.5, 2.0, 0.8, 1.0])
+            .unwrap();
+        let h = 1e-6;
+        for u in [0.1, 0.4, 0.66, 0.9] {
+            let d = c.derivatives_at(u, 2);
+            let numeric = (c.point_at(u + h) - c.point_at(u - h)) * (0.5 / h);
+            assert!((numeric - d[1]).length() < 1e-5);
+            let numeric2 =
+                (c.derivatives_at(u + h, 1)[1] - c.derivatives_at(u - h, 1)[1]) * (0.5 / h);
+            assert!((numeric2 - d[2]).length() < 1e-3);
+        }
+        let mut refined = c.clone();
+        refined.insert_knot(0.5, 1).unwrap();
+        refined.insert_knot(0.2, 2).unwrap();
+        assert_eq!(refined.weights().unwrap().len(), 8);
+        for u in [0.0, 0.15, 0.5, 0.8, 1.0] {
+            assert_point_eq(refined.point_at(u), c.point_at(u));
+        }
+        assert_point_eq(c.reversed().point_at(0.3), c.point_at(0.7));
+
+        assert!(sample().with_weights(vec![1.0; 3]).is_err());
+        assert!(sample()
+            .with_weights(vec![1.0, -1.0, 1.0, 1.0, 1.0, 1.0])
+            .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let c = sample();
+        let json = serde_json::to_string(&c).unwrap();
+        assert!(!json.contains("weights"));
+        assert_eq!(serde_json::from_str::<BSplineCurve>(&json).unwrap(), c);
+        let r = sample()
+            .with_weights(vec![1.0, 2.0, 1.0, 0.5, 1.0, 1.0])
+            .unwrap();
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(serde_json::from_str::<BSplineCurve>(&json).unwrap(), r);
+    }
+
+    #[test]
+    fn test_trim_and_split() {
+        let c = sample();
+        let t = c.trim(0.5, 3.0).unwrap();
+        assert_eq!((t.first_parameter(), t.last_parameter()), (0.5, 3.0));
+        assert_eq!(t.knots(), vec![0.5, 1.0, 2.5, 3.0]);
+        assert_eq!(t.multiplicities(), vec![4, 1, 1, 4]);
+        for u in [0.5, 0.9, 1.0, 2.0, 2.7, 3.0] {
+            assert_point_eq(t.point_at(u), c.point_at(u));
+        }
+        // ノット上での切り出しと有理曲線
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 1.0).unwrap();
+        let nurbs = BSplineCurve::from_circle(&circle);
+        let parts = nurbs.split_at(&[FRAC_PI_2, 2.0]).unwrap();
+        assert_eq!(parts.len(), 3);
+        for part in &parts {
+            for i in 0..=10 {
+                let u = part.first_parameter()
+                    + (part.last_parameter() - part.first_parameter()) * i as f64 / 10.0;
+                assert_point_eq(part.point_at(u), nurbs.point_at(u));
+            }
+        }
+        assert_eq!(parts[0].control_points().len(), 3);
+        assert!(c.trim(-1.0, 1.0).is_err());
+        assert!(c.split_at(&[2.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_refine_and_remove_knots() {
+        let c = sample();
+        let mut refined = c.clone();
+        refined.refine_knots(&[0.5, 1.0, 3.0, 3.0]).unwrap();
+        assert_eq!(refined.multiplicities(), vec![4, 1, 2, 1, 2, 4]);
+        for u in [0.0, 0.7, 1.8, 3.0, 3.9] {
+            assert_point_eq(refined.point_at(u), c.point_at(u));
+        }
+        // 失敗した場合は変更しない
+        let before = refined.clone();
+        assert!(refined.refine_knots(&[2.0, 3.0, 3.0]).is_err());
+        assert_eq!(refined, before);
+
+        // 挿入したノットは形状を変えずに取り除ける
+        assert_eq!(refined.remove_knot(3.0, 2, 1e-9).unwrap(), 2);
+        assert_eq!(refined.remove_knot(0.5, 5, 1e-9).unwrap(), 1);
+        assert_eq!(refined.remove_knot(1.0, 1, 1e-9).unwrap(), 1);
+        assert_eq!(refined.flat_knots(), c.flat_knots());
+        for (a, b) in refined.control_points().iter().zip(c.control_points()) {
+            assert_point_eq(*a, *b);
+        }
+        // 元からあるノットは許容誤差内では取り除けない
+        let mut c2 = c.clone();
+        assert_eq!(c2.remove_knot(1.0, 1, 1e-9).unwrap(), 0);
+        assert_eq!(c2, c);
+        assert!(c2.remove_knot(0.0, 1, 1.0).is_err());
+        assert!(c2.remove_knot(2.0, 1, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_remove_knot_rational() {
+        let circle = Circle::new(Axis2::world(), 2.0).unwrap();
+        let c = BSplineCurve::from_circle(&circle);
+        let mut refined = c.clone();
+        refined.insert_knot(1.0, 1).unwrap();
+        assert_eq!(refined.remove_knot(1.0, 1, 1e-9).unwrap(), 1);
+        for (i, p) in c.control_points().iter().enumerate() {
+            assert_point_eq(refined.control_points()[i], *p);
+            assert!((refined.weight(i) - c.weight(i)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_elevate_degree() {
+        let c = sample();
+        let e = c.elevate_degree(2);
+        assert_eq!(e.degree(), 5);
+        assert_eq!(e.multiplicities(), vec![6, 3, 3, 6]);
+        assert_eq!(e.first_parameter(), 0.0);
+        assert_eq!(e.last_parameter(), 4.0);
+        for u in [0.0, 0.4, 1.0, 2.2, 3.7, 4.0] {
+            assert_point_eq(e.point_at(u), c.point_at(u));
+        }
+        assert_eq!(c.elevate_degree(0), c);
+
+        let circle = Circle::new(Axis2::world(), 1.5).unwrap();
+        let nurbs = BSplineCurve::from_circle(&circle);
+        let r = nurbs.elevate_degree(1);
+        assert_eq!(r.degree(), 3);
+        assert!(r.is_rational());
+        for u in [0.3, 1.9, 4.0, 6.0] {
+            assert_point_eq(r.point_at(u), nurbs.point_at(u));
+            assert!((r.point_at(u).distance(Point3::ORIGIN) - 1.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_reduce_degree() {
+        let c = sample();
+        let back = c.elevate_degree(1).reduce_degree(1e-9).unwrap();
+        assert_eq!(back.degree(), 3);
+        for u in [0.0, 0.4, 1.0, 2.2, 3.7, 4.0] {
+            assert_point_eq(back.point_at(u), c.point_at(u));
+        }
+        // 本来の3次曲線は2次では表せない
+        assert!(matches!(
+            c.reduce_degree(1e-3),
+            Err(OcctKrsError::ToleranceExceeded { .. })
+        ));
+        let rough = c.reduce_degree(10.0).unwrap();
+        assert_eq!(rough.degree(), 2);
+        assert_point_eq(rough.start_point(), c.start_point());
+        assert_point_eq(rough.end_point(), c.end_point());
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        assert!(BSplineCurve::from_circle(&circle)
+            .reduce_degree(1.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_reparametrized_keeps_shape() {
+        let c = sample();
+        let r = c.reparametrized(-1.0, 1.0).unwrap();
+        assert_eq!((r.first_parameter(), r.last_parameter()), (-1.0, 1.0));
+        for t in [0.0, 0.3, 0.8, 1.0] {
+            assert_point_eq(r.point_at(-1.0 + 2.0 * t), c.point_at(4.0 * t));
+        }
+        assert!(c.reparametrized(1.0, 1.0).is_err());
+    }
+}
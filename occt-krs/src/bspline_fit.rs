@@ -0,0 +1,553 @@
+use crate::bspline::basis_function_derivatives;
+use crate::{BSplineCurve, OcctKrsError, Point3, Result, Vector3};
+
+/// 3次スプライン補間の端点条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EndConditions {
+    /// 両端で2階微分をゼロにする（自然スプライン）
+    Natural,
+    /// 両端の接ベクトル（弦長パラメータについての1階微分）を指定する
+    Tangents { start: Vector3, end: Vector3 },
+    /// 終点から始点へ戻る閉曲線にする（全体で C2 連続）
+    Periodic,
+}
+
+/// 最小二乗近似の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Approximation {
+    /// 近似曲線
+    pub curve: BSplineCurve,
+    /// 各点と、その点に対応するパラメータでの曲線上の点との距離の最大値
+    pub max_error: f64,
+}
+
+impl BSplineCurve {
+    /// 点列を通る C2 連続な3次 B-スプライン曲線を生成する
+    ///
+    /// パラメータは累積弦長で、`points[k]` を通るパラメータが `k` 番目までの弦長の和になる。
+    /// `Periodic` の場合、点列に始点を重ねて含める必要はない。
+    /// 点が不足している、または隣り合う点が一致する場合はエラーを返す。
+    pub fn interpolate(points: &[Point3], conditions: EndConditions) -> Result<BSplineCurve> {
+        if conditions == EndConditions::Periodic {
+            return interpolate_periodic(points);
+        }
+        let params = chord_parameters(points, false)?;
+        let n = points.len() - 1;
+        let mut knots = vec![params[0]; 4];
+        knots.extend_from_slice(&params[1..n]);
+        knots.extend(std::iter::repeat_n(params[n], 4));
+
+        // 未知数は P1..P(n+1)（P0 と P(n+2) は両端点に一致する）
+        let (first, last) = (points[0].to_vector(), points[n].to_vector());
+        let mut system = Tridiagonal::new(n + 1);
+        let (order, start_rhs, end_rhs) = match conditions {
+            EndConditions::Tangents { start, end } => (1, start, end),
+            _ => (2, Vector3::ZERO, Vector3::ZERO),
+        };
+        let start = basis_function_derivatives(&knots, 3, params[0], 3, order);
+        system.set_row(
+            0,
+            [0.0, start[order][1], start[order][2]],
+            start_rhs - first * start[order][0],
+        );
+        for k in 1..n {
+            let b = basis_function_derivatives(&knots, k + 3, params[k], 3, 0);
+            system.set_row(k, [b[0][0], b[0][1], b[0][2]], points[k].to_vector());
+        }
+        let end = basis_function_derivatives(&knots, n + 2, params[n], 3, order);
+        system.set_row(
+            n,
+            [end[order][1], end[order][2], 0.0],
+            end_rhs - last * end[order][3],
+        );
+        let inner = system.solve().ok_or_else(singular_system)?;
+
+        let mut control_points = vec![points[0]];
+        control_points.extend(inner.into_iter().map(Point3::from));
+        control_points.push(points[n]);
+        BSplineCurve::from_flat_knots(3, control_points, knots)
+    }
+
+    /// 点列を許容誤差以内で近似する B-スプライン曲線を最小二乗法で生成する
+    ///
+    /// 次数は `max_degree` と `points.len() - 1` の小さい方。
+    /// 両端点は厳密に通り、制御点を増やしながら誤差が `tolerance` 以下になるまで近似を繰り返す。
+    /// 誤差は各点の弦長パラメータでの距離で測るため、曲線までの真の距離の上限になる。
+    /// 制御点数が点の数に達しても満たせない場合は `ToleranceExceeded` を返す。
+    pub fn approximate(
+        points: &[Point3],
+        max_degree: usize,
+        tolerance: f64,
+    ) -> Result<Approximation> {
+        if max_degree == 0 {
+            return Err(OcctKrsError::InvalidInput(
+                "次数は1以上である必要があります".to_string(),
+            ));
+        }
+        if !(tolerance >= 0.0 && tolerance.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "許容誤差が不正です: {}",
+                tolerance
+            )));
+        }
+        let params = chord_parameters(points, false)?;
+        let degree = max_degree.min(points.len() - 1);
+        let mut count = degree + 1;
+        loop {
+            let curve = fit_least_squares(points, &params, degree, count)?;
+            let max_error = points
+                .iter()
+                .zip(&params)
+                .map(|(q, t)| curve.point_at(*t).distance(*q))
+                .fold(0.0, f64::max);
+            if max_error <= tolerance {
+                return Ok(Approximation { curve, max_error });
+            }
+            if count == points.len() {
+                return Err(OcctKrsError::ToleranceExceeded {
+                    tolerance,
+                    deviation: max_error,
+                });
+            }
+            count = (count + count.div_ceil(2)).min(points.len());
+        }
+    }
+}
+
+/// 制御点数 `count` で、両端点を固定した最小二乗近似を行う（NURBS Book 9.4.1）
+pub(crate) fn fit_least_squares(
+    points: &[Point3],
+    params: &[f64],
+    degree: usize,
+    count: usize,
+) -> Result<BSplineCurve> {
+    let m = points.len() - 1;
+    let n = count - 1;
+    let p = degree;
+    let mut knots = vec![params[0]; p + 1];
+    if n == m {
+        // 点の数と制御点の数が等しい場合は補間になるので、パラメータの平均をノットにする
+        for j in 1..=n - p {
+            knots.push(params[j..j + p].iter().sum::<f64>() / p as f64);
+        }
+    } else {
+        // 各ノット区間に少なくとも1つのパラメータが含まれるように配置する
+        let d = (m + 1) as f64 / (n - p + 1) as f64;
+        for j in 1..=n - p {
+            let i = (j as f64 * d).floor() as usize;
+            let alpha = j as f64 * d - i as f64;
+            knots.push((1.0 - alpha) * params[i - 1] + alpha * params[i]);
+        }
+    }
+    knots.extend(std::iter::repeat_n(params[m], p + 1));
+
+    let (first, last) = (points[0], points[m]);
+    let mut control_points = vec![first];
+    if n > 1 {
+        // 未知数は P1..P(n-1)。正規方程式 (NᵀN) P = Nᵀ R を組み立てる
+        let unknowns = n - 1;
+        let mut normal = vec![vec![0.0; unknowns]; unknowns];
+        let mut rhs = vec![Vector3::ZERO; unknowns];
+        for k in 1..m {
+            let span = knots[..=n].partition_point(|u| *u <= params[k]) - 1;
+            let basis = &basis_function_derivatives(&knots, span, params[k], p, 0)[0];
+            let coef = |i: usize| -> f64 {
+                if i + p < span || i > span {
+                    0.0
+                } else {
+                    basis[i + p - span]
+                }
+            };
+            let r =
+                points[k].to_vector() - first.to_vector() * coef(0) - last.to_vector() * coef(n);
+            for a in span.saturating_sub(p).max(1)..=span.min(n - 1) {
+                rhs[a - 1] += r * coef(a);
+                for b in span.saturating_sub(p).max(1)..=span.min(n - 1) {
+                    normal[a - 1][b - 1] += coef(a) * coef(b);
+                }
+            }
+        }
+        let inner = solve_dense(normal, rhs).ok_or_else(singular_system)?;
+        control_points.extend(inner.into_iter().map(Point3::from));
+    }
+    control_points.push(last);
+    BSplineCurve::from_flat_knots(p, control_points, knots)
+}
+
+/// 閉曲線の補間（巡回三重対角系を解く）
+fn interpolate_periodic(points: &[Point3]) -> Result<BSplineCurve> {
+    if points.len() < 3 {
+        return Err(OcctKrsError::InvalidInput(
+            "閉曲線の補間には3点以上が必要です".to_string(),
+        ));
+    }
+    let params = chord_parameters(points, true)?;
+    let n = points.len();
+    let period = params[n];
+    // ノットを周期的に延長する: u_i = t_(i-3)
+    let knot = |i: isize| -> f64 {
+        let j = i - 3;
+        let shift = j.div_euclid(n as isize);
+        params[j.rem_euclid(n as isize) as usize] + period * shift as f64
+    };
+    let knots: Vec<f64> = (0..(n + 7) as isize).map(knot).collect();
+
+    // Y_k = P_(k+1) とすると、k 番目の点の条件は a Y_(k-1) + b Y_k + c Y_(k+1) = Q_k
+    let mut system = Tridiagonal::new(n);
+    for k in 0..n {
+        let b = basis_function_derivatives(&knots, k + 3, params[k], 3, 0);
+        system.set_row(k, [b[0][0], b[0][1], b[0][2]], points[k].to_vector());
+    }
+    let y = system.solve_cyclic().ok_or_else(singular_system)?;
+    // P_j = Y_(j-1)、末尾の3点は先頭の3点と一致させる
+    let mut control_points: Vec<Point3> =
+        (0..n).map(|j| Point3::from(y[(j + n - 1) % n])).collect();
+    control_points.extend_from_within(0..3);
+    BSplineCurve::from_flat_knots(3, control_points, knots)
+}
+
+/// 累積弦長パラメータ（`closed` の場合は始点へ戻る弦も含めた n + 1 個）を返す
+pub(crate) fn chord_parameters(points: &[Point3], closed: bool) -> Result<Vec<f64>> {
+    if points.len() < 2 {
+        return Err(OcctKrsError::InvalidInput(
+            "補間には2点以上が必要です".to_string(),
+        ));
+    }
+    let mut params = vec![0.0];
+    let mut add = |a: Point3, b: Point3| -> Result<()> {
+        let d = a.distance(b);
+        if d <= f64::MIN_POSITIVE {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "隣り合う点が一致しています".to_string(),
+            ));
+        }
+        params.push(params.last().unwrap() + d);
+        Ok(())
+    };
+    for w in points.windows(2) {
+        add(w[0], w[1])?;
+    }
+    if closed {
+        add(points[points.len() - 1], points[0])?;
+    }
+    Ok(params)
+}
+
+fn singular_system() -> OcctKrsError {
+    OcctKrsError::DegenerateGeometry("連立方程式が解けません".to_string())
+}
+
+/// 右辺がベクトルの三重対角連立方程式
+struct Tridiagonal {
+    lower: Vec<f64>,
+    diag: Vec<f64>,
+    upper: Vec<f64>,
+    rhs: Vec<Vector3>,
+}
+
+impl Tridiagonal {
+    fn new(n: usize) -> Self {
+        Self {
+            lower: vec![0.0; n],
+            diag: vec![0.0; n],
+            upper: vec![0.0; n],
+            rhs: vec![Vector3::ZERO; n],
+        }
+    }
+
+    /// i 行目の (i-1, i, i+1) 列の係数と右辺を設定する（巡回系では端が反対側の列になる）
+    fn set_row(&mut self, i: usize, coefs: [f64; 3], rhs: Vector3) {
+        self.lower[i] = coefs[0];
+        self.diag[i] = coefs[1];
+        self.upper[i] = coefs[2];
+        self.rhs[i] = rhs;
+    }
+
+    /// Thomas 法で解く（ピボットがゼロになる場合は `None`）
+    fn solve(&self) -> Option<Vec<Vector3>> {
+        solve_tridiagonal(&self.lower, &self.diag, &self.upper, &self.rhs)
+    }
+
+    /// 巡回三重対角系を Sherman–Morrison の公式で解く
+    fn solve_cyclic(&self) -> Option<Vec<Vector3>> {
+        let n = self.diag.len();
+        let alpha = self.upper[n - 1];
+        let beta = self.lower[0];
+        let gamma = -self.diag[0];
+        let mut diag = self.diag.clone();
+        diag[0] -= gamma;
+        diag[n - 1] -= alpha * beta / gamma;
+        let x = solve_tridiagonal(&self.lower, &diag, &self.upper, &self.rhs)?;
+        let mut u = vec![Vector3::ZERO; n];
+        u[0] = Vector3::ONE * gamma;
+        u[n - 1] = Vector3::ONE * alpha;
+        let z = solve_tridiagonal(&self.lower, &diag, &self.upper, &u)?;
+        // u の各成分は等しいので z も各成分が等しい（x 成分をスカラーとして使う）
+        let fact = (x[0] + x[n - 1] * (beta / gamma)) / (1.0 + z[0].x + beta * z[n - 1].x / gamma);
+        Some(x.iter().zip(&z).map(|(xi, zi)| *xi - fact * zi.x).collect())
+    }
+}
+
+/// 部分ピボット選択付きのガウスの消去法で密な連立方程式を解く
+pub(crate) fn solve_dense(mut a: Vec<Vec<f64>>, mut rhs: Vec<Vector3>) -> Option<Vec<Vector3>> {
+    let n = rhs.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= f64::MIN_POSITIVE {
+            return None;
+        }
+        a.swap(col, pivot);
+        rhs.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let f = row[col] / pivot_row[col];
+            if f == 0.0 {
+                continue;
+            }
+            for (x, y) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= f * y;
+            }
+            let r = rhs[col] * f;
+            rhs[col + 1 + offset] -= r;
+        }
+    }
+    for row in (0..n).rev() {
+        let mut v = rhs[row];
+        for (k, coef) in a[row].iter().enumerate().skip(row + 1) {
+            v -= rhs[k] * *coef;
+        }
+        rhs[row] = v / a[row][row];
+    }
+    Some(rhs)
+}
+
+fn solve_tridiagonal(
+    lower: &[f64],
+    diag: &[f64],
+    upper: &[f64],
+    rhs: &[Vector3],
+) -> Option<Vec<Vector3>> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut d = vec![Vector3::ZERO; n];
+    let mut denom = diag[0];
+    if denom.abs() <= f64::MIN_POSITIVE {
+        return None;
+    }
+    c[0] = upper[0] / denom;
+    d[0] = rhs[0] / denom;
+    for i in 1..n {
+        denom = diag[i] - lower[i] * c[i - 1];
+        if denom.abs() <= f64::MIN_POSITIVE {
+            return None;
+        }
+        c[i] = upper[i] / denom;
+        d[i] = (rhs[i] - d[i - 1] * lower[i]) / denom;
+    }
+    for i in (0..n - 1).rev() {
+        d[i] = d[i] - d[i + 1] * c[i];
+    }
+    Some(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Curve3;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn assert_vector_eq(a: Vector3, b: Vector3, tol: f64) {
+        assert!((a - b).length() < tol, "{:?} != {:?}", a, b);
+    }
+
+    fn sample_points() -> Vec<Point3> {
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(3.0, 1.0, 1.0),
+            Point3::new(4.0, 3.0, 0.5),
+            Point3::new(6.0, 2.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_interpolate_passes_through_points() {
+        let points = sample_points();
+        let params = chord_parameters(&points, false).unwrap();
+        for conditions in [
+            EndConditions::Natural,
+            EndConditions::Tangents {
+                start: Vector3::X,
+                end: Vector3::Y,
+            },
+        ] {
+            let c = BSplineCurve::interpolate(&points, conditions).unwrap();
+            assert_eq!(c.degree(), 3);
+            assert_eq!(c.control_points().len(), points.len() + 2);
+            for (p, t) in points.iter().zip(&params) {
+                assert_point_eq(c.point_at(*t), *p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolate_is_c2_at_knots() {
+        let points = sample_points();
+        let c = BSplineCurve::interpolate(&points, EndConditions::Natural).unwrap();
+        let params = chord_parameters(&points, false).unwrap();
+        let h = 1e-7;
+        for t in &params[1..params.len() - 1] {
+            let left = c.derivatives_at(t - h, 2);
+            let right = c.derivatives_at(t + h, 2);
+            assert_vector_eq(left[1], right[1], 1e-5);
+            assert_vector_eq(left[2], right[2], 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_end_conditions() {
+        let points = sample_points();
+        let natural = BSplineCurve::interpolate(&points, EndConditions::Natural).unwrap();
+        let (t0, t1) = (natural.first_parameter(), natural.last_parameter());
+        assert_vector_eq(natural.derivatives_at(t0, 2)[2], Vector3::ZERO, 1e-9);
+        assert_vector_eq(natural.derivatives_at(t1, 2)[2], Vector3::ZERO, 1e-9);
+
+        let start = Vector3::new(0.0, 1.0, 0.0);
+        let end = Vector3::new(1.0, 0.0, -1.0);
+        let c = BSplineCurve::interpolate(&points, EndConditions::Tangents { start, end }).unwrap();
+        assert_vector_eq(c.derivative_at(t0), start, 1e-9);
+        assert_vector_eq(c.derivative_at(t1), end, 1e-9);
+    }
+
+    #[test]
+    fn test_interpolate_two_points_is_line() {
+        let a = Point3::new(1.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 4.0, 0.0);
+        let c = BSplineCurve::interpolate(&[a, b], EndConditions::Natural).unwrap();
+        assert_point_eq(c.point_at(2.0), Point3::new(1.0, 2.0, 0.0));
+        assert!((c.arc_length() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_interpolate_periodic() {
+        let points: Vec<Point3> = (0..6)
+            .map(|k| {
+                let a = k as f64 * std::f64::consts::TAU / 6.0;
+                Point3::new(2.0 * a.cos(), a.sin(), 0.1 * k as f64)
+            })
+            .collect();
+        let c = BSplineCurve::interpolate(&points, EndConditions::Periodic).unwrap();
+        let params = chord_parameters(&points, true).unwrap();
+        for (p, t) in points.iter().zip(&params) {
+            assert_point_eq(c.point_at(*t), *p);
+        }
+        assert!(c.is_closed());
+        let (t0, t1) = (c.first_parameter(), c.last_parameter());
+        assert_eq!(t0, 0.0);
+        assert!((t1 - params[6]).abs() < 1e-12);
+        let start = c.derivatives_at(t0, 2);
+        let end = c.derivatives_at(t1, 2);
+        assert_point_eq(Point3::from(end[0]), points[0]);
+        assert_vector_eq(start[1], end[1], 1e-9);
+        assert_vector_eq(start[2], end[2], 1e-9);
+    }
+
+    #[test]
+    fn test_interpolate_rejects_invalid_input() {
+        let p = Point3::new(1.0, 1.0, 1.0);
+        assert!(BSplineCurve::interpolate(&[p], EndConditions::Natural).is_err());
+        assert!(matches!(
+            BSplineCurve::interpolate(&[Point3::ORIGIN, p, p], EndConditions::Natural),
+            Err(OcctKrsError::DegenerateGeometry(_))
+        ));
+        assert!(BSplineCurve::interpolate(&[Point3::ORIGIN, p], EndConditions::Periodic).is_err());
+        // 閉じた点列の終点が始点と重なる場合も退化とみなす
+        assert!(BSplineCurve::interpolate(
+            &[
+                Point3::ORIGIN,
+                p,
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::ORIGIN
+            ],
+            EndConditions::Periodic
+        )
+        .is_err());
+    }
+
+    fn sine_points(count: usize) -> Vec<Point3> {
+        (0..count)
+            .map(|k| {
+                let x = k as f64 * 0.1;
+                Point3::new(x, x.sin(), 0.2 * x)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_approximate_within_tolerance() {
+        let points = sine_points(80);
+        let result = BSplineCurve::approximate(&points, 3, 1e-4).unwrap();
+        assert!(result.max_error <= 1e-4);
+        assert_eq!(result.curve.degree(), 3);
+        // 点の数より十分少ない制御点で表せる
+        assert!(result.curve.control_points().len() < points.len() / 2);
+        assert_point_eq(result.curve.start_point(), points[0]);
+        assert_point_eq(result.curve.end_point(), points[79]);
+        let params = chord_parameters(&points, false).unwrap();
+        for (q, t) in points.iter().zip(&params) {
+            assert!(result.curve.point_at(*t).distance(*q) <= result.max_error + 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_approximate_tighter_tolerance_uses_more_poles() {
+        let points = sine_points(60);
+        let coarse = BSplineCurve::approximate(&points, 3, 1e-2).unwrap();
+        let fine = BSplineCurve::approximate(&points, 3, 1e-6).unwrap();
+        assert!(fine.curve.control_points().len() > coarse.curve.control_points().len());
+        assert!(fine.max_error <= 1e-6);
+    }
+
+    #[test]
+    fn test_approximate_collinear_points_is_exact() {
+        let points: Vec<Point3> = (0..10)
+            .map(|k| Point3::new(k as f64, 2.0 * k as f64, 0.0))
+            .collect();
+        let result = BSplineCurve::approximate(&points, 3, 1e-9).unwrap();
+        assert!(result.max_error < 1e-9);
+        assert_eq!(result.curve.control_points().len(), 4);
+    }
+
+    #[test]
+    fn test_approximate_degree_is_limited_by_point_count() {
+        let points = [
+            Point3::ORIGIN,
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+        ];
+        let result = BSplineCurve::approximate(&points, 5, 0.0).unwrap();
+        assert_eq!(result.curve.degree(), 2);
+        assert!(result.max_error < 1e-12);
+    }
+
+    #[test]
+    fn test_approximate_rejects_invalid_input() {
+        let points = sine_points(10);
+        assert!(BSplineCurve::approximate(&points, 0, 1e-3).is_err());
+        assert!(BSplineCurve::approximate(&points, 3, -1.0).is_err());
+        assert!(BSplineCurve::approximate(&points, 3, f64::NAN).is_err());
+        assert!(BSplineCurve::approximate(&points[..1], 3, 1e-3).is_err());
+        // 次数1でも制御点を点の数まで増やせば折れ線を再現できる
+        let zigzag: Vec<Point3> = (0..6)
+            .map(|k| Point3::new(k as f64, (k % 2) as f64, 0.0))
+            .collect();
+        assert!(matches!(
+            BSplineCurve::approximate(&zigzag, 1, 0.0),
+            Ok(Approximation { max_error, .. }) if max_error < 1e-12
+        ));
+    }
+}
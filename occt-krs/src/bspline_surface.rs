@@ -0,0 +1,403 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::bspline::{basis_function_derivatives, find_span};
+use crate::{OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// B-スプライン曲面（OCCT の `Geom_BSplineSurface` 相当、非周期）
+///
+/// 制御点は `control_points[i][j]`（i が u 方向、j が v 方向）の格子で保持する。
+/// 重みを持つ場合は有理 B-スプライン曲面（NURBS）となる。
+/// ノットは曲線と同じくフラットノットで、u 方向の数は `u方向の制御点数 + u次数 + 1` になる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BSplineSurface {
+    u_degree: usize,
+    v_degree: usize,
+    control_points: Vec<Vec<Point3>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    weights: Option<Vec<Vec<f64>>>,
+    u_knots: Vec<f64>,
+    v_knots: Vec<f64>,
+}
+
+/// 1方向のノット列を検証する
+fn check_knots(direction: &str, degree: usize, count: usize, knots: &[f64]) -> Result<()> {
+    if degree == 0 || count < degree + 1 {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "{} 方向の次数 {} には {} 個以上の制御点が必要です（{} 個）",
+            direction,
+            degree,
+            degree + 1,
+            count
+        )));
+    }
+    if knots.len() != count + degree + 1 {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "{} 方向のノット数 {} が 制御点数 + 次数 + 1 = {} と一致しません",
+            direction,
+            knots.len(),
+            count + degree + 1
+        )));
+    }
+    if knots.iter().any(|k| !k.is_finite()) || knots.windows(2).any(|w| w[0] > w[1]) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "{} 方向のノット列は有限かつ単調非減少である必要があります",
+            direction
+        )));
+    }
+    if knots[degree] >= knots[count] {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "{} 方向のパラメータ範囲が空です",
+            direction
+        )));
+    }
+    Ok(())
+}
+
+impl BSplineSurface {
+    /// 次数・制御点の格子・フラットノットから曲面を生成する
+    ///
+    /// 制御点が長方形の格子でない、ノット列の長さが合わない、単調非減少でない場合などはエラーを返す。
+    pub fn from_flat_knots(
+        u_degree: usize,
+        v_degree: usize,
+        control_points: Vec<Vec<Point3>>,
+        u_knots: Vec<f64>,
+        v_knots: Vec<f64>,
+    ) -> Result<Self> {
+        let nv = control_points.first().map_or(0, |row| row.len());
+        if control_points.iter().any(|row| row.len() != nv) {
+            return Err(OcctKrsError::InvalidInput(
+                "制御点は長方形の格子である必要があります".to_string(),
+            ));
+        }
+        check_knots("u", u_degree, control_points.len(), &u_knots)?;
+        check_knots("v", v_degree, nv, &v_knots)?;
+        Ok(Self {
+            u_degree,
+            v_degree,
+            control_points,
+            weights: None,
+            u_knots,
+            v_knots,
+        })
+    }
+
+    /// 各制御点に重みを与えた有理曲面を返す
+    /// 重みの格子が制御点と一致しない、または正の有限値でない重みがある場合はエラーを返す
+    pub fn with_weights(mut self, weights: Vec<Vec<f64>>) -> Result<Self> {
+        if weights.len() != self.control_points.len()
+            || weights
+                .iter()
+                .zip(&self.control_points)
+                .any(|(w, p)| w.len() != p.len())
+        {
+            return Err(OcctKrsError::InvalidInput(
+                "重みの格子が制御点の格子と一致しません".to_string(),
+            ));
+        }
+        if weights
+            .iter()
+            .flatten()
+            .any(|w| !(*w > 0.0 && w.is_finite()))
+        {
+            return Err(OcctKrsError::InvalidInput(
+                "重みは正の有限値である必要があります".to_string(),
+            ));
+        }
+        self.weights = Some(weights);
+        Ok(self)
+    }
+
+    /// u 方向の次数を返す
+    pub fn u_degree(&self) -> usize {
+        self.u_degree
+    }
+
+    /// v 方向の次数を返す
+    pub fn v_degree(&self) -> usize {
+        self.v_degree
+    }
+
+    /// 制御点の格子を返す
+    pub fn control_points(&self) -> &[Vec<Point3>] {
+        &self.control_points
+    }
+
+    /// 重みの格子を返す（非有理の場合は `None`）
+    pub fn weights(&self) -> Option<&[Vec<f64>]> {
+        self.weights.as_deref()
+    }
+
+    /// 有理曲面かどうかを返す
+    pub fn is_rational(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    /// `(i, j)` 番目の制御点の重みを返す（非有理の場合は 1）
+    pub fn weight(&self, i: usize, j: usize) -> f64 {
+        self.weights.as_ref().map_or(1.0, |w| w[i][j])
+    }
+
+    /// u 方向のフラットノットを返す
+    pub fn u_flat_knots(&self) -> &[f64] {
+        &self.u_knots
+    }
+
+    /// v 方向のフラットノットを返す
+    pub fn v_flat_knots(&self) -> &[f64] {
+        &self.v_knots
+    }
+
+    /// u のパラメータ範囲を返す
+    pub(crate) fn u_bounds(&self) -> (f64, f64) {
+        (
+            self.u_knots[self.u_degree],
+            self.u_knots[self.control_points.len()],
+        )
+    }
+
+    /// v のパラメータ範囲を返す
+    pub(crate) fn v_bounds(&self) -> (f64, f64) {
+        (
+            self.v_knots[self.v_degree],
+            self.v_knots[self.control_points[0].len()],
+        )
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        Point3::from(self.derivatives_at(u, v, 0)[0][0])
+    }
+
+    /// パラメータ `(u, v)` における `k + l <= order` までの偏微分を返す（The NURBS Book の A3.6, A4.4）
+    ///
+    /// 返り値 `[k][l]` は u で k 回、v で l 回微分したもの。`[0][0]` は曲面上の点の位置ベクトル。
+    pub fn derivatives_at(&self, u: f64, v: f64, order: usize) -> Vec<Vec<Vector3>> {
+        let (p, q) = (self.u_degree, self.v_degree);
+        let (nu, nv) = (self.control_points.len(), self.control_points[0].len());
+        let u_span = find_span(&self.u_knots, nu - 1, p, u);
+        let v_span = find_span(&self.v_knots, nv - 1, q, v);
+        let du = order.min(p);
+        let dv = order.min(q);
+        let bu = basis_function_derivatives(&self.u_knots, u_span, u, p, du);
+        let bv = basis_function_derivatives(&self.v_knots, v_span, v, q, dv);
+
+        // 同次座標での偏微分
+        let mut a = vec![vec![Vector3::ZERO; order + 1]; order + 1];
+        let mut w = vec![vec![0.0; order + 1]; order + 1];
+        for (k, row_u) in bu.iter().enumerate() {
+            let mut temp = vec![(Vector3::ZERO, 0.0); q + 1];
+            for (s, t) in temp.iter_mut().enumerate() {
+                for (r, &b) in row_u.iter().enumerate() {
+                    let (i, j) = (u_span - p + r, v_span - q + s);
+                    let wij = self.weight(i, j);
+                    t.0 += self.control_points[i][j].to_vector() * (wij * b);
+                    t.1 += wij * b;
+                }
+            }
+            for (l, row_v) in bv.iter().enumerate().take(order - k + 1) {
+                for (t, &b) in temp.iter().zip(row_v) {
+                    a[k][l] += t.0 * b;
+                    w[k][l] += t.1 * b;
+                }
+            }
+        }
+        if !self.is_rational() {
+            return a;
+        }
+
+        // 有理曲面の偏微分
+        let binom = |n: usize, k: usize| -> f64 {
+            (1..=k).fold(1.0, |acc, i| acc * (n + 1 - i) as f64 / i as f64)
+        };
+        let mut skl = vec![vec![Vector3::ZERO; order + 1]; order + 1];
+        for k in 0..=order {
+            for l in 0..=order - k {
+                let mut v = a[k][l];
+                for j in 1..=l {
+                    v -= skl[k][l - j] * (binom(l, j) * w[0][j]);
+                }
+                for i in 1..=k {
+                    v -= skl[k - i][l] * (binom(k, i) * w[i][0]);
+                    let mut v2 = Vector3::ZERO;
+                    for j in 1..=l {
+                        v2 += skl[k - i][l - j] * (binom(l, j) * w[i][j]);
+                    }
+                    v -= v2 * binom(k, i);
+                }
+                skl[k][l] = v / w[0][0];
+            }
+        }
+        skl
+    }
+
+    /// u の向きを反転した曲面を返す（パラメータ範囲は保たれる）
+    ///
+    /// u の範囲を `[a, b]` とすると、元の `(u, v)` の点は `(a + b - u, v)` の点になる。
+    pub fn u_reversed(&self) -> BSplineSurface {
+        let (a, b) = self.u_bounds();
+        let mut control_points = self.control_points.clone();
+        control_points.reverse();
+        let weights = self
+            .weights
+            .as_ref()
+            .map(|w| w.iter().rev().cloned().collect());
+        Self {
+            control_points,
+            weights,
+            u_knots: self.u_knots.iter().rev().map(|k| a + b - k).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// 変換を適用した曲面を返す
+    pub fn transformed(&self, t: &Transform) -> BSplineSurface {
+        Self {
+            control_points: self
+                .control_points
+                .iter()
+                .map(|row| row.iter().map(|p| t.transform_point(*p)).collect())
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, BSplineCurve, Circle, Surface};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    /// 3×4 の制御点を持つ (2, 3) 次の曲面
+    fn sample() -> BSplineSurface {
+        let grid = (0..3)
+            .map(|i| {
+                (0..4)
+                    .map(|j| Point3::new(i as f64, j as f64, ((i * j) % 3) as f64 * 0.5))
+                    .collect()
+            })
+            .collect();
+        BSplineSurface::from_flat_knots(
+            2,
+            3,
+            grid,
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            vec![0.0, 0.0, 0.0, 0.0, 2.0, 2.0, 2.0, 2.0],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_invalid_input_is_rejected() {
+        let s = sample();
+        let mut grid = s.control_points().to_vec();
+        grid[1].pop();
+        assert!(BSplineSurface::from_flat_knots(
+            2,
+            3,
+            grid,
+            s.u_flat_knots().to_vec(),
+            s.v_flat_knots().to_vec()
+        )
+        .is_err());
+        assert!(BSplineSurface::from_flat_knots(
+            2,
+            2,
+            s.control_points().to_vec(),
+            s.u_flat_knots().to_vec(),
+            s.v_flat_knots().to_vec()
+        )
+        .is_err());
+        assert!(s.clone().with_weights(vec![vec![1.0; 4]; 2]).is_err());
+        assert!(s.with_weights(vec![vec![-1.0; 4]; 3]).is_err());
+    }
+
+    #[test]
+    fn test_corners_and_ranges() {
+        let s = sample();
+        assert_eq!(s.u_range(), (0.0, 1.0));
+        assert_eq!(s.v_range(), (0.0, 2.0));
+        let g = s.control_points();
+        assert_point_eq(s.point_at(0.0, 0.0), g[0][0]);
+        assert_point_eq(s.point_at(1.0, 2.0), g[2][3]);
+        assert_point_eq(s.point_at(0.0, 2.0), g[0][3]);
+    }
+
+    #[test]
+    fn test_iso_curve_matches_bspline_curve() {
+        // u = 0 の等パラメータ線は最初の行の制御点による曲線
+        let s = sample();
+        let c = BSplineCurve::from_flat_knots(
+            3,
+            s.control_points()[0].clone(),
+            s.v_flat_knots().to_vec(),
+        )
+        .unwrap();
+        for v in [0.0, 0.3, 1.1, 2.0] {
+            assert_point_eq(s.point_at(0.0, v), c.point_at(v));
+        }
+    }
+
+    #[test]
+    fn test_derivatives_match_differences() {
+        let s = sample();
+        let mut weights = vec![vec![1.0; 4]; 3];
+        weights[1][2] = 3.0;
+        weights[2][1] = 0.5;
+        let r = sample().with_weights(weights).unwrap();
+        let h = 1e-5;
+        for surface in [&s, &r] {
+            let (u, v) = (0.35, 1.3);
+            let d = surface.derivatives_at(u, v, 2);
+            let du = (surface.point_at(u + h, v) - surface.point_at(u - h, v)) / (2.0 * h);
+            let dv = (surface.point_at(u, v + h) - surface.point_at(u, v - h)) / (2.0 * h);
+            assert!((d[1][0] - du).length() < 1e-7);
+            assert!((d[0][1] - dv).length() < 1e-7);
+            let duv = (surface.derivatives_at(u, v + h, 1)[1][0]
+                - surface.derivatives_at(u, v - h, 1)[1][0])
+                / (2.0 * h);
+            let duu = (surface.derivatives_at(u + h, v, 1)[1][0]
+                - surface.derivatives_at(u - h, v, 1)[1][0])
+                / (2.0 * h);
+            assert!((d[1][1] - duv).length() < 1e-6);
+            assert!((d[2][0] - duu).length() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_rational_surface_of_circles() {
+        // 円の NURBS を v 方向に平行移動して並べると円柱になる
+        let c = BSplineCurve::from_circle(&Circle::new(Axis2::world(), 2.0).unwrap());
+        let rows: Vec<Vec<Point3>> = c
+            .control_points()
+            .iter()
+            .map(|p| vec![*p, *p + Vector3::Z * 3.0])
+            .collect();
+        let weights = c.weights().unwrap().iter().map(|w| vec![*w, *w]).collect();
+        let s = BSplineSurface::from_flat_knots(
+            2,
+            1,
+            rows,
+            c.flat_knots().to_vec(),
+            vec![0.0, 0.0, 1.0, 1.0],
+        )
+        .unwrap()
+        .with_weights(weights)
+        .unwrap();
+        for &(u, v) in &[(0.3, 0.2), (4.0, 0.9)] {
+            let p = s.point_at(u, v);
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 2.0).abs() < 1e-12);
+            assert!((p.z - 3.0 * v).abs() < 1e-12);
+        }
+        assert!(s.is_u_closed() && !s.is_v_closed());
+    }
+}
@@ -0,0 +1,191 @@
+use std::f64::consts::PI;
+
+use crate::fillet::{apply_tools, edge_frames, EdgeFrame};
+use crate::{precision, Edge, Face, OcctKrsError, Result, Shape};
+
+/// 立体のエッジを両側のフェイスに沿って同じ距離だけ面取りする（OCCT の `BRepFilletAPI_MakeChamfer` 相当）
+///
+/// 稜線から両側のフェイスに沿って `distance` の位置を結ぶ平面（円の稜線では円錐面）で稜線を置き換える。
+/// 凸の稜線では材料を削り、凹の稜線では材料を足す。面取りした稜線が集まる角では、
+/// 隣り合う面取りの面どうしの交線で角を閉じる。対象の稜線は [`fillet`](crate::fillet) と同じ。
+///
+/// 距離が正の有限値でない場合や、対象の稜線が [`fillet`](crate::fillet) と同じ条件を満たさない場合は
+/// エラーを返す。
+pub fn chamfer(shape: &Shape, edges: &[Edge], distance: f64) -> Result<Shape> {
+    check_distance(distance)?;
+    let frames = edge_frames(shape, edges)?;
+    let lengths = vec![(distance, distance); frames.len()];
+    bevel(shape, &frames, &lengths)
+}
+
+/// 立体のエッジを、基準のフェイスに沿った距離と、そのフェイスとの角度で面取りする
+///
+/// `edges` はエッジと、その両側のフェイスのうち基準にするものの組。面取りの面は基準のフェイス上で
+/// 稜線から `distance` の位置を通り、基準のフェイスと角度 `angle`（ラジアン）をなす。
+/// 角度が 0 以下の場合や、面取りの面がもう一方のフェイスと交わらない角度の場合、
+/// 基準のフェイスがエッジに隣接していない場合は [`chamfer`] のエラーに加えてエラーを返す。
+pub fn chamfer_with_angle(
+    shape: &Shape,
+    edges: &[(Edge, Face)],
+    distance: f64,
+    angle: f64,
+) -> Result<Shape> {
+    check_distance(distance)?;
+    let (edges, references): (Vec<Edge>, Vec<Face>) = edges.iter().cloned().unzip();
+    let mut frames = edge_frames(shape, &edges)?;
+    let mut lengths = Vec::with_capacity(frames.len());
+    for (frame, reference) in frames.iter_mut().zip(&references) {
+        if frame.faces[1].is_same(reference) {
+            frame.swap_sides();
+        } else if !frame.faces[0].is_same(reference) {
+            return Err(OcctKrsError::InvalidInput(
+                "基準のフェイスがエッジに隣接していません".to_string(),
+            ));
+        }
+        // 稜線・基準側の点・もう一方の点の三角形で正弦定理を使う
+        let opening = frame.inwards[0].angle_between(frame.inwards[1]);
+        if !(angle > 0.0 && opening + angle < PI - precision::angular()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "面取りの角度が不正です: {}",
+                angle
+            )));
+        }
+        lengths.push((distance, distance * angle.sin() / (opening + angle).sin()));
+    }
+    bevel(shape, &frames, &lengths)
+}
+
+fn check_distance(distance: f64) -> Result<()> {
+    if !(distance > 0.0 && distance.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "面取りの距離が不正です: {}",
+            distance
+        )));
+    }
+    Ok(())
+}
+
+/// 稜線ごとに、両側のフェイスに沿った距離 `lengths` の点を結んで面取りする
+fn bevel(shape: &Shape, frames: &[EdgeFrame], lengths: &[(f64, f64)]) -> Result<Shape> {
+    let (mut convex, mut concave) = (Vec::new(), Vec::new());
+    for (frame, &(d1, d2)) in frames.iter().zip(lengths) {
+        let points = [
+            frame.point,
+            frame.point + frame.inwards[0] * d1,
+            frame.point + frame.inwards[1] * d2,
+        ];
+        let tool = frame.sweep_profile(&points, None, frame.extensions(d1 + d2, &[]))?;
+        if frame.convex {
+            convex.push(tool);
+        } else {
+            concave.push(tool);
+        }
+    }
+    apply_tools(shape, convex, concave)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Dir, GeomSurface, Point3};
+
+    fn cube() -> Shape {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap())
+    }
+
+    /// 頂点 `vertex` に集まるエッジ
+    fn edges_at(shape: &Shape, vertex: Point3) -> Vec<Edge> {
+        shape
+            .edges()
+            .into_iter()
+            .filter(|e| e.start_point() == vertex || e.end_point() == vertex)
+            .collect()
+    }
+
+    #[test]
+    fn test_chamfer_box() {
+        let cube = cube();
+        let edges = edges_at(&cube, Point3::new(1.0, 1.0, 1.0));
+        let vertical: Vec<Edge> = edges
+            .iter()
+            .filter(|e| e.start_point().z != e.end_point().z)
+            .cloned()
+            .collect();
+        let result = chamfer(&cube, &vertical, 0.5).unwrap();
+        assert_eq!(result.faces().len(), 7);
+        let volume = checked_volume(&result);
+        assert!((volume / 0.875 - 1.0).abs() < 1e-6, "{}", volume);
+        // 3本の稜線が集まる角
+        let d = 0.3;
+        let result = chamfer(&cube, &edges, d).unwrap();
+        assert_eq!(result.faces().len(), 9);
+        let volume = checked_volume(&result);
+        let expected = 1.0 - (1.5 * d * d - 0.75 * d * d * d);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        assert!(chamfer(&cube, &edges, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_chamfer_with_angle() {
+        let cube = cube();
+        let edge = edges_at(&cube, Point3::new(1.0, 1.0, 1.0))
+            .into_iter()
+            .find(|e| e.start_point().x != e.end_point().x)
+            .unwrap();
+        let top = cube
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Plane(p) if p.origin().z == 1.0))
+            .unwrap();
+        let angle = PI / 6.0;
+        let result = chamfer_with_angle(&cube, &[(edge.clone(), top.clone())], 0.5, angle).unwrap();
+        checked_volume(&result);
+        // 面取りの面は上面と 30° をなす
+        let bevel = result
+            .faces()
+            .into_iter()
+            .filter_map(|f| match f.surface() {
+                GeomSurface::Plane(p) => Some(p.normal()),
+                _ => None,
+            })
+            .find(|n| {
+                n.dot(Dir::X).abs() < 1e-9
+                    && n.dot(Dir::Z).abs() < 0.99
+                    && n.dot(Dir::Y).abs() < 0.99
+            })
+            .unwrap();
+        assert!((bevel.dot(Dir::Z).abs() - angle.cos()).abs() < 1e-9);
+        assert!(chamfer_with_angle(&cube, &[(edge.clone(), top.clone())], 0.5, 2.0).is_err());
+        let bottom = cube
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Plane(p) if p.origin().z == 0.0))
+            .unwrap();
+        assert!(chamfer_with_angle(&cube, &[(edge, bottom)], 0.5, angle).is_err());
+    }
+
+    #[test]
+    fn test_chamfer_circular_edge() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 1.0).unwrap());
+        let top = cylinder
+            .edges()
+            .into_iter()
+            .find(|e| e.is_closed() && e.start_point().z > 0.5)
+            .unwrap();
+        let d = 0.3;
+        let result = chamfer(&cylinder, &[top], d).unwrap();
+        let cones = result
+            .faces()
+            .iter()
+            .filter(|f| matches!(f.surface(), GeomSurface::Cone(_)))
+            .count();
+        assert_eq!(cones, 1);
+        let volume = checked_volume(&result);
+        let expected = PI - 2.0 * PI * (1.0 - d / 3.0) * d * d / 2.0;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+}
@@ -0,0 +1,489 @@
+use std::collections::HashMap;
+
+use crate::shape_builder::signed_area;
+use crate::topo::ShapeId;
+use crate::{
+    Edge, Face, GeomCurve, GeomSurface, Orientation, Shape, ShapeKind, Shell, Solid, Surface,
+    Vector2, Wire,
+};
+
+/// 曲線のエッジを調べるときの分割数
+const CHECK_SAMPLES: usize = 16;
+
+/// 形状の検査で見つかった不具合の種類（OCCT の `BRepCheck_Status` 相当）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    /// エッジが曲面から離れている
+    ///
+    /// pcurve 上の点（pcurve がなければ曲面への射影）との距離 `distance` がエッジの許容誤差を超える。
+    CurveOffSurface { distance: f64 },
+    /// 平面以外のフェイスの境界のエッジに pcurve が設定されていない
+    MissingPCurve,
+    /// ワイヤが曲面のパラメータ空間で閉じていない
+    ///
+    /// `gap` は隣り合う pcurve の端のずれを3次元の距離に換算したもので、頂点の許容誤差を超える。
+    OpenWire { gap: f64 },
+    /// ワイヤが曲面のパラメータ空間で自己交差している（`edges` は交差するエッジのたどる順の番号）
+    SelfIntersectingWire { edges: (usize, usize) },
+    /// 外側のワイヤが反時計回り、穴のワイヤが時計回りになっていない（パラメータ空間で見たとき）
+    BadWireOrientation,
+    /// 2枚のフェイスが共有するエッジを同じ向きで使っていて、フェイスの向きがそろっていない
+    InconsistentOrientation,
+    /// 立体の境界のシェルが閉じていない
+    ShellNotClosed,
+}
+
+/// 検査で見つかった1つの不具合
+#[derive(Debug, Clone)]
+pub struct CheckIssue {
+    /// 不具合のある部分形状
+    pub shape: Shape,
+    /// 不具合を見つけたときに調べていた上位の形状（エッジを含むフェイスなど）
+    pub parent: Option<Shape>,
+    /// 不具合の種類
+    pub status: CheckStatus,
+}
+
+/// 形状の検査結果（OCCT の `BRepCheck_Analyzer` 相当）
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    /// 不具合が見つからなければ `true` を返す
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// 見つかった不具合を返す
+    pub fn issues(&self) -> &[CheckIssue] {
+        &self.issues
+    }
+
+    /// 部分形状 `shape`（`is_same` で比較）の不具合を返す
+    pub fn issues_of<'a>(&'a self, shape: &'a Shape) -> impl Iterator<Item = &'a CheckIssue> {
+        self.issues.iter().filter(move |i| i.shape.is_same(shape))
+    }
+
+    fn push(&mut self, shape: impl Into<Shape>, parent: impl Into<Shape>, status: CheckStatus) {
+        self.issues.push(CheckIssue {
+            shape: shape.into(),
+            parent: Some(parent.into()),
+            status,
+        });
+    }
+}
+
+impl Shape {
+    /// 形状の妥当性を検査し、見つかった不具合を部分形状ごとに返す
+    ///
+    /// フェイスごとにエッジと曲面のずれ・pcurve の有無・ワイヤの閉じ方・自己交差・向きを、
+    /// シェルごとに隣り合うフェイスの向きの整合性を、立体ごとにシェルが閉じているかを調べる。
+    /// 曲線は等分点で調べるので、等分点の間だけで起きるずれや交差は見逃すことがある。
+    pub fn check(&self) -> CheckReport {
+        let mut report = CheckReport::default();
+        for face in self.faces() {
+            check_face(&face.oriented(Orientation::Forward), &mut report);
+        }
+        for shape in self.explore(ShapeKind::Shell).unique() {
+            if let Ok(shell) = Shell::try_from(shape) {
+                check_shell(&shell, &mut report);
+            }
+        }
+        for shape in self.explore(ShapeKind::Solid).unique() {
+            if let Ok(solid) = Solid::try_from(shape) {
+                check_solid(&solid, &mut report);
+            }
+        }
+        report
+    }
+}
+
+/// エッジのパラメータを、向きに沿ってたどる順に返す（直線は両端のみ）
+pub(crate) fn edge_parameters(edge: &Edge) -> Vec<f64> {
+    let (first, last) = edge.range();
+    let n = if matches!(edge.curve(), Some(GeomCurve::Line(_))) {
+        1
+    } else {
+        CHECK_SAMPLES
+    };
+    let mut params: Vec<f64> = (0..=n)
+        .map(|k| first + (last - first) * k as f64 / n as f64)
+        .collect();
+    if edge.orientation() == Orientation::Reversed {
+        params.reverse();
+    }
+    params
+}
+
+/// エッジを曲面のパラメータ空間へ移した折れ線（pcurve がなく平面でもなければ `None`）
+pub(crate) fn edge_uv(face: &Face, edge: &Edge, params: &[f64]) -> Option<Vec<Vector2>> {
+    if let Some(pcurve) = face.pcurve(edge) {
+        return Some(params.iter().map(|&t| pcurve.point_at(t)).collect());
+    }
+    let GeomSurface::Plane(plane) = face.surface() else {
+        return None;
+    };
+    let inverse = face.location().inverse()?;
+    Some(
+        params
+            .iter()
+            .map(|&t| {
+                let (u, v) = plane.parameters_of(inverse.apply(edge.point_at(t)));
+                Vector2::new(u, v)
+            })
+            .collect(),
+    )
+}
+
+fn check_face(face: &Face, report: &mut CheckReport) {
+    let surface = face.located_geometry();
+    let scale = face.location().transform().scale.abs();
+    for (index, wire) in face.wires().into_iter().enumerate() {
+        let edges = wire.edges();
+        let mut polylines = Vec::with_capacity(edges.len());
+        for edge in &edges {
+            let params = edge_parameters(edge);
+            let uv = edge_uv(face, edge, &params);
+            let distance = match (&uv, face.pcurve(edge)) {
+                (Some(uv), Some(_)) => params
+                    .iter()
+                    .zip(uv)
+                    .map(|(&t, p)| edge.point_at(t).distance(face.point_at(p.x, p.y)))
+                    .fold(0.0, f64::max),
+                _ => params
+                    .iter()
+                    .filter_map(|&t| surface.project(edge.point_at(t)))
+                    .map(|p| p.distance)
+                    .fold(0.0, f64::max),
+            };
+            if distance.is_nan() || distance > edge.tolerance() {
+                report.push(
+                    edge.clone(),
+                    face.clone(),
+                    CheckStatus::CurveOffSurface { distance },
+                );
+            }
+            if uv.is_none() {
+                report.push(edge.clone(), face.clone(), CheckStatus::MissingPCurve);
+            }
+            polylines.push(uv);
+        }
+        let Some(polylines) = polylines.into_iter().collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+        check_wire_uv(face, &wire, &edges, &polylines, scale, report);
+        let points: Vec<Vector2> = polylines
+            .iter()
+            .flat_map(|p| p[..p.len() - 1].iter().copied())
+            .collect();
+        let area = signed_area(&points);
+        if area != 0.0 && (area > 0.0) != (index == 0) {
+            report.push(wire.clone(), face.clone(), CheckStatus::BadWireOrientation);
+        }
+    }
+}
+
+/// パラメータ空間でのワイヤの閉じ方と自己交差を調べる
+fn check_wire_uv(
+    face: &Face,
+    wire: &Wire,
+    edges: &[Edge],
+    polylines: &[Vec<Vector2>],
+    scale: f64,
+    report: &mut CheckReport,
+) {
+    let surface = face.surface();
+    let n = edges.len();
+    for i in 0..n {
+        let j = (i + 1) % n;
+        let a = polylines[i][polylines[i].len() - 1];
+        let b = polylines[j][0];
+        let d = b - a;
+        // パラメータのずれを曲面の1階微分で3次元の距離に換算する
+        let gap = (surface.derivative_u_at(a.x, a.y) * d.x
+            + surface.derivative_v_at(a.x, a.y) * d.y)
+            .length()
+            * scale;
+        if gap.is_nan() || gap > edges[i].end_vertex().tolerance() {
+            report.push(wire.clone(), face.clone(), CheckStatus::OpenWire { gap });
+        }
+    }
+    for i in 0..n {
+        for j in i + 1..n {
+            if polylines_cross(
+                &polylines[i],
+                &polylines[j],
+                j == i + 1,
+                i == 0 && j == n - 1,
+            ) {
+                report.push(
+                    wire.clone(),
+                    face.clone(),
+                    CheckStatus::SelfIntersectingWire { edges: (i, j) },
+                );
+            }
+        }
+    }
+}
+
+/// 2本の折れ線が交差するか調べる
+///
+/// `a` の終わりと `b` の始まり（`joined_after`）、`a` の始まりと `b` の終わり（`joined_before`）が
+/// 頂点でつながっている場合は、その頂点に接する線分どうしは比べない。
+fn polylines_cross(a: &[Vector2], b: &[Vector2], joined_after: bool, joined_before: bool) -> bool {
+    let (m, n) = (a.len() - 1, b.len() - 1);
+    for i in 0..m {
+        for j in 0..n {
+            if (joined_after && i == m - 1 && j == 0) || (joined_before && i == 0 && j == n - 1) {
+                continue;
+            }
+            if segments_cross(a[i], a[i + 1], b[j], b[j + 1]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// 線分 `p0`-`p1` と `q0`-`q1` が互いの内部で交差すれば `true` を返す
+fn segments_cross(p0: Vector2, p1: Vector2, q0: Vector2, q1: Vector2) -> bool {
+    let side = |a: Vector2, b: Vector2, c: Vector2| (b - a).perp_dot(c - a);
+    side(p0, p1, q0) * side(p0, p1, q1) < 0.0 && side(q0, q1, p0) * side(q0, q1, p1) < 0.0
+}
+
+fn check_shell(shell: &Shell, report: &mut CheckReport) {
+    // エッジごとに、使っているフェイスの番号と向き
+    let mut uses: HashMap<ShapeId, Vec<(usize, Edge)>> = HashMap::new();
+    for (i, face) in shell.faces().iter().enumerate() {
+        for wire in face.wires() {
+            for edge in wire.edges() {
+                if !edge.is_degenerate() {
+                    uses.entry(edge.id()).or_default().push((i, edge));
+                }
+            }
+        }
+    }
+    let mut inconsistent: Vec<&Edge> = uses
+        .values()
+        .filter(|u| {
+            u.len() == 2
+                && u[0].0 != u[1].0
+                && u[0].1.orientation() == u[1].1.orientation()
+                && matches!(
+                    u[0].1.orientation(),
+                    Orientation::Forward | Orientation::Reversed
+                )
+        })
+        .map(|u| &u[0].1)
+        .collect();
+    // 結果の順序を決まったものにする
+    inconsistent.sort_by_key(|e| e.id());
+    for edge in inconsistent {
+        report.push(
+            edge.oriented(Orientation::Forward),
+            shell.clone(),
+            CheckStatus::InconsistentOrientation,
+        );
+    }
+}
+
+fn check_solid(solid: &Solid, report: &mut CheckReport) {
+    for shell in solid.shells() {
+        if !shell.is_closed() {
+            report.push(shell, solid.clone(), CheckStatus::ShellNotClosed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Axis2, Axis3, BSplineCurve2, Circle, CylindricalSurface, Dir, Line, Plane, Point3, Vertex,
+    };
+
+    fn segment_edge(a: &Vertex, b: &Vertex) -> Edge {
+        let line = Line::from_points(a.point(), b.point()).unwrap();
+        let length = a.point().distance(b.point());
+        Edge::new(line, (0.0, length), a.clone(), b.clone()).unwrap()
+    }
+
+    /// xy 平面上の点を順に結んだ閉じたワイヤ
+    fn polygon(points: &[(f64, f64)]) -> Wire {
+        let v: Vec<Vertex> = points
+            .iter()
+            .map(|&(x, y)| Vertex::new(Point3::new(x, y, 0.0)))
+            .collect();
+        let n = v.len();
+        Wire::new(
+            (0..n)
+                .map(|i| segment_edge(&v[i], &v[(i + 1) % n]))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    /// 頂点・エッジを共有する四面体のフェイス
+    fn tetrahedron_faces() -> Vec<Face> {
+        let v: Vec<Vertex> = [
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vertex::new(Point3::new(x, y, z)))
+        .collect();
+        let e: Vec<Edge> = [(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]
+            .iter()
+            .map(|&(a, b): &(usize, usize)| segment_edge(&v[a], &v[b]))
+            .collect();
+        let loops = [
+            [(0, true), (2, true), (1, true)],
+            [(0, false), (4, false), (3, true)],
+            [(1, false), (5, false), (4, true)],
+            [(2, false), (3, false), (5, true)],
+        ];
+        loops
+            .iter()
+            .map(|l| {
+                let edges: Vec<Edge> = l
+                    .iter()
+                    .map(|&(i, rev)| if rev { e[i].reversed() } else { e[i].clone() })
+                    .collect();
+                let p: Vec<Point3> = edges.iter().map(|e| e.start_point()).collect();
+                let plane = Plane::from_points(p[0], p[1], p[2]).unwrap();
+                Face::new(plane, vec![Wire::new(edges).unwrap()]).unwrap()
+            })
+            .collect()
+    }
+
+    fn statuses(report: &CheckReport) -> Vec<CheckStatus> {
+        report.issues().iter().map(|i| i.status).collect()
+    }
+
+    #[test]
+    fn test_valid_solid() {
+        let solid = Solid::new(vec![Shell::new(tetrahedron_faces()).unwrap()]).unwrap();
+        assert!(Shape::from(solid.clone()).check().is_valid());
+        // 配置や向きを変えても妥当なまま
+        let t = crate::Transform::new(
+            crate::Quaternion::from_axis_angle(crate::Vector3::X, 0.4),
+            crate::Vector3::new(1.0, 2.0, 3.0),
+            2.0,
+        );
+        assert!(Shape::from(solid.moved(&t).reversed()).check().is_valid());
+        let face = Face::from_planar_wires(
+            polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)]),
+            vec![polygon(&[(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)])],
+            1e-7,
+        )
+        .unwrap();
+        assert!(Shape::from(face).check().is_valid());
+        // 円の穴は pcurve を射影で求める
+        let circle = Circle::new(Axis2::from_normal(Point3::new(2.0, 1.5, 0.0), Dir::Z), 0.5);
+        let hole = Wire::new(vec![Edge::from_curve(circle.unwrap()).unwrap()]).unwrap();
+        let face = Face::from_planar_wires(
+            polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)]),
+            vec![hole],
+            1e-7,
+        )
+        .unwrap();
+        let report = Shape::from(face).check();
+        assert!(report.is_valid(), "{:?}", statuses(&report));
+    }
+
+    #[test]
+    fn test_orientation_problems() {
+        let mut faces = tetrahedron_faces();
+        faces[2] = faces[2].reversed();
+        let shell = Shell::new(faces.clone()).unwrap();
+        let report = Shape::from(Solid::new(vec![shell.clone()]).unwrap()).check();
+        // 反転したフェイスの3本のエッジがすべて隣と同じ向きになる
+        assert_eq!(report.issues().len(), 3);
+        for issue in report.issues() {
+            assert_eq!(issue.status, CheckStatus::InconsistentOrientation);
+            assert_eq!(issue.shape.kind(), ShapeKind::Edge);
+            assert!(issue
+                .parent
+                .as_ref()
+                .unwrap()
+                .is_same(&shell.clone().into()));
+        }
+
+        // 時計回りの外側のワイヤ
+        let wire = polygon(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let face = Face::new(Plane::xy(), vec![wire.clone()]).unwrap();
+        let report = Shape::from(face).check();
+        assert_eq!(statuses(&report), vec![CheckStatus::BadWireOrientation]);
+        assert_eq!(report.issues_of(&wire.into()).count(), 1);
+    }
+
+    #[test]
+    fn test_open_shell_in_solid() {
+        let faces = tetrahedron_faces();
+        let shell = Shell::new(faces[..3].to_vec()).unwrap();
+        let report = Shape::from(Solid::new(vec![shell.clone()]).unwrap()).check();
+        assert_eq!(statuses(&report), vec![CheckStatus::ShellNotClosed]);
+        // シェルだけなら開いていても問題ない
+        assert!(Shape::from(shell).check().is_valid());
+    }
+
+    #[test]
+    fn test_self_intersecting_wire() {
+        let wire = polygon(&[(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)]);
+        let report = Shape::from(Face::new(Plane::xy(), vec![wire]).unwrap()).check();
+        assert_eq!(
+            statuses(&report),
+            vec![CheckStatus::SelfIntersectingWire { edges: (0, 2) }]
+        );
+    }
+
+    #[test]
+    fn test_curve_and_pcurve_problems() {
+        // 平面から離れたエッジ
+        let wire = polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let lifted = Plane::new(Point3::new(0.0, 0.0, 0.1), Dir::Z);
+        let report = Shape::from(Face::new(lifted, vec![wire.clone()]).unwrap()).check();
+        assert_eq!(report.issues().len(), 4);
+        assert!(report.issues().iter().all(|i| matches!(
+            i.status,
+            CheckStatus::CurveOffSurface { distance } if (distance - 0.1).abs() < 1e-12
+        )));
+
+        // 平面以外の曲面では pcurve が必要
+        let cylinder =
+            CylindricalSurface::new(Axis3::from_normal(Point3::new(0.0, 0.0, 0.0), Dir::X), 1.0)
+                .unwrap();
+        let report = Shape::from(Face::new(cylinder, vec![wire.clone()]).unwrap()).check();
+        assert!(statuses(&report).contains(&CheckStatus::MissingPCurve));
+
+        // 1本だけずれた pcurve はワイヤの切れ目と曲面からのずれになる
+        let edges = wire.edges();
+        let pcurves = edges
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let shift = if i == 1 { 0.2 } else { 0.0 };
+                let uv = |p: Point3| Vector2::new(p.x + shift, p.y);
+                let knots = vec![0.0, 0.0, 1.0, 1.0];
+                let points = vec![uv(e.start_point()), uv(e.end_point())];
+                Some(BSplineCurve2::from_flat_knots(1, points, knots).unwrap())
+            })
+            .collect();
+        let face = Face::with_pcurves(Plane::xy(), vec![wire], vec![pcurves]).unwrap();
+        let report = Shape::from(face).check();
+        let s = statuses(&report);
+        assert!(s.iter().any(
+            |s| matches!(s, CheckStatus::CurveOffSurface { distance } if (distance - 0.2).abs() < 1e-12)
+        ));
+        assert_eq!(
+            s.iter()
+                .filter(|s| matches!(s, CheckStatus::OpenWire { gap } if (gap - 0.2).abs() < 1e-12))
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,407 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{PI, TAU};
+
+use crate::curve::{check_trim_range, split_ranges};
+use crate::{Axis1, Axis2, BSplineCurve, Dir, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 円（OCCT の `gp_Circ` 相当）
+///
+/// 局所座標系 `position` の原点を中心とし、X・Y方向の張る平面上にある。
+/// 角度パラメータ `u` の点は `center + r (cos u · X + sin u · Y)` で表される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle {
+    position: Axis2,
+    radius: f64,
+}
+
+/// 円弧
+///
+/// 元の円の角度 `start_angle` から `end_angle` まで、主方向まわりに反時計回りに進む部分。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Arc {
+    circle: Circle,
+    start_angle: f64,
+    end_angle: f64,
+}
+
+impl Circle {
+    /// 局所座標系と半径から円を生成する
+    /// 半径が負または有限でない場合はエラーを返す
+    pub fn new(position: Axis2, radius: f64) -> Result<Self> {
+        if !(radius >= 0.0 && radius.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "円の半径が不正です: {}",
+                radius
+            )));
+        }
+        Ok(Self { position, radius })
+    }
+
+    /// 中心・法線・半径から円を生成する
+    pub fn from_center_normal(center: Point3, normal: Dir, radius: f64) -> Result<Self> {
+        Self::new(Axis2::from_normal(center, normal), radius)
+    }
+
+    /// 3点を通る円を生成する
+    ///
+    /// 法線は `(b - a) × (c - a)` の向き、角度 0 の位置は `a` とする。
+    /// 3点が同一直線上にある場合はエラーを返す。
+    pub fn from_three_points(a: Point3, b: Point3, c: Point3) -> Result<Self> {
+        let center = circumcenter(a, b, c)?;
+        let normal = Dir::from_vector((b - a).cross(c - a))?;
+        let x_hint = Dir::from_vector(a - center)?;
+        Self::new(Axis2::new(center, normal, x_hint)?, center.distance(a))
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis2 {
+        self.position
+    }
+
+    /// 中心を返す
+    pub fn center(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 中心を通り円の平面に垂直な軸を返す
+    pub fn axis(&self) -> Axis1 {
+        self.position.axis()
+    }
+
+    /// 半径を返す
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// 円周の長さを計算する
+    pub fn length(&self) -> f64 {
+        TAU * self.radius
+    }
+
+    /// 面積を計算する
+    pub fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    /// 角度 `u` における点を返す
+    pub fn point_at(&self, u: f64) -> Point3 {
+        let (s, c) = u.sin_cos();
+        self.position
+            .point_at(self.radius * c, self.radius * s, 0.0)
+    }
+
+    /// 角度 `u` における接ベクトル（角度についての1階微分）を返す
+    pub fn tangent_at(&self, u: f64) -> Vector3 {
+        let (s, c) = u.sin_cos();
+        (self.position.x_direction().to_vector() * -s + self.position.y_direction().to_vector() * c)
+            * self.radius
+    }
+
+    /// 点を円の平面に射影したときの角度（0 以上 2π 未満）を返す
+    /// 点が軸上にある場合は 0 を返す
+    pub fn parameter_of(&self, p: Point3) -> f64 {
+        let d = p - self.center();
+        let x = d.dot(self.position.x_direction().to_vector());
+        let y = d.dot(self.position.y_direction().to_vector());
+        if x == 0.0 && y == 0.0 {
+            return 0.0;
+        }
+        y.atan2(x).rem_euclid(TAU)
+    }
+
+    /// 円周上で点に最も近い点を返す
+    pub fn closest_point(&self, p: Point3) -> Point3 {
+        self.point_at(self.parameter_of(p))
+    }
+
+    /// 点と円周の距離を計算する
+    pub fn distance(&self, p: Point3) -> f64 {
+        p.distance(self.closest_point(p))
+    }
+
+    /// 角度範囲 `[u1, u2]` を円弧として切り出す
+    /// `u1 < u2 <= u1 + 2π` でない場合はエラーを返す
+    pub fn trim(&self, u1: f64, u2: f64) -> Result<Arc> {
+        Arc::new(*self, u1, u2)
+    }
+
+    /// 変換を適用した円を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            position: self.position.transformed(t),
+            radius: self.radius * t.scale.abs(),
+        }
+    }
+
+    /// 厳密に円を表す有理 B-スプライン曲線に変換する（パラメータは角度と区間の端でのみ一致する）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        BSplineCurve::from_circle(self)
+    }
+}
+
+impl Arc {
+    /// 円と開始・終了角度から円弧を生成する
+    /// `end_angle` が `start_angle` より大きくない、または一周を超える場合はエラーを返す
+    pub fn new(circle: Circle, start_angle: f64, end_angle: f64) -> Result<Self> {
+        let sweep = end_angle - start_angle;
+        if !(sweep > 0.0 && sweep <= TAU) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "円弧の角度範囲が不正です: {} 〜 {}",
+                start_angle, end_angle
+            )));
+        }
+        Ok(Self {
+            circle,
+            start_angle,
+            end_angle,
+        })
+    }
+
+    /// 始点 `a` から `b` を経由して終点 `c` に至る円弧を生成する
+    /// 3点が同一直線上にある場合はエラーを返す
+    pub fn from_three_points(a: Point3, b: Point3, c: Point3) -> Result<Self> {
+        let circle = Circle::from_three_points(a, b, c)?;
+        Self::new(circle, 0.0, circle.parameter_of(c))
+    }
+
+    /// 始点・終点・半径から、`normal` まわりに反時計回りに進む円弧を生成する
+    ///
+    /// `radius` が正なら中心角が π 以下の円弧、負なら π 以上の円弧を選ぶ。
+    /// 半径が2点間の距離の半分より小さい場合はエラーを返す。
+    pub fn from_endpoints_radius(
+        start: Point3,
+        end: Point3,
+        radius: f64,
+        normal: Dir,
+    ) -> Result<Self> {
+        let chord = end - start;
+        let half = chord.length() * 0.5;
+        let r = radius.abs();
+        if half == 0.0 || r < half {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "半径 {} では2点（距離 {}）を結べません",
+                radius,
+                half * 2.0
+            )));
+        }
+        // 弦の中点から、法線と弦に垂直な方向へ中心をずらす
+        let n = normal.to_vector();
+        let left = Dir::from_vector(n.cross(chord)).map_err(|_| {
+            OcctKrsError::DegenerateGeometry("2点を結ぶ向きが法線と平行です".to_string())
+        })?;
+        let offset = (r * r - half * half).sqrt();
+        let side = if radius > 0.0 { 1.0 } else { -1.0 };
+        let center = start.midpoint(end) + left.to_vector() * (offset * side);
+        let x_hint = Dir::from_vector(start - center)?;
+        let circle = Circle::new(Axis2::new(center, normal, x_hint)?, r)?;
+        let mut end_angle = circle.parameter_of(end);
+        if end_angle == 0.0 {
+            end_angle = TAU;
+        }
+        Self::new(circle, 0.0, end_angle)
+    }
+
+    /// 元の円を返す
+    pub fn circle(&self) -> Circle {
+        self.circle
+    }
+
+    /// 開始角度を返す
+    pub fn start_angle(&self) -> f64 {
+        self.start_angle
+    }
+
+    /// 終了角度を返す
+    pub fn end_angle(&self) -> f64 {
+        self.end_angle
+    }
+
+    /// 中心角を返す
+    pub fn sweep_angle(&self) -> f64 {
+        self.end_angle - self.start_angle
+    }
+
+    /// 円弧の長さを計算する
+    pub fn length(&self) -> f64 {
+        self.circle.radius * self.sweep_angle()
+    }
+
+    /// 角度 `u` における点を返す
+    pub fn point_at_angle(&self, u: f64) -> Point3 {
+        self.circle.point_at(u)
+    }
+
+    /// 正規化パラメータ `t`（0 で始点、1 で終点）における点を返す
+    pub fn point_at(&self, t: f64) -> Point3 {
+        self.point_at_angle(self.start_angle + self.sweep_angle() * t)
+    }
+
+    /// 始点を返す
+    pub fn start_point(&self) -> Point3 {
+        self.point_at_angle(self.start_angle)
+    }
+
+    /// 終点を返す
+    pub fn end_point(&self) -> Point3 {
+        self.point_at_angle(self.end_angle)
+    }
+
+    /// 円弧の中点を返す
+    pub fn midpoint(&self) -> Point3 {
+        self.point_at(0.5)
+    }
+
+    /// 角度範囲 `[u1, u2]` を切り出す（円弧の範囲内である必要がある）
+    pub fn trim(&self, u1: f64, u2: f64) -> Result<Arc> {
+        check_trim_range(self.start_angle, self.end_angle, u1, u2)?;
+        Arc::new(self.circle, u1, u2)
+    }
+
+    /// 指定した角度で分割する
+    pub fn split_at(&self, angles: &[f64]) -> Result<Vec<Arc>> {
+        split_ranges(self.start_angle, self.end_angle, angles)?
+            .into_iter()
+            .map(|(a, b)| self.trim(a, b))
+            .collect()
+    }
+
+    /// 角度 `u` が円弧の範囲内にあるか判定する（2π の整数倍の差は同一とみなす）
+    pub fn contains_angle(&self, u: f64) -> bool {
+        (u - self.start_angle).rem_euclid(TAU) <= self.sweep_angle()
+    }
+
+    /// 厳密に円弧を表す有理 B-スプライン曲線に変換する（パラメータは角度と区間の端でのみ一致する）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        BSplineCurve::from_arc(self)
+    }
+}
+
+/// 3点の外心を計算する
+fn circumcenter(a: Point3, b: Point3, c: Point3) -> Result<Point3> {
+    let ab = b - a;
+    let ac = c - a;
+    let n = ab.cross(ac);
+    let n2 = n.dot(n);
+    if n2 <= f64::MIN_POSITIVE || n2.sqrt() <= 1e-14 * ab.length() * ac.length() {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "3点が同一直線上にあります".to_string(),
+        ));
+    }
+    let offset = (n.cross(ab) * ac.dot(ac) + ac.cross(n) * ab.dot(ab)) * (0.5 / n2);
+    Ok(a + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_circle_evaluation() {
+        let c = Circle::from_center_normal(Point3::new(1.0, 0.0, 0.0), Dir::Z, 2.0).unwrap();
+        assert_point_eq(c.point_at(0.0), Point3::new(3.0, 0.0, 0.0));
+        assert_point_eq(c.point_at(FRAC_PI_2), Point3::new(1.0, 2.0, 0.0));
+        assert!((c.tangent_at(0.0) - Vector3::new(0.0, 2.0, 0.0)).length() < 1e-12);
+        assert!((c.length() - 4.0 * PI).abs() < 1e-12);
+        let p = Point3::new(1.0, -5.0, 3.0);
+        assert!((c.parameter_of(p) - 1.5 * PI).abs() < 1e-12);
+        assert_point_eq(c.closest_point(p), Point3::new(1.0, -2.0, 0.0));
+        assert!((c.distance(p) - 18f64.sqrt()).abs() < 1e-12);
+        assert!(Circle::from_center_normal(Point3::origin(), Dir::Z, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_circle_from_three_points() {
+        let a = Point3::new(1.0, 0.0, 2.0);
+        let b = Point3::new(0.0, 1.0, 2.0);
+        let c = Point3::new(-1.0, 0.0, 2.0);
+        let circle = Circle::from_three_points(a, b, c).unwrap();
+        assert_point_eq(circle.center(), Point3::new(0.0, 0.0, 2.0));
+        assert!((circle.radius() - 1.0).abs() < 1e-12);
+        assert_point_eq(circle.point_at(0.0), a);
+        let err = Circle::from_three_points(a, a, c).unwrap_err();
+        assert!(matches!(err, OcctKrsError::DegenerateGeometry(_)));
+    }
+
+    #[test]
+    fn test_arc_from_three_points() {
+        let a = Point3::new(1.0, 0.0, 0.0);
+        let b = Point3::new(0.0, 1.0, 0.0);
+        let c = Point3::new(-1.0, 0.0, 0.0);
+        let arc = Arc::from_three_points(a, b, c).unwrap();
+        assert!((arc.sweep_angle() - PI).abs() < 1e-12);
+        assert!((arc.length() - PI).abs() < 1e-12);
+        assert_point_eq(arc.start_point(), a);
+        assert_point_eq(arc.end_point(), c);
+        assert_point_eq(arc.midpoint(), b);
+
+        // 経由点によって円弧の向きが変わる
+        let arc = Arc::from_three_points(a, Point3::new(0.0, -1.0, 0.0), b).unwrap();
+        assert!((arc.sweep_angle() - 1.5 * PI).abs() < 1e-12);
+        assert_point_eq(arc.end_point(), b);
+    }
+
+    #[test]
+    fn test_arc_from_endpoints_radius() {
+        let s = Point3::new(1.0, 0.0, 0.0);
+        let e = Point3::new(0.0, 1.0, 0.0);
+        let minor = Arc::from_endpoints_radius(s, e, 1.0, Dir::Z).unwrap();
+        assert_point_eq(minor.circle().center(), Point3::origin());
+        assert!((minor.sweep_angle() - FRAC_PI_2).abs() < 1e-12);
+        assert_point_eq(minor.end_point(), e);
+
+        let major = Arc::from_endpoints_radius(s, e, -1.0, Dir::Z).unwrap();
+        assert!((major.sweep_angle() - 1.5 * PI).abs() < 1e-12);
+        assert_point_eq(major.start_point(), s);
+        assert_point_eq(major.end_point(), e);
+
+        assert!(Arc::from_endpoints_radius(s, e, 0.5, Dir::Z).is_err());
+    }
+
+    #[test]
+    fn test_arc_range() {
+        let c = Circle::from_center_normal(Point3::origin(), Dir::Z, 1.0).unwrap();
+        let arc = Arc::new(c, -FRAC_PI_2, FRAC_PI_2).unwrap();
+        assert!(arc.contains_angle(0.0));
+        assert!(arc.contains_angle(TAU - 0.1));
+        assert!(!arc.contains_angle(PI));
+        assert!(Arc::new(c, 1.0, 1.0).is_err());
+        assert!(Arc::new(c, 0.0, 7.0).is_err());
+    }
+
+    #[test]
+    fn test_trim_and_split() {
+        let c = Circle::from_center_normal(Point3::origin(), Dir::Z, 1.0).unwrap();
+        let arc = c.trim(FRAC_PI_2, 1.5 * PI).unwrap();
+        assert_point_eq(arc.start_point(), Point3::new(0.0, 1.0, 0.0));
+        let parts = arc.split_at(&[PI]).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].end_angle(), PI);
+        assert_eq!(parts[1].start_angle(), PI);
+        assert!((parts[0].length() + parts[1].length() - arc.length()).abs() < 1e-12);
+        assert!(arc.trim(0.0, PI).is_err());
+        assert!(c.trim(0.0, 7.0).is_err());
+    }
+
+    #[test]
+    fn test_to_nurbs() {
+        let circle = Circle::from_center_normal(Point3::new(1.0, 1.0, 0.0), Dir::Z, 2.0).unwrap();
+        let c = circle.to_nurbs();
+        assert!(c.is_rational());
+        for u in [0.0, 0.7, 2.0, 5.5] {
+            assert!((c.point_at(u).distance(circle.center()) - 2.0).abs() < 1e-12);
+        }
+        let arc = Arc::new(circle, 0.5, 2.5).unwrap();
+        let a = arc.to_nurbs();
+        assert_point_eq(a.start_point(), arc.start_point());
+        assert_point_eq(a.end_point(), arc.end_point());
+        assert!((a.point_at(1.5).distance(circle.center()) - 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,492 @@
+use crate::{
+    BoundingBox, Curve3, Dir, Edge, Face, GeomCurve, GeomSurface, Line, OcctKrsError, Orientation,
+    Point3, PointClassification, Result, Surface, Vector2, Vector3,
+};
+
+/// 曲線のエッジを折れ線で近似するときの分割数
+pub(crate) const EDGE_SAMPLES: usize = 64;
+
+/// 半直線と曲面の交点を探すときの分割数
+const RAY_SAMPLES: usize = 256;
+
+/// 立体の内外判定に使う半直線の向き（辺を通る・曲面に接する場合は次の向きで試す）
+const RAY_DIRECTIONS: [(f64, f64, f64); 6] = [
+    (0.5377, 0.8214, 0.1893),
+    (-0.3121, 0.2746, 0.9095),
+    (0.8727, -0.4218, 0.2459),
+    (-0.6113, -0.5324, -0.5855),
+    (0.1571, -0.9386, 0.3071),
+    (-0.7419, 0.3598, -0.5658),
+];
+
+/// 点との位置関係を調べるために、境界をパラメータ空間の多角形にしたフェイス
+#[derive(Debug, Clone)]
+pub(crate) struct FaceClassifier {
+    /// 向きを `Forward` にしたフェイス
+    pub(crate) face: Face,
+    /// 配置を適用した曲面
+    pub(crate) surface: GeomSurface,
+    /// 境界のワイヤをたどった多角形（外側は反時計回り、穴は時計回り）
+    loops: Vec<Vec<Vector2>>,
+    /// 退化していない境界のエッジと、その境界ボックス
+    edges: Vec<(Edge, BoundingBox)>,
+    /// 境界のパラメータ空間での範囲（境界がなければ曲面のパラメータ範囲）
+    uv_box: (Vector2, Vector2),
+    /// フェイスを囲む境界ボックス（少し広げた近似）
+    pub(crate) bounding_box: BoundingBox,
+}
+
+impl FaceClassifier {
+    /// 平面以外で pcurve のない境界のエッジがある場合はエラーを返す
+    pub(crate) fn new(face: &Face) -> Result<Self> {
+        let face = face.oriented(Orientation::Forward);
+        let surface = face.located_geometry().into_owned();
+        let mut loops = Vec::new();
+        let mut edges = Vec::new();
+        for wire in face.wires() {
+            let mut points = Vec::new();
+            for edge in wire.edges() {
+                let params = sample_parameters(&edge);
+                let uv = edge_uv(&face, &surface, &edge, &params).ok_or_else(|| {
+                    OcctKrsError::InvalidInput(
+                        "平面以外のフェイスの境界に pcurve のないエッジがあります".to_string(),
+                    )
+                })?;
+                points.extend_from_slice(&uv[..uv.len() - 1]);
+                if !edge.is_degenerate() {
+                    let bbox = BoundingBox::from_points(params.iter().map(|&t| edge.point_at(t)))
+                        .unwrap_or_else(BoundingBox::infinite);
+                    edges.push((edge, bbox));
+                }
+            }
+            loops.push(points);
+        }
+        let uv_box = match loops.iter().flatten().next() {
+            Some(&first) => loops.iter().flatten().fold((first, first), |(lo, hi), p| {
+                (
+                    Vector2::new(lo.x.min(p.x), lo.y.min(p.y)),
+                    Vector2::new(hi.x.max(p.x), hi.y.max(p.y)),
+                )
+            }),
+            None => {
+                let ((u0, u1), (v0, v1)) = (surface.u_range(), surface.v_range());
+                (Vector2::new(u0, v0), Vector2::new(u1, v1))
+            }
+        };
+        let bounding_box = if [uv_box.0.x, uv_box.0.y, uv_box.1.x, uv_box.1.y]
+            .iter()
+            .all(|x| x.is_finite())
+        {
+            // 境界の点と、パラメータ空間の範囲を格子状に分割した点を囲む
+            let n = 16;
+            let grid = (0..=n).flat_map(|i| {
+                let surface = &surface;
+                (0..=n).map(move |j| {
+                    let (s, t) = (i as f64 / n as f64, j as f64 / n as f64);
+                    surface.point_at(
+                        uv_box.0.x + (uv_box.1.x - uv_box.0.x) * s,
+                        uv_box.0.y + (uv_box.1.y - uv_box.0.y) * t,
+                    )
+                })
+            });
+            let corners = edges.iter().flat_map(|(_, b)| [b.min, b.max]);
+            let bbox =
+                BoundingBox::from_points(grid.chain(corners)).unwrap_or_else(BoundingBox::infinite);
+            bbox.enlarged(bbox.size().length() * 0.05 + crate::precision::confusion())
+        } else {
+            BoundingBox::infinite()
+        };
+        Ok(Self {
+            face,
+            surface,
+            loops,
+            edges,
+            uv_box,
+            bounding_box,
+        })
+    }
+
+    /// 境界のパラメータ空間での範囲 `(最小, 最大)` を返す
+    pub(crate) fn uv_box(&self) -> (Vector2, Vector2) {
+        self.uv_box
+    }
+
+    /// 点を曲面に射影したパラメータを、閉じた方向ではフェイスの範囲の中央に近い周期に寄せて返す
+    pub(crate) fn uv_of(&self, p: Point3) -> Vector2 {
+        let (u, v) = self.surface.project(p).map_or((0.0, 0.0), |q| (q.u, q.v));
+        self.unwrap(Vector2::new(u, v))
+    }
+
+    /// 閉じた方向のパラメータを、フェイスの範囲の中央から半周期以内に寄せる
+    pub(crate) fn unwrap(&self, uv: Vector2) -> Vector2 {
+        let shift = |x: f64, (a, b): (f64, f64), lo: f64, hi: f64, closed: bool| {
+            let period = b - a;
+            if !closed || !period.is_finite() || !(lo + hi).is_finite() {
+                return x;
+            }
+            x + period * (((lo + hi) / 2.0 - x) / period).round()
+        };
+        Vector2::new(
+            shift(
+                uv.x,
+                self.surface.u_range(),
+                self.uv_box.0.x,
+                self.uv_box.1.x,
+                self.surface.is_u_closed(),
+            ),
+            shift(
+                uv.y,
+                self.surface.v_range(),
+                self.uv_box.0.y,
+                self.uv_box.1.y,
+                self.surface.is_v_closed(),
+            ),
+        )
+    }
+
+    /// パラメータ空間の点が境界の内側にあれば `true` を返す（境界がなければ常に `true`）
+    pub(crate) fn contains_uv(&self, uv: Vector2) -> bool {
+        self.loops.is_empty()
+            || self
+                .loops
+                .iter()
+                .map(|l| winding_number(l, uv))
+                .sum::<i32>()
+                > 0
+    }
+
+    /// 曲面の近くにある点と、フェイスの境界との位置関係を返す
+    ///
+    /// 境界のエッジとの距離が `tolerance` 以下なら境界上とし、それ以外はパラメータ空間の多角形で判定する。
+    pub(crate) fn classify(&self, p: Point3, tolerance: f64) -> PointClassification {
+        let on_boundary = self.edges.iter().any(|(edge, bbox)| {
+            bbox.enlarged(tolerance).contains(p) && edge_distance(edge, p) <= tolerance
+        });
+        if on_boundary {
+            PointClassification::OnBoundary
+        } else if self.contains_uv(self.uv_of(p)) {
+            PointClassification::Inside
+        } else {
+            PointClassification::Outside
+        }
+    }
+
+    /// 点が曲面から `tolerance` 以内にあり、フェイスの内側か境界上にあれば `true` を返す
+    pub(crate) fn touches(&self, p: Point3, tolerance: f64) -> bool {
+        self.bounding_box.contains(p)
+            && signed_distance(&self.surface, p).abs() <= tolerance
+            && self.classify(p, tolerance) != PointClassification::Outside
+    }
+
+    /// 点での曲面の法線を、フェイスの向き（`Forward`）で返す
+    pub(crate) fn normal_at(&self, p: Point3) -> Option<Dir> {
+        let uv = self.uv_of(p);
+        self.surface.normal_at(uv.x, uv.y)
+    }
+}
+
+/// 閉じた多角形が点のまわりを回る回数（反時計回りで正）
+pub(crate) fn winding_number(polygon: &[Vector2], p: Vector2) -> i32 {
+    let n = polygon.len();
+    let mut winding = 0;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        let side = (b - a).perp_dot(p - a);
+        if a.y <= p.y {
+            if b.y > p.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= p.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// エッジを折れ線で近似するパラメータを、向きに沿ってたどる順に返す（直線は両端のみ）
+pub(crate) fn sample_parameters(edge: &Edge) -> Vec<f64> {
+    let (first, last) = edge.range();
+    let n = match edge.curve() {
+        Some(GeomCurve::Line(_)) => 1,
+        Some(GeomCurve::BSpline(c)) if c.degree() == 1 && !c.is_rational() => {
+            (c.control_points().len() - 1).max(1)
+        }
+        _ => EDGE_SAMPLES,
+    };
+    let mut params: Vec<f64> = match edge.curve() {
+        // 折れ線は折れ点で区切る
+        Some(GeomCurve::BSpline(c)) if n > 1 && c.degree() == 1 && !c.is_rational() => {
+            let knots = c.flat_knots();
+            std::iter::once(first)
+                .chain(knots[1..knots.len() - 1].iter().copied())
+                .filter(|&t| t >= first && t <= last)
+                .chain(std::iter::once(last))
+                .collect::<Vec<_>>()
+                .windows(2)
+                .filter(|w| w[1] > w[0])
+                .map(|w| w[0])
+                .chain(std::iter::once(last))
+                .collect()
+        }
+        _ => (0..=n)
+            .map(|k| first + (last - first) * k as f64 / n as f64)
+            .collect(),
+    };
+    if edge.orientation() == Orientation::Reversed {
+        params.reverse();
+    }
+    params
+}
+
+/// エッジを配置を適用した曲面 `surface` のパラメータ空間へ移した点列
+/// （pcurve がなく平面でもなければ `None`）
+pub(crate) fn edge_uv(
+    face: &Face,
+    surface: &GeomSurface,
+    edge: &Edge,
+    params: &[f64],
+) -> Option<Vec<Vector2>> {
+    if let Some(pcurve) = face.pcurve(edge) {
+        return Some(params.iter().map(|&t| pcurve.point_at(t)).collect());
+    }
+    let GeomSurface::Plane(plane) = surface else {
+        return None;
+    };
+    Some(
+        params
+            .iter()
+            .map(|&t| {
+                let (u, v) = plane.parameters_of(edge.point_at(t));
+                Vector2::new(u, v)
+            })
+            .collect(),
+    )
+}
+
+/// 周期的な曲線（円・楕円）の周期
+pub(crate) fn curve_period(curve: &GeomCurve) -> Option<f64> {
+    match curve {
+        GeomCurve::Circle(_) | GeomCurve::Ellipse(_) => Some(std::f64::consts::TAU),
+        _ => None,
+    }
+}
+
+/// 点とエッジ（パラメータ範囲内の曲線）との距離
+pub(crate) fn edge_distance(edge: &Edge, p: Point3) -> f64 {
+    let Some(curve) = edge.located_geometry() else {
+        return edge.start_point().distance(p);
+    };
+    let (t0, t1) = edge.range();
+    let mut best = curve
+        .point_at(t0)
+        .distance(p)
+        .min(curve.point_at(t1).distance(p));
+    let period = curve_period(&curve);
+    for q in curve.project(p) {
+        let t = match period {
+            Some(period) => t0 + (q.parameter - t0).rem_euclid(period),
+            None => q.parameter,
+        };
+        if t >= t0 && t <= t1 {
+            best = best.min(q.distance);
+        }
+    }
+    best
+}
+
+/// 点から曲面までの符号付き距離（符号は曲面の片側で正、反対側で負になればよく、法線の向きとは限らない）
+///
+/// 平面・円柱面・円錐面・球面・トーラスは式で求め、それ以外は最近点の法線との内積で求める。
+pub(crate) fn signed_distance(surface: &GeomSurface, p: Point3) -> f64 {
+    let local = |position: crate::Axis3| {
+        let w = p - position.location();
+        let z = w.dot(position.direction().to_vector());
+        let rho = (w - position.direction().to_vector() * z).length();
+        (rho, z)
+    };
+    match surface {
+        GeomSurface::Plane(s) => s.signed_distance(p),
+        GeomSurface::Cylinder(s) => local(s.position()).0 - s.radius(),
+        GeomSurface::Sphere(s) => p.distance(s.center()) - s.radius(),
+        GeomSurface::Cone(s) => {
+            let (rho, z) = local(s.position());
+            let (sin, cos) = s.semi_angle().sin_cos();
+            (rho - s.ref_radius()) * cos - z * sin
+        }
+        GeomSurface::Torus(s) => {
+            let (rho, z) = local(s.position());
+            (rho - s.major_radius()).hypot(z) - s.minor_radius()
+        }
+        _ => {
+            let Some(q) = surface.project(p) else {
+                return f64::NAN;
+            };
+            let d = p - q.point;
+            match surface.normal_at(q.u, q.v) {
+                Some(n) => d.dot(n.to_vector()),
+                None => d.length(),
+            }
+        }
+    }
+}
+
+/// 曲線がパラメータ区間 `range` で曲面を横切るパラメータを昇順に返す
+///
+/// 区間を `samples` 等分した点で符号付き距離の符号が変わるところを二分法で求める。
+/// 最近点が曲面の端や離れた部分へ移って符号が変わっただけのところ（求めた点が曲面上にない）は除く。
+/// 区間の端が曲面から `tolerance` 以内にあればその端も返す。区間全体が曲面から
+/// `tolerance` 以内にある（曲線が曲面上にある）場合は空を返す。
+pub(crate) fn curve_surface_roots(
+    curve: &(impl Curve3 + ?Sized),
+    (t0, t1): (f64, f64),
+    surface: &GeomSurface,
+    samples: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    let g = |t: f64| signed_distance(surface, curve.point_at(t));
+    let ts: Vec<f64> = (0..=samples)
+        .map(|k| t0 + (t1 - t0) * k as f64 / samples as f64)
+        .collect();
+    let gs: Vec<f64> = ts.iter().map(|&t| g(t)).collect();
+    if gs.iter().all(|d| d.abs() <= tolerance) {
+        return Vec::new();
+    }
+    let mut roots = Vec::new();
+    if gs[0].abs() <= tolerance {
+        roots.push(t0);
+    }
+    for k in 0..samples {
+        let (ga, gb) = (gs[k], gs[k + 1]);
+        if ga.is_nan() || gb.is_nan() || (ga >= 0.0) == (gb >= 0.0) {
+            continue;
+        }
+        let (mut lo, mut hi, mut glo) = (ts[k], ts[k + 1], ga);
+        for _ in 0..100 {
+            let mid = 0.5 * (lo + hi);
+            if mid <= lo || mid >= hi {
+                break;
+            }
+            let gm = g(mid);
+            if (gm >= 0.0) == (glo >= 0.0) {
+                lo = mid;
+                glo = gm;
+            } else {
+                hi = mid;
+            }
+        }
+        let t = 0.5 * (lo + hi);
+        let p = curve.point_at(t);
+        let on_surface = surface
+            .project(p)
+            .is_some_and(|q| p.distance(q.point) <= tolerance.max(crate::precision::confusion()));
+        if on_surface {
+            roots.push(t);
+        }
+    }
+    if gs[samples].abs() <= tolerance {
+        roots.push(t1);
+    }
+    roots
+}
+
+/// 閉じたシェルの集まりで囲まれた領域（フェイスは外向き）と点の位置関係を返す
+///
+/// 点を通る半直線がフェイスを横切る回数の偶奇で判定する。半直線が境界のエッジを通る、
+/// 曲面に接する、または点が曲面上にある場合は向きを変えて試す。フェイスから `tolerance` 以内の点は境界上とする。
+pub(crate) fn classify_point(
+    faces: &[FaceClassifier],
+    p: Point3,
+    tolerance: f64,
+) -> PointClassification {
+    if faces.iter().any(|f| f.touches(p, tolerance)) {
+        return PointClassification::OnBoundary;
+    }
+    let Some(bbox) = faces
+        .iter()
+        .map(|f| f.bounding_box)
+        .reduce(|a, b| a.union(&b))
+    else {
+        return PointClassification::Outside;
+    };
+    if !bbox.contains(p) {
+        return PointClassification::Outside;
+    }
+    let reach = p.distance(bbox.center()) + bbox.size().length();
+    let mut result = PointClassification::Outside;
+    for (x, y, z) in RAY_DIRECTIONS {
+        let Ok(direction) = Dir::from_vector(Vector3::new(x, y, z)) else {
+            continue;
+        };
+        match count_crossings(faces, Line::new(p, direction), reach, tolerance) {
+            Some(count) => {
+                return if count % 2 == 1 {
+                    PointClassification::Inside
+                } else {
+                    PointClassification::Outside
+                };
+            }
+            None => result = PointClassification::Outside,
+        }
+    }
+    result
+}
+
+/// 半直線が長さ `reach` までにフェイスを横切る回数（判定があいまいな場合は `None`）
+fn count_crossings(
+    faces: &[FaceClassifier],
+    ray: Line,
+    reach: f64,
+    tolerance: f64,
+) -> Option<usize> {
+    let end = ray.point_at(reach);
+    let mut count = 0;
+    for face in faces {
+        if !segment_meets_box(ray.point_at(0.0), end, &face.bounding_box) {
+            continue;
+        }
+        for t in curve_surface_roots(&ray, (0.0, reach), &face.surface, RAY_SAMPLES, 0.0) {
+            let q = ray.point_at(t);
+            match face.classify(q, tolerance) {
+                PointClassification::Outside => {}
+                PointClassification::OnBoundary => return None,
+                PointClassification::Inside => {
+                    let normal = face.normal_at(q)?;
+                    if normal.dot(ray.dir).abs() < 1e-3 {
+                        return None;
+                    }
+                    count += 1;
+                }
+            }
+        }
+    }
+    Some(count)
+}
+
+/// 線分が境界ボックスと交わるかを返す（スラブ法）
+fn segment_meets_box(a: Point3, b: Point3, bbox: &BoundingBox) -> bool {
+    if bbox.is_infinite() {
+        return true;
+    }
+    let d = b - a;
+    let (mut lo, mut hi) = (0.0_f64, 1.0_f64);
+    for (o, d, min, max) in [
+        (a.x, d.x, bbox.min.x, bbox.max.x),
+        (a.y, d.y, bbox.min.y, bbox.max.y),
+        (a.z, d.z, bbox.min.z, bbox.max.z),
+    ] {
+        if d.abs() < 1e-300 {
+            if o < min || o > max {
+                return false;
+            }
+            continue;
+        }
+        let (t0, t1) = ((min - o) / d, (max - o) / d);
+        let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+        lo = lo.max(t0);
+        hi = hi.min(t1);
+        if lo > hi {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,469 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::curve::check_trim_range;
+use crate::{Axis2, BSplineCurve, Line, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 楕円（OCCT の `gp_Elips` 相当）
+///
+/// 局所座標系の原点を中心とし、X方向を長軸、Y方向を短軸とする。
+/// 角度パラメータ `u` の点は `center + a cos u · X + b sin u · Y` で表される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse {
+    position: Axis2,
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+/// 放物線（OCCT の `gp_Parab` 相当）
+///
+/// 局所座標系の原点を頂点とし、X方向を対称軸とする。
+/// パラメータ `u` の点は `apex + u² / (4f) · X + u · Y` で表される（`f` は焦点距離）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Parabola {
+    position: Axis2,
+    focal_length: f64,
+}
+
+/// 双曲線（OCCT の `gp_Hypr` 相当）
+///
+/// 局所座標系の原点を中心とし、X方向を主軸とする。X方向の正の側の分枝のみを表す。
+/// パラメータ `u` の点は `center + a cosh u · X + b sinh u · Y` で表される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hyperbola {
+    position: Axis2,
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+fn check_length(name: &str, v: f64) -> Result<()> {
+    if v >= 0.0 && v.is_finite() {
+        Ok(())
+    } else {
+        Err(OcctKrsError::InvalidInput(format!(
+            "{}が不正です: {}",
+            name, v
+        )))
+    }
+}
+
+impl Ellipse {
+    /// 局所座標系と長半径・短半径から楕円を生成する
+    /// 半径が負、または短半径が長半径より大きい場合はエラーを返す
+    pub fn new(position: Axis2, major_radius: f64, minor_radius: f64) -> Result<Self> {
+        check_length("長半径", major_radius)?;
+        check_length("短半径", minor_radius)?;
+        if minor_radius > major_radius {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "短半径 {} が長半径 {} より大きいです",
+                minor_radius, major_radius
+            )));
+        }
+        Ok(Self {
+            position,
+            major_radius,
+            minor_radius,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis2 {
+        self.position
+    }
+
+    /// 中心を返す
+    pub fn center(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 長半径を返す
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    /// 短半径を返す
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    /// 中心から焦点までの距離を返す
+    pub fn focal_distance(&self) -> f64 {
+        (self.major_radius * self.major_radius - self.minor_radius * self.minor_radius).sqrt()
+    }
+
+    /// 離心率を返す（長半径がゼロの場合は 0）
+    pub fn eccentricity(&self) -> f64 {
+        if self.major_radius == 0.0 {
+            0.0
+        } else {
+            self.focal_distance() / self.major_radius
+        }
+    }
+
+    /// 長軸上の2つの焦点を返す（X方向の正の側が先）
+    pub fn foci(&self) -> (Point3, Point3) {
+        let c = self.focal_distance();
+        (
+            self.position.point_at(c, 0.0, 0.0),
+            self.position.point_at(-c, 0.0, 0.0),
+        )
+    }
+
+    /// 半通径（焦点を通り長軸に垂直な弦の半分の長さ `b² / a`）を返す
+    pub fn parameter(&self) -> f64 {
+        self.minor_radius * self.minor_radius / self.major_radius
+    }
+
+    /// X方向の正の側の準線を返す
+    /// 円（離心率ゼロ）の場合は準線が存在しないので `None` を返す
+    pub fn directrix(&self) -> Option<Line> {
+        let e = self.eccentricity();
+        if e == 0.0 {
+            return None;
+        }
+        let origin = self.position.point_at(self.major_radius / e, 0.0, 0.0);
+        Some(Line::new(origin, self.position.y_direction()))
+    }
+
+    /// 面積を計算する
+    pub fn area(&self) -> f64 {
+        PI * self.major_radius * self.minor_radius
+    }
+
+    /// 角度 `u` における点を返す
+    pub fn point_at(&self, u: f64) -> Point3 {
+        let (s, c) = u.sin_cos();
+        self.position
+            .point_at(self.major_radius * c, self.minor_radius * s, 0.0)
+    }
+
+    /// 角度 `u` における1階微分を返す
+    pub fn derivative_at(&self, u: f64) -> Vector3 {
+        let (s, c) = u.sin_cos();
+        self.position.x_direction().to_vector() * (-self.major_radius * s)
+            + self.position.y_direction().to_vector() * (self.minor_radius * c)
+    }
+
+    /// 変換を適用した楕円を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        let k = t.scale.abs();
+        Self {
+            position: self.position.transformed(t),
+            major_radius: self.major_radius * k,
+            minor_radius: self.minor_radius * k,
+        }
+    }
+
+    /// 厳密に楕円を表す有理 B-スプライン曲線に変換する（パラメータは角度と区間の端でのみ一致する）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        BSplineCurve::from_ellipse(self)
+    }
+}
+
+impl Parabola {
+    /// 局所座標系（原点が頂点）と焦点距離から放物線を生成する
+    /// 焦点距離が負の場合はエラーを返す
+    pub fn new(position: Axis2, focal_length: f64) -> Result<Self> {
+        check_length("焦点距離", focal_length)?;
+        Ok(Self {
+            position,
+            focal_length,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis2 {
+        self.position
+    }
+
+    /// 頂点を返す
+    pub fn apex(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 焦点距離（頂点から焦点までの距離）を返す
+    pub fn focal_length(&self) -> f64 {
+        self.focal_length
+    }
+
+    /// 焦点を返す
+    pub fn focus(&self) -> Point3 {
+        self.position.point_at(self.focal_length, 0.0, 0.0)
+    }
+
+    /// 準線を返す
+    pub fn directrix(&self) -> Line {
+        let origin = self.position.point_at(-self.focal_length, 0.0, 0.0);
+        Line::new(origin, self.position.y_direction())
+    }
+
+    /// 離心率を返す（常に 1）
+    pub fn eccentricity(&self) -> f64 {
+        1.0
+    }
+
+    /// 半通径（焦点から準線までの距離 `2f`）を返す
+    pub fn parameter(&self) -> f64 {
+        2.0 * self.focal_length
+    }
+
+    /// パラメータ `u` における点を返す
+    /// 焦点距離がゼロの場合は対称軸上の半直線に退化する
+    pub fn point_at(&self, u: f64) -> Point3 {
+        if self.focal_length == 0.0 {
+            return self.position.point_at(u, 0.0, 0.0);
+        }
+        self.position
+            .point_at(u * u / (4.0 * self.focal_length), u, 0.0)
+    }
+
+    /// パラメータ `u` における1階微分を返す
+    pub fn derivative_at(&self, u: f64) -> Vector3 {
+        if self.focal_length == 0.0 {
+            return self.position.x_direction().to_vector();
+        }
+        self.position.x_direction().to_vector() * (u / (2.0 * self.focal_length))
+            + self.position.y_direction().to_vector()
+    }
+
+    /// 変換を適用した放物線を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            position: self.position.transformed(t),
+            focal_length: self.focal_length * t.scale.abs(),
+        }
+    }
+
+    /// パラメータ範囲 `[u1, u2]` を2次の B-スプライン曲線に変換する（パラメータは放物線と同じ値を保つ）
+    ///
+    /// 放物線はパラメータの2次式なので、両端の点と接線の交点を制御点として厳密に表せる。
+    /// `u1 < u2` でない、または有限でない場合はエラーを返す。
+    pub fn to_nurbs(&self, u1: f64, u2: f64) -> Result<BSplineCurve> {
+        check_trim_range(f64::NEG_INFINITY, f64::INFINITY, u1, u2)?;
+        let middle = self.point_at(u1) + self.derivative_at(u1) * ((u2 - u1) / 2.0);
+        BSplineCurve::from_flat_knots(
+            2,
+            vec![self.point_at(u1), middle, self.point_at(u2)],
+            vec![u1, u1, u1, u2, u2, u2],
+        )
+    }
+}
+
+impl Hyperbola {
+    /// 局所座標系と主半径・副半径から双曲線を生成する
+    /// 半径が負の場合はエラーを返す
+    pub fn new(position: Axis2, major_radius: f64, minor_radius: f64) -> Result<Self> {
+        check_length("主半径", major_radius)?;
+        check_length("副半径", minor_radius)?;
+        Ok(Self {
+            position,
+            major_radius,
+            minor_radius,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis2 {
+        self.position
+    }
+
+    /// 中心を返す
+    pub fn center(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 主半径を返す
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    /// 副半径を返す
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    /// 中心から焦点までの距離を返す
+    pub fn focal_distance(&self) -> f64 {
+        self.major_radius.hypot(self.minor_radius)
+    }
+
+    /// 離心率を返す
+    /// 主半径がゼロの場合は無限大を返す
+    pub fn eccentricity(&self) -> f64 {
+        self.focal_distance() / self.major_radius
+    }
+
+    /// 2つの焦点を返す（X方向の正の側が先）
+    pub fn foci(&self) -> (Point3, Point3) {
+        let c = self.focal_distance();
+        (
+            self.position.point_at(c, 0.0, 0.0),
+            self.position.point_at(-c, 0.0, 0.0),
+        )
+    }
+
+    /// 半通径 `b² / a` を返す
+    pub fn parameter(&self) -> f64 {
+        self.minor_radius * self.minor_radius / self.major_radius
+    }
+
+    /// X方向の正の側の準線を返す
+    pub fn directrix(&self) -> Line {
+        let origin = self
+            .position
+            .point_at(self.major_radius / self.eccentricity(), 0.0, 0.0);
+        Line::new(origin, self.position.y_direction())
+    }
+
+    /// 中心を通る2本の漸近線を返す（方向は `a X + b Y` と `a X - b Y`）
+    /// 両半径がゼロの場合は `None` を返す
+    pub fn asymptotes(&self) -> Option<(Line, Line)> {
+        let x = self.position.x_direction().to_vector() * self.major_radius;
+        let y = self.position.y_direction().to_vector() * self.minor_radius;
+        let a = Line::from_points(self.center(), self.center() + (x + y)).ok()?;
+        let b = Line::from_points(self.center(), self.center() + (x - y)).ok()?;
+        Some((a, b))
+    }
+
+    /// パラメータ `u` における点を返す
+    pub fn point_at(&self, u: f64) -> Point3 {
+        self.position.point_at(
+            self.major_radius * u.cosh(),
+            self.minor_radius * u.sinh(),
+            0.0,
+        )
+    }
+
+    /// パラメータ `u` における1階微分を返す
+    pub fn derivative_at(&self, u: f64) -> Vector3 {
+        self.position.x_direction().to_vector() * (self.major_radius * u.sinh())
+            + self.position.y_direction().to_vector() * (self.minor_radius * u.cosh())
+    }
+
+    /// 変換を適用した双曲線を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        let k = t.scale.abs();
+        Self {
+            position: self.position.transformed(t),
+            major_radius: self.major_radius * k,
+            minor_radius: self.minor_radius * k,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dir;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn numeric_derivative(f: impl Fn(f64) -> Point3, u: f64) -> Vector3 {
+        let h = 1e-6;
+        (f(u + h) - f(u - h)) * (0.5 / h)
+    }
+
+    #[test]
+    fn test_ellipse_focal_property() {
+        let e = Ellipse::new(Axis2::world(), 5.0, 3.0).unwrap();
+        assert_eq!(e.focal_distance(), 4.0);
+        assert_eq!(e.eccentricity(), 0.8);
+        assert_eq!(e.parameter(), 1.8);
+        let (f1, f2) = e.foci();
+        assert_point_eq(f1, Point3::new(4.0, 0.0, 0.0));
+        // 2焦点からの距離の和は長径に等しい
+        for u in [0.0, 0.7, 2.0, 4.5] {
+            let p = e.point_at(u);
+            assert!((p.distance(f1) + p.distance(f2) - 10.0).abs() < 1e-12);
+            let d = numeric_derivative(|t| e.point_at(t), u);
+            assert!((d - e.derivative_at(u)).length() < 1e-6);
+        }
+        // 点と焦点の距離 / 点と準線の距離 = 離心率
+        let p = e.point_at(1.1);
+        let ratio = p.distance(f1) / e.directrix().unwrap().distance(p);
+        assert!((ratio - e.eccentricity()).abs() < 1e-12);
+        assert!(Ellipse::new(Axis2::world(), 1.0, 2.0).is_err());
+        assert!(Ellipse::new(Axis2::world(), 1.0, 1.0)
+            .unwrap()
+            .directrix()
+            .is_none());
+    }
+
+    #[test]
+    fn test_parabola_focal_property() {
+        let pos = Axis2::new(Point3::new(1.0, 1.0, 0.0), Dir::Z, Dir::Y).unwrap();
+        let p = Parabola::new(pos, 0.5).unwrap();
+        assert_point_eq(p.focus(), Point3::new(1.0, 1.5, 0.0));
+        for u in [-3.0, -0.2, 0.0, 1.7] {
+            let q = p.point_at(u);
+            // 焦点までの距離と準線までの距離が等しい
+            assert!((q.distance(p.focus()) - p.directrix().distance(q)).abs() < 1e-12);
+            let d = numeric_derivative(|t| p.point_at(t), u);
+            assert!((d - p.derivative_at(u)).length() < 1e-6);
+        }
+        assert!(Parabola::new(pos, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_hyperbola_focal_property() {
+        let h = Hyperbola::new(Axis2::world(), 3.0, 4.0).unwrap();
+        assert_eq!(h.focal_distance(), 5.0);
+        let (f1, f2) = h.foci();
+        for u in [-1.5, 0.0, 0.4, 2.0] {
+            let p = h.point_at(u);
+            // 2焦点からの距離の差は主軸の長さに等しい
+            assert!((p.distance(f2) - p.distance(f1) - 6.0).abs() < 1e-9);
+            let d = numeric_derivative(|t| h.point_at(t), u);
+            assert!((d - h.derivative_at(u)).length() < 1e-5);
+        }
+        // 漸近線に近づいていく
+        let (a1, _) = h.asymptotes().unwrap();
+        assert!(a1.distance(h.point_at(8.0)) < 1e-2);
+        let p = h.point_at(0.8);
+        let ratio = p.distance(f1) / h.directrix().distance(p);
+        assert!((ratio - h.eccentricity()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transformed() {
+        let e = Ellipse::new(Axis2::world(), 2.0, 1.0).unwrap();
+        let t =
+            Transform::from_translation(Vector3::new(0.0, 0.0, 3.0)) * Transform::from_scale(2.0);
+        let moved = e.transformed(&t);
+        assert_eq!(moved.major_radius(), 4.0);
+        assert_point_eq(moved.point_at(0.9), t * e.point_at(0.9));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let h = Hyperbola::new(Axis2::world(), 3.0, 4.0).unwrap();
+        let json = serde_json::to_string(&h).unwrap();
+        let back: Hyperbola = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, h);
+    }
+
+    #[test]
+    fn test_to_nurbs() {
+        let ellipse = Ellipse::new(Axis2::world(), 3.0, 1.0).unwrap();
+        let c = ellipse.to_nurbs();
+        for u in [0.0, 0.4, 2.0, 5.0] {
+            let p = c.point_at(u);
+            assert!(((p.x / 3.0).powi(2) + p.y.powi(2) - 1.0).abs() < 1e-12);
+        }
+
+        let parabola = Parabola::new(Axis2::world(), 0.5).unwrap();
+        let c = parabola.to_nurbs(-2.0, 3.0).unwrap();
+        assert!(!c.is_rational());
+        for u in [-2.0, -0.3, 1.0, 3.0] {
+            assert_point_eq(c.point_at(u), parabola.point_at(u));
+        }
+        assert!(parabola.to_nurbs(1.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+use crate::check::edge_uv;
+use crate::precision::Tolerances;
+use crate::{
+    Curve3, Edge, Face, OcctKrsError, Orientation, Point3, Result, Shape, Surface, Vector3,
+};
+
+/// 2つのフェイスの境界のエッジに沿って連続性を調べる点の数
+const EDGE_SAMPLES: usize = 16;
+
+/// 接続部の幾何学的連続性の段階
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Continuity {
+    /// 位置が一致しない
+    Discontinuous,
+    /// 位置が一致する
+    G0,
+    /// 位置と接線の向きが一致する
+    G1,
+    /// 位置・接線の向き・曲率ベクトルが一致する
+    G2,
+}
+
+/// 接続部の連続性の診断結果
+///
+/// フェイスの間のエッジ（[`check_edge_continuity`]）では、エッジに沿って調べた点での最大値を持つ。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContinuityReport {
+    /// 許容誤差内で満たす最も高い連続性
+    pub continuity: Continuity,
+    /// 2点間の距離
+    pub gap: f64,
+    /// 接線（曲面では法線）のなす角（ラジアン）。どちらかの1階微分がゼロの場合は `None`
+    pub tangent_angle: Option<f64>,
+    /// 曲率ベクトル（主法線方向 × 曲率）の差の大きさ。曲面ではエッジの方向とそれに垂直な方向の
+    /// 法曲率の差の大きさ。接線が定まらない場合は `None`
+    pub curvature_difference: Option<f64>,
+}
+
+/// 曲線 `c1` のパラメータ `u1` と曲線 `c2` のパラメータ `u2` の接続部の連続性を調べる
+///
+/// 位置は `tol.confusion`、接線の角度は `tol.angular`、曲率ベクトルの差は `curvature_tolerance` で判定する。
+pub fn check_continuity(
+    c1: &(impl Curve3 + ?Sized),
+    u1: f64,
+    c2: &(impl Curve3 + ?Sized),
+    u2: f64,
+    tol: &Tolerances,
+    curvature_tolerance: f64,
+) -> ContinuityReport {
+    let gap = c1.point_at(u1).distance(c2.point_at(u2));
+    let (d1, d2) = (c1.derivative_at(u1), c2.derivative_at(u2));
+    let tangent_angle = match (d1.try_normalized(), d2.try_normalized()) {
+        (Ok(t1), Ok(t2)) => Some(t1.cross(t2).length().atan2(t1.dot(t2))),
+        _ => None,
+    };
+    let curvature_difference = tangent_angle.map(|_| {
+        let k1 = curvature_vector(d1, c1.second_derivative_at(u1));
+        let k2 = curvature_vector(d2, c2.second_derivative_at(u2));
+        (k1 - k2).length()
+    });
+
+    report(
+        gap,
+        tangent_angle,
+        curvature_difference,
+        tol,
+        curvature_tolerance,
+    )
+}
+
+/// 位置・接線・曲率の差から、許容誤差内で満たす最も高い連続性を決める
+fn report(
+    gap: f64,
+    tangent_angle: Option<f64>,
+    curvature_difference: Option<f64>,
+    tol: &Tolerances,
+    curvature_tolerance: f64,
+) -> ContinuityReport {
+    let continuity = if gap > tol.confusion {
+        Continuity::Discontinuous
+    } else if !tangent_angle.is_some_and(|a| a <= tol.angular) {
+        Continuity::G0
+    } else if !curvature_difference.is_some_and(|d| d <= curvature_tolerance) {
+        Continuity::G1
+    } else {
+        Continuity::G2
+    };
+    ContinuityReport {
+        continuity,
+        gap,
+        tangent_angle,
+        curvature_difference,
+    }
+}
+
+/// 曲線 `c1` の終点と曲線 `c2` の始点の接続部の連続性を調べる
+pub fn check_junction(
+    c1: &(impl Curve3 + ?Sized),
+    c2: &(impl Curve3 + ?Sized),
+    tol: &Tolerances,
+    curvature_tolerance: f64,
+) -> ContinuityReport {
+    check_continuity(
+        c1,
+        c1.last_parameter(),
+        c2,
+        c2.first_parameter(),
+        tol,
+        curvature_tolerance,
+    )
+}
+
+/// フェイス `face1` と `face2` の境界にあるエッジ `edge` に沿って、2つの曲面の連続性を調べる
+///
+/// エッジに沿った点ごとに、それぞれのフェイスの pcurve（平面では点の射影）で曲面上の点を求め、
+/// 位置のずれ（G0）、向きを考慮した法線のなす角（G1）、エッジの方向とそれに垂直な方向の
+/// 法曲率の差（G2）を調べて、最も悪い値で判定する。曲面の極など法線が定まらない点は
+/// 角と曲率の判定から除く。許容誤差の扱いは [`check_continuity`] と同じ。
+///
+/// エッジが退化エッジの場合や、どちらかのフェイスの境界にない場合、平面以外のフェイスで
+/// pcurve がない場合はエラーを返す。
+pub fn check_edge_continuity(
+    edge: &Edge,
+    face1: &Face,
+    face2: &Face,
+    tol: &Tolerances,
+    curvature_tolerance: f64,
+) -> Result<ContinuityReport> {
+    let Some(curve) = edge.curve() else {
+        return Err(OcctKrsError::InvalidInput(
+            "退化エッジの連続性は調べられません".to_string(),
+        ));
+    };
+    let (first, last) = edge.range();
+    let params: Vec<f64> = (0..=EDGE_SAMPLES)
+        .map(|k| first + (last - first) * k as f64 / EDGE_SAMPLES as f64)
+        .collect();
+    let mut sides = Vec::with_capacity(2);
+    for face in [face1, face2] {
+        if !Shape::from(face.clone())
+            .edges()
+            .iter()
+            .any(|e| e.is_same(edge))
+        {
+            return Err(OcctKrsError::InvalidInput(
+                "エッジがフェイスの境界にありません".to_string(),
+            ));
+        }
+        let uv = edge_uv(face, edge, &params).ok_or_else(|| {
+            OcctKrsError::InvalidInput("フェイスにエッジの pcurve がありません".to_string())
+        })?;
+        sides.push(uv);
+    }
+    let transform = edge.location().transform();
+    let mut gap: f64 = 0.0;
+    let mut angles = Vec::with_capacity(params.len());
+    let mut curvatures = Vec::with_capacity(params.len());
+    for (k, &t) in params.iter().enumerate() {
+        let [a, b] = [(face1, &sides[0]), (face2, &sides[1])]
+            .map(|(face, uv)| SurfacePoint::new(face, uv[k].x, uv[k].y));
+        gap = gap.max(a.point.distance(b.point));
+        let (Some(n1), Some(n2)) = (a.normal, b.normal) else {
+            continue;
+        };
+        angles.push(n1.cross(n2).length().atan2(n1.dot(n2)));
+        let tangent = transform.transform_vector(curve.derivative_at(t));
+        let Ok(tangent) = tangent.try_normalized() else {
+            continue;
+        };
+        let difference = [tangent, n1.cross(tangent)]
+            .iter()
+            .filter_map(|&w| Some((a.normal_curvature(w)? - b.normal_curvature(w)?).abs()))
+            .fold(0.0, f64::max);
+        curvatures.push(difference);
+    }
+    let worst = |values: Vec<f64>| values.into_iter().reduce(f64::max);
+    Ok(report(
+        gap,
+        worst(angles),
+        worst(curvatures),
+        tol,
+        curvature_tolerance,
+    ))
+}
+
+/// フェイスの曲面上の点での位置・向きを考慮した単位法線・基本形式の係数（配置を適用したもの）
+struct SurfacePoint {
+    point: Point3,
+    normal: Option<Vector3>,
+    /// 第1基本形式 `(E, F, G)` と、`normal` を基準にした第2基本形式 `(L, M, N)`
+    forms: [f64; 6],
+    derivatives: [Vector3; 2],
+}
+
+impl SurfacePoint {
+    fn new(face: &Face, u: f64, v: f64) -> Self {
+        let surface = face.surface();
+        let transform = face.location().transform();
+        let map = |v: Vector3| transform.transform_vector(v);
+        let (su, sv) = (
+            map(surface.derivative_u_at(u, v)),
+            map(surface.derivative_v_at(u, v)),
+        );
+        let (suu, suv, svv) = surface.second_derivatives_at(u, v);
+        let (suu, suv, svv) = (map(suu), map(suv), map(svv));
+        // 鏡映を含む配置と反転したフェイスでは、外向きの法線が Su × Sv の逆になる
+        let flip = (transform.scale < 0.0) != (face.orientation() == Orientation::Reversed);
+        let normal = su
+            .cross(sv)
+            .try_normalized()
+            .ok()
+            .map(|n| if flip { -n } else { n });
+        let n = normal.unwrap_or(Vector3::new(0.0, 0.0, 0.0));
+        Self {
+            point: face.point_at(u, v),
+            normal,
+            forms: [
+                su.dot(su),
+                su.dot(sv),
+                sv.dot(sv),
+                suu.dot(n),
+                suv.dot(n),
+                svv.dot(n),
+            ],
+            derivatives: [su, sv],
+        }
+    }
+
+    /// 接平面上の向き `w` の法曲率（法線の側へ曲がる場合に正）
+    fn normal_curvature(&self, w: Vector3) -> Option<f64> {
+        let [e, f, g, l, m, n] = self.forms;
+        let det = e * g - f * f;
+        if det <= f64::MIN_POSITIVE {
+            return None;
+        }
+        // w = a Su + b Sv となる (a, b) を第1基本形式で解く
+        let (wu, wv) = (w.dot(self.derivatives[0]), w.dot(self.derivatives[1]));
+        let (a, b) = ((g * wu - f * wv) / det, (e * wv - f * wu) / det);
+        let first = e * a * a + 2.0 * f * a * b + g * b * b;
+        (first > f64::MIN_POSITIVE).then(|| (l * a * a + 2.0 * m * a * b + n * b * b) / first)
+    }
+}
+
+/// 曲率ベクトル `(C' × C'') × C' / |C'|⁴`
+fn curvature_vector(d1: Vector3, d2: Vector3) -> Vector3 {
+    let len2 = d1.dot(d1);
+    d1.cross(d2).cross(d1) / (len2 * len2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arc, Axis2, BezierCurve, Circle, Point3, Segment};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn tolerances() -> Tolerances {
+        Tolerances::new(1e-9, 1e-9, 1e-9)
+    }
+
+    #[test]
+    fn test_levels() {
+        let a = Segment::new(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0));
+        let tol = tolerances();
+
+        let gap = Segment::new(Point3::new(1.0, 0.1, 0.0), Point3::new(2.0, 0.0, 0.0));
+        let r = check_junction(&a, &gap, &tol, 1e-9);
+        assert_eq!(r.continuity, Continuity::Discontinuous);
+        assert!((r.gap - 0.1).abs() < 1e-15);
+
+        let corner = Segment::new(Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        let r = check_junction(&a, &corner, &tol, 1e-9);
+        assert_eq!(r.continuity, Continuity::G0);
+        assert!((r.tangent_angle.unwrap() - FRAC_PI_2).abs() < 1e-15);
+
+        // 長さの違う同じ向きの線分は G2（曲率はどちらもゼロ）
+        let longer = Segment::new(Point3::new(1.0, 0.0, 0.0), Point3::new(4.0, 0.0, 0.0));
+        let r = check_junction(&a, &longer, &tol, 1e-9);
+        assert_eq!(r.continuity, Continuity::G2);
+        assert!(r.continuity >= Continuity::G1);
+    }
+
+    #[test]
+    fn test_line_to_arc_is_g1_only() {
+        // 直線から接する円弧へのつなぎは曲率が不連続
+        let a = Segment::new(Point3::new(-1.0, -1.0, 0.0), Point3::new(0.0, -1.0, 0.0));
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        let arc = Arc::new(circle, -FRAC_PI_2, 0.0).unwrap();
+        let r = check_junction(&a, &arc, &tolerances(), 1e-6);
+        assert_eq!(r.continuity, Continuity::G1);
+        assert!((r.curvature_difference.unwrap() - 1.0).abs() < 1e-9);
+
+        // 同じ円の隣り合う円弧は G2
+        let next = Arc::new(circle, 0.0, PI).unwrap();
+        let r = check_junction(&arc, &next, &tolerances(), 1e-6);
+        assert_eq!(r.continuity, Continuity::G2);
+    }
+
+    /// 2つのフェイスが共有するエッジ
+    fn shared_edge(a: &Face, b: &Face) -> Option<Edge> {
+        let edges = Shape::from(b.clone()).edges();
+        Shape::from(a.clone())
+            .edges()
+            .into_iter()
+            .find(|e| edges.iter().any(|f| f.is_same(e)))
+    }
+
+    #[test]
+    fn test_edge_between_faces() {
+        use crate::fillet::fillet;
+        use crate::primitives::make_box;
+        use crate::GeomSurface;
+        let tol = tolerances();
+        let position = Axis2::new(Point3::ORIGIN, crate::Dir::Z, crate::Dir::X).unwrap();
+        let cube = Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap());
+        // 直方体の稜線は直角に折れる
+        let faces = cube.faces();
+        let (a, b, edge) = faces
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| faces[i + 1..].iter().map(move |b| (a, b)))
+            .find_map(|(a, b)| shared_edge(a, b).map(|e| (a, b, e)))
+            .unwrap();
+        let r = check_edge_continuity(&edge, a, b, &tol, 1e-6).unwrap();
+        assert_eq!(r.continuity, Continuity::G0);
+        assert!(r.gap < 1e-12);
+        assert!((r.tangent_angle.unwrap() - FRAC_PI_2).abs() < 1e-12);
+
+        // 丸めの円柱面は隣の平面に接するが、稜線に垂直な向きの曲率が 1/r だけ違う
+        let edge = cube
+            .edges()
+            .into_iter()
+            .find(|e| {
+                let (p, q) = (e.start_point(), e.end_point());
+                p.x == 1.0 && p.y == 1.0 && q.x == 1.0 && q.y == 1.0
+            })
+            .unwrap();
+        let rounded = fillet(&cube, &[edge], 0.5).unwrap();
+        let faces = rounded.faces();
+        let cylinder = faces
+            .iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Cylinder(_)))
+            .unwrap();
+        let cylinder_edges = Shape::from(cylinder.clone()).edges();
+        // 円柱面と縦の直線の稜線を共有する側面
+        let side = faces
+            .iter()
+            .filter(|f| matches!(f.surface(), GeomSurface::Plane(_)))
+            .find(|f| {
+                Shape::from((*f).clone()).edges().iter().any(|e| {
+                    (e.end_point().z - e.start_point().z).abs() > 0.5
+                        && cylinder_edges.iter().any(|c| c.is_same(e))
+                })
+            })
+            .unwrap();
+        let edge = shared_edge(cylinder, side).unwrap();
+        let r = check_edge_continuity(&edge, cylinder, side, &tol, 1e-6).unwrap();
+        assert_eq!(r.continuity, Continuity::G1);
+        assert!(r.tangent_angle.unwrap() < 1e-9);
+        assert!((r.curvature_difference.unwrap() - 2.0).abs() < 1e-6);
+        // 曲率の許容誤差を大きくすれば G2 とみなす
+        let r = check_edge_continuity(&edge, side, cylinder, &tol, 2.5).unwrap();
+        assert_eq!(r.continuity, Continuity::G2);
+    }
+
+    #[test]
+    fn test_edge_between_coplanar_faces() {
+        use crate::{Plane, Vertex, Wire};
+        let p = |x: f64, y: f64| Vertex::new(Point3::new(x, y, 0.0));
+        let v = [
+            p(0.0, 0.0),
+            p(1.0, 0.0),
+            p(1.0, 1.0),
+            p(0.0, 1.0),
+            p(2.0, 0.0),
+            p(2.0, 1.0),
+        ];
+        let segment = |a: usize, b: usize| {
+            let line = crate::Line::from_points(v[a].point(), v[b].point()).unwrap();
+            let length = v[a].point().distance(v[b].point());
+            Edge::new(line, (0.0, length), v[a].clone(), v[b].clone()).unwrap()
+        };
+        let shared = segment(1, 2);
+        let left = Face::new(
+            Plane::xy(),
+            vec![Wire::new(vec![
+                segment(0, 1),
+                shared.clone(),
+                segment(2, 3),
+                segment(3, 0),
+            ])
+            .unwrap()],
+        )
+        .unwrap();
+        let right = Face::new(
+            Plane::xy(),
+            vec![Wire::new(vec![
+                segment(1, 4),
+                segment(4, 5),
+                segment(5, 2),
+                shared.reversed(),
+            ])
+            .unwrap()],
+        )
+        .unwrap();
+        let r = check_edge_continuity(&shared, &left, &right, &tolerances(), 1e-9).unwrap();
+        assert_eq!(r.continuity, Continuity::G2);
+        assert_eq!(r.curvature_difference, Some(0.0));
+        // 片方のフェイスを反転すると法線が逆向きになる
+        let r =
+            check_edge_continuity(&shared, &left, &right.reversed(), &tolerances(), 1e-9).unwrap();
+        assert_eq!(r.continuity, Continuity::G0);
+        assert!((r.tangent_angle.unwrap() - PI).abs() < 1e-12);
+        // 境界にないエッジ
+        assert!(check_edge_continuity(&segment(4, 5), &left, &right, &tolerances(), 1e-9).is_err());
+    }
+
+    #[test]
+    fn test_degenerate_tangent() {
+        let a = Segment::new(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0));
+        // 始点側の制御点が重なり、始点で1階微分がゼロになるベジエ曲線
+        let b = BezierCurve::new(vec![
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 0.0),
+        ])
+        .unwrap();
+        let r = check_junction(&a, &b, &tolerances(), 1e-6);
+        assert_eq!(r.continuity, Continuity::G0);
+        assert_eq!(r.tangent_angle, None);
+        assert_eq!(r.curvature_difference, None);
+    }
+}
@@ -0,0 +1,1034 @@
+use std::cmp::Ordering;
+
+use crate::precision;
+use crate::projection::{project_point, CurveProjection};
+use crate::{
+    Arc, BSplineCurve, BezierCurve, BoundingBox, Circle, Curve2, CurveOnSurface, Dir, Ellipse,
+    GeomCurve, Helix, Hyperbola, Line, OcctKrsError, Parabola, Point3, Polyline3, Result, Segment,
+    Surface, Vector3,
+};
+
+/// 曲線上の点におけるフレネ標構（接線・主法線・従法線）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrenetFrame {
+    pub origin: Point3,
+    pub tangent: Dir,
+    pub normal: Dir,
+    pub binormal: Dir,
+}
+
+/// 3次元のパラメトリック曲線（OCCT の `Geom_Curve` 相当）
+///
+/// テッセレーションや交差計算などのアルゴリズムは `&dyn Curve3` を受け取るので、
+/// 利用側で独自の曲線を実装して渡すこともできる。
+pub trait Curve3 {
+    /// パラメータ `t` における点を返す
+    fn point_at(&self, t: f64) -> Point3;
+
+    /// パラメータ `t` における1階微分を返す
+    fn derivative_at(&self, t: f64) -> Vector3;
+
+    /// パラメータ `t` における2階微分を返す
+    ///
+    /// 既定の実装は1階微分の中心差分による近似。
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        let h = difference_step(t);
+        (self.derivative_at(t + h) - self.derivative_at(t - h)) / (2.0 * h)
+    }
+
+    /// パラメータ `t` における3階微分を返す
+    ///
+    /// 既定の実装は2階微分の中心差分による近似。
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        let h = difference_step(t);
+        (self.second_derivative_at(t + h) - self.second_derivative_at(t - h)) / (2.0 * h)
+    }
+
+    /// パラメータ範囲の始まりを返す（無限に延びる場合は負の無限大）
+    fn first_parameter(&self) -> f64;
+
+    /// パラメータ範囲の終わりを返す（無限に延びる場合は正の無限大）
+    fn last_parameter(&self) -> f64;
+
+    /// 始点と終点が一致していれば `true` を返す
+    fn is_closed(&self) -> bool {
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        a.is_finite()
+            && b.is_finite()
+            && self.point_at(a).distance(self.point_at(b)) <= precision::confusion()
+    }
+
+    /// 曲線を囲む境界ボックスを返す
+    ///
+    /// 既定の実装はパラメータ範囲を等分した点から求める近似で、厳密に曲線全体を含むとは限らない。
+    fn bounding_box(&self) -> BoundingBox {
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        if !(a.is_finite() && b.is_finite()) {
+            return BoundingBox::infinite();
+        }
+        BoundingBox::from_points(tessellate(self, 64)).unwrap()
+    }
+
+    /// 点に最も近い曲線上の点を返す（距離が等しい点が複数あればパラメータ順にすべて）
+    ///
+    /// 既定の実装はパラメータ範囲を分割して初期値を探し、ニュートン法で精密化する。
+    /// パラメータ範囲が無限の場合は空を返す。
+    fn project(&self, p: Point3) -> Vec<CurveProjection> {
+        project_point(self, p)
+    }
+
+    /// パラメータ `t0` から `t1` までの弧長を適応 Gauss–Legendre 積分で計算する
+    /// `t1 < t0` の場合は負の値を返す
+    fn arc_length_between(&self, t0: f64, t1: f64) -> f64 {
+        if !(t0.is_finite() && t1.is_finite()) {
+            return f64::INFINITY;
+        }
+        let speed = |t: f64| self.derivative_at(t).length();
+        let whole = gauss_legendre(&speed, t0, t1);
+        adaptive_length(
+            &speed,
+            t0,
+            t1,
+            whole,
+            LENGTH_TOLERANCE * whole.abs().max(1e-300),
+            0,
+        )
+    }
+
+    /// パラメータ範囲全体の弧長を返す（無限に延びる場合は無限大）
+    fn arc_length(&self) -> f64 {
+        self.arc_length_between(self.first_parameter(), self.last_parameter())
+    }
+
+    /// 始点からの弧長が `s` となるパラメータを返す
+    /// `s` が 0 から全長の範囲外、またはパラメータ範囲が無限の場合は `None` を返す
+    fn parameter_at_length(&self, s: f64) -> Option<f64> {
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        let total = self.arc_length();
+        if !total.is_finite() || !(0.0..=total).contains(&s) {
+            return None;
+        }
+        if total == 0.0 {
+            return Some(a);
+        }
+        // 弧長はパラメータについて単調増加なので、二分法で保護したニュートン法で解く
+        let (mut lo, mut hi) = (a, b);
+        let mut t = a + (b - a) * (s / total);
+        for _ in 0..100 {
+            let f = self.arc_length_between(a, t) - s;
+            if f.abs() <= LENGTH_TOLERANCE * total.max(1.0) {
+                break;
+            }
+            if f > 0.0 {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let speed = self.derivative_at(t).length();
+            let next = if speed > 0.0 { t - f / speed } else { f64::NAN };
+            t = if next > lo && next < hi {
+                next
+            } else {
+                0.5 * (lo + hi)
+            };
+        }
+        Some(t)
+    }
+
+    /// パラメータ `t` における曲率 `|C' × C''| / |C'|³` を返す
+    /// 1階微分がゼロ（特異点）の場合は 0 を返す
+    fn curvature_at(&self, t: f64) -> f64 {
+        let d1 = self.derivative_at(t);
+        let len = d1.length();
+        if len <= f64::MIN_POSITIVE {
+            return 0.0;
+        }
+        d1.cross(self.second_derivative_at(t)).length() / (len * len * len)
+    }
+
+    /// パラメータ `t` における捩率 `(C' × C'') · C''' / |C' × C''|²` を返す
+    /// 曲率がゼロの場合は 0 を返す
+    fn torsion_at(&self, t: f64) -> f64 {
+        let c = self.derivative_at(t).cross(self.second_derivative_at(t));
+        let n2 = c.dot(c);
+        if n2 <= f64::MIN_POSITIVE {
+            return 0.0;
+        }
+        c.dot(self.third_derivative_at(t)) / n2
+    }
+
+    /// パラメータ `t` におけるフレネ標構を返す
+    /// 1階微分がゼロ、または曲率がゼロで主法線が定まらない場合は `None` を返す
+    fn frenet_frame_at(&self, t: f64) -> Option<FrenetFrame> {
+        let d1 = self.derivative_at(t);
+        let d2 = self.second_derivative_at(t);
+        let tangent = Dir::from_vector(d1).ok()?;
+        let b = d1.cross(d2);
+        if b.length() <= precision::angular() * d1.length() * d2.length() {
+            return None;
+        }
+        let binormal = Dir::from_vector(b).ok()?;
+        let normal = Dir::from_vector(b.cross(d1)).ok()?;
+        Some(FrenetFrame {
+            origin: self.point_at(t),
+            tangent,
+            normal,
+            binormal,
+        })
+    }
+}
+
+/// 基になる曲線のパラメータ範囲を `[first, last]` に制限した曲線（OCCT の `Geom_TrimmedCurve` 相当）
+///
+/// パラメータは基の曲線と同じものをそのまま使う。
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrimmedCurve<C> {
+    basis: C,
+    first: f64,
+    last: f64,
+}
+
+impl<C: Curve3> TrimmedCurve<C> {
+    /// 基の曲線のパラメータ範囲 `[u1, u2]` を切り出す
+    /// `u1 < u2` でない、または基の曲線の範囲外の場合はエラーを返す
+    pub fn new(basis: C, u1: f64, u2: f64) -> Result<Self> {
+        check_trim_range(basis.first_parameter(), basis.last_parameter(), u1, u2)?;
+        Ok(Self {
+            basis,
+            first: u1,
+            last: u2,
+        })
+    }
+
+    /// 基の曲線を返す
+    pub fn basis(&self) -> &C {
+        &self.basis
+    }
+
+    /// パラメータ範囲 `[u1, u2]` をさらに切り出す
+    pub fn trim(&self, u1: f64, u2: f64) -> Result<Self>
+    where
+        C: Clone,
+    {
+        check_trim_range(self.first, self.last, u1, u2)?;
+        Ok(Self {
+            basis: self.basis.clone(),
+            first: u1,
+            last: u2,
+        })
+    }
+
+    /// 指定したパラメータで分割する
+    pub fn split_at(&self, params: &[f64]) -> Result<Vec<Self>>
+    where
+        C: Clone,
+    {
+        split_ranges(self.first, self.last, params)?
+            .into_iter()
+            .map(|(a, b)| self.trim(a, b))
+            .collect()
+    }
+}
+
+impl<C: Curve3> Curve3 for TrimmedCurve<C> {
+    fn point_at(&self, t: f64) -> Point3 {
+        self.basis.point_at(t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        self.basis.derivative_at(t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.basis.second_derivative_at(t)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        self.basis.third_derivative_at(t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        self.first
+    }
+
+    fn last_parameter(&self) -> f64 {
+        self.last
+    }
+}
+
+/// 切り出す範囲 `[u1, u2]` が `[first, last]` に含まれる有限の区間か確認する
+pub(crate) fn check_trim_range(first: f64, last: f64, u1: f64, u2: f64) -> Result<()> {
+    if !(u1.is_finite() && u2.is_finite() && u1 < u2 && first <= u1 && u2 <= last) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "切り出す範囲 [{}, {}] がパラメータ範囲 [{}, {}] に含まれません",
+            u1, u2, first, last
+        )));
+    }
+    Ok(())
+}
+
+/// `[first, last]` を分割パラメータで区切った区間の列を返す
+/// 分割パラメータは範囲の内部で狭義単調増加である必要がある
+pub(crate) fn split_ranges(first: f64, last: f64, params: &[f64]) -> Result<Vec<(f64, f64)>> {
+    let mut bounds = Vec::with_capacity(params.len() + 2);
+    bounds.push(first);
+    bounds.extend_from_slice(params);
+    bounds.push(last);
+    if bounds
+        .windows(2)
+        .any(|w| w[0].partial_cmp(&w[1]) != Some(Ordering::Less))
+    {
+        return Err(OcctKrsError::InvalidInput(
+            "分割パラメータは範囲の内部で狭義単調増加である必要があります".to_string(),
+        ));
+    }
+    Ok(bounds.windows(2).map(|w| (w[0], w[1])).collect())
+}
+
+/// 弧長計算の相対許容誤差
+const LENGTH_TOLERANCE: f64 = 1e-12;
+
+/// 5点 Gauss–Legendre 積分
+pub(crate) fn gauss_legendre(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    const NODES: [(f64, f64); 5] = [
+        (0.0, 0.568_888_888_888_888_9),
+        (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+        (-0.906_179_845_938_664, 0.236_926_885_056_189_08),
+        (0.906_179_845_938_664, 0.236_926_885_056_189_08),
+    ];
+    let (c, h) = (0.5 * (a + b), 0.5 * (b - a));
+    NODES.iter().map(|(x, w)| w * f(c + h * x)).sum::<f64>() * h
+}
+
+/// 区間を二分しながら、二分前後の積分値の差が許容誤差以下になるまで細分する
+fn adaptive_length(
+    f: &dyn Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    whole: f64,
+    tol: f64,
+    depth: u32,
+) -> f64 {
+    let m = 0.5 * (a + b);
+    let left = gauss_legendre(f, a, m);
+    let right = gauss_legendre(f, m, b);
+    if depth >= 30 || (left + right - whole).abs() <= tol {
+        return left + right;
+    }
+    adaptive_length(f, a, m, left, tol * 0.5, depth + 1)
+        + adaptive_length(f, m, b, right, tol * 0.5, depth + 1)
+}
+
+/// 弧長が等間隔になるよう `segments` 等分した点列を返す（両端を含む `segments + 1` 点）
+/// パラメータ範囲が無限の場合は `None` を返す
+pub fn tessellate_by_length(
+    curve: &(impl Curve3 + ?Sized),
+    segments: usize,
+) -> Option<Vec<Point3>> {
+    let total = curve.arc_length();
+    if !total.is_finite() {
+        return None;
+    }
+    if segments == 0 {
+        return Some(vec![curve.point_at(curve.first_parameter())]);
+    }
+    (0..=segments)
+        .map(|i| {
+            let s = (total * i as f64 / segments as f64).min(total);
+            curve.parameter_at_length(s).map(|t| curve.point_at(t))
+        })
+        .collect()
+}
+
+/// パラメータ `t` の点を射影の結果として返す
+fn projection_at(curve: &impl Curve3, p: Point3, t: f64) -> CurveProjection {
+    let point = curve.point_at(t);
+    CurveProjection {
+        parameter: t,
+        point,
+        distance: point.distance(p),
+    }
+}
+
+/// 中心差分の刻み幅（パラメータの大きさに応じて調整する）
+fn difference_step(t: f64) -> f64 {
+    1e-4 * t.abs().max(1.0)
+}
+
+/// パラメータ範囲を `segments` 等分した点列を返す（両端を含む `segments + 1` 点）
+/// `segments` が 0 の場合は始点のみを返す
+pub fn tessellate(curve: &(impl Curve3 + ?Sized), segments: usize) -> Vec<Point3> {
+    let (a, b) = (curve.first_parameter(), curve.last_parameter());
+    if segments == 0 {
+        return vec![curve.point_at(a)];
+    }
+    (0..=segments)
+        .map(|i| curve.point_at(a + (b - a) * i as f64 / segments as f64))
+        .collect()
+}
+
+impl Curve3 for Line {
+    fn point_at(&self, t: f64) -> Point3 {
+        Line::point_at(self, t)
+    }
+
+    fn derivative_at(&self, _t: f64) -> Vector3 {
+        self.dir.to_vector()
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn third_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn first_parameter(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+
+    fn last_parameter(&self) -> f64 {
+        f64::INFINITY
+    }
+
+    /// 座標軸に平行な方向では有限、それ以外では無限に延びる
+    fn bounding_box(&self) -> BoundingBox {
+        let o = self.origin.to_vector().to_array();
+        let d = self.dir.to_vector().to_array();
+        let mut min = o;
+        let mut max = o;
+        for i in 0..3 {
+            if d[i] != 0.0 {
+                min[i] = f64::NEG_INFINITY;
+                max[i] = f64::INFINITY;
+            }
+        }
+        BoundingBox::new(
+            Point3::from(Vector3::from(min)),
+            Point3::from(Vector3::from(max)),
+        )
+    }
+
+    fn project(&self, p: Point3) -> Vec<CurveProjection> {
+        vec![projection_at(self, p, self.parameter_of(p))]
+    }
+}
+
+impl Curve3 for Segment {
+    fn point_at(&self, t: f64) -> Point3 {
+        Segment::point_at(self, t)
+    }
+
+    fn derivative_at(&self, _t: f64) -> Vector3 {
+        self.vector()
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn third_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        1.0
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::new(self.start, self.end)
+    }
+}
+
+impl Curve3 for Polyline3 {
+    fn point_at(&self, t: f64) -> Point3 {
+        Polyline3::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        self.segment(self.locate(t).0).vector()
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn third_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        self.segment_count() as f64
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        Polyline3::bounding_box(self)
+    }
+
+    fn arc_length_between(&self, t0: f64, t1: f64) -> f64 {
+        self.length_to(t1) - self.length_to(t0)
+    }
+}
+
+impl Curve3 for Circle {
+    fn point_at(&self, t: f64) -> Point3 {
+        Circle::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        self.tangent_at(t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.center() - Circle::point_at(self, t)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        -self.tangent_at(t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        std::f64::consts::TAU
+    }
+
+    fn is_closed(&self) -> bool {
+        true
+    }
+
+    /// 各軸方向の広がりは `r √(1 - n_i²)`（`n` は法線）になる
+    fn bounding_box(&self) -> BoundingBox {
+        let n = self.axis().direction.to_vector();
+        let r = self.radius();
+        let e = Vector3::new(
+            r * (1.0 - n.x * n.x).max(0.0).sqrt(),
+            r * (1.0 - n.y * n.y).max(0.0).sqrt(),
+            r * (1.0 - n.z * n.z).max(0.0).sqrt(),
+        );
+        BoundingBox::new(self.center() - e, self.center() + e)
+    }
+
+    fn project(&self, p: Point3) -> Vec<CurveProjection> {
+        vec![projection_at(self, p, self.parameter_of(p))]
+    }
+}
+
+/// 円弧のパラメータは元の円の角度（`start_angle` から `end_angle`）
+impl Curve3 for Arc {
+    fn point_at(&self, t: f64) -> Point3 {
+        self.point_at_angle(t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        self.circle().tangent_at(t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.circle().center() - self.point_at_angle(t)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        -self.circle().tangent_at(t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        self.start_angle()
+    }
+
+    fn last_parameter(&self) -> f64 {
+        self.end_angle()
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BSplineCurve::from_arc(self).bounding_box()
+    }
+}
+
+impl Curve3 for Ellipse {
+    fn point_at(&self, t: f64) -> Point3 {
+        Ellipse::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        Ellipse::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.center() - Ellipse::point_at(self, t)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        -Ellipse::derivative_at(self, t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        std::f64::consts::TAU
+    }
+
+    fn is_closed(&self) -> bool {
+        true
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BSplineCurve::from_ellipse(self).bounding_box()
+    }
+}
+
+impl Curve3 for Helix {
+    fn point_at(&self, t: f64) -> Point3 {
+        Helix::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        Helix::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 2)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 3)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        Helix::last_parameter(self)
+    }
+
+    fn arc_length_between(&self, t0: f64, t1: f64) -> f64 {
+        // 速さが一定なので弧長はパラメータに比例する
+        self.derivative_at(t0).length() * (t1 - t0)
+    }
+}
+
+impl Curve3 for Parabola {
+    fn point_at(&self, t: f64) -> Point3 {
+        Parabola::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        Parabola::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector3 {
+        if self.focal_length() == 0.0 {
+            return Vector3::ZERO;
+        }
+        self.position().x_direction().to_vector() / (2.0 * self.focal_length())
+    }
+
+    fn third_derivative_at(&self, _t: f64) -> Vector3 {
+        Vector3::ZERO
+    }
+
+    fn first_parameter(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+
+    fn last_parameter(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Curve3 for Hyperbola {
+    fn point_at(&self, t: f64) -> Point3 {
+        Hyperbola::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        Hyperbola::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        Hyperbola::point_at(self, t) - self.center()
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        Hyperbola::derivative_at(self, t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+
+    fn last_parameter(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Curve3 for BezierCurve {
+    fn point_at(&self, t: f64) -> Point3 {
+        BezierCurve::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        BezierCurve::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 2)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 3)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        0.0
+    }
+
+    fn last_parameter(&self) -> f64 {
+        1.0
+    }
+
+    /// 凸包性により制御点の境界ボックスは曲線全体を含む
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_points(self.control_points().iter().copied()).unwrap()
+    }
+}
+
+impl Curve3 for BSplineCurve {
+    fn point_at(&self, t: f64) -> Point3 {
+        BSplineCurve::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        BSplineCurve::derivative_at(self, t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.derivatives_at(t, 2)[2]
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        self.derivatives_at(t, 3)[3]
+    }
+
+    fn first_parameter(&self) -> f64 {
+        BSplineCurve::first_parameter(self)
+    }
+
+    fn last_parameter(&self) -> f64 {
+        BSplineCurve::last_parameter(self)
+    }
+
+    /// 凸包性（重みが正の場合は有理曲線でも成り立つ）により制御点の境界ボックスは曲線全体を含む
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_points(self.control_points().iter().copied()).unwrap()
+    }
+}
+
+impl GeomCurve {
+    /// 保持している曲線を `Curve3` として返す
+    fn as_curve3(&self) -> &dyn Curve3 {
+        match self {
+            GeomCurve::Line(c) => c,
+            GeomCurve::Circle(c) => c,
+            GeomCurve::Ellipse(c) => c,
+            GeomCurve::Parabola(c) => c,
+            GeomCurve::Hyperbola(c) => c,
+            GeomCurve::Helix(c) => c,
+            GeomCurve::BSpline(c) => c,
+        }
+    }
+}
+
+/// 各メソッドは保持している曲線の実装に委ねる
+impl Curve3 for GeomCurve {
+    fn point_at(&self, t: f64) -> Point3 {
+        self.as_curve3().point_at(t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        self.as_curve3().derivative_at(t)
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        self.as_curve3().second_derivative_at(t)
+    }
+
+    fn third_derivative_at(&self, t: f64) -> Vector3 {
+        self.as_curve3().third_derivative_at(t)
+    }
+
+    fn first_parameter(&self) -> f64 {
+        self.as_curve3().first_parameter()
+    }
+
+    fn last_parameter(&self) -> f64 {
+        self.as_curve3().last_parameter()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.as_curve3().is_closed()
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.as_curve3().bounding_box()
+    }
+
+    fn project(&self, p: Point3) -> Vec<CurveProjection> {
+        self.as_curve3().project(p)
+    }
+
+    fn arc_length_between(&self, t0: f64, t1: f64) -> f64 {
+        self.as_curve3().arc_length_between(t0, t1)
+    }
+}
+
+impl<C: Curve2, S: Surface> Curve3 for CurveOnSurface<C, S> {
+    fn point_at(&self, t: f64) -> Point3 {
+        CurveOnSurface::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector3 {
+        let uv = self.pcurve().point_at(t);
+        let d = self.pcurve().derivative_at(t);
+        let s = self.surface();
+        s.derivative_u_at(uv.x, uv.y) * d.x + s.derivative_v_at(uv.x, uv.y) * d.y
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector3 {
+        let uv = self.pcurve().point_at(t);
+        let d = self.pcurve().derivative_at(t);
+        let dd = self.pcurve().second_derivative_at(t);
+        let s = self.surface();
+        let (suu, suv, svv) = s.second_derivatives_at(uv.x, uv.y);
+        suu * (d.x * d.x)
+            + suv * (2.0 * d.x * d.y)
+            + svv * (d.y * d.y)
+            + s.derivative_u_at(uv.x, uv.y) * dd.x
+            + s.derivative_v_at(uv.x, uv.y) * dd.y
+    }
+
+    fn first_parameter(&self) -> f64 {
+        self.pcurve().first_parameter()
+    }
+
+    fn last_parameter(&self) -> f64 {
+        self.pcurve().last_parameter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, Dir};
+
+    /// 利用側で定義する独自の曲線の例（つるまき線）
+    struct Spiral;
+
+    impl Curve3 for Spiral {
+        fn point_at(&self, t: f64) -> Point3 {
+            Point3::new(t.cos(), t.sin(), 0.1 * t)
+        }
+        fn derivative_at(&self, t: f64) -> Vector3 {
+            Vector3::new(-t.sin(), t.cos(), 0.1)
+        }
+        fn first_parameter(&self) -> f64 {
+            0.0
+        }
+        fn last_parameter(&self) -> f64 {
+            10.0
+        }
+    }
+
+    fn curves() -> Vec<Box<dyn Curve3>> {
+        let circle = Circle::from_center_normal(Point3::new(1.0, 0.0, 0.0), Dir::Z, 2.0).unwrap();
+        vec![
+            Box::new(Segment::new(Point3::origin(), Point3::new(1.0, 2.0, 3.0))),
+            Box::new(circle),
+            Box::new(Arc::new(circle, 0.5, 2.0).unwrap()),
+            Box::new(Ellipse::new(Axis2::world(), 3.0, 1.0).unwrap()),
+            Box::new(
+                BezierCurve::new(vec![
+                    Point3::origin(),
+                    Point3::new(1.0, 3.0, 0.0),
+                    Point3::new(2.0, -1.0, 1.0),
+                ])
+                .unwrap(),
+            ),
+            Box::new(BSplineCurve::from_circle(&circle)),
+            Box::new(Spiral),
+        ]
+    }
+
+    #[test]
+    fn test_dyn_dispatch() {
+        for c in curves() {
+            let c: &dyn Curve3 = c.as_ref();
+            let pts = tessellate(c, 50);
+            assert_eq!(pts.len(), 51);
+            // 境界ボックスは曲線上の点を含む
+            let b = c.bounding_box().enlarged(1e-9);
+            assert!(pts.iter().all(|p| b.contains(*p)));
+            // 微分は数値微分と一致する
+            let t = c.first_parameter() * 0.7 + c.last_parameter() * 0.3;
+            let h = 1e-6;
+            let numeric = (c.point_at(t + h) - c.point_at(t - h)) * (0.5 / h);
+            assert!((numeric - c.derivative_at(t)).length() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_is_closed() {
+        let closed: Vec<bool> = curves().iter().map(|c| c.is_closed()).collect();
+        assert_eq!(closed, vec![false, true, false, true, false, true, false]);
+        assert!(!Curve3::is_closed(&Line::new(Point3::origin(), Dir::X)));
+    }
+
+    #[test]
+    fn test_exact_boxes() {
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 2.0).unwrap();
+        let b = Curve3::bounding_box(&circle);
+        assert!((b.min - Point3::new(-2.0, -2.0, 0.0)).length() < 1e-12);
+        assert!((b.max - Point3::new(2.0, 2.0, 0.0)).length() < 1e-12);
+
+        let l = Line::new(Point3::new(1.0, 2.0, 3.0), Dir::Y);
+        let b = Curve3::bounding_box(&l);
+        assert_eq!((b.min.x, b.max.x), (1.0, 1.0));
+        assert!(b.min.y.is_infinite() && b.is_infinite());
+    }
+
+    #[test]
+    fn test_higher_derivatives() {
+        for c in curves() {
+            let c: &dyn Curve3 = c.as_ref();
+            let t = c.first_parameter() * 0.4 + c.last_parameter() * 0.6;
+            let h = 1e-5;
+            let n2 = (c.derivative_at(t + h) - c.derivative_at(t - h)) * (0.5 / h);
+            assert!((n2 - c.second_derivative_at(t)).length() < 1e-5);
+            let n3 = (c.second_derivative_at(t + h) - c.second_derivative_at(t - h)) * (0.5 / h);
+            assert!((n3 - c.third_derivative_at(t)).length() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_curvature_and_torsion() {
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 2.0).unwrap();
+        assert!((circle.curvature_at(0.3) - 0.5).abs() < 1e-12);
+        assert!(circle.torsion_at(0.3).abs() < 1e-12);
+
+        // つるまき線 (cos t, sin t, 0.1 t) の曲率は 1 / 1.01、捩率は 0.1 / 1.01
+        let s = Spiral;
+        assert!((s.curvature_at(2.0) - 1.0 / 1.01).abs() < 1e-6);
+        assert!((s.torsion_at(2.0) - 0.1 / 1.01).abs() < 1e-5);
+
+        let seg = Segment::new(Point3::origin(), Point3::new(1.0, 1.0, 0.0));
+        assert_eq!(seg.curvature_at(0.5), 0.0);
+        assert_eq!(seg.torsion_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_frenet_frame() {
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 2.0).unwrap();
+        let f = circle.frenet_frame_at(0.0).unwrap();
+        assert_eq!(f.origin, Point3::new(2.0, 0.0, 0.0));
+        // 主法線は中心を向き、従法線は円の法線と一致する
+        assert!((f.tangent.to_vector() - Vector3::Y).length() < 1e-12);
+        assert!((f.normal.to_vector() + Vector3::X).length() < 1e-12);
+        assert!((f.binormal.to_vector() - Vector3::Z).length() < 1e-12);
+
+        let f = Spiral.frenet_frame_at(1.0).unwrap();
+        assert!(f.tangent.dot(f.normal).abs() < 1e-9);
+        assert!(
+            (f.tangent.to_vector().cross(f.normal.to_vector()) - f.binormal.to_vector()).length()
+                < 1e-9
+        );
+
+        let seg = Segment::new(Point3::origin(), Point3::new(1.0, 1.0, 0.0));
+        assert!(seg.frenet_frame_at(0.5).is_none());
+    }
+
+    #[test]
+    fn test_arc_length() {
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 2.0).unwrap();
+        assert!((circle.arc_length() - 4.0 * std::f64::consts::PI).abs() < 1e-10);
+        let seg = Segment::new(Point3::origin(), Point3::new(3.0, 4.0, 0.0));
+        assert!((Curve3::arc_length(&seg) - 5.0).abs() < 1e-12);
+        // つるまき線の長さは √1.01 · t
+        assert!((Spiral.arc_length() - 1.01f64.sqrt() * 10.0).abs() < 1e-9);
+        // NURBS 表現の円も同じ長さになる
+        let nurbs = BSplineCurve::from_circle(&circle);
+        assert!((nurbs.arc_length() - circle.length()).abs() < 1e-9);
+        assert!(Curve3::arc_length(&Line::new(Point3::origin(), Dir::X)).is_infinite());
+    }
+
+    #[test]
+    fn test_parameter_at_length() {
+        // NURBS の円はパラメータと弧長が比例しないが、逆算すれば等間隔になる
+        let circle = Circle::from_center_normal(Point3::origin(), Dir::Z, 1.0).unwrap();
+        let nurbs = BSplineCurve::from_circle(&circle);
+        for s in [0.0, 0.3, 1.0, 2.5, 6.0] {
+            let t = nurbs.parameter_at_length(s).unwrap();
+            assert!((nurbs.arc_length_between(0.0, t) - s).abs() < 1e-9);
+            let p = BSplineCurve::point_at(&nurbs, t);
+            assert!((p.y.atan2(p.x).rem_euclid(std::f64::consts::TAU) - s).abs() < 1e-9);
+        }
+        assert!(nurbs.parameter_at_length(-0.1).is_none());
+        assert!(nurbs.parameter_at_length(7.0).is_none());
+
+        let bezier = BezierCurve::new(vec![
+            Point3::origin(),
+            Point3::new(0.0, 3.0, 0.0),
+            Point3::new(4.0, 3.0, 0.0),
+            Point3::new(4.0, 0.0, 1.0),
+        ])
+        .unwrap();
+        let pts = tessellate_by_length(&bezier, 10).unwrap();
+        assert_eq!(pts.len(), 11);
+        let step = bezier.arc_length() / 10.0;
+        for w in pts.windows(2) {
+            // 弦長は弧長以下で、ほぼ等しい
+            let chord = w[0].distance(w[1]);
+            assert!(chord <= step + 1e-9 && chord > step * 0.95);
+        }
+        assert!(tessellate_by_length(&Line::new(Point3::origin(), Dir::X), 4).is_none());
+    }
+
+    #[test]
+    fn test_trimmed_curve() {
+        let e = Ellipse::new(Axis2::world(), 3.0, 1.0).unwrap();
+        let t = TrimmedCurve::new(e, 0.5, 2.0).unwrap();
+        assert_eq!((t.first_parameter(), t.last_parameter()), (0.5, 2.0));
+        assert_eq!(Curve3::point_at(&t, 1.0), Ellipse::point_at(&e, 1.0));
+        assert!(!t.is_closed());
+        let parts = t.split_at(&[1.0, 1.5]).unwrap();
+        assert_eq!(parts.len(), 3);
+        let total: f64 = parts.iter().map(|p| p.arc_length()).sum();
+        assert!((total - t.arc_length()).abs() < 1e-10);
+        assert!(t.trim(0.0, 1.0).is_err());
+        assert!(t.split_at(&[1.5, 1.0]).is_err());
+
+        // 無限に延びる曲線も有限の範囲に切り出せる
+        let p = Parabola::new(Axis2::world(), 0.25).unwrap();
+        let tp = TrimmedCurve::new(p, -1.0, 1.0).unwrap();
+        assert!(tp.arc_length().is_finite());
+        assert!(TrimmedCurve::new(p, f64::NEG_INFINITY, 0.0).is_err());
+        let h = Hyperbola::new(Axis2::world(), 1.0, 1.0).unwrap();
+        let th = TrimmedCurve::new(h, -1.0, 1.0).unwrap();
+        let b = th.bounding_box();
+        assert!(!b.is_infinite() && b.contains(Point3::new(1.0, 0.0, 0.0)));
+    }
+}
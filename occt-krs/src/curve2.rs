@@ -0,0 +1,335 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::precision;
+use crate::{BSplineCurve, NormalizeError, Point3, Result, Vector2};
+
+/// 2次元のパラメトリック曲線を表すトレイト（OCCT の `Geom2d_Curve` 相当）
+///
+/// 主に曲面のパラメータ空間 (u, v) 上の曲線（pcurve）に使う。点は `Vector2` で表す。
+pub trait Curve2 {
+    /// パラメータ `t` における点を返す
+    fn point_at(&self, t: f64) -> Vector2;
+
+    /// パラメータ `t` における1階微分を返す
+    fn derivative_at(&self, t: f64) -> Vector2;
+
+    /// パラメータ `t` における2階微分を返す
+    ///
+    /// 既定の実装は1階微分の中心差分による近似。
+    fn second_derivative_at(&self, t: f64) -> Vector2 {
+        let h = 1e-4 * t.abs().max(1.0);
+        (self.derivative_at(t + h) - self.derivative_at(t - h)) / (2.0 * h)
+    }
+
+    /// パラメータ範囲の始まりを返す（無限に延びる場合は負の無限大）
+    fn first_parameter(&self) -> f64;
+
+    /// パラメータ範囲の終わりを返す（無限に延びる場合は正の無限大）
+    fn last_parameter(&self) -> f64;
+
+    /// 始点と終点が一致していれば `true` を返す
+    fn is_closed(&self) -> bool {
+        let (a, b) = (self.first_parameter(), self.last_parameter());
+        a.is_finite()
+            && b.is_finite()
+            && (self.point_at(a) - self.point_at(b)).length() <= precision::parametric()
+    }
+}
+
+/// 2次元の無限直線（OCCT の `Geom2d_Line` 相当）
+///
+/// パラメータ `t` の点は `origin + t * dir`。`dir` は単位ベクトル。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Line2 {
+    origin: Vector2,
+    dir: Vector2,
+}
+
+impl Line2 {
+    /// 原点と方向から直線を生成する（方向は正規化される）
+    /// 方向の長さがほぼゼロの場合はエラーを返す
+    pub fn new(origin: Vector2, dir: Vector2) -> std::result::Result<Self, NormalizeError> {
+        Ok(Self {
+            origin,
+            dir: dir.try_normalized()?,
+        })
+    }
+
+    /// 2点を通る直線を生成する（`a` を原点、`a` から `b` への向きを方向とする）
+    /// 2点が一致する場合はエラーを返す
+    pub fn from_points(a: Vector2, b: Vector2) -> std::result::Result<Self, NormalizeError> {
+        Self::new(a, b - a)
+    }
+
+    /// 原点を返す
+    pub fn origin(&self) -> Vector2 {
+        self.origin
+    }
+
+    /// 単位方向ベクトルを返す
+    pub fn dir(&self) -> Vector2 {
+        self.dir
+    }
+
+    /// パラメータ `t` における点を返す
+    pub fn point_at(&self, t: f64) -> Vector2 {
+        self.origin + self.dir * t
+    }
+
+    /// 点を直線に投影したときのパラメータを返す
+    pub fn parameter_of(&self, p: Vector2) -> f64 {
+        (p - self.origin).dot(self.dir)
+    }
+
+    /// 点と直線の符号付き距離を返す（進行方向の左側が正）
+    pub fn signed_distance(&self, p: Vector2) -> f64 {
+        self.dir.perp_dot(p - self.origin)
+    }
+}
+
+/// 2次元の B-スプライン曲線（OCCT の `Geom2d_BSplineCurve` 相当、非周期）
+///
+/// z = 0 の平面上の `BSplineCurve` として保持し、評価やノット操作をそのまま利用する。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct BSplineCurve2 {
+    curve: BSplineCurve,
+}
+
+impl BSplineCurve2 {
+    /// 次数・制御点・フラットノットから曲線を生成する
+    /// 条件は `BSplineCurve::from_flat_knots` と同じ
+    pub fn from_flat_knots(
+        degree: usize,
+        control_points: Vec<Vector2>,
+        knots: Vec<f64>,
+    ) -> Result<Self> {
+        let points = control_points
+            .into_iter()
+            .map(|p| Point3::from(p.extend(0.0)))
+            .collect();
+        BSplineCurve::from_flat_knots(degree, points, knots).map(|curve| Self { curve })
+    }
+
+    /// 各制御点に重みを与えた有理曲線を返す
+    /// 重みの数が制御点の数と一致しない、または正の有限値でない重みがある場合はエラーを返す
+    pub fn with_weights(self, weights: Vec<f64>) -> Result<Self> {
+        self.curve.with_weights(weights).map(|curve| Self { curve })
+    }
+
+    /// z = 0 の平面上にある3次元曲線から生成する
+    /// 呼び出し側で制御点の z 成分がゼロであることを保証すること
+    pub(crate) fn from_planar(curve: BSplineCurve) -> Self {
+        Self { curve }
+    }
+
+    /// 次数を返す
+    pub fn degree(&self) -> usize {
+        self.curve.degree()
+    }
+
+    /// 制御点を返す
+    pub fn control_points(&self) -> Vec<Vector2> {
+        self.curve
+            .control_points()
+            .iter()
+            .map(|p| Vector2::new(p.x, p.y))
+            .collect()
+    }
+
+    /// 重みを返す（非有理曲線の場合は `None`）
+    pub fn weights(&self) -> Option<&[f64]> {
+        self.curve.weights()
+    }
+
+    /// 有理曲線であれば `true` を返す
+    pub fn is_rational(&self) -> bool {
+        self.curve.is_rational()
+    }
+
+    /// フラットノットを返す
+    pub fn flat_knots(&self) -> &[f64] {
+        self.curve.flat_knots()
+    }
+
+    /// パラメータ範囲の始まりを返す
+    pub fn first_parameter(&self) -> f64 {
+        self.curve.first_parameter()
+    }
+
+    /// パラメータ範囲の終わりを返す
+    pub fn last_parameter(&self) -> f64 {
+        self.curve.last_parameter()
+    }
+
+    /// パラメータ `t` における点を返す
+    pub fn point_at(&self, t: f64) -> Vector2 {
+        let p = self.curve.point_at(t);
+        Vector2::new(p.x, p.y)
+    }
+
+    /// パラメータ `t` における 0 階から `order` 階までの微分を返す
+    pub fn derivatives_at(&self, t: f64, order: usize) -> Vec<Vector2> {
+        self.curve
+            .derivatives_at(t, order)
+            .into_iter()
+            .map(|d| Vector2::new(d.x, d.y))
+            .collect()
+    }
+
+    /// 向きを反転した曲線を返す
+    pub fn reversed(&self) -> BSplineCurve2 {
+        Self {
+            curve: self.curve.reversed(),
+        }
+    }
+
+    /// パラメータ範囲 `[t1, t2]` を切り出した曲線を返す
+    pub fn trim(&self, t1: f64, t2: f64) -> Result<BSplineCurve2> {
+        self.curve.trim(t1, t2).map(|curve| Self { curve })
+    }
+
+    /// パラメータを `parameter_scale` 倍、座標を `(u_scale, v_scale)` 倍した曲線を返す
+    /// 呼び出し側で倍率が正の有限値であることを保証すること
+    pub(crate) fn scaled(&self, parameter_scale: f64, u_scale: f64, v_scale: f64) -> BSplineCurve2 {
+        let curve = self.curve.mapped(
+            |p| Point3::new(p.x * u_scale, p.y * v_scale, 0.0),
+            parameter_scale,
+        );
+        Self { curve }
+    }
+
+    /// 座標を `(u, v) → (u_scale·u + u_offset, v_scale·v + v_offset)` で写した曲線を返す（パラメータは変えない）
+    pub(crate) fn mapped(
+        &self,
+        u_scale: f64,
+        u_offset: f64,
+        v_scale: f64,
+        v_offset: f64,
+    ) -> BSplineCurve2 {
+        let curve = self.curve.mapped(
+            |p| Point3::new(p.x * u_scale + u_offset, p.y * v_scale + v_offset, 0.0),
+            1.0,
+        );
+        Self { curve }
+    }
+}
+
+impl Curve2 for Line2 {
+    fn point_at(&self, t: f64) -> Vector2 {
+        Line2::point_at(self, t)
+    }
+
+    fn derivative_at(&self, _t: f64) -> Vector2 {
+        self.dir
+    }
+
+    fn second_derivative_at(&self, _t: f64) -> Vector2 {
+        Vector2::ZERO
+    }
+
+    fn first_parameter(&self) -> f64 {
+        f64::NEG_INFINITY
+    }
+
+    fn last_parameter(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Curve2 for BSplineCurve2 {
+    fn point_at(&self, t: f64) -> Vector2 {
+        BSplineCurve2::point_at(self, t)
+    }
+
+    fn derivative_at(&self, t: f64) -> Vector2 {
+        self.derivatives_at(t, 1)[1]
+    }
+
+    fn second_derivative_at(&self, t: f64) -> Vector2 {
+        self.derivatives_at(t, 2)[2]
+    }
+
+    fn first_parameter(&self) -> f64 {
+        BSplineCurve2::first_parameter(self)
+    }
+
+    fn last_parameter(&self) -> f64 {
+        BSplineCurve2::last_parameter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec2_eq(a: Vector2, b: Vector2) {
+        assert!((a - b).length() < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_line2() {
+        let l = Line2::from_points(Vector2::new(1.0, 1.0), Vector2::new(4.0, 5.0)).unwrap();
+        assert_vec2_eq(l.dir(), Vector2::new(0.6, 0.8));
+        assert_vec2_eq(Curve2::point_at(&l, 5.0), Vector2::new(4.0, 5.0));
+        assert!((l.parameter_of(Vector2::new(4.0, 5.0)) - 5.0).abs() < 1e-12);
+        assert!(l.signed_distance(Vector2::new(0.2, 1.6)) > 0.0);
+        assert!(!l.is_closed());
+        assert!(Line2::new(Vector2::ZERO, Vector2::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_bspline_curve2_evaluation() {
+        // 2次のベジエ形状 (0,0) -> (1,2) -> (2,0)
+        let c = BSplineCurve2::from_flat_knots(
+            2,
+            vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 2.0),
+                Vector2::new(2.0, 0.0),
+            ],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap();
+        assert_vec2_eq(Curve2::point_at(&c, 0.5), Vector2::new(1.0, 1.0));
+        assert_vec2_eq(c.derivative_at(0.0), Vector2::new(2.0, 4.0));
+        assert_vec2_eq(c.second_derivative_at(0.3), Vector2::new(0.0, -8.0));
+        assert_eq!(c.control_points()[1], Vector2::new(1.0, 2.0));
+        assert_vec2_eq(c.reversed().point_at(0.0), Vector2::new(2.0, 0.0));
+        assert!(BSplineCurve2::from_flat_knots(2, vec![Vector2::ZERO], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_closed_rational_bspline_curve2() {
+        // 単位円の 1/4 を重み付きで表した 2 次曲線を4つつないだ円
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let pts = [
+            (1.0, 0.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+            (-1.0, 1.0),
+            (-1.0, 0.0),
+            (-1.0, -1.0),
+            (0.0, -1.0),
+            (1.0, -1.0),
+            (1.0, 0.0),
+        ];
+        let c = BSplineCurve2::from_flat_knots(
+            2,
+            pts.iter().map(|&(x, y)| Vector2::new(x, y)).collect(),
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0],
+        )
+        .unwrap()
+        .with_weights(vec![1.0, w, 1.0, w, 1.0, w, 1.0, w, 1.0])
+        .unwrap();
+        assert!(c.is_rational());
+        assert!(c.is_closed());
+        for i in 0..=16 {
+            let t = 4.0 * i as f64 / 16.0;
+            assert!((Curve2::point_at(&c, t).length() - 1.0).abs() < 1e-12);
+        }
+    }
+}
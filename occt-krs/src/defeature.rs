@@ -0,0 +1,442 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::offset::{is_smooth, replace_surfaces};
+use crate::shape_builder::union_find_root;
+use crate::topo::ShapeId;
+use crate::{
+    precision, Edge, Face, OcctKrsError, Orientation, Point3, Result, Shape, ShapeKind, Shell,
+    Solid, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 立体から選んだフェイスを取り除き、周りのフェイスを延ばしてふさぐ（OCCT の `BRepAlgoAPI_Defeaturing` 相当）
+///
+/// 選んだフェイスは辺でつながったまとまりごとに扱う。まとまりが周りのフェイスと穴の境界
+/// （内側のワイヤ）だけで接していれば（穴・ボス・ポケットなど）、まとまりと穴を取り除く。
+/// 4本のエッジで囲まれた1枚のフェイス（丸みや面取り）なら、両脇のフェイスを延ばした交わりで
+/// つなぎ、両端のエッジを点につぶす。両脇は、フェイスと滑らかにつながる側（どちらもそうでなければ
+/// 長いエッジの側）とする。頂点とエッジは、残ったフェイスの曲面の交わりとして求め直す。
+///
+/// 形状に立体がない場合、フェイスがないか立体のものでない場合、それ以外の形のまとまりの場合、
+/// 延ばしたフェイスの交わりが求められない場合はエラーを返す。
+pub fn defeature(shape: &Shape, faces: &[Face]) -> Result<Shape> {
+    let solid = shape
+        .explore(ShapeKind::Solid)
+        .next()
+        .and_then(|s| Solid::try_from(s).ok())
+        .ok_or_else(|| {
+            OcctKrsError::InvalidInput("フェイスを取り除く形状に立体がありません".to_string())
+        })?;
+    if faces.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "取り除くフェイスがありません".to_string(),
+        ));
+    }
+    let solid_shape = Shape::from(solid.clone());
+    let all = solid_shape.faces();
+    let mut removed = Vec::new();
+    for face in faces {
+        let Some(i) = all.iter().position(|f| f.is_same(face)) else {
+            return Err(OcctKrsError::InvalidInput(
+                "取り除くフェイスが立体にありません".to_string(),
+            ));
+        };
+        if !removed.contains(&i) {
+            removed.push(i);
+        }
+    }
+    let index: HashMap<ShapeId, usize> = all.iter().enumerate().map(|(i, f)| (f.id(), i)).collect();
+    let edge_faces = solid_shape.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+    let neighbors = |edge: &Edge| -> Vec<usize> {
+        edge_faces
+            .get(&Shape::from(edge.clone()))
+            .iter()
+            .filter_map(|f| index.get(&f.id()).copied())
+            .collect()
+    };
+
+    // 取り除くフェイスを、共有するエッジでまとまりに分ける
+    let mut parent: Vec<usize> = (0..all.len()).collect();
+    for &i in &removed {
+        for edge in all[i].wires().iter().flat_map(Wire::edges) {
+            for j in neighbors(&edge) {
+                if removed.contains(&j) {
+                    let (a, b) = (
+                        union_find_root(&mut parent, i),
+                        union_find_root(&mut parent, j),
+                    );
+                    parent[a] = b;
+                }
+            }
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut roots: Vec<usize> = Vec::new();
+    for &i in &removed {
+        let root = union_find_root(&mut parent, i);
+        match roots.iter().position(|&r| r == root) {
+            Some(k) => groups[k].push(i),
+            None => {
+                roots.push(root);
+                groups.push(vec![i]);
+            }
+        }
+    }
+
+    let mut edits = Edits::default();
+    for group in &groups {
+        if !edits.fill_holes(&all, group, &neighbors) {
+            edits.collapse_blend(&all, group, &neighbors)?;
+        }
+    }
+    let collapsed = edits.apply(&solid, &index, &removed)?;
+    let (healed, _) = replace_surfaces(&collapsed, |face| {
+        Ok((face.located_geometry().into_owned(), Some(Vector2::ZERO)))
+    })?;
+    Ok(healed)
+}
+
+/// 取り除くフェイスのまとまりごとに決めた、残るフェイスの位相の変更
+#[derive(Default)]
+struct Edits {
+    /// 取り除く穴のワイヤ（フェイスとワイヤの番号）
+    holes: HashSet<(usize, usize)>,
+    /// 点につぶすエッジ
+    collapsed: HashSet<ShapeId>,
+    /// 両脇の一方のエッジから、置き換えるもう一方のエッジと、それを立体の中でたどる向き
+    merged: HashMap<ShapeId, (Edge, Orientation)>,
+    /// 1つの頂点にまとめる頂点の組
+    joined: Vec<(Vertex, Vertex)>,
+}
+
+impl Edits {
+    /// まとまりが周りのフェイスと穴のワイヤだけで接していれば、その穴を取り除くことにして `true` を返す
+    fn fill_holes(
+        &mut self,
+        all: &[Face],
+        group: &[usize],
+        neighbors: &impl Fn(&Edge) -> Vec<usize>,
+    ) -> bool {
+        let mut holes = Vec::new();
+        for (i, face) in all.iter().enumerate() {
+            if group.contains(&i) {
+                continue;
+            }
+            for (w, wire) in face.wires().iter().enumerate() {
+                let edges = wire.edges();
+                let touching = edges
+                    .iter()
+                    .filter(|e| neighbors(e).iter().any(|j| group.contains(j)))
+                    .count();
+                if touching == 0 {
+                    continue;
+                }
+                if w == 0 || touching < edges.len() {
+                    return false;
+                }
+                holes.push((i, w));
+            }
+        }
+        if holes.is_empty() {
+            return false;
+        }
+        self.holes.extend(holes);
+        true
+    }
+
+    /// 4本のエッジで囲まれた1枚のフェイスを、両脇のエッジを1本にまとめ、両端のエッジを点につぶして取り除く
+    fn collapse_blend(
+        &mut self,
+        all: &[Face],
+        group: &[usize],
+        neighbors: &impl Fn(&Edge) -> Vec<usize>,
+    ) -> Result<()> {
+        let unsupported = || {
+            OcctKrsError::InvalidInput(
+                "取り除けるのは周りのフェイスと穴の境界で接するまとまりか、4本のエッジで囲まれた1枚のフェイスだけです"
+                    .to_string(),
+            )
+        };
+        let &[i] = group else {
+            return Err(unsupported());
+        };
+        let face = &all[i];
+        let wires = face.wires();
+        let [wire] = wires.as_slice() else {
+            return Err(unsupported());
+        };
+        let edges = wire.edges();
+        if edges.len() != 4 {
+            return Err(unsupported());
+        }
+        let across = |e: &Edge| neighbors(e).into_iter().find(|&j| j != i);
+        // 両端のエッジの組（0: 0番目と2番目、1: 1番目と3番目）を選ぶ
+        let seam = |k: usize| edges[k].is_same(&edges[k + 2]);
+        let smooth = |k: usize| -> Result<bool> {
+            for e in [&edges[k + 1], &edges[(k + 3) % 4]] {
+                let Some(j) = across(e) else {
+                    return Ok(false);
+                };
+                if !is_smooth(e, [face, &all[j]])? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        let length = |k: usize| {
+            [&edges[k], &edges[k + 2]]
+                .iter()
+                .map(|e| e.start_vertex().point().distance(e.end_vertex().point()))
+                .sum::<f64>()
+        };
+        let k = if seam(0) {
+            0
+        } else if seam(1) {
+            1
+        } else {
+            match (smooth(0)?, smooth(1)?) {
+                (true, false) => 0,
+                (false, true) => 1,
+                _ if length(0) <= length(1) => 0,
+                _ => 1,
+            }
+        };
+        let (a, b) = (&edges[k + 1], &edges[(k + 3) % 4]);
+        if across(a).is_none() || across(b).is_none() || a.is_same(b) {
+            return Err(unsupported());
+        }
+        for end in [&edges[k], &edges[k + 2]] {
+            self.collapsed.insert(end.id());
+            self.joined.push((end.start_vertex(), end.end_vertex()));
+        }
+        // フェイスは両脇を逆向きにたどるので、隣のフェイスは `b` を `a` と同じ向きにたどる
+        self.merged.insert(b.id(), (a.clone(), a.orientation()));
+        Ok(())
+    }
+
+    /// 変更を適用した立体を作る（頂点とエッジの位置は、曲面の交わりとして求め直す前の推定）
+    fn apply(
+        &self,
+        solid: &Solid,
+        index: &HashMap<ShapeId, usize>,
+        removed: &[usize],
+    ) -> Result<Shape> {
+        let vertices = self.joined_vertices()?;
+        // まとめた頂点に接するエッジは、同じ曲線と区間のまま頂点を付け替える
+        let mut rebuilt: HashMap<ShapeId, Edge> = HashMap::new();
+        let mut rebuild = |edge: &Edge| -> Result<Edge> {
+            let forward = edge.oriented(Orientation::Forward);
+            if let Some(e) = rebuilt.get(&forward.id()) {
+                return Ok(e.clone());
+            }
+            let (start, end) = (forward.start_vertex(), forward.end_vertex());
+            if !vertices.contains_key(&start.id()) && !vertices.contains_key(&end.id()) {
+                return Ok(forward);
+            }
+            let curve = forward.located_geometry().ok_or_else(|| {
+                OcctKrsError::InvalidInput("退化したエッジの頂点は付け替えられません".to_string())
+            })?;
+            let e = Edge::new(
+                curve.into_owned(),
+                forward.range(),
+                vertices.get(&start.id()).cloned().unwrap_or(start),
+                vertices.get(&end.id()).cloned().unwrap_or(end),
+            )?;
+            rebuilt.insert(forward.id(), e.clone());
+            Ok(e)
+        };
+
+        let mut shells = Vec::new();
+        for shell in solid.shells() {
+            let mut faces = Vec::new();
+            for face in shell.faces() {
+                let Some(&i) = index.get(&face.id()) else {
+                    continue;
+                };
+                if removed.contains(&i) {
+                    continue;
+                }
+                let reversed = face.orientation() == Orientation::Reversed;
+                let forward = face.oriented(Orientation::Forward);
+                let mut wires = Vec::new();
+                let mut pcurves = Vec::new();
+                for (w, wire) in forward.wires().iter().enumerate() {
+                    if self.holes.contains(&(i, w)) {
+                        continue;
+                    }
+                    let mut wire_edges = Vec::new();
+                    let mut wire_pcurves = Vec::new();
+                    for e in wire.edges() {
+                        if self.collapsed.contains(&e.id()) {
+                            continue;
+                        }
+                        let (new, pcurve) = match self.merged.get(&e.id()) {
+                            Some((other, orientation)) => {
+                                let orientation = if reversed {
+                                    orientation.reversed()
+                                } else {
+                                    *orientation
+                                };
+                                (rebuild(other)?.oriented(orientation), None)
+                            }
+                            None => (
+                                rebuild(&e)?.oriented(e.orientation()),
+                                forward.pcurve(&e).cloned(),
+                            ),
+                        };
+                        wire_edges.push(new);
+                        wire_pcurves.push(pcurve);
+                    }
+                    wires.push(Wire::new(wire_edges)?);
+                    pcurves.push(wire_pcurves);
+                }
+                let new =
+                    Face::with_pcurves(forward.located_geometry().into_owned(), wires, pcurves)?;
+                faces.push(new.oriented(face.orientation()));
+            }
+            shells.push(Shell::new(faces)?);
+        }
+        Ok(Shape::from(Solid::new(shells)?))
+    }
+
+    /// まとめる頂点の ID から、まとめた頂点への対応
+    ///
+    /// まとめた頂点は元の頂点の重心に置き、元の位置をすべて許容誤差で含むようにする。
+    fn joined_vertices(&self) -> Result<HashMap<ShapeId, Vertex>> {
+        let mut found: Vec<Vertex> = Vec::new();
+        let mut position = |v: &Vertex| match found.iter().position(|u| u.id() == v.id()) {
+            Some(i) => i,
+            None => {
+                found.push(v.clone());
+                found.len() - 1
+            }
+        };
+        let pairs: Vec<(usize, usize)> = self
+            .joined
+            .iter()
+            .map(|(a, b)| (position(a), position(b)))
+            .collect();
+        let mut parent: Vec<usize> = (0..found.len()).collect();
+        for (a, b) in pairs {
+            let (a, b) = (
+                union_find_root(&mut parent, a),
+                union_find_root(&mut parent, b),
+            );
+            parent[a] = b;
+        }
+        let mut vertices = HashMap::new();
+        for root in 0..found.len() {
+            let members: Vec<usize> = (0..found.len())
+                .filter(|&i| union_find_root(&mut parent, i) == root)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let center = Point3::from(
+                members
+                    .iter()
+                    .fold(Vector3::ZERO, |s, &i| s + found[i].point().to_vector())
+                    / members.len() as f64,
+            );
+            let gap = members
+                .iter()
+                .map(|&i| found[i].point().distance(center))
+                .fold(0.0, f64::max);
+            let vertex = Vertex::new(center).with_tolerance(gap + precision::confusion())?;
+            for i in members {
+                vertices.insert(found[i].id(), vertex.clone());
+            }
+        }
+        Ok(vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean::{cut, fuse};
+    use crate::fillet::fillet;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Dir, GeomSurface};
+
+    fn block() -> Shape {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, 2.0, 2.0, 1.0).unwrap())
+    }
+
+    fn non_planar(shape: &Shape) -> Vec<Face> {
+        shape
+            .faces()
+            .into_iter()
+            .filter(|f| !matches!(f.located_geometry().as_ref(), GeomSurface::Plane(_)))
+            .collect()
+    }
+
+    #[test]
+    fn test_defeature_hole_and_boss() {
+        let axis = Axis2::new(Point3::new(1.0, 1.0, -1.0), Dir::Z, Dir::X).unwrap();
+        let drilled = cut(
+            &block(),
+            &Shape::from(make_cylinder(axis, 0.25, 3.0).unwrap()),
+        )
+        .unwrap();
+        let result = defeature(&drilled, &non_planar(&drilled)).unwrap();
+        assert!((checked_volume(&result) - 4.0).abs() < 1e-9);
+        assert_eq!(result.faces().len(), 6);
+
+        // 上面に立てた円柱のボスは側面と天面をまとめて取り除く
+        let axis = Axis2::new(Point3::new(1.0, 1.0, 1.0), Dir::Z, Dir::X).unwrap();
+        let bossed = fuse(
+            &block(),
+            &Shape::from(make_cylinder(axis, 0.25, 0.5).unwrap()),
+        )
+        .unwrap();
+        let boss: Vec<Face> = bossed
+            .faces()
+            .into_iter()
+            .filter(|f| {
+                f.wires()
+                    .iter()
+                    .flat_map(Wire::edges)
+                    .all(|e| e.start_vertex().point().z > 1.0 - 1e-9)
+                    && f.wires().len() == 1
+            })
+            .collect();
+        assert_eq!(boss.len(), 2);
+        let result = defeature(&bossed, &boss).unwrap();
+        assert!((checked_volume(&result) - 4.0).abs() < 1e-9);
+        assert_eq!(result.faces().len(), 6);
+    }
+
+    #[test]
+    fn test_defeature_fillet() {
+        let shape = block();
+        let edge = shape
+            .edges()
+            .into_iter()
+            .find(|e| {
+                let (a, b) = (e.start_vertex().point(), e.end_vertex().point());
+                a.z == 1.0 && b.z == 1.0 && a.y == 0.0 && b.y == 0.0
+            })
+            .unwrap();
+        let rounded = fillet(&shape, &[edge], 0.3).unwrap();
+        assert!(checked_volume(&rounded) < 4.0 - 1e-3);
+        let result = defeature(&rounded, &non_planar(&rounded)).unwrap();
+        assert!((checked_volume(&result) - 4.0).abs() < 1e-7);
+        assert_eq!(result.faces().len(), 6);
+        assert_eq!(result.vertices().len(), 8);
+    }
+
+    #[test]
+    fn test_defeature_errors() {
+        let shape = block();
+        assert!(defeature(&shape, &[]).is_err());
+        let other = block().faces()[0].clone();
+        assert!(defeature(&shape, &[other]).is_err());
+        // 箱の面は穴の境界でも4本のエッジの丸みでもない（両脇を延ばしても交わらない）
+        let face = shape.faces()[0].clone();
+        assert!(defeature(&shape, &[face]).is_err());
+        let compound = Shape::from(crate::Compound::new(Vec::new()));
+        assert!(defeature(&compound, &[shape.faces()[0].clone()]).is_err());
+    }
+}
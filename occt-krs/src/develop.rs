@@ -0,0 +1,446 @@
+use crate::{AnalyticSurface, OcctKrsError, Result, Surface, TrimmedSurface, Vector2};
+
+/// 母線に沿って展開するときの最初の区間数
+const INITIAL_STATIONS: usize = 32;
+
+/// 母線に沿って展開するときの区間数の上限
+const MAX_STATIONS: usize = 4096;
+
+/// 可展性や母線の直線性を調べるためにパラメータ範囲を各方向に分割する区間数
+const CHECK_SAMPLES: usize = 16;
+
+/// 展開図の輪郭を細分するときの再帰の深さの上限
+const MAX_OUTLINE_DEPTH: usize = 12;
+
+/// 可展面を平面へ展開する写像（パラメータ `(u, v)` から展開図上の点へ）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Development {
+    mapping: Mapping,
+}
+
+/// 展開の方法
+#[derive(Debug, Clone, PartialEq)]
+enum Mapping {
+    /// 平面はパラメータがそのまま平面座標になる
+    Plane,
+    /// 円柱面は `(R u, v)`
+    Cylinder { radius: f64 },
+    /// 円錐面は頂点を中心とした扇形（頂点からの母線の長さと、`sin A` 倍した角度）
+    Cone { apex_offset: f64, sin_angle: f64 },
+    /// 直線の母線を持つ面を、母線で区切った細い帯ごとに順に展開する
+    Strips {
+        /// 母線の方向が u なら `true`
+        swapped: bool,
+        /// 母線を並べる方向のパラメータ範囲
+        range: (f64, f64),
+        /// 母線方向のパラメータ範囲
+        ruling_range: (f64, f64),
+        /// 等間隔に並べた各母線の、展開図上の始点と終点
+        rulings: Vec<(Vector2, Vector2)>,
+    },
+}
+
+/// 切り取られた面を展開した図形
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatPattern {
+    /// 外側の境界を展開した多角形（始点を末尾に重ねない）
+    pub outer: Vec<Vector2>,
+    /// 穴の境界を展開した多角形
+    pub inners: Vec<Vec<Vector2>>,
+    /// パラメータから展開図への写像
+    pub development: Development,
+}
+
+impl Development {
+    /// 曲面のパラメータ範囲 `u_range` × `v_range` を平面へ展開する写像を求める
+    ///
+    /// 平面・円柱面・円錐面は厳密に展開する。それ以外の曲面は、u または v の等パラメータ線が
+    /// パラメータに比例する直線（母線）であれば、母線で区切った帯を長さを保って順に並べて展開する
+    /// （押し出し面、線織面、一方の次数が1の B-スプライン曲面など）。
+    /// 後者は、範囲内のガウス曲率の絶対値の積分（角度の過剰）と対角の長さの積が `tolerance` を超える場合や、
+    /// 母線が直線でない場合はエラーを返す。球面・トーラス面は展開できないのでエラーを返す。
+    pub fn new(
+        surface: &(impl Surface + ?Sized),
+        u_range: (f64, f64),
+        v_range: (f64, f64),
+        tolerance: f64,
+    ) -> Result<Self> {
+        if !(tolerance > 0.0 && tolerance.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "許容誤差が不正です: {}",
+                tolerance
+            )));
+        }
+        let finite = [u_range.0, u_range.1, v_range.0, v_range.1]
+            .iter()
+            .all(|x| x.is_finite());
+        if !finite || u_range.0 >= u_range.1 || v_range.0 >= v_range.1 {
+            return Err(OcctKrsError::InvalidInput(
+                "展開するパラメータ範囲は有限かつ空でない必要があります".to_string(),
+            ));
+        }
+        let mapping = match surface.as_analytic() {
+            Some(AnalyticSurface::Plane(_)) => Mapping::Plane,
+            Some(AnalyticSurface::Cylinder(c)) => Mapping::Cylinder { radius: c.radius() },
+            Some(AnalyticSurface::Cone(c)) => {
+                let sin_angle = c.semi_angle().sin();
+                Mapping::Cone {
+                    apex_offset: c.ref_radius() / sin_angle,
+                    sin_angle,
+                }
+            }
+            Some(AnalyticSurface::Sphere(_) | AnalyticSurface::Torus(_)) => {
+                return Err(OcctKrsError::DegenerateGeometry(
+                    "球面・トーラス面は可展ではありません".to_string(),
+                ));
+            }
+            None => develop_ruled(surface, u_range, v_range, tolerance)?,
+        };
+        Ok(Self { mapping })
+    }
+
+    /// パラメータ `(u, v)` の点の展開図上の位置を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Vector2 {
+        match &self.mapping {
+            Mapping::Plane => Vector2::new(u, v),
+            Mapping::Cylinder { radius } => Vector2::new(radius * u, v),
+            Mapping::Cone {
+                apex_offset,
+                sin_angle,
+            } => {
+                let (s, c) = (u * sin_angle).sin_cos();
+                Vector2::new(c, s) * (v + apex_offset)
+            }
+            Mapping::Strips {
+                swapped,
+                range,
+                ruling_range,
+                rulings,
+            } => {
+                let (a, b) = if *swapped { (v, u) } else { (u, v) };
+                let n = rulings.len() - 1;
+                let x = (a - range.0) / (range.1 - range.0) * n as f64;
+                let i = (x.floor().max(0.0) as usize).min(n - 1);
+                let t = x - i as f64;
+                let lambda = (b - ruling_range.0) / (ruling_range.1 - ruling_range.0);
+                let on = |(p, q): (Vector2, Vector2)| p + (q - p) * lambda;
+                let (p, q) = (on(rulings[i]), on(rulings[i + 1]));
+                p + (q - p) * t
+            }
+        }
+    }
+}
+
+/// 切り取られた面を平面へ展開し、境界の展開図と写像を返す
+///
+/// 写像は外側の境界を囲むパラメータ範囲で `Development::new` により求める。
+/// 境界は近似多角形の各辺を、展開図上での弦との隔たりが `tolerance` 以下になるまで細分して写す。
+pub fn flatten<S: Surface>(face: &TrimmedSurface<S>, tolerance: f64) -> Result<FlatPattern> {
+    let (u_range, v_range) = face.bounds();
+    let development = Development::new(face.basis(), u_range, v_range, tolerance)?;
+    let map_loop = |polygon: &[Vector2]| -> Vec<Vector2> {
+        let mut result = Vec::new();
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            result.push(development.point_at(a.x, a.y));
+            subdivide(&development, a, b, tolerance, 0, &mut result);
+        }
+        result
+    };
+    Ok(FlatPattern {
+        outer: map_loop(face.outer().polygon()),
+        inners: face
+            .inners()
+            .iter()
+            .map(|l| map_loop(l.polygon()))
+            .collect(),
+        development,
+    })
+}
+
+/// パラメータ空間の線分 `a`–`b` の像が弦から `tolerance` 以上離れていれば、中点を加えて再帰的に細分する
+/// （端点 `a` は呼び出し側で加え、`b` は加えない）
+fn subdivide(
+    development: &Development,
+    a: Vector2,
+    b: Vector2,
+    tolerance: f64,
+    depth: usize,
+    result: &mut Vec<Vector2>,
+) {
+    if depth >= MAX_OUTLINE_DEPTH {
+        return;
+    }
+    let m = (a + b) * 0.5;
+    let (pa, pb, pm) = (
+        development.point_at(a.x, a.y),
+        development.point_at(b.x, b.y),
+        development.point_at(m.x, m.y),
+    );
+    if (pm - (pa + pb) * 0.5).length() <= tolerance {
+        return;
+    }
+    subdivide(development, a, m, tolerance, depth + 1, result);
+    result.push(pm);
+    subdivide(development, m, b, tolerance, depth + 1, result);
+}
+
+/// 母線が等パラメータ線である曲面を、帯ごとに順に展開する
+fn develop_ruled(
+    surface: &(impl Surface + ?Sized),
+    u_range: (f64, f64),
+    v_range: (f64, f64),
+    tolerance: f64,
+) -> Result<Mapping> {
+    // 母線方向のパラメータで点を返す（swapped なら (a, b) = (v, u)）
+    let point = |swapped: bool, a: f64, b: f64| {
+        if swapped {
+            surface.point_at(b, a)
+        } else {
+            surface.point_at(a, b)
+        }
+    };
+    let is_ruled = |swapped: bool| {
+        let (range, ruling) = if swapped {
+            (v_range, u_range)
+        } else {
+            (u_range, v_range)
+        };
+        (0..=CHECK_SAMPLES).all(|i| {
+            let a = range.0 + (range.1 - range.0) * i as f64 / CHECK_SAMPLES as f64;
+            let (p, q) = (point(swapped, a, ruling.0), point(swapped, a, ruling.1));
+            (1..4).all(|k| {
+                let t = k as f64 / 4.0;
+                let b = ruling.0 + (ruling.1 - ruling.0) * t;
+                point(swapped, a, b).distance(p + (q - p) * t) <= tolerance
+            })
+        })
+    };
+    let swapped = if is_ruled(false) {
+        false
+    } else if is_ruled(true) {
+        true
+    } else {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "等パラメータ線が直線の母線になっていないので展開できません".to_string(),
+        ));
+    };
+
+    // ガウス・ボネの定理より、角度の過剰（ガウス曲率の面積分）と大きさの積が長さの歪みの目安になる
+    let (du, dv) = (
+        (u_range.1 - u_range.0) / CHECK_SAMPLES as f64,
+        (v_range.1 - v_range.0) / CHECK_SAMPLES as f64,
+    );
+    let mut excess = 0.0;
+    for i in 0..CHECK_SAMPLES {
+        for j in 0..CHECK_SAMPLES {
+            let (u, v) = (
+                u_range.0 + du * (i as f64 + 0.5),
+                v_range.0 + dv * (j as f64 + 0.5),
+            );
+            let area = surface
+                .derivative_u_at(u, v)
+                .cross(surface.derivative_v_at(u, v))
+                .length()
+                * du
+                * dv;
+            if let Some(k) = surface.curvature_at(u, v) {
+                excess += k.gaussian.abs() * area;
+            }
+        }
+    }
+    let diagonal = surface
+        .point_at(u_range.0, v_range.0)
+        .distance(surface.point_at(u_range.1, v_range.1))
+        .max(
+            surface
+                .point_at(u_range.0, v_range.1)
+                .distance(surface.point_at(u_range.1, v_range.0)),
+        );
+    if excess * diagonal > tolerance {
+        return Err(OcctKrsError::ToleranceExceeded {
+            tolerance,
+            deviation: excess * diagonal,
+        });
+    }
+
+    let (range, ruling_range) = if swapped {
+        (v_range, u_range)
+    } else {
+        (u_range, v_range)
+    };
+    let unfold = |n: usize| -> Result<Vec<(Vector2, Vector2)>> {
+        let ends = |i: usize| {
+            let a = range.0 + (range.1 - range.0) * i as f64 / n as f64;
+            (
+                point(swapped, a, ruling_range.0),
+                point(swapped, a, ruling_range.1),
+            )
+        };
+        let (mut a, mut b) = ends(0);
+        let mut rulings = vec![(Vector2::ZERO, Vector2::new(0.0, a.distance(b)))];
+        for i in 1..=n {
+            let (pa, pb) = *rulings.last().unwrap();
+            let (na, nb) = ends(i);
+            let fa = place(pa, pb, na.distance(a), na.distance(b))?;
+            let fb = place(fa, pb, nb.distance(na), nb.distance(b))?;
+            rulings.push((fa, fb));
+            (a, b) = (na, nb);
+        }
+        Ok(rulings)
+    };
+    let mut n = INITIAL_STATIONS;
+    let mut rulings = unfold(n)?;
+    loop {
+        // 区間数を倍にした展開と、母線上で比べて収束を調べる
+        let finer = unfold(2 * n)?;
+        let deviation = rulings
+            .iter()
+            .zip(finer.iter().step_by(2))
+            .map(|(p, q)| (p.0 - q.0).length().max((p.1 - q.1).length()))
+            .fold(0.0, f64::max);
+        rulings = finer;
+        n *= 2;
+        if deviation <= tolerance {
+            break;
+        }
+        if n >= MAX_STATIONS {
+            return Err(OcctKrsError::ToleranceExceeded {
+                tolerance,
+                deviation,
+            });
+        }
+    }
+    Ok(Mapping::Strips {
+        swapped,
+        range,
+        ruling_range,
+        rulings,
+    })
+}
+
+/// `p`・`q` からの距離がそれぞれ `dp`・`dq` で、有向線分 `p`→`q` の右側にある点を返す
+fn place(p: Vector2, q: Vector2, dp: f64, dq: f64) -> Result<Vector2> {
+    let e = q - p;
+    let d = e.length();
+    if d <= f64::MIN_POSITIVE {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "母線の長さがゼロです".to_string(),
+        ));
+    }
+    let e = e / d;
+    let x = (dp * dp - dq * dq + d * d) / (2.0 * d);
+    let y = (dp * dp - x * x).max(0.0).sqrt();
+    Ok(p + e * x - e.perp() * y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Arc, Axis2, Axis3, Circle, ConicalSurface, CylindricalSurface, Dir, ExtrudedSurface,
+        Point3, RuledSurface, Segment, SphericalSurface, UvLoop,
+    };
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn rectangle(u: (f64, f64), v: (f64, f64)) -> UvLoop {
+        UvLoop::from_polygon(&[
+            Vector2::new(u.0, v.0),
+            Vector2::new(u.1, v.0),
+            Vector2::new(u.1, v.1),
+            Vector2::new(u.0, v.1),
+        ])
+        .unwrap()
+    }
+
+    fn polygon_length(points: &[Vector2]) -> f64 {
+        (0..points.len())
+            .map(|i| (points[(i + 1) % points.len()] - points[i]).length())
+            .sum()
+    }
+
+    #[test]
+    fn test_cylinder_unrolls_to_rectangle() {
+        let cylinder =
+            CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap();
+        let face =
+            TrimmedSurface::new(cylinder, rectangle((0.0, PI), (0.0, 3.0)), Vec::new()).unwrap();
+        let flat = flatten(&face, 1e-9).unwrap();
+        // 半周分を展開すると 2π × 3 の長方形
+        assert_eq!(flat.outer.len(), 4);
+        assert!((polygon_length(&flat.outer) - 2.0 * (2.0 * PI + 3.0)).abs() < 1e-12);
+        let p = flat.development.point_at(PI, 3.0);
+        assert!((p - Vector2::new(2.0 * PI, 3.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn test_cone_unrolls_to_annular_sector() {
+        // 半頂角 30° の円錐は、中心角 π（= 2π sin 30°）の扇形に展開される
+        let cone =
+            ConicalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), PI / 6.0, 1.0).unwrap();
+        let d = Development::new(&cone, (0.0, 2.0 * PI), (0.0, 1.0), 1e-9).unwrap();
+        // 頂点から基準円までの母線の長さは R / sin A = 2
+        assert!((d.point_at(0.0, 0.0) - Vector2::new(2.0, 0.0)).length() < 1e-12);
+        assert!((d.point_at(2.0 * PI, 0.0) - Vector2::new(-2.0, 0.0)).length() < 1e-12);
+        assert!((d.point_at(PI, 1.0) - Vector2::new(0.0, 3.0)).length() < 1e-12);
+        // 母線に沿った長さと、平行円に沿った長さが保たれる
+        let (a, b) = (cone.point_at(0.3, 0.2), cone.point_at(1.9, 0.7));
+        let (fa, fb) = (d.point_at(0.3, 0.2), d.point_at(1.9, 0.7));
+        let arc = |v: f64| cone.radius_at(v) * (1.9 - 0.3);
+        assert!(((fa - d.point_at(0.3, 0.7)).length() - 0.5).abs() < 1e-12);
+        let outer_arc = (d.point_at(1.9, 0.7) - Vector2::ZERO).length() * (1.6 * 0.5);
+        assert!((outer_arc - arc(0.7)).abs() < 1e-12);
+        assert!(a.distance(b) <= (fa - fb).length() + 1e-12);
+    }
+
+    #[test]
+    fn test_sphere_is_not_developable() {
+        let sphere =
+            SphericalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        assert!(Development::new(&sphere, (0.0, 1.0), (0.0, 1.0), 1e-6).is_err());
+    }
+
+    #[test]
+    fn test_extruded_arc_matches_cylinder() {
+        let circle = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap();
+        let arc = Arc::new(circle, 0.0, FRAC_PI_2).unwrap();
+        let surface = ExtrudedSurface::new(arc, Dir::Z);
+        let d = Development::new(&surface, (0.0, FRAC_PI_2), (0.0, 3.0), 1e-6).unwrap();
+        let corner = d.point_at(FRAC_PI_2, 3.0) - d.point_at(0.0, 0.0);
+        // 幅は円弧の長さ π、高さは 3
+        assert!((corner.x.abs() - PI).abs() < 1e-5, "{:?}", corner);
+        assert!((corner.y - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_ruled_frustum_is_developable() {
+        // 同じ角度範囲の半径 1 と 2 の円弧を結ぶ線織面は円錐台
+        let bottom = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        let top = Circle::new(Axis2::from_normal(Point3::new(0.0, 0.0, 1.0), Dir::Z), 2.0).unwrap();
+        let surface = RuledSurface::new(
+            Arc::new(bottom, 0.0, PI).unwrap(),
+            Arc::new(top, 0.0, PI).unwrap(),
+        )
+        .unwrap();
+        let face = TrimmedSurface::from_bounds(surface).unwrap();
+        let flat = flatten(&face, 1e-6).unwrap();
+        // 展開図の周長は 2 本の円弧と 2 本の母線の長さの和
+        let expected = PI * 1.0 + PI * 2.0 + 2.0 * 2f64.sqrt();
+        assert!(
+            (polygon_length(&flat.outer) - expected).abs() < 1e-3,
+            "{}",
+            polygon_length(&flat.outer)
+        );
+    }
+
+    #[test]
+    fn test_twisted_ruled_surface_is_rejected() {
+        // ねじれた線織面（双曲放物面）は可展ではない
+        let surface = RuledSurface::new(
+            Segment::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)),
+            Segment::new(Point3::new(0.0, 1.0, 0.0), Point3::new(1.0, 1.0, 1.0)),
+        )
+        .unwrap();
+        assert!(Development::new(&surface, (0.0, 1.0), (0.0, 1.0), 1e-3).is_err());
+    }
+}
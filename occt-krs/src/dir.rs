@@ -0,0 +1,177 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Neg;
+
+use crate::{NormalizeError, Quaternion, Transform, Vector3};
+
+/// 単位長の方向ベクトル（OCCT の `gp_Dir` 相当）
+///
+/// 生成時に必ず正規化されるため、常に長さ1であることが保証される。
+/// 軸や平面の法線など、方向のみが意味を持つ引数に利用する。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Vector3", into = "Vector3"))]
+pub struct Dir(Vector3);
+
+impl Dir {
+    /// 成分から方向を生成する（内部で正規化する）
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn new(x: f64, y: f64, z: f64) -> Result<Self, NormalizeError> {
+        Self::from_vector(Vector3::new(x, y, z))
+    }
+
+    /// ベクトルを正規化して方向を生成する
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn from_vector(v: Vector3) -> Result<Self, NormalizeError> {
+        v.try_normalized().map(Self)
+    }
+
+    /// 正規化済みであることが分かっているベクトルから方向を生成する
+    /// 呼び出し側で単位長であることを保証すること
+    pub(crate) fn new_unchecked(v: Vector3) -> Self {
+        Self(v)
+    }
+
+    /// X軸方向 (1, 0, 0)
+    pub const X: Dir = Dir(Vector3::X);
+    /// Y軸方向 (0, 1, 0)
+    pub const Y: Dir = Dir(Vector3::Y);
+    /// Z軸方向 (0, 0, 1)
+    pub const Z: Dir = Dir(Vector3::Z);
+
+    /// X軸方向 (1, 0, 0) を返す
+    pub const fn x_axis() -> Self {
+        Self::X
+    }
+
+    /// Y軸方向 (0, 1, 0) を返す
+    pub const fn y_axis() -> Self {
+        Self::Y
+    }
+
+    /// Z軸方向 (0, 0, 1) を返す
+    pub const fn z_axis() -> Self {
+        Self::Z
+    }
+
+    /// x成分を返す
+    pub fn x(self) -> f64 {
+        self.0.x
+    }
+
+    /// y成分を返す
+    pub fn y(self) -> f64 {
+        self.0.y
+    }
+
+    /// z成分を返す
+    pub fn z(self) -> f64 {
+        self.0.z
+    }
+
+    /// 単位ベクトルとして取り出す
+    pub fn to_vector(self) -> Vector3 {
+        self.0
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Dir) -> f64 {
+        self.0.dot(other.0)
+    }
+
+    /// 外積を正規化した方向を返す
+    /// 2つの方向が平行な場合はエラーを返す
+    pub fn crossed(self, other: Dir) -> Result<Dir, NormalizeError> {
+        Dir::from_vector(self.0.cross(other.0))
+    }
+
+    /// 2つの方向のなす角（0〜π）を計算する
+    pub fn angle(self, other: Dir) -> f64 {
+        // 小さな角度でも精度を保つため atan2 を用いる
+        self.0.cross(other.0).length().atan2(self.dot(other))
+    }
+
+    /// 逆向きの方向を返す
+    pub fn reversed(self) -> Dir {
+        Self(-self.0)
+    }
+
+    /// 四元数で回転した方向を返す
+    pub fn rotated(self, q: Quaternion) -> Dir {
+        Self(q.normalized().rotate(self.0))
+    }
+
+    /// 変換を適用した方向を返す（負のスケールでは向きが反転する）
+    pub fn transformed(self, t: &Transform) -> Dir {
+        let v = t.rotation.normalized().rotate(self.0);
+        if t.scale < 0.0 {
+            Self(-v)
+        } else {
+            Self(v)
+        }
+    }
+}
+
+/// ベクトルからの変換（正規化に失敗した場合はエラー）
+impl TryFrom<Vector3> for Dir {
+    type Error = NormalizeError;
+    fn try_from(v: Vector3) -> Result<Self, NormalizeError> {
+        Dir::from_vector(v)
+    }
+}
+
+/// 単位ベクトルへの変換
+impl From<Dir> for Vector3 {
+    fn from(d: Dir) -> Self {
+        d.0
+    }
+}
+
+/// 逆向きの方向
+impl Neg for Dir {
+    type Output = Dir;
+    fn neg(self) -> Dir {
+        self.reversed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_normalized_on_construction() {
+        let d = Dir::new(3.0, 0.0, 4.0).unwrap();
+        assert!((d.to_vector().length() - 1.0).abs() < 1e-15);
+        assert!((d.x() - 0.6).abs() < 1e-15);
+        assert!((d.z() - 0.8).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_zero_vector_is_rejected() {
+        assert_eq!(Dir::new(0.0, 0.0, 0.0), Err(NormalizeError));
+        assert!(Dir::new(f64::NAN, 0.0, 1.0).is_err());
+        assert!(Dir::try_from(Vector3::new(0.0, 0.0, 0.0)).is_err());
+    }
+
+    #[test]
+    fn test_cross_and_angle() {
+        let x = Dir::x_axis();
+        let y = Dir::y_axis();
+        assert_eq!(x.crossed(y).unwrap(), Dir::z_axis());
+        assert!(x.crossed(-x).is_err());
+        assert!((x.angle(y) - FRAC_PI_2).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates() {
+        let d = Dir::new(0.0, 2.0, 0.0).unwrap();
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, r#"{"x":0.0,"y":1.0,"z":0.0}"#);
+        let back: Dir = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, d);
+        assert!(serde_json::from_str::<Dir>(r#"{"x":0.0,"y":0.0,"z":0.0}"#).is_err());
+    }
+}
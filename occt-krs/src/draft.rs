@@ -0,0 +1,229 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::fillet::solve_planes;
+use crate::offset::replace_surfaces;
+use crate::{
+    precision, Axis3, ConicalSurface, Dir, Face, GeomSurface, OcctKrsError, Orientation, Plane,
+    Result, Shape, ShapeKind, Surface, Vector2,
+};
+
+/// 立体のフェイスに抜き勾配をつける（OCCT の `BRepOffsetAPI_DraftAngle` 相当）
+///
+/// 選んだフェイスを中立面 `neutral_plane` との交わり（中立線）のまわりに傾け、型を抜く向き
+/// `pull_direction` となす角を `angle` にする。角が正なら `pull_direction` へ進むほど立体が細くなる
+/// （穴は太くなる）向きに傾ける。平面のフェイスは中立線を通る平面に、軸が抜く向きに平行な円柱面は
+/// 中立面の上で同じ半径の円錐面にする。隣り合うエッジと頂点は、傾けたフェイスとの交わりとして求め直す。
+///
+/// 角が ±90° 未満の有限値でない場合、形状に立体がない場合、フェイスがないか立体のものでない場合、
+/// 平面のフェイスの法線が抜く向きや中立面の法線と平行な場合、円柱面の軸が抜く向きや中立面の法線と
+/// 平行でない場合、それ以外の曲面の場合はエラーを返す。
+pub fn draft(
+    shape: &Shape,
+    faces: &[Face],
+    pull_direction: Dir,
+    angle: f64,
+    neutral_plane: &Plane,
+) -> Result<Shape> {
+    if !angle.is_finite() || angle.abs() >= FRAC_PI_2 {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "抜き勾配の角度が不正です: {}",
+            angle
+        )));
+    }
+    if shape.explore(ShapeKind::Solid).next().is_none() {
+        return Err(OcctKrsError::InvalidInput(
+            "抜き勾配をつける形状に立体がありません".to_string(),
+        ));
+    }
+    if faces.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "抜き勾配をつけるフェイスがありません".to_string(),
+        ));
+    }
+    let solid_faces = shape.faces();
+    if !faces
+        .iter()
+        .all(|f| solid_faces.iter().any(|g| g.is_same(f)))
+    {
+        return Err(OcctKrsError::InvalidInput(
+            "抜き勾配をつけるフェイスが立体にありません".to_string(),
+        ));
+    }
+    let (moved, _) = replace_surfaces(shape, |face| {
+        let surface = face.located_geometry().into_owned();
+        if faces.iter().any(|f| f.is_same(face)) {
+            let drafted = drafted_surface(face, &surface, pull_direction, angle, neutral_plane)?;
+            Ok((drafted, Some(Vector2::new(0.0, 0.0))))
+        } else {
+            Ok((surface, Some(Vector2::new(0.0, 0.0))))
+        }
+    })?;
+    Ok(moved)
+}
+
+/// フェイスの曲面 `surface` を傾けた曲面を返す
+fn drafted_surface(
+    face: &Face,
+    surface: &GeomSurface,
+    pull: Dir,
+    angle: f64,
+    neutral: &Plane,
+) -> Result<GeomSurface> {
+    let d = pull.to_vector();
+    let m = neutral.normal().to_vector();
+    // フェイスの外向きの法線を曲面の法線に戻す符号
+    let sign = if face.orientation() == Orientation::Reversed {
+        -1.0
+    } else {
+        1.0
+    };
+    match surface {
+        GeomSurface::Plane(plane) => {
+            let n = plane.normal().to_vector();
+            let outward = n * sign;
+            let across = outward - d * outward.dot(d);
+            let line = n.cross(m);
+            if across.length() <= precision::angular() || line.length() <= precision::angular() {
+                return Err(OcctKrsError::InvalidInput(
+                    "法線が抜く向きか中立面の法線と平行な平面のフェイスには勾配をつけられません"
+                        .to_string(),
+                ));
+            }
+            let on_line = solve_planes(&[
+                (n, n.dot(plane.origin().to_vector())),
+                (m, m.dot(neutral.origin().to_vector())),
+                (line, line.dot(plane.origin().to_vector())),
+            ])?;
+            let drafted = across.try_normalized()? * angle.cos() + d * angle.sin();
+            let normal = Dir::from_vector(drafted * sign)?;
+            Ok(GeomSurface::Plane(Plane::new(on_line, normal)))
+        }
+        GeomSurface::Cylinder(cylinder) => {
+            let position = cylinder.position();
+            let axis = position.direction().to_vector();
+            let along = axis.dot(d);
+            if along.abs() < 1.0 - precision::angular()
+                || axis.dot(m).abs() < 1.0 - precision::angular()
+            {
+                return Err(OcctKrsError::InvalidInput(
+                    "軸が抜く向きと中立面の法線に平行な円柱面にだけ勾配をつけられます".to_string(),
+                ));
+            }
+            // 外向きの法線が軸から離れる向き（ボス）なら 1、軸へ向かう向き（穴）なら -1
+            let radial = position.x_direction().to_vector();
+            let normal = surface.normal_at(0.0, 0.0).map_or(radial, Dir::to_vector);
+            let outward = if normal.dot(radial) * sign < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            let semi_angle = -outward * angle * along.signum();
+            if semi_angle == 0.0 {
+                return Ok(surface.clone());
+            }
+            let location = position.location();
+            let height = (neutral.origin() - location).dot(m) / axis.dot(m);
+            let mut cone_position = Axis3::new(
+                location + axis * height,
+                position.direction(),
+                position.x_direction(),
+            )?;
+            if cone_position.is_direct() != position.is_direct() {
+                cone_position.y_reverse();
+            }
+            let cone = ConicalSurface::new(cone_position, semi_angle, cylinder.radius())?;
+            Ok(GeomSurface::Cone(cone))
+        }
+        other => Err(OcctKrsError::InvalidInput(format!(
+            "平面と円柱面のフェイスにだけ勾配をつけられます: {:?}",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Point3};
+    use std::f64::consts::PI;
+
+    /// 抜く向き（Z）に沿った側面のフェイス（法線が Z に垂直な平面と円柱面）
+    fn side_faces(shape: &Shape) -> Vec<Face> {
+        shape
+            .faces()
+            .into_iter()
+            .filter(|f| match f.surface() {
+                GeomSurface::Plane(p) => p.normal().dot(Dir::Z).abs() < 1e-9,
+                GeomSurface::Cylinder(_) => true,
+                _ => false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_draft_box() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let block = Shape::from(make_box(position, 2.0, 2.0, 2.0).unwrap());
+        let angle = 5f64.to_radians();
+        let result = draft(&block, &side_faces(&block), Dir::Z, angle, &Plane::xy()).unwrap();
+        assert_eq!(result.faces().len(), 6);
+        // 底面の正方形はそのままで、上面は各辺が 2 tan(5°) ずつ内側へ寄る
+        let top = 2.0 - 2.0 * 2.0 * angle.tan();
+        let corner = result
+            .explore(ShapeKind::Vertex)
+            .unique()
+            .filter_map(|v| crate::Vertex::try_from(v).ok())
+            .map(|v| v.point())
+            .find(|p| p.z > 1.0 && p.x > 1.0 && p.y > 1.0)
+            .unwrap();
+        assert!(
+            (corner.x - (2.0 - 2.0 * angle.tan())).abs() < 1e-9,
+            "{:?}",
+            corner
+        );
+        let volume = checked_volume(&result);
+        let expected = 2.0 / 3.0 * (4.0 + top * top + 2.0 * top);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_draft_cylinder() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let angle = 10f64.to_radians();
+        let neutral = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::Z);
+        let faces = side_faces(&cylinder);
+        let result = draft(&cylinder, &faces, Dir::Z, angle, &neutral).unwrap();
+        let cones = result
+            .faces()
+            .iter()
+            .filter(|f| matches!(f.surface(), GeomSurface::Cone(_)))
+            .count();
+        assert_eq!(cones, 1);
+        // 中立面の高さで半径 1、上へ行くほど細くなる
+        let (r0, r1) = (1.0 + angle.tan(), 1.0 - angle.tan());
+        let volume = checked_volume(&result);
+        let expected = PI * 2.0 / 3.0 * (r0 * r0 + r0 * r1 + r1 * r1);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_draft_errors() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let block = Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap());
+        let sides = side_faces(&block);
+        assert!(draft(&block, &sides, Dir::Z, FRAC_PI_2, &Plane::xy()).is_err());
+        assert!(draft(&block, &[], Dir::Z, 0.1, &Plane::xy()).is_err());
+        // 上面は法線が抜く向きに平行
+        let top: Vec<Face> = block
+            .faces()
+            .into_iter()
+            .filter(|f| !sides.iter().any(|s| s.is_same(f)))
+            .collect();
+        assert!(draft(&block, &top, Dir::Z, 0.1, &Plane::xy()).is_err());
+        let other = Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap());
+        assert!(draft(&block, &side_faces(&other), Dir::Z, 0.1, &Plane::xy()).is_err());
+    }
+}
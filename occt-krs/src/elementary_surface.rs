@@ -0,0 +1,539 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+use crate::{Axis3, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 円柱面（OCCT の `Geom_CylindricalSurface` 相当）
+///
+/// `P(u, v) = O + R (cos u · X + sin u · Y) + v · Z`（u は 0〜2π、v は無限）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CylindricalSurface {
+    position: Axis3,
+    radius: f64,
+}
+
+/// 球面（OCCT の `Geom_SphericalSurface` 相当）
+///
+/// `P(u, v) = O + R cos v (cos u · X + sin u · Y) + R sin v · Z`（u は 0〜2π、v は -π/2〜π/2）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SphericalSurface {
+    position: Axis3,
+    radius: f64,
+}
+
+/// 円錐面（OCCT の `Geom_ConicalSurface` 相当）
+///
+/// 半頂角を `A`、基準半径を `R` として
+/// `P(u, v) = O + (R + v sin A)(cos u · X + sin u · Y) + v cos A · Z`（u は 0〜2π、v は無限）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConicalSurface {
+    position: Axis3,
+    semi_angle: f64,
+    radius: f64,
+}
+
+/// トーラス面（OCCT の `Geom_ToroidalSurface` 相当）
+///
+/// `P(u, v) = O + (R + r cos v)(cos u · X + sin u · Y) + r sin v · Z`（u, v は 0〜2π）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ToroidalSurface {
+    position: Axis3,
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+/// 局所座標系の XY 平面上で角度 `u` の方向 `cos u · X + sin u · Y` とその微分を返す
+pub(crate) fn radial(position: &Axis3, u: f64) -> (Vector3, Vector3) {
+    let (s, c) = u.sin_cos();
+    let (x, y) = (
+        position.x_direction().to_vector(),
+        position.y_direction().to_vector(),
+    );
+    (x * c + y * s, y * c - x * s)
+}
+
+/// 点の局所座標 `(x, y, z)` を返す
+fn local(position: &Axis3, p: Point3) -> (f64, f64, f64) {
+    let d = p - position.location();
+    (
+        d.dot(position.x_direction().to_vector()),
+        d.dot(position.y_direction().to_vector()),
+        d.dot(position.direction().to_vector()),
+    )
+}
+
+/// 局所座標から角度 u（0 以上 2π 未満、軸上では 0）を返す
+fn angle_of(x: f64, y: f64) -> f64 {
+    if x == 0.0 && y == 0.0 {
+        0.0
+    } else {
+        y.atan2(x).rem_euclid(TAU)
+    }
+}
+
+fn check_positive(name: &str, value: f64) -> Result<()> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(OcctKrsError::InvalidInput(format!(
+            "{}が不正です: {}",
+            name, value
+        )))
+    }
+}
+
+impl CylindricalSurface {
+    /// 局所座標系と半径から円柱面を生成する
+    /// 半径が正の有限値でない場合はエラーを返す
+    pub fn new(position: Axis3, radius: f64) -> Result<Self> {
+        check_positive("円柱の半径", radius)?;
+        Ok(Self { position, radius })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis3 {
+        self.position
+    }
+
+    /// 半径を返す
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let (e, _) = radial(&self.position, u);
+        self.position.location() + e * self.radius + self.position.direction().to_vector() * v
+    }
+
+    /// 点を曲面に射影したときのパラメータ `(u, v)` を返す
+    pub fn parameters_of(&self, p: Point3) -> (f64, f64) {
+        let (x, y, z) = local(&self.position, p);
+        (angle_of(x, y), z)
+    }
+
+    /// u の向きを反転した円柱面を返す
+    ///
+    /// 局所座標系の Y 方向を反転するので、元の `(u, v)` の点は `(-u, v)` の点になり、右手系と左手系が入れ替わる。
+    pub fn u_reversed(&self) -> Self {
+        let mut position = self.position;
+        position.y_reverse();
+        Self { position, ..*self }
+    }
+
+    /// 変換を適用した円柱面を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            position: self.position.transformed(t),
+            radius: self.radius * t.scale.abs(),
+        }
+    }
+}
+
+impl SphericalSurface {
+    /// 局所座標系と半径から球面を生成する
+    /// 半径が正の有限値でない場合はエラーを返す
+    pub fn new(position: Axis3, radius: f64) -> Result<Self> {
+        check_positive("球の半径", radius)?;
+        Ok(Self { position, radius })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis3 {
+        self.position
+    }
+
+    /// 中心を返す
+    pub fn center(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 半径を返す
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// 表面積を計算する
+    pub fn area(&self) -> f64 {
+        4.0 * PI * self.radius * self.radius
+    }
+
+    /// 体積を計算する
+    pub fn volume(&self) -> f64 {
+        4.0 / 3.0 * PI * self.radius.powi(3)
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let (e, _) = radial(&self.position, u);
+        let (s, c) = v.sin_cos();
+        self.center() + (e * c + self.position.direction().to_vector() * s) * self.radius
+    }
+
+    /// 点を曲面に射影したときのパラメータ `(u, v)` を返す（中心では `(0, 0)`）
+    pub fn parameters_of(&self, p: Point3) -> (f64, f64) {
+        let (x, y, z) = local(&self.position, p);
+        let rho = x.hypot(y);
+        let v = if rho == 0.0 && z == 0.0 {
+            0.0
+        } else {
+            z.atan2(rho)
+        };
+        (angle_of(x, y), v.clamp(-FRAC_PI_2, FRAC_PI_2))
+    }
+
+    /// u の向きを反転した球面を返す
+    ///
+    /// 局所座標系の Y 方向を反転するので、元の `(u, v)` の点は `(-u, v)` の点になり、右手系と左手系が入れ替わる。
+    pub fn u_reversed(&self) -> Self {
+        let mut position = self.position;
+        position.y_reverse();
+        Self { position, ..*self }
+    }
+
+    /// 変換を適用した球面を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            position: self.position.transformed(t),
+            radius: self.radius * t.scale.abs(),
+        }
+    }
+}
+
+impl ConicalSurface {
+    /// 局所座標系・半頂角・基準半径（v = 0 での半径）から円錐面を生成する
+    /// 半頂角の絶対値が 0 より大きく π/2 未満でない、または半径が負の場合はエラーを返す
+    pub fn new(position: Axis3, semi_angle: f64, radius: f64) -> Result<Self> {
+        if !(semi_angle.abs() > 0.0 && semi_angle.abs() < FRAC_PI_2) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "円錐の半頂角が不正です: {}",
+                semi_angle
+            )));
+        }
+        if !(radius >= 0.0 && radius.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "円錐の半径が不正です: {}",
+                radius
+            )));
+        }
+        Ok(Self {
+            position,
+            semi_angle,
+            radius,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis3 {
+        self.position
+    }
+
+    /// 半頂角を返す
+    pub fn semi_angle(&self) -> f64 {
+        self.semi_angle
+    }
+
+    /// 基準半径（v = 0 での半径）を返す
+    pub fn ref_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// 頂点を返す
+    pub fn apex(&self) -> Point3 {
+        let v = -self.radius / self.semi_angle.sin();
+        self.position.location()
+            + self.position.direction().to_vector() * (v * self.semi_angle.cos())
+    }
+
+    /// パラメータ `v` での半径 `R + v sin A`（頂点を越えると負になる）を返す
+    pub(crate) fn radius_at(&self, v: f64) -> f64 {
+        self.radius + v * self.semi_angle.sin()
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let (e, _) = radial(&self.position, u);
+        self.position.location()
+            + e * self.radius_at(v)
+            + self.position.direction().to_vector() * (v * self.semi_angle.cos())
+    }
+
+    /// 点を曲面に射影したときのパラメータ `(u, v)` を返す
+    pub fn parameters_of(&self, p: Point3) -> (f64, f64) {
+        let (x, y, z) = local(&self.position, p);
+        let (s, c) = self.semi_angle.sin_cos();
+        let rho = x.hypot(y);
+        // 母線の方向 (sin A, cos A) への射影で v を求める
+        let v = (rho - self.radius) * s + z * c;
+        if self.radius_at(v) < 0.0 {
+            // 頂点を越えた側では母線が反対側の角度にある
+            let v = (-rho - self.radius) * s + z * c;
+            return (angle_of(-x, -y), v);
+        }
+        (angle_of(x, y), v)
+    }
+
+    /// u の向きを反転した円錐面を返す
+    ///
+    /// 局所座標系の Y 方向を反転するので、元の `(u, v)` の点は `(-u, v)` の点になり、右手系と左手系が入れ替わる。
+    pub fn u_reversed(&self) -> Self {
+        let mut position = self.position;
+        position.y_reverse();
+        Self { position, ..*self }
+    }
+
+    /// 変換を適用した円錐面を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            position: self.position.transformed(t),
+            semi_angle: self.semi_angle,
+            radius: self.radius * t.scale.abs(),
+        }
+    }
+}
+
+impl ToroidalSurface {
+    /// 局所座標系・大半径・小半径からトーラス面を生成する
+    /// 半径が正の有限値でない場合はエラーを返す
+    pub fn new(position: Axis3, major_radius: f64, minor_radius: f64) -> Result<Self> {
+        check_positive("トーラスの大半径", major_radius)?;
+        check_positive("トーラスの小半径", minor_radius)?;
+        Ok(Self {
+            position,
+            major_radius,
+            minor_radius,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis3 {
+        self.position
+    }
+
+    /// 大半径を返す
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    /// 小半径を返す
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    /// 表面積を計算する
+    pub fn area(&self) -> f64 {
+        4.0 * PI * PI * self.major_radius * self.minor_radius
+    }
+
+    /// 体積を計算する
+    pub fn volume(&self) -> f64 {
+        2.0 * PI * PI * self.major_radius * self.minor_radius * self.minor_radius
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let (e, _) = radial(&self.position, u);
+        let (s, c) = v.sin_cos();
+        self.position.location()
+            + e * (self.major_radius + self.minor_radius * c)
+            + self.position.direction().to_vector() * (self.minor_radius * s)
+    }
+
+    /// 点を曲面に射影したときのパラメータ `(u, v)` を返す
+    pub fn parameters_of(&self, p: Point3) -> (f64, f64) {
+        let (x, y, z) = local(&self.position, p);
+        let r = x.hypot(y) - self.major_radius;
+        (angle_of(x, y), angle_of(r, z))
+    }
+
+    /// u の向きを反転したトーラス面を返す
+    ///
+    /// 局所座標系の Y 方向を反転するので、元の `(u, v)` の点は `(-u, v)` の点になり、右手系と左手系が入れ替わる。
+    pub fn u_reversed(&self) -> Self {
+        let mut position = self.position;
+        position.y_reverse();
+        Self { position, ..*self }
+    }
+
+    /// 変換を適用したトーラス面を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        let k = t.scale.abs();
+        Self {
+            position: self.position.transformed(t),
+            major_radius: self.major_radius * k,
+            minor_radius: self.minor_radius * k,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dir, Quaternion, Surface};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!((a - b).length() < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn tilted() -> Axis3 {
+        Axis3::new(
+            Point3::new(1.0, -2.0, 0.5),
+            Dir::new(1.0, 1.0, 1.0).unwrap(),
+            Dir::x_axis(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        let a = Axis3::from_normal(Point3::ORIGIN, Dir::z_axis());
+        assert!(CylindricalSurface::new(a, 0.0).is_err());
+        assert!(SphericalSurface::new(a, f64::NAN).is_err());
+        assert!(ConicalSurface::new(a, 0.0, 1.0).is_err());
+        assert!(ConicalSurface::new(a, FRAC_PI_2, 1.0).is_err());
+        assert!(ConicalSurface::new(a, 0.3, -1.0).is_err());
+        assert!(ConicalSurface::new(a, -0.3, 0.0).is_ok());
+        assert!(ToroidalSurface::new(a, 2.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_occt_parameterization() {
+        let a = Axis3::from_normal(Point3::ORIGIN, Dir::z_axis());
+        let cyl = CylindricalSurface::new(a, 2.0).unwrap();
+        assert_point_eq(cyl.point_at(FRAC_PI_2, 3.0), Point3::new(0.0, 2.0, 3.0));
+        let sph = SphericalSurface::new(a, 2.0).unwrap();
+        assert_point_eq(sph.point_at(0.0, FRAC_PI_2), Point3::new(0.0, 0.0, 2.0));
+        assert_point_eq(sph.point_at(PI, 0.0), Point3::new(-2.0, 0.0, 0.0));
+        let cone = ConicalSurface::new(a, PI / 6.0, 1.0).unwrap();
+        assert_point_eq(
+            cone.point_at(0.0, 2.0),
+            Point3::new(2.0, 0.0, 3.0_f64.sqrt()),
+        );
+        assert_point_eq(cone.apex(), Point3::new(0.0, 0.0, -(3.0_f64.sqrt())));
+        let torus = ToroidalSurface::new(a, 3.0, 1.0).unwrap();
+        assert_point_eq(torus.point_at(0.0, PI), Point3::new(2.0, 0.0, 0.0));
+        assert_point_eq(
+            torus.point_at(FRAC_PI_2, FRAC_PI_2),
+            Point3::new(0.0, 3.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn test_parameters_round_trip() {
+        let cyl = CylindricalSurface::new(tilted(), 1.5).unwrap();
+        let sph = SphericalSurface::new(tilted(), 1.5).unwrap();
+        let cone = ConicalSurface::new(tilted(), 0.4, 1.0).unwrap();
+        let torus = ToroidalSurface::new(tilted(), 3.0, 0.5).unwrap();
+        for &(u, v) in &[(0.3, 0.7), (2.5, -1.2), (5.9, 0.1)] {
+            let (pu, pv) = cyl.parameters_of(cyl.point_at(u, v));
+            assert!((pu - u).abs() < 1e-12 && (pv - v).abs() < 1e-12);
+            let (pu, pv) = sph.parameters_of(sph.point_at(u, v));
+            assert!((pu - u).abs() < 1e-12 && (pv - v).abs() < 1e-12);
+            let (pu, pv) = cone.parameters_of(cone.point_at(u, v));
+            assert!((pu - u).abs() < 1e-12 && (pv - v).abs() < 1e-12);
+            let tv = v.rem_euclid(TAU);
+            let (pu, pv) = torus.parameters_of(torus.point_at(u, tv));
+            assert!((pu - u).abs() < 1e-12 && (pv - tv).abs() < 1e-12);
+        }
+        // 頂点を越えた側の円錐
+        let (u, v) = cone.parameters_of(cone.point_at(1.0, -4.0));
+        assert_point_eq(cone.point_at(u, v), cone.point_at(1.0, -4.0));
+        assert!((v + 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_exact_derivatives_match_differences() {
+        let surfaces: Vec<Box<dyn Surface>> = vec![
+            Box::new(CylindricalSurface::new(tilted(), 1.5).unwrap()),
+            Box::new(SphericalSurface::new(tilted(), 1.5).unwrap()),
+            Box::new(ConicalSurface::new(tilted(), -0.4, 1.0).unwrap()),
+            Box::new(ToroidalSurface::new(tilted(), 3.0, 0.5).unwrap()),
+        ];
+        let (u, v, h) = (0.8, 0.3, 1e-5);
+        for s in &surfaces {
+            let du = (s.point_at(u + h, v) - s.point_at(u - h, v)) / (2.0 * h);
+            let dv = (s.point_at(u, v + h) - s.point_at(u, v - h)) / (2.0 * h);
+            assert!((du - s.derivative_u_at(u, v)).length() < 1e-8);
+            assert!((dv - s.derivative_v_at(u, v)).length() < 1e-8);
+            let (suu, suv, svv) = s.second_derivatives_at(u, v);
+            let duu = (s.derivative_u_at(u + h, v) - s.derivative_u_at(u - h, v)) / (2.0 * h);
+            let duv = (s.derivative_u_at(u, v + h) - s.derivative_u_at(u, v - h)) / (2.0 * h);
+            let dvv = (s.derivative_v_at(u, v + h) - s.derivative_v_at(u, v - h)) / (2.0 * h);
+            assert!((suu - duu).length() < 1e-8);
+            assert!((suv - duv).length() < 1e-8);
+            assert!((svv - dvv).length() < 1e-8);
+            // 法線は Su × Sv の向き
+            let n = s.normal_at(u, v).unwrap().to_vector();
+            let cross = s.derivative_u_at(u, v).cross(s.derivative_v_at(u, v));
+            assert!((n - cross.normalized()).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_normals_and_singularities() {
+        let a = Axis3::from_normal(Point3::ORIGIN, Dir::z_axis());
+        let sph = SphericalSurface::new(a, 2.0).unwrap();
+        // 極でも法線は外向き
+        let n = sph.normal_at(1.0, FRAC_PI_2).unwrap();
+        assert!((n.to_vector() - Vector3::Z).length() < 1e-12);
+        let cone = ConicalSurface::new(a, 0.5, 1.0).unwrap();
+        let apex_v = -1.0 / 0.5_f64.sin();
+        assert!(cone.normal_at(0.0, apex_v).is_none());
+        // 頂点の両側で法線は外向き（軸から離れる向き）
+        for &v in &[apex_v - 1.0, 1.0] {
+            let p = cone.point_at(0.3, v);
+            let n = cone.normal_at(0.3, v).unwrap().to_vector();
+            assert!(n.dot(Vector3::new(p.x, p.y, 0.0)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_closedness() {
+        let a = tilted();
+        let cyl = CylindricalSurface::new(a, 1.0).unwrap();
+        assert!(cyl.is_u_closed() && !cyl.is_v_closed());
+        let torus = ToroidalSurface::new(a, 2.0, 0.5).unwrap();
+        assert!(torus.is_u_closed() && torus.is_v_closed());
+        let sph = SphericalSurface::new(a, 1.0).unwrap();
+        assert!(sph.is_u_closed() && !sph.is_v_closed());
+        assert!((sph.area() - 4.0 * PI).abs() < 1e-12);
+        assert!((torus.area() - 4.0 * PI * PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transformed() {
+        let t = Transform::new(
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 1.0), 0.7),
+            Vector3::new(1.0, 2.0, 3.0),
+            2.0,
+        );
+        let torus = ToroidalSurface::new(tilted(), 3.0, 0.5).unwrap();
+        let moved = torus.transformed(&t);
+        assert!((moved.minor_radius() - 1.0).abs() < 1e-12);
+        assert_point_eq(
+            moved.point_at(1.1, 2.2),
+            t.transform_point(torus.point_at(1.1, 2.2)),
+        );
+
+        // 負のスケールでは座標系が左手系になり、法線 Su × Sv は内向きになる（OCCT と同じ）
+        let mirror = Transform::new(Quaternion::identity(), Vector3::ZERO, -1.0);
+        let sph = SphericalSurface::new(tilted(), 1.5)
+            .unwrap()
+            .transformed(&mirror);
+        assert!(!sph.position().is_direct());
+        let p = sph.point_at(0.4, 0.2);
+        let n = sph.normal_at(0.4, 0.2).unwrap().to_vector();
+        assert!(n.dot(p - sph.center()) < 0.0);
+        let cone = ConicalSurface::new(tilted(), 0.4, 1.0).unwrap();
+        let mirrored = cone.transformed(&mirror);
+        assert_point_eq(
+            mirrored.point_at(0.4, 0.2),
+            mirror.transform_point(cone.point_at(0.4, 0.2)),
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use crate::boolean::{cut_all, fuse};
+use crate::classify::FaceClassifier;
+use crate::offset::offset_shells;
+use crate::{
+    extrude, precision, project_curve_onto_surface, thicken, BSplineCurve, Compound, Edge,
+    EndConditions, Face, GeomSurface, OcctKrsError, Orientation, Point3, PointClassification,
+    Result, Shape, Shell, Surface, Transform, TrimmedCurve, Wire,
+};
+
+/// 射影した断面の境界のエッジを曲面に載せるときの、エッジあたりの区間数
+const EDGE_SAMPLES: usize = 16;
+
+/// 断面を射影するときの許容誤差
+const PROJECTION_TOLERANCE: f64 = 1e-6;
+
+/// 立体 `shape` のフェイス `face` に断面 `profile` を浮き彫りにする
+///
+/// 断面の各フェイスの境界をフェイスの曲面へ最近点で射影し、射影した図形を曲面の法線に沿って外側へ
+/// `depth` だけ厚みをつけて立体に加える。平面のフェイスと平行な平面上の断面は、法線に沿って
+/// フェイスの平面へ移して押し出すだけにする。それ以外では、面と重ならないように厚みを深さの 1/4 だけ
+/// 面の内側から始める。断面はフェイス、閉じたワイヤ、またはそれらの `Compound`
+/// （文字ごとのフェイスなど）で、穴のある図形はフェイスで渡す。断面のフェイスどうしは重ならないこと。
+///
+/// 深さが正の有限値でない場合、フェイスが立体のものでない場合、断面がフェイスや閉じたワイヤでない場合、
+/// 射影した断面がフェイスからはみ出す場合、射影や厚み付け、ブーリアン演算に失敗した場合はエラーを返す。
+pub fn emboss(shape: &Shape, face: &Face, profile: &Shape, depth: f64) -> Result<Shape> {
+    let prisms = prisms(shape, face, profile, depth, true)?;
+    fuse(shape, &Shape::from(Compound::new(prisms)))
+}
+
+/// 立体 `shape` のフェイス `face` に断面 `profile` を彫り込む
+///
+/// 断面をフェイスの曲面へ射影し、内側へ `depth` だけ厚みをつけた部分を立体から取り除く。
+/// 射影の仕方やエラーの条件は [`emboss`] と同じ（断面のフェイスどうしは重なってもよい）。
+pub fn engrave(shape: &Shape, face: &Face, profile: &Shape, depth: f64) -> Result<Shape> {
+    let prisms = prisms(shape, face, profile, depth, false)?;
+    cut_all(shape, &prisms)
+}
+
+/// 断面のフェイスをフェイスの曲面へ射影し、`raised` なら外側へ、そうでなければ内側へ厚みをつけた立体
+fn prisms(
+    shape: &Shape,
+    face: &Face,
+    profile: &Shape,
+    depth: f64,
+    raised: bool,
+) -> Result<Vec<Shape>> {
+    if !(depth > 0.0 && depth.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "浮き彫り・彫り込みの深さが不正です: {}",
+            depth
+        )));
+    }
+    if !shape.faces().iter().any(|f| f.is_same(face)) {
+        return Err(OcctKrsError::InvalidInput(
+            "浮き彫り・彫り込みをするフェイスが立体にありません".to_string(),
+        ));
+    }
+    let mut faces = Vec::new();
+    profile_faces(profile, &mut faces)?;
+    if faces.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "断面にフェイスがありません".to_string(),
+        ));
+    }
+    let surface = face.located_geometry().into_owned();
+    let classifier = FaceClassifier::new(face)?;
+    // 曲面の法線に対する外向きの符号
+    let outward = if face.orientation() == Orientation::Reversed {
+        -1.0
+    } else {
+        1.0
+    };
+    let mut prisms = Vec::with_capacity(faces.len());
+    for f in faces {
+        let prism = match (&surface, f.located_geometry().as_ref()) {
+            (GeomSurface::Plane(plane), GeomSurface::Plane(p))
+                if p.normal()
+                    .to_vector()
+                    .cross(plane.normal().to_vector())
+                    .length()
+                    <= precision::angular() =>
+            {
+                let n = plane.normal();
+                let offset = plane.signed_distance(p.origin());
+                let projected = f.moved(&Transform::from_translation(n.to_vector() * -offset));
+                let points = projected
+                    .wires()
+                    .iter()
+                    .flat_map(Wire::edges)
+                    .flat_map(|e| edge_samples(&e))
+                    .collect::<Vec<_>>();
+                check_inside(&classifier, &points)?;
+                let direction = if outward < 0.0 { n.reversed() } else { n };
+                extrude(
+                    &Shape::from(projected),
+                    direction,
+                    if raised { depth } else { -depth },
+                )?
+            }
+            _ => {
+                let patch = projected_patch(&classifier, &surface, &f)?;
+                // 面と重ならないように、反対側へ深さの 1/4 だけ食い込ませてから厚みをつける
+                let overlap = depth / 4.0;
+                let (start, thickness) = if raised {
+                    (-overlap, depth + overlap)
+                } else {
+                    (overlap, -(depth + overlap))
+                };
+                let base =
+                    offset_shells(&Shape::from(Shell::new(vec![patch])?), |_| outward * start)?;
+                thicken(&base, outward * thickness)?
+            }
+        };
+        prisms.push(prism);
+    }
+    Ok(prisms)
+}
+
+/// 断面のフェイスの境界を曲面へ最近点で射影し、曲面上のフェイスにする
+///
+/// 射影した境界がフェイス（`classifier`）の外に出る場合はエラーを返す。
+fn projected_patch(
+    classifier: &FaceClassifier,
+    surface: &GeomSurface,
+    profile: &Face,
+) -> Result<Face> {
+    let mut wires = Vec::new();
+    for wire in profile.wires() {
+        let mut edges = Vec::new();
+        for edge in wire.edges() {
+            let Some(curve) = edge.located_geometry() else {
+                continue;
+            };
+            let (first, last) = edge.range();
+            let trimmed = TrimmedCurve::new(curve.into_owned(), first, last)?;
+            let pcurve =
+                project_curve_onto_surface(&trimmed, surface, PROJECTION_TOLERANCE)?.pcurve;
+            let points: Vec<Point3> = (0..=EDGE_SAMPLES)
+                .map(|k| {
+                    let uv =
+                        pcurve.point_at(first + (last - first) * k as f64 / EDGE_SAMPLES as f64);
+                    surface.point_at(uv.x, uv.y)
+                })
+                .collect();
+            check_inside(classifier, &points)?;
+            let projected = BSplineCurve::interpolate(&points, EndConditions::Natural)?;
+            edges.push(Edge::from_curve(projected)?);
+        }
+        wires.push(Wire::fix_edges(edges, PROJECTION_TOLERANCE)?);
+    }
+    let Some((outer, inners)) = wires.split_first() else {
+        return Err(OcctKrsError::InvalidInput(
+            "境界を持たない断面は射影できません".to_string(),
+        ));
+    };
+    Face::from_wires_on_surface(
+        surface.clone(),
+        outer.clone(),
+        inners.to_vec(),
+        PROJECTION_TOLERANCE,
+    )
+}
+
+/// エッジを等分した点
+fn edge_samples(edge: &Edge) -> Vec<Point3> {
+    let (first, last) = edge.range();
+    (0..=EDGE_SAMPLES)
+        .map(|k| edge.point_at(first + (last - first) * k as f64 / EDGE_SAMPLES as f64))
+        .collect()
+}
+
+/// 射影した断面の点がすべてフェイスの内側か境界上にあることを確かめる
+fn check_inside(classifier: &FaceClassifier, points: &[Point3]) -> Result<()> {
+    let tolerance = PROJECTION_TOLERANCE;
+    if points
+        .iter()
+        .any(|&p| classifier.classify(p, tolerance) == PointClassification::Outside)
+    {
+        return Err(OcctKrsError::InvalidInput(
+            "射影した断面がフェイスからはみ出しています".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 断面に含まれるフェイス（閉じたワイヤは平面のフェイスにする）を集める
+fn profile_faces(profile: &Shape, faces: &mut Vec<Face>) -> Result<()> {
+    match profile {
+        Shape::Face(f) => faces.push(f.clone()),
+        Shape::Wire(w) => faces.push(Face::from_planar_wires(
+            w.clone(),
+            Vec::new(),
+            precision::confusion(),
+        )?),
+        Shape::Compound(c) => {
+            for s in c.shapes() {
+                profile_faces(&s, faces)?;
+            }
+        }
+        _ => {
+            return Err(OcctKrsError::InvalidInput(
+                "断面はフェイスか閉じたワイヤにしてください".to_string(),
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Circle, Dir, ShapeKind, Vector3};
+    use std::f64::consts::PI;
+
+    fn block() -> Shape {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, 2.0, 2.0, 1.0).unwrap())
+    }
+
+    fn top(shape: &Shape) -> Face {
+        shape
+            .faces()
+            .into_iter()
+            .find(|f| match f.located_geometry().as_ref() {
+                GeomSurface::Plane(p) => p.origin().z == 1.0 && p.normal().dot(Dir::Z).abs() > 0.5,
+                _ => false,
+            })
+            .unwrap()
+    }
+
+    /// xy 平面に平行な高さ `z` の長方形のワイヤ
+    fn rectangle(x0: f64, y0: f64, x1: f64, y1: f64, z: f64) -> Wire {
+        let p = [
+            Point3::new(x0, y0, z),
+            Point3::new(x1, y0, z),
+            Point3::new(x1, y1, z),
+            Point3::new(x0, y1, z),
+        ];
+        let edges = (0..4)
+            .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        Wire::from_edges(edges, 1e-9).unwrap()
+    }
+
+    #[test]
+    fn test_emboss() {
+        let shape = block();
+        // 上面より高い位置に描いた断面を上面へ投影する
+        let profile = Shape::from(rectangle(0.5, 0.5, 1.5, 1.0, 5.0));
+        let result = emboss(&shape, &top(&shape), &profile, 0.25).unwrap();
+        assert_eq!(result.kind(), ShapeKind::Solid);
+        assert!((checked_volume(&result) - (4.0 + 0.5 * 0.25)).abs() < 1e-9);
+        assert!(result
+            .vertices()
+            .iter()
+            .any(|v| (v.point().z - 1.25).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_engrave() {
+        let shape = block();
+        // 離れた2つの図形（長方形と円）をまとめて彫り込む
+        let circle = Circle::from_center_normal(Point3::new(1.5, 1.5, 0.0), Dir::Z, 0.25).unwrap();
+        let disk = Face::from_planar_wires(
+            Wire::from_edges(vec![Edge::from_curve(circle).unwrap()], 1e-9).unwrap(),
+            Vec::new(),
+            1e-9,
+        )
+        .unwrap();
+        let profile = Shape::from(Compound::new(vec![
+            Shape::from(rectangle(0.25, 0.25, 1.0, 0.75, 0.0)),
+            Shape::from(disk),
+        ]));
+        let result = engrave(&shape, &top(&shape), &profile, 0.5).unwrap();
+        let removed = 0.75 * 0.5 * 0.5 + PI * 0.0625 * 0.5;
+        let volume = checked_volume(&result);
+        assert!((volume / (4.0 - removed) - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_emboss_cylinder() {
+        // 半径 2 の円柱の側面に、外側の平面 y = 3 に描いた長方形を射影する（継ぎ目は +X 側）
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let shape = Shape::from(make_cylinder(position, 2.0, 4.0).unwrap());
+        let side = shape
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.located_geometry().as_ref(), GeomSurface::Cylinder(_)))
+            .unwrap();
+        let p = [
+            Point3::new(0.5, 3.0, 1.0),
+            Point3::new(-0.5, 3.0, 1.0),
+            Point3::new(-0.5, 3.0, 2.0),
+            Point3::new(0.5, 3.0, 2.0),
+        ];
+        let edges = (0..4)
+            .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        let profile = Shape::from(Wire::from_edges(edges, 1e-9).unwrap());
+        // 射影した長方形は角度 ±atan(0.5/3)、高さ 1..2 の円筒面の帯になり、半径 2 ± depth まで盛り上がる・沈む
+        let angle = (0.5f64 / 3.0).atan();
+        let depth: f64 = 0.25;
+        for (result, radius) in [
+            (emboss(&shape, &side, &profile, depth).unwrap(), 2.0 + depth),
+            (
+                engrave(&shape, &side, &profile, depth).unwrap(),
+                2.0 - depth,
+            ),
+        ] {
+            assert_eq!(result.kind(), ShapeKind::Solid);
+            // 扇形の帯の分だけ体積が増減する（断面の辺は補間曲線なので、その誤差の分だけずれる）
+            let expected = PI * 16.0 + angle * (radius * radius - 4.0);
+            let volume = checked_volume(&result);
+            assert!((volume / expected - 1.0).abs() < 1e-5, "{}", volume);
+            for (x, z) in [(1.0, 1.0), (-1.0, 1.0), (-1.0, 2.0), (1.0, 2.0)] {
+                let corner = Point3::new(x * radius * angle.sin(), radius * angle.cos(), z);
+                assert!(
+                    result
+                        .vertices()
+                        .iter()
+                        .any(|v| v.point().distance(corner) < 1e-6),
+                    "{:?}",
+                    corner
+                );
+            }
+        }
+
+        // 円柱の高さからはみ出す断面
+        let tall = profile.moved(&Transform::from_translation(Vector3::new(0.0, 0.0, 2.5)));
+        assert!(emboss(&shape, &side, &tall, depth).is_err());
+    }
+
+    #[test]
+    fn test_emboss_errors() {
+        let shape = block();
+        let face = top(&shape);
+        let profile = Shape::from(rectangle(0.5, 0.5, 1.5, 1.0, 0.0));
+        assert!(emboss(&shape, &face, &profile, 0.0).is_err());
+        assert!(engrave(&shape, &face, &Shape::from(Compound::new(Vec::new())), 0.1).is_err());
+        // 上面からはみ出す断面
+        let overhanging = Shape::from(rectangle(1.5, 0.5, 2.5, 1.0, 3.0));
+        assert!(emboss(&shape, &face, &overhanging, 0.1).is_err());
+        assert!(engrave(&shape, &face, &overhanging, 0.1).is_err());
+        // 立体にないフェイス
+        let other = top(&block());
+        assert!(emboss(&shape, &other, &profile, 0.1).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+/// ゼロ長（またはほぼゼロ長）のベクトルを正規化しようとした場合のエラー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeError;
+
+impl fmt::Display for NormalizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ゼロ長ベクトルは正規化できません")
+    }
+}
+
+impl std::error::Error for NormalizeError {}
+
+/// 文字列からベクトルや点を読み取れなかった場合のエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVectorError {
+    pub(crate) message: String,
+}
+
+impl ParseVectorError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseVectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ベクトルを解析できません: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseVectorError {}
+
+/// クレート全体で用いるエラー型
+///
+/// 失敗の原因ごとにバリアントを分けているので、利用側で `match` して処理を分岐できる。
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OcctKrsError {
+    /// ファイル入出力に失敗した
+    Io(std::io::Error),
+    /// JSON のシリアライズ・デシリアライズに失敗した
+    #[cfg(feature = "serde")]
+    Serde(serde_json::Error),
+    /// ベクトルの正規化に失敗した
+    Normalize(NormalizeError),
+    /// 退化した（長さや面積がゼロの、平行な等）幾何形状のため処理できない
+    DegenerateGeometry(String),
+    /// 要求された許容誤差を満たせなかった
+    ToleranceExceeded { tolerance: f64, deviation: f64 },
+    /// 引数が不正
+    InvalidInput(String),
+    /// 文字列の解析に失敗した
+    Parse(ParseVectorError),
+}
+
+/// クレート全体で用いる `Result` 型
+pub type Result<T> = std::result::Result<T, OcctKrsError>;
+
+impl fmt::Display for OcctKrsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcctKrsError::Io(e) => write!(f, "入出力エラー: {}", e),
+            #[cfg(feature = "serde")]
+            OcctKrsError::Serde(e) => write!(f, "JSON変換エラー: {}", e),
+            OcctKrsError::Normalize(e) => e.fmt(f),
+            OcctKrsError::DegenerateGeometry(msg) => write!(f, "退化した幾何形状です: {}", msg),
+            OcctKrsError::ToleranceExceeded {
+                tolerance,
+                deviation,
+            } => write!(
+                f,
+                "許容誤差を満たせません（許容誤差: {}, 誤差: {}）",
+                tolerance, deviation
+            ),
+            OcctKrsError::InvalidInput(msg) => write!(f, "不正な入力です: {}", msg),
+            OcctKrsError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for OcctKrsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OcctKrsError::Io(e) => Some(e),
+            #[cfg(feature = "serde")]
+            OcctKrsError::Serde(e) => Some(e),
+            OcctKrsError::Normalize(e) => Some(e),
+            OcctKrsError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for OcctKrsError {
+    fn from(e: std::io::Error) -> Self {
+        OcctKrsError::Io(e)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for OcctKrsError {
+    fn from(e: serde_json::Error) -> Self {
+        OcctKrsError::Serde(e)
+    }
+}
+
+impl From<NormalizeError> for OcctKrsError {
+    fn from(e: NormalizeError) -> Self {
+        OcctKrsError::Normalize(e)
+    }
+}
+
+impl From<ParseVectorError> for OcctKrsError {
+    fn from(e: ParseVectorError) -> Self {
+        OcctKrsError::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector3;
+
+    #[test]
+    fn test_question_mark_conversion() {
+        fn unit_x(v: Vector3) -> Result<f64> {
+            Ok(v.try_normalized()?.x)
+        }
+        assert_eq!(unit_x(Vector3::new(2.0, 0.0, 0.0)).unwrap(), 1.0);
+        let err = unit_x(Vector3::new(0.0, 0.0, 0.0)).unwrap_err();
+        assert!(matches!(err, OcctKrsError::Normalize(NormalizeError)));
+        assert_eq!(err.to_string(), "ゼロ長ベクトルは正規化できません");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_io_error_conversion() {
+        let result = crate::output_vector_as_json(
+            &Vector3::new(1.0, 2.0, 3.0),
+            "/nonexistent-directory/vector.json",
+        );
+        let err = result.unwrap_err();
+        assert!(matches!(err, OcctKrsError::Io(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Matrix3, Quaternion, Vector3};
+
+/// オイラー角の回転順序
+///
+/// `XYZ` は回転行列 `Rx(x) * Ry(y) * Rz(z)` を表す（X → Y' → Z'' の内因性回転）。
+/// 他の順序も同様に、名前の順に行列を左から掛ける。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EulerOrder {
+    XYZ,
+    ZYX,
+    ZXY,
+}
+
+/// オイラー角（各軸まわりの回転角、ラジアン）
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EulerAngles {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub order: EulerOrder,
+}
+
+/// ジンバルロックとみなす中間軸の sin の閾値
+const GIMBAL_THRESHOLD: f64 = 1.0 - 1e-12;
+
+impl EulerAngles {
+    /// 各軸の角度と回転順序からオイラー角を生成する
+    pub fn new(x: f64, y: f64, z: f64, order: EulerOrder) -> Self {
+        Self { x, y, z, order }
+    }
+}
+
+impl Matrix3 {
+    /// オイラー角から回転行列を生成する
+    pub fn from_euler(e: &EulerAngles) -> Self {
+        let rx = Matrix3::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), e.x);
+        let ry = Matrix3::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), e.y);
+        let rz = Matrix3::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), e.z);
+        match e.order {
+            EulerOrder::XYZ => rx * ry * rz,
+            EulerOrder::ZYX => rz * ry * rx,
+            EulerOrder::ZXY => rz * rx * ry,
+        }
+    }
+
+    /// 回転行列から指定した順序のオイラー角を取り出す
+    ///
+    /// 中間軸の角度が ±π/2 となるジンバルロック状態では、最後の軸の角度を 0 として
+    /// 残りの回転を最初の軸に割り当てる。
+    pub fn to_euler(&self, order: EulerOrder) -> EulerAngles {
+        let m = &self.m;
+        let (x, y, z) = match order {
+            EulerOrder::XYZ => {
+                let s = m[0][2].clamp(-1.0, 1.0);
+                let y = s.asin();
+                if s.abs() < GIMBAL_THRESHOLD {
+                    ((-m[1][2]).atan2(m[2][2]), y, (-m[0][1]).atan2(m[0][0]))
+                } else {
+                    (m[2][1].atan2(m[1][1]), y, 0.0)
+                }
+            }
+            EulerOrder::ZYX => {
+                let s = -m[2][0].clamp(-1.0, 1.0);
+                let y = s.asin();
+                if s.abs() < GIMBAL_THRESHOLD {
+                    (m[2][1].atan2(m[2][2]), y, m[1][0].atan2(m[0][0]))
+                } else {
+                    (0.0, y, (-m[0][1]).atan2(m[1][1]))
+                }
+            }
+            EulerOrder::ZXY => {
+                let s = m[2][1].clamp(-1.0, 1.0);
+                let x = s.asin();
+                if s.abs() < GIMBAL_THRESHOLD {
+                    (x, (-m[2][0]).atan2(m[2][2]), (-m[0][1]).atan2(m[1][1]))
+                } else {
+                    (x, 0.0, m[1][0].atan2(m[0][0]))
+                }
+            }
+        };
+        EulerAngles::new(x, y, z, order)
+    }
+}
+
+impl Quaternion {
+    /// オイラー角から回転を表す四元数を生成する
+    pub fn from_euler(e: &EulerAngles) -> Self {
+        let qx = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), e.x);
+        let qy = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), e.y);
+        let qz = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), e.z);
+        match e.order {
+            EulerOrder::XYZ => qx * qy * qz,
+            EulerOrder::ZYX => qz * qy * qx,
+            EulerOrder::ZXY => qz * qx * qy,
+        }
+    }
+
+    /// 指定した順序のオイラー角を取り出す（単位四元数であることを前提とする）
+    pub fn to_euler(self, order: EulerOrder) -> EulerAngles {
+        self.to_matrix3().to_euler(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    const ORDERS: [EulerOrder; 3] = [EulerOrder::XYZ, EulerOrder::ZYX, EulerOrder::ZXY];
+
+    fn assert_matrix_eq(a: &Matrix3, b: &Matrix3) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-9, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_all_orders() {
+        for order in ORDERS {
+            let e = EulerAngles::new(0.3, -0.7, 1.2, order);
+            let m = Matrix3::from_euler(&e);
+            let back = m.to_euler(order);
+            assert!((back.x - e.x).abs() < 1e-12, "{:?}", order);
+            assert!((back.y - e.y).abs() < 1e-12, "{:?}", order);
+            assert!((back.z - e.z).abs() < 1e-12, "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_matches_matrix() {
+        for order in ORDERS {
+            let e = EulerAngles::new(-1.0, 0.4, 2.5, order);
+            let q = Quaternion::from_euler(&e);
+            assert_matrix_eq(&q.to_matrix3(), &Matrix3::from_euler(&e));
+            let back = q.to_euler(order);
+            assert_matrix_eq(&Matrix3::from_euler(&back), &Matrix3::from_euler(&e));
+        }
+    }
+
+    #[test]
+    fn test_xyz_convention() {
+        // XYZ は Rx * Ry * Rz の順で掛ける
+        let e = EulerAngles::new(FRAC_PI_2, 0.0, FRAC_PI_2, EulerOrder::XYZ);
+        let v = Matrix3::from_euler(&e) * Vector3::new(1.0, 0.0, 0.0);
+        // Rz で (0,1,0)、続いて Rx で (0,0,1)
+        assert!((v - Vector3::new(0.0, 0.0, 1.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn test_gimbal_lock() {
+        for order in ORDERS {
+            // 中間軸が ±π/2 になる回転
+            let e = match order {
+                EulerOrder::XYZ | EulerOrder::ZYX => EulerAngles::new(0.4, FRAC_PI_2, 0.9, order),
+                EulerOrder::ZXY => EulerAngles::new(-FRAC_PI_2, 0.4, 0.9, order),
+            };
+            let m = Matrix3::from_euler(&e);
+            let back = m.to_euler(order);
+            assert!(back.x.is_finite() && back.y.is_finite() && back.z.is_finite());
+            // 角度の組は一意でないが、同じ回転を表す
+            assert_matrix_eq(&Matrix3::from_euler(&back), &m);
+        }
+    }
+}
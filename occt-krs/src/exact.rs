@@ -0,0 +1,436 @@
+//! 適応精度による頑健な幾何述語（Shewchuk の手法）
+//!
+//! 浮動小数点の誤差により符号を誤りやすい、ほぼ退化した配置でも正しい符号を返す。
+//! まず通常の浮動小数点演算で計算し、誤差限界を超えて結果が確定しない場合のみ
+//! 誤差のない多倍長表現（展開、expansion）で計算し直す。
+//!
+//! 戻り値は行列式の近似値で、符号は常に正確である。
+
+use crate::{Point3, Vector2};
+
+/// 丸め単位（2^-53）
+const EPSILON: f64 = f64::EPSILON * 0.5;
+const CCW_ERRBOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const O3D_ERRBOUND: f64 = (7.0 + 56.0 * EPSILON) * EPSILON;
+const ICC_ERRBOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+const ISP_ERRBOUND: f64 = (16.0 + 224.0 * EPSILON) * EPSILON;
+
+/// 3点 a, b, c の向きを判定する
+///
+/// 反時計回りなら正、時計回りなら負、同一直線上なら 0 を返す。
+pub fn orient2d(a: Vector2, b: Vector2, c: Vector2) -> f64 {
+    let detleft = (a.x - c.x) * (b.y - c.y);
+    let detright = (a.y - c.y) * (b.x - c.x);
+    let det = detleft - detright;
+    let errbound = CCW_ERRBOUND * (detleft.abs() + detright.abs());
+    if det.abs() > errbound {
+        return det;
+    }
+
+    let acx = Expansion::diff(a.x, c.x);
+    let bcx = Expansion::diff(b.x, c.x);
+    let acy = Expansion::diff(a.y, c.y);
+    let bcy = Expansion::diff(b.y, c.y);
+    acx.mul(&bcy).sub(&acy.mul(&bcx)).signed_estimate()
+}
+
+/// 点 d が a, b, c を通る平面のどちら側にあるかを判定する
+///
+/// 平面の上方から見て a, b, c が反時計回りに並ぶとき、d が平面の下方にあれば正、
+/// 上方にあれば負、同一平面上なら 0 を返す（Shewchuk の `orient3d` と同じ規約）。
+pub fn orient3d(a: Point3, b: Point3, c: Point3, d: Point3) -> f64 {
+    let (adx, ady, adz) = (a.x - d.x, a.y - d.y, a.z - d.z);
+    let (bdx, bdy, bdz) = (b.x - d.x, b.y - d.y, b.z - d.z);
+    let (cdx, cdy, cdz) = (c.x - d.x, c.y - d.y, c.z - d.z);
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+
+    let det = adz * (bdxcdy - cdxbdy) + bdz * (cdxady - adxcdy) + cdz * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * adz.abs()
+        + (cdxady.abs() + adxcdy.abs()) * bdz.abs()
+        + (adxbdy.abs() + bdxady.abs()) * cdz.abs();
+    if det.abs() > O3D_ERRBOUND * permanent {
+        return det;
+    }
+
+    let [adx, ady, adz] = diff3(a, d);
+    let [bdx, bdy, bdz] = diff3(b, d);
+    let [cdx, cdy, cdz] = diff3(c, d);
+    let bc = bdx.mul(&cdy).sub(&cdx.mul(&bdy));
+    let ca = cdx.mul(&ady).sub(&adx.mul(&cdy));
+    let ab = adx.mul(&bdy).sub(&bdx.mul(&ady));
+    adz.mul(&bc)
+        .add(&bdz.mul(&ca))
+        .add(&cdz.mul(&ab))
+        .signed_estimate()
+}
+
+/// 点 d が a, b, c を通る円の内側にあるかを判定する
+///
+/// a, b, c が反時計回りに並ぶとき、d が円の内側なら正、外側なら負、円周上なら 0 を返す。
+pub fn incircle(a: Vector2, b: Vector2, c: Vector2, d: Vector2) -> f64 {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > ICC_ERRBOUND * permanent {
+        return det;
+    }
+
+    let (adx, ady) = (Expansion::diff(a.x, d.x), Expansion::diff(a.y, d.y));
+    let (bdx, bdy) = (Expansion::diff(b.x, d.x), Expansion::diff(b.y, d.y));
+    let (cdx, cdy) = (Expansion::diff(c.x, d.x), Expansion::diff(c.y, d.y));
+    let alift = adx.mul(&adx).add(&ady.mul(&ady));
+    let blift = bdx.mul(&bdx).add(&bdy.mul(&bdy));
+    let clift = cdx.mul(&cdx).add(&cdy.mul(&cdy));
+    let bc = bdx.mul(&cdy).sub(&cdx.mul(&bdy));
+    let ca = cdx.mul(&ady).sub(&adx.mul(&cdy));
+    let ab = adx.mul(&bdy).sub(&bdx.mul(&ady));
+    alift
+        .mul(&bc)
+        .add(&blift.mul(&ca))
+        .add(&clift.mul(&ab))
+        .signed_estimate()
+}
+
+/// 点 e が a, b, c, d を通る球の内側にあるかを判定する
+///
+/// `orient3d(a, b, c, d)` が正となる並びのとき、e が球の内側なら正、外側なら負、
+/// 球面上なら 0 を返す。
+pub fn insphere(a: Point3, b: Point3, c: Point3, d: Point3, e: Point3) -> f64 {
+    let (aex, aey, aez) = (a.x - e.x, a.y - e.y, a.z - e.z);
+    let (bex, bey, bez) = (b.x - e.x, b.y - e.y, b.z - e.z);
+    let (cex, cey, cez) = (c.x - e.x, c.y - e.y, c.z - e.z);
+    let (dex, dey, dez) = (d.x - e.x, d.y - e.y, d.z - e.z);
+
+    let ab = aex * bey - bex * aey;
+    let bc = bex * cey - cex * bey;
+    let cd = cex * dey - dex * cey;
+    let da = dex * aey - aex * dey;
+    let ac = aex * cey - cex * aey;
+    let bd = bex * dey - dex * bey;
+
+    let abc = aez * bc - bez * ac + cez * ab;
+    let bcd = bez * cd - cez * bd + dez * bc;
+    let cda = cez * da + dez * ac + aez * cd;
+    let dab = dez * ab + aez * bd + bez * da;
+
+    let alift = aex * aex + aey * aey + aez * aez;
+    let blift = bex * bex + bey * bey + bez * bez;
+    let clift = cex * cex + cey * cey + cez * cez;
+    let dlift = dex * dex + dey * dey + dez * dez;
+
+    let det = (dlift * abc - clift * dab) + (blift * cda - alift * bcd);
+
+    let p2 = |x1: f64, y1: f64, x2: f64, y2: f64| (x1 * y2).abs() + (x2 * y1).abs();
+    let abp = p2(aex, aey, bex, bey);
+    let bcp = p2(bex, bey, cex, cey);
+    let cdp = p2(cex, cey, dex, dey);
+    let dap = p2(dex, dey, aex, aey);
+    let acp = p2(aex, aey, cex, cey);
+    let bdp = p2(bex, bey, dex, dey);
+    let abcp = aez.abs() * bcp + bez.abs() * acp + cez.abs() * abp;
+    let bcdp = bez.abs() * cdp + cez.abs() * bdp + dez.abs() * bcp;
+    let cdap = cez.abs() * dap + dez.abs() * acp + aez.abs() * cdp;
+    let dabp = dez.abs() * abp + aez.abs() * bdp + bez.abs() * dap;
+    let permanent = dlift * abcp + clift * dabp + blift * cdap + alift * bcdp;
+    if det.abs() > ISP_ERRBOUND * permanent {
+        return det;
+    }
+
+    let [aex, aey, aez] = diff3(a, e);
+    let [bex, bey, bez] = diff3(b, e);
+    let [cex, cey, cez] = diff3(c, e);
+    let [dex, dey, dez] = diff3(d, e);
+    let cross = |x1: &Expansion, y1: &Expansion, x2: &Expansion, y2: &Expansion| {
+        x1.mul(y2).sub(&x2.mul(y1))
+    };
+    let ab = cross(&aex, &aey, &bex, &bey);
+    let bc = cross(&bex, &bey, &cex, &cey);
+    let cd = cross(&cex, &cey, &dex, &dey);
+    let da = cross(&dex, &dey, &aex, &aey);
+    let ac = cross(&aex, &aey, &cex, &cey);
+    let bd = cross(&bex, &bey, &dex, &dey);
+
+    let abc = aez.mul(&bc).sub(&bez.mul(&ac)).add(&cez.mul(&ab));
+    let bcd = bez.mul(&cd).sub(&cez.mul(&bd)).add(&dez.mul(&bc));
+    let cda = cez.mul(&da).add(&dez.mul(&ac)).add(&aez.mul(&cd));
+    let dab = dez.mul(&ab).add(&aez.mul(&bd)).add(&bez.mul(&da));
+
+    let lift = |x: &Expansion, y: &Expansion, z: &Expansion| x.mul(x).add(&y.mul(y)).add(&z.mul(z));
+    let alift = lift(&aex, &aey, &aez);
+    let blift = lift(&bex, &bey, &bez);
+    let clift = lift(&cex, &cey, &cez);
+    let dlift = lift(&dex, &dey, &dez);
+
+    dlift
+        .mul(&abc)
+        .sub(&clift.mul(&dab))
+        .add(&blift.mul(&cda).sub(&alift.mul(&bcd)))
+        .signed_estimate()
+}
+
+fn diff3(a: Point3, b: Point3) -> [Expansion; 3] {
+    [
+        Expansion::diff(a.x, b.x),
+        Expansion::diff(a.y, b.y),
+        Expansion::diff(a.z, b.z),
+    ]
+}
+
+/// a + b を丸め誤差なしで (和, 誤差) に分解する
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+    (x, (a - av) + (b - bv))
+}
+
+/// a * b を丸め誤差なしで (積, 誤差) に分解する（融合積和演算を利用）
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// 浮動小数点数の和で実数を誤差なく表す展開
+///
+/// 成分は絶対値の小さい順に並び、互いに重ならない（nonoverlapping）。ゼロの成分は持たない。
+#[derive(Debug, Clone)]
+struct Expansion(Vec<f64>);
+
+impl Expansion {
+    fn from_f64(a: f64) -> Self {
+        Self::from_components(vec![a])
+    }
+
+    fn from_components(c: Vec<f64>) -> Self {
+        Self(c.into_iter().filter(|v| *v != 0.0).collect())
+    }
+
+    /// a - b を誤差なく表す
+    fn diff(a: f64, b: f64) -> Self {
+        let (x, y) = two_sum(a, -b);
+        Self::from_components(vec![y, x])
+    }
+
+    /// 1つの浮動小数点数を加える（Shewchuk の Grow-Expansion）
+    fn grow(&self, b: f64) -> Self {
+        let mut q = b;
+        let mut h = Vec::with_capacity(self.0.len() + 1);
+        for &e in &self.0 {
+            let (s, err) = two_sum(q, e);
+            h.push(err);
+            q = s;
+        }
+        h.push(q);
+        Self::from_components(h)
+    }
+
+    fn add(&self, other: &Expansion) -> Self {
+        let mut r = self.clone();
+        for &f in &other.0 {
+            r = r.grow(f);
+        }
+        r
+    }
+
+    fn neg(&self) -> Self {
+        Self(self.0.iter().map(|v| -v).collect())
+    }
+
+    fn sub(&self, other: &Expansion) -> Self {
+        self.add(&other.neg())
+    }
+
+    /// 浮動小数点数倍する（Shewchuk の Scale-Expansion）
+    fn scale(&self, b: f64) -> Self {
+        let mut h = Vec::with_capacity(self.0.len() * 2);
+        let mut iter = self.0.iter();
+        let Some(&e0) = iter.next() else {
+            return Self(Vec::new());
+        };
+        let (mut q, h0) = two_product(e0, b);
+        h.push(h0);
+        for &e in iter {
+            let (t_hi, t_lo) = two_product(e, b);
+            let (s, err) = two_sum(q, t_lo);
+            h.push(err);
+            let (s2, err2) = two_sum(t_hi, s);
+            h.push(err2);
+            q = s2;
+        }
+        h.push(q);
+        Self::from_components(h)
+    }
+
+    fn mul(&self, other: &Expansion) -> Self {
+        other
+            .0
+            .iter()
+            .fold(Self(Vec::new()), |acc, &f| acc.add(&self.scale(f)))
+    }
+
+    /// 値の近似値を、正確な符号を保って返す
+    fn signed_estimate(&self) -> f64 {
+        let Some(&top) = self.0.last() else {
+            return 0.0;
+        };
+        let estimate: f64 = self.0.iter().sum();
+        if estimate.signum() == top.signum() && estimate != 0.0 {
+            estimate
+        } else {
+            top
+        }
+    }
+}
+
+impl From<f64> for Expansion {
+    fn from(a: f64) -> Self {
+        Self::from_f64(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2^-53 を単位とした整数座標による厳密な orient2d
+    fn orient2d_i128(a: (i128, i128), b: (i128, i128), c: (i128, i128)) -> i128 {
+        (a.0 - c.0) * (b.1 - c.1) - (a.1 - c.1) * (b.0 - c.0)
+    }
+
+    #[test]
+    fn test_orient2d_basic() {
+        let a = Vector2::new(0.0, 0.0);
+        let b = Vector2::new(1.0, 0.0);
+        let c = Vector2::new(0.0, 1.0);
+        assert!(orient2d(a, b, c) > 0.0);
+        assert!(orient2d(a, c, b) < 0.0);
+        assert_eq!(orient2d(a, b, Vector2::new(2.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_orient2d_near_degenerate_matches_exact() {
+        // Kettner らの例：(0.5, 0.5) 付近の点と直線 y = x 上の2点
+        let u = 2f64.powi(-53);
+        let q = Vector2::new(12.0, 12.0);
+        let r = Vector2::new(24.0, 24.0);
+        let scale = 2f64.powi(53);
+        let to_i = |v: f64| (v * scale) as i128;
+        for i in 0..64 {
+            for j in 0..64 {
+                let p = Vector2::new(0.5 + i as f64 * u, 0.5 + j as f64 * u);
+                let exact = orient2d_i128(
+                    (to_i(p.x), to_i(p.y)),
+                    (to_i(q.x), to_i(q.y)),
+                    (to_i(r.x), to_i(r.y)),
+                );
+                let got = orient2d(p, q, r);
+                assert_eq!(got.signum() as i128 * (got != 0.0) as i128, exact.signum());
+            }
+        }
+    }
+
+    #[test]
+    fn test_orient3d() {
+        let a = Point3::new(0.0, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+        assert!(orient3d(a, b, c, Point3::new(0.0, 0.0, -1.0)) > 0.0);
+        assert!(orient3d(a, b, c, Point3::new(0.0, 0.0, 1.0)) < 0.0);
+
+        // 平面 z = x + y 上の点（座標は 2進で正確に表せるが積で丸めが生じる）
+        let on_plane = |x: f64, y: f64| Point3::new(x, y, x + y);
+        let u = 2f64.powi(-30);
+        let a = on_plane(0.1 + 3.0 * u, 0.7);
+        let b = on_plane(1.3, 0.2 + u);
+        let c = on_plane(0.4, 1.9);
+        let d = on_plane(0.3 + 5.0 * u, 0.6 + 7.0 * u);
+        // x + y の丸めによる誤差があるので、厳密に同一平面とは限らないが符号は一貫する
+        let s = orient3d(a, b, c, d);
+        assert_eq!(s.signum(), -orient3d(b, a, c, d).signum());
+
+        // 厳密に同一平面上にある点（小さな整数座標）
+        let a = Point3::new(1.0, 2.0, 3.0);
+        let b = Point3::new(4.0, 5.0, 9.0);
+        let c = Point3::new(-1.0, 7.0, 6.0);
+        let d = Point3::new(2.0 * 4.0 - 1.0, 2.0 * 5.0 - 2.0, 2.0 * 9.0 - 3.0);
+        assert_eq!(orient3d(a, b, c, d), 0.0);
+    }
+
+    #[test]
+    fn test_orient3d_tiny_offsets() {
+        // 大きな座標の平面から 1ulp だけずれた点でも符号を誤らない
+        let a = Point3::new(1e6, 0.0, 0.0);
+        let b = Point3::new(0.0, 1e6, 0.0);
+        let c = Point3::new(-1e6, -1e6, 0.0);
+        let above = Point3::new(0.1, 0.3, f64::MIN_POSITIVE);
+        let below = Point3::new(0.1, 0.3, -f64::MIN_POSITIVE);
+        assert!(orient3d(a, b, c, above) < 0.0);
+        assert!(orient3d(a, b, c, below) > 0.0);
+        assert_eq!(orient3d(a, b, c, Point3::new(0.1, 0.3, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_incircle() {
+        let a = Vector2::new(1.0, 0.0);
+        let b = Vector2::new(0.0, 1.0);
+        let c = Vector2::new(-1.0, 0.0);
+        assert!(incircle(a, b, c, Vector2::new(0.0, 0.0)) > 0.0);
+        assert!(incircle(a, b, c, Vector2::new(2.0, 0.0)) < 0.0);
+        // 円周上の点
+        assert_eq!(incircle(a, b, c, Vector2::new(0.0, -1.0)), 0.0);
+        // 円周から極めて僅かに内側
+        let d = Vector2::new(0.0, -1.0 + f64::EPSILON);
+        assert!(incircle(a, b, c, d) > 0.0);
+    }
+
+    #[test]
+    fn test_insphere() {
+        let a = Point3::new(1.0, 0.0, 0.0);
+        let b = Point3::new(0.0, 1.0, 0.0);
+        let c = Point3::new(-1.0, 0.0, 0.0);
+        let d = Point3::new(0.0, 0.0, 1.0);
+        let (a, b) = if orient3d(a, b, c, d) > 0.0 {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        assert!(insphere(a, b, c, d, Point3::origin()) > 0.0);
+        assert!(insphere(a, b, c, d, Point3::new(0.0, 0.0, 3.0)) < 0.0);
+        // 球面上の点
+        assert_eq!(insphere(a, b, c, d, Point3::new(0.0, 0.0, -1.0)), 0.0);
+        assert!(insphere(a, b, c, d, Point3::new(0.0, 0.0, -1.0 + f64::EPSILON)) > 0.0);
+    }
+
+    #[test]
+    fn test_expansion_is_exact() {
+        // 1 + 2^-60 - 1 は通常の浮動小数点では 0 になるが、展開では正確に残る
+        let tiny = 2f64.powi(-60);
+        let e = Expansion::from(1.0).grow(tiny).sub(&Expansion::from(1.0));
+        assert_eq!(e.signed_estimate(), tiny);
+        let p = Expansion::diff(1.0, tiny).mul(&Expansion::diff(1.0, -tiny));
+        // (1 - t)(1 + t) = 1 - t^2
+        let r = p.sub(&Expansion::from(1.0));
+        assert_eq!(r.signed_estimate(), -tiny * tiny);
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::topo::ShapeId;
+use crate::{Edge, Face, Shape, ShapeKind, Vertex};
+
+/// 形状に含まれる特定の種類の部分形状を順にたどるイテレータ（OCCT の `TopExp_Explorer` 相当）
+///
+/// 深さ優先で格納順にたどり、見つかった部分形状の中へは降りない。
+/// 向きは親から順に合成したものになる。既定では共有された部分形状を現れた回数だけ返す。
+#[derive(Debug, Clone)]
+pub struct ShapeExplorer {
+    stack: Vec<Shape>,
+    kind: ShapeKind,
+    seen: Option<HashSet<ShapeId>>,
+}
+
+impl ShapeExplorer {
+    /// 同じ形状（`is_same`）を最初の1回だけ返すようにする（OCCT の `TopExp::MapShapes` 相当）
+    pub fn unique(mut self) -> Self {
+        self.seen = Some(HashSet::new());
+        self
+    }
+}
+
+impl Iterator for ShapeExplorer {
+    type Item = Shape;
+
+    fn next(&mut self) -> Option<Shape> {
+        while let Some(shape) = self.stack.pop() {
+            if shape.kind() == self.kind {
+                if let Some(seen) = &mut self.seen {
+                    if !seen.insert(shape.id()) {
+                        continue;
+                    }
+                }
+                return Some(shape);
+            }
+            // 種類が探すものより下位なら、その中には見つからない
+            if shape.kind() < self.kind {
+                self.stack.extend(shape.children().into_iter().rev());
+            }
+        }
+        None
+    }
+}
+
+/// 部分形状から、それを含む上位の形状への対応（OCCT の `TopExp::MapShapesAndAncestors` 相当）
+///
+/// 部分形状は最初に見つかった順に並び、各部分形状の上位の形状は重複なく見つかった順に並ぶ。
+#[derive(Debug, Clone)]
+pub struct AncestorMap {
+    entries: Vec<(Shape, Vec<Shape>)>,
+    index: HashMap<ShapeId, usize>,
+}
+
+impl AncestorMap {
+    /// 部分形状 `shape` を含む上位の形状を返す（含まれていなければ空）
+    pub fn get(&self, shape: &Shape) -> &[Shape] {
+        match self.index.get(&shape.id()) {
+            Some(&i) => &self.entries[i].1,
+            None => &[],
+        }
+    }
+
+    /// 部分形状の数を返す
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 部分形状がない場合に `true` を返す
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 部分形状と、それを含む上位の形状の組を順に返す
+    pub fn iter(&self) -> impl Iterator<Item = (&Shape, &[Shape])> {
+        self.entries.iter().map(|(s, a)| (s, a.as_slice()))
+    }
+}
+
+impl Shape {
+    /// 種類 `kind` の部分形状をたどるイテレータを返す
+    ///
+    /// この形状自身が `kind` の場合はそれだけを返す。
+    pub fn explore(&self, kind: ShapeKind) -> ShapeExplorer {
+        ShapeExplorer {
+            stack: vec![self.clone()],
+            kind,
+            seen: None,
+        }
+    }
+
+    /// 種類 `kind` の部分形状ごとに、それを含む種類 `ancestor_kind` の部分形状を対応付ける
+    ///
+    /// 例えば `(ShapeKind::Edge, ShapeKind::Face)` でエッジから隣接するフェイスへの対応が得られる。
+    pub fn ancestor_map(&self, kind: ShapeKind, ancestor_kind: ShapeKind) -> AncestorMap {
+        let mut map = AncestorMap {
+            entries: Vec::new(),
+            index: HashMap::new(),
+        };
+        for ancestor in self.explore(ancestor_kind).unique() {
+            for sub in ancestor.explore(kind).unique() {
+                let i = *map.index.entry(sub.id()).or_insert_with(|| {
+                    map.entries.push((sub.clone(), Vec::new()));
+                    map.entries.len() - 1
+                });
+                map.entries[i].1.push(ancestor.clone());
+            }
+        }
+        map
+    }
+
+    /// 重複のないフェイスを見つかった順に返す
+    pub fn faces(&self) -> Vec<Face> {
+        self.explore(ShapeKind::Face)
+            .unique()
+            .filter_map(|s| Face::try_from(s).ok())
+            .collect()
+    }
+
+    /// 重複のないエッジを見つかった順に返す
+    pub fn edges(&self) -> Vec<Edge> {
+        self.explore(ShapeKind::Edge)
+            .unique()
+            .filter_map(|s| Edge::try_from(s).ok())
+            .collect()
+    }
+
+    /// 重複のない頂点を見つかった順に返す
+    pub fn vertices(&self) -> Vec<Vertex> {
+        self.explore(ShapeKind::Vertex)
+            .unique()
+            .filter_map(|s| Vertex::try_from(s).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Compound, Line, Orientation, Plane, Point3, Shell, Solid, Wire};
+
+    /// 頂点・エッジを共有する四面体の立体
+    fn tetrahedron() -> Solid {
+        let v: Vec<Vertex> = [
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vertex::new(Point3::new(x, y, z)))
+        .collect();
+        let e: Vec<Edge> = [(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]
+            .iter()
+            .map(|&(a, b): &(usize, usize)| {
+                let (p, q) = (v[a].point(), v[b].point());
+                let line = Line::from_points(p, q).unwrap();
+                Edge::new(line, (0.0, p.distance(q)), v[a].clone(), v[b].clone()).unwrap()
+            })
+            .collect();
+        let loops = [
+            [(0, true), (2, true), (1, true)],
+            [(0, false), (4, false), (3, true)],
+            [(1, false), (5, false), (4, true)],
+            [(2, false), (3, false), (5, true)],
+        ];
+        let faces = loops
+            .iter()
+            .map(|l| {
+                let edges: Vec<Edge> = l
+                    .iter()
+                    .map(|&(i, rev)| if rev { e[i].reversed() } else { e[i].clone() })
+                    .collect();
+                let p: Vec<Point3> = edges.iter().map(|e| e.start_point()).collect();
+                let plane = Plane::from_points(p[0], p[1], p[2]).unwrap();
+                Face::new(plane, vec![Wire::new(edges).unwrap()]).unwrap()
+            })
+            .collect();
+        Solid::new(vec![Shell::new(faces).unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn test_explore_counts() {
+        let solid = Shape::from(tetrahedron());
+        assert_eq!(solid.explore(ShapeKind::Face).count(), 4);
+        // 共有されたエッジ・頂点は現れた回数だけ返る
+        assert_eq!(solid.explore(ShapeKind::Edge).count(), 12);
+        assert_eq!(solid.explore(ShapeKind::Edge).unique().count(), 6);
+        assert_eq!(solid.explore(ShapeKind::Vertex).count(), 24);
+        assert_eq!(solid.vertices().len(), 4);
+        assert_eq!(solid.edges().len(), 6);
+        assert_eq!(solid.faces().len(), 4);
+        assert_eq!(solid.explore(ShapeKind::Solid).count(), 1);
+        assert_eq!(solid.explore(ShapeKind::Compound).count(), 0);
+
+        let face = Shape::from(solid.faces()[1].clone());
+        assert_eq!(face.explore(ShapeKind::Edge).count(), 3);
+        assert_eq!(face.explore(ShapeKind::Shell).count(), 0);
+    }
+
+    #[test]
+    fn test_explore_composes_orientation() {
+        let solid = tetrahedron();
+        let forward: Vec<Orientation> = Shape::from(solid.clone())
+            .explore(ShapeKind::Edge)
+            .map(|s| s.orientation())
+            .collect();
+        let reversed: Vec<Orientation> = Shape::from(solid.reversed())
+            .explore(ShapeKind::Edge)
+            .map(|s| s.orientation())
+            .collect();
+        assert_eq!(forward.len(), reversed.len());
+        // 向きの反転はすべての部分形状に伝わる（エッジは逆順になる）
+        let n = forward
+            .iter()
+            .filter(|&&o| o == Orientation::Forward)
+            .count();
+        let m = reversed
+            .iter()
+            .filter(|&&o| o == Orientation::Reversed)
+            .count();
+        assert_eq!(n, m);
+    }
+
+    #[test]
+    fn test_ancestor_map() {
+        let solid = Shape::from(tetrahedron());
+        let map = solid.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+        assert_eq!(map.len(), 6);
+        for (edge, faces) in map.iter() {
+            assert_eq!(edge.kind(), ShapeKind::Edge);
+            assert_eq!(faces.len(), 2);
+            assert!(!faces[0].is_same(&faces[1]));
+        }
+        let map = solid.ancestor_map(ShapeKind::Vertex, ShapeKind::Edge);
+        let v = Shape::from(solid.vertices()[0].clone());
+        assert_eq!(map.get(&v).len(), 3);
+        assert!(map.get(&solid).is_empty());
+
+        // 同じ立体を2回含む複合形状でも上位の形状は重複しない
+        let compound = Shape::from(Compound::new(vec![solid.clone(), solid.reversed()]));
+        let map = compound.ancestor_map(ShapeKind::Face, ShapeKind::Solid);
+        assert_eq!(map.len(), 4);
+        assert!(map.iter().all(|(_, s)| s.len() == 1));
+        assert!(Shape::from(Compound::new(Vec::new()))
+            .ancestor_map(ShapeKind::Edge, ShapeKind::Face)
+            .is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Curve3, OcctKrsError, Point3, Result};
+
+/// 極小を探すためにパラメータ範囲を分割する区間数
+const SAMPLES: usize = 64;
+
+/// 2曲線間の距離の極小
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveExtremum {
+    /// 1つ目の曲線のパラメータ
+    pub u1: f64,
+    /// 2つ目の曲線のパラメータ
+    pub u2: f64,
+    /// 1つ目の曲線上の点
+    pub point1: Point3,
+    /// 2つ目の曲線上の点
+    pub point2: Point3,
+    /// 2点間の距離
+    pub distance: f64,
+}
+
+/// 2曲線間の距離が極小となる点の組をすべて求める（OCCT の `Extrema_ExtCC` 相当）
+///
+/// 両曲線のパラメータ範囲を格子状に分割して距離の極小を探し、ニュートン法で精密化する。
+/// 結果は距離の小さい順に並べる。平行な区間のように距離が一定の場合は代表の1組だけを返す。
+/// どちらかのパラメータ範囲が無限の場合はエラーを返す。
+pub fn extrema(
+    c1: &(impl Curve3 + ?Sized),
+    c2: &(impl Curve3 + ?Sized),
+) -> Result<Vec<CurveExtremum>> {
+    let g1 = Grid::new(c1)?;
+    let g2 = Grid::new(c2)?;
+    let p1: Vec<Point3> = (0..=SAMPLES).map(|i| c1.point_at(g1.at(i))).collect();
+    let p2: Vec<Point3> = (0..=SAMPLES).map(|j| c2.point_at(g2.at(j))).collect();
+    let d = |i: usize, j: usize| p1[i].distance_squared(p2[j]);
+
+    let mut result: Vec<CurveExtremum> = Vec::new();
+    for i in 0..=SAMPLES {
+        for j in 0..=SAMPLES {
+            let here = d(i, j);
+            let mut is_min = true;
+            for (di, dj) in NEIGHBORS {
+                let (Some(ni), Some(nj)) = (i.checked_add_signed(di), j.checked_add_signed(dj))
+                else {
+                    continue;
+                };
+                if ni > SAMPLES || nj > SAMPLES {
+                    continue;
+                }
+                // 距離が等しい谷では、先に現れる格子点だけを極小とみなす
+                let before = (di, dj) < (0, 0);
+                let there = d(ni, nj);
+                if there < here || (before && there == here) {
+                    is_min = false;
+                    break;
+                }
+            }
+            if !is_min {
+                continue;
+            }
+            let (u1, u2) = refine(c1, &g1, c2, &g2, g1.at(i), g2.at(j));
+            let (point1, point2) = (c1.point_at(u1), c2.point_at(u2));
+            let found = CurveExtremum {
+                u1,
+                u2,
+                point1,
+                point2,
+                distance: point1.distance(point2),
+            };
+            // 隣り合う格子点から同じ極小に収束したものはまとめる
+            match result
+                .iter_mut()
+                .find(|x| g1.near(x.u1, u1) && g2.near(x.u2, u2))
+            {
+                Some(x) if x.distance <= found.distance => {}
+                Some(x) => *x = found,
+                None => result.push(found),
+            }
+        }
+    }
+    result.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    Ok(result)
+}
+
+const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// パラメータ範囲の等分
+struct Grid {
+    first: f64,
+    last: f64,
+    step: f64,
+    closed: bool,
+}
+
+impl Grid {
+    fn new(curve: &(impl Curve3 + ?Sized)) -> Result<Self> {
+        let (first, last) = (curve.first_parameter(), curve.last_parameter());
+        if !(first.is_finite() && last.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(
+                "極値計算にはパラメータ範囲が有限の曲線が必要です".to_string(),
+            ));
+        }
+        Ok(Self {
+            first,
+            last,
+            step: (last - first) / SAMPLES as f64,
+            closed: curve.is_closed(),
+        })
+    }
+
+    fn at(&self, i: usize) -> f64 {
+        self.first + self.step * i as f64
+    }
+
+    fn clamp(&self, u: f64) -> f64 {
+        u.clamp(self.first, self.last)
+    }
+
+    /// 2つのパラメータが1区間以内か（閉曲線では始点と終点をつないで測る）
+    fn near(&self, a: f64, b: f64) -> bool {
+        let d = (a - b).abs();
+        d <= self.step || (self.closed && (self.last - self.first) - d <= self.step)
+    }
+}
+
+/// 距離の2乗の半分 `F = |C1(u1) - C2(u2)|² / 2` の極小をニュートン法で求める
+///
+/// ヘッセ行列が正定値でない場合はガウス・ニュートン法に、それも退化する（平行な）場合は
+/// 交互に相手の曲線へ射影する方法に切り替える。
+fn refine(
+    c1: &(impl Curve3 + ?Sized),
+    g1: &Grid,
+    c2: &(impl Curve3 + ?Sized),
+    g2: &Grid,
+    mut u1: f64,
+    mut u2: f64,
+) -> (f64, f64) {
+    for _ in 0..100 {
+        let diff = c1.point_at(u1) - c2.point_at(u2);
+        let (d1, d2) = (c1.derivative_at(u1), c2.derivative_at(u2));
+        let (gs, gt) = (diff.dot(d1), -diff.dot(d2));
+        let gauss = (d1.dot(d1), -d1.dot(d2), d2.dot(d2));
+        let full = (
+            gauss.0 + diff.dot(c1.second_derivative_at(u1)),
+            gauss.1,
+            gauss.2 - diff.dot(c2.second_derivative_at(u2)),
+        );
+        let solve = |(a, b, c): (f64, f64, f64)| {
+            let det = a * c - b * b;
+            (a > 0.0 && det > 1e-10 * a * c)
+                .then(|| ((c * gs - b * gt) / det, (a * gt - b * gs) / det))
+        };
+        let (n1, n2) = match solve(full).or_else(|| solve(gauss)) {
+            Some((s, t)) => (g1.clamp(u1 - s), g2.clamp(u2 - t)),
+            None => {
+                let n1 = if gauss.0 > 0.0 {
+                    g1.clamp(u1 - gs / gauss.0)
+                } else {
+                    u1
+                };
+                let diff = c1.point_at(n1) - c2.point_at(u2);
+                let n2 = if gauss.2 > 0.0 {
+                    g2.clamp(u2 + diff.dot(d2) / gauss.2)
+                } else {
+                    u2
+                };
+                (n1, n2)
+            }
+        };
+        let moved = (n1 - u1).abs() + (n2 - u2).abs();
+        u1 = n1;
+        u2 = n2;
+        if moved <= 1e-15 * (1.0 + u1.abs() + u2.abs()) {
+            break;
+        }
+    }
+    (u1, u2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, BSplineCurve, Circle, Dir, Segment};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_skew_segments() {
+        let a = Segment::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let b = Segment::new(Point3::new(0.5, -1.0, 2.0), Point3::new(0.5, 1.0, 2.0));
+        let r = extrema(&a, &b).unwrap();
+        assert_eq!(r.len(), 1);
+        assert!((r[0].distance - 2.0).abs() < 1e-12);
+        assert_point_eq(r[0].point1, Point3::new(0.5, 0.0, 0.0));
+        assert_point_eq(r[0].point2, Point3::new(0.5, 0.0, 2.0));
+        assert!((r[0].u1 - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_circle_and_segment() {
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        let seg = Segment::new(Point3::new(3.0, -5.0, 0.0), Point3::new(3.0, 5.0, 0.0));
+        let r = extrema(&circle, &seg).unwrap();
+        assert!((r[0].distance - 2.0).abs() < 1e-10);
+        assert_point_eq(r[0].point1, Point3::new(1.0, 0.0, 0.0));
+        assert_point_eq(r[0].point2, Point3::new(3.0, 0.0, 0.0));
+        // 結果は距離順
+        for w in r.windows(2) {
+            assert!(w[0].distance <= w[1].distance);
+        }
+    }
+
+    #[test]
+    fn test_multiple_local_minima_between_cables() {
+        let wave = BSplineCurve::clamped_uniform(
+            2,
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 0.0),
+                Point3::new(2.0, -2.0, 0.0),
+                Point3::new(3.0, 2.0, 0.0),
+                Point3::new(4.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let straight = Segment::new(Point3::new(0.0, 3.0, 1.0), Point3::new(4.0, 3.0, 1.0));
+        let r = extrema(&wave, &straight).unwrap();
+        // 2つの山の頂上がそれぞれ極小になる
+        let interior: Vec<_> = r.iter().filter(|x| x.u1 > 0.0 && x.u1 < 1.0).collect();
+        assert_eq!(interior.len(), 2);
+        for x in &interior {
+            // 極小では2点を結ぶ線分が両曲線に直交する
+            let v = x.point2 - x.point1;
+            assert!(v.dot(wave.derivative_at(x.u1)).abs() < 1e-9);
+            assert!(v.dot(straight.vector()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_intersecting_and_parallel() {
+        let a = Segment::new(Point3::ORIGIN, Point3::new(2.0, 2.0, 0.0));
+        let b = Segment::new(Point3::new(0.0, 2.0, 0.0), Point3::new(2.0, 0.0, 0.0));
+        let r = extrema(&a, &b).unwrap();
+        assert!(r[0].distance < 1e-12);
+        assert_point_eq(r[0].point1, Point3::new(1.0, 1.0, 0.0));
+
+        // 平行な線分は距離が一定なので代表の1組だけを返す
+        let c = Segment::new(Point3::new(0.0, 1.0, 0.0), Point3::new(1.0, 1.0, 0.0));
+        let d = Segment::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let r = extrema(&c, &d).unwrap();
+        assert_eq!(r.len(), 1);
+        assert!((r[0].distance - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_infinite_curve_is_rejected() {
+        let line = crate::Line::new(Point3::ORIGIN, Dir::X);
+        let seg = Segment::new(Point3::ORIGIN, Point3::new(0.0, 1.0, 0.0));
+        assert!(extrema(&line, &seg).is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+
+use crate::check::edge_parameters;
+use crate::precision;
+use crate::topo::ShapeId;
+use crate::{
+    Axis3, BSplineCurve, BSplineCurve2, BSplineSurface, CylindricalSurface, Dir, Edge,
+    ExtrudedSurface, Face, GeomCurve, GeomSurface, Line, OcctKrsError, Orientation, Result, Shape,
+    Shell, Solid, Transform, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 形状を方向 `direction` に長さ `length` だけ押し出す（OCCT の `BRepPrimAPI_MakePrism` 相当）
+///
+/// エッジからはフェイス、ワイヤからはシェル、フェイスからは両端に蓋をした立体を作る。
+/// 側面の曲面は、直線のエッジでは平面、押し出す向きに軸が平行な円のエッジでは円柱面、
+/// B-スプライン曲線のエッジでは B-スプライン曲面、それ以外の曲線のエッジでは押し出し面にする。
+/// 立体の蓋は元のフェイスと、それを平行移動したフェイスで、フェイスの法線はすべて外側を向く。
+/// `length` が負なら `direction` と逆向きに押し出す。
+///
+/// 長さが 0 または有限でない場合、エッジ・ワイヤ・フェイス以外の形状の場合、
+/// 押し出す向きがフェイスや直線のエッジと平行な場合はエラーを返す。
+pub fn extrude(profile: &Shape, direction: Dir, length: f64) -> Result<Shape> {
+    if !(length != 0.0 && length.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "押し出す長さが不正です: {}",
+            length
+        )));
+    }
+    let direction = if length < 0.0 {
+        direction.reversed()
+    } else {
+        direction
+    };
+    let mut prism = Prism::new(direction, length.abs());
+    match profile {
+        Shape::Edge(edge) => prism
+            .side_face(edge, false)?
+            .map(Shape::from)
+            .ok_or_else(|| {
+                OcctKrsError::DegenerateGeometry("退化エッジは押し出せません".to_string())
+            }),
+        Shape::Wire(wire) => Ok(Shell::new(prism.side_faces(wire, false)?)?.into()),
+        Shape::Face(face) => prism.solid(face).map(Shape::from),
+        other => Err(OcctKrsError::InvalidInput(format!(
+            "{:?} は押し出せません（エッジ・ワイヤ・フェイスのみ）",
+            other.kind()
+        ))),
+    }
+}
+
+/// 押し出しの途中で作った頂点・エッジを、元の形状ごとに共有するための表
+struct Prism {
+    direction: Dir,
+    length: f64,
+    shift: Transform,
+    /// 元の頂点から、押し出した先の頂点と、そこへ伸びる側面の稜線への対応
+    vertices: HashMap<ShapeId, (Vertex, Edge)>,
+    /// 元のエッジ（`Forward`）から、押し出した先のエッジへの対応
+    edges: HashMap<ShapeId, Edge>,
+}
+
+impl Prism {
+    fn new(direction: Dir, length: f64) -> Self {
+        Self {
+            direction,
+            length,
+            shift: Transform::from_translation(direction.to_vector() * length),
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    /// 頂点を押し出した先の頂点と、元の頂点からそこへ伸びる稜線
+    fn lateral(&mut self, vertex: &Vertex) -> Result<(Vertex, Edge)> {
+        if let Some(found) = self.vertices.get(&vertex.id()) {
+            return Ok(found.clone());
+        }
+        let p = vertex.point();
+        let top = Vertex::new(self.shift.transform_point(p)).with_tolerance(vertex.tolerance())?;
+        let line = Line::new(p, self.direction);
+        let edge = Edge::new(
+            line,
+            (0.0, self.length),
+            vertex.oriented(Orientation::Forward),
+            top.clone(),
+        )?;
+        self.vertices
+            .insert(vertex.id(), (top.clone(), edge.clone()));
+        Ok((top, edge))
+    }
+
+    /// `Forward` のエッジを押し出した先のエッジ（曲線とパラメータ区間は元のまま平行移動する）
+    fn top_edge(&mut self, edge: &Edge) -> Result<Edge> {
+        if let Some(found) = self.edges.get(&edge.id()) {
+            return Ok(found.clone());
+        }
+        let (start, _) = self.lateral(&edge.start_vertex())?;
+        let (end, _) = self.lateral(&edge.end_vertex())?;
+        let top = match edge.located_geometry() {
+            Some(curve) => Edge::new(curve.transformed(&self.shift), edge.range(), start, end)?,
+            None => Edge::degenerate(start, edge.range())?,
+        }
+        .with_tolerance(edge.tolerance())?;
+        self.edges.insert(edge.id(), top.clone());
+        Ok(top)
+    }
+
+    /// ワイヤの各エッジを押し出した側面（退化エッジは除く）
+    fn side_faces(&mut self, wire: &Wire, flip: bool) -> Result<Vec<Face>> {
+        let mut faces = Vec::with_capacity(wire.len());
+        for edge in wire.edges() {
+            faces.extend(self.side_face(&edge, flip)?);
+        }
+        Ok(faces)
+    }
+
+    /// エッジを押し出した側面（退化エッジでは `None`）
+    ///
+    /// 曲線の接線と押し出す向きの外積の側を表にし、エッジが反転していれば裏返す。
+    /// `flip` が `true` なら、さらに裏返す。
+    fn side_face(&mut self, edge: &Edge, flip: bool) -> Result<Option<Face>> {
+        let Some(curve) = edge.located_geometry().map(|c| c.into_owned()) else {
+            return Ok(None);
+        };
+        let e = edge.oriented(Orientation::Forward);
+        let (_, start) = self.lateral(&e.start_vertex())?;
+        let (_, end) = self.lateral(&e.end_vertex())?;
+        let top = self.top_edge(&e)?;
+        let wire = Wire::new(vec![
+            e.clone(),
+            end.clone(),
+            top.reversed(),
+            start.reversed(),
+        ])?;
+        let tolerance = precision::confusion();
+        let (face, reversed) = if let GeomCurve::Line(line) = &curve {
+            if line.dir.dot(self.direction).abs() >= 1.0 - precision::angular() {
+                return Err(OcctKrsError::DegenerateGeometry(
+                    "押し出す向きと平行な直線のエッジがあります".to_string(),
+                ));
+            }
+            (Face::from_planar_wires(wire, Vec::new(), tolerance)?, false)
+        } else {
+            let (surface, bottom, dv) = self.side_surface(&e, &curve)?;
+            let (t0, t1) = e.range();
+            let rise = Vector2::new(0.0, dv * self.length);
+            let lateral = |t: f64| {
+                let p = bottom.point_at(t);
+                uv_segment(p, p + rise, (0.0, self.length))
+            };
+            let pcurves = vec![vec![
+                Some(bottom.clone()),
+                Some(lateral(t1)?),
+                Some(translated(&bottom, rise)?),
+                Some(lateral(t0)?),
+            ]];
+            // v が押し出す向きと逆に増える曲面では、パラメータ空間でワイヤが時計回りになる
+            let wire = if dv < 0.0 { wire.reversed() } else { wire };
+            (Face::with_pcurves(surface, vec![wire], pcurves)?, dv < 0.0)
+        };
+        let reversed = reversed ^ (edge.orientation() == Orientation::Reversed) ^ flip;
+        Ok(Some(if reversed { face.reversed() } else { face }))
+    }
+
+    /// `Forward` のエッジを押し出した曲面と、その曲面でのエッジの pcurve、
+    /// 押し出す向きに進むときの v の増え方（`1` か `-1`）
+    ///
+    /// どの曲面も `v` は押し出した長さ（の符号を変えたもの）で、元のエッジは `v = 0` にある。
+    fn side_surface(
+        &self,
+        edge: &Edge,
+        curve: &GeomCurve,
+    ) -> Result<(GeomSurface, BSplineCurve2, f64)> {
+        let (t0, t1) = edge.range();
+        let exact = || uv_segment(Vector2::new(t0, 0.0), Vector2::new(t1, 0.0), (t0, t1));
+        match curve {
+            GeomCurve::Circle(c) => {
+                let axis = c.position().direction().dot(self.direction);
+                if axis.abs() >= 1.0 - precision::angular() {
+                    let cylinder = CylindricalSurface::new(Axis3::from(c.position()), c.radius())?;
+                    return Ok((cylinder.into(), exact()?, axis.signum()));
+                }
+            }
+            GeomCurve::BSpline(c) => return Ok((self.extruded(c)?.into(), exact()?, 1.0)),
+            _ => {}
+        }
+        let surface = ExtrudedSurface::new(curve.clone(), self.direction);
+        Ok((surface.into(), exact()?, 1.0))
+    }
+
+    /// 曲線を押し出した B-スプライン曲面（`P(u, v) = C(u) + v · D`、v は 0〜長さ）
+    fn extruded(&self, curve: &BSplineCurve) -> Result<BSplineSurface> {
+        let d = self.direction.to_vector() * self.length;
+        let rows = curve
+            .control_points()
+            .iter()
+            .map(|&p| vec![p, p + d])
+            .collect();
+        let surface = BSplineSurface::from_flat_knots(
+            curve.degree(),
+            1,
+            rows,
+            curve.flat_knots().to_vec(),
+            vec![0.0, 0.0, self.length, self.length],
+        )?;
+        match curve.weights() {
+            Some(w) => surface.with_weights(w.iter().map(|&w| vec![w, w]).collect()),
+            None => Ok(surface),
+        }
+    }
+
+    /// フェイスを押し出した立体
+    fn solid(&mut self, face: &Face) -> Result<Solid> {
+        // 外側のワイヤの面積ベクトルで、フェイスの表が押し出す向きを向いているか調べる
+        let outer = face.outer_wire().ok_or_else(|| {
+            OcctKrsError::InvalidInput("境界を持たないフェイスは押し出せません".to_string())
+        })?;
+        let area = area_vector(&wire_points(&outer));
+        let height = area.dot(self.direction.to_vector());
+        if height.abs() <= precision::confusion() * area.length() {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "押し出す向きがフェイスと平行です".to_string(),
+            ));
+        }
+        let flip = height < 0.0;
+        let mut faces = Vec::new();
+        let mut top_wires = Vec::new();
+        let mut top_pcurves = Vec::new();
+        let cap = face.oriented(Orientation::Forward);
+        for wire in face.wires() {
+            faces.extend(self.side_faces(&wire, flip)?);
+        }
+        // 蓋はフェイスの向きによらず、格納された順とエッジの向きで作る
+        for wire in cap.wires() {
+            let mut edges = Vec::with_capacity(wire.len());
+            let mut pcurves = Vec::with_capacity(wire.len());
+            for edge in wire.edges() {
+                let top = self.top_edge(&edge.oriented(Orientation::Forward))?;
+                pcurves.push(cap.pcurve(&edge).cloned());
+                edges.push(top.oriented(edge.orientation()));
+            }
+            top_wires.push(Wire::new(edges)?);
+            top_pcurves.push(pcurves);
+        }
+        let surface = cap.located_geometry().transformed(&self.shift);
+        let top = Face::with_pcurves(surface, top_wires, top_pcurves)?
+            .with_tolerance(face.tolerance())?
+            .oriented(face.orientation());
+        // 元のフェイスの表が押し出す向きを向いていれば、元のフェイスを裏返して底にする
+        let (bottom, top) = if flip {
+            (face.clone(), top.reversed())
+        } else {
+            (face.reversed(), top)
+        };
+        faces.insert(0, bottom);
+        faces.push(top);
+        Solid::new(vec![Shell::new(faces)?])
+    }
+}
+
+/// ワイヤをたどる折れ線の頂点（各エッジの分割点で、エッジの終点は次のエッジの始点として数える）
+pub(crate) fn wire_points(wire: &Wire) -> Vec<Vector3> {
+    wire.edges()
+        .iter()
+        .flat_map(|e| {
+            let params = edge_parameters(e);
+            let n = params.len() - 1;
+            params[..n]
+                .iter()
+                .map(|&t| e.point_at(t).to_vector())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// 閉じた折れ線の面積ベクトル（向きは折れ線を反時計回りに見る側、大きさは面積の2倍）
+pub(crate) fn area_vector(points: &[Vector3]) -> Vector3 {
+    (0..points.len())
+        .map(|i| points[i].cross(points[(i + 1) % points.len()]))
+        .fold(Vector3::ZERO, |a, b| a + b)
+}
+
+/// パラメータ区間 `range` で `a` から `b` へ進むパラメータ空間上の線分
+pub(crate) fn uv_segment(a: Vector2, b: Vector2, range: (f64, f64)) -> Result<BSplineCurve2> {
+    BSplineCurve2::from_flat_knots(1, vec![a, b], vec![range.0, range.0, range.1, range.1])
+}
+
+/// pcurve を `offset` だけ平行移動したもの
+pub(crate) fn translated(curve: &BSplineCurve2, offset: Vector2) -> Result<BSplineCurve2> {
+    let points = curve.control_points().iter().map(|&p| p + offset).collect();
+    let moved =
+        BSplineCurve2::from_flat_knots(curve.degree(), points, curve.flat_knots().to_vec())?;
+    match curve.weights() {
+        Some(w) => moved.with_weights(w.to_vec()),
+        None => Ok(moved),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Circle, Ellipse, Point3, ShapeKind};
+    use std::f64::consts::PI;
+
+    /// xy 平面上の点を順に結んだワイヤ（`closed` なら最後の点と最初の点も結ぶ）
+    fn polyline(points: &[(f64, f64)], closed: bool) -> Wire {
+        let p: Vec<Point3> = points
+            .iter()
+            .map(|&(x, y)| Point3::new(x, y, 0.0))
+            .collect();
+        let n = if closed { p.len() } else { p.len() - 1 };
+        let edges = (0..n)
+            .map(|i| Edge::from_points(p[i], p[(i + 1) % p.len()]).unwrap())
+            .collect();
+        Wire::from_edges(edges, 1e-9).unwrap()
+    }
+
+    fn square_with_hole() -> Face {
+        Face::from_planar_wires(
+            polyline(&[(0.0, 0.0), (4.0, 0.0), (4.0, 3.0), (0.0, 3.0)], true),
+            vec![polyline(
+                &[(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)],
+                true,
+            )],
+            1e-9,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extrude_face_with_hole() {
+        let face = Shape::from(square_with_hole());
+        for (profile, length) in [
+            (face.clone(), 2.0),
+            (face.clone(), -2.0),
+            (face.reversed(), 2.0),
+        ] {
+            let solid = extrude(&profile, Dir::Z, length).unwrap();
+            let volume = checked_volume(&solid);
+            assert!((volume / 22.0 - 1.0).abs() < 1e-6, "{}", volume);
+            assert_eq!(solid.explore(ShapeKind::Face).unique().count(), 10);
+            assert_eq!(solid.explore(ShapeKind::Edge).unique().count(), 24);
+            assert_eq!(solid.explore(ShapeKind::Vertex).unique().count(), 16);
+        }
+        // 底の蓋は元のフェイスを共有する
+        let solid = extrude(&face, Dir::Z, 2.0).unwrap();
+        assert!(solid.explore(ShapeKind::Face).any(|f| f.is_same(&face)));
+    }
+
+    #[test]
+    fn test_extrude_disk_makes_cylinder() {
+        let circle = Circle::new(Axis2::world(), 2.0).unwrap();
+        let wire = Wire::new(vec![Edge::from_curve(circle).unwrap()]).unwrap();
+        let disk = Shape::from(Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap());
+        for length in [3.0, -3.0] {
+            let solid = extrude(&disk, Dir::Z, length).unwrap();
+            let volume = checked_volume(&solid);
+            assert!((volume / (PI * 12.0) - 1.0).abs() < 1e-6, "{}", volume);
+            assert!(solid
+                .faces()
+                .iter()
+                .any(|f| matches!(f.surface(), GeomSurface::Cylinder(_))));
+            assert_eq!(solid.explore(ShapeKind::Edge).unique().count(), 3);
+        }
+        // 斜めに押し出すと B-スプライン曲面の側面になる
+        let direction = Dir::from_vector(Vector3::new(1.0, 0.0, 1.0)).unwrap();
+        let solid = extrude(&disk, direction, 2.0).unwrap();
+        let volume = checked_volume(&solid);
+        assert!(
+            (volume / (PI * 8.0 / 2f64.sqrt()) - 1.0).abs() < 1e-6,
+            "{}",
+            volume
+        );
+    }
+
+    #[test]
+    fn test_extrude_ellipse() {
+        let ellipse = Ellipse::new(Axis2::world(), 3.0, 1.0).unwrap();
+        let wire = Wire::new(vec![Edge::from_curve(ellipse).unwrap()]).unwrap();
+        let face = Shape::from(Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap());
+        let solid = extrude(&face, Dir::Z, 2.0).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / (PI * 6.0) - 1.0).abs() < 1e-6, "{}", volume);
+        assert!(solid
+            .faces()
+            .iter()
+            .any(|f| matches!(f.surface(), GeomSurface::Extrusion(_))));
+    }
+
+    #[test]
+    fn test_extrude_wire_and_edge() {
+        let wire = polyline(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)], false);
+        let shell = extrude(&wire.clone().into(), Dir::Z, 1.0).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+        assert!(shell.check().is_valid());
+        let bounds = shell.free_bounds().unwrap();
+        assert_eq!(bounds.free_edge_count(), 6);
+        // 隣り合う側面は稜線を共有する
+        assert_eq!(shell.explore(ShapeKind::Edge).unique().count(), 7);
+
+        let face = extrude(&wire.edges()[0].clone().into(), Dir::Z, 1.0).unwrap();
+        assert_eq!(face.kind(), ShapeKind::Face);
+        assert!(face.check().is_valid());
+    }
+
+    #[test]
+    fn test_extrude_errors() {
+        let face = Shape::from(square_with_hole());
+        assert!(extrude(&face, Dir::Z, 0.0).is_err());
+        assert!(extrude(&face, Dir::Z, f64::INFINITY).is_err());
+        assert!(extrude(&face, Dir::X, 1.0).is_err());
+        let edge = Edge::from_points(Point3::ORIGIN, Point3::new(0.0, 0.0, 1.0)).unwrap();
+        assert!(extrude(&edge.clone().into(), Dir::Z, 1.0).is_err());
+        assert!(extrude(&edge.start_vertex().into(), Dir::Z, 1.0).is_err());
+    }
+}
@@ -0,0 +1,1028 @@
+use std::f64::consts::TAU;
+
+use crate::boolean;
+use crate::classify::FaceClassifier;
+use crate::primitives::{make_sphere, polyhedron};
+use crate::{
+    extrude, loft, precision, revolve, Axis1, Axis2, Circle, Curve3, Dir, Edge, Face, GeomCurve,
+    GeomSurface, Line, OcctKrsError, Orientation, Point3, PointClassification, Result, Shape,
+    ShapeKind, Shell, Solid, Vector3, Vertex, Wire,
+};
+
+/// 半径の変わるフィレットで、稜線の内側に置く断面の区間数
+const LAW_SECTIONS: usize = 16;
+
+/// 立体のエッジに一定半径のフィレット（丸め）をかける（OCCT の `BRepFilletAPI_MakeFillet` 相当）
+///
+/// 両側のフェイスに接しながら稜線に沿って転がる球の包絡面で稜線を置き換える。凸の稜線では材料を削り、
+/// 凹の稜線では材料を足す。対象は平面どうしの直線の稜線と、平面・円柱面・円錐面が同じ軸の
+/// まわりに交わる円の稜線。
+///
+/// 3本の凸の直線の稜線がフィレットされて集まる頂点には、3つのフェイスに接する球面の角を作る。
+///
+/// 半径が正の有限値でない場合、エッジがない場合や立体の2つのフェイスに挟まれたエッジでない場合、
+/// 対応していない稜線や滑らかにつながる稜線の場合、選んだ稜線が2本以上集まる頂点が上の角でない
+/// 場合（3本目の稜線を丸めない頂点など）や、結果が正しい立体にならない場合はエラーを返す。
+pub fn fillet(shape: &Shape, edges: &[Edge], radius: f64) -> Result<Shape> {
+    if !(radius > 0.0 && radius.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "フィレットの半径が不正です: {}",
+            radius
+        )));
+    }
+    let frames = edge_frames(shape, edges)?;
+    let corners = convex_corners(shape, edges, &frames, radius)?;
+    let (mut convex, mut concave) = (Vec::new(), Vec::new());
+    for frame in &frames {
+        let (u1, u2) = (frame.inwards[0], frame.inwards[1]);
+        let distance = radius / (0.5 * u1.angle_between(u2)).tan();
+        let side = if frame.convex { -radius } else { radius };
+        let center = frame.point + u1 * distance + frame.normals[0] * side;
+        let points = [
+            frame.point,
+            frame.point + u1 * distance,
+            frame.point + u2 * distance,
+        ];
+        let ends = frame.extensions(distance + radius, &corners);
+        let tool = frame.sweep_profile(&points, Some(center), ends)?;
+        if frame.convex {
+            convex.push(tool);
+        } else {
+            concave.push(tool);
+        }
+    }
+    for corner in &corners {
+        convex.push(corner.tool(radius)?);
+    }
+    apply_tools(shape, convex, concave)
+}
+
+/// フィレットの半径の稜線に沿った変化（OCCT の `BRepFilletAPI_MakeFillet` に与える半径の法則相当）
+///
+/// パラメータはエッジの始点を 0、終点を 1 とする稜線に沿った長さの比。
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadiusLaw {
+    /// 始点の半径から終点の半径へ線形に変わる
+    Linear(f64, f64),
+    /// パラメータと半径の組を通る、単調な3次エルミート補間（範囲外は端の半径）
+    Interpolated(Vec<(f64, f64)>),
+}
+
+impl RadiusLaw {
+    /// パラメータ `t` での半径（`t` は 0〜1 に切り詰める）
+    pub fn radius_at(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear(r0, r1) => r0 + (r1 - r0) * t,
+            Self::Interpolated(pairs) => interpolate_monotone(pairs, t),
+        }
+    }
+
+    /// パラメータが 0〜1 で増加し、半径がすべて正の有限値であることを確かめる
+    fn validate(&self) -> Result<()> {
+        let pairs = match self {
+            Self::Linear(r0, r1) => vec![(0.0, *r0), (1.0, *r1)],
+            Self::Interpolated(pairs) => pairs.clone(),
+        };
+        if pairs.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "半径の法則に値がありません".to_string(),
+            ));
+        }
+        for (i, &(t, r)) in pairs.iter().enumerate() {
+            if !(r > 0.0 && r.is_finite()) {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "フィレットの半径が不正です: {}",
+                    r
+                )));
+            }
+            if !(0.0..=1.0).contains(&t) || (i > 0 && t <= pairs[i - 1].0) {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "半径の法則のパラメータは 0〜1 で増加する必要があります: {}",
+                    t
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 単調な3次エルミート補間（Fritsch–Carlson の傾き）で `t` での値を求める
+///
+/// 隣り合う値の間で増減が変わらないので、正の値の組から負の値は出てこない。
+fn interpolate_monotone(pairs: &[(f64, f64)], t: f64) -> f64 {
+    let n = pairs.len();
+    match pairs {
+        [] => return 0.0,
+        [(_, r)] => return *r,
+        _ => {}
+    }
+    if t <= pairs[0].0 {
+        return pairs[0].1;
+    }
+    if t >= pairs[n - 1].0 {
+        return pairs[n - 1].1;
+    }
+    let secant = |i: usize| (pairs[i + 1].1 - pairs[i].1) / (pairs[i + 1].0 - pairs[i].0);
+    let slope = |i: usize| {
+        if i == 0 {
+            secant(0)
+        } else if i == n - 1 {
+            secant(n - 2)
+        } else {
+            let (a, b) = (secant(i - 1), secant(i));
+            if a * b <= 0.0 {
+                0.0
+            } else {
+                // 重み付き調和平均は両側の傾きの3倍を超えない
+                let (h0, h1) = (pairs[i].0 - pairs[i - 1].0, pairs[i + 1].0 - pairs[i].0);
+                let (w0, w1) = (2.0 * h1 + h0, h1 + 2.0 * h0);
+                (w0 + w1) / (w0 / a + w1 / b)
+            }
+        }
+    };
+    let i = pairs.partition_point(|p| p.0 <= t) - 1;
+    let ((t0, r0), (t1, r1)) = (pairs[i], pairs[i + 1]);
+    let h = t1 - t0;
+    let s = (t - t0) / h;
+    let (m0, m1) = (slope(i) * h, slope(i + 1) * h);
+    let (s2, s3) = (s * s, s * s * s);
+    (2.0 * s3 - 3.0 * s2 + 1.0) * r0
+        + (s3 - 2.0 * s2 + s) * m0
+        + (-2.0 * s3 + 3.0 * s2) * r1
+        + (s3 - s2) * m1
+}
+
+/// 稜線に沿って半径が変わるフィレットをかける
+///
+/// 稜線に垂直な断面ごとに `law` の半径で両側のフェイスに接する円弧を置き、それらを通る
+/// B-スプライン曲面で稜線を置き換える。対象は平面どうしの直線の稜線だけで、
+/// 3本の凸の稜線が集まる角は丸められない。
+///
+/// 法則のパラメータが 0〜1 で増加しない場合や半径が正の有限値でない場合、円の稜線や
+/// 3本の凸の稜線が集まる角がある場合は、[`fillet`] のエラーに加えてエラーを返す。
+pub fn fillet_with_law(shape: &Shape, edges: &[Edge], law: &RadiusLaw) -> Result<Shape> {
+    law.validate()?;
+    let frames = edge_frames(shape, edges)?;
+    if !convex_corners(shape, edges, &frames, law.radius_at(0.0))?.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "半径の変わるフィレットでは3本の稜線が集まる角を丸められません".to_string(),
+        ));
+    }
+    let (mut convex, mut concave) = (Vec::new(), Vec::new());
+    for (frame, edge) in frames.iter().zip(edges) {
+        let tool = law_tool(frame, edge, law)?;
+        if frame.convex {
+            convex.push(tool);
+        } else {
+            concave.push(tool);
+        }
+    }
+    apply_tools(shape, convex, concave)
+}
+
+/// 半径の変わるフィレットの道具
+///
+/// 断面の円弧を通る曲面と、両側のフェイスの平面上の面、両端の断面の平面の面で囲んだ立体。
+fn law_tool(frame: &EdgeFrame, edge: &Edge, law: &RadiusLaw) -> Result<Shape> {
+    let Some((a, b)) = frame.line_ends() else {
+        return Err(OcctKrsError::InvalidInput(
+            "半径の変わるフィレットは直線の稜線にだけ対応しています".to_string(),
+        ));
+    };
+    let length = a.distance(b);
+    let direction = (b - a).try_normalized()?;
+    let reversed = edge.start_point().distance(a) > edge.start_point().distance(b);
+    let radius = |s: f64| {
+        let t = s / length;
+        law.radius_at(if reversed { 1.0 - t } else { t })
+    };
+    let (u1, u2) = (frame.inwards[0], frame.inwards[1]);
+    let ratio = 1.0 / (0.5 * u1.angle_between(u2)).tan();
+    let side = if frame.convex { -1.0 } else { 1.0 };
+    // 凸の稜線は、両端の半径で決まる長さだけ立体の外まで延ばす
+    let extension = |s: f64| {
+        if frame.convex {
+            radius(s) * (ratio + 1.0)
+        } else {
+            0.0
+        }
+    };
+    // 延ばした部分にも同じ間隔で断面を置き、補間した曲面が端で膨らまないようにする
+    let step = length / LAW_SECTIONS as f64;
+    let (n0, n1) = (
+        (extension(0.0) / step).ceil() as usize,
+        (extension(length) / step).ceil() as usize,
+    );
+    let mut stations: Vec<f64> = (0..n0)
+        .map(|k| -extension(0.0) * (n0 - k) as f64 / n0 as f64)
+        .collect();
+    stations.extend((0..=LAW_SECTIONS).map(|k| step * k as f64));
+    stations.extend((1..=n1).map(|k| length + extension(length) * k as f64 / n1 as f64));
+    // 断面の稜線上の点、両側のフェイスに接する点と円弧の中心
+    // （接する点を中心から法線の向きに置き、どの断面の円弧も同じ角度にする）
+    let section = |s: f64| {
+        let (p, r) = (a + direction * s, radius(s));
+        let center = p + u1 * (r * ratio) + frame.normals[0] * (side * r);
+        let contact = |k: usize| center - frame.normals[k] * (side * r);
+        (p, contact(0), contact(1), center)
+    };
+    let mut sections = Vec::with_capacity(stations.len());
+    for &s in &stations {
+        let (_, t1, t2, center) = section(s);
+        sections.push(Wire::new(vec![arc_edge(
+            center,
+            &Vertex::new(t1),
+            &Vertex::new(t2),
+        )?])?);
+    }
+    let blend = loft(&sections, false, false)?
+        .faces()
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            OcctKrsError::DegenerateGeometry("断面を通る曲面がありません".to_string())
+        })?;
+    // 曲面の境界のエッジを、両端の断面の円弧と両側のフェイスに接する線に分ける
+    let boundary = Shape::from(blend.clone()).edges();
+    let (p0, t10, t20, _) = section(stations[0]);
+    let (p1, t11, t21, _) = section(stations[stations.len() - 1]);
+    let find = |x: Point3, y: Point3| {
+        boundary
+            .iter()
+            .find(|e| {
+                let (s, t) = (e.start_point(), e.end_point());
+                let tol = precision::confusion();
+                (s.distance(x) <= tol && t.distance(y) <= tol)
+                    || (s.distance(y) <= tol && t.distance(x) <= tol)
+            })
+            .cloned()
+            .ok_or_else(|| {
+                OcctKrsError::DegenerateGeometry("断面を通る曲面の境界が見つかりません".to_string())
+            })
+    };
+    let (arc0, arc1) = (find(t10, t20)?, find(t11, t21)?);
+    let (contact1, contact2) = (find(t10, t11)?, find(t20, t21)?);
+    let vertex_at = |edge: &Edge, p: Point3| {
+        if edge.start_point().distance(p) <= precision::confusion() {
+            edge.start_vertex()
+        } else {
+            edge.end_vertex()
+        }
+    };
+    let (v0, v1) = (Vertex::new(p0), Vertex::new(p1));
+    let segment = |v: &Vertex, w: Vertex| {
+        let (x, y) = (v.point(), w.point());
+        Edge::new(Line::from_points(x, y)?, (0.0, x.distance(y)), v.clone(), w)
+    };
+    let spine = segment(&v0, v1.clone())?;
+    let legs0 = [
+        segment(&v0, vertex_at(&arc0, t10))?,
+        segment(&v0, vertex_at(&arc0, t20))?,
+    ];
+    let legs1 = [
+        segment(&v1, vertex_at(&arc1, t11))?,
+        segment(&v1, vertex_at(&arc1, t21))?,
+    ];
+    let planar = |edges: Vec<Edge>| {
+        Face::from_planar_wires(chained_wire(edges)?, Vec::new(), precision::confusion())
+    };
+    let faces = vec![
+        blend,
+        planar(vec![
+            spine.clone(),
+            legs1[0].clone(),
+            contact1,
+            legs0[0].clone(),
+        ])?,
+        planar(vec![spine, legs1[1].clone(), contact2, legs0[1].clone()])?,
+        planar(vec![legs0[0].clone(), arc0, legs0[1].clone()])?,
+        planar(vec![legs1[0].clone(), arc1, legs1[1].clone()])?,
+    ];
+    let solid = Solid::new(vec![Shell::new(faces)?])?.fix_orientation()?;
+    Ok(Shape::from(solid))
+}
+
+/// 2本目以降のエッジを、前のエッジの終点から始まる向きにそろえたワイヤを作る
+fn chained_wire(edges: Vec<Edge>) -> Result<Wire> {
+    let mut chained: Vec<Edge> = Vec::with_capacity(edges.len());
+    for edge in edges {
+        let edge = match chained.last() {
+            Some(previous) if !previous.end_vertex().is_same(&edge.start_vertex()) => {
+                edge.reversed()
+            }
+            _ => edge,
+        };
+        chained.push(edge);
+    }
+    Wire::new(chained)
+}
+
+/// 3本の凸の稜線が集まる頂点の角（3つのフェイスに接する球で丸める）
+pub(crate) struct Corner {
+    /// 角の頂点
+    vertex: Point3,
+    /// 3つのフェイスから `radius` だけ内側の点（球の中心）
+    center: Point3,
+    /// 3つのフェイスの外向きの法線
+    normals: [Vector3; 3],
+}
+
+impl Corner {
+    /// 角の頂点と、球の中心を通り各稜線に垂直な3つの平面で囲んだ六面体から球を除いた道具を返す
+    fn tool(&self, radius: f64) -> Result<Shape> {
+        let n = self.normals;
+        let planes = |i: usize, far: bool| {
+            if far {
+                let m = n[(i + 1) % 3].cross(n[(i + 2) % 3]);
+                (m, m.dot(self.center.to_vector()))
+            } else {
+                (n[i], n[i].dot(self.vertex.to_vector()))
+            }
+        };
+        // 番号の各ビットが、フェイスの平面と球の中心を通る平面のどちらを使うかを表す
+        let mut points = Vec::with_capacity(8);
+        for bits in 0..8 {
+            let rows: Vec<(Vector3, f64)> = (0..3).map(|i| planes(i, bits >> i & 1 == 1)).collect();
+            points.push(solve_planes(&rows)?);
+        }
+        let centroid = points
+            .iter()
+            .fold(Vector3::ZERO, |s, p| s + p.to_vector() * 0.125);
+        let mut quads = Vec::with_capacity(6);
+        for i in 0..3 {
+            let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+            for side in [0, 1 << i] {
+                let mut quad = [side, side | 1 << j, side | 1 << j | 1 << k, side | 1 << k];
+                let [a, b, _, d] = quad.map(|q| points[q]);
+                let outward = (b - a).cross(d - a).dot(a.to_vector() - centroid);
+                if outward < 0.0 {
+                    quad.reverse();
+                }
+                quads.push(quad);
+            }
+        }
+        let block = Shape::from(polyhedron(&points, &quads)?);
+        // 極と継ぎ目を角の球面から離す
+        let axis = Dir::from_vector(n[0] + n[1] - n[2] * 2.0)?;
+        let seam = Dir::from_vector(-(n[0] + n[1] + n[2]))?;
+        let ball = make_sphere(Axis2::new(self.center, axis, seam)?, radius)?;
+        boolean::cut(&block, &Shape::from(ball))
+    }
+}
+
+/// 3つの平面 `normal · x = d` の交点を返す
+pub(crate) fn solve_planes(rows: &[(Vector3, f64)]) -> Result<Point3> {
+    let (a, b, c) = (rows[0].0, rows[1].0, rows[2].0);
+    let det = a.dot(b.cross(c));
+    if det.abs() <= precision::angular() {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "角の3つのフェイスの平面が1点で交わりません".to_string(),
+        ));
+    }
+    let v = (b.cross(c) * rows[0].1 + c.cross(a) * rows[1].1 + a.cross(b) * rows[2].1) / det;
+    Ok(Point3::new(v.x, v.y, v.z))
+}
+
+/// 選んだ3本の凸の直線の稜線だけが集まり、3つのフェイスで囲まれた頂点を角として返す
+///
+/// 選んだ稜線が2本以上集まる頂点がそれ以外の形の場合、丸め同士をつなぐ面を作れないのでエラーを返す。
+fn convex_corners(
+    shape: &Shape,
+    edges: &[Edge],
+    frames: &[EdgeFrame],
+    radius: f64,
+) -> Result<Vec<Corner>> {
+    let map = shape.ancestor_map(ShapeKind::Vertex, ShapeKind::Edge);
+    let mut corners: Vec<Corner> = Vec::new();
+    for edge in edges {
+        for vertex in [edge.start_vertex(), edge.end_vertex()] {
+            let p = vertex.point();
+            if corners
+                .iter()
+                .any(|c| c.vertex.distance(p) <= precision::confusion())
+            {
+                continue;
+            }
+            let incident = map.get(&Shape::from(vertex));
+            let chosen: Vec<&EdgeFrame> = incident
+                .iter()
+                .filter_map(|s| {
+                    edges
+                        .iter()
+                        .position(|e| Shape::from(e.clone()).is_same(s))
+                        .map(|i| &frames[i])
+                })
+                .collect();
+            if chosen.len() < 2 {
+                continue;
+            }
+            if incident.len() != 3
+                || chosen.len() != 3
+                || !chosen.iter().all(|f| f.convex && f.line_ends().is_some())
+            {
+                return Err(unsupported_corner(p));
+            }
+            let mut normals: Vec<Vector3> = Vec::with_capacity(3);
+            for n in chosen.iter().flat_map(|f| f.normals) {
+                if !normals
+                    .iter()
+                    .any(|m| m.dot(n) >= 1.0 - precision::angular())
+                {
+                    normals.push(n);
+                }
+            }
+            let [a, b, c] = normals[..] else {
+                return Err(unsupported_corner(p));
+            };
+            let rows: Vec<(Vector3, f64)> = [a, b, c]
+                .iter()
+                .map(|&n| (n, n.dot(p.to_vector()) - radius))
+                .collect();
+            corners.push(Corner {
+                vertex: p,
+                center: solve_planes(&rows)?,
+                normals: [a, b, c],
+            });
+        }
+    }
+    Ok(corners)
+}
+
+/// 稜線に垂直な断面での、稜線と両側のフェイスの向き
+pub(crate) struct EdgeFrame {
+    /// 断面を置く稜線上の点（エッジの中央）
+    pub(crate) point: Point3,
+    /// 両側のフェイスの外向きの法線
+    pub(crate) normals: [Vector3; 2],
+    /// 両側のフェイスに沿って稜線から離れる向き（稜線と法線に垂直）
+    pub(crate) inwards: [Vector3; 2],
+    /// 稜線が凸なら `true`
+    pub(crate) convex: bool,
+    /// 両側のフェイス（`normals`・`inwards` と同じ順）
+    pub(crate) faces: [Face; 2],
+    /// 断面を稜線に沿って動かす方法
+    sweep: Sweep,
+}
+
+/// 稜線の形
+enum Sweep {
+    /// 直線（始点と終点）
+    Line(Point3, Point3),
+    /// 円（回転軸、半径、断面の点から測ったエッジの両端の角度と、一周するかどうか）
+    Circle(Axis1, f64, (f64, f64), bool),
+}
+
+impl EdgeFrame {
+    /// 両側のフェイスの順を入れ替える
+    pub(crate) fn swap_sides(&mut self) {
+        self.normals.swap(0, 1);
+        self.inwards.swap(0, 1);
+        self.faces.swap(0, 1);
+    }
+
+    /// 直線の稜線なら始点と終点を返す
+    pub(crate) fn line_ends(&self) -> Option<(Point3, Point3)> {
+        match self.sweep {
+            Sweep::Line(a, b) => Some((a, b)),
+            Sweep::Circle(..) => None,
+        }
+    }
+
+    /// 道具を両端で延ばす長さ
+    ///
+    /// 凸の稜線は立体の外まで `extension` だけ延ばし、凹の稜線は延ばさない。
+    /// 角で終わる端は、角の球の中心を通り稜線に垂直な平面まで縮める。
+    pub(crate) fn extensions(&self, extension: f64, corners: &[Corner]) -> (f64, f64) {
+        let base = if self.convex { extension } else { 0.0 };
+        let Some((a, b)) = self.line_ends() else {
+            return (base, base);
+        };
+        let direction = (b - a).normalize_or(Vector3::ZERO);
+        let end = |p: Point3, sign: f64| {
+            corners
+                .iter()
+                .find(|c| c.vertex.distance(p) <= precision::confusion())
+                .map_or(base, |c| sign * (c.center - p).dot(direction))
+        };
+        (end(a, -1.0), end(b, 1.0))
+    }
+
+    /// 断面上の点 `points` を結んだ多角形（`center` があれば最初と最後の間を除く辺を、
+    /// それを中心とする円弧にした図形）を稜線に沿って動かした立体を返す
+    ///
+    /// 始点側と終点側をそれぞれ `ends` の長さだけ延ばす（負なら縮める）。一周する円では無視する。
+    pub(crate) fn sweep_profile(
+        &self,
+        points: &[Point3],
+        center: Option<Point3>,
+        ends: (f64, f64),
+    ) -> Result<Shape> {
+        match self.sweep {
+            Sweep::Line(start, end) => {
+                let length = start.distance(end);
+                let direction = Dir::from_vector(end - start)?;
+                let shift = (start - self.point) - direction.to_vector() * ends.0;
+                let moved: Vec<Point3> = points.iter().map(|&p| p + shift).collect();
+                let profile = profile_face(&moved, center.map(|c| c + shift))?;
+                extrude(&Shape::from(profile), direction, length + ends.0 + ends.1)
+            }
+            Sweep::Circle(axis, radius, (a, b), closed) => {
+                // 一周する場合も、継ぎ目を稜線の頂点にそろえて立体の継ぎ目と交わるようにする
+                let (start, angle) = if closed {
+                    (a, TAU)
+                } else {
+                    let limit = 0.25 * (TAU - (b - a));
+                    let (m0, m1) = ((ends.0 / radius).min(limit), (ends.1 / radius).min(limit));
+                    (a - m0, b - a + m0 + m1)
+                };
+                let rotate = |p: Point3| {
+                    let origin = axis.location;
+                    origin + (p - origin).rotated_about(axis.direction, start)
+                };
+                let moved: Vec<Point3> = points.iter().map(|&p| rotate(p)).collect();
+                let profile = profile_face(&moved, center.map(rotate))?;
+                revolve(&Shape::from(profile), axis, angle)
+            }
+        }
+    }
+}
+
+/// 立体のエッジごとに断面の向きを求める
+///
+/// エッジは立体のちょうど2つのフェイスに挟まれ、平面どうしの直線の稜線か、平面・円柱面・円錐面が
+/// 同じ軸のまわりに交わる円の稜線でなければならない。
+pub(crate) fn edge_frames(shape: &Shape, edges: &[Edge]) -> Result<Vec<EdgeFrame>> {
+    if shape.explore(ShapeKind::Solid).next().is_none() {
+        return Err(OcctKrsError::InvalidInput(
+            "稜線を加工する形状に立体がありません".to_string(),
+        ));
+    }
+    if edges.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "加工するエッジがありません".to_string(),
+        ));
+    }
+    let map = shape.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+    let mut frames = Vec::with_capacity(edges.len());
+    for edge in edges {
+        let faces: Vec<Face> = map
+            .get(&Shape::from(edge.clone()))
+            .iter()
+            .filter_map(|s| Face::try_from(s.clone()).ok())
+            .collect();
+        let [a, b] = faces.as_slice() else {
+            return Err(OcctKrsError::InvalidInput(
+                "立体の2つのフェイスに挟まれたエッジではありません".to_string(),
+            ));
+        };
+        frames.push(edge_frame(edge, [a, b])?);
+    }
+    Ok(frames)
+}
+
+fn edge_frame(edge: &Edge, faces: [&Face; 2]) -> Result<EdgeFrame> {
+    let unsupported = || {
+        OcctKrsError::InvalidInput(
+            "平面どうしの直線の稜線か、同じ軸のまわりの円の稜線にだけ対応しています".to_string(),
+        )
+    };
+    let Some(curve) = edge.located_geometry() else {
+        return Err(unsupported());
+    };
+    let (t0, t1) = edge.range();
+    let mid = 0.5 * (t0 + t1);
+    let point = curve.point_at(mid);
+    let tangent = curve.derivative_at(mid).try_normalized()?;
+    let sweep = match curve.as_ref() {
+        GeomCurve::Line(_) => {
+            if !faces
+                .iter()
+                .all(|f| matches!(f.located_geometry().as_ref(), GeomSurface::Plane(_)))
+            {
+                return Err(unsupported());
+            }
+            Sweep::Line(curve.point_at(t0), curve.point_at(t1))
+        }
+        GeomCurve::Circle(circle) => {
+            let position = circle.position();
+            let axis = Axis1::new(position.location(), position.direction());
+            if !faces.iter().all(|f| coaxial(&f.located_geometry(), &axis)) {
+                return Err(unsupported());
+            }
+            Sweep::Circle(
+                axis,
+                circle.radius(),
+                (t0 - mid, t1 - mid),
+                edge.is_closed(),
+            )
+        }
+        _ => return Err(unsupported()),
+    };
+    let step = 1e-3
+        * point
+            .distance(curve.point_at(t0))
+            .max(precision::confusion());
+    let mut normals = [Vector3::ZERO; 2];
+    let mut inwards = [Vector3::ZERO; 2];
+    for (k, face) in faces.iter().enumerate() {
+        let classifier = FaceClassifier::new(face)?;
+        let normal = classifier
+            .normal_at(point)
+            .ok_or_else(unsupported)?
+            .to_vector();
+        normals[k] = if face.orientation() == Orientation::Reversed {
+            -normal
+        } else {
+            normal
+        };
+        let inward = normals[k].cross(tangent).try_normalized()?;
+        let inside = classifier.classify(point + inward * step, precision::confusion())
+            == PointClassification::Inside;
+        inwards[k] = if inside { inward } else { -inward };
+    }
+    let bend = inwards[0].dot(normals[1]);
+    if bend.abs() <= precision::angular() {
+        return Err(OcctKrsError::InvalidInput(
+            "滑らかにつながるフェイスの間の稜線は加工できません".to_string(),
+        ));
+    }
+    Ok(EdgeFrame {
+        point,
+        normals,
+        inwards,
+        faces: faces.map(Face::clone),
+        convex: bend < 0.0,
+        sweep,
+    })
+}
+
+/// 曲面が軸 `axis` のまわりの回転対称な平面・円柱面・円錐面なら `true` を返す
+fn coaxial(surface: &GeomSurface, axis: &Axis1) -> bool {
+    let position = match surface {
+        GeomSurface::Plane(plane) => {
+            return plane.normal().dot(axis.direction).abs() >= 1.0 - precision::angular()
+        }
+        GeomSurface::Cylinder(s) => s.position(),
+        GeomSurface::Cone(s) => s.position(),
+        _ => return false,
+    };
+    let offset = position.location() - axis.location;
+    position.direction().dot(axis.direction).abs() >= 1.0 - precision::angular()
+        && offset.cross(axis.direction.to_vector()).length() <= precision::confusion()
+}
+
+/// 断面上の点を結んだ閉じた平面のフェイスを返す
+///
+/// `center` があれば2番目から最後の1つ手前の点までを、それを中心とする円弧で結ぶ。
+fn profile_face(points: &[Point3], center: Option<Point3>) -> Result<Face> {
+    let vertices: Vec<Vertex> = points.iter().map(|&p| Vertex::new(p)).collect();
+    let n = points.len();
+    let mut edges = Vec::with_capacity(n);
+    for i in 0..n {
+        let j = (i + 1) % n;
+        let (a, b) = (points[i], points[j]);
+        let edge = match center {
+            Some(c) if i == 1 && j + 1 == n => arc_edge(c, &vertices[i], &vertices[j])?,
+            _ => Edge::new(
+                Line::from_points(a, b)?,
+                (0.0, a.distance(b)),
+                vertices[i].clone(),
+                vertices[j].clone(),
+            )?,
+        };
+        edges.push(edge);
+    }
+    Face::from_planar_wires(Wire::new(edges)?, Vec::new(), precision::confusion())
+}
+
+/// 中心 `center` のまわりに頂点 `start` から `end` へ短い側を回る円弧のエッジ
+fn arc_edge(center: Point3, start: &Vertex, end: &Vertex) -> Result<Edge> {
+    let (a, b) = (start.point() - center, end.point() - center);
+    let normal = Dir::from_vector(a.cross(b))?;
+    let x = Dir::from_vector(a)?;
+    let circle = Circle::new(Axis2::new(center, normal, x)?, a.length())?;
+    Edge::new(
+        circle,
+        (0.0, a.angle_between(b)),
+        start.clone(),
+        end.clone(),
+    )
+}
+
+/// 選んだ稜線が集まるが角として丸められない頂点のエラー
+fn unsupported_corner(p: Point3) -> OcctKrsError {
+    OcctKrsError::InvalidInput(format!(
+        "選んだ稜線が集まる頂点 ({}, {}, {}) は、3本の凸の直線の稜線がすべて選ばれた角でないので丸められません",
+        p.x, p.y, p.z
+    ))
+}
+
+/// 凸の稜線の道具を立体から取り除き、凹の稜線の道具を立体に加える
+///
+/// 結果を [`Shape::check`] で調べ、不具合があればエラーを返す。
+pub(crate) fn apply_tools(shape: &Shape, convex: Vec<Shape>, concave: Vec<Shape>) -> Result<Shape> {
+    let mut result = if convex.is_empty() {
+        shape.clone()
+    } else {
+        boolean::cut_all(shape, &convex)?
+    };
+    for tool in concave {
+        result = boolean::fuse(&result, &tool)?;
+    }
+    let report = result.check();
+    if let Some(issue) = report.issues().first() {
+        return Err(OcctKrsError::DegenerateGeometry(format!(
+            "丸めた結果が正しい立体になりません: {:?}",
+            issue.status
+        )));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder, make_wedge};
+    use crate::test_support::checked_volume;
+    use std::f64::consts::PI;
+
+    fn make_block(x: f64, y: f64, z: f64, size: (f64, f64, f64)) -> Shape {
+        let position = Axis2::new(Point3::new(x, y, z), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, size.0, size.1, size.2).unwrap())
+    }
+
+    /// 2点を結ぶ直線のエッジを探す
+    fn edge_between(shape: &Shape, a: Point3, b: Point3) -> Edge {
+        shape
+            .edges()
+            .into_iter()
+            .find(|e| {
+                let (p, q) = (e.start_point(), e.end_point());
+                (p.distance(a) < 1e-9 && q.distance(b) < 1e-9)
+                    || (p.distance(b) < 1e-9 && q.distance(a) < 1e-9)
+            })
+            .unwrap()
+    }
+
+    fn count_faces(shape: &Shape, surface: fn(&GeomSurface) -> bool) -> usize {
+        shape
+            .faces()
+            .iter()
+            .filter(|f| surface(f.surface()))
+            .count()
+    }
+
+    #[test]
+    fn test_fillet_box_edges() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let edge = edge_between(
+            &cube,
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(1.0, 1.0, 1.0),
+        );
+        let result = fillet(&cube, &[edge], 0.5).unwrap();
+        assert_eq!(result.faces().len(), 7);
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::Cylinder(_))),
+            1
+        );
+        let volume = checked_volume(&result);
+        let expected = 1.0 - (1.0 - PI / 4.0) * 0.25;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 縦の4本の稜線
+        let edges: Vec<Edge> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| edge_between(&cube, Point3::new(x, y, 0.0), Point3::new(x, y, 1.0)))
+            .collect();
+        let result = fillet(&cube, &edges, 0.3).unwrap();
+        assert_eq!(result.faces().len(), 10);
+        let volume = checked_volume(&result);
+        let expected = 1.0 - 4.0 * (1.0 - PI / 4.0) * 0.09;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_fillet_corners() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let vertex = Point3::new(1.0, 1.0, 1.0);
+        let edges: Vec<Edge> = cube
+            .edges()
+            .into_iter()
+            .filter(|e| e.start_point() == vertex || e.end_point() == vertex)
+            .collect();
+        let result = fillet(&cube, &edges, 0.2).unwrap();
+        assert_eq!(result.faces().len(), 10);
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::Sphere(_))),
+            1
+        );
+        checked_volume(&result);
+        // すべての稜線を丸めた直方体
+        let result = fillet(&cube, &cube.edges(), 0.2).unwrap();
+        assert_eq!(result.faces().len(), 26);
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::Sphere(_))),
+            8
+        );
+        let volume = checked_volume(&result);
+        let (r, a) = (0.2, 0.6);
+        let expected =
+            a * a * a + 6.0 * a * a * r + 3.0 * PI * r * r * a + 4.0 / 3.0 * PI * r * r * r;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 直角でない角
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let wedge = Shape::from(make_wedge(position, 1.0, 1.0, 1.0, 0.5).unwrap());
+        let vertex = Point3::new(0.5, 1.0, 0.0);
+        let edges: Vec<Edge> = wedge
+            .edges()
+            .into_iter()
+            .filter(|e| e.start_point() == vertex || e.end_point() == vertex)
+            .collect();
+        let result = fillet(&wedge, &edges, 0.1).unwrap();
+        assert_eq!(result.faces().len(), 10);
+        checked_volume(&result);
+    }
+
+    #[test]
+    fn test_fillet_two_edge_corners() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let top = |a: (f64, f64), b: (f64, f64)| {
+            edge_between(
+                &cube,
+                Point3::new(a.0, a.1, 1.0),
+                Point3::new(b.0, b.1, 1.0),
+            )
+        };
+        // 3本目の稜線を丸めない頂点で2本が集まる
+        let edges = [top((0.0, 1.0), (1.0, 1.0)), top((1.0, 0.0), (1.0, 1.0))];
+        assert!(matches!(
+            fillet(&cube, &edges, 0.2),
+            Err(OcctKrsError::InvalidInput(_))
+        ));
+        // 上面の4本の稜線
+        let edges = [
+            top((0.0, 0.0), (1.0, 0.0)),
+            top((1.0, 0.0), (1.0, 1.0)),
+            top((1.0, 1.0), (0.0, 1.0)),
+            top((0.0, 1.0), (0.0, 0.0)),
+        ];
+        assert!(matches!(
+            fillet(&cube, &edges, 0.2),
+            Err(OcctKrsError::InvalidInput(_))
+        ));
+        assert!(fillet_with_law(&cube, &edges, &RadiusLaw::Linear(0.1, 0.2)).is_err());
+        // 離れた2本なら角はなく、閉じた立体になる
+        let edges = [top((0.0, 0.0), (1.0, 0.0)), top((1.0, 1.0), (0.0, 1.0))];
+        let result = fillet(&cube, &edges, 0.2).unwrap();
+        assert!(result.check().is_valid());
+        let volume = checked_volume(&result);
+        let expected = 1.0 - 2.0 * (1.0 - PI / 4.0) * 0.04;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_fillet_circular_edge() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 1.0).unwrap());
+        let top = cylinder
+            .edges()
+            .into_iter()
+            .find(|e| e.is_closed() && e.start_point().z > 0.5)
+            .unwrap();
+        let result = fillet(&cylinder, &[top], 0.5).unwrap();
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::Torus(_))),
+            1
+        );
+        let volume = checked_volume(&result);
+        // 断面の面積 (1 - π/4)r² を重心の半径で回した体積を引く
+        let area = (1.0 - PI / 4.0) * 0.25;
+        let centroid = 1.0 - 0.5 * (10.0 - 3.0 * PI) / (12.0 - 3.0 * PI);
+        let expected = PI - TAU * centroid * area;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_fillet_concave_edge() {
+        let lower = make_block(0.0, 0.0, 0.0, (2.0, 1.0, 1.0));
+        let upper = make_block(0.0, 0.0, 1.0, (1.0, 1.0, 1.0));
+        let l_shape = boolean::fuse(&lower, &upper).unwrap();
+        let edge = edge_between(
+            &l_shape,
+            Point3::new(1.0, 0.0, 1.0),
+            Point3::new(1.0, 1.0, 1.0),
+        );
+        let result = fillet(&l_shape, &[edge], 0.5).unwrap();
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::Cylinder(_))),
+            1
+        );
+        let volume = checked_volume(&result);
+        let expected = 3.0 + (1.0 - PI / 4.0) * 0.25;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_fillet_errors() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let edge = cube.edges()[0].clone();
+        assert!(fillet(&cube, std::slice::from_ref(&edge), 0.0).is_err());
+        assert!(fillet(&cube, &[], 0.1).is_err());
+        let other = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0)).edges()[0].clone();
+        assert!(fillet(&cube, &[other], 0.1).is_err());
+        assert!(fillet(&Shape::from(cube.faces()[0].clone()), &[edge], 0.1).is_err());
+    }
+
+    #[test]
+    fn test_radius_law() {
+        let law = RadiusLaw::Linear(0.2, 0.4);
+        assert!((law.radius_at(0.5) - 0.3).abs() < 1e-12);
+        assert_eq!(law.radius_at(2.0), 0.4);
+        let law = RadiusLaw::Interpolated(vec![(0.2, 0.1), (0.5, 0.3), (1.0, 0.3)]);
+        assert_eq!(law.radius_at(0.0), 0.1);
+        assert!((law.radius_at(0.5) - 0.3).abs() < 1e-12);
+        // 単調な補間なので、平らな区間で値を超えない
+        for k in 0..=100 {
+            let r = law.radius_at(k as f64 / 100.0);
+            assert!((0.1..=0.3 + 1e-12).contains(&r), "{}", r);
+        }
+        assert!((law.radius_at(0.75) - 0.3).abs() < 1e-12);
+        assert!(RadiusLaw::Linear(0.2, 0.0).validate().is_err());
+        assert!(RadiusLaw::Interpolated(vec![]).validate().is_err());
+        assert!(RadiusLaw::Interpolated(vec![(0.5, 0.1), (0.5, 0.2)])
+            .validate()
+            .is_err());
+        assert!(RadiusLaw::Interpolated(vec![(0.0, 0.1), (1.5, 0.2)])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_fillet_with_law() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let edge = edge_between(
+            &cube,
+            Point3::new(0.0, 1.0, 1.0),
+            Point3::new(1.0, 1.0, 1.0),
+        );
+        let (r0, r1) = (0.2, 0.4);
+        let result = fillet_with_law(
+            &cube,
+            std::slice::from_ref(&edge),
+            &RadiusLaw::Linear(r0, r1),
+        )
+        .unwrap();
+        assert_eq!(result.faces().len(), 7);
+        assert_eq!(
+            count_faces(&result, |s| matches!(s, GeomSurface::BSpline(_))),
+            1
+        );
+        let volume = checked_volume(&result);
+        // 丸めの面は B-スプライン曲面で近似するので、体積もその誤差の分だけずれる
+        let expected = 1.0 - (1.0 - PI / 4.0) * (r0 * r0 + r0 * r1 + r1 * r1) / 3.0;
+        assert!((volume / expected - 1.0).abs() < 1e-4, "{}", volume);
+        // エッジの始点側が始めの半径になる
+        let start = edge.start_point();
+        assert!(result
+            .edges()
+            .iter()
+            .flat_map(|e| [e.start_point(), e.end_point()])
+            .any(|p| p.distance(Point3::new(start.x, 1.0 - r0, 1.0)) < 1e-9));
+        // 補間した法則の道具は、断面の半径から求めた体積の閉じた立体になる
+        let law = RadiusLaw::Interpolated(vec![(0.0, 0.3), (0.4, 0.1), (1.0, 0.3)]);
+        let frames = edge_frames(&cube, std::slice::from_ref(&edge)).unwrap();
+        let tool = law_tool(&frames[0], &edge, &law).unwrap();
+        let volume = checked_volume(&tool);
+        let n = 200;
+        let inner: f64 = (0..n)
+            .map(|k| law.radius_at((k as f64 + 0.5) / n as f64).powi(2) / n as f64)
+            .sum();
+        // 断面の面積は (1 - π/4)r²。両端は立体の外まで 2r だけ端の半径のまま延びている
+        // （断面の間を補間した誤差があるので、許容誤差を大きめにする）
+        let expected = (1.0 - PI / 4.0) * (inner + 2.0 * 0.09 * 0.6);
+        assert!((volume / expected - 1.0).abs() < 2e-3, "{}", volume);
+    }
+
+    #[test]
+    fn test_fillet_with_law_errors() {
+        let cube = make_block(0.0, 0.0, 0.0, (1.0, 1.0, 1.0));
+        let vertex = Point3::new(1.0, 1.0, 1.0);
+        let edges: Vec<Edge> = cube
+            .edges()
+            .into_iter()
+            .filter(|e| e.start_point() == vertex || e.end_point() == vertex)
+            .collect();
+        let law = RadiusLaw::Linear(0.1, 0.2);
+        assert!(fillet_with_law(&cube, &edges, &law).is_err());
+        assert!(fillet_with_law(&cube, &edges[..1], &RadiusLaw::Linear(0.1, -0.2)).is_err());
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 1.0).unwrap());
+        let circle = cylinder
+            .edges()
+            .into_iter()
+            .find(|e| e.is_closed() && e.start_point().z > 0.5)
+            .unwrap();
+        assert!(fillet_with_law(&cylinder, &[circle], &law).is_err());
+    }
+}
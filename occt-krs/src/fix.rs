@@ -0,0 +1,556 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::check::{edge_parameters, edge_uv};
+use crate::curve::gauss_legendre;
+use crate::shape_builder::{edge_pcurve, signed_area};
+use crate::topo::{check_tolerance, ShapeId};
+use crate::{
+    Curve2, Edge, Face, GeomCurve, Location, OcctKrsError, Orientation, Point3, Result, Shell,
+    Solid, Surface, Vector2, Wire,
+};
+
+/// 体積を求めるときの、境界の直線のエッジ1本あたりの区間数
+const VOLUME_LINE_SEGMENTS: usize = 2;
+
+/// 体積を求めるときの、境界の曲線のエッジ1本あたりの区間数
+const VOLUME_EDGE_SEGMENTS: usize = 16;
+
+/// 体積を求めるときの、`u` 方向の積分の区間数
+const VOLUME_INNER_SEGMENTS: usize = 8;
+
+impl Wire {
+    /// 順序や向きのそろっていないエッジからワイヤを生成する（OCCT の `ShapeFix_Wire` 相当）
+    ///
+    /// 長さが `tolerance` 以下のエッジ（退化エッジを除く）を取り除き、端点が近いものから順に並べて向きをそろえ、
+    /// `tolerance` 以内の隙間は [`Wire::from_edges`] と同じように頂点をまとめてふさぐ。
+    /// 取り除いた後にエッジが残らない場合や、`tolerance` 以内でつながらないエッジがある場合はエラーを返す。
+    pub fn fix_edges(edges: Vec<Edge>, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        let mut edges: Vec<Edge> = edges
+            .into_iter()
+            .filter(|e| !is_small(e, tolerance))
+            .collect();
+        if edges.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "長さのあるエッジがありません".to_string(),
+            ));
+        }
+        let mut chain = VecDeque::from([edges.remove(0)]);
+        while !edges.is_empty() {
+            let end = chain[chain.len() - 1].end_point();
+            let (i, gap, reverse) =
+                nearest_end(&edges, |e| e.start_point(), |e| e.end_point(), end);
+            if gap <= tolerance {
+                let edge = edges.remove(i);
+                chain.push_back(if reverse { edge.reversed() } else { edge });
+                continue;
+            }
+            // 末尾につながらなければ先頭につなぐ
+            let start = chain[0].start_point();
+            let (i, gap, reverse) =
+                nearest_end(&edges, |e| e.end_point(), |e| e.start_point(), start);
+            if gap > tolerance {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 本のエッジがワイヤにつながりません（最も近い端点までの距離 {}、許容誤差 {}）",
+                    edges.len(),
+                    gap,
+                    tolerance
+                )));
+            }
+            let edge = edges.remove(i);
+            chain.push_front(if reverse { edge.reversed() } else { edge });
+        }
+        Wire::from_edges(chain.into(), tolerance)
+    }
+
+    /// 長さが `tolerance` 以下のエッジを取り除き、`tolerance` 以内の隙間をふさいだワイヤを返す
+    ///
+    /// 最後のエッジの終点が最初のエッジの始点から `tolerance` 以内なら閉じたワイヤにする。
+    /// 直す必要のないワイヤでは、同じエッジ（`is_same`）をそのまま使う。
+    pub fn fix(&self, tolerance: f64) -> Result<Self> {
+        Self::fix_edges(self.edges(), tolerance)
+    }
+}
+
+/// 長さが `tolerance` 以下の（退化エッジではない）エッジなら `true` を返す
+fn is_small(edge: &Edge, tolerance: f64) -> bool {
+    let start = edge.start_point();
+    !edge.is_degenerate()
+        && edge_parameters(edge)
+            .iter()
+            .all(|&t| edge.point_at(t).distance(start) <= tolerance)
+}
+
+/// 点 `p` に最も近い端点を持つエッジの番号・距離と、`far` 側の端点の方が近いか（反転が必要か）を返す
+fn nearest_end(
+    edges: &[Edge],
+    near: impl Fn(&Edge) -> Point3,
+    far: impl Fn(&Edge) -> Point3,
+    p: Point3,
+) -> (usize, f64, bool) {
+    edges
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let (dn, df) = (near(e).distance(p), far(e).distance(p));
+            if df < dn {
+                (i, df, true)
+            } else {
+                (i, dn, false)
+            }
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, f64::INFINITY, false))
+}
+
+/// ワイヤを曲面のパラメータ空間へ移した多角形（始点を末尾に重ねない。求められなければ `None`）
+fn wire_uv(face: &Face, wire: &Wire) -> Option<Vec<Vector2>> {
+    let mut points = Vec::new();
+    for edge in wire.edges() {
+        let uv = edge_uv(face, &edge, &edge_parameters(&edge))?;
+        points.extend_from_slice(&uv[..uv.len() - 1]);
+    }
+    Some(points)
+}
+
+impl Face {
+    /// 境界のワイヤを直したフェイスを返す（OCCT の `ShapeFix_Face` 相当）
+    ///
+    /// 各ワイヤに [`Wire::fix`] を適用し、pcurve を持たない退化エッジは取り除く。
+    /// 失われた pcurve は求め直し、パラメータ空間で最も広い範囲を囲むワイヤを外側の境界として
+    /// 反時計回りに、残りの穴のワイヤを時計回りにそろえる。
+    /// 直した後も閉じていないワイヤがある場合はエラーを返す。
+    pub fn fix(&self, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        // 配置と向きを外した中身を直してから、元の配置と向きに戻す
+        let face = self
+            .oriented(Orientation::Forward)
+            .located(Location::identity());
+        let surface = face.surface();
+        let mut wires = Vec::new();
+        let mut pcurves = Vec::new();
+        for (i, wire) in face.wires().iter().enumerate() {
+            let edges: Vec<Edge> = wire
+                .edges()
+                .into_iter()
+                .filter(|e| !e.is_degenerate() || face.pcurve(e).is_some())
+                .collect();
+            if edges.is_empty() {
+                continue;
+            }
+            let wire = Wire::fix_edges(edges, tolerance)?;
+            if !wire.is_closed() {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のワイヤが閉じていません",
+                    i
+                )));
+            }
+            // 作り直したワイヤは向きが Forward なので、たどる順が格納順になる
+            pcurves.push(
+                wire.edges()
+                    .iter()
+                    .map(|e| match face.pcurve(e) {
+                        Some(p) => Some(p.clone()),
+                        None => edge_pcurve(e, surface, tolerance).unwrap_or(None),
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            wires.push(wire);
+        }
+        if wires.is_empty() {
+            return Ok(self.clone());
+        }
+        let unoriented = Face::with_pcurves(surface.clone(), wires, pcurves.clone())?;
+        let areas: Vec<Option<f64>> = unoriented
+            .wires()
+            .iter()
+            .map(|w| wire_uv(&unoriented, w).map(|uv| signed_area(&uv)))
+            .collect();
+        let outer = areas
+            .iter()
+            .enumerate()
+            .filter_map(|(i, a)| a.map(|a| (i, a.abs())))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(i, _)| i);
+        let mut order: Vec<usize> = (0..areas.len()).collect();
+        order.swap(0, outer);
+        let mut wires = Vec::with_capacity(order.len());
+        let mut sorted = Vec::with_capacity(order.len());
+        for (k, &i) in order.iter().enumerate() {
+            let wire = &unoriented.wires()[i];
+            // pcurve は格納順に並ぶので、ワイヤを反転しても並べ替えない
+            let reverse = areas[i].is_some_and(|a| a != 0.0 && (a > 0.0) != (k == 0));
+            wires.push(if reverse {
+                wire.reversed()
+            } else {
+                wire.clone()
+            });
+            sorted.push(pcurves[i].clone());
+        }
+        Ok(Face::with_pcurves(surface.clone(), wires, sorted)?
+            .with_tolerance(face.tolerance())?
+            .located(self.location())
+            .oriented(self.orientation()))
+    }
+}
+
+impl Shell {
+    /// 隣り合うフェイスが共有するエッジを逆向きに使うように、フェイスの向きをそろえたシェルを返す
+    /// （OCCT の `ShapeFix_Shell` 相当）
+    ///
+    /// つながったフェイスの組ごとに、最初のフェイスの向きに合わせる。
+    /// 3枚以上のフェイスが共有するエッジは向きの判定に使わない。
+    pub fn fix_orientation(&self) -> Result<Self> {
+        let shell = self
+            .oriented(Orientation::Forward)
+            .located(Location::identity());
+        let faces = shell.faces();
+        let mut uses: HashMap<ShapeId, Vec<(usize, Orientation)>> = HashMap::new();
+        for (i, face) in faces.iter().enumerate() {
+            for wire in face.wires() {
+                for edge in wire.edges() {
+                    if !edge.is_degenerate() {
+                        uses.entry(edge.id())
+                            .or_default()
+                            .push((i, edge.orientation()));
+                    }
+                }
+            }
+        }
+        // 隣のフェイスと、共有するエッジを同じ向きで使っているか
+        let mut neighbors: Vec<Vec<(usize, bool)>> = vec![Vec::new(); faces.len()];
+        for u in uses.values() {
+            if let [(a, oa), (b, ob)] = u[..] {
+                if a != b {
+                    neighbors[a].push((b, oa == ob));
+                    neighbors[b].push((a, oa == ob));
+                }
+            }
+        }
+        let mut flip: Vec<Option<bool>> = vec![None; faces.len()];
+        for seed in 0..faces.len() {
+            if flip[seed].is_some() {
+                continue;
+            }
+            flip[seed] = Some(false);
+            let mut queue = VecDeque::from([seed]);
+            while let Some(i) = queue.pop_front() {
+                let fi = flip[i].unwrap_or(false);
+                for &(j, same) in &neighbors[i] {
+                    if flip[j].is_none() {
+                        flip[j] = Some(fi != same);
+                        queue.push_back(j);
+                    }
+                }
+            }
+        }
+        let faces = faces
+            .iter()
+            .zip(&flip)
+            .map(|(f, &flip)| {
+                if flip == Some(true) {
+                    f.reversed()
+                } else {
+                    f.clone()
+                }
+            })
+            .collect();
+        Ok(Shell::new(faces)?
+            .located(self.location())
+            .oriented(self.orientation()))
+    }
+}
+
+impl Solid {
+    /// シェルのフェイスの向きをそろえ、外側の境界のシェルは外向き、空洞のシェルは内向きにした立体を返す
+    /// （OCCT の `ShapeFix_Solid` 相当）
+    ///
+    /// 囲む体積が最も大きいシェルを外側の境界として最初に置く。
+    /// 体積はフェイスのパラメータ空間を格子に分けて求めるので、pcurve を求められないフェイスは無視する。
+    pub fn fix_orientation(&self) -> Result<Self> {
+        let solid = self
+            .oriented(Orientation::Forward)
+            .located(Location::identity());
+        let shells = solid
+            .shells()
+            .iter()
+            .map(|s| s.fix_orientation())
+            .collect::<Result<Vec<_>>>()?;
+        let volumes: Vec<f64> = shells.iter().map(signed_volume).collect();
+        let outer = volumes
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map_or(0, |(i, _)| i);
+        let mut order: Vec<usize> = (0..shells.len()).collect();
+        order.swap(0, outer);
+        let shells = order
+            .iter()
+            .enumerate()
+            .map(|(k, &i)| {
+                let v = volumes[i];
+                if v != 0.0 && (v > 0.0) != (k == 0) {
+                    shells[i].reversed()
+                } else {
+                    shells[i].clone()
+                }
+            })
+            .collect();
+        Ok(Solid::new(shells)?
+            .located(self.location())
+            .oriented(self.orientation()))
+    }
+}
+
+/// シェルが囲む符号付き体積（フェイスの法線が外向きなら正）
+///
+/// 発散定理により `∮ p·n dA / 3` を求める。各フェイスの面積分は Green の定理で境界の pcurve に沿った
+/// 線積分に移し、Gauss–Legendre 積分で求める。
+pub(crate) fn signed_volume(shell: &Shell) -> f64 {
+    shell.faces().iter().filter_map(face_volume).sum()
+}
+
+/// フェイスの `∫ p·n dA / 3`（境界をパラメータ空間へ移せなければ `None`）
+///
+/// `g(u, v) = p·(S_u × S_v)` の領域上の積分を、`G(u, v) = ∫_{u0}^{u} g(s, v) ds` の境界に沿った
+/// `∮ G dv` として求める。ワイヤの向きによらないよう、パラメータ空間で最も広い範囲を囲むワイヤを外側、
+/// 残りを穴として向きをそろえてから足し合わせる。
+fn face_volume(face: &Face) -> Option<f64> {
+    let t = face.location().transform();
+    let surface = face.surface();
+    let wires = face.wires();
+    let first_edge = wires.first()?.edges().into_iter().next()?;
+    let u0 = edge_uv(face, &first_edge, &[first_edge.range().0])?[0].x;
+    let g = |u: f64, v: f64| {
+        let n = t
+            .transform_vector(surface.derivative_u_at(u, v))
+            .cross(t.transform_vector(surface.derivative_v_at(u, v)));
+        face.point_at(u, v).to_vector().dot(n)
+    };
+    let inner = |u: f64, v: f64| {
+        let du = (u - u0) / VOLUME_INNER_SEGMENTS as f64;
+        (0..VOLUME_INNER_SEGMENTS)
+            .map(|k| {
+                let a = u0 + du * k as f64;
+                gauss_legendre(&|s| g(s, v), a, a + du)
+            })
+            .sum::<f64>()
+    };
+    // ワイヤごとの (∮ (u - u0) dv, ∮ G dv)
+    let mut loops = Vec::with_capacity(wires.len());
+    for wire in &wires {
+        let (mut area, mut integral) = (0.0, 0.0);
+        for edge in wire.edges() {
+            let (first, last) = edge.range();
+            let (a, b) = if edge.orientation() == Orientation::Reversed {
+                (last, first)
+            } else {
+                (first, last)
+            };
+            let segments = if matches!(edge.curve(), Some(GeomCurve::Line(_))) {
+                VOLUME_LINE_SEGMENTS
+            } else {
+                VOLUME_EDGE_SEGMENTS
+            };
+            let h = (b - a) / segments as f64;
+            for k in 0..segments {
+                let s = a + h * k as f64;
+                let along = |f: &dyn Fn(Vector2) -> f64| {
+                    gauss_legendre(
+                        &|x| {
+                            edge_uv_derivative(face, &edge, x, h)
+                                .map_or(f64::NAN, |(p, d)| f(p) * d.y)
+                        },
+                        s,
+                        s + h,
+                    )
+                };
+                area += along(&|p| p.x - u0);
+                integral += along(&|p| inner(p.x, p.y));
+            }
+        }
+        if !(area.is_finite() && integral.is_finite()) {
+            return None;
+        }
+        loops.push((area, integral));
+    }
+    let outer = (0..loops.len()).max_by(|&i, &j| loops[i].0.abs().total_cmp(&loops[j].0.abs()))?;
+    if loops[outer].0 == 0.0 {
+        return None;
+    }
+    let sum: f64 = loops
+        .iter()
+        .enumerate()
+        .map(|(i, &(area, integral))| {
+            let ccw = integral * area.signum();
+            if i == outer {
+                ccw
+            } else {
+                -ccw
+            }
+        })
+        .sum();
+    let sign = if face.orientation() == Orientation::Reversed {
+        -1.0
+    } else {
+        1.0
+    };
+    Some(sign * sum / 3.0)
+}
+
+/// エッジの pcurve（平面で pcurve がなければ平面への射影）の、パラメータ `t` における点と微分
+///
+/// pcurve がない場合の微分は、区間幅 `h` に比べて十分小さい差分で求める。
+fn edge_uv_derivative(face: &Face, edge: &Edge, t: f64, h: f64) -> Option<(Vector2, Vector2)> {
+    if let Some(pcurve) = face.pcurve(edge) {
+        return Some((pcurve.point_at(t), pcurve.derivative_at(t)));
+    }
+    let d = h.abs() * 1e-4;
+    let uv = edge_uv(face, edge, &[t - d, t, t + d])?;
+    Some((uv[1], (uv[2] - uv[0]) / (2.0 * d)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Line, Plane, Shape, Vertex};
+
+    fn segment_edge(a: &Vertex, b: &Vertex) -> Edge {
+        let line = Line::from_points(a.point(), b.point()).unwrap();
+        let length = a.point().distance(b.point());
+        Edge::new(line, (0.0, length), a.clone(), b.clone()).unwrap()
+    }
+
+    /// 頂点・エッジを共有する、外向きの四面体のフェイス
+    fn tetrahedron_faces() -> Vec<Face> {
+        let v: Vec<Vertex> = [
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vertex::new(Point3::new(x, y, z)))
+        .collect();
+        let e: Vec<Edge> = [(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)]
+            .iter()
+            .map(|&(a, b): &(usize, usize)| segment_edge(&v[a], &v[b]))
+            .collect();
+        let loops = [
+            [(0, true), (2, true), (1, true)],
+            [(0, false), (4, false), (3, true)],
+            [(1, false), (5, false), (4, true)],
+            [(2, false), (3, false), (5, true)],
+        ];
+        loops
+            .iter()
+            .map(|l| {
+                let edges: Vec<Edge> = l
+                    .iter()
+                    .map(|&(i, rev)| if rev { e[i].reversed() } else { e[i].clone() })
+                    .collect();
+                let p: Vec<Point3> = edges.iter().map(|e| e.start_point()).collect();
+                let plane = Plane::from_points(p[0], p[1], p[2]).unwrap();
+                Face::new(plane, vec![Wire::new(edges).unwrap()]).unwrap()
+            })
+            .collect()
+    }
+
+    /// xy 平面上の点を順に結んだ、頂点を共有する閉じたワイヤ
+    fn polygon(points: &[(f64, f64)]) -> Wire {
+        let v: Vec<Vertex> = points
+            .iter()
+            .map(|&(x, y)| Vertex::new(Point3::new(x, y, 0.0)))
+            .collect();
+        let n = v.len();
+        Wire::new(
+            (0..n)
+                .map(|i| segment_edge(&v[i], &v[(i + 1) % n]))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fix_edges_reorders_and_closes() {
+        let p = |x: f64, y: f64| Point3::new(x, y, 0.0);
+        // 順序・向きがばらばらで、端点に小さな隙間がある正方形と、ごく短いエッジ
+        let edges = vec![
+            Edge::from_points(p(1.0, 0.0), p(1.0, 1.0)).unwrap(),
+            Edge::from_points(p(0.0, 1.0), p(0.0, 1e-6)).unwrap(),
+            Edge::from_points(p(0.0, 1.0), p(1.0, 1.0 + 1e-6)).unwrap(),
+            Edge::from_points(p(0.0, 0.0), p(1.0, 0.0)).unwrap(),
+            Edge::from_points(p(0.0, 0.0), p(0.0, 1e-6)).unwrap(),
+        ];
+        let wire = Wire::fix_edges(edges, 1e-5).unwrap();
+        assert_eq!(wire.len(), 4);
+        assert!(wire.is_closed());
+        let face = Face::new(Plane::xy(), vec![wire]).unwrap();
+        let report = Shape::from(face.fix(1e-5).unwrap()).check();
+        assert!(report.is_valid(), "{:?}", report.issues());
+
+        let far = vec![
+            Edge::from_points(p(0.0, 0.0), p(1.0, 0.0)).unwrap(),
+            Edge::from_points(p(2.0, 0.0), p(3.0, 0.0)).unwrap(),
+        ];
+        assert!(Wire::fix_edges(far, 1e-5).is_err());
+        let tiny = vec![Edge::from_points(p(0.0, 0.0), p(1e-6, 0.0)).unwrap()];
+        assert!(Wire::fix_edges(tiny, 1e-5).is_err());
+
+        // 直す必要のないワイヤはそのまま
+        let square = polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let fixed = square.fix(1e-7).unwrap();
+        assert!(fixed
+            .edges()
+            .iter()
+            .zip(square.edges())
+            .all(|(a, b)| a.is_same(&b)));
+    }
+
+    #[test]
+    fn test_fix_face_wire_order_and_orientation() {
+        // 穴が先で、どちらのワイヤも時計回りのフェイス
+        let outer = polygon(&[(0.0, 0.0), (0.0, 3.0), (4.0, 3.0), (4.0, 0.0)]);
+        let hole = polygon(&[(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (2.0, 1.0)]);
+        let face = Face::new(Plane::xy(), vec![hole.clone(), outer.clone()]).unwrap();
+        assert!(!Shape::from(face.clone()).check().is_valid());
+        let fixed = face.fix(1e-7).unwrap();
+        assert!(Shape::from(fixed.clone()).check().is_valid());
+        assert!(fixed
+            .outer_wire()
+            .unwrap()
+            .edges()
+            .iter()
+            .any(|e| e.is_partner(&outer.edges()[0])));
+        assert_eq!(fixed.inner_wires().len(), 1);
+        // 配置と向きは引き継ぐ
+        let t = crate::Transform::from_translation(crate::Vector3::new(0.0, 0.0, 2.0));
+        let moved = face.moved(&t).reversed().fix(1e-7).unwrap();
+        assert_eq!(moved.orientation(), Orientation::Reversed);
+        assert_eq!(moved.location().transform(), t);
+        assert!(Shape::from(moved).check().is_valid());
+    }
+
+    #[test]
+    fn test_fix_shell_and_solid_orientation() {
+        let mut faces = tetrahedron_faces();
+        faces[2] = faces[2].reversed();
+        let shell = Shell::new(faces).unwrap();
+        let fixed = shell.fix_orientation().unwrap();
+        assert!(Shape::from(fixed.clone()).check().is_valid());
+        // 最初のフェイスの向きに合わせるので、外向きのまま
+        assert!((signed_volume(&fixed) - 1.0 / 6.0).abs() < 1e-12);
+        assert!(fixed.faces()[2].is_same(&shell.faces()[2].reversed()));
+
+        // 裏返しの立体は外向きに直す
+        let inside_out =
+            Shell::new(tetrahedron_faces().iter().map(|f| f.reversed()).collect()).unwrap();
+        assert!((signed_volume(&inside_out) + 1.0 / 6.0).abs() < 1e-12);
+        let solid = Solid::new(vec![inside_out])
+            .unwrap()
+            .fix_orientation()
+            .unwrap();
+        assert!((signed_volume(&solid.shells()[0]) - 1.0 / 6.0).abs() < 1e-12);
+        assert!(Shape::from(solid).check().is_valid());
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::topo::ShapeId;
+use crate::{Edge, Orientation, Result, Shape, ShapeKind, Wire};
+
+/// シェルや立体の境界で、フェイスの使われ方が閉じた多様体になっていないエッジ
+/// （OCCT の `ShapeAnalysis_FreeBounds` 相当）
+#[derive(Debug, Clone)]
+pub struct FreeBounds {
+    free: Vec<Wire>,
+    non_manifold: Vec<Wire>,
+}
+
+impl FreeBounds {
+    /// 1枚のフェイスにしか使われていない自由エッジをつないだワイヤを返す
+    ///
+    /// エッジはフェイスの境界をたどる向きでつなぐ。穴の縁のように一周する場合は閉じたワイヤになる。
+    pub fn free_wires(&self) -> &[Wire] {
+        &self.free
+    }
+
+    /// 3回以上フェイスに使われている非多様体エッジをつないだワイヤを返す
+    pub fn non_manifold_wires(&self) -> &[Wire] {
+        &self.non_manifold
+    }
+
+    /// 自由エッジの本数を返す
+    pub fn free_edge_count(&self) -> usize {
+        self.free.iter().map(Wire::len).sum()
+    }
+
+    /// 非多様体エッジの本数を返す
+    pub fn non_manifold_edge_count(&self) -> usize {
+        self.non_manifold.iter().map(Wire::len).sum()
+    }
+
+    /// 自由エッジも非多様体エッジもなければ `true` を返す
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty() && self.non_manifold.is_empty()
+    }
+}
+
+impl Shape {
+    /// 自由エッジ（1回だけフェイスに使われる）と非多様体エッジ（3回以上使われる）を探し、
+    /// 頂点を共有するものをつないだワイヤとして返す
+    ///
+    /// 継ぎ目のエッジのように1枚のフェイスに2回現れるエッジは2回と数える。退化エッジは数えない。
+    pub fn free_bounds(&self) -> Result<FreeBounds> {
+        let mut uses: HashMap<ShapeId, (usize, Edge)> = HashMap::new();
+        let mut order = Vec::new();
+        for face in self.explore(ShapeKind::Face).unique() {
+            for edge in face.explore(ShapeKind::Edge) {
+                let Ok(edge) = Edge::try_from(edge) else {
+                    continue;
+                };
+                if edge.is_degenerate() {
+                    continue;
+                }
+                let entry = uses.entry(edge.id()).or_insert_with(|| {
+                    order.push(edge.id());
+                    (0, edge)
+                });
+                entry.0 += 1;
+            }
+        }
+        let pick = |keep: fn(usize) -> bool| -> Vec<Edge> {
+            order
+                .iter()
+                .filter(|id| keep(uses[*id].0))
+                .map(|id| uses[id].1.clone())
+                .collect()
+        };
+        let free = pick(|n| n == 1);
+        let non_manifold: Vec<Edge> = pick(|n| n > 2)
+            .into_iter()
+            .map(|e| e.oriented(Orientation::Forward))
+            .collect();
+        Ok(FreeBounds {
+            free: connect_edges(free)?,
+            non_manifold: connect_edges(non_manifold)?,
+        })
+    }
+}
+
+/// 頂点を共有するエッジを順につなぎ、ワイヤの列にする
+///
+/// 3本以上のエッジが集まる頂点では、見つかった順に1本を選んでつなぐ。
+fn connect_edges(edges: Vec<Edge>) -> Result<Vec<Wire>> {
+    // 頂点からその頂点を端に持つエッジの番号への対応
+    let mut at_vertex: HashMap<ShapeId, Vec<usize>> = HashMap::new();
+    for (i, e) in edges.iter().enumerate() {
+        at_vertex.entry(e.start_vertex().id()).or_default().push(i);
+        if !e.is_closed() {
+            at_vertex.entry(e.end_vertex().id()).or_default().push(i);
+        }
+    }
+    let mut used = HashSet::new();
+    // 頂点 `v` を端に持つ未使用のエッジを、`v` から始まる向きで取り出す
+    let mut take = |v: ShapeId, from_start: bool, used: &mut HashSet<usize>| -> Option<Edge> {
+        let candidates = at_vertex.get_mut(&v)?;
+        let k = candidates.iter().position(|i| !used.contains(i))?;
+        let i = candidates.remove(k);
+        used.insert(i);
+        let e = &edges[i];
+        let starts_here = e.start_vertex().id() == v;
+        Some(if starts_here == from_start {
+            e.clone()
+        } else {
+            e.reversed()
+        })
+    };
+    let mut wires = Vec::new();
+    for (i, edge) in edges.iter().enumerate() {
+        if !used.insert(i) {
+            continue;
+        }
+        let mut chain = VecDeque::from([edge.clone()]);
+        loop {
+            let back = &chain[chain.len() - 1];
+            if back.end_vertex().is_same(&chain[0].start_vertex()) {
+                break;
+            }
+            match take(back.end_vertex().id(), true, &mut used) {
+                Some(e) => chain.push_back(e),
+                None => break,
+            }
+        }
+        loop {
+            let front = &chain[0];
+            if front
+                .start_vertex()
+                .is_same(&chain[chain.len() - 1].end_vertex())
+            {
+                break;
+            }
+            match take(front.start_vertex().id(), false, &mut used) {
+                Some(e) => chain.push_front(e),
+                None => break,
+            }
+        }
+        wires.push(Wire::new(chain.into())?);
+    }
+    Ok(wires)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sew, Face, Plane, Point3, Shell, Vertex};
+
+    /// 別々に作った立方体のフェイス（`skip` 番目の面を除く）
+    fn open_box(skip: usize) -> Vec<Face> {
+        let corner =
+            |i: usize| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64);
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        quads
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| k != skip)
+            .map(|(_, q)| {
+                let p: Vec<Point3> = q.iter().map(|&i| corner(i)).collect();
+                let edges = (0..4)
+                    .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+                    .collect();
+                Face::from_planar_wires(Wire::from_edges(edges, 1e-9).unwrap(), Vec::new(), 1e-9)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_free_edges_of_open_box() {
+        let sewing = sew(open_box(1), 1e-7).unwrap();
+        let shell = Shape::from(sewing.shells()[0].clone());
+        let bounds = shell.free_bounds().unwrap();
+        assert_eq!(bounds.free_wires().len(), 1);
+        let wire = &bounds.free_wires()[0];
+        assert_eq!(wire.len(), 4);
+        assert!(wire.is_closed());
+        assert!(wire
+            .edges()
+            .iter()
+            .all(|e| (e.start_point().z - 1.0).abs() < 1e-12));
+        assert_eq!(bounds.non_manifold_edge_count(), 0);
+
+        let sewing = sew(open_box(6), 1e-7).unwrap();
+        assert!(Shape::from(sewing.solids().unwrap()[0].clone())
+            .free_bounds()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_non_manifold_edge() {
+        // 1本のエッジを3枚のフェイスが共有する
+        let a = Vertex::new(Point3::new(0.0, 0.0, 0.0));
+        let b = Vertex::new(Point3::new(0.0, 0.0, 1.0));
+        let line = crate::Line::from_points(a.point(), b.point()).unwrap();
+        let shared = Edge::new(line, (0.0, 1.0), a.clone(), b.clone()).unwrap();
+        let fins: Vec<Face> = [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]
+            .iter()
+            .map(|&(x, y)| {
+                let c = Point3::new(x, y, 1.0);
+                let d = Point3::new(x, y, 0.0);
+                let (vc, vd) = (Vertex::new(c), Vertex::new(d));
+                let edge = |p: &Vertex, q: &Vertex| {
+                    let line = crate::Line::from_points(p.point(), q.point()).unwrap();
+                    Edge::new(line, (0.0, 1.0), p.clone(), q.clone()).unwrap()
+                };
+                let wire = Wire::new(vec![
+                    shared.clone(),
+                    edge(&b, &vc),
+                    edge(&vc, &vd),
+                    edge(&vd, &a),
+                ])
+                .unwrap();
+                let plane = Plane::from_points(a.point(), b.point(), c).unwrap();
+                Face::new(plane, vec![wire]).unwrap()
+            })
+            .collect();
+        let shell = Shape::from(Shell::new(fins).unwrap());
+        let bounds = shell.free_bounds().unwrap();
+        assert_eq!(bounds.non_manifold_wires().len(), 1);
+        assert!(bounds.non_manifold_wires()[0].edges()[0].is_same(&shared));
+        assert_eq!(bounds.free_edge_count(), 9);
+        for wire in bounds.free_wires() {
+            assert!(wire.edges().iter().all(|e| !e.is_same(&shared)));
+        }
+    }
+}
@@ -0,0 +1,1697 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::bspline_fit::solve_dense;
+use crate::classify::{
+    classify_point, curve_period, curve_surface_roots, edge_distance, sample_parameters,
+    signed_distance, winding_number, FaceClassifier,
+};
+use crate::extrude::translated;
+use crate::projection::refine_surface_projection;
+use crate::shape_builder::{signed_area, union_find_root};
+use crate::surface_fit::{averaged_knots, interpolation_matrix};
+use crate::topo::ShapeId;
+use crate::{
+    intersect_surfaces, precision, AnalyticSurface, BSplineCurve, BSplineCurve2, BoundingBox,
+    Curve3, Dir, Edge, Face, GeomCurve, GeomSurface, OcctKrsError, Orientation, Plane, Point3,
+    PointClassification, Result, Surface, SurfaceIntersection, SurfaceProjection, Vector2, Vector3,
+    Vertex, Wire,
+};
+
+/// 交線を追跡するときの許容誤差
+const MARCH_TOLERANCE: f64 = 1e-6;
+
+/// エッジが曲面を横切る点を探すときの分割数
+const CROSSING_SAMPLES: usize = 64;
+
+/// pcurve を補間で求めるときの最大の分割数
+const MAX_PCURVE_SAMPLES: usize = 256;
+
+/// 分割したフェイスと、他の引数の立体との位置関係
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum State {
+    /// 立体の内側
+    In,
+    /// 立体の外側
+    Out,
+    /// 立体の境界のフェイス上（`same` は法線が同じ向きなら `true`）
+    On { same: bool },
+}
+
+/// 分割したフェイス
+#[derive(Debug, Clone)]
+pub(crate) struct Fragment {
+    /// 分割したフェイス（元のフェイスと同じ向き）
+    pub(crate) face: Face,
+    /// 元のフェイスを含む引数の番号
+    pub(crate) argument: usize,
+    /// 引数ごとの位置関係（自身の引数に対しては `Out`）
+    pub(crate) states: Vec<State>,
+    /// 位置関係を調べたフェイスの内部の点
+    pub(crate) point: Point3,
+}
+
+/// 引数ごとのフェイスの集まりを、互いの交線で分割した結果（OCCT の `BOPAlgo_Builder` 相当）
+pub(crate) struct GeneralFuse {
+    /// 分割したフェイス
+    pub(crate) fragments: Vec<Fragment>,
+    /// 2つ以上の引数の境界に乗っている頂点があれば `true`
+    pub(crate) touching: bool,
+}
+
+impl GeneralFuse {
+    /// 引数ごとのフェイス（立体の境界なら外向き）を他の引数のフェイスとの交線で分割し、
+    /// 分割したフェイスと他の引数との位置関係を調べる
+    pub(crate) fn new(arguments: &[Vec<Face>]) -> Result<Self> {
+        let mut builder = Builder::new(arguments)?;
+        builder.add_triple_points();
+        builder.split_edges()?;
+        builder.intersect_faces()?;
+        let mut fragments = Vec::new();
+        let mut samples = Vec::new();
+        for f in 0..builder.faces.len() {
+            for (face, sample) in builder.split_face(f)? {
+                fragments.push(Fragment {
+                    face,
+                    argument: builder.faces[f].argument,
+                    states: Vec::new(),
+                    point: sample.0,
+                });
+                samples.push(sample);
+            }
+        }
+        builder.classify(&mut fragments, &samples);
+        Ok(Self {
+            fragments,
+            touching: builder.touching(),
+        })
+    }
+
+    /// 引数どうしのフェイスの交線のエッジを返す（フェイスの分割と分類は行わない）
+    ///
+    /// 一方の境界のエッジが他方のフェイスに乗っている部分や、曲面が一致するフェイスの境界も含める。
+    pub(crate) fn sections(arguments: &[Vec<Face>]) -> Result<Vec<Edge>> {
+        let mut builder = Builder::new(arguments)?;
+        builder.add_triple_points();
+        builder.split_edges()?;
+        builder.intersect_faces()?;
+        let mut seen = HashSet::new();
+        Ok(builder
+            .faces
+            .into_iter()
+            .flat_map(|f| f.cuts)
+            .filter(|e| !e.is_degenerate() && seen.insert(e.id()))
+            .map(|e| e.oriented(Orientation::Forward))
+            .collect())
+    }
+}
+
+/// 分割に使う頂点（許容誤差以内の点は同じ頂点にまとめる）
+struct VertexPool {
+    vertices: Vec<Vertex>,
+    /// 元の頂点から番号への対応
+    originals: HashMap<ShapeId, usize>,
+}
+
+impl VertexPool {
+    /// 点 `p` の頂点の番号を返す（近くに頂点がなければ追加する）
+    fn add(&mut self, p: Point3, tolerance: f64) -> usize {
+        if let Some(i) = self
+            .vertices
+            .iter()
+            .position(|v| v.point().distance(p) <= tolerance)
+        {
+            return i;
+        }
+        self.vertices.push(Vertex::new(p));
+        self.vertices.len() - 1
+    }
+
+    /// 元の頂点に対応する頂点の番号を返す（他の頂点と一致しなければ元の頂点をそのまま使う）
+    fn original(&mut self, vertex: &Vertex, tolerance: f64) -> usize {
+        if let Some(&i) = self.originals.get(&vertex.id()) {
+            return i;
+        }
+        let p = vertex.point();
+        let i = match self
+            .vertices
+            .iter()
+            .position(|v| v.point().distance(p) <= tolerance.max(vertex.tolerance()))
+        {
+            Some(i) => i,
+            None => {
+                self.vertices.push(vertex.oriented(Orientation::Forward));
+                self.vertices.len() - 1
+            }
+        };
+        self.originals.insert(vertex.id(), i);
+        i
+    }
+
+    fn point(&self, i: usize) -> Point3 {
+        self.vertices[i].point()
+    }
+}
+
+/// 分割する元のエッジ
+struct SplitEdge {
+    /// 配置を適用した曲線（退化エッジでは `None`）
+    curve: Option<GeomCurve>,
+    range: (f64, f64),
+    /// 始点と終点の頂点の番号
+    ends: (usize, usize),
+    argument: usize,
+    /// 区間の内側で分割するパラメータと頂点の番号
+    splits: Vec<(f64, usize)>,
+    /// 分割したエッジ（曲線の向きに並べ、それぞれ曲線の向きにたどる）
+    pieces: Vec<Edge>,
+    /// 元の曲線の区間をそのまま使ったエッジでは、その区間（元の pcurve を切り出せる）
+    ranges: Vec<Option<(f64, f64)>>,
+}
+
+impl SplitEdge {
+    fn split_at(&mut self, t: f64, vertex: usize) {
+        if vertex != self.ends.0
+            && vertex != self.ends.1
+            && !self.splits.iter().any(|&(_, v)| v == vertex)
+        {
+            self.splits.push((t, vertex));
+        }
+    }
+}
+
+/// 分割する元のフェイス
+struct SourceFace {
+    argument: usize,
+    /// 引数の中での番号
+    index: usize,
+    /// 引数の中での向き
+    orientation: Orientation,
+    /// 境界のエッジ（`Builder::edges` の番号）
+    edges: Vec<usize>,
+    /// フェイスを分割するエッジ（交線や、曲面が一致するフェイスの境界）
+    cuts: Vec<Edge>,
+}
+
+/// 交線（解析的な曲線、または追跡で求めた折れ線）
+enum Section {
+    Curve(GeomCurve),
+    /// 折れ線の頂点（閉じている場合は最後に始点を繰り返す）。パラメータは辺の番号に対応する
+    Walked(Vec<Point3>, bool),
+}
+
+impl Section {
+    fn point_at(&self, s: f64) -> Point3 {
+        match self {
+            Section::Curve(c) => c.point_at(s),
+            Section::Walked(points, closed) => {
+                let n = points.len() - 1;
+                let s = if *closed {
+                    s.rem_euclid(n as f64)
+                } else {
+                    s.clamp(0.0, n as f64)
+                };
+                let i = (s.floor() as usize).min(n - 1);
+                let f = s - i as f64;
+                points[i] + (points[i + 1] - points[i]) * f
+            }
+        }
+    }
+
+    /// 閉じた交線の周期
+    fn period(&self) -> Option<f64> {
+        match self {
+            Section::Curve(c) => curve_period(c),
+            Section::Walked(points, true) => Some((points.len() - 1) as f64),
+            Section::Walked(_, false) => None,
+        }
+    }
+
+    /// 交線上の点とみなす距離
+    fn tolerance(&self, tolerance: f64) -> f64 {
+        match self {
+            Section::Curve(_) => tolerance,
+            Section::Walked(..) => tolerance.max(10.0 * MARCH_TOLERANCE),
+        }
+    }
+
+    /// 点が交線から `tolerance` 以内にあれば、最も近い点のパラメータを返す（閉じた交線では周期内）
+    fn parameter_of(&self, p: Point3, tolerance: f64) -> Option<f64> {
+        match self {
+            Section::Curve(c) => {
+                let period = curve_period(c);
+                c.project(p)
+                    .into_iter()
+                    .filter(|q| q.distance <= tolerance)
+                    .min_by(|a, b| a.distance.total_cmp(&b.distance))
+                    .map(|q| match period {
+                        Some(period) => q.parameter.rem_euclid(period),
+                        None => q.parameter,
+                    })
+            }
+            Section::Walked(points, _) => {
+                let mut best: Option<(f64, f64)> = None;
+                for (i, w) in points.windows(2).enumerate() {
+                    let v = w[1] - w[0];
+                    let len2 = v.dot(v);
+                    let f = if len2 > 0.0 {
+                        ((p - w[0]).dot(v) / len2).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    let d = (w[0] + v * f).distance(p);
+                    if best.is_none_or(|(_, b)| d < b) {
+                        best = Some((i as f64 + f, d));
+                    }
+                }
+                let (s, d) = best?;
+                let s = match self.period() {
+                    Some(period) => s.rem_euclid(period),
+                    None => s,
+                };
+                (d <= tolerance).then_some(s)
+            }
+        }
+    }
+
+    /// パラメータ `[sa, sb]` の部分を、両端が `start`・`end` の点になる曲線とその区間で返す
+    fn curve_between(
+        &self,
+        (sa, sb): (f64, f64),
+        start: Point3,
+        end: Point3,
+    ) -> Result<(GeomCurve, (f64, f64))> {
+        let (points, closed) = match self {
+            Section::Curve(c) => return Ok((c.clone(), (sa, sb))),
+            Section::Walked(points, closed) => (points, *closed),
+        };
+        let n = points.len() - 1;
+        let mut polyline = vec![start];
+        let first = sa.floor() as i64 + 1;
+        let last = sb.ceil() as i64 - 1;
+        for k in first..=last {
+            let i = if closed {
+                k.rem_euclid(n as i64) as usize
+            } else if k < 0 || k as usize > n {
+                continue;
+            } else {
+                k as usize
+            };
+            let p = points[i];
+            if p.distance(start) > MARCH_TOLERANCE && p.distance(end) > MARCH_TOLERANCE {
+                polyline.push(p);
+            }
+        }
+        polyline.push(end);
+        // パラメータは弦長にする
+        let mut knots = vec![0.0, 0.0];
+        let mut length = 0.0;
+        for w in polyline.windows(2) {
+            length += w[0].distance(w[1]).max(f64::EPSILON);
+            knots.push(length);
+        }
+        knots.push(length);
+        let curve = BSplineCurve::from_flat_knots(1, polyline, knots)?;
+        Ok((GeomCurve::BSpline(curve), (0.0, length)))
+    }
+}
+
+/// 交線の追跡のために、フェイスの範囲の近くに制限した曲面
+struct Window<'a> {
+    surface: &'a GeomSurface,
+    u: (f64, f64),
+    v: (f64, f64),
+}
+
+impl<'a> Window<'a> {
+    /// 閉じていない方向をフェイスの範囲に1割の余裕を持たせて制限する
+    fn new(classifier: &'a FaceClassifier) -> Self {
+        let surface = &classifier.surface;
+        let (lo, hi) = classifier.uv_box();
+        let limit = |range: (f64, f64), closed: bool, a: f64, b: f64| {
+            if closed || !(a.is_finite() && b.is_finite()) {
+                return range;
+            }
+            let margin = 0.1 * (b - a) + 1e-3;
+            ((a - margin).max(range.0), (b + margin).min(range.1))
+        };
+        Self {
+            surface,
+            u: limit(surface.u_range(), surface.is_u_closed(), lo.x, hi.x),
+            v: limit(surface.v_range(), surface.is_v_closed(), lo.y, hi.y),
+        }
+    }
+}
+
+impl Surface for Window<'_> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.surface.point_at(u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        self.surface.derivative_u_at(u, v)
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        self.surface.derivative_v_at(u, v)
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        self.surface.second_derivatives_at(u, v)
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        self.surface.normal_at(u, v)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        self.u
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        self.v
+    }
+
+    fn is_u_closed(&self) -> bool {
+        self.surface.is_u_closed()
+    }
+
+    fn is_v_closed(&self) -> bool {
+        self.surface.is_v_closed()
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        self.surface.project(p)
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        self.surface.as_analytic()
+    }
+}
+
+/// パラメータ空間でたどる向き付きのエッジ
+struct HalfEdge {
+    /// たどる向きにしたエッジ
+    edge: Edge,
+    pcurve: Option<BSplineCurve2>,
+    /// パラメータ空間の点列（たどる順）
+    points: Vec<Vector2>,
+    /// 切断のエッジなら `true`（逆向きが隣に並ぶ）
+    cut: bool,
+    from: usize,
+    to: usize,
+}
+
+impl HalfEdge {
+    /// 始点での向き
+    fn start_direction(&self) -> Vector2 {
+        direction(self.points.iter().copied())
+    }
+
+    /// 終点から逆にたどる向き
+    fn end_direction(&self) -> Vector2 {
+        direction(self.points.iter().rev().copied())
+    }
+}
+
+/// 点列の最初の点から、最初の離れた点への向き
+fn direction(mut points: impl Iterator<Item = Vector2>) -> Vector2 {
+    let Some(first) = points.next() else {
+        return Vector2::new(0.0, 0.0);
+    };
+    let mut last = first;
+    for p in points {
+        last = p;
+        if (p - first).length() > 1e-9 {
+            break;
+        }
+    }
+    (last - first).normalize_or(Vector2::new(0.0, 0.0))
+}
+
+/// 分割したフェイスの内側の点と、その点での法線（フェイスの向き）
+type Sample = (Point3, Option<Dir>);
+
+/// 分割したエッジと交線のエッジ（両端の頂点の番号の組ごと、作った引数の番号と共に。交線は `None`）
+type Registry = HashMap<(usize, usize), Vec<(Edge, Option<usize>)>>;
+
+/// 交線を区切る点のパラメータと頂点の番号（閉じた交線に頂点がなければ `None`）
+type Break = (f64, Option<usize>);
+
+struct Builder {
+    tolerance: f64,
+    /// 引数ごとのフェイスの分類器
+    classifiers: Vec<Vec<FaceClassifier>>,
+    faces: Vec<SourceFace>,
+    /// 引数ごとの最初のフェイスの番号
+    offsets: Vec<usize>,
+    pool: VertexPool,
+    edges: Vec<SplitEdge>,
+    edge_index: HashMap<ShapeId, usize>,
+    registry: Registry,
+    /// 当てはめた pcurve（フェイスの番号とエッジごと）
+    pcurves: HashMap<(usize, ShapeId), BSplineCurve2>,
+}
+
+impl Builder {
+    fn new(arguments: &[Vec<Face>]) -> Result<Self> {
+        let tolerance = precision::confusion();
+        let mut builder = Self {
+            tolerance,
+            classifiers: Vec::new(),
+            faces: Vec::new(),
+            offsets: Vec::new(),
+            pool: VertexPool {
+                vertices: Vec::new(),
+                originals: HashMap::new(),
+            },
+            edges: Vec::new(),
+            edge_index: HashMap::new(),
+            registry: HashMap::new(),
+            pcurves: HashMap::new(),
+        };
+        for (argument, faces) in arguments.iter().enumerate() {
+            builder.offsets.push(builder.faces.len());
+            let mut classifiers = Vec::new();
+            for (index, face) in faces.iter().enumerate() {
+                let classifier = FaceClassifier::new(face)?;
+                let mut edges = Vec::new();
+                for edge in classifier.face.wires().iter().flat_map(Wire::edges) {
+                    let e = match builder.edge_index.get(&edge.id()) {
+                        Some(&e) => e,
+                        None => {
+                            let edge = edge.oriented(Orientation::Forward);
+                            let start = builder.pool.original(&edge.start_vertex(), tolerance);
+                            let end = builder.pool.original(&edge.end_vertex(), tolerance);
+                            builder.edges.push(SplitEdge {
+                                curve: edge.located_geometry().map(|c| c.into_owned()),
+                                range: edge.range(),
+                                ends: (start, end),
+                                argument,
+                                splits: Vec::new(),
+                                pieces: Vec::new(),
+                                ranges: Vec::new(),
+                            });
+                            builder
+                                .edge_index
+                                .insert(edge.id(), builder.edges.len() - 1);
+                            builder.edges.len() - 1
+                        }
+                    };
+                    if !edges.contains(&e) {
+                        edges.push(e);
+                    }
+                }
+                builder.faces.push(SourceFace {
+                    argument,
+                    index,
+                    orientation: face.orientation(),
+                    edges,
+                    cuts: Vec::new(),
+                });
+                classifiers.push(classifier);
+            }
+            builder.classifiers.push(classifiers);
+        }
+        Ok(builder)
+    }
+
+    fn classifier(&self, f: usize) -> &FaceClassifier {
+        let face = &self.faces[f];
+        &self.classifiers[face.argument][face.index]
+    }
+
+    /// 3つの異なる引数の平面のフェイスが1点で交わる点を頂点に加える
+    ///
+    /// 交線どうしが既存のエッジから離れた点で交わる場合に、そこで交線を区切るため。
+    fn add_triple_points(&mut self) {
+        let planes: Vec<(usize, Plane)> = (0..self.faces.len())
+            .filter_map(|f| match self.classifier(f).surface {
+                GeomSurface::Plane(plane) => Some((f, plane)),
+                _ => None,
+            })
+            .collect();
+        let mut points = Vec::new();
+        for (i, &(fa, a)) in planes.iter().enumerate() {
+            for (j, &(fb, b)) in planes.iter().enumerate().skip(i + 1) {
+                let (ca, cb) = (self.classifier(fa), self.classifier(fb));
+                if self.faces[fa].argument == self.faces[fb].argument
+                    || !ca.bounding_box.intersects(&cb.bounding_box)
+                {
+                    continue;
+                }
+                for &(fc, c) in &planes[j + 1..] {
+                    let cc = self.classifier(fc);
+                    let argument = self.faces[fc].argument;
+                    if argument == self.faces[fa].argument
+                        || argument == self.faces[fb].argument
+                        || !cc.bounding_box.intersects(&ca.bounding_box)
+                        || !cc.bounding_box.intersects(&cb.bounding_box)
+                    {
+                        continue;
+                    }
+                    let Some(p) = plane_triple_point(&a, &b, &c) else {
+                        continue;
+                    };
+                    if [ca, cb, cc]
+                        .iter()
+                        .all(|k| k.classify(p, self.tolerance) != PointClassification::Outside)
+                    {
+                        points.push(p);
+                    }
+                }
+            }
+        }
+        for p in points {
+            self.pool.add(p, self.tolerance);
+        }
+    }
+
+    /// エッジを他の引数のフェイスを横切る点と、内側に乗っている頂点で分割する
+    fn split_edges(&mut self) -> Result<()> {
+        let tol = self.tolerance;
+        let boxes: Vec<Option<BoundingBox>> = self
+            .edges
+            .iter()
+            .map(|e| e.curve.as_ref().and_then(|c| curve_box(c, e.range, tol)))
+            .collect();
+        for (e, &bbox) in boxes.iter().enumerate() {
+            let (Some(curve), Some(bbox)) = (&self.edges[e].curve, bbox) else {
+                continue;
+            };
+            let mut hits = Vec::new();
+            for f in 0..self.faces.len() {
+                if self.faces[f].argument == self.edges[e].argument {
+                    continue;
+                }
+                let classifier = self.classifier(f);
+                if !classifier.bounding_box.intersects(&bbox) {
+                    continue;
+                }
+                let roots = curve_surface_roots(
+                    curve,
+                    self.edges[e].range,
+                    &classifier.surface,
+                    CROSSING_SAMPLES,
+                    tol,
+                );
+                for t in roots {
+                    let p = curve.point_at(t);
+                    if classifier.classify(p, tol) != PointClassification::Outside {
+                        hits.push((t, p));
+                    }
+                }
+            }
+            for (t, p) in hits {
+                let v = self.pool.add(p, tol);
+                self.edges[e].split_at(t, v);
+            }
+        }
+        for (e, &bbox) in boxes.iter().enumerate() {
+            let (Some(curve), Some(bbox)) = (&self.edges[e].curve, bbox) else {
+                continue;
+            };
+            let mut hits = Vec::new();
+            for v in 0..self.pool.vertices.len() {
+                let p = self.pool.point(v);
+                if bbox.contains(p) {
+                    if let Some(t) = parameter_on(curve, self.edges[e].range, p, tol) {
+                        hits.push((t, v));
+                    }
+                }
+            }
+            for (t, v) in hits {
+                self.edges[e].split_at(t, v);
+            }
+        }
+        for e in 0..self.edges.len() {
+            self.build_pieces(e)?;
+        }
+        Ok(())
+    }
+
+    /// 分割する点でエッジを区切る（他の引数の一致するエッジがあればそれを使う）
+    fn build_pieces(&mut self, e: usize) -> Result<()> {
+        let split = &mut self.edges[e];
+        split.splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (range, ends, argument) = (split.range, split.ends, split.argument);
+        let Some(curve) = split.curve.clone() else {
+            split.pieces = vec![Edge::degenerate(self.pool.vertices[ends.0].clone(), range)?];
+            split.ranges = vec![Some(range)];
+            return Ok(());
+        };
+        let mut cuts = vec![(range.0, ends.0)];
+        cuts.extend(split.splits.iter().copied());
+        cuts.push((range.1, ends.1));
+        let mut pieces = Vec::new();
+        let mut ranges = Vec::new();
+        for w in cuts.windows(2) {
+            let ((a, va), (b, vb)) = (w[0], w[1]);
+            let probes = [
+                curve.point_at(0.5 * (a + b)),
+                curve.point_at(0.75 * a + 0.25 * b),
+            ];
+            let tangent = curve.derivative_at(0.5 * (a + b));
+            if let Some(edge) =
+                self.find_edge((va, vb), probes, tangent, Some(argument), self.tolerance)
+            {
+                pieces.push(edge);
+                ranges.push(None);
+                continue;
+            }
+            let edge = Edge::new(
+                curve.clone(),
+                (a, b),
+                self.pool.vertices[va].clone(),
+                self.pool.vertices[vb].clone(),
+            )?;
+            self.register((va, vb), edge.clone(), Some(argument));
+            pieces.push(edge);
+            ranges.push(Some((a, b)));
+        }
+        self.edges[e].pieces = pieces;
+        self.edges[e].ranges = ranges;
+        Ok(())
+    }
+
+    fn register(&mut self, (va, vb): (usize, usize), edge: Edge, argument: Option<usize>) {
+        self.registry
+            .entry((va.min(vb), va.max(vb)))
+            .or_default()
+            .push((edge, argument));
+    }
+
+    /// 頂点 `va` から `vb` へ、点 `probes` を通るエッジがあればその向きにして返す
+    ///
+    /// 閉じたエッジの向きは `tangent` に合わせる。`skip` の引数が作ったエッジは除く。
+    fn find_edge(
+        &self,
+        (va, vb): (usize, usize),
+        probes: [Point3; 2],
+        tangent: Vector3,
+        skip: Option<usize>,
+        tolerance: f64,
+    ) -> Option<Edge> {
+        let candidates = self.registry.get(&(va.min(vb), va.max(vb)))?;
+        let (edge, _) = candidates.iter().find(|(edge, argument)| {
+            (skip.is_none() || *argument != skip)
+                && probes.iter().all(|&p| edge_distance(edge, p) <= tolerance)
+        })?;
+        let forward = if va != vb {
+            edge.start_vertex().is_same(&self.pool.vertices[va])
+        } else {
+            edge_tangent(edge, probes[0]).dot(tangent) > 0.0
+        };
+        Some(if forward {
+            edge.clone()
+        } else {
+            edge.reversed()
+        })
+    }
+
+    /// 異なる引数のフェイスの組ごとに交線を求め、フェイスを分割するエッジを加える
+    fn intersect_faces(&mut self) -> Result<()> {
+        let n = self.faces.len();
+        for fa in 0..n {
+            for fb in fa + 1..n {
+                if self.faces[fa].argument == self.faces[fb].argument {
+                    continue;
+                }
+                let (ca, cb) = (self.classifier(fa), self.classifier(fb));
+                if !ca.bounding_box.intersects(&cb.bounding_box) {
+                    continue;
+                }
+                let intersections = if same_surface(&ca.surface, &cb.surface, self.tolerance) {
+                    vec![SurfaceIntersection::Coincident]
+                } else {
+                    intersect_surfaces(&Window::new(ca), &Window::new(cb), MARCH_TOLERANCE)?
+                };
+                for intersection in intersections {
+                    let section = match intersection {
+                        SurfaceIntersection::Line(l) => Section::Curve(GeomCurve::Line(l)),
+                        SurfaceIntersection::Circle(c) => Section::Curve(GeomCurve::Circle(c)),
+                        SurfaceIntersection::Ellipse(e) => Section::Curve(GeomCurve::Ellipse(e)),
+                        SurfaceIntersection::Walked(w) => {
+                            let mut points = w.curve.points().to_vec();
+                            let closed = w.curve.is_closed();
+                            if closed {
+                                points.push(points[0]);
+                            }
+                            Section::Walked(points, closed)
+                        }
+                        SurfaceIntersection::Coincident => {
+                            self.overlap(fa, fb);
+                            self.overlap(fb, fa);
+                            continue;
+                        }
+                        SurfaceIntersection::Point(_) => continue,
+                    };
+                    self.add_section(fa, fb, &section)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 曲面が一致するフェイスの組で、`fb` の境界のうち `fa` の内側を通るものを `fa` の切断に加える
+    fn overlap(&mut self, fa: usize, fb: usize) {
+        let classifier = self.classifier(fa);
+        let cuts: Vec<Edge> = self.faces[fb]
+            .edges
+            .iter()
+            .flat_map(|&e| &self.edges[e].pieces)
+            .filter(|piece| {
+                !piece.is_degenerate()
+                    && classifier.classify(midpoint(piece), self.tolerance)
+                        == PointClassification::Inside
+            })
+            .cloned()
+            .collect();
+        self.faces[fa].cuts.extend(cuts);
+    }
+
+    /// 交線を頂点で区切り、両フェイスの内側を通る部分を切断に加える
+    ///
+    /// 区切る頂点には両フェイスの境界の頂点のほか、3つ目の引数の頂点も含める
+    /// （同じ直線上に重なる交線を同じ点で区切り、エッジを共有するため）。
+    fn add_section(&mut self, fa: usize, fb: usize, section: &Section) -> Result<()> {
+        let tol = section.tolerance(self.tolerance);
+        let (ba, bb) = (
+            self.classifier(fa).bounding_box,
+            self.classifier(fb).bounding_box,
+        );
+        let mut breaks: Vec<(f64, usize)> = Vec::new();
+        for v in 0..self.pool.vertices.len() {
+            let p = self.pool.point(v);
+            if !(ba.contains(p) && bb.contains(p)) {
+                continue;
+            }
+            if let Some(s) = section.parameter_of(p, tol) {
+                breaks.push((s, v));
+            }
+        }
+        breaks.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut intervals: Vec<(Break, Break)> = breaks
+            .windows(2)
+            .map(|w| ((w[0].0, Some(w[0].1)), (w[1].0, Some(w[1].1))))
+            .collect();
+        match (section.period(), breaks.first(), breaks.last()) {
+            (Some(period), Some(&(s0, v0)), Some(&(s1, v1))) => {
+                intervals.push(((s1, Some(v1)), (s0 + period, Some(v0))));
+            }
+            (Some(period), None, _) => intervals.push(((0.0, None), (period, None))),
+            _ => {}
+        }
+        for ((sa, va), (sb, vb)) in intervals {
+            if sb - sa <= 1e-9 * (1.0 + sa.abs()) {
+                continue;
+            }
+            let mid = section.point_at(0.5 * (sa + sb));
+            let ka = self.classifier(fa).classify(mid, tol);
+            let kb = self.classifier(fb).classify(mid, tol);
+            use PointClassification::{Inside, OnBoundary};
+            let target = match (ka, kb) {
+                (Inside, Inside) => {
+                    let (va, vb) = match (va, vb) {
+                        (Some(va), Some(vb)) => (va, vb),
+                        _ => {
+                            let v = self.pool.add(section.point_at(sa), self.tolerance);
+                            (v, v)
+                        }
+                    };
+                    let edge = self.section_edge(section, fa, fb, (sa, va), (sb, vb))?;
+                    self.faces[fa].cuts.push(edge.clone());
+                    self.faces[fb].cuts.push(edge);
+                    continue;
+                }
+                (Inside, OnBoundary) => fa,
+                (OnBoundary, Inside) => fb,
+                _ => continue,
+            };
+            // 一方の境界に沿う部分は、その境界のエッジでもう一方を分割する
+            let (Some(va), Some(vb)) = (va, vb) else {
+                continue;
+            };
+            let probes = [mid, section.point_at(0.75 * sa + 0.25 * sb)];
+            if let Some(edge) = self.find_edge(
+                (va, vb),
+                probes,
+                section_tangent(section, 0.5 * (sa + sb)),
+                None,
+                tol,
+            ) {
+                self.faces[target].cuts.push(edge);
+            }
+        }
+        Ok(())
+    }
+
+    /// 交線のパラメータ `[sa, sb]` の部分をエッジにし、両フェイスの pcurve を求める
+    fn section_edge(
+        &mut self,
+        section: &Section,
+        fa: usize,
+        fb: usize,
+        (sa, va): (f64, usize),
+        (sb, vb): (f64, usize),
+    ) -> Result<Edge> {
+        let tol = section.tolerance(self.tolerance);
+        let probes = [
+            section.point_at(0.5 * (sa + sb)),
+            section.point_at(0.75 * sa + 0.25 * sb),
+        ];
+        let tangent = section_tangent(section, 0.5 * (sa + sb));
+        if let Some(edge) = self.find_edge((va, vb), probes, tangent, None, tol) {
+            return Ok(edge);
+        }
+        let (curve, range) =
+            section.curve_between((sa, sb), self.pool.point(va), self.pool.point(vb))?;
+        // 交線の近似の誤差と pcurve の誤差をエッジの許容誤差にする
+        let mut deviation: f64 = 0.0;
+        let mut fitted = Vec::new();
+        for f in [fa, fb] {
+            let classifier = self.classifier(f);
+            deviation = (0..=32)
+                .map(|k| {
+                    let t = range.0 + (range.1 - range.0) * (k as f64 + 0.5) / 33.0;
+                    signed_distance(&classifier.surface, curve.point_at(t)).abs()
+                })
+                .fold(deviation, f64::max);
+            if !matches!(classifier.surface, GeomSurface::Plane(_)) {
+                let (pcurve, error) = fit_pcurve(classifier, &curve, range)?;
+                deviation = deviation.max(error);
+                fitted.push((f, pcurve));
+            }
+        }
+        let mut edge = Edge::new(
+            curve,
+            range,
+            self.pool.vertices[va].clone(),
+            self.pool.vertices[vb].clone(),
+        )?;
+        if deviation > 0.5 * self.tolerance {
+            edge = edge.with_tolerance(2.0 * deviation)?;
+        }
+        for (f, pcurve) in fitted {
+            self.pcurves.insert((f, edge.id()), pcurve);
+        }
+        self.register((va, vb), edge.clone(), None);
+
+        Ok(edge)
+    }
+
+    /// フェイス `f` の曲面でのエッジの pcurve（平面では `None`）
+    fn pcurve(&self, f: usize, edge: &Edge) -> Result<Option<BSplineCurve2>> {
+        let classifier = self.classifier(f);
+        if matches!(classifier.surface, GeomSurface::Plane(_)) {
+            return Ok(None);
+        }
+        if let Some(pcurve) = self.pcurves.get(&(f, edge.id())) {
+            return Ok(Some(pcurve.clone()));
+        }
+        let Some(curve) = edge.located_geometry() else {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "退化エッジの pcurve を求められません".to_string(),
+            ));
+        };
+        Ok(Some(fit_pcurve(classifier, &curve, edge.range())?.0))
+    }
+
+    /// フェイスを分割したエッジと切断のエッジでできる領域ごとのフェイスに分ける
+    fn split_face(&self, f: usize) -> Result<Vec<(Face, Sample)>> {
+        let source = &self.faces[f];
+        let classifier = self.classifier(f);
+        let face = &classifier.face;
+        let planar = matches!(classifier.surface, GeomSurface::Plane(_));
+        let mut halves = Vec::new();
+        for wire in face.wires() {
+            for edge in wire.edges() {
+                let split = &self.edges[self.edge_index[&edge.id()]];
+                let pcurve = face.pcurve(&edge);
+                let reversed = edge.orientation() == Orientation::Reversed;
+                let mut order: Vec<usize> = (0..split.pieces.len()).collect();
+                if reversed {
+                    order.reverse();
+                }
+                for k in order {
+                    let piece = &split.pieces[k];
+                    let pcurve = match (pcurve, split.ranges[k]) {
+                        (Some(pcurve), Some((a, b))) => {
+                            if (a, b) == split.range {
+                                Some(pcurve.clone())
+                            } else {
+                                Some(pcurve.trim(a, b)?)
+                            }
+                        }
+                        _ if planar => None,
+                        (original, _) => {
+                            let fitted = self.pcurve(f, piece)?;
+                            match (fitted, original, &split.curve) {
+                                // 継ぎ目では、元の pcurve と同じ側に寄せる
+                                (Some(fitted), Some(original), Some(curve)) => {
+                                    let target = parameter_on(
+                                        curve,
+                                        split.range,
+                                        midpoint(piece),
+                                        self.tolerance,
+                                    )
+                                    .map(|t| original.point_at(t));
+                                    match target {
+                                        Some(target) => Some(align_periodic(
+                                            &classifier.surface,
+                                            fitted,
+                                            target,
+                                        )?),
+                                        None => Some(fitted),
+                                    }
+                                }
+                                (fitted, _, _) => fitted,
+                            }
+                        }
+                    };
+                    let piece = if reversed {
+                        piece.reversed()
+                    } else {
+                        piece.clone()
+                    };
+                    halves.push(self.half_edge(classifier, piece, pcurve, false)?);
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        for cut in &source.cuts {
+            if !seen.insert(cut.id()) {
+                continue;
+            }
+            let pcurve = self.pcurve(f, cut)?;
+            halves.push(self.half_edge(classifier, cut.clone(), pcurve.clone(), true)?);
+            halves.push(self.half_edge(classifier, cut.reversed(), pcurve, true)?);
+        }
+        let mut halves = self.split_degenerate(classifier, halves)?;
+        let loops = trace_loops(classifier, &mut halves)?;
+
+        // 正の向きの閉路を外側の境界とし、負の向きの閉路を含む外側の境界に穴として加える
+        let mut outers: Vec<(Vec<usize>, Vec<Vector2>, f64)> = Vec::new();
+        let mut holes: Vec<(Vec<usize>, Vec<Vector2>)> = Vec::new();
+        let (lo, hi) = classifier.uv_box();
+        let area_tol = 1e-12 * (1.0 + (hi - lo).length().powi(2));
+        for lp in loops {
+            let polygon: Vec<Vector2> = lp
+                .iter()
+                .flat_map(|&h| {
+                    let points = &halves[h].points;
+                    points[..points.len() - 1].iter().copied()
+                })
+                .collect();
+            let area = signed_area(&polygon);
+            if area > area_tol {
+                outers.push((lp, polygon, area));
+            } else if area < -area_tol {
+                holes.push((lp, polygon));
+            }
+        }
+        let mut groups: Vec<Vec<usize>> = vec![Vec::new(); outers.len()];
+        for (i, (lp, _)) in holes.iter().enumerate() {
+            // 穴の境界から、フェイスの側（たどる向きの左）へわずかにずらした点で調べる
+            let points = &halves[lp[0]].points;
+            let k = (points.len() - 1) / 2;
+            let (a, b) = (points[k], points[k + 1]);
+            let left = (b - a).normalize_or(Vector2::new(0.0, 0.0)).perp();
+            let p = (a + b) * 0.5 + left * (1e-6 * (1.0 + (hi - lo).length()));
+            let owner = outers
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, polygon, _))| winding_number(polygon, p) != 0)
+                .min_by(|a, b| a.1 .2.total_cmp(&b.1 .2));
+            if let Some((o, _)) = owner {
+                groups[o].push(i);
+            }
+        }
+        let mut result = Vec::new();
+        for ((lp, polygon, _), group) in outers.iter().zip(groups) {
+            let mut wires = Vec::new();
+            let mut pcurves = Vec::new();
+            let mut polygons = vec![polygon.clone()];
+            for lp in std::iter::once(lp).chain(group.iter().map(|&i| &holes[i].0)) {
+                wires.push(Wire::new(
+                    lp.iter().map(|&h| halves[h].edge.clone()).collect(),
+                )?);
+                pcurves.push(lp.iter().map(|&h| halves[h].pcurve.clone()).collect());
+            }
+            polygons.extend(group.iter().map(|&i| holes[i].1.clone()));
+            let new_face = Face::with_pcurves(classifier.surface.clone(), wires, pcurves)?
+                .oriented(source.orientation);
+            let uv = interior_point(&polygons).unwrap_or(polygon[0]);
+            let point = classifier.surface.point_at(uv.x, uv.y);
+            let normal = classifier.surface.normal_at(uv.x, uv.y).map(|n| {
+                if source.orientation == Orientation::Reversed {
+                    -n
+                } else {
+                    n
+                }
+            });
+            result.push((new_face, (point, normal)));
+        }
+        Ok(result)
+    }
+
+    /// 極や頂点の退化エッジを、切断のエッジが端で触れるパラメータで区切る
+    ///
+    /// 交線が球の極などを通ると、切断のエッジの端は退化エッジの途中のパラメータに来るので、
+    /// そこで区切らないと閉路をたどれない。
+    fn split_degenerate(
+        &self,
+        classifier: &FaceClassifier,
+        halves: Vec<HalfEdge>,
+    ) -> Result<Vec<HalfEdge>> {
+        let (lo, hi) = classifier.uv_box();
+        let uv_tol = 1e-6 * (1.0 + (hi - lo).length());
+        let surface = &classifier.surface;
+        let (u0, u1) = surface.u_range();
+        let u_period = (surface.is_u_closed() && (u1 - u0).is_finite()).then_some(u1 - u0);
+        let ends: Vec<(Vertex, Vector2)> = halves
+            .iter()
+            .filter(|h| h.cut)
+            .flat_map(|h| {
+                [
+                    (h.edge.start_vertex(), h.points[0]),
+                    (h.edge.end_vertex(), h.points[h.points.len() - 1]),
+                ]
+            })
+            .collect();
+        let mut result = Vec::with_capacity(halves.len());
+        for half in halves {
+            let (Some(pcurve), true) = (&half.pcurve, half.edge.is_degenerate()) else {
+                result.push(half);
+                continue;
+            };
+            let vertex = half.edge.start_vertex();
+            let (t0, t1) = half.edge.range();
+            let (a, b) = (pcurve.point_at(t0), pcurve.point_at(t1));
+            let d = b - a;
+            let mut params: Vec<f64> = Vec::new();
+            for (v, uv) in &ends {
+                if !v.is_same(&vertex) {
+                    continue;
+                }
+                let shifts = match u_period {
+                    Some(period) => vec![0.0, period, -period],
+                    None => vec![0.0],
+                };
+                for shift in shifts {
+                    let q = *uv + Vector2::new(shift, 0.0);
+                    let s = (q - a).dot(d) / d.dot(d);
+                    if s > 1e-9 && s < 1.0 - 1e-9 && (a + d * s - q).length() <= uv_tol {
+                        params.push(t0 + (t1 - t0) * s);
+                    }
+                }
+            }
+            if params.is_empty() {
+                result.push(half);
+                continue;
+            }
+            params.sort_by(f64::total_cmp);
+            params.dedup_by(|x, y| (*x - *y).abs() <= 1e-9 * (t1 - t0));
+            let mut bounds = vec![t0];
+            bounds.extend(params);
+            bounds.push(t1);
+            let reversed = half.edge.orientation() == Orientation::Reversed;
+            let mut pieces = Vec::with_capacity(bounds.len() - 1);
+            for w in bounds.windows(2) {
+                let piece = Edge::degenerate(vertex.clone(), (w[0], w[1]))?;
+                let piece = if reversed { piece.reversed() } else { piece };
+                pieces.push(self.half_edge(
+                    classifier,
+                    piece,
+                    Some(pcurve.trim(w[0], w[1])?),
+                    false,
+                )?);
+            }
+            if reversed {
+                pieces.reverse();
+            }
+            result.extend(pieces);
+        }
+        Ok(result)
+    }
+
+    fn half_edge(
+        &self,
+        classifier: &FaceClassifier,
+        edge: Edge,
+        pcurve: Option<BSplineCurve2>,
+        cut: bool,
+    ) -> Result<HalfEdge> {
+        let params = sample_parameters(&edge);
+        let points: Vec<Vector2> = match &pcurve {
+            Some(pcurve) => params.iter().map(|&t| pcurve.point_at(t)).collect(),
+            None => {
+                let GeomSurface::Plane(plane) = &classifier.surface else {
+                    return Err(OcctKrsError::InvalidInput(
+                        "平面以外のフェイスに pcurve のないエッジがあります".to_string(),
+                    ));
+                };
+                params
+                    .iter()
+                    .map(|&t| {
+                        let (u, v) = plane.parameters_of(edge.point_at(t));
+                        Vector2::new(u, v)
+                    })
+                    .collect()
+            }
+        };
+        Ok(HalfEdge {
+            edge,
+            pcurve,
+            points,
+            cut,
+            from: 0,
+            to: 0,
+        })
+    }
+
+    /// 分割したフェイスと他の引数との位置関係を調べる
+    ///
+    /// 他の引数に触れないエッジを共有するフェイスは同じ位置関係になるので、まとめて1度だけ調べる。
+    fn classify(&self, fragments: &mut [Fragment], samples: &[Sample]) {
+        let cuts: HashSet<ShapeId> = self
+            .faces
+            .iter()
+            .flat_map(|f| f.cuts.iter().map(Edge::id))
+            .collect();
+        let mut touching: HashMap<ShapeId, bool> = HashMap::new();
+        let mut owner: HashMap<ShapeId, usize> = HashMap::new();
+        let mut parent: Vec<usize> = (0..fragments.len()).collect();
+        for (i, fragment) in fragments.iter().enumerate() {
+            for edge in fragment.face.wires().iter().flat_map(Wire::edges) {
+                if edge.is_degenerate() {
+                    continue;
+                }
+                let id = edge.id();
+                let touches = *touching.entry(id).or_insert_with(|| {
+                    cuts.contains(&id) || self.touches_other(&edge, fragment.argument)
+                });
+                if touches {
+                    continue;
+                }
+                match owner.get(&id) {
+                    Some(&j) => {
+                        let (a, b) = (
+                            union_find_root(&mut parent, i),
+                            union_find_root(&mut parent, j),
+                        );
+                        parent[a] = b;
+                    }
+                    None => {
+                        owner.insert(id, i);
+                    }
+                }
+            }
+        }
+        let mut states: HashMap<usize, Vec<State>> = HashMap::new();
+        for i in 0..fragments.len() {
+            let root = union_find_root(&mut parent, i);
+            let argument = fragments[root].argument;
+            let s = states
+                .entry(root)
+                .or_insert_with(|| self.states(argument, samples[root]));
+            fragments[i].states = s.clone();
+        }
+    }
+
+    /// 2つ以上の引数の境界に乗っている頂点があれば `true` を返す
+    fn touching(&self) -> bool {
+        (0..self.pool.vertices.len()).any(|v| {
+            let p = self.pool.point(v);
+            self.classifiers
+                .iter()
+                .filter(|faces| faces.iter().any(|f| f.touches(p, self.tolerance)))
+                .count()
+                >= 2
+        })
+    }
+
+    /// エッジの中点が他の引数のフェイスに触れていれば `true` を返す
+    fn touches_other(&self, edge: &Edge, argument: usize) -> bool {
+        let p = midpoint(edge);
+        self.classifiers
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| k != argument)
+            .any(|(_, faces)| faces.iter().any(|f| f.touches(p, self.tolerance)))
+    }
+
+    fn states(&self, argument: usize, (p, normal): Sample) -> Vec<State> {
+        (0..self.classifiers.len())
+            .map(|k| {
+                if k == argument {
+                    return State::Out;
+                }
+                for (g, classifier) in self.classifiers[k].iter().enumerate() {
+                    if !classifier.touches(p, self.tolerance) {
+                        continue;
+                    }
+                    let reversed =
+                        self.faces[self.offsets[k] + g].orientation == Orientation::Reversed;
+                    let same = match (normal, classifier.normal_at(p)) {
+                        (Some(n), Some(m)) => (n.dot(m) > 0.0) != reversed,
+                        _ => true,
+                    };
+                    return State::On { same };
+                }
+                match classify_point(&self.classifiers[k], p, self.tolerance) {
+                    PointClassification::Inside => State::In,
+                    _ => State::Out,
+                }
+            })
+            .collect()
+    }
+}
+
+/// 向き付きのエッジをパラメータ空間でつなぎ、閉路（エッジの番号の列）に分ける
+///
+/// 各頂点では、来た向きから時計回りに最も小さい角度で出ていくエッジを選ぶ。
+/// 行き止まりになる切断のエッジは先に取り除く。
+fn trace_loops(classifier: &FaceClassifier, halves: &mut [HalfEdge]) -> Result<Vec<Vec<usize>>> {
+    let (lo, hi) = classifier.uv_box();
+    let uv_tol = 1e-6 * (1.0 + (hi - lo).length());
+    let mut nodes: Vec<(Vertex, Vector2)> = Vec::new();
+    let mut node = |vertex: Vertex, uv: Vector2| {
+        if let Some(i) = nodes
+            .iter()
+            .position(|(v, p)| v.is_same(&vertex) && (*p - uv).length() <= uv_tol)
+        {
+            return i;
+        }
+        nodes.push((vertex, uv));
+        nodes.len() - 1
+    };
+    for h in halves.iter_mut() {
+        h.from = node(h.edge.start_vertex(), h.points[0]);
+        h.to = node(h.edge.end_vertex(), h.points[h.points.len() - 1]);
+    }
+    let node_count = nodes.len();
+
+    // 行き止まりの切断のエッジ（隣に逆向きが並ぶ）を取り除く
+    let twin = |h: usize| if h.is_multiple_of(2) { h + 1 } else { h - 1 };
+    let first_cut = halves.iter().position(|h| h.cut).unwrap_or(halves.len());
+    let twin_of = |h: usize| (h >= first_cut).then(|| first_cut + twin(h - first_cut));
+    let mut alive = vec![true; halves.len()];
+    loop {
+        let mut degree = vec![0usize; node_count];
+        for (h, half) in halves.iter().enumerate() {
+            if alive[h] {
+                degree[half.from] += 1;
+                degree[half.to] += 1;
+            }
+        }
+        let mut changed = false;
+        for h in first_cut..halves.len() {
+            let half = &halves[h];
+            if alive[h] && half.from != half.to && (degree[half.from] <= 2 || degree[half.to] <= 2)
+            {
+                alive[h] = false;
+                if let Some(t) = twin_of(h) {
+                    alive[t] = false;
+                }
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for (h, half) in halves.iter().enumerate() {
+        if alive[h] {
+            outgoing[half.from].push(h);
+        }
+    }
+    let next = |h: usize| -> Option<usize> {
+        let back = halves[h].end_direction();
+        outgoing[halves[h].to]
+            .iter()
+            .copied()
+            .map(|c| {
+                let out = halves[c].start_direction();
+                let mut angle = out.perp_dot(back).atan2(out.dot(back));
+                if angle < 0.0 {
+                    angle += std::f64::consts::TAU;
+                }
+                if Some(c) == twin_of(h) {
+                    angle = std::f64::consts::TAU;
+                }
+                (c, angle)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(c, _)| c)
+    };
+    let mut used = vec![false; halves.len()];
+    let mut loops = Vec::new();
+    for start in 0..halves.len() {
+        if !alive[start] || used[start] {
+            continue;
+        }
+        let mut lp = vec![start];
+        used[start] = true;
+        let mut h = start;
+        loop {
+            let n = next(h).ok_or_else(|| {
+                OcctKrsError::DegenerateGeometry("フェイスの分割で閉路をたどれません".to_string())
+            })?;
+            if n == start {
+                break;
+            }
+            if used[n] {
+                return Err(OcctKrsError::DegenerateGeometry(
+                    "フェイスの分割で閉路をたどれません".to_string(),
+                ));
+            }
+            used[n] = true;
+            lp.push(n);
+            h = n;
+        }
+        loops.push(lp);
+    }
+    Ok(loops)
+}
+
+/// 多角形（最初が外側、残りが穴）で囲まれた領域の内側の点を返す
+///
+/// 水平線で切った区間の中点のうち、境界から最も離れたものを選ぶ
+/// （穴に接する水平線では区間の端が境界に乗るので、区間の幅では選ばない）。
+fn interior_point(polygons: &[Vec<Vector2>]) -> Option<Vector2> {
+    let outer = polygons.first()?;
+    let (lo, hi) = outer
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p.y), hi.max(p.y))
+        });
+    let mut best: Option<(Vector2, f64)> = None;
+    for fraction in [0.5, 0.3, 0.7, 0.15, 0.85, 0.42, 0.58] {
+        let y = lo + (hi - lo) * fraction;
+        let mut xs: Vec<f64> = polygons
+            .iter()
+            .flat_map(|polygon| {
+                let n = polygon.len();
+                (0..n).filter_map(move |i| {
+                    let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                    ((a.y > y) != (b.y > y)).then(|| a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x))
+                })
+            })
+            .collect();
+        xs.sort_by(f64::total_cmp);
+        for pair in xs.chunks_exact(2) {
+            let p = Vector2::new(0.5 * (pair[0] + pair[1]), y);
+            let clearance = boundary_distance(polygons, p);
+            if best.is_none_or(|(_, c)| clearance > c) {
+                best = Some((p, clearance));
+            }
+        }
+    }
+    best.map(|(p, _)| p)
+}
+
+/// 点から多角形の辺までの最短距離
+fn boundary_distance(polygons: &[Vec<Vector2>], p: Vector2) -> f64 {
+    polygons
+        .iter()
+        .flat_map(|polygon| {
+            let n = polygon.len();
+            (0..n).map(move |i| (polygon[i], polygon[(i + 1) % n]))
+        })
+        .map(|(a, b)| {
+            let ab = b - a;
+            let length = ab.dot(ab);
+            let t = if length > 0.0 {
+                ((p - a).dot(ab) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (p - (a + ab * t)).length()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// エッジのパラメータ範囲の中央の点
+fn midpoint(edge: &Edge) -> Point3 {
+    let (a, b) = edge.range();
+    edge.point_at(0.5 * (a + b))
+}
+
+/// 点に最も近いエッジ上の点での、エッジの向きの接線
+fn edge_tangent(edge: &Edge, p: Point3) -> Vector3 {
+    let Some(curve) = edge.located_geometry() else {
+        return Vector3::new(0.0, 0.0, 0.0);
+    };
+    let (a, b) = edge.range();
+    let t = curve
+        .project(p)
+        .into_iter()
+        .min_by(|x, y| x.distance.total_cmp(&y.distance))
+        .map_or(0.5 * (a + b), |q| q.parameter);
+    let d = curve.derivative_at(t);
+    if edge.orientation() == Orientation::Reversed {
+        -d
+    } else {
+        d
+    }
+}
+
+/// 交線のパラメータ `s` での接線の向き
+fn section_tangent(section: &Section, s: f64) -> Vector3 {
+    let h = 1e-4;
+    section.point_at(s + h) - section.point_at(s - h)
+}
+
+/// 曲線の区間 `range` を囲む境界ボックス（少し広げた近似）
+fn curve_box(curve: &GeomCurve, (t0, t1): (f64, f64), tolerance: f64) -> Option<BoundingBox> {
+    let n = CROSSING_SAMPLES;
+    let bbox = BoundingBox::from_points(
+        (0..=n).map(|k| curve.point_at(t0 + (t1 - t0) * k as f64 / n as f64)),
+    )?;
+    Some(bbox.enlarged(0.01 * bbox.size().length() + tolerance))
+}
+
+/// 点が曲線の区間 `range` の内側から `tolerance` 以内にあれば、そのパラメータを返す
+fn parameter_on(curve: &GeomCurve, (t0, t1): (f64, f64), p: Point3, tolerance: f64) -> Option<f64> {
+    let period = curve_period(curve);
+    curve
+        .project(p)
+        .into_iter()
+        .filter(|q| q.distance <= tolerance)
+        .map(|q| match period {
+            Some(period) => t0 + (q.parameter - t0).rem_euclid(period),
+            None => q.parameter,
+        })
+        .find(|&t| t > t0 && t < t1)
+}
+
+/// 3つの平面がただ1点で交わればその点を返す
+fn plane_triple_point(a: &Plane, b: &Plane, c: &Plane) -> Option<Point3> {
+    let (na, nb, nc) = (
+        a.normal().to_vector(),
+        b.normal().to_vector(),
+        c.normal().to_vector(),
+    );
+    let det = na.dot(nb.cross(nc));
+    if det.abs() <= precision::angular() {
+        return None;
+    }
+    let (da, db, dc) = (
+        na.dot(a.origin().to_vector()),
+        nb.dot(b.origin().to_vector()),
+        nc.dot(c.origin().to_vector()),
+    );
+    let v = (nb.cross(nc) * da + nc.cross(na) * db + na.cross(nb) * dc) / det;
+    Some(Point3::new(v.x, v.y, v.z))
+}
+
+/// pcurve の中央が `target` に最も近くなるように、閉じた向きに周期の整数倍だけずらす
+fn align_periodic(
+    surface: &GeomSurface,
+    pcurve: BSplineCurve2,
+    target: Vector2,
+) -> Result<BSplineCurve2> {
+    let mid = pcurve.point_at(0.5 * (pcurve.first_parameter() + pcurve.last_parameter()));
+    let shift = |closed: bool, (lo, hi): (f64, f64), d: f64| {
+        let period = hi - lo;
+        if closed && period.is_finite() {
+            (d / period).round() * period
+        } else {
+            0.0
+        }
+    };
+    let offset = Vector2::new(
+        shift(surface.is_u_closed(), surface.u_range(), target.x - mid.x),
+        shift(surface.is_v_closed(), surface.v_range(), target.y - mid.y),
+    );
+    if offset.x == 0.0 && offset.y == 0.0 {
+        return Ok(pcurve);
+    }
+    translated(&pcurve, offset)
+}
+
+/// 2つの曲面が同じ形状（平面以外の一致を交線の計算の前に調べる）なら `true` を返す
+fn same_surface(a: &GeomSurface, b: &GeomSurface, tolerance: f64) -> bool {
+    let coaxial = |pa: crate::Axis3, pb: crate::Axis3| {
+        let (da, db) = (pa.direction(), pb.direction());
+        let w = pb.location() - pa.location();
+        let off_axis = (w - da.to_vector() * w.dot(da.to_vector())).length();
+        da.dot(db).abs() >= 1.0 - 1e-12 && off_axis <= tolerance
+    };
+    match (a, b) {
+        (GeomSurface::Cylinder(a), GeomSurface::Cylinder(b)) => {
+            (a.radius() - b.radius()).abs() <= tolerance && coaxial(a.position(), b.position())
+        }
+        (GeomSurface::Sphere(a), GeomSurface::Sphere(b)) => {
+            (a.radius() - b.radius()).abs() <= tolerance
+                && a.center().distance(b.center()) <= tolerance
+        }
+        (GeomSurface::Torus(a), GeomSurface::Torus(b)) => {
+            (a.major_radius() - b.major_radius()).abs() <= tolerance
+                && (a.minor_radius() - b.minor_radius()).abs() <= tolerance
+                && coaxial(a.position(), b.position())
+                && a.position().location().distance(b.position().location()) <= tolerance
+        }
+        _ => false,
+    }
+}
+
+/// 点列を曲面のパラメータ空間へ移す（閉じた方向では隣の点から連続になるようにし、
+/// 中央の点がフェイスの範囲に入るように周期をずらす）
+///
+/// 2点目以降は隣の点のパラメータから精密化する。
+fn unwrap_points(
+    classifier: &FaceClassifier,
+    points: impl Iterator<Item = Point3>,
+) -> Vec<Vector2> {
+    let surface = &classifier.surface;
+    let period =
+        |(a, b): (f64, f64), closed: bool| (closed && (b - a).is_finite()).then_some(b - a);
+    let periods = [
+        period(surface.u_range(), surface.is_u_closed()),
+        period(surface.v_range(), surface.is_v_closed()),
+    ];
+    let near = |x: f64, prev: f64, period: Option<f64>| match period {
+        Some(period) => x + period * ((prev - x) / period).round(),
+        None => x,
+    };
+    let mut uv: Vec<Vector2> = Vec::new();
+    for p in points {
+        let q = match uv.last() {
+            Some(prev) => {
+                // 隣の点から精密化して曲面上に届けばそれを使い、届かなければ全体から探す
+                let (u, v) = refine_surface_projection(surface, p, prev.x, prev.y);
+                let q = if surface.point_at(u, v).distance(p) <= 10.0 * MARCH_TOLERANCE {
+                    Vector2::new(u, v)
+                } else {
+                    classifier.uv_of(p)
+                };
+                Vector2::new(near(q.x, prev.x, periods[0]), near(q.y, prev.y, periods[1]))
+            }
+            None => classifier.uv_of(p),
+        };
+        uv.push(q);
+    }
+    // 球の極や円錐の頂点では u が決まらないので、隣の点の u に合わせる
+    for i in 0..uv.len() {
+        let neighbor = if i == 0 { 1 } else { i - 1 };
+        let (Some(&q), Some(&n)) = (uv.get(i), uv.get(neighbor)) else {
+            continue;
+        };
+        let du = surface.derivative_u_at(q.x, q.y).length();
+        if du <= 1e-9 * surface.derivative_v_at(q.x, q.y).length() {
+            uv[i].x = n.x;
+        }
+    }
+    if let Some(&m) = uv.get(uv.len() / 2) {
+        let shift = classifier.unwrap(m) - m;
+        for q in &mut uv {
+            *q += shift;
+        }
+    }
+    uv
+}
+
+/// 曲線の区間をフェイスの曲面のパラメータ空間へ移した pcurve と、その3次元での誤差
+///
+/// 折れ線は折れ点ごとに移し、両端を結ぶ線分で表せればそれを使う。
+/// それ以外は分割数を増やしながら3次の補間曲線を求める。
+fn fit_pcurve(
+    classifier: &FaceClassifier,
+    curve: &GeomCurve,
+    (t0, t1): (f64, f64),
+) -> Result<(BSplineCurve2, f64)> {
+    let surface = &classifier.surface;
+    let error = |pcurve: &BSplineCurve2, params: &[f64]| {
+        params
+            .windows(2)
+            .flat_map(|w| [w[0], 0.5 * (w[0] + w[1])])
+            .chain(std::iter::once(t1))
+            .map(|t| {
+                let uv = pcurve.point_at(t);
+                surface.point_at(uv.x, uv.y).distance(curve.point_at(t))
+            })
+            .fold(0.0, f64::max)
+    };
+    if let GeomCurve::BSpline(c) = curve {
+        if c.degree() == 1 && !c.is_rational() {
+            let knots = c.flat_knots();
+            let mut params = vec![t0];
+            for &t in &knots[1..knots.len() - 1] {
+                if t > t0 && t < t1 && params.last().is_some_and(|&last| t > last) {
+                    params.push(t);
+                }
+            }
+            params.push(t1);
+            let uv = unwrap_points(classifier, params.iter().map(|&t| curve.point_at(t)));
+            let mut flat = vec![t0];
+            flat.extend_from_slice(&params);
+            flat.push(t1);
+            let pcurve = BSplineCurve2::from_flat_knots(1, uv, flat)?;
+            let err = error(&pcurve, &params);
+            return Ok((pcurve, err));
+        }
+    }
+    let samples = |n: usize| -> Vec<f64> {
+        (0..=n)
+            .map(|k| t0 + (t1 - t0) * k as f64 / n as f64)
+            .collect()
+    };
+    // 円柱の母線や緯線のようにパラメータ空間で線分になる場合
+    let params = samples(16);
+    let uv = unwrap_points(classifier, params.iter().map(|&t| curve.point_at(t)));
+    let line = BSplineCurve2::from_flat_knots(1, vec![uv[0], uv[16]], vec![t0, t0, t1, t1])?;
+    let err = error(&line, &params);
+    if err <= precision::confusion() {
+        return Ok((line, err));
+    }
+    let mut best: Option<(BSplineCurve2, f64)> = None;
+    let mut n = 16;
+    while n <= MAX_PCURVE_SAMPLES {
+        let params = samples(n);
+        let uv = unwrap_points(classifier, params.iter().map(|&t| curve.point_at(t)));
+        let knots = averaged_knots(&params, 3);
+        let matrix = interpolation_matrix(&params, &knots, 3);
+        let Some(poles) = solve_dense(matrix, uv.iter().map(|p| p.extend(0.0)).collect()) else {
+            break;
+        };
+        let pcurve = BSplineCurve2::from_flat_knots(
+            3,
+            poles.into_iter().map(Vector2::from).collect(),
+            knots,
+        )?;
+        let err = error(&pcurve, &params);
+        if best.as_ref().is_none_or(|b| err < b.1) {
+            best = Some((pcurve, err));
+        }
+        if err <= precision::confusion() {
+            break;
+        }
+        n *= 2;
+    }
+    best.ok_or_else(|| {
+        OcctKrsError::DegenerateGeometry("交線の pcurve を求められませんでした".to_string())
+    })
+}
@@ -0,0 +1,231 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::{Matrix3, Matrix4, Point3, Quaternion, Transform, Vector3};
+
+/// 非一様スケールやせん断を含む一般のアフィン変換（OCCT の `gp_GTrsf` 相当）
+///
+/// 点 `p` は `matrix * p + translation` に写される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeneralTransform {
+    pub matrix: Matrix3,
+    pub translation: Vector3,
+}
+
+/// 一般アフィン変換を 平行移動・回転・スケール・せん断 に分解した結果
+///
+/// 元の変換は `translation + rotation * diag(scale) * H` と一致する。
+/// ここで `H` は対角成分が1の上三角行列で、`shear` は `(xy, xz, yz)` 成分を表す。
+/// 鏡映を含む場合は `scale.z` が負になる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AffineDecomposition {
+    pub translation: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+    pub shear: Vector3,
+}
+
+impl GeneralTransform {
+    /// 線形部分と平行移動から変換を生成する
+    pub fn new(matrix: Matrix3, translation: Vector3) -> Self {
+        Self {
+            matrix,
+            translation,
+        }
+    }
+
+    /// 恒等変換を返す
+    pub fn identity() -> Self {
+        Self::new(Matrix3::identity(), Vector3::new(0.0, 0.0, 0.0))
+    }
+
+    /// 原点を中心とする各軸方向の非一様スケールを生成する
+    pub fn from_scale(scale: Vector3) -> Self {
+        Self::new(Matrix3::from_diagonal(scale), Vector3::new(0.0, 0.0, 0.0))
+    }
+
+    /// せん断変換を生成する
+    /// `shear` の `(xy, xz, yz)` はそれぞれ x += xy*y + xz*z, y += yz*z を表す
+    pub fn from_shear(shear: Vector3) -> Self {
+        Self::new(shear_matrix(shear), Vector3::new(0.0, 0.0, 0.0))
+    }
+
+    /// 分解結果から変換を再構成する
+    pub fn from_decomposition(d: &AffineDecomposition) -> Self {
+        let linear =
+            d.rotation.to_matrix3() * Matrix3::from_diagonal(d.scale) * shear_matrix(d.shear);
+        Self::new(linear, d.translation)
+    }
+
+    /// 点を変換する
+    pub fn transform_point(&self, p: Point3) -> Point3 {
+        Point3::from(self.matrix * p.to_vector() + self.translation)
+    }
+
+    /// ベクトルを変換する（平行移動は影響しない）
+    pub fn transform_vector(&self, v: Vector3) -> Vector3 {
+        self.matrix * v
+    }
+
+    /// 逆変換を返す
+    /// 線形部分が特異な場合は `None` を返す
+    pub fn inverse(&self) -> Option<Self> {
+        let inv = self.matrix.inverse()?;
+        Some(Self::new(inv, -(inv * self.translation)))
+    }
+
+    /// 4x4の同次変換行列に変換する
+    pub fn to_matrix4(&self) -> Matrix4 {
+        let mut m = self.matrix.to_matrix4();
+        m.m[0][3] = self.translation.x;
+        m.m[1][3] = self.translation.y;
+        m.m[2][3] = self.translation.z;
+        m
+    }
+
+    /// 平行移動・回転・スケール・せん断に分解する
+    /// 線形部分が特異な場合は `None` を返す
+    pub fn decompose(&self) -> Option<AffineDecomposition> {
+        self.matrix.inverse()?;
+        let det = self.matrix.determinant();
+        // 列ベクトルのグラム・シュミット直交化（QR分解）
+        let c0 = self.matrix.column(0);
+        let c1 = self.matrix.column(1);
+        let c2 = self.matrix.column(2);
+
+        let sx = c0.length();
+        let q0 = c0 * (1.0 / sx);
+        let r01 = q0.dot(c1);
+        let u1 = c1 - q0 * r01;
+        let sy = u1.length();
+        let q1 = u1 * (1.0 / sy);
+        let r02 = q0.dot(c2);
+        let r12 = q1.dot(c2);
+        let u2 = c2 - q0 * r02 - q1 * r12;
+        let mut sz = u2.length();
+        let mut q2 = u2 * (1.0 / sz);
+        // 鏡映を含む場合は z 方向のスケールの符号で表す
+        if det < 0.0 {
+            sz = -sz;
+            q2 = -q2;
+        }
+
+        let rotation = Quaternion::from_matrix3(&Matrix3::from_columns(q0, q1, q2));
+        Some(AffineDecomposition {
+            translation: self.translation,
+            rotation,
+            scale: Vector3::new(sx, sy, sz),
+            shear: Vector3::new(r01 / sx, r02 / sx, r12 / sy),
+        })
+    }
+}
+
+fn shear_matrix(shear: Vector3) -> Matrix3 {
+    Matrix3::from_rows([
+        [1.0, shear.x, shear.y],
+        [0.0, 1.0, shear.z],
+        [0.0, 0.0, 1.0],
+    ])
+}
+
+impl Default for GeneralTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 剛体変換からの変換
+impl From<Transform> for GeneralTransform {
+    fn from(t: Transform) -> Self {
+        Self::new(t.linear_matrix(), t.translation)
+    }
+}
+
+/// 変換の合成の実装
+/// `a * b` は「b を適用した後に a を適用する」変換を表す
+impl Mul for GeneralTransform {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.matrix * other.matrix,
+            self.matrix * other.translation + self.translation,
+        )
+    }
+}
+
+/// 変換による点の写像
+impl Mul<Point3> for GeneralTransform {
+    type Output = Point3;
+    fn mul(self, p: Point3) -> Point3 {
+        self.transform_point(p)
+    }
+}
+
+/// 変換によるベクトルの写像
+impl Mul<Vector3> for GeneralTransform {
+    type Output = Vector3;
+    fn mul(self, v: Vector3) -> Vector3 {
+        self.transform_vector(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn sample() -> GeneralTransform {
+        GeneralTransform::from(Transform::new(
+            Quaternion::from_axis_angle(Vector3::new(1.0, -1.0, 2.0), 0.9),
+            Vector3::new(3.0, 0.0, -1.0),
+            1.0,
+        )) * GeneralTransform::from_scale(Vector3::new(2.0, 0.5, 3.0))
+            * GeneralTransform::from_shear(Vector3::new(0.3, -0.2, 0.7))
+    }
+
+    #[test]
+    fn test_non_uniform_scale() {
+        let t = GeneralTransform::from_scale(Vector3::new(2.0, 3.0, 4.0));
+        assert_point_eq(t * Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let a = sample();
+        let b = GeneralTransform::from_shear(Vector3::new(1.0, 0.0, 0.0));
+        let p = Point3::new(0.2, -1.0, 0.5);
+        assert_point_eq((a * b) * p, a * (b * p));
+        let inv = a.inverse().unwrap();
+        assert_point_eq(inv * (a * p), p);
+        assert!(GeneralTransform::from_scale(Vector3::new(1.0, 0.0, 1.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn test_decompose_roundtrip() {
+        let t = sample();
+        let d = t.decompose().unwrap();
+        assert!((d.scale - Vector3::new(2.0, 0.5, 3.0)).length() < 1e-10);
+        assert!((d.shear - Vector3::new(0.3, -0.2, 0.7)).length() < 1e-10);
+        let back = GeneralTransform::from_decomposition(&d);
+        let p = Point3::new(1.0, 2.0, 3.0);
+        assert_point_eq(back * p, t * p);
+    }
+
+    #[test]
+    fn test_decompose_mirror() {
+        let t = GeneralTransform::from_scale(Vector3::new(1.0, 1.0, -2.0));
+        let d = t.decompose().unwrap();
+        assert!(d.scale.z < 0.0);
+        let back = GeneralTransform::from_decomposition(&d);
+        let p = Point3::new(1.0, -2.0, 3.0);
+        assert_point_eq(back * p, t * p);
+    }
+}
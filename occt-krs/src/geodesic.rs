@@ -0,0 +1,572 @@
+use crate::precision;
+use crate::projection::refine_surface_projection;
+use crate::{OcctKrsError, Point3, Polyline3, Result, Surface, Vector3};
+
+/// 最短経路の初期値を探すためにパラメータ範囲を各方向に分割する区間数
+const GRID_CELLS: usize = 24;
+
+/// 最短測地線を精密化するときの折れ線の最初の区間数
+const INITIAL_SEGMENTS: usize = 8;
+
+/// 最短測地線を精密化するときの折れ線の区間数の上限
+const MAX_SEGMENTS: usize = 1024;
+
+/// 各段階で点を動かす反復の上限
+const MAX_SWEEPS: usize = 200;
+
+/// 曲面上の測地線を近似する点列
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geodesic {
+    /// 測地線を近似する折れ線（各頂点は曲面上にある）
+    pub curve: Polyline3,
+    /// 各点での曲面のパラメータ `(u, v)`（閉じた方向では範囲内に折り返す）
+    pub parameters: Vec<(f64, f64)>,
+    /// 測地線の長さ
+    pub length: f64,
+}
+
+/// 曲面上の点 `start` から接方向 `direction` へ、長さ `length` の測地線を追跡する
+///
+/// 測地線の方程式（曲線の加速度の接平面成分がゼロ）を弧長パラメータで4次のルンゲ・クッタ法により積分する。
+/// `direction` は接平面へ射影して使う。閉じていない方向でパラメータ範囲の端に達した場合は、
+/// そこで打ち切った測地線を返す（`length` は実際に進んだ長さ）。
+/// `length`・`step` が正の有限値でない場合や、`direction` が接平面に垂直な場合はエラーを返す。
+pub fn trace_geodesic(
+    surface: &(impl Surface + ?Sized),
+    start: (f64, f64),
+    direction: Vector3,
+    length: f64,
+    step: f64,
+) -> Result<Geodesic> {
+    for (name, x) in [("長さ", length), ("刻み幅", step)] {
+        if !(x > 0.0 && x.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "{}が不正です: {}",
+                name, x
+            )));
+        }
+    }
+    let (u, v) = start;
+    let (su, sv) = (surface.derivative_u_at(u, v), surface.derivative_v_at(u, v));
+    let (p, q) = solve_metric(su, sv, su.dot(direction), sv.dot(direction)).ok_or_else(|| {
+        OcctKrsError::DegenerateGeometry("始点で接平面が定まりません".to_string())
+    })?;
+    let speed = (su * p + sv * q).length();
+    if speed <= precision::confusion() * direction.length().max(1.0) {
+        return Err(OcctKrsError::InvalidInput(
+            "方向が接平面に垂直です".to_string(),
+        ));
+    }
+    let ranges = [surface.u_range(), surface.v_range()];
+    let closed = [surface.is_u_closed(), surface.is_v_closed()];
+    let mut state = [u, v, p / speed, q / speed];
+    let mut parameters = vec![fit_parameters(surface, u, v)];
+    let mut travelled = 0.0;
+    while travelled < length {
+        let h = step.min(length - travelled);
+        let Some(mut next) = rk4_step(surface, state, h) else {
+            break;
+        };
+        // パラメータ範囲の外へ出る場合は、端までの割合で打ち切る
+        let mut fraction: f64 = 1.0;
+        for k in 0..2 {
+            let (lo, hi) = ranges[k];
+            if closed[k] {
+                continue;
+            }
+            let bound = if next[k] > hi {
+                hi
+            } else if next[k] < lo {
+                lo
+            } else {
+                continue;
+            };
+            fraction = fraction.min(((bound - state[k]) / (next[k] - state[k])).max(0.0));
+        }
+        if fraction <= 0.0 {
+            break;
+        }
+        if fraction < 1.0 {
+            for (x, s) in next.iter_mut().zip(state) {
+                *x = s + (*x - s) * fraction;
+            }
+        }
+        // 弧長パラメータとなるよう速さを1に戻す
+        let (su, sv) = (
+            surface.derivative_u_at(next[0], next[1]),
+            surface.derivative_v_at(next[0], next[1]),
+        );
+        let speed = (su * next[2] + sv * next[3]).length();
+        if speed > f64::MIN_POSITIVE {
+            next[2] /= speed;
+            next[3] /= speed;
+        }
+        travelled += h * fraction;
+        parameters.push(fit_parameters(surface, next[0], next[1]));
+        state = next;
+        if fraction < 1.0 {
+            break;
+        }
+    }
+    if parameters.len() < 2 {
+        return Err(OcctKrsError::InvalidInput(
+            "始点からパラメータ範囲の外へ向かっています".to_string(),
+        ));
+    }
+    let points = parameters
+        .iter()
+        .map(|&(u, v)| surface.point_at(u, v))
+        .collect();
+    Ok(Geodesic {
+        curve: Polyline3::new(points, false)?,
+        parameters,
+        length: travelled,
+    })
+}
+
+/// 曲面上の2点 `start`・`end`（パラメータで指定）を結ぶ最短の測地線を求める
+///
+/// パラメータ範囲を格子状に分割したグラフの最短経路を初期値とし、各頂点を隣の2点の中点の射影へ
+/// 動かす反復で折れ線を縮める（離散測地線）。閉じた方向では継ぎ目をまたぐ経路も考える。
+/// 区間を倍に細かくしながら、各区間の弦と曲面との隔たりが `tolerance` 以下になるまで繰り返す。
+/// 範囲が無限の方向は、2点を含む有限の窓で探す。
+/// 満たせない場合は `ToleranceExceeded` を返す。
+pub fn shortest_geodesic(
+    surface: &(impl Surface + ?Sized),
+    start: (f64, f64),
+    end: (f64, f64),
+    tolerance: f64,
+) -> Result<Geodesic> {
+    if !(tolerance > 0.0 && tolerance.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "許容誤差が不正です: {}",
+            tolerance
+        )));
+    }
+    let (a, b) = (
+        surface.point_at(start.0, start.1),
+        surface.point_at(end.0, end.1),
+    );
+    if a.distance(b) <= precision::confusion() {
+        return Err(OcctKrsError::InvalidInput(
+            "始点と終点が一致しています".to_string(),
+        ));
+    }
+    let mut path = resample(surface, &grid_path(surface, start, end), INITIAL_SEGMENTS);
+    loop {
+        relax(surface, &mut path, tolerance);
+        // 各区間の中点を曲面へ射影し、弦との隔たりを測る
+        let mut refined = vec![path[0]];
+        let mut sag: f64 = 0.0;
+        for w in path.windows(2) {
+            let (p, q) = (
+                surface.point_at(w[0].0, w[0].1),
+                surface.point_at(w[1].0, w[1].1),
+            );
+            let mid = p + (q - p) * 0.5;
+            let uv = project_near(
+                surface,
+                mid,
+                ((w[0].0 + w[1].0) / 2.0, (w[0].1 + w[1].1) / 2.0),
+            );
+            sag = sag.max(surface.point_at(uv.0, uv.1).distance(mid));
+            refined.push(uv);
+            refined.push(w[1]);
+        }
+        if sag <= tolerance {
+            break;
+        }
+        if path.len() > MAX_SEGMENTS {
+            return Err(OcctKrsError::ToleranceExceeded {
+                tolerance,
+                deviation: sag,
+            });
+        }
+        path = refined;
+    }
+    let parameters: Vec<(f64, f64)> = path
+        .iter()
+        .map(|&(u, v)| fit_parameters(surface, u, v))
+        .collect();
+    let points: Vec<Point3> = parameters
+        .iter()
+        .map(|&(u, v)| surface.point_at(u, v))
+        .collect();
+    let curve = Polyline3::new(points, false)?;
+    Ok(Geodesic {
+        length: curve.length(),
+        curve,
+        parameters,
+    })
+}
+
+/// 内部の各点を隣の2点の中点の射影へ動かす反復を、点の移動量が十分小さくなるまで繰り返す
+fn relax(surface: &(impl Surface + ?Sized), path: &mut [(f64, f64)], tolerance: f64) {
+    for _ in 0..MAX_SWEEPS {
+        let mut moved: f64 = 0.0;
+        for i in 1..path.len() - 1 {
+            let (p, q) = (
+                surface.point_at(path[i - 1].0, path[i - 1].1),
+                surface.point_at(path[i + 1].0, path[i + 1].1),
+            );
+            let before = surface.point_at(path[i].0, path[i].1);
+            path[i] = project_near(surface, p + (q - p) * 0.5, path[i]);
+            moved = moved.max(surface.point_at(path[i].0, path[i].1).distance(before));
+        }
+        if moved <= tolerance * 0.01 {
+            break;
+        }
+    }
+}
+
+/// 点を初期値 `near` の近くで曲面へ射影し、閉じた方向では `near` に最も近い周期へずらしたパラメータを返す
+fn project_near(surface: &(impl Surface + ?Sized), p: Point3, near: (f64, f64)) -> (f64, f64) {
+    let (u, v) = refine_surface_projection(surface, p, near.0, near.1);
+    (
+        unwrap(u, near.0, period(surface.is_u_closed(), surface.u_range())),
+        unwrap(v, near.1, period(surface.is_v_closed(), surface.v_range())),
+    )
+}
+
+/// 閉じた方向の周期を返す
+fn period(closed: bool, (lo, hi): (f64, f64)) -> Option<f64> {
+    closed.then_some(hi - lo)
+}
+
+/// `x` を周期分ずらして `near` に最も近い値にする
+fn unwrap(x: f64, near: f64, period: Option<f64>) -> f64 {
+    match period {
+        Some(t) => x + t * ((near - x) / t).round(),
+        None => x,
+    }
+}
+
+/// 閉じた方向のパラメータを範囲内に折り返す
+fn fit_parameters(surface: &(impl Surface + ?Sized), u: f64, v: f64) -> (f64, f64) {
+    let fit = |x: f64, closed: bool, (lo, hi): (f64, f64)| {
+        if closed {
+            lo + (x - lo).rem_euclid(hi - lo)
+        } else {
+            x
+        }
+    };
+    (
+        fit(u, surface.is_u_closed(), surface.u_range()),
+        fit(v, surface.is_v_closed(), surface.v_range()),
+    )
+}
+
+/// 弧長パラメータと速度 `(p, q)` での加速度 `(u'', v'')` を返す（測地線の方程式）
+fn geodesic_acceleration(
+    surface: &(impl Surface + ?Sized),
+    u: f64,
+    v: f64,
+    p: f64,
+    q: f64,
+) -> Option<(f64, f64)> {
+    let (su, sv) = (surface.derivative_u_at(u, v), surface.derivative_v_at(u, v));
+    let (suu, suv, svv) = surface.second_derivatives_at(u, v);
+    let w = suu * (p * p) + suv * (2.0 * p * q) + svv * (q * q);
+    let (a, b) = solve_metric(su, sv, -su.dot(w), -sv.dot(w))?;
+    Some((a, b))
+}
+
+/// 第1基本形式 `[[E, F], [F, G]] x = (b1, b2)` を解く
+fn solve_metric(su: Vector3, sv: Vector3, b1: f64, b2: f64) -> Option<(f64, f64)> {
+    let (e, f, g) = (su.dot(su), su.dot(sv), sv.dot(sv));
+    let det = e * g - f * f;
+    if det.is_nan() || det <= 1e-14 * (e * g).max(f64::MIN_POSITIVE) {
+        return None;
+    }
+    Some(((g * b1 - f * b2) / det, (e * b2 - f * b1) / det))
+}
+
+/// 状態 `(u, v, u', v')` を弧長 `h` だけ4次のルンゲ・クッタ法で進める
+fn rk4_step(surface: &(impl Surface + ?Sized), y: [f64; 4], h: f64) -> Option<[f64; 4]> {
+    let f = |y: [f64; 4]| -> Option<[f64; 4]> {
+        let (a, b) = geodesic_acceleration(surface, y[0], y[1], y[2], y[3])?;
+        Some([y[2], y[3], a, b])
+    };
+    let add =
+        |y: [f64; 4], k: [f64; 4], s: f64| -> [f64; 4] { std::array::from_fn(|i| y[i] + k[i] * s) };
+    let k1 = f(y)?;
+    let k2 = f(add(y, k1, h / 2.0))?;
+    let k3 = f(add(y, k2, h / 2.0))?;
+    let k4 = f(add(y, k3, h))?;
+    Some(std::array::from_fn(|i| {
+        y[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
+    }))
+}
+
+/// パラメータ範囲を格子状に分割したグラフで、2点を結ぶ最短経路をダイクストラ法で求める
+///
+/// 閉じた方向では継ぎ目をまたぐ辺も張る。返すパラメータ列は継ぎ目で跳ばないようにつないである。
+fn grid_path(
+    surface: &(impl Surface + ?Sized),
+    start: (f64, f64),
+    end: (f64, f64),
+) -> Vec<(f64, f64)> {
+    let closed = [surface.is_u_closed(), surface.is_v_closed()];
+    let window = |(lo, hi): (f64, f64), a: f64, b: f64| {
+        let margin = 0.5 * (a - b).abs().max(1.0);
+        (
+            if lo.is_finite() {
+                lo
+            } else {
+                a.min(b) - margin
+            },
+            if hi.is_finite() {
+                hi
+            } else {
+                a.max(b) + margin
+            },
+        )
+    };
+    let ranges = [
+        window(surface.u_range(), start.0, end.0),
+        window(surface.v_range(), start.1, end.1),
+    ];
+    // 閉じた方向では最後の格子線が最初と重なるので省く
+    let counts = [0, 1].map(|k| GRID_CELLS + usize::from(!closed[k]));
+    let coordinate = |k: usize, i: usize| {
+        let (lo, hi) = ranges[k];
+        lo + (hi - lo) * i as f64 / GRID_CELLS as f64
+    };
+    let grid_nodes = counts[0] * counts[1];
+    let (s_node, e_node) = (grid_nodes, grid_nodes + 1);
+    let mut nodes: Vec<(f64, f64)> = (0..grid_nodes)
+        .map(|n| (coordinate(0, n / counts[1]), coordinate(1, n % counts[1])))
+        .collect();
+    nodes.push(start);
+    nodes.push(end);
+    let points: Vec<Point3> = nodes.iter().map(|&(u, v)| surface.point_at(u, v)).collect();
+
+    // 隣接リスト（8近傍と、始点・終点を含むセルの4隅）
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let index = |i: isize, j: isize| -> Option<usize> {
+        let wrap = |x: isize, k: usize| -> Option<usize> {
+            let n = counts[k] as isize;
+            if closed[k] {
+                Some(x.rem_euclid(n) as usize)
+            } else {
+                (0..n).contains(&x).then_some(x as usize)
+            }
+        };
+        Some(wrap(i, 0)? * counts[1] + wrap(j, 1)?)
+    };
+    for (n, neighbours) in adjacent.iter_mut().enumerate().take(grid_nodes) {
+        let (i, j) = ((n / counts[1]) as isize, (n % counts[1]) as isize);
+        for (di, dj) in [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ] {
+            if let Some(m) = index(i + di, j + dj) {
+                if m != n {
+                    neighbours.push(m);
+                }
+            }
+        }
+    }
+    for (node, (u, v)) in [(s_node, start), (e_node, end)] {
+        let cell = |k: usize, x: f64| {
+            let (lo, hi) = ranges[k];
+            ((x - lo) / (hi - lo) * GRID_CELLS as f64).floor() as isize
+        };
+        let (i, j) = (cell(0, u), cell(1, v));
+        for (di, dj) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            if let Some(m) = index(i + di, j + dj) {
+                adjacent[node].push(m);
+                adjacent[m].push(node);
+            }
+        }
+    }
+    adjacent[s_node].push(e_node);
+    adjacent[e_node].push(s_node);
+
+    let mut dist = vec![f64::INFINITY; nodes.len()];
+    let mut previous = vec![usize::MAX; nodes.len()];
+    let mut done = vec![false; nodes.len()];
+    dist[s_node] = 0.0;
+    while let Some(n) = (0..nodes.len())
+        .filter(|&n| !done[n] && dist[n].is_finite())
+        .min_by(|&a, &b| dist[a].total_cmp(&dist[b]))
+    {
+        if n == e_node {
+            break;
+        }
+        done[n] = true;
+        for &m in &adjacent[n] {
+            let d = dist[n] + points[n].distance(points[m]);
+            if d < dist[m] {
+                dist[m] = d;
+                previous[m] = n;
+            }
+        }
+    }
+    let mut route = vec![e_node];
+    while let Some(&n) = route.last() {
+        if n == s_node || previous[n] == usize::MAX {
+            break;
+        }
+        route.push(previous[n]);
+    }
+    route.reverse();
+
+    // 継ぎ目をまたいでもパラメータが連続するようにつなぐ
+    let periods = [
+        period(closed[0], surface.u_range()),
+        period(closed[1], surface.v_range()),
+    ];
+    let mut path = vec![start];
+    for &n in &route[1..] {
+        let (u, v) = nodes[n];
+        let (pu, pv) = *path.last().unwrap();
+        path.push((unwrap(u, pu, periods[0]), unwrap(v, pv, periods[1])));
+    }
+    path
+}
+
+/// 折れ線を3次元の長さで等分した `segments` 区間のパラメータ列に並べ直す
+fn resample(
+    surface: &(impl Surface + ?Sized),
+    path: &[(f64, f64)],
+    segments: usize,
+) -> Vec<(f64, f64)> {
+    let mut cumulative = vec![0.0];
+    for w in path.windows(2) {
+        let d = surface
+            .point_at(w[0].0, w[0].1)
+            .distance(surface.point_at(w[1].0, w[1].1));
+        cumulative.push(cumulative.last().unwrap() + d);
+    }
+    let total = *cumulative.last().unwrap();
+    (0..=segments)
+        .map(|k| {
+            let s = total * k as f64 / segments as f64;
+            let i = cumulative
+                .partition_point(|&c| c < s)
+                .clamp(1, path.len() - 1);
+            let span = cumulative[i] - cumulative[i - 1];
+            let t = if span > 0.0 {
+                (s - cumulative[i - 1]) / span
+            } else {
+                0.0
+            };
+            let (a, b) = (path[i - 1], path[i]);
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis3, BSplineSurface, CylindricalSurface, Dir, Plane, SphericalSurface};
+    use std::f64::consts::{PI, TAU};
+
+    fn sphere() -> SphericalSurface {
+        SphericalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap()
+    }
+
+    fn cylinder() -> CylindricalSurface {
+        CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap()
+    }
+
+    #[test]
+    fn test_trace_great_circle() {
+        let s = sphere();
+        // 赤道上の点から北東へ進む測地線は中心を通る平面上の大円になる
+        let dir = Vector3::new(0.0, 1.0, 1.0);
+        let g = trace_geodesic(&s, (0.0, 0.0), dir, 2.0 * PI, 0.05).unwrap();
+        assert!((g.length - 2.0 * PI).abs() < 1e-12);
+        let normal = Vector3::new(2.0, 0.0, 0.0).cross(dir);
+        for p in g.curve.points() {
+            assert!((p.distance(Point3::ORIGIN) - 2.0).abs() < 1e-9);
+            assert!(p.to_vector().dot(normal).abs() < 1e-6);
+        }
+        // 半周で対蹠点に着く
+        let last = *g.curve.points().last().unwrap();
+        assert!(last.distance(Point3::new(-2.0, 0.0, 0.0)) < 1e-6);
+    }
+
+    #[test]
+    fn test_trace_helix_on_cylinder() {
+        // 円柱の測地線はつるまき線（展開すると直線）
+        let c = cylinder();
+        let dir = Vector3::new(0.0, 1.0, 1.0);
+        let g = trace_geodesic(&c, (0.0, 0.0), dir, 10.0, 0.1).unwrap();
+        let t = 10.0 / 2f64.sqrt();
+        let (u, v) = *g.parameters.last().unwrap();
+        assert!((u - t.rem_euclid(TAU)).abs() < 1e-8, "{}", u);
+        assert!((v - t).abs() < 1e-8);
+        assert!(g.parameters.iter().all(|&(u, _)| (0.0..=TAU).contains(&u)));
+    }
+
+    #[test]
+    fn test_trace_stops_at_boundary() {
+        let patch = BSplineSurface::from_flat_knots(
+            1,
+            1,
+            vec![
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+                vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+            ],
+            vec![0.0, 0.0, 1.0, 1.0],
+            vec![0.0, 0.0, 1.0, 1.0],
+        )
+        .unwrap();
+        let g =
+            trace_geodesic(&patch, (0.5, 0.25), Vector3::new(1.0, 1.0, 0.0), 10.0, 0.1).unwrap();
+        assert!((g.length - 0.5 * 2f64.sqrt()).abs() < 1e-9);
+        let (u, v) = *g.parameters.last().unwrap();
+        assert!((u - 1.0).abs() < 1e-9 && (v - 0.75).abs() < 1e-9);
+        assert!(trace_geodesic(&patch, (1.0, 0.5), Vector3::X, 1.0, 0.1).is_err());
+        assert!(trace_geodesic(&patch, (0.5, 0.5), Vector3::Z, 1.0, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_shortest_on_plane_is_straight() {
+        let g = shortest_geodesic(&Plane::xy(), (0.0, 0.0), (3.0, 4.0), 1e-6).unwrap();
+        assert!((g.length - 5.0).abs() < 1e-6);
+        for p in g.curve.points() {
+            assert!((p.x * 4.0 - p.y * 3.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_shortest_on_sphere_is_great_circle_arc() {
+        let s = sphere();
+        let (a, b) = ((0.3, 0.2), (2.0, -0.7));
+        let g = shortest_geodesic(&s, a, b, 1e-5).unwrap();
+        let (p, q) = (s.point_at(a.0, a.1), s.point_at(b.0, b.1));
+        let arc = 2.0 * p.to_vector().angle_between(q.to_vector());
+        assert!((g.length - arc).abs() < 1e-3, "{} != {}", g.length, arc);
+        assert!(g.length <= arc + 1e-9);
+        // 頂点は曲面上にあり、中心と2点を通る平面上に並ぶ
+        let normal = p.to_vector().cross(q.to_vector()).normalized();
+        for p in g.curve.points() {
+            assert!((p.distance(Point3::ORIGIN) - 2.0).abs() < 1e-9);
+            assert!(p.to_vector().dot(normal).abs() < 1e-3);
+        }
+        assert!(shortest_geodesic(&s, a, a, 1e-5).is_err());
+    }
+
+    #[test]
+    fn test_shortest_crosses_seam() {
+        // 継ぎ目をはさむ2点は継ぎ目をまたぐ方が短い
+        let c = cylinder();
+        let g = shortest_geodesic(&c, (0.3, 0.0), (TAU - 0.3, 1.0), 1e-6).unwrap();
+        let expected = (0.6f64 * 0.6 + 1.0).sqrt();
+        assert!((g.length - expected).abs() < 1e-4, "{}", g.length);
+        assert!(g
+            .parameters
+            .iter()
+            .all(|&(u, _)| !(0.31..TAU - 0.31).contains(&u)));
+    }
+}
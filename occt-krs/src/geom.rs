@@ -0,0 +1,352 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::f64::consts::TAU;
+
+use crate::{
+    BSplineCurve, BSplineCurve2, BSplineSurface, Circle, ConicalSurface, CylindricalSurface,
+    Ellipse, ExtrudedSurface, Helix, Hyperbola, Line, Parabola, Plane, RevolvedSurface,
+    SphericalSurface, ToroidalSurface, Transform,
+};
+
+/// 稜線（エッジ）が参照する3次元曲線（OCCT の `Geom_Curve` 相当）
+///
+/// 位相構造から曲線の種類を問わず扱えるように、このクレートの曲線型をまとめた列挙型。
+/// `Curve3` を実装しているので、曲線を受け取るアルゴリズムにそのまま渡せる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GeomCurve {
+    Line(Line),
+    Circle(Circle),
+    Ellipse(Ellipse),
+    Parabola(Parabola),
+    Hyperbola(Hyperbola),
+    Helix(Helix),
+    BSpline(BSplineCurve),
+}
+
+/// 面（フェイス）が参照する曲面（OCCT の `Geom_Surface` 相当）
+///
+/// `Surface` を実装しているので、曲面を受け取るアルゴリズムにそのまま渡せる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GeomSurface {
+    Plane(Plane),
+    Cylinder(CylindricalSurface),
+    Sphere(SphericalSurface),
+    Cone(ConicalSurface),
+    Torus(ToroidalSurface),
+    BSpline(BSplineSurface),
+    Extrusion(ExtrudedSurface<GeomCurve>),
+    Revolution(RevolvedSurface<GeomCurve>),
+}
+
+impl GeomCurve {
+    /// 変換を適用した曲線を返す
+    ///
+    /// 角度をパラメータとする曲線と B-スプライン曲線ではパラメータは変わらないが、
+    /// 直線のように長さをパラメータとする曲線では、スケールを含む変換でパラメータが `|scale|` 倍になる。
+    pub fn transformed(&self, t: &Transform) -> Self {
+        match self {
+            GeomCurve::Line(c) => GeomCurve::Line(c.transformed(t)),
+            GeomCurve::Circle(c) => GeomCurve::Circle(c.transformed(t)),
+            GeomCurve::Ellipse(c) => GeomCurve::Ellipse(c.transformed(t)),
+            GeomCurve::Parabola(c) => GeomCurve::Parabola(c.transformed(t)),
+            GeomCurve::Hyperbola(c) => GeomCurve::Hyperbola(c.transformed(t)),
+            GeomCurve::Helix(c) => GeomCurve::Helix(c.transformed(t)),
+            GeomCurve::BSpline(c) => GeomCurve::BSpline(c.transformed(t)),
+        }
+    }
+
+    /// 変換 `t` を適用した曲線（[`GeomCurve::transformed`]）でパラメータが何倍になるかを返す
+    /// （OCCT の `Geom_Curve::ParametricTransformation` 相当）
+    pub(crate) fn parametric_scale(&self, t: &Transform) -> f64 {
+        match self {
+            GeomCurve::Line(_) | GeomCurve::Parabola(_) => t.scale.abs(),
+            _ => 1.0,
+        }
+    }
+}
+
+impl GeomSurface {
+    /// 変換を適用した曲面を返す
+    ///
+    /// 平面や円柱の母線方向のように長さをパラメータとする方向では、スケールを含む変換でパラメータが `|scale|` 倍になる。
+    pub fn transformed(&self, t: &Transform) -> Self {
+        match self {
+            GeomSurface::Plane(s) => GeomSurface::Plane(s.transformed(t)),
+            GeomSurface::Cylinder(s) => GeomSurface::Cylinder(s.transformed(t)),
+            GeomSurface::Sphere(s) => GeomSurface::Sphere(s.transformed(t)),
+            GeomSurface::Cone(s) => GeomSurface::Cone(s.transformed(t)),
+            GeomSurface::Torus(s) => GeomSurface::Torus(s.transformed(t)),
+            GeomSurface::BSpline(s) => GeomSurface::BSpline(s.transformed(t)),
+            GeomSurface::Extrusion(s) => GeomSurface::Extrusion(ExtrudedSurface::new(
+                s.basis_curve().transformed(t),
+                s.direction().transformed(t),
+            )),
+            GeomSurface::Revolution(s) => GeomSurface::Revolution(RevolvedSurface::new(
+                s.basis_curve().transformed(t),
+                s.axis().transformed(t),
+            )),
+        }
+    }
+
+    /// 鏡映を含む変換 `t` を適用し、パラメータの向きを1つ反転して法線 `Su × Sv` を鏡映した法線の向きにそろえた曲面を返す
+    ///
+    /// [`GeomSurface::transformed`] のままでは局所座標系が左手系になり、法線が鏡映した法線と逆を向く。
+    /// 局所座標系を持つ曲面は右手系に戻す。元の曲面の `(u, v)` の点は、返した反転で写した `(u, v)` の点になる。
+    pub(crate) fn mirror_transformed(&self, t: &Transform) -> (Self, ParameterFlip) {
+        match self.transformed(t) {
+            GeomSurface::Plane(s) => (GeomSurface::Plane(s.u_reversed()), ParameterFlip::U(0.0)),
+            GeomSurface::Cylinder(s) => {
+                (GeomSurface::Cylinder(s.u_reversed()), ParameterFlip::U(TAU))
+            }
+            GeomSurface::Sphere(s) => (GeomSurface::Sphere(s.u_reversed()), ParameterFlip::U(TAU)),
+            GeomSurface::Cone(s) => (GeomSurface::Cone(s.u_reversed()), ParameterFlip::U(TAU)),
+            GeomSurface::Torus(s) => (GeomSurface::Torus(s.u_reversed()), ParameterFlip::U(TAU)),
+            GeomSurface::BSpline(s) => {
+                let (a, b) = s.u_bounds();
+                (
+                    GeomSurface::BSpline(s.u_reversed()),
+                    ParameterFlip::U(a + b),
+                )
+            }
+            GeomSurface::Extrusion(s) => (
+                GeomSurface::Extrusion(ExtrudedSurface::new(
+                    s.basis_curve().clone(),
+                    s.direction().reversed(),
+                )),
+                ParameterFlip::V(0.0),
+            ),
+            // 鏡映した軸まわりの回転は向きが逆になるので、変換しただけで u が反転している
+            s @ GeomSurface::Revolution(_) => (s, ParameterFlip::U(TAU)),
+        }
+    }
+
+    /// 変換 `t` を適用した曲面（[`GeomSurface::transformed`]）で `(u, v)` パラメータがそれぞれ何倍になるかを返す
+    /// （OCCT の `Geom_Surface::ParametricTransformation` 相当）
+    pub(crate) fn parametric_scale(&self, t: &Transform) -> (f64, f64) {
+        let k = t.scale.abs();
+        match self {
+            GeomSurface::Plane(_) => (k, k),
+            GeomSurface::Cylinder(_) | GeomSurface::Cone(_) => (1.0, k),
+            GeomSurface::Sphere(_) | GeomSurface::Torus(_) | GeomSurface::BSpline(_) => (1.0, 1.0),
+            GeomSurface::Extrusion(s) => (s.basis_curve().parametric_scale(t), k),
+            GeomSurface::Revolution(s) => (1.0, s.basis_curve().parametric_scale(t)),
+        }
+    }
+}
+
+/// 曲面のパラメータの反転（[`GeomSurface::mirror_transformed`] の戻り値）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ParameterFlip {
+    /// `(u, v) → (c - u, v)`
+    U(f64),
+    /// `(u, v) → (u, c - v)`
+    V(f64),
+}
+
+impl ParameterFlip {
+    /// pcurve を反転したパラメータ空間へ写す
+    pub(crate) fn apply(&self, pcurve: &BSplineCurve2) -> BSplineCurve2 {
+        match *self {
+            ParameterFlip::U(c) => pcurve.mapped(-1.0, c, 1.0, 0.0),
+            ParameterFlip::V(c) => pcurve.mapped(1.0, 0.0, -1.0, c),
+        }
+    }
+}
+
+impl From<Line> for GeomCurve {
+    fn from(c: Line) -> Self {
+        GeomCurve::Line(c)
+    }
+}
+
+impl From<Circle> for GeomCurve {
+    fn from(c: Circle) -> Self {
+        GeomCurve::Circle(c)
+    }
+}
+
+impl From<Ellipse> for GeomCurve {
+    fn from(c: Ellipse) -> Self {
+        GeomCurve::Ellipse(c)
+    }
+}
+
+impl From<Parabola> for GeomCurve {
+    fn from(c: Parabola) -> Self {
+        GeomCurve::Parabola(c)
+    }
+}
+
+impl From<Hyperbola> for GeomCurve {
+    fn from(c: Hyperbola) -> Self {
+        GeomCurve::Hyperbola(c)
+    }
+}
+
+impl From<Helix> for GeomCurve {
+    fn from(c: Helix) -> Self {
+        GeomCurve::Helix(c)
+    }
+}
+
+impl From<BSplineCurve> for GeomCurve {
+    fn from(c: BSplineCurve) -> Self {
+        GeomCurve::BSpline(c)
+    }
+}
+
+impl From<Plane> for GeomSurface {
+    fn from(s: Plane) -> Self {
+        GeomSurface::Plane(s)
+    }
+}
+
+impl From<CylindricalSurface> for GeomSurface {
+    fn from(s: CylindricalSurface) -> Self {
+        GeomSurface::Cylinder(s)
+    }
+}
+
+impl From<SphericalSurface> for GeomSurface {
+    fn from(s: SphericalSurface) -> Self {
+        GeomSurface::Sphere(s)
+    }
+}
+
+impl From<ConicalSurface> for GeomSurface {
+    fn from(s: ConicalSurface) -> Self {
+        GeomSurface::Cone(s)
+    }
+}
+
+impl From<ToroidalSurface> for GeomSurface {
+    fn from(s: ToroidalSurface) -> Self {
+        GeomSurface::Torus(s)
+    }
+}
+
+impl From<BSplineSurface> for GeomSurface {
+    fn from(s: BSplineSurface) -> Self {
+        GeomSurface::BSpline(s)
+    }
+}
+
+impl From<ExtrudedSurface<GeomCurve>> for GeomSurface {
+    fn from(s: ExtrudedSurface<GeomCurve>) -> Self {
+        GeomSurface::Extrusion(s)
+    }
+}
+
+impl From<RevolvedSurface<GeomCurve>> for GeomSurface {
+    fn from(s: RevolvedSurface<GeomCurve>) -> Self {
+        GeomSurface::Revolution(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, Axis3, Curve3, Dir, Point3, Surface};
+
+    #[test]
+    fn test_curve_delegates_to_variant() {
+        let circle = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap();
+        let curve = GeomCurve::from(circle);
+        assert!(curve.is_closed());
+        assert_eq!(Curve3::point_at(&curve, 1.0), circle.point_at(1.0));
+        assert!((curve.arc_length() - circle.length()).abs() < 1e-9);
+        let line = GeomCurve::from(Line::new(Point3::ORIGIN, Dir::X));
+        assert_eq!(line.first_parameter(), f64::NEG_INFINITY);
+        assert_eq!(line.project(Point3::new(3.0, 1.0, 0.0))[0].parameter, 3.0);
+    }
+
+    #[test]
+    fn test_surface_delegates_to_variant() {
+        let sphere =
+            SphericalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap();
+        let surface = GeomSurface::from(sphere);
+        assert!(surface.is_u_closed());
+        assert!(surface.as_analytic().is_some());
+        assert_eq!(
+            Surface::point_at(&surface, 0.5, 0.3),
+            Surface::point_at(&sphere, 0.5, 0.3)
+        );
+        let proj = surface.project(Point3::new(0.0, 0.0, 5.0)).unwrap();
+        assert!((proj.distance - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transformed_keeps_parameters() {
+        let t = Transform::new(
+            crate::Quaternion::from_axis_angle(crate::Vector3::Z, 0.7),
+            crate::Vector3::new(1.0, -2.0, 0.5),
+            1.5,
+        );
+        let circle = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Y), 2.0).unwrap();
+        let curve = GeomCurve::from(circle);
+        let moved = curve.transformed(&t);
+        for s in [0.0, 1.0, 4.0] {
+            let expected = t.transform_point(Curve3::point_at(&curve, s));
+            assert!(Curve3::point_at(&moved, s).distance(expected) < 1e-12);
+        }
+        let plane = GeomSurface::from(Plane::xy());
+        let moved = plane.transformed(&t);
+        let expected = t.transform_point(Surface::point_at(&plane, 0.3, -0.4));
+        assert!(Surface::point_at(&moved, 0.3 * 1.5, -0.4 * 1.5).distance(expected) < 1e-12);
+    }
+
+    #[test]
+    fn test_mirror_transformed_keeps_points_and_normals() {
+        use crate::{Axis1, Vector3};
+        let normal = Dir::from_vector(Vector3::new(1.0, 1.0, 0.3)).unwrap();
+        let mirror = Plane::new(Point3::new(0.5, 0.2, 0.0), normal).mirror_transform();
+        let t = Transform::from_scale(1.5) * mirror;
+        let frame = Axis3::new(Point3::new(0.1, 0.2, 0.3), Dir::Z, Dir::X).unwrap();
+        let grid = (0..3)
+            .map(|i| {
+                (0..3)
+                    .map(|j| Point3::new(i as f64, j as f64, 0.2 * (i * j) as f64))
+                    .collect()
+            })
+            .collect();
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let segment = GeomCurve::from(
+            Line::from_points(Point3::new(2.0, 0.0, 0.0), Point3::new(3.0, 0.0, 1.0)).unwrap(),
+        );
+        let surfaces = [
+            GeomSurface::from(Plane::from_axis(frame)),
+            GeomSurface::from(CylindricalSurface::new(frame, 2.0).unwrap()),
+            GeomSurface::from(SphericalSurface::new(frame, 2.0).unwrap()),
+            GeomSurface::from(ConicalSurface::new(frame, 0.4, 2.0).unwrap()),
+            GeomSurface::from(ToroidalSurface::new(frame, 3.0, 1.0).unwrap()),
+            GeomSurface::from(
+                BSplineSurface::from_flat_knots(2, 2, grid, knots.clone(), knots).unwrap(),
+            ),
+            GeomSurface::Extrusion(ExtrudedSurface::new(segment.clone(), Dir::Y)),
+            GeomSurface::Revolution(RevolvedSurface::new(segment, Axis1::oz())),
+        ];
+        for surface in surfaces {
+            let (mirrored, flip) = surface.mirror_transformed(&t);
+            let (ku, kv) = surface.parametric_scale(&t);
+            for (u0, v0) in [(0.3, 0.4), (0.7, 0.1), (0.5, 0.9)] {
+                let (u, v) = match flip {
+                    ParameterFlip::U(c) => (c - u0 * ku, v0 * kv),
+                    ParameterFlip::V(c) => (u0 * ku, c - v0 * kv),
+                };
+                let expected = t.transform_point(Surface::point_at(&surface, u0, v0));
+                let p = Surface::point_at(&mirrored, u, v);
+                assert!(p.distance(expected) < 1e-9, "{:?}", surface);
+                // 法線は鏡映した法線と同じ向きになる
+                let n = surface.normal_at(u0, v0).unwrap().to_vector();
+                let m = mirrored.normal_at(u, v).unwrap().to_vector();
+                assert!(m.dot(t.transform_vector(n)) > 0.0, "{:?}", surface);
+            }
+            if let GeomSurface::Cylinder(c) = &mirrored {
+                assert!(c.position().is_direct());
+            }
+        }
+    }
+}
@@ -0,0 +1,296 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f64::consts::TAU;
+
+use crate::{Axis2, OcctKrsError, Point3, Result, Transform, Vector3};
+
+/// 螺旋の巻き方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Handedness {
+    /// 右巻き（軸方向に進みながら反時計回りに回る）
+    Right,
+    /// 左巻き（軸方向に進みながら時計回りに回る）
+    Left,
+}
+
+/// 円柱上の螺旋（つるまき線）
+///
+/// 局所座標系 `position` の主方向を軸とし、角度パラメータ `t`（0〜2π × 巻き数）の点は
+/// `center + r (cos t · X ± sin t · Y) + pitch · t / 2π · Z` で表される（符号は巻き方向で決まる）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Helix {
+    position: Axis2,
+    radius: f64,
+    pitch: f64,
+    turns: f64,
+    handedness: Handedness,
+}
+
+impl Helix {
+    /// 局所座標系・半径・ピッチ（1巻きあたりの軸方向の進み）・巻き数・巻き方向から螺旋を生成する
+    /// 半径・ピッチが負、巻き数が正でない、またはいずれかが有限でない場合はエラーを返す
+    pub fn new(
+        position: Axis2,
+        radius: f64,
+        pitch: f64,
+        turns: f64,
+        handedness: Handedness,
+    ) -> Result<Self> {
+        if !(radius >= 0.0 && radius.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "螺旋の半径が不正です: {}",
+                radius
+            )));
+        }
+        if !(pitch >= 0.0 && pitch.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "螺旋のピッチが不正です: {}",
+                pitch
+            )));
+        }
+        if !(turns > 0.0 && turns.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "螺旋の巻き数が不正です: {}",
+                turns
+            )));
+        }
+        Ok(Self {
+            position,
+            radius,
+            pitch,
+            turns,
+            handedness,
+        })
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis2 {
+        self.position
+    }
+
+    /// 半径を返す
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// ピッチを返す
+    pub fn pitch(&self) -> f64 {
+        self.pitch
+    }
+
+    /// 巻き数を返す
+    pub fn turns(&self) -> f64 {
+        self.turns
+    }
+
+    /// 巻き方向を返す
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    /// 軸方向の高さ（ピッチ × 巻き数）を返す
+    pub fn height(&self) -> f64 {
+        self.pitch * self.turns
+    }
+
+    /// 全長を計算する
+    pub fn length(&self) -> f64 {
+        self.speed() * self.last_parameter()
+    }
+
+    /// パラメータ範囲の終わり（2π × 巻き数）を返す
+    pub fn last_parameter(&self) -> f64 {
+        TAU * self.turns
+    }
+
+    /// 角度 `t` における点を返す
+    pub fn point_at(&self, t: f64) -> Point3 {
+        let (s, c) = t.sin_cos();
+        self.position.point_at(
+            self.radius * c,
+            self.sign() * self.radius * s,
+            self.rise() * t,
+        )
+    }
+
+    /// 角度 `t` における `n` 階微分を返す（`n` が 0 のときは原点からの位置ベクトル）
+    pub fn nth_derivative_at(&self, t: f64, n: usize) -> Vector3 {
+        if n == 0 {
+            return self.point_at(t).to_vector();
+        }
+        // cos と sin の n 階微分は位相を nπ/2 進めたもの
+        let phase = t + n as f64 * std::f64::consts::FRAC_PI_2;
+        let (s, c) = phase.sin_cos();
+        let axial = if n == 1 { self.rise() } else { 0.0 };
+        self.position.x_direction().to_vector() * (self.radius * c)
+            + self.position.y_direction().to_vector() * (self.sign() * self.radius * s)
+            + self.position.direction().to_vector() * axial
+    }
+
+    /// 角度 `t` における接ベクトル（角度についての1階微分）を返す
+    pub fn derivative_at(&self, t: f64) -> Vector3 {
+        self.nth_derivative_at(t, 1)
+    }
+
+    /// 曲率（一定値 `r / (r² + c²)`、`c = pitch / 2π`）を返す
+    pub fn curvature(&self) -> f64 {
+        let denom = self.speed().powi(2);
+        if denom == 0.0 {
+            0.0
+        } else {
+            self.radius / denom
+        }
+    }
+
+    /// 捩率（一定値 `± c / (r² + c²)`、右巻きで正）を返す
+    pub fn torsion(&self) -> f64 {
+        let denom = self.speed().powi(2);
+        if denom == 0.0 {
+            0.0
+        } else {
+            self.sign() * self.rise() / denom
+        }
+    }
+
+    /// 変換を適用した螺旋を返す
+    ///
+    /// 負のスケールは鏡映を含むため、巻き方向が反転する。
+    pub fn transformed(&self, t: &Transform) -> Self {
+        let mut position = self.position.transformed(t);
+        let mut handedness = self.handedness;
+        if t.scale < 0.0 {
+            // 座標系は右手系に保たれ軸方向が反転しないので、軸を反転して巻き方向を入れ替える
+            // （X方向は主方向と直交しているので失敗しない）
+            position = Axis2::new(
+                position.location(),
+                position.direction().reversed(),
+                position.x_direction(),
+            )
+            .unwrap();
+            handedness = match handedness {
+                Handedness::Right => Handedness::Left,
+                Handedness::Left => Handedness::Right,
+            };
+        }
+        Self {
+            position,
+            radius: self.radius * t.scale.abs(),
+            pitch: self.pitch * t.scale.abs(),
+            turns: self.turns,
+            handedness,
+        }
+    }
+
+    /// 角度あたりの軸方向の進み `pitch / 2π`
+    fn rise(&self) -> f64 {
+        self.pitch / TAU
+    }
+
+    /// 速さ（一定値 `√(r² + c²)`）
+    fn speed(&self) -> f64 {
+        self.radius.hypot(self.rise())
+    }
+
+    fn sign(&self) -> f64 {
+        match self.handedness {
+            Handedness::Right => 1.0,
+            Handedness::Left => -1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Curve3, Dir, Quaternion};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn spring(handedness: Handedness) -> Helix {
+        Helix::new(Axis2::world(), 2.0, 0.5, 3.0, handedness).unwrap()
+    }
+
+    #[test]
+    fn test_new_validates() {
+        let a = Axis2::world();
+        assert!(Helix::new(a, -1.0, 1.0, 1.0, Handedness::Right).is_err());
+        assert!(Helix::new(a, 1.0, -1.0, 1.0, Handedness::Right).is_err());
+        assert!(Helix::new(a, 1.0, 1.0, 0.0, Handedness::Right).is_err());
+        assert!(Helix::new(a, 1.0, f64::NAN, 1.0, Handedness::Right).is_err());
+    }
+
+    #[test]
+    fn test_points_and_handedness() {
+        let right = spring(Handedness::Right);
+        let left = spring(Handedness::Left);
+        assert_point_eq(right.point_at(0.0), Point3::new(2.0, 0.0, 0.0));
+        assert_point_eq(right.point_at(TAU), Point3::new(2.0, 0.0, 0.5));
+        assert_point_eq(right.point_at(TAU / 4.0), Point3::new(0.0, 2.0, 0.125));
+        assert_point_eq(left.point_at(TAU / 4.0), Point3::new(0.0, -2.0, 0.125));
+        assert_point_eq(
+            right.point_at(right.last_parameter()),
+            Point3::new(2.0, 0.0, 1.5),
+        );
+        assert!((right.height() - 1.5).abs() < 1e-15);
+        assert!(right.torsion() > 0.0);
+        assert!(left.torsion() < 0.0);
+    }
+
+    #[test]
+    fn test_length_curvature_and_torsion_match_generic() {
+        let h = spring(Handedness::Left);
+        let expected = 3.0 * (TAU * 2.0).hypot(0.5);
+        assert!((h.length() - expected).abs() < 1e-12);
+        assert!((Curve3::arc_length(&h) - expected).abs() < 1e-12);
+        assert!((h.arc_length_between(1.0, 2.0) - expected / h.last_parameter()).abs() < 1e-12);
+        for t in [0.3, 2.0, 10.0] {
+            assert!((h.curvature_at(t) - h.curvature()).abs() < 1e-12);
+            assert!((h.torsion_at(t) - h.torsion()).abs() < 1e-12);
+        }
+        // 微分は数値微分と一致する
+        let eps = 1e-6;
+        let numeric = (h.point_at(1.0 + eps) - h.point_at(1.0 - eps)) / (2.0 * eps);
+        assert!((numeric - h.derivative_at(1.0)).length() < 1e-8);
+        assert!(!h.is_closed());
+    }
+
+    #[test]
+    fn test_transformed_including_mirror() {
+        let h = Helix::new(
+            Axis2::from_normal(Point3::new(1.0, 2.0, 3.0), Dir::new(1.0, 1.0, 0.0).unwrap()),
+            1.5,
+            0.8,
+            2.5,
+            Handedness::Right,
+        )
+        .unwrap();
+        for t in [
+            Transform::new(
+                Quaternion::from_axis_angle(Vector3::Z, 0.7),
+                Vector3::new(1.0, 0.0, -2.0),
+                2.0,
+            ),
+            Transform::new(
+                Quaternion::from_axis_angle(Vector3::X, 1.1),
+                Vector3::new(0.5, 1.0, 0.0),
+                -1.5,
+            ),
+        ] {
+            let moved = h.transformed(&t);
+            for s in [0.0, 1.0, 7.0, h.last_parameter()] {
+                assert_point_eq(moved.point_at(s), t.transform_point(h.point_at(s)));
+            }
+            let mirrored = t.scale < 0.0;
+            assert_eq!(
+                moved.handedness() == Handedness::Left,
+                mirrored,
+                "scale {}",
+                t.scale
+            );
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! 他の線形代数ライブラリとの相互変換
+//!
+//! 各ライブラリの型との `From`/`Into` による変換は、同名の機能（`glam`・`nalgebra`・`mint`）を
+//! 有効にすると使える。機能を有効にしない場合も、各ライブラリが受け付ける配列表現
+//! （列優先の行列、`[x, y, z, w]` 順の四元数）を経由して変換できる。
+//!
+//! | 型 | 配列表現 | glam | nalgebra | mint |
+//! |----|----------|------|----------|------|
+//! | `Vector3` / `Point3` | `[f64; 3]` | `DVec3::from_array` | `Vector3::from` | `Vector3::from` |
+//! | `Matrix4` | `[f64; 16]`（列優先） | `DMat4::from_cols_array` | `Matrix4::from_column_slice` | `ColumnMatrix4::from` (`[[f64; 4]; 4]`) |
+//! | `Quaternion` | `[f64; 4]`（`[x, y, z, w]`） | `DQuat::from_array` | `Quaternion::from(Vector4)` | `Quaternion::from` |
+
+use crate::{Matrix4, Point3, Quaternion, Vector3};
+
+impl Matrix4 {
+    /// 列優先の配列に変換する
+    pub fn to_cols_array(&self) -> [f64; 16] {
+        let mut a = [0.0; 16];
+        for (j, col) in a.chunks_exact_mut(4).enumerate() {
+            for (i, v) in col.iter_mut().enumerate() {
+                *v = self.m[i][j];
+            }
+        }
+        a
+    }
+
+    /// 列優先の配列から行列を生成する
+    pub fn from_cols_array(a: &[f64; 16]) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (j, col) in a.chunks_exact(4).enumerate() {
+            for (i, v) in col.iter().enumerate() {
+                m[i][j] = *v;
+            }
+        }
+        Self::from_rows(m)
+    }
+
+    /// 列ごとの2次元配列に変換する（`[列][行]`）
+    pub fn to_cols_array_2d(&self) -> [[f64; 4]; 4] {
+        self.transpose().m
+    }
+
+    /// 列ごとの2次元配列（`[列][行]`）から行列を生成する
+    pub fn from_cols_array_2d(cols: &[[f64; 4]; 4]) -> Self {
+        Self::from_rows(*cols).transpose()
+    }
+}
+
+impl Quaternion {
+    /// `[x, y, z, w]` の順の配列に変換する
+    pub fn to_array(self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// `[x, y, z, w]` の順の配列から四元数を生成する
+    pub fn from_array(a: [f64; 4]) -> Self {
+        Self::new(a[3], a[0], a[1], a[2])
+    }
+}
+
+impl Point3 {
+    /// 成分を配列として返す
+    pub fn to_array(self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl From<(f64, f64, f64)> for Vector3 {
+    fn from((x, y, z): (f64, f64, f64)) -> Self {
+        Vector3::new(x, y, z)
+    }
+}
+
+impl From<Vector3> for (f64, f64, f64) {
+    fn from(v: Vector3) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl From<[f64; 3]> for Point3 {
+    fn from(a: [f64; 3]) -> Self {
+        Point3::new(a[0], a[1], a[2])
+    }
+}
+
+impl From<Point3> for [f64; 3] {
+    fn from(p: Point3) -> Self {
+        p.to_array()
+    }
+}
+
+/// `[x, y, z, w]` の順の配列からの変換
+impl From<[f64; 4]> for Quaternion {
+    fn from(a: [f64; 4]) -> Self {
+        Quaternion::from_array(a)
+    }
+}
+
+/// `[x, y, z, w]` の順の配列への変換
+impl From<Quaternion> for [f64; 4] {
+    fn from(q: Quaternion) -> Self {
+        q.to_array()
+    }
+}
+
+/// 列優先の配列からの変換
+impl From<[f64; 16]> for Matrix4 {
+    fn from(a: [f64; 16]) -> Self {
+        Matrix4::from_cols_array(&a)
+    }
+}
+
+/// 列優先の配列への変換
+impl From<Matrix4> for [f64; 16] {
+    fn from(m: Matrix4) -> Self {
+        m.to_cols_array()
+    }
+}
+
+/// glam の倍精度の型との変換
+#[cfg(feature = "glam")]
+mod glam_conversions {
+    use crate::{Matrix4, Point3, Quaternion, Vector3};
+    use glam::{DMat4, DQuat, DVec3};
+
+    impl From<DVec3> for Vector3 {
+        fn from(v: DVec3) -> Self {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<Vector3> for DVec3 {
+        fn from(v: Vector3) -> Self {
+            DVec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<DVec3> for Point3 {
+        fn from(v: DVec3) -> Self {
+            Point3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<Point3> for DVec3 {
+        fn from(p: Point3) -> Self {
+            DVec3::new(p.x, p.y, p.z)
+        }
+    }
+
+    impl From<DMat4> for Matrix4 {
+        fn from(m: DMat4) -> Self {
+            Matrix4::from_cols_array(&m.to_cols_array())
+        }
+    }
+
+    impl From<Matrix4> for DMat4 {
+        fn from(m: Matrix4) -> Self {
+            DMat4::from_cols_array(&m.to_cols_array())
+        }
+    }
+
+    impl From<DQuat> for Quaternion {
+        fn from(q: DQuat) -> Self {
+            Quaternion::from_array(q.to_array())
+        }
+    }
+
+    impl From<Quaternion> for DQuat {
+        fn from(q: Quaternion) -> Self {
+            DQuat::from_array(q.to_array())
+        }
+    }
+}
+
+/// nalgebra の `f64` の型との変換
+#[cfg(feature = "nalgebra")]
+mod nalgebra_conversions {
+    use crate::{Matrix4, Point3, Quaternion, Vector3};
+
+    impl From<nalgebra::Vector3<f64>> for Vector3 {
+        fn from(v: nalgebra::Vector3<f64>) -> Self {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<Vector3> for nalgebra::Vector3<f64> {
+        fn from(v: Vector3) -> Self {
+            nalgebra::Vector3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<nalgebra::Point3<f64>> for Point3 {
+        fn from(p: nalgebra::Point3<f64>) -> Self {
+            Point3::new(p.x, p.y, p.z)
+        }
+    }
+
+    impl From<Point3> for nalgebra::Point3<f64> {
+        fn from(p: Point3) -> Self {
+            nalgebra::Point3::new(p.x, p.y, p.z)
+        }
+    }
+
+    impl From<nalgebra::Matrix4<f64>> for Matrix4 {
+        fn from(m: nalgebra::Matrix4<f64>) -> Self {
+            let mut a = [0.0; 16];
+            a.copy_from_slice(m.as_slice());
+            Matrix4::from_cols_array(&a)
+        }
+    }
+
+    impl From<Matrix4> for nalgebra::Matrix4<f64> {
+        fn from(m: Matrix4) -> Self {
+            nalgebra::Matrix4::from_column_slice(&m.to_cols_array())
+        }
+    }
+
+    impl From<nalgebra::Quaternion<f64>> for Quaternion {
+        fn from(q: nalgebra::Quaternion<f64>) -> Self {
+            Quaternion::new(q.w, q.i, q.j, q.k)
+        }
+    }
+
+    impl From<Quaternion> for nalgebra::Quaternion<f64> {
+        fn from(q: Quaternion) -> Self {
+            nalgebra::Quaternion::new(q.w, q.x, q.y, q.z)
+        }
+    }
+
+    /// 単位四元数の中身（正規化済みの四元数）からの変換
+    impl From<nalgebra::UnitQuaternion<f64>> for Quaternion {
+        fn from(q: nalgebra::UnitQuaternion<f64>) -> Self {
+            q.into_inner().into()
+        }
+    }
+}
+
+/// mint の `f64` の型との変換
+#[cfg(feature = "mint")]
+mod mint_conversions {
+    use crate::{Matrix4, Point3, Quaternion, Vector3};
+
+    impl From<mint::Vector3<f64>> for Vector3 {
+        fn from(v: mint::Vector3<f64>) -> Self {
+            Vector3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl From<Vector3> for mint::Vector3<f64> {
+        fn from(v: Vector3) -> Self {
+            mint::Vector3 {
+                x: v.x,
+                y: v.y,
+                z: v.z,
+            }
+        }
+    }
+
+    impl From<mint::Point3<f64>> for Point3 {
+        fn from(p: mint::Point3<f64>) -> Self {
+            Point3::new(p.x, p.y, p.z)
+        }
+    }
+
+    impl From<Point3> for mint::Point3<f64> {
+        fn from(p: Point3) -> Self {
+            mint::Point3 {
+                x: p.x,
+                y: p.y,
+                z: p.z,
+            }
+        }
+    }
+
+    impl From<mint::ColumnMatrix4<f64>> for Matrix4 {
+        fn from(m: mint::ColumnMatrix4<f64>) -> Self {
+            Matrix4::from_cols_array_2d(&m.into())
+        }
+    }
+
+    impl From<Matrix4> for mint::ColumnMatrix4<f64> {
+        fn from(m: Matrix4) -> Self {
+            m.to_cols_array_2d().into()
+        }
+    }
+
+    impl From<mint::Quaternion<f64>> for Quaternion {
+        fn from(q: mint::Quaternion<f64>) -> Self {
+            Quaternion::new(q.s, q.v.x, q.v.y, q.v.z)
+        }
+    }
+
+    impl From<Quaternion> for mint::Quaternion<f64> {
+        fn from(q: Quaternion) -> Self {
+            mint::Quaternion {
+                v: mint::Vector3 {
+                    x: q.x,
+                    y: q.y,
+                    z: q.z,
+                },
+                s: q.w,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_column_major_layout() {
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let a = m.to_cols_array();
+        // 平行移動成分は列優先では末尾の列（12〜14番目）に入る
+        assert_eq!(&a[12..15], &[1.0, 2.0, 3.0]);
+        assert_eq!(Matrix4::from_cols_array(&a), m);
+        assert_eq!(m.to_cols_array_2d()[3], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(Matrix4::from_cols_array_2d(&m.to_cols_array_2d()), m);
+        let back: Matrix4 = <[f64; 16]>::from(m).into();
+        assert_eq!(back, m);
+    }
+
+    #[test]
+    fn test_quaternion_xyzw_layout() {
+        let q = Quaternion::new(0.5, 0.1, 0.2, 0.3);
+        assert_eq!(q.to_array(), [0.1, 0.2, 0.3, 0.5]);
+        assert_eq!(Quaternion::from([0.1, 0.2, 0.3, 0.5]), q);
+    }
+
+    #[test]
+    fn test_tuple_and_point_arrays() {
+        let v: Vector3 = (1.0, 2.0, 3.0).into();
+        assert_eq!(<(f64, f64, f64)>::from(v), (1.0, 2.0, 3.0));
+        let p = Point3::from([4.0, 5.0, 6.0]);
+        assert_eq!(<[f64; 3]>::from(p), [4.0, 5.0, 6.0]);
+    }
+
+    /// 各ライブラリとの変換を確かめる行列（平行移動と回転を含む）と四元数
+    #[cfg(any(feature = "glam", feature = "nalgebra", feature = "mint"))]
+    fn samples() -> (Matrix4, Quaternion) {
+        let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let mut m = m.m;
+        m[0][1] = 0.5;
+        m[2][0] = -0.25;
+        (Matrix4::from_rows(m), Quaternion::new(0.5, 0.1, 0.2, 0.3))
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_conversions() {
+        use glam::{DMat4, DQuat, DVec3};
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(DVec3::from(v), DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(DVec3::from(v)), v);
+        let p: DVec3 = Point3::new(4.0, 5.0, 6.0).into();
+        assert_eq!(Point3::from(p), Point3::new(4.0, 5.0, 6.0));
+        let (m, q) = samples();
+        let g = DMat4::from(m);
+        // 平行移動は4列目、行列の成分は同じ行・列に入る
+        assert_eq!(g.col(3).truncate(), DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(g.row(0).y, m.m[0][1]);
+        assert_eq!(Matrix4::from(g), m);
+        let g = DQuat::from(q);
+        assert_eq!((g.w, g.x, g.y, g.z), (0.5, 0.1, 0.2, 0.3));
+        assert_eq!(Quaternion::from(g), q);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra_conversions() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let n: nalgebra::Vector3<f64> = v.into();
+        assert_eq!(n, nalgebra::Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(n), v);
+        let p = nalgebra::Point3::new(4.0, 5.0, 6.0);
+        assert_eq!(nalgebra::Point3::from(Point3::from(p)), p);
+        let (m, q) = samples();
+        let n = nalgebra::Matrix4::from(m);
+        assert_eq!(n[(0, 1)], m.m[0][1]);
+        assert_eq!(n[(2, 0)], m.m[2][0]);
+        assert_eq!(n[(0, 3)], 1.0);
+        assert_eq!(Matrix4::from(n), m);
+        let n = nalgebra::Quaternion::from(q);
+        assert_eq!((n.w, n.i, n.j, n.k), (0.5, 0.1, 0.2, 0.3));
+        assert_eq!(Quaternion::from(n), q);
+        let unit = nalgebra::UnitQuaternion::from_quaternion(n);
+        let back = Quaternion::from(unit);
+        assert!(
+            (back.w * back.w + back.x * back.x + back.y * back.y + back.z * back.z - 1.0).abs()
+                < 1e-12
+        );
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn test_mint_conversions() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let t: mint::Vector3<f64> = v.into();
+        assert_eq!((t.x, t.y, t.z), (1.0, 2.0, 3.0));
+        assert_eq!(Vector3::from(t), v);
+        let p = Point3::new(4.0, 5.0, 6.0);
+        assert_eq!(Point3::from(mint::Point3::from(p)), p);
+        let (m, q) = samples();
+        let t = mint::ColumnMatrix4::from(m);
+        assert_eq!(t.y.x, m.m[0][1]);
+        assert_eq!(t.w.z, 3.0);
+        assert_eq!(Matrix4::from(t), m);
+        let t = mint::Quaternion::from(q);
+        assert_eq!((t.s, t.v.x, t.v.y, t.v.z), (0.5, 0.1, 0.2, 0.3));
+        assert_eq!(Quaternion::from(t), q);
+    }
+}
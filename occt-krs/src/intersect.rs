@@ -0,0 +1,463 @@
+use crate::projection::refine_projection;
+use crate::{BoundingBox, Curve3, OcctKrsError, Point3, Result};
+
+/// 交差判定でパラメータ範囲を分割する区間数
+const SAMPLES: usize = 128;
+
+/// 2曲線の交差
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveIntersection {
+    /// 孤立した交点
+    Point {
+        point: Point3,
+        /// 1つ目の曲線のパラメータ
+        u1: f64,
+        /// 2つ目の曲線のパラメータ
+        u2: f64,
+        /// 交点で2曲線が接している（接線が平行）場合は `true`
+        tangent: bool,
+    },
+    /// 2曲線が重なっている区間
+    Overlap {
+        /// 1つ目の曲線のパラメータ範囲
+        first: (f64, f64),
+        /// 2つ目の曲線のパラメータ範囲（昇順）
+        second: (f64, f64),
+    },
+}
+
+/// 2曲線の交差を求める（1つ目の曲線のパラメータ順に並べて返す）
+///
+/// パラメータ範囲を等分した折れ線で候補を絞り、ニュートン法で距離が `tolerance` 以下の点に収束させる。
+/// 距離が `tolerance` 以下のまま続く区間は重なりとして返す。
+/// 2次元の曲線は XY 平面上の曲線として扱えばよい。
+/// どちらかのパラメータ範囲が無限、または `tolerance` が正でない場合はエラーを返す。
+pub fn intersect_curves(
+    c1: &(impl Curve3 + ?Sized),
+    c2: &(impl Curve3 + ?Sized),
+    tolerance: f64,
+) -> Result<Vec<CurveIntersection>> {
+    if !(tolerance > 0.0 && tolerance.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "許容誤差が不正です: {}",
+            tolerance
+        )));
+    }
+    let s1 = Sampled::new(c1)?;
+    let s2 = Sampled::new(c2)?;
+
+    let mut overlaps = find_overlaps(c1, &s1, c2, &s2, tolerance);
+    for (second, first) in find_overlaps(c2, &s2, c1, &s1, tolerance) {
+        if !overlaps
+            .iter()
+            .any(|(a, _)| ranges_touch(*a, first, s1.step))
+        {
+            overlaps.push((first, second));
+        }
+    }
+    let in_overlap = |u1: f64, u2: f64| {
+        overlaps.iter().any(|(a, b)| {
+            ranges_touch(*a, (u1, u1), s1.step) && ranges_touch(*b, (u2, u2), s2.step)
+        })
+    };
+
+    let mut points: Vec<(f64, f64, f64)> = Vec::new();
+    for i in 0..SAMPLES {
+        let box1 = s1.segment_box(i, tolerance);
+        for j in 0..SAMPLES {
+            if !box1.intersects(&s2.segment_box(j, tolerance)) {
+                continue;
+            }
+            let (a, b) = closest_segment_parameters(
+                s1.points[i],
+                s1.points[i + 1],
+                s2.points[j],
+                s2.points[j + 1],
+            );
+            let start = (s1.parameter(i, a), s2.parameter(j, b));
+            let Some((u1, u2, d)) = refine(c1, &s1, c2, &s2, start, tolerance) else {
+                continue;
+            };
+            if in_overlap(u1, u2) {
+                continue;
+            }
+            // 隣り合う区間の組から同じ交点に収束したものはまとめる
+            match points
+                .iter_mut()
+                .find(|(v1, v2, _)| s1.near(*v1, u1) && s2.near(*v2, u2))
+            {
+                Some(existing) if existing.2 <= d => {}
+                Some(existing) => *existing = (u1, u2, d),
+                None => points.push((u1, u2, d)),
+            }
+        }
+    }
+
+    let mut result: Vec<CurveIntersection> = points
+        .into_iter()
+        .map(|(u1, u2, _)| {
+            let (d1, d2) = (c1.derivative_at(u1), c2.derivative_at(u2));
+            let sine = d1.cross(d2).length() / (d1.length() * d2.length());
+            CurveIntersection::Point {
+                point: c1.point_at(u1),
+                u1,
+                u2,
+                tangent: sine.is_nan() || sine <= tolerance.sqrt(),
+            }
+        })
+        .chain(
+            overlaps
+                .into_iter()
+                .map(|(first, second)| CurveIntersection::Overlap { first, second }),
+        )
+        .collect();
+    result.sort_by(|a, b| start_parameter(a).total_cmp(&start_parameter(b)));
+    Ok(result)
+}
+
+fn start_parameter(x: &CurveIntersection) -> f64 {
+    match x {
+        CurveIntersection::Point { u1, .. } => *u1,
+        CurveIntersection::Overlap { first, .. } => first.0,
+    }
+}
+
+/// 2つの範囲が `margin` 以内で接するか重なっていれば `true` を返す
+fn ranges_touch(a: (f64, f64), b: (f64, f64), margin: f64) -> bool {
+    a.0 <= b.1 + margin && b.0 <= a.1 + margin
+}
+
+/// 曲線をパラメータ等分した折れ線
+struct Sampled {
+    first: f64,
+    last: f64,
+    step: f64,
+    closed: bool,
+    points: Vec<Point3>,
+    /// 各区間での曲線と弦のずれの見積もり
+    sag: Vec<f64>,
+}
+
+impl Sampled {
+    fn new(curve: &(impl Curve3 + ?Sized)) -> Result<Self> {
+        let (first, last) = (curve.first_parameter(), curve.last_parameter());
+        if !(first.is_finite() && last.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(
+                "交差計算にはパラメータ範囲が有限の曲線が必要です".to_string(),
+            ));
+        }
+        let step = (last - first) / SAMPLES as f64;
+        let points: Vec<Point3> = (0..=SAMPLES)
+            .map(|i| curve.point_at(first + step * i as f64))
+            .collect();
+        let sag = (0..SAMPLES)
+            .map(|i| {
+                let mid = curve.point_at(first + step * (i as f64 + 0.5));
+                let chord_mid = points[i] + (points[i + 1] - points[i]) * 0.5;
+                // 区間内で最大となる位置は中点とは限らないので余裕を持たせる
+                2.0 * mid.distance(chord_mid)
+            })
+            .collect();
+        Ok(Self {
+            first,
+            last,
+            step,
+            closed: curve.is_closed(),
+            points,
+            sag,
+        })
+    }
+
+    /// i 番目の区間上の位置 `t`（0〜1）に対応する曲線のパラメータ
+    fn parameter(&self, i: usize, t: f64) -> f64 {
+        self.first + self.step * (i as f64 + t)
+    }
+
+    fn segment_box(&self, i: usize, tolerance: f64) -> BoundingBox {
+        BoundingBox::new(self.points[i], self.points[i + 1]).enlarged(self.sag[i] + tolerance)
+    }
+
+    /// 2つのパラメータが1区間以内か（閉曲線では始点と終点をつないで測る）
+    fn near(&self, a: f64, b: f64) -> bool {
+        let d = (a - b).abs();
+        d <= self.step || (self.closed && (self.last - self.first) - d <= self.step)
+    }
+
+    fn clamp(&self, u: f64) -> f64 {
+        u.clamp(self.first, self.last)
+    }
+
+    /// 点に最も近い曲線上のパラメータと距離を返す
+    fn project(&self, curve: &(impl Curve3 + ?Sized), p: Point3) -> (f64, f64) {
+        let (i, t) = (0..SAMPLES)
+            .map(|i| {
+                let (a, b) = (self.points[i], self.points[i + 1]);
+                let v = b - a;
+                let len2 = v.dot(v);
+                let t = if len2 > 0.0 {
+                    ((p - a).dot(v) / len2).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                (i, t, p.distance_squared(a + v * t))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(i, t, _)| (i, t))
+            .unwrap();
+        let u = refine_projection(curve, p, self.parameter(i, t), self.first, self.last);
+        (u, curve.point_at(u).distance(p))
+    }
+}
+
+/// 2つの線分上で最も近い点の位置（それぞれ 0〜1）を返す
+fn closest_segment_parameters(p1: Point3, q1: Point3, p2: Point3, q2: Point3) -> (f64, f64) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+    if a <= f64::MIN_POSITIVE && e <= f64::MIN_POSITIVE {
+        return (0.0, 0.0);
+    }
+    if a <= f64::MIN_POSITIVE {
+        return (0.0, (f / e).clamp(0.0, 1.0));
+    }
+    let c = d1.dot(r);
+    if e <= f64::MIN_POSITIVE {
+        return ((-c / a).clamp(0.0, 1.0), 0.0);
+    }
+    let b = d1.dot(d2);
+    let denom = a * e - b * b;
+    let mut s = if denom > 0.0 {
+        ((b * f - c * e) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+    (s, t)
+}
+
+/// 2曲線の距離が最小となるパラメータの組をガウス・ニュートン法で求める
+///
+/// 距離が `tolerance` 以下に収束した場合のみ `(u1, u2, 距離)` を返す。
+/// 接する交点ではヤコビ行列が退化するので、交互に相手の曲線へ射影する方法に切り替える。
+fn refine(
+    c1: &(impl Curve3 + ?Sized),
+    s1: &Sampled,
+    c2: &(impl Curve3 + ?Sized),
+    s2: &Sampled,
+    start: (f64, f64),
+    tolerance: f64,
+) -> Option<(f64, f64, f64)> {
+    let (mut u1, mut u2) = start;
+    for _ in 0..100 {
+        let f = c1.point_at(u1) - c2.point_at(u2);
+        let d1 = c1.derivative_at(u1);
+        let d2 = c2.derivative_at(u2);
+        let (a, b, c) = (d1.dot(d1), -d1.dot(d2), d2.dot(d2));
+        let (g1, g2) = (f.dot(d1), -f.dot(d2));
+        let det = a * c - b * b;
+        let (n1, n2) = if det > 1e-10 * a * c {
+            (
+                s1.clamp(u1 - (c * g1 - b * g2) / det),
+                s2.clamp(u2 - (a * g2 - b * g1) / det),
+            )
+        } else {
+            let n1 = if a > 0.0 { s1.clamp(u1 - g1 / a) } else { u1 };
+            let f = c1.point_at(n1) - c2.point_at(u2);
+            let n2 = if c > 0.0 {
+                s2.clamp(u2 + f.dot(d2) / c)
+            } else {
+                u2
+            };
+            (n1, n2)
+        };
+        let moved = (n1 - u1).abs() + (n2 - u2).abs();
+        u1 = n1;
+        u2 = n2;
+        if moved <= 1e-15 * (1.0 + u1.abs() + u2.abs()) {
+            break;
+        }
+    }
+    let d = c1.point_at(u1).distance(c2.point_at(u2));
+    (d <= tolerance).then_some((u1, u2, d))
+}
+
+/// `c1` の標本点のうち `c2` との距離が許容誤差以下で連続する区間を重なりとして返す
+fn find_overlaps(
+    c1: &(impl Curve3 + ?Sized),
+    s1: &Sampled,
+    c2: &(impl Curve3 + ?Sized),
+    s2: &Sampled,
+    tolerance: f64,
+) -> Vec<((f64, f64), (f64, f64))> {
+    let on = |u: f64| s2.project(c2, c1.point_at(u)).1 <= tolerance;
+    let flags: Vec<bool> = (0..=SAMPLES).map(|i| on(s1.parameter(i, 0.0))).collect();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < SAMPLES {
+        // 両端と中点が乗っている区間が続く範囲を探す
+        if !(flags[i] && flags[i + 1] && on(s1.parameter(i, 0.5))) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < SAMPLES && flags[i] && flags[i + 1] && on(s1.parameter(i, 0.5)) {
+            i += 1;
+        }
+        let mut a = s1.parameter(start, 0.0);
+        let mut b = s1.parameter(i, 0.0);
+        // 境界を二分法で詰める
+        if start > 0 {
+            a = bisect_boundary(&on, a, s1.parameter(start - 1, 0.0));
+        }
+        if i < SAMPLES {
+            b = bisect_boundary(&on, b, s1.parameter(i + 1, 0.0));
+        }
+        let (t0, t1) = (
+            s2.project(c2, c1.point_at(a)).0,
+            s2.project(c2, c1.point_at(b)).0,
+        );
+        result.push(((a, b), (t0.min(t1), t0.max(t1))));
+    }
+    result
+}
+
+/// `inside` が満たされる `a` から満たされない `b` に向かって境界を二分法で求める
+fn bisect_boundary(inside: &impl Fn(f64) -> bool, mut a: f64, mut b: f64) -> f64 {
+    for _ in 0..60 {
+        let m = 0.5 * (a + b);
+        if inside(m) {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arc, Axis2, BezierCurve, Circle, Dir, Line, Segment};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-7, "{:?} != {:?}", a, b);
+    }
+
+    fn points(result: &[CurveIntersection]) -> Vec<(Point3, f64, f64, bool)> {
+        result
+            .iter()
+            .filter_map(|x| match *x {
+                CurveIntersection::Point {
+                    point,
+                    u1,
+                    u2,
+                    tangent,
+                } => Some((point, u1, u2, tangent)),
+                CurveIntersection::Overlap { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_segment_and_circle() {
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        let seg = Segment::new(Point3::new(-2.0, 0.5, 0.0), Point3::new(2.0, 0.5, 0.0));
+        let result = intersect_curves(&seg, &circle, 1e-9).unwrap();
+        let pts = points(&result);
+        assert_eq!(pts.len(), 2);
+        let x = 0.75_f64.sqrt();
+        assert_point_eq(pts[0].0, Point3::new(-x, 0.5, 0.0));
+        assert_point_eq(pts[1].0, Point3::new(x, 0.5, 0.0));
+        assert!((pts[0].2 - 5.0 * std::f64::consts::PI / 6.0).abs() < 1e-7);
+        assert!(pts.iter().all(|p| !p.3));
+    }
+
+    #[test]
+    fn test_tangent_circles() {
+        let a = Circle::new(Axis2::world(), 1.0).unwrap();
+        let b = Circle::from_center_normal(Point3::new(3.0, 0.0, 0.0), Dir::Z, 2.0).unwrap();
+        let pts = points(&intersect_curves(&a, &b, 1e-7).unwrap());
+        assert_eq!(pts.len(), 1);
+        assert_point_eq(pts[0].0, Point3::new(1.0, 0.0, 0.0));
+        assert!(pts[0].3);
+    }
+
+    #[test]
+    fn test_bezier_crosses_line_three_times() {
+        let b = BezierCurve::new(vec![
+            Point3::new(0.0, -1.0, 0.0),
+            Point3::new(1.0, 3.0, 0.0),
+            Point3::new(2.0, -3.0, 0.0),
+            Point3::new(3.0, 1.0, 0.0),
+        ])
+        .unwrap();
+        let axis = Line::new(Point3::ORIGIN, Dir::X)
+            .to_nurbs(-1.0, 4.0)
+            .unwrap();
+        let pts = points(&intersect_curves(&b, &axis, 1e-9).unwrap());
+        assert_eq!(pts.len(), 3);
+        for w in pts.windows(2) {
+            assert!(w[0].1 < w[1].1);
+        }
+        for p in &pts {
+            assert!(p.0.y.abs() < 1e-9);
+            assert!((p.2 - p.0.x).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_skew_and_touching_endpoints() {
+        let a = Segment::new(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0));
+        let b = Segment::new(Point3::new(0.5, -1.0, 1.0), Point3::new(0.5, 1.0, 1.0));
+        assert!(intersect_curves(&a, &b, 1e-7).unwrap().is_empty());
+
+        // 線分の端点どうしが接続している場合
+        let c = Segment::new(Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 2.0, 0.0));
+        let pts = points(&intersect_curves(&a, &c, 1e-7).unwrap());
+        assert_eq!(pts.len(), 1);
+        assert!((pts[0].1 - 1.0).abs() < 1e-12);
+        assert!(pts[0].2.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_overlapping_segments_and_arcs() {
+        let a = Segment::new(Point3::ORIGIN, Point3::new(2.0, 0.0, 0.0));
+        let b = Segment::new(Point3::new(1.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0));
+        let result = intersect_curves(&a, &b, 1e-7).unwrap();
+        assert_eq!(result.len(), 1);
+        let CurveIntersection::Overlap { first, second } = result[0] else {
+            panic!("重なりではありません: {:?}", result[0]);
+        };
+        assert!((first.0 - 0.5).abs() < 1e-7 && (first.1 - 1.0).abs() < 1e-12);
+        assert!(second.0.abs() < 1e-12 && (second.1 - 0.5).abs() < 1e-7);
+
+        // 短い曲線が長い曲線の内側に重なる場合
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        let short = Arc::new(circle, 1.0, 1.01).unwrap();
+        let result = intersect_curves(&circle, &short, 1e-7).unwrap();
+        assert_eq!(result.len(), 1);
+        let CurveIntersection::Overlap { first, second } = result[0] else {
+            panic!("重なりではありません: {:?}", result[0]);
+        };
+        assert!((first.0 - 1.0).abs() < 1e-6 && (first.1 - 1.01).abs() < 1e-6);
+        assert!((second.0 - 1.0).abs() < 1e-6 && (second.1 - 1.01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let line = Line::new(Point3::ORIGIN, Dir::X);
+        let seg = Segment::new(Point3::ORIGIN, Point3::new(0.0, 1.0, 0.0));
+        assert!(intersect_curves(&line, &seg, 1e-7).is_err());
+        assert!(intersect_curves(&seg, &seg, 0.0).is_err());
+    }
+}
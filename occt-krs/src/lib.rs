@@ -1,145 +1,152 @@
-use serde::{Serialize, Deserialize};
-use std::error::Error;
+#[cfg(feature = "serde")]
 use std::fs::File;
+#[cfg(feature = "serde")]
 use std::io::Write;
-use std::ops::{Add, Sub, Mul};
 
-/// 3次元ベクトルを表す構造体
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub struct Vector3 {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
-
-impl Vector3 {
-    /// 新しいベクトルを生成する
-    pub fn new(x: f64, y: f64, z: f64) -> Self {
-        Self { x, y, z }
-    }
-
-    /// 内積を計算する
-    pub fn dot(self, other: Vector3) -> f64 {
-        self.x * other.x + self.y * other.y + self.z * other.z
-    }
-
-    /// 外積を計算する
-    pub fn cross(self, other: Vector3) -> Vector3 {
-        Vector3 {
-            x: self.y * other.z - self.z * other.y,
-            y: self.z * other.x - self.x * other.z,
-            z: self.x * other.y - self.y * other.x,
-        }
-    }
-
-    /// ベクトルの長さ（ノルム）を計算する
-    pub fn length(self) -> f64 {
-        self.dot(self).sqrt()
-    }
-
-    /// 正規化（単位ベクトル化）する  
-    /// ※長さがゼロの場合はpanicするので注意
-    pub fn normalized(self) -> Vector3 {
-        let len = self.length();
-        if len == 0.0 {
-            panic!("ゼロ長ベクトルは正規化できません");
-        }
-        Vector3 {
-            x: self.x / len,
-            y: self.y / len,
-            z: self.z / len,
-        }
-    }
-}
-
-/// Vector3同士の加算の実装
-impl Add for Vector3 {
-    type Output = Self;
-    fn add(self, other: Self) -> Self {
-        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
-    }
-}
-
-/// Vector3同士の減算の実装
-impl Sub for Vector3 {
-    type Output = Self;
-    fn sub(self, other: Self) -> Self {
-        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
-    }
-}
-
-/// Vector3のスカラー倍の実装 (ベクトル * スカラー)
-impl Mul<f64> for Vector3 {
-    type Output = Self;
-    fn mul(self, scalar: f64) -> Self {
-        Vector3::new(self.x * scalar, self.y * scalar, self.z * scalar)
-    }
-}
-
-/// スカラー倍の右側にベクトルを許容するための実装 (スカラー * ベクトル)
-impl Mul<Vector3> for f64 {
-    type Output = Vector3;
-    fn mul(self, vector: Vector3) -> Vector3 {
-        vector * self
-    }
-}
+mod axis;
+pub mod batch;
+mod bezier;
+pub mod boolean;
+mod bounding_box;
+mod bspline;
+mod bspline_fit;
+mod bspline_surface;
+mod chamfer;
+mod check;
+mod circle;
+mod classify;
+mod conic;
+mod continuity;
+mod curve;
+mod curve2;
+mod defeature;
+mod develop;
+mod dir;
+mod draft;
+mod elementary_surface;
+mod emboss;
+mod error;
+mod euler;
+pub mod exact;
+mod explore;
+mod extrema;
+mod extrude;
+mod fillet;
+mod fix;
+mod free_bounds;
+mod general_fuse;
+mod general_transform;
+mod geodesic;
+mod geom;
+mod helix;
+mod interop;
+mod intersect;
+mod line;
+mod location;
+mod loft;
+mod matrix3;
+mod matrix4;
+mod offset;
+mod offset_surface;
+pub mod pattern;
+mod pcurve;
+mod plane;
+mod point3;
+mod polyline;
+pub mod precision;
+pub mod primitives;
+mod projection;
+mod quaternion;
+mod revolve;
+mod sew;
+mod shape_arena;
+mod shape_builder;
+mod surface;
+mod surface_fit;
+mod surface_intersect;
+mod sweep;
+mod swept_surface;
+#[cfg(test)]
+mod test_support;
+mod thread;
+mod topo;
+mod transform;
+mod trimmed_surface;
+mod vector2;
+mod vector3;
+mod vector_f32;
+
+pub use axis::{Axis1, Axis2, Axis3};
+pub use bezier::BezierCurve;
+pub use bounding_box::BoundingBox;
+pub use bspline::BSplineCurve;
+pub use bspline_fit::{Approximation, EndConditions};
+pub use bspline_surface::BSplineSurface;
+pub use chamfer::{chamfer, chamfer_with_angle};
+pub use check::{CheckIssue, CheckReport, CheckStatus};
+pub use circle::{Arc, Circle};
+pub use conic::{Ellipse, Hyperbola, Parabola};
+pub use continuity::{
+    check_continuity, check_edge_continuity, check_junction, Continuity, ContinuityReport,
+};
+pub use curve::{tessellate, tessellate_by_length, Curve3, FrenetFrame, TrimmedCurve};
+pub use curve2::{BSplineCurve2, Curve2, Line2};
+pub use defeature::defeature;
+pub use develop::{flatten, Development, FlatPattern};
+pub use dir::Dir;
+pub use draft::draft;
+pub use elementary_surface::{
+    ConicalSurface, CylindricalSurface, SphericalSurface, ToroidalSurface,
+};
+pub use emboss::{emboss, engrave};
+pub use error::{NormalizeError, OcctKrsError, ParseVectorError, Result};
+pub use euler::{EulerAngles, EulerOrder};
+pub use explore::{AncestorMap, ShapeExplorer};
+pub use extrema::{extrema, CurveExtremum};
+pub use extrude::extrude;
+pub use fillet::{fillet, fillet_with_law, RadiusLaw};
+pub use free_bounds::FreeBounds;
+pub use general_transform::{AffineDecomposition, GeneralTransform};
+pub use geodesic::{shortest_geodesic, trace_geodesic, Geodesic};
+pub use geom::{GeomCurve, GeomSurface};
+pub use helix::{Handedness, Helix};
+pub use intersect::{intersect_curves, CurveIntersection};
+pub use line::{Line, Segment};
+pub use location::Location;
+pub use loft::{loft, RuledSurface};
+pub use matrix3::Matrix3;
+pub use matrix4::Matrix4;
+pub use offset::{offset_shape, shell, thicken, JoinType};
+pub use offset_surface::OffsetSurface;
+pub use pcurve::{project_curve_onto_surface, CurveOnSurface, ProjectedCurve};
+pub use plane::Plane;
+pub use point3::Point3;
+pub use polyline::Polyline3;
+pub use projection::{CurveProjection, SurfaceProjection};
+pub use quaternion::Quaternion;
+pub use revolve::revolve;
+pub use sew::{sew, Sewing};
+pub use shape_arena::{ShapeArena, ShapeHandle, ShapeLink};
+pub use shape_builder::make_face;
+pub use surface::{tessellate_grid, AnalyticSurface, Surface, SurfaceCurvature};
+pub use surface_fit::SurfaceApproximation;
+pub use surface_intersect::{intersect_surfaces, SurfaceIntersection, WalkedCurve};
+pub use sweep::{sweep, sweep_sections, FrameMode};
+pub use swept_surface::{ExtrudedSurface, RevolvedSurface};
+pub use thread::{iso_metric_pitch, iso_metric_thread, iso_thread_profile, thread};
+pub use topo::{Compound, Edge, Face, Orientation, Shape, ShapeKind, Shell, Solid, Vertex, Wire};
+pub use transform::Transform;
+pub use trimmed_surface::{PointClassification, TrimmedSurface, UvLoop};
+pub use vector2::Vector2;
+pub use vector3::Vector3;
+pub use vector_f32::{points_to_f32, points_to_f64, Point3f32, Vector2f32, Vector3f32};
 
 /// Vector3をJSON形式でファイルに出力する関数  
 /// テストなどで、OpenCascade側の出力との比較に利用できます。
-pub fn output_vector_as_json(vector: &Vector3, filename: &str) -> Result<(), Box<dyn Error>> {
+#[cfg(feature = "serde")]
+pub fn output_vector_as_json(vector: &Vector3, filename: &str) -> Result<()> {
     let json = serde_json::to_string_pretty(vector)?;
     let mut file = File::create(filename)?;
     file.write_all(json.as_bytes())?;
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_dot_product() {
-        let a = Vector3::new(1.0, 0.0, 0.0);
-        let b = Vector3::new(0.0, 1.0, 0.0);
-        // 直交するので内積は0
-        assert!((a.dot(b) - 0.0).abs() < 1e-10);
-
-        let c = Vector3::new(1.0, 2.0, 3.0);
-        let d = Vector3::new(4.0, -5.0, 6.0);
-        let dot = c.dot(d);
-        // 計算結果：1*4 + 2*(-5) + 3*6 = 4 - 10 + 18 = 12
-        assert!((dot - 12.0).abs() < 1e-10);
-    }
-
-    #[test]
-    fn test_cross_product() {
-        let a = Vector3::new(1.0, 0.0, 0.0);
-        let b = Vector3::new(0.0, 1.0, 0.0);
-        let cross = a.cross(b);
-        // 右手系で外積は (0, 0, 1)
-        let expected = Vector3::new(0.0, 0.0, 1.0);
-        assert!((cross.x - expected.x).abs() < 1e-10);
-        assert!((cross.y - expected.y).abs() < 1e-10);
-        assert!((cross.z - expected.z).abs() < 1e-10);
-    }
-
-    #[test]
-    fn test_length_and_normalization() {
-        let v = Vector3::new(3.0, 4.0, 0.0);
-        let len = v.length();
-        // 3-4-5の三角形なので長さは5
-        assert!((len - 5.0).abs() < 1e-10);
-
-        let normalized = v.normalized();
-        // 正規化後は長さが1
-        assert!((normalized.length() - 1.0).abs() < 1e-10);
-    }
-
-    #[test]
-    #[should_panic(expected = "ゼロ長ベクトルは正規化できません")]
-    fn test_normalize_zero_vector() {
-        let zero = Vector3::new(0.0, 0.0, 0.0);
-        // ゼロベクトルの正規化はpanicするのでテストが成功するはず
-        let _ = zero.normalized();
-    }
-}
@@ -0,0 +1,292 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::curve::{check_trim_range, split_ranges};
+use crate::{
+    Axis1, BSplineCurve, Dir, NormalizeError, Point3, Result as CrateResult, Transform, Vector3,
+};
+
+/// 無限直線（OCCT の `gp_Lin` 相当）
+///
+/// パラメータ `t` の点は `origin + t * dir` で表される。`dir` は単位ベクトルなので `t` は弧長に等しい。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Line {
+    pub origin: Point3,
+    pub dir: Dir,
+}
+
+/// 始点と終点を結ぶ線分
+///
+/// パラメータ `t` は 0 で始点、1 で終点に対応する。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Segment {
+    pub start: Point3,
+    pub end: Point3,
+}
+
+impl Line {
+    /// 原点と方向から直線を生成する
+    pub fn new(origin: Point3, dir: Dir) -> Self {
+        Self { origin, dir }
+    }
+
+    /// 2点を通る直線を生成する（`a` を原点、`a` から `b` への向きを方向とする）
+    /// 2点が一致する場合はエラーを返す
+    pub fn from_points(a: Point3, b: Point3) -> Result<Self, NormalizeError> {
+        Ok(Self::new(a, Dir::from_vector(b - a)?))
+    }
+
+    /// パラメータ `t` における点を返す
+    pub fn point_at(&self, t: f64) -> Point3 {
+        self.origin + self.dir.to_vector() * t
+    }
+
+    /// 点を直線に投影したときのパラメータを返す
+    pub fn parameter_of(&self, p: Point3) -> f64 {
+        (p - self.origin).dot(self.dir.to_vector())
+    }
+
+    /// 直線上で点に最も近い点を返す
+    pub fn closest_point(&self, p: Point3) -> Point3 {
+        self.point_at(self.parameter_of(p))
+    }
+
+    /// 点と直線の距離を計算する
+    pub fn distance(&self, p: Point3) -> f64 {
+        self.distance_squared(p).sqrt()
+    }
+
+    /// 点と直線の距離の2乗を計算する
+    pub fn distance_squared(&self, p: Point3) -> f64 {
+        p.distance_squared(self.closest_point(p))
+    }
+
+    /// 点が直線上（距離が `tol` 以下）にあるか判定する
+    pub fn contains(&self, p: Point3, tol: f64) -> bool {
+        self.distance(p) <= tol
+    }
+
+    /// 向きを反転した直線を返す
+    pub fn reversed(&self) -> Self {
+        Self::new(self.origin, self.dir.reversed())
+    }
+
+    /// 変換を適用した直線を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self::new(t.transform_point(self.origin), self.dir.transformed(t))
+    }
+
+    /// 軸として返す
+    pub fn to_axis(&self) -> Axis1 {
+        Axis1::new(self.origin, self.dir)
+    }
+
+    /// パラメータ範囲 `[u1, u2]` を線分として切り出す
+    /// `u1 < u2` でない、または有限でない場合はエラーを返す
+    pub fn trim(&self, u1: f64, u2: f64) -> CrateResult<Segment> {
+        check_trim_range(f64::NEG_INFINITY, f64::INFINITY, u1, u2)?;
+        Ok(Segment::new(self.point_at(u1), self.point_at(u2)))
+    }
+
+    /// パラメータ範囲 `[u1, u2]` を1次の B-スプライン曲線に変換する（パラメータは直線と同じ値を保つ）
+    /// `u1 < u2` でない、または有限でない場合はエラーを返す
+    pub fn to_nurbs(&self, u1: f64, u2: f64) -> CrateResult<BSplineCurve> {
+        let s = self.trim(u1, u2)?;
+        BSplineCurve::from_flat_knots(1, vec![s.start, s.end], vec![u1, u1, u2, u2])
+    }
+}
+
+/// 軸から直線への変換
+impl From<Axis1> for Line {
+    fn from(a: Axis1) -> Self {
+        Self::new(a.location, a.direction)
+    }
+}
+
+impl Segment {
+    /// 始点と終点から線分を生成する
+    pub fn new(start: Point3, end: Point3) -> Self {
+        Self { start, end }
+    }
+
+    /// 始点から終点へのベクトルを返す
+    pub fn vector(&self) -> Vector3 {
+        self.end - self.start
+    }
+
+    /// 線分の長さを計算する
+    pub fn length(&self) -> f64 {
+        self.vector().length()
+    }
+
+    /// 始点から終点への方向を返す
+    /// 長さがゼロの場合はエラーを返す
+    pub fn direction(&self) -> Result<Dir, NormalizeError> {
+        Dir::from_vector(self.vector())
+    }
+
+    /// 線分を含む直線を返す
+    /// 長さがゼロの場合はエラーを返す
+    pub fn to_line(&self) -> Result<Line, NormalizeError> {
+        Line::from_points(self.start, self.end)
+    }
+
+    /// パラメータ `t` における点を返す（範囲外の `t` は延長線上の点になる）
+    pub fn point_at(&self, t: f64) -> Point3 {
+        Point3::lerp(self.start, self.end, t)
+    }
+
+    /// 中点を返す
+    pub fn midpoint(&self) -> Point3 {
+        self.start.midpoint(self.end)
+    }
+
+    /// 線分上で点に最も近い位置のパラメータ（0〜1）を返す
+    /// 長さがゼロの場合は 0 を返す
+    pub fn closest_parameter(&self, p: Point3) -> f64 {
+        let v = self.vector();
+        let len2 = v.dot(v);
+        if len2 == 0.0 {
+            return 0.0;
+        }
+        ((p - self.start).dot(v) / len2).clamp(0.0, 1.0)
+    }
+
+    /// 線分上で点に最も近い点を返す
+    pub fn closest_point(&self, p: Point3) -> Point3 {
+        self.point_at(self.closest_parameter(p))
+    }
+
+    /// 点と線分の距離を計算する
+    pub fn distance(&self, p: Point3) -> f64 {
+        p.distance(self.closest_point(p))
+    }
+
+    /// パラメータ範囲 `[u1, u2]`（`0 <= u1 < u2 <= 1`）を切り出す（結果のパラメータは再び 0〜1）
+    pub fn trim(&self, u1: f64, u2: f64) -> CrateResult<Segment> {
+        check_trim_range(0.0, 1.0, u1, u2)?;
+        Ok(Segment::new(self.point_at(u1), self.point_at(u2)))
+    }
+
+    /// 指定したパラメータで分割する
+    pub fn split_at(&self, params: &[f64]) -> CrateResult<Vec<Segment>> {
+        split_ranges(0.0, 1.0, params)?
+            .into_iter()
+            .map(|(a, b)| self.trim(a, b))
+            .collect()
+    }
+
+    /// 始点と終点を入れ替えた線分を返す
+    pub fn reversed(&self) -> Self {
+        Self::new(self.end, self.start)
+    }
+
+    /// 変換を適用した線分を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self::new(t.transform_point(self.start), t.transform_point(self.end))
+    }
+
+    /// 1次の B-スプライン曲線に変換する（パラメータ範囲は `[0, 1]`）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        BSplineCurve::from_flat_knots(1, vec![self.start, self.end], vec![0.0, 0.0, 1.0, 1.0])
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quaternion;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_line_queries() {
+        let l = Line::from_points(Point3::new(1.0, 1.0, 0.0), Point3::new(3.0, 1.0, 0.0)).unwrap();
+        assert_point_eq(l.point_at(2.0), Point3::new(3.0, 1.0, 0.0));
+        let p = Point3::new(-1.0, 4.0, 4.0);
+        assert!((l.parameter_of(p) + 2.0).abs() < 1e-12);
+        assert_point_eq(l.closest_point(p), Point3::new(-1.0, 1.0, 0.0));
+        assert!((l.distance(p) - 5.0).abs() < 1e-12);
+        assert!(l.contains(Point3::new(10.0, 1.0, 0.0), 1e-9));
+        assert!(!l.contains(p, 1e-9));
+        assert!(Line::from_points(p, p).is_err());
+    }
+
+    #[test]
+    fn test_line_transform() {
+        let l = Line::from(Axis1::ox());
+        let t = Transform::new(
+            Quaternion::from_axis_angle(Vector3::Z, std::f64::consts::FRAC_PI_2),
+            Vector3::new(0.0, 0.0, 1.0),
+            1.0,
+        );
+        let m = l.transformed(&t);
+        assert_point_eq(m.point_at(1.0), t * l.point_at(1.0));
+        assert_eq!(l.reversed().to_axis(), Axis1::ox().reversed());
+    }
+
+    #[test]
+    fn test_segment_closest_point() {
+        let s = Segment::new(Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 0.0));
+        assert_eq!(s.length(), 4.0);
+        assert_point_eq(s.midpoint(), Point3::new(2.0, 0.0, 0.0));
+        // 内部に射影される点
+        let p = Point3::new(1.0, 3.0, 0.0);
+        assert!((s.closest_parameter(p) - 0.25).abs() < 1e-12);
+        assert!((s.distance(p) - 3.0).abs() < 1e-12);
+        // 端点の外側はクランプされる
+        let q = Point3::new(-3.0, 4.0, 0.0);
+        assert_eq!(s.closest_parameter(q), 0.0);
+        assert!((s.distance(q) - 5.0).abs() < 1e-12);
+        assert_eq!(s.closest_parameter(Point3::new(9.0, 0.0, 0.0)), 1.0);
+        assert_eq!(s.reversed().start, s.end);
+    }
+
+    #[test]
+    fn test_degenerate_segment() {
+        let p = Point3::new(1.0, 2.0, 3.0);
+        let s = Segment::new(p, p);
+        assert!(s.direction().is_err());
+        assert!(s.to_line().is_err());
+        assert_eq!(s.closest_point(Point3::origin()), p);
+    }
+
+    #[test]
+    fn test_trim_and_split() {
+        let l = Line::new(Point3::origin(), Dir::X);
+        let s = l.trim(-1.0, 3.0).unwrap();
+        assert_eq!(
+            s,
+            Segment::new(Point3::new(-1.0, 0.0, 0.0), Point3::new(3.0, 0.0, 0.0))
+        );
+        assert!(l.trim(2.0, 1.0).is_err());
+        let parts = s.split_at(&[0.25, 0.5]).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].end, Point3::origin());
+        assert_eq!(parts[2].start, Point3::new(1.0, 0.0, 0.0));
+        assert_eq!(s.trim(0.0, 0.5).unwrap().end, Point3::new(1.0, 0.0, 0.0));
+        assert!(s.trim(0.5, 1.5).is_err());
+        assert!(s.split_at(&[0.0]).is_err());
+    }
+
+    #[test]
+    fn test_to_nurbs() {
+        let l = Line::new(Point3::new(1.0, 0.0, 0.0), Dir::Y);
+        let c = l.to_nurbs(-1.0, 3.0).unwrap();
+        assert_eq!(c.degree(), 1);
+        for u in [-1.0, 0.5, 3.0] {
+            assert_point_eq(c.point_at(u), l.point_at(u));
+        }
+        assert!(l.to_nurbs(2.0, 2.0).is_err());
+        let s = Segment::new(Point3::ORIGIN, Point3::new(2.0, 4.0, 6.0));
+        let c = s.to_nurbs();
+        for t in [0.0, 0.25, 1.0] {
+            assert_point_eq(c.point_at(t), s.point_at(t));
+        }
+    }
+}
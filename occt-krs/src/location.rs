@@ -0,0 +1,109 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::{Point3, Transform};
+
+/// 形状の配置（OCCT の `TopLoc_Location` 相当）
+///
+/// 形状の中身（幾何と部分形状）に適用する変換で、親から子へ順に合成される。
+/// 恒等変換は変換を持たない値として扱い、合成や比較を省く。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Location {
+    transform: Option<Transform>,
+}
+
+impl Location {
+    /// 恒等変換の配置を返す
+    pub fn identity() -> Self {
+        Self { transform: None }
+    }
+
+    /// 変換 `transform` の配置を生成する
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform: Some(transform),
+        }
+    }
+
+    /// 恒等変換なら `true` を返す
+    pub fn is_identity(&self) -> bool {
+        self.transform.is_none()
+    }
+
+    /// 変換を返す
+    pub fn transform(&self) -> Transform {
+        self.transform.unwrap_or_default()
+    }
+
+    /// 逆の配置を返す（スケールがゼロの場合は `None`）
+    pub fn inverse(&self) -> Option<Self> {
+        match self.transform {
+            None => Some(Self::identity()),
+            Some(t) => t.inverse().map(Self::new),
+        }
+    }
+
+    /// 点に配置を適用する
+    pub fn apply(&self, p: Point3) -> Point3 {
+        match &self.transform {
+            None => p,
+            Some(t) => t.transform_point(p),
+        }
+    }
+
+    /// 同じ配置どうしで等しくなる、ハッシュに使える値
+    pub(crate) fn key(&self) -> Option<[u64; 8]> {
+        self.transform.map(|t| {
+            let q = t.rotation;
+            let v = t.translation;
+            [q.w, q.x, q.y, q.z, v.x, v.y, v.z, t.scale].map(f64::to_bits)
+        })
+    }
+}
+
+impl From<Transform> for Location {
+    fn from(t: Transform) -> Self {
+        Self::new(t)
+    }
+}
+
+/// 配置の合成
+/// `a * b` は「b を適用した後に a を適用する」配置を表す
+impl Mul for Location {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        match (self.transform, other.transform) {
+            (None, _) => other,
+            (_, None) => self,
+            (Some(a), Some(b)) => Self::new(a * b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vector3;
+
+    #[test]
+    fn test_compose_and_inverse() {
+        let a = Location::new(Transform::from_translation(Vector3::new(1.0, 0.0, 0.0)));
+        let b = Location::new(Transform::from_scale(2.0));
+        let id = Location::identity();
+        assert!(id.is_identity() && (id * id).is_identity());
+        assert_eq!(a * id, a);
+        assert_eq!(id * b, b);
+        let p = Point3::new(1.0, 2.0, 3.0);
+        assert_eq!((a * b).apply(p), Point3::new(3.0, 4.0, 6.0));
+        assert_eq!((b * a).apply(p), Point3::new(4.0, 4.0, 6.0));
+        let back = (a * b).inverse().unwrap() * (a * b);
+        assert!(back.apply(p).distance(p) < 1e-12);
+        assert_eq!(a.key(), Location::from(a.transform()).key());
+        assert_ne!(a.key(), b.key());
+        assert!(Location::new(Transform::from_scale(0.0))
+            .inverse()
+            .is_none());
+    }
+}
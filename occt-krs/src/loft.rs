@@ -0,0 +1,639 @@
+use crate::bspline_fit::solve_dense;
+use crate::curve::Curve3;
+use crate::extrude::{area_vector, uv_segment, wire_points};
+use crate::precision;
+use crate::surface_fit::{averaged_knots, averaged_parameters, interpolation_matrix};
+use crate::sweep::bspline_section;
+use crate::{
+    BSplineCurve, BSplineSurface, Edge, Face, OcctKrsError, Orientation, Point3, Result, Shape,
+    Shell, Solid, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 2本の曲線を直線で結んだ線織面（OCCT の `GeomFill::Surface` で作る線織面相当）
+///
+/// `P(u, v) = (1 - v) C1(u) + v C2(s(u))`。`s` は1本目のパラメータ範囲を2本目の範囲へ線形に対応させる。
+/// u の範囲は1本目の曲線のパラメータ範囲、v の範囲は 0〜1。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuledSurface<C1, C2> {
+    first: C1,
+    second: C2,
+}
+
+impl<C1: Curve3, C2: Curve3> RuledSurface<C1, C2> {
+    /// 2本の曲線から線織面を生成する
+    /// いずれかの曲線のパラメータ範囲が有限でない場合はエラーを返す
+    pub fn new(first: C1, second: C2) -> Result<Self> {
+        let bounds = [
+            first.first_parameter(),
+            first.last_parameter(),
+            second.first_parameter(),
+            second.last_parameter(),
+        ];
+        if !bounds.iter().all(|b| b.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(
+                "線織面の曲線はパラメータ範囲が有限である必要があります".to_string(),
+            ));
+        }
+        Ok(Self { first, second })
+    }
+
+    /// 1本目（v = 0）の曲線を返す
+    pub fn first_curve(&self) -> &C1 {
+        &self.first
+    }
+
+    /// 2本目（v = 1）の曲線を返す
+    pub fn second_curve(&self) -> &C2 {
+        &self.second
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let a = self.first.point_at(u);
+        let b = self.second.point_at(self.second_parameter(u).0);
+        a + (b - a) * v
+    }
+
+    /// 1本目のパラメータ `u` に対応する2本目のパラメータと、その `u` についての微分係数を返す
+    pub(crate) fn second_parameter(&self, u: f64) -> (f64, f64) {
+        let (a0, a1) = (self.first.first_parameter(), self.first.last_parameter());
+        let (b0, b1) = (self.second.first_parameter(), self.second.last_parameter());
+        let k = (b1 - b0) / (a1 - a0);
+        (b0 + (u - a0) * k, k)
+    }
+}
+
+impl BSplineCurve {
+    /// 曲線群を互換な形（同じ次数・同じパラメータ範囲 0〜1・同じノット列）に揃える
+    ///
+    /// 次数を最大のものに上げ、パラメータ範囲を正規化した後、不足するノットを挿入する。
+    /// 形状は変わらない。曲線が空の場合はエラーを返す。
+    pub fn make_compatible(curves: &[BSplineCurve]) -> Result<Vec<BSplineCurve>> {
+        let degree = curves
+            .iter()
+            .map(|c| c.degree())
+            .max()
+            .ok_or_else(|| OcctKrsError::InvalidInput("曲線が空です".to_string()))?;
+        let mut curves = curves
+            .iter()
+            .map(|c| {
+                c.elevate_degree(degree - c.degree())
+                    .reparametrized(0.0, 1.0)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // すべての曲線の内部ノットを、最大の多重度で合わせた列
+        let mut merged: Vec<(f64, usize)> = Vec::new();
+        for c in &curves {
+            let (knots, mults) = (c.knots(), c.multiplicities());
+            for (&k, &m) in knots[1..knots.len() - 1].iter().zip(&mults[1..]) {
+                match merged.iter_mut().find(|(u, _)| *u == k) {
+                    Some(entry) => entry.1 = entry.1.max(m),
+                    None => merged.push((k, m)),
+                }
+            }
+        }
+        for c in &mut curves {
+            let mut missing = Vec::new();
+            for &(k, m) in &merged {
+                let current = c.flat_knots().iter().filter(|&&u| u == k).count();
+                missing.extend(std::iter::repeat_n(k, m.saturating_sub(current)));
+            }
+            c.refine_knots(&missing)?;
+        }
+        Ok(curves)
+    }
+}
+
+impl BSplineSurface {
+    /// 断面曲線を順に通る B-スプライン曲面を生成する（スキニング、The NURBS Book の 10.3 節）
+    ///
+    /// 断面は `BSplineCurve::make_compatible` で互換にした後、対応する制御点を v 方向に
+    /// 次数 `v_degree`（断面数 - 1 が上限）で補間する。u は正規化した断面のパラメータ（0〜1）、
+    /// v は 0〜1 で、最初の断面が v = 0、最後の断面が v = 1 に対応する。
+    /// 断面が2本未満、`v_degree` が 0、断面がすべて一致する場合などはエラーを返す。
+    pub fn loft(sections: &[BSplineCurve], v_degree: usize) -> Result<BSplineSurface> {
+        let m = sections.len();
+        if m < 2 || v_degree == 0 {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "断面は2本以上、v 方向の次数は1以上が必要です（断面 {} 本、次数 {}）",
+                m, v_degree
+            )));
+        }
+        let sections = BSplineCurve::make_compatible(sections)?;
+        let n = sections[0].control_points().len();
+        let columns: Vec<Vec<Point3>> = (0..n)
+            .map(|j| sections.iter().map(|c| c.control_points()[j]).collect())
+            .collect();
+        let params = averaged_parameters(&columns)?;
+        Self::skin(&sections, &params, v_degree.min(m - 1))
+    }
+
+    /// 互換な断面曲線を、v 方向のパラメータ `params` で順に通る次数 `q` の曲面を生成する
+    ///
+    /// 断面の重みごとに同次座標で補間する。ノットは `params` から平均法で求める。
+    pub(crate) fn skin(
+        sections: &[BSplineCurve],
+        params: &[f64],
+        q: usize,
+    ) -> Result<BSplineSurface> {
+        let m = sections.len();
+        let rational = sections.iter().any(|c| c.is_rational());
+        let n = sections[0].control_points().len();
+        let knots = averaged_knots(params, q);
+        let matrix = interpolation_matrix(params, &knots, q);
+
+        let singular = || OcctKrsError::DegenerateGeometry("補間の方程式が解けません".to_string());
+        let mut grid = Vec::with_capacity(n);
+        let mut weights = Vec::with_capacity(n);
+        for j in 0..n {
+            let rhs = sections
+                .iter()
+                .map(|c| c.control_points()[j].to_vector() * c.weight(j))
+                .collect();
+            let poles = solve_dense(matrix.clone(), rhs).ok_or_else(singular)?;
+            let w = if rational {
+                let rhs = sections
+                    .iter()
+                    .map(|c| Vector3::new(c.weight(j), 0.0, 0.0))
+                    .collect();
+                let w: Vec<f64> = solve_dense(matrix.clone(), rhs)
+                    .ok_or_else(singular)?
+                    .iter()
+                    .map(|w| w.x)
+                    .collect();
+                if w.iter().any(|w| *w <= 0.0) {
+                    return Err(OcctKrsError::DegenerateGeometry(
+                        "補間した重みが正になりません".to_string(),
+                    ));
+                }
+                w
+            } else {
+                vec![1.0; m]
+            };
+            grid.push(
+                poles
+                    .iter()
+                    .zip(&w)
+                    .map(|(p, w)| Point3::from(*p / *w))
+                    .collect(),
+            );
+            weights.push(w);
+        }
+        let surface = BSplineSurface::from_flat_knots(
+            sections[0].degree(),
+            q,
+            grid,
+            sections[0].flat_knots().to_vec(),
+            knots,
+        )?;
+        if rational {
+            surface.with_weights(weights)
+        } else {
+            Ok(surface)
+        }
+    }
+}
+
+/// 断面のワイヤを順に通るシェルか立体を生成する（OCCT の `BRepOffsetAPI_ThruSections` 相当）
+///
+/// 断面はすべて閉じたワイヤか、すべて開いたワイヤとする。エッジの数が揃わない断面は、
+/// 長いエッジから順に半分に分割して揃え、各断面の i 番目のエッジどうしを結んで側面を作る。
+/// `ruled` が `true` なら隣り合う断面の間を線織面で、`false` ならすべての断面を通る
+/// 滑らかな B-スプライン曲面（v 方向は3次まで）で結ぶ。各断面の v は頂点の弦長から決める。
+/// 断面のエッジは、パラメータ範囲を 0〜1 にした B-スプライン曲線のエッジに置き換える。
+/// `closed` が `true` なら最初と最後の断面に平面の蓋をして立体にし、`false` ならシェルを返す。
+///
+/// 断面が2つ未満の場合、閉じた断面と開いた断面が混ざる場合、`closed` が `true` で断面が
+/// 開いているか両端の断面が平面でない場合、双曲線や螺旋のエッジがある場合はエラーを返す。
+pub fn loft(sections: &[Wire], ruled: bool, closed: bool) -> Result<Shape> {
+    let m = sections.len();
+    if m < 2 {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "断面は2つ以上必要です（{} 個）",
+            m
+        )));
+    }
+    let periodic = sections[0].is_closed();
+    if sections.iter().any(|w| w.is_closed() != periodic) {
+        return Err(OcctKrsError::InvalidInput(
+            "閉じた断面と開いた断面が混ざっています".to_string(),
+        ));
+    }
+    if closed && !periodic {
+        return Err(OcctKrsError::InvalidInput(
+            "立体にするには断面が閉じている必要があります".to_string(),
+        ));
+    }
+    let mut profiles = sections
+        .iter()
+        .map(|w| Profile::new(w, periodic))
+        .collect::<Result<Vec<_>>>()?;
+    let count = profiles.iter().map(|p| p.curves.len()).max().unwrap_or(0);
+    for p in &mut profiles {
+        p.split_to(count)?;
+    }
+    let vertex_count = profiles[0].vertices.len();
+    let columns: Vec<Vec<Point3>> = (0..vertex_count)
+        .map(|j| profiles.iter().map(|p| p.vertices[j].point()).collect())
+        .collect();
+    let params = averaged_parameters(&columns)?;
+    let edges = profiles
+        .iter()
+        .map(Profile::edges)
+        .collect::<Result<Vec<_>>>()?;
+
+    // 最初の断面の面積ベクトルが断面の進む向きを向いていれば、側面の表が外側になる
+    let centroid = |w: &Wire| {
+        let points = wire_points(w);
+        points.iter().fold(Vector3::ZERO, |s, &p| s + p) / points.len() as f64
+    };
+    let direction = centroid(&sections[m - 1]) - centroid(&sections[0]);
+    let area = area_vector(&wire_points(&sections[0]));
+    let height = area.dot(direction);
+    if closed && height.abs() <= precision::confusion() * area.length() * direction.length() {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "断面が進む向きと最初の断面が平行です".to_string(),
+        ));
+    }
+    let flip = height < 0.0;
+
+    let bands: Vec<(usize, usize)> = if ruled {
+        (0..m - 1).map(|k| (k, k + 1)).collect()
+    } else {
+        vec![(0, m - 1)]
+    };
+    let mut faces = Vec::new();
+    for (k0, k1) in bands {
+        let band = &params[k0..=k1];
+        let q = (k1 - k0).min(3);
+        let laterals = (0..vertex_count)
+            .map(|j| {
+                lateral(
+                    &columns[j][k0..=k1],
+                    band,
+                    q,
+                    &profiles[k0].vertices[j],
+                    &profiles[k1].vertices[j],
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (v0, v1) = (band[0], band[band.len() - 1]);
+        let uv = |u: f64, v: f64| Vector2::new(u, v);
+        for i in 0..count {
+            let curves: Vec<BSplineCurve> = profiles[k0..=k1]
+                .iter()
+                .map(|p| p.curves[i].clone())
+                .collect();
+            let surface = BSplineSurface::skin(&BSplineCurve::make_compatible(&curves)?, band, q)?;
+            let next = (i + 1) % vertex_count;
+            let wire = Wire::new(vec![
+                edges[k0][i].clone(),
+                laterals[next].clone(),
+                edges[k1][i].reversed(),
+                laterals[i].reversed(),
+            ])?;
+            let pcurves = vec![vec![
+                Some(uv_segment(uv(0.0, v0), uv(1.0, v0), (0.0, 1.0))?),
+                Some(uv_segment(uv(1.0, v0), uv(1.0, v1), (v0, v1))?),
+                Some(uv_segment(uv(0.0, v1), uv(1.0, v1), (0.0, 1.0))?),
+                Some(uv_segment(uv(0.0, v0), uv(0.0, v1), (v0, v1))?),
+            ]];
+            let face = Face::with_pcurves(surface, vec![wire], pcurves)?;
+            faces.push(if flip { face.reversed() } else { face });
+        }
+    }
+    if !closed {
+        return Ok(Shell::new(faces)?.into());
+    }
+    // 蓋の法線は断面のワイヤを反時計回りに見る側なので、外側を向くように裏返す
+    let tolerance = precision::confusion();
+    let cap =
+        |edges: &[Edge]| Face::from_planar_wires(Wire::new(edges.to_vec())?, Vec::new(), tolerance);
+    let bottom = cap(&edges[0])?;
+    let top = cap(&edges[m - 1])?;
+    let last = area_vector(&wire_points(&sections[m - 1]));
+    faces.insert(
+        0,
+        if height > 0.0 {
+            bottom.reversed()
+        } else {
+            bottom
+        },
+    );
+    faces.push(if last.dot(direction) > 0.0 {
+        top
+    } else {
+        top.reversed()
+    });
+    Ok(Solid::new(vec![Shell::new(faces)?])?.into())
+}
+
+/// 互換にする途中の断面（エッジの曲線をワイヤの向きにそろえ、パラメータ範囲を 0〜1 にしたもの）
+pub(crate) struct Profile {
+    pub(crate) curves: Vec<BSplineCurve>,
+    /// 各エッジの始点の頂点（開いた断面では最後に終点を加える）
+    pub(crate) vertices: Vec<Vertex>,
+}
+
+impl Profile {
+    pub(crate) fn new(wire: &Wire, periodic: bool) -> Result<Self> {
+        let mut curves = Vec::new();
+        let mut vertices = Vec::new();
+        let mut end = None;
+        for edge in wire.edges() {
+            let Some(curve) = edge.located_geometry() else {
+                continue;
+            };
+            let c = bspline_section(&curve, edge.range())?;
+            let c = if edge.orientation() == Orientation::Reversed {
+                c.reversed()
+            } else {
+                c
+            };
+            curves.push(c.reparametrized(0.0, 1.0)?);
+            vertices.push(edge.start_vertex());
+            end = Some(edge.end_vertex());
+        }
+        let Some(end) = end else {
+            return Err(OcctKrsError::InvalidInput(
+                "エッジを持たない断面があります".to_string(),
+            ));
+        };
+        if !periodic {
+            vertices.push(end);
+        }
+        Ok(Self { curves, vertices })
+    }
+
+    /// 長いエッジから順に半分に分割して、エッジの数を `count` にする
+    pub(crate) fn split_to(&mut self, count: usize) -> Result<()> {
+        let length = |c: &BSplineCurve| -> f64 {
+            (0..8)
+                .map(|k| {
+                    c.point_at(k as f64 / 8.0)
+                        .distance(c.point_at((k + 1) as f64 / 8.0))
+                })
+                .sum()
+        };
+        while self.curves.len() < count {
+            let (i, _) = self
+                .curves
+                .iter()
+                .map(length)
+                .enumerate()
+                .fold((0, f64::NEG_INFINITY), |a, b| if b.1 > a.1 { b } else { a });
+            let parts = self.curves[i]
+                .split_at(&[0.5])?
+                .into_iter()
+                .map(|c| c.reparametrized(0.0, 1.0))
+                .collect::<Result<Vec<_>>>()?;
+            self.vertices
+                .insert(i + 1, Vertex::new(parts[0].end_point()));
+            self.curves.splice(i..=i, parts);
+        }
+        Ok(())
+    }
+
+    /// 断面のエッジ（曲線のパラメータ範囲は 0〜1）
+    fn edges(&self) -> Result<Vec<Edge>> {
+        let n = self.vertices.len();
+        self.curves
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                Edge::new(
+                    c.clone(),
+                    (0.0, 1.0),
+                    self.vertices[i].oriented(Orientation::Forward),
+                    self.vertices[(i + 1) % n].oriented(Orientation::Forward),
+                )
+            })
+            .collect()
+    }
+}
+
+/// 頂点の位置 `points` をパラメータ `params` で通る次数 `q` の曲線の稜線
+///
+/// 側面の曲面と同じノットで補間するので、曲面の u 方向の端の曲線と一致する。
+fn lateral(
+    points: &[Point3],
+    params: &[f64],
+    q: usize,
+    start: &Vertex,
+    end: &Vertex,
+) -> Result<Edge> {
+    let knots = averaged_knots(params, q);
+    let matrix = interpolation_matrix(params, &knots, q);
+    let poles = solve_dense(matrix, points.iter().map(|p| p.to_vector()).collect())
+        .ok_or_else(|| OcctKrsError::DegenerateGeometry("補間の方程式が解けません".to_string()))?;
+    let curve =
+        BSplineCurve::from_flat_knots(q, poles.into_iter().map(Point3::from).collect(), knots)?;
+    Edge::new(
+        curve,
+        (params[0], params[params.len() - 1]),
+        start.oriented(Orientation::Forward),
+        end.oriented(Orientation::Forward),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Circle, Dir, Segment, ShapeKind, Surface};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn circle_at(z: f64, radius: f64) -> BSplineCurve {
+        let axis = Axis2::new(Point3::new(0.0, 0.0, z), Dir::z_axis(), Dir::x_axis()).unwrap();
+        BSplineCurve::from_circle(&Circle::new(axis, radius).unwrap())
+    }
+
+    #[test]
+    fn test_ruled_surface_between_segments() {
+        let a = Segment::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0));
+        let b = circle_at(1.0, 1.0);
+        let s = RuledSurface::new(a, b.clone()).unwrap();
+        assert_eq!(s.u_range(), (0.0, 1.0));
+        assert_eq!(s.v_range(), (0.0, 1.0));
+        assert_point_eq(s.point_at(0.5, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let t = b.first_parameter() + 0.5 * (b.last_parameter() - b.first_parameter());
+        assert_point_eq(s.point_at(0.5, 1.0), b.point_at(t));
+
+        let (u, v, h) = (0.3, 0.6, 1e-5);
+        let du = (s.point_at(u + h, v) - s.point_at(u - h, v)) / (2.0 * h);
+        assert!((du - s.derivative_u_at(u, v)).length() < 1e-7);
+        let duv = (s.derivative_u_at(u, v + h) - s.derivative_u_at(u, v - h)) / (2.0 * h);
+        let (suu, suv, svv) = s.second_derivatives_at(u, v);
+        let duu = (s.derivative_u_at(u + h, v) - s.derivative_u_at(u - h, v)) / (2.0 * h);
+        assert!((suv - duv).length() < 1e-7);
+        assert!((suu - duu).length() < 1e-6);
+        assert_eq!(svv, Vector3::ZERO);
+        assert!(RuledSurface::new(a, crate::Line::new(Point3::ORIGIN, Dir::x_axis())).is_err());
+    }
+
+    #[test]
+    fn test_make_compatible_preserves_shape() {
+        let cubic = BSplineCurve::new(
+            3,
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 0.0),
+                Point3::new(2.0, -1.0, 0.0),
+                Point3::new(3.0, 1.0, 0.0),
+                Point3::new(4.0, 0.0, 0.0),
+            ],
+            &[0.0, 0.4, 2.0],
+            &[4, 1, 4],
+        )
+        .unwrap();
+        let circle = circle_at(1.0, 2.0);
+        let curves = BSplineCurve::make_compatible(&[cubic.clone(), circle.clone()]).unwrap();
+        assert_eq!(curves[0].degree(), 3);
+        assert_eq!(curves[1].degree(), 3);
+        assert_eq!(curves[0].flat_knots(), curves[1].flat_knots());
+        for (original, c) in [&cubic, &circle].iter().zip(&curves) {
+            let (a, b) = (original.first_parameter(), original.last_parameter());
+            for t in [0.0, 0.13, 0.5, 0.77, 1.0] {
+                let p = original.point_at(a + (b - a) * t);
+                assert!(c.point_at(t).distance(p) < 1e-9);
+            }
+        }
+        assert!(BSplineCurve::make_compatible(&[]).is_err());
+    }
+
+    #[test]
+    fn test_loft_passes_through_sections() {
+        let sections = [
+            circle_at(0.0, 1.0),
+            circle_at(1.0, 2.0),
+            circle_at(2.0, 1.0),
+        ];
+        let s = BSplineSurface::loft(&sections, 2).unwrap();
+        assert_eq!(s.v_degree(), 2);
+        assert!(s.is_rational());
+        let compatible = BSplineCurve::make_compatible(&sections).unwrap();
+        // 対称な配置なので中央の断面は v = 0.5 に対応する
+        for (c, v) in compatible.iter().zip([0.0, 0.5, 1.0]) {
+            for u in [0.0, 0.1, 0.45, 0.8] {
+                assert_point_eq(s.point_at(u, v), c.point_at(u));
+            }
+        }
+        assert!(s.is_u_closed() && !s.is_v_closed());
+    }
+
+    #[test]
+    fn test_loft_of_two_sections_is_ruled() {
+        let a = circle_at(0.0, 1.0);
+        let b = circle_at(3.0, 2.0).reparametrized(0.0, 1.0).unwrap();
+        let a = a.reparametrized(0.0, 1.0).unwrap();
+        let lofted = BSplineSurface::loft(&[a.clone(), b.clone()], 3).unwrap();
+        assert_eq!(lofted.v_degree(), 1);
+        let ruled = RuledSurface::new(a, b).unwrap();
+        for &(u, v) in &[(0.2, 0.3), (0.7, 0.9)] {
+            assert_point_eq(lofted.point_at(u, v), ruled.point_at(u, v));
+        }
+    }
+
+    #[test]
+    fn test_loft_rejects_invalid_input() {
+        let c = circle_at(0.0, 1.0);
+        assert!(BSplineSurface::loft(std::slice::from_ref(&c), 2).is_err());
+        assert!(BSplineSurface::loft(&[c.clone(), circle_at(1.0, 1.0)], 0).is_err());
+        assert!(matches!(
+            BSplineSurface::loft(&[c.clone(), c.clone()], 1),
+            Err(OcctKrsError::DegenerateGeometry(_))
+        ));
+    }
+    /// 高さ `z` で原点を中心とする一辺 `a` の正方形のワイヤ
+    fn square_wire(z: f64, a: f64) -> Wire {
+        let h = a / 2.0;
+        let p = [(-h, -h), (h, -h), (h, h), (-h, h)].map(|(x, y)| Point3::new(x, y, z));
+        let edges = (0..4)
+            .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        Wire::from_edges(edges, 1e-9).unwrap()
+    }
+
+    fn circle_wire(z: f64, radius: f64) -> Wire {
+        let axis = Axis2::new(Point3::new(0.0, 0.0, z), Dir::z_axis(), Dir::x_axis()).unwrap();
+        Wire::new(vec![
+            Edge::from_curve(Circle::new(axis, radius).unwrap()).unwrap()
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_loft_ruled_squares() {
+        let sections = [
+            square_wire(0.0, 1.0),
+            square_wire(1.0, 1.0),
+            square_wire(3.0, 1.0),
+        ];
+        let solid = loft(&sections, true, true).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / 3.0 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(solid.faces().len(), 10);
+        // 断面を逆順に並べても外向きの立体になる
+        let reversed: Vec<Wire> = sections.iter().rev().cloned().collect();
+        let volume = checked_volume(&loft(&reversed, true, true).unwrap());
+        assert!((volume / 3.0 - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_loft_smooth_circles() {
+        // 半径が z の2次式 r = 1 + 2z - z² で変わる回転体の体積は 86π/15
+        let sections = [
+            circle_wire(0.0, 1.0),
+            circle_wire(1.0, 2.0),
+            circle_wire(2.0, 1.0),
+        ];
+        let solid = loft(&sections, false, true).unwrap();
+        let volume = checked_volume(&solid);
+        let expected = 86.0 * std::f64::consts::PI / 15.0;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(solid.faces().len(), 3);
+    }
+
+    #[test]
+    fn test_loft_splits_edges_to_match() {
+        let sections = [circle_wire(0.0, 1.0), square_wire(2.0, 2.0)];
+        let solid = loft(&sections, false, true).unwrap();
+        checked_volume(&solid);
+        assert_eq!(solid.faces().len(), 6);
+        let shell = loft(&sections, false, false).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+        assert_eq!(shell.free_bounds().unwrap().free_wires().len(), 2);
+    }
+
+    #[test]
+    fn test_loft_open_sections() {
+        let p = |x: f64, z: f64| Point3::new(x, 0.0, z);
+        let open = |z: f64| {
+            let edges = vec![
+                Edge::from_points(p(0.0, z), p(1.0, z)).unwrap(),
+                Edge::from_points(p(1.0, z), p(1.0, z + 1.0)).unwrap(),
+            ];
+            Wire::from_edges(edges, 1e-9).unwrap()
+        };
+        let shell = loft(&[open(0.0), open(2.0)], true, false).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+        assert_eq!(shell.faces().len(), 2);
+        assert!(shell.check().is_valid(), "{:?}", shell.check().issues());
+        assert!(matches!(
+            loft(&[open(0.0), open(2.0)], true, true),
+            Err(OcctKrsError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            loft(&[open(0.0), square_wire(1.0, 1.0)], true, false),
+            Err(OcctKrsError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            loft(&[open(0.0)], true, false),
+            Err(OcctKrsError::InvalidInput(_))
+        ));
+    }
+}
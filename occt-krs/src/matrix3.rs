@@ -0,0 +1,198 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::{Matrix4, Vector3};
+
+/// 3x3の行列を表す構造体
+///
+/// 回転などの線形写像に利用する。要素は行優先で `m[行][列]` として保持する。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix3 {
+    pub m: [[f64; 3]; 3],
+}
+
+impl Matrix3 {
+    /// 行の配列から行列を生成する
+    pub fn from_rows(m: [[f64; 3]; 3]) -> Self {
+        Self { m }
+    }
+
+    /// 3本の列ベクトルから行列を生成する
+    pub fn from_columns(c0: Vector3, c1: Vector3, c2: Vector3) -> Self {
+        Self::from_rows([[c0.x, c1.x, c2.x], [c0.y, c1.y, c2.y], [c0.z, c1.z, c2.z]])
+    }
+
+    /// 単位行列を返す
+    pub fn identity() -> Self {
+        Self::from_diagonal(Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    /// 対角成分を指定して対角行列を生成する
+    pub fn from_diagonal(d: Vector3) -> Self {
+        Self::from_rows([[d.x, 0.0, 0.0], [0.0, d.y, 0.0], [0.0, 0.0, d.z]])
+    }
+
+    /// 原点を通る任意軸まわりの回転行列を生成する（角度はラジアン）
+    /// ※軸がゼロ長の場合はpanicするので注意
+    pub fn from_axis_angle(axis: Vector3, angle: f64) -> Self {
+        Matrix4::from_axis_angle(axis, angle).linear_part()
+    }
+
+    /// 列ベクトルを取り出す
+    pub fn column(&self, j: usize) -> Vector3 {
+        Vector3::new(self.m[0][j], self.m[1][j], self.m[2][j])
+    }
+
+    /// 行ベクトルを取り出す
+    pub fn row(&self, i: usize) -> Vector3 {
+        Vector3::new(self.m[i][0], self.m[i][1], self.m[i][2])
+    }
+
+    /// 転置行列を返す
+    pub fn transpose(&self) -> Self {
+        Self::from_columns(self.row(0), self.row(1), self.row(2))
+    }
+
+    /// 行列式を計算する
+    pub fn determinant(&self) -> f64 {
+        self.row(0).dot(self.row(1).cross(self.row(2)))
+    }
+
+    /// 逆行列を計算する
+    /// 特異（逆行列を持たない）な場合は `None` を返す
+    pub fn inverse(&self) -> Option<Self> {
+        let (r0, r1, r2) = (self.row(0), self.row(1), self.row(2));
+        let det = self.determinant();
+        let scale = self
+            .m
+            .iter()
+            .flat_map(|row| row.iter())
+            .fold(0.0_f64, |acc, v| acc.max(v.abs()));
+        if scale == 0.0 || det.abs() <= scale * scale * scale * 1e-14 {
+            return None;
+        }
+        // 余因子行列の転置（各列は2行の外積）を行列式で割る
+        let inv = Self::from_columns(r1.cross(r2), r2.cross(r0), r0.cross(r1));
+        Some(inv * (1.0 / det))
+    }
+
+    /// 法線ベクトルの変換に用いる逆転置行列を返す
+    pub fn normal_matrix(&self) -> Option<Self> {
+        self.inverse().map(|inv| inv.transpose())
+    }
+
+    /// 4x4の同次変換行列に埋め込む
+    pub fn to_matrix4(&self) -> Matrix4 {
+        let mut r = Matrix4::identity();
+        for i in 0..3 {
+            r.m[i][..3].copy_from_slice(&self.m[i]);
+        }
+        r
+    }
+}
+
+impl Matrix4 {
+    /// 左上3x3の線形部分を取り出す
+    pub fn linear_part(&self) -> Matrix3 {
+        let mut r = [[0.0; 3]; 3];
+        for (i, row) in r.iter_mut().enumerate() {
+            row.copy_from_slice(&self.m[i][..3]);
+        }
+        Matrix3::from_rows(r)
+    }
+}
+
+impl Default for Matrix3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 行列同士の積の実装
+impl Mul for Matrix3 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let mut r = [[0.0; 3]; 3];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = self.row(i).dot(other.column(j));
+            }
+        }
+        Self { m: r }
+    }
+}
+
+/// 行列とベクトルの積の実装
+impl Mul<Vector3> for Matrix3 {
+    type Output = Vector3;
+    fn mul(self, v: Vector3) -> Vector3 {
+        Vector3::new(self.row(0).dot(v), self.row(1).dot(v), self.row(2).dot(v))
+    }
+}
+
+/// 行列のスカラー倍の実装
+impl Mul<f64> for Matrix3 {
+    type Output = Self;
+    fn mul(self, scalar: f64) -> Self {
+        let mut r = self.m;
+        for v in r.iter_mut().flat_map(|row| row.iter_mut()) {
+            *v *= scalar;
+        }
+        Self { m: r }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_matrix_eq(a: &Matrix3, b: &Matrix3) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-10, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_vector() {
+        let r = Matrix3::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+        let v = r * Vector3::new(1.0, 0.0, 0.0);
+        assert!((v - Vector3::new(0.0, 1.0, 0.0)).length() < 1e-10);
+        // 回転行列の逆行列は転置行列
+        assert_matrix_eq(&r.inverse().unwrap(), &r.transpose());
+        assert!((r.determinant() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_inverse_and_determinant() {
+        let m = Matrix3::from_rows([[2.0, 1.0, 0.0], [0.0, 3.0, 1.0], [1.0, 0.0, 4.0]]);
+        // 2*(12-0) - 1*(0-1) + 0 = 25
+        assert!((m.determinant() - 25.0).abs() < 1e-10);
+        let inv = m.inverse().unwrap();
+        assert_matrix_eq(&(m * inv), &Matrix3::identity());
+
+        let singular = Matrix3::from_rows([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]);
+        assert!(singular.inverse().is_none());
+    }
+
+    #[test]
+    fn test_normal_matrix() {
+        // 非一様スケールでは法線を逆転置行列で変換すると面に垂直なまま保たれる
+        let m = Matrix3::from_diagonal(Vector3::new(2.0, 1.0, 1.0));
+        let tangent = Vector3::new(1.0, -1.0, 0.0);
+        let normal = Vector3::new(1.0, 1.0, 0.0);
+        let t = m * tangent;
+        let n = m.normal_matrix().unwrap() * normal;
+        assert!(t.dot(n).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_matrix4_roundtrip() {
+        let m = Matrix3::from_axis_angle(Vector3::new(1.0, 2.0, 3.0), 0.3);
+        assert_matrix_eq(&m.to_matrix4().linear_part(), &m);
+    }
+}
@@ -0,0 +1,284 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::{Point3, Vector3};
+
+/// 4x4の同次変換行列を表す構造体
+///
+/// 要素は行優先で `m[行][列]` として保持する。
+/// 列ベクトルに左から掛ける規約（`M * v`）を採用している。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix4 {
+    pub m: [[f64; 4]; 4],
+}
+
+impl Matrix4 {
+    /// 行の配列から行列を生成する
+    pub fn from_rows(m: [[f64; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    /// 単位行列を返す
+    pub fn identity() -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Self { m }
+    }
+
+    /// 平行移動行列を生成する
+    pub fn from_translation(t: Vector3) -> Self {
+        let mut r = Self::identity();
+        r.m[0][3] = t.x;
+        r.m[1][3] = t.y;
+        r.m[2][3] = t.z;
+        r
+    }
+
+    /// 各軸方向の拡大縮小行列を生成する
+    pub fn from_scale(s: Vector3) -> Self {
+        let mut r = Self::identity();
+        r.m[0][0] = s.x;
+        r.m[1][1] = s.y;
+        r.m[2][2] = s.z;
+        r
+    }
+
+    /// 原点を通る任意軸まわりの回転行列を生成する（角度はラジアン）
+    /// ※軸がゼロ長の場合はpanicするので注意
+    pub fn from_axis_angle(axis: Vector3, angle: f64) -> Self {
+        let a = axis.normalized();
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        Self::from_rows([
+            [
+                t * a.x * a.x + c,
+                t * a.x * a.y - s * a.z,
+                t * a.x * a.z + s * a.y,
+                0.0,
+            ],
+            [
+                t * a.x * a.y + s * a.z,
+                t * a.y * a.y + c,
+                t * a.y * a.z - s * a.x,
+                0.0,
+            ],
+            [
+                t * a.x * a.z - s * a.y,
+                t * a.y * a.z + s * a.x,
+                t * a.z * a.z + c,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// X軸まわりの回転行列を生成する
+    pub fn from_rotation_x(angle: f64) -> Self {
+        Self::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), angle)
+    }
+
+    /// Y軸まわりの回転行列を生成する
+    pub fn from_rotation_y(angle: f64) -> Self {
+        Self::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), angle)
+    }
+
+    /// Z軸まわりの回転行列を生成する
+    pub fn from_rotation_z(angle: f64) -> Self {
+        Self::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), angle)
+    }
+
+    /// 転置行列を返す
+    pub fn transpose(&self) -> Self {
+        let mut r = [[0.0; 4]; 4];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = self.m[j][i];
+            }
+        }
+        Self { m: r }
+    }
+
+    /// 行列式を計算する
+    pub fn determinant(&self) -> f64 {
+        let m = &self.m;
+        // 下2行の2x2小行列式を使った展開
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// 逆行列を計算する
+    /// 特異（逆行列を持たない）な場合は `None` を返す
+    pub fn inverse(&self) -> Option<Self> {
+        let mut a = self.m;
+        let mut inv = Self::identity().m;
+        let scale = a
+            .iter()
+            .flat_map(|row| row.iter())
+            .fold(0.0_f64, |acc, v| acc.max(v.abs()));
+        if scale == 0.0 {
+            return None;
+        }
+
+        // 部分ピボット選択付きのガウス・ジョルダン法
+        for col in 0..4 {
+            let pivot_row = (col..4)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            if a[pivot_row][col].abs() <= scale * 1e-14 {
+                return None;
+            }
+            a.swap(col, pivot_row);
+            inv.swap(col, pivot_row);
+
+            let p = a[col][col];
+            for j in 0..4 {
+                a[col][j] /= p;
+                inv[col][j] /= p;
+            }
+            for i in 0..4 {
+                if i == col {
+                    continue;
+                }
+                let f = a[i][col];
+                if f == 0.0 {
+                    continue;
+                }
+                for j in 0..4 {
+                    a[i][j] -= f * a[col][j];
+                    inv[i][j] -= f * inv[col][j];
+                }
+            }
+        }
+        Some(Self { m: inv })
+    }
+
+    /// 点を変換する（w = 1 として扱い、射影成分は無視する）
+    pub fn transform_point(&self, p: Point3) -> Point3 {
+        let m = &self.m;
+        Point3::new(
+            m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3],
+            m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3],
+            m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3],
+        )
+    }
+
+    /// 点を変換し、得られた同次座標を w で割って返す（透視除算あり）
+    /// w がゼロになる場合は `None` を返す
+    pub fn project_point(&self, p: Point3) -> Option<Point3> {
+        let m = &self.m;
+        let q = self.transform_point(p);
+        let w = m[3][0] * p.x + m[3][1] * p.y + m[3][2] * p.z + m[3][3];
+        if w == 0.0 {
+            return None;
+        }
+        Some(Point3::new(q.x / w, q.y / w, q.z / w))
+    }
+
+    /// ベクトルを変換する（w = 0 として扱うため平行移動は影響しない）
+    pub fn transform_vector(&self, v: Vector3) -> Vector3 {
+        let m = &self.m;
+        Vector3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 行列同士の積の実装
+impl Mul for Matrix4 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let mut r = [[0.0; 4]; 4];
+        for (i, row) in r.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Self { m: r }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_matrix_eq(a: &Matrix4, b: &Matrix4) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-10, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_translation_and_point() {
+        let t = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let p = t.transform_point(Point3::new(1.0, 1.0, 1.0));
+        assert_eq!(p, Point3::new(2.0, 3.0, 4.0));
+        // ベクトルは平行移動の影響を受けない
+        let v = t.transform_vector(Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(v, Vector3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let r = Matrix4::from_rotation_z(FRAC_PI_2);
+        let v = r.transform_vector(Vector3::new(1.0, 0.0, 0.0));
+        assert!((v.x - 0.0).abs() < 1e-10);
+        assert!((v.y - 1.0).abs() < 1e-10);
+        assert!((r.determinant() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_multiply_and_inverse() {
+        let m = Matrix4::from_translation(Vector3::new(1.0, -2.0, 0.5))
+            * Matrix4::from_axis_angle(Vector3::new(1.0, 1.0, 0.0), 0.7)
+            * Matrix4::from_scale(Vector3::new(2.0, 3.0, 4.0));
+        assert!((m.determinant() - 24.0).abs() < 1e-9);
+        let inv = m.inverse().unwrap();
+        assert_matrix_eq(&(m * inv), &Matrix4::identity());
+        assert_matrix_eq(&(inv * m), &Matrix4::identity());
+        assert_matrix_eq(&m.transpose().transpose(), &m);
+    }
+
+    #[test]
+    fn test_singular_matrix() {
+        let m = Matrix4::from_scale(Vector3::new(1.0, 0.0, 1.0));
+        assert!(m.determinant().abs() < 1e-12);
+        assert!(m.inverse().is_none());
+    }
+
+    #[test]
+    fn test_perspective_divide() {
+        let mut m = Matrix4::identity();
+        // w' = z となる単純な射影
+        m.m[3][2] = 1.0;
+        m.m[3][3] = 0.0;
+        let p = m.project_point(Point3::new(2.0, 4.0, 2.0)).unwrap();
+        assert_eq!(p, Point3::new(1.0, 2.0, 1.0));
+        assert!(m.project_point(Point3::new(1.0, 1.0, 0.0)).is_none());
+    }
+}
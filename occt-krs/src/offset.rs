@@ -0,0 +1,1061 @@
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use crate::boolean;
+use crate::check::edge_parameters;
+use crate::classify::FaceClassifier;
+use crate::extrude::{area_vector, translated, wire_points};
+use crate::fillet::{edge_frames, solve_planes, EdgeFrame};
+use crate::primitives::make_box;
+use crate::projection::project_point;
+use crate::shape_builder::edge_pcurve;
+use crate::topo::ShapeId;
+use crate::{
+    extrude, fillet, loft, precision, sew, AncestorMap, Axis2, BSplineCurve, BSplineCurve2,
+    BSplineSurface, BoundingBox, Circle, Compound, ConicalSurface, Curve3, CylindricalSurface, Dir,
+    Edge, EndConditions, Face, GeomCurve, GeomSurface, Line, OcctKrsError, OffsetSurface,
+    Orientation, Plane, Point3, Result, Shape, ShapeKind, Shell, Solid, SphericalSurface, Surface,
+    ToroidalSurface, Transform, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 自由曲面をずらした曲面を近似するときの、各方向の標本の区間数
+const OFFSET_SAMPLES: usize = 16;
+
+/// 一般の曲線のエッジをずらすときの標本の区間数
+const EDGE_SAMPLES: usize = 16;
+
+/// 曲面どうしの交点を求める反復の上限
+const MAX_ITERATIONS: usize = 50;
+
+/// 求めた交点を曲面の上とみなす距離の上限（近似した曲面への射影の誤差を見込む）
+const MAX_GAP: f64 = 1e-5;
+
+/// 立体をくり抜いて一定の厚さの殻にする（OCCT の `BRepOffsetAPI_MakeThickSolid` 相当）
+///
+/// 外形を保ったまま、各フェイスから内側へ `thickness` の位置にずらした曲面で囲んだ立体を取り除く。
+/// `faces_to_remove` のフェイスは取り除く側を外へずらすので、そこが開口になる。
+/// 空なら内部に空洞を持つ閉じた立体になる。
+///
+/// 厚さが正の有限値でない場合、形状に立体がない場合、取り除くフェイスが立体のものでない場合は
+/// エラーを返す。厚さで位相が変わる形状（厚さより小さい半径の凹の丸みなど）には対応しない。
+pub fn shell(shape: &Shape, faces_to_remove: &[Face], thickness: f64) -> Result<Shape> {
+    if !(thickness > 0.0 && thickness.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "殻の厚さが不正です: {}",
+            thickness
+        )));
+    }
+    let faces = shape.faces();
+    if let Some(face) = faces_to_remove
+        .iter()
+        .find(|f| !faces.iter().any(|g| g.is_same(f)))
+    {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "取り除くフェイスが立体にありません: {:?}",
+            face.surface()
+        )));
+    }
+    let inner = offset_shells(shape, |face| {
+        if faces_to_remove.iter().any(|f| f.is_same(face)) {
+            thickness
+        } else {
+            -thickness
+        }
+    })?;
+    boolean::cut(shape, &inner)
+}
+
+/// 開いたフェイスやシェルに厚さをつけて立体にする（OCCT の `BRepOffset_MakeOffset` の厚み付け相当）
+///
+/// フェイスを法線の向きに `thickness` だけずらした面を作り（負なら法線と反対の向き）、
+/// 元の面の自由な境界とずらした面の境界の間を側面でふさいで縫い合わせる。
+/// 形状にシェルが複数あれば、それぞれを立体にした複合形状を返す。
+///
+/// 厚さが 0 や有限でない場合、フェイスやシェルがない場合、閉じたシェルの場合
+/// （[`offset_shape`] を使う）、縫い合わせても閉じない場合はエラーを返す。
+pub fn thicken(shape: &Shape, thickness: f64) -> Result<Shape> {
+    if !(thickness != 0.0 && thickness.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "厚さが不正です: {}",
+            thickness
+        )));
+    }
+    let shells: Vec<Shape> = match Face::try_from(shape.clone()) {
+        Ok(face) => vec![Shape::from(Shell::new(vec![face])?)],
+        Err(_) => shape.explore(ShapeKind::Shell).unique().collect(),
+    };
+    if shells.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "厚さをつける形状にフェイスもシェルもありません".to_string(),
+        ));
+    }
+    let mut solids = Vec::new();
+    for shell in &shells {
+        let bounds = shell.free_bounds()?;
+        if bounds.free_wires().is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "閉じたシェルには厚さをつけられません".to_string(),
+            ));
+        }
+        let (moved, edges) = offset_with_edges(shell, |_| thickness)?;
+        let mut faces = shell.faces();
+        faces.extend(moved.faces());
+        for edge in bounds.free_wires().iter().flat_map(Wire::edges) {
+            let moved = edges.get(&edge.id()).ok_or_else(|| {
+                OcctKrsError::InvalidInput("境界のエッジがシェルにありません".to_string())
+            })?;
+            faces.push(side_face(&edge, &moved.oriented(edge.orientation()))?);
+        }
+        let sewing = sew(faces, precision::confusion())?;
+        if !sewing.is_closed() {
+            return Err(OcctKrsError::DegenerateGeometry(format!(
+                "厚さをつけた面を縫い合わせても閉じません（自由エッジ {} 本）",
+                sewing.free_edges().len()
+            )));
+        }
+        solids.extend(sewing.solids()?);
+    }
+    Ok(if solids.len() == 1 {
+        Shape::from(solids.remove(0))
+    } else {
+        Shape::from(Compound::new(solids.into_iter().map(Shape::from).collect()))
+    })
+}
+
+/// 自由な境界のエッジ `edge` と、それをずらしたエッジ `moved` の間をふさぐ側面を返す
+///
+/// 一定の向きにずらしたエッジなら押し出し、2本が同じ平面の上にあれば平面のフェイス
+/// （閉じたエッジなら環）、それ以外は2本を結ぶ線織面にする。
+fn side_face(edge: &Edge, moved: &Edge) -> Result<Face> {
+    let shift = moved.start_point() - edge.start_point();
+    let translated = moved.located_geometry().is_some_and(|curve| {
+        edge_parameters(edge).iter().all(|&t| {
+            let p = edge.point_at(t) + shift;
+            project_point(curve.as_ref(), p)
+                .iter()
+                .any(|q| q.distance <= precision::confusion())
+        })
+    });
+    if translated {
+        let direction = Dir::from_vector(shift)?;
+        return Face::try_from(extrude(
+            &Shape::from(edge.clone()),
+            direction,
+            shift.length(),
+        )?);
+    }
+    let tolerance = precision::confusion();
+    if edge.is_closed() {
+        // 閉じたエッジの間の平面は、大きい方を外側、小さい方を穴にした環
+        let (a, b) = (
+            Wire::new(vec![edge.clone()])?,
+            Wire::new(vec![moved.clone()])?,
+        );
+        let area = |w: &Wire| area_vector(&wire_points(w)).length();
+        let (outer, inner) = if area(&a) >= area(&b) { (a, b) } else { (b, a) };
+        if let Ok(face) = Face::from_planar_wires(outer, vec![inner], tolerance) {
+            return Ok(face);
+        }
+    } else {
+        let lateral = |a: Vertex, b: Vertex| -> Result<Edge> {
+            let length = a.point().distance(b.point());
+            Edge::new(
+                Line::from_points(a.point(), b.point())?,
+                (0.0, length),
+                a,
+                b,
+            )
+        };
+        let wire = Wire::new(vec![
+            edge.clone(),
+            lateral(edge.end_vertex(), moved.end_vertex())?,
+            moved.reversed(),
+            lateral(moved.start_vertex(), edge.start_vertex())?,
+        ])?;
+        if let Ok(face) = Face::from_planar_wires(wire, Vec::new(), tolerance) {
+            return Ok(face);
+        }
+    }
+    let sections = [
+        Wire::new(vec![edge.clone()])?,
+        Wire::new(vec![moved.clone()])?,
+    ];
+    let ruled = loft(&sections, true, false)?;
+    ruled
+        .faces()
+        .pop()
+        .ok_or_else(|| OcctKrsError::DegenerateGeometry("側面の線織面が作れません".to_string()))
+}
+
+/// オフセットで、ずらしたフェイスの間の隙間のつなぎ方（OCCT の `GeomAbs_JoinType` 相当）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    /// 元の稜線を軸とする円弧の面（丸み）でつなぐ
+    Arc,
+    /// ずらした曲面を延ばして交わらせる
+    Intersection,
+}
+
+/// 形状を外向きの法線の向きに `distance` だけずらした形状を作る（OCCT の `BRepOffsetAPI_MakeOffsetShape` 相当）
+///
+/// `distance` が正なら外へ太らせ、負なら内へ細らせる。0 なら形状をそのまま返す。
+/// 立体では、太らせるときに凸の稜線、細らせるときに凹の稜線の隙間を `join` でつなぐ。
+/// `JoinType::Arc` は半径 `|distance|` のフィレットで丸める（対応する稜線は [`crate::fillet`] と同じ）。
+/// シェルはフェイスをずらすだけで、`join` は使わない。
+///
+/// 凹の部分（細らせるときは凸の部分）がつぶれてフェイスが反転する場合は、平面の稜線で凸な部分に
+/// 切り分けてそれぞれをずらし、和をとる（細らせるときは外側の補集合で同じことをして取り除く）。
+///
+/// 距離が有限でない場合、立体もシェルもない場合、つぶれる部分を平面で切り分けられない場合はエラーを返す。
+pub fn offset_shape(shape: &Shape, distance: f64, join: JoinType) -> Result<Shape> {
+    if !distance.is_finite() {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "オフセットの距離が不正です: {}",
+            distance
+        )));
+    }
+    if distance == 0.0 {
+        return Ok(shape.clone());
+    }
+    if shape.explore(ShapeKind::Solid).next().is_none() {
+        return offset_shells(shape, |_| distance);
+    }
+    match offset_whole(shape, distance, join) {
+        Err(OcctKrsError::DegenerateGeometry(_)) if distance > 0.0 => {
+            let mut result: Option<Shape> = None;
+            for piece in convex_pieces(shape)? {
+                let grown = offset_whole(&piece, distance, join)?;
+                result = Some(match result {
+                    Some(r) => boolean::fuse(&r, &grown)?,
+                    None => grown,
+                });
+            }
+            result.ok_or_else(|| {
+                OcctKrsError::InvalidInput("オフセットする立体がありません".to_string())
+            })
+        }
+        Err(OcctKrsError::DegenerateGeometry(_)) => {
+            // 細らせた立体は、外側の補集合を太らせたものを取り除いた残り
+            let outside = boolean::cut(&enclosing_box(shape, -2.0 * distance)?, shape)?;
+            let grown = convex_pieces(&outside)?
+                .iter()
+                .map(|piece| offset_whole(piece, -distance, join))
+                .collect::<Result<Vec<_>>>()?;
+            boolean::cut_all(shape, &grown)
+        }
+        other => other,
+    }
+}
+
+/// 立体の位相を保ってずらし、`join` が `Arc` なら隙間の稜線を丸める
+fn offset_whole(shape: &Shape, distance: f64, join: JoinType) -> Result<Shape> {
+    let moved = offset_shells(shape, |_| distance)?;
+    if join == JoinType::Intersection {
+        return Ok(moved);
+    }
+    let edges: Vec<Edge> = sharp_frames(&moved)?
+        .into_iter()
+        .filter(|(_, frame)| frame.convex == (distance > 0.0))
+        .map(|(edge, _)| edge)
+        .collect();
+    if edges.is_empty() {
+        return Ok(moved);
+    }
+    fillet(&moved, &edges, distance.abs())
+}
+
+/// 立体を、凹の直線の稜線に接するフェイスの平面で切り分けた凸な部分を返す
+///
+/// 凹の稜線がなければ立体そのものを返す。凹の稜線が平面どうしの直線でない場合はエラーを返す。
+fn convex_pieces(shape: &Shape) -> Result<Vec<Shape>> {
+    let mut planes: Vec<Plane> = Vec::new();
+    for (_, frame) in sharp_frames(shape)? {
+        if frame.convex {
+            continue;
+        }
+        let GeomSurface::Plane(plane) = frame.faces[0].located_geometry().into_owned() else {
+            return Err(OcctKrsError::InvalidInput(
+                "平面どうしの直線でない凹の稜線では立体を切り分けられません".to_string(),
+            ));
+        };
+        let same = |p: &Plane| {
+            p.normal()
+                .to_vector()
+                .cross(plane.normal().to_vector())
+                .length()
+                <= precision::angular()
+                && p.signed_distance(plane.origin()).abs() <= precision::confusion()
+        };
+        if !planes.iter().any(same) {
+            planes.push(plane);
+        }
+    }
+    if planes.is_empty() {
+        return Ok(vec![shape.clone()]);
+    }
+    Ok(boolean::split_by_planes(shape, &planes)?
+        .into_iter()
+        .map(|piece| Shape::from(piece.solid))
+        .collect())
+}
+
+/// 立体の滑らかでない稜線と、その断面の向きを返す
+///
+/// [`crate::fillet`] で扱える形の稜線だけを対象にし、それ以外の形の滑らかでない稜線があればエラーを返す。
+fn sharp_frames(shape: &Shape) -> Result<Vec<(Edge, EdgeFrame)>> {
+    let map = shape.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+    let mut frames = Vec::new();
+    for edge in shape.edges() {
+        let faces: Vec<Face> = map
+            .get(&Shape::from(edge.clone()))
+            .iter()
+            .filter_map(|s| Face::try_from(s.clone()).ok())
+            .collect();
+        let [a, b] = faces.as_slice() else {
+            continue;
+        };
+        if is_smooth(&edge, [a, b])? {
+            continue;
+        }
+        if let Some(frame) = edge_frames(shape, std::slice::from_ref(&edge))?.pop() {
+            frames.push((edge, frame));
+        }
+    }
+    Ok(frames)
+}
+
+/// エッジの中点で両側のフェイスの外向きの法線がそろっていれば `true` を返す（退化したエッジも `true`）
+pub(crate) fn is_smooth(edge: &Edge, faces: [&Face; 2]) -> Result<bool> {
+    let Some(curve) = edge.located_geometry() else {
+        return Ok(true);
+    };
+    let (t0, t1) = edge.range();
+    let p = curve.point_at(0.5 * (t0 + t1));
+    let mut normals = [Vector3::ZERO; 2];
+    for (k, face) in faces.iter().enumerate() {
+        let Some(n) = FaceClassifier::new(face)?.normal_at(p) else {
+            return Ok(false);
+        };
+        normals[k] = if face.orientation() == Orientation::Reversed {
+            -n.to_vector()
+        } else {
+            n.to_vector()
+        };
+    }
+    Ok(normals[0].dot(normals[1]) >= 1.0 - precision::angular())
+}
+
+/// 立体の境界ボックスを `margin` だけ広げた直方体を返す
+fn enclosing_box(shape: &Shape, margin: f64) -> Result<Shape> {
+    let mut bounds: Option<BoundingBox> = None;
+    for face in shape.faces() {
+        let b = FaceClassifier::new(&face)?.bounding_box;
+        bounds = Some(bounds.map_or(b, |a| a.union(&b)));
+    }
+    let Some(bounds) = bounds else {
+        return Err(OcctKrsError::InvalidInput(
+            "フェイスがありません".to_string(),
+        ));
+    };
+    let size = bounds.size() + Vector3::new(2.0 * margin, 2.0 * margin, 2.0 * margin);
+    let corner = bounds.min - Vector3::new(margin, margin, margin);
+    let position = Axis2::new(corner, Dir::Z, Dir::X)?;
+    Ok(Shape::from(make_box(position, size.x, size.y, size.z)?))
+}
+
+/// 立体（なければシェル）のフェイスをそれぞれ外向きの法線の向きに `distance(face)` だけずらし、
+/// 同じ位相の形状を作る
+///
+/// 頂点とエッジは、隣り合うフェイスをずらした曲面どうしの交わりとして元の位置の近くで求める。
+/// 直線のエッジは平行移動、円のエッジは同じ軸の円にする。解析曲面はパラメータを保ってずらすので、
+/// pcurve は元のものを引き継ぎ、合わなければパラメータ空間の線分として作り直す。
+///
+/// ずらした曲面が退化する場合や、エッジが縮んで向きが反転する場合（凹の部分がつぶれる場合など）は
+/// `DegenerateGeometry` を返す。
+pub(crate) fn offset_shells(shape: &Shape, distance: impl Fn(&Face) -> f64) -> Result<Shape> {
+    offset_with_edges(shape, distance).map(|(moved, _)| moved)
+}
+
+/// [`offset_shells`] と同じようにずらした形状と、元のエッジの ID からずらしたエッジへの対応を返す
+fn offset_with_edges(
+    shape: &Shape,
+    distance: impl Fn(&Face) -> f64,
+) -> Result<(Shape, HashMap<ShapeId, Edge>)> {
+    replace_surfaces(shape, |face| {
+        let d = distance(face);
+        let s = if face.orientation() == Orientation::Reversed {
+            -d
+        } else {
+            d
+        };
+        offset_surface(&face.located_geometry(), s)
+    })
+}
+
+/// 立体（なければシェル）の各フェイスの曲面を `surface_of(face)` に置き換え、同じ位相の形状を作る
+///
+/// `surface_of` は新しい曲面と、元の pcurve を新しい曲面のパラメータへ移すずれ
+/// （パラメータを保たない曲面なら `None`）を返す。頂点とエッジは、隣り合うフェイスの新しい曲面どうしの
+/// 交わりとして元の位置の近くで求め直す。置き換えた形状と、元のエッジの ID から新しいエッジへの対応を返す。
+pub(crate) fn replace_surfaces(
+    shape: &Shape,
+    surface_of: impl Fn(&Face) -> Result<(GeomSurface, Option<Vector2>)>,
+) -> Result<(Shape, HashMap<ShapeId, Edge>)> {
+    let solid = shape
+        .explore(ShapeKind::Solid)
+        .next()
+        .and_then(|s| Solid::try_from(s).ok());
+    let shells: Vec<Shell> = match &solid {
+        Some(solid) => solid.shells(),
+        None => shape
+            .explore(ShapeKind::Shell)
+            .unique()
+            .filter_map(|s| Shell::try_from(s).ok())
+            .collect(),
+    };
+    if shells.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "形状に立体もシェルもありません".to_string(),
+        ));
+    }
+    let solid_shape = match &solid {
+        Some(solid) => Shape::from(solid.clone()),
+        None => Shape::from(Compound::new(
+            shells.iter().cloned().map(Shape::from).collect(),
+        )),
+    };
+    let mut surfaces: HashMap<ShapeId, (GeomSurface, Option<Vector2>)> = HashMap::new();
+    for face in solid_shape.faces() {
+        surfaces.insert(face.id(), surface_of(&face)?);
+    }
+    let vertex_faces = solid_shape.ancestor_map(ShapeKind::Vertex, ShapeKind::Face);
+    let edge_faces = solid_shape.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+    let surfaces_of = |map: &AncestorMap, shape: &Shape| -> Vec<&GeomSurface> {
+        map.get(shape)
+            .iter()
+            .filter_map(|f| surfaces.get(&f.id()).map(|(s, _)| s))
+            .collect()
+    };
+
+    let mut vertices: HashMap<ShapeId, Vertex> = HashMap::new();
+    for vertex in solid_shape.explore(ShapeKind::Vertex).unique() {
+        let Ok(v) = Vertex::try_from(vertex.clone()) else {
+            continue;
+        };
+        let on = surfaces_of(&vertex_faces, &vertex);
+        let p = intersection_point(&on, v.point(), None)?;
+        vertices.insert(v.id(), Vertex::new(p));
+    }
+    let mut edges: HashMap<ShapeId, (Edge, ParameterMap)> = HashMap::new();
+    for shape in solid_shape.explore(ShapeKind::Edge).unique() {
+        let Ok(edge) = Edge::try_from(shape.clone()) else {
+            continue;
+        };
+        let edge = edge.oriented(Orientation::Forward);
+        let on = surfaces_of(&edge_faces, &shape);
+        let moved = offset_edge(&edge, &on, &vertices)?;
+        edges.insert(edge.id(), moved);
+    }
+
+    let mut moved_shells = Vec::new();
+    for shell in shells {
+        let mut moved = Vec::new();
+        for face in shell.faces() {
+            let Some((surface, shift)) = surfaces.get(&face.id()) else {
+                continue;
+            };
+            let forward = face.oriented(Orientation::Forward);
+            let mut wires = Vec::new();
+            let mut pcurves = Vec::new();
+            for wire in forward.wires() {
+                let mut wire_edges = Vec::new();
+                let mut wire_pcurves = Vec::new();
+                for e in wire.edges() {
+                    let Some((new, mapping)) = edges.get(&e.id()) else {
+                        continue;
+                    };
+                    let mut pcurve = match (shift, forward.pcurve(&e)) {
+                        (Some(shift), Some(p)) => transfer_pcurve(p, mapping, *shift)?
+                            .and_then(|p| fit_on(p, new, surface)),
+                        _ => None,
+                    };
+                    if pcurve.is_none() && !matches!(surface, GeomSurface::Plane(_)) {
+                        pcurve = edge_pcurve(new, surface, new.tolerance())?;
+                    }
+                    wire_edges.push(new.oriented(e.orientation()));
+                    wire_pcurves.push(pcurve);
+                }
+                wires.push(Wire::new(wire_edges)?);
+                pcurves.push(wire_pcurves);
+            }
+            let new = Face::with_pcurves(surface.clone(), wires, pcurves)?;
+            moved.push(new.oriented(face.orientation()));
+        }
+        moved_shells.push(Shell::new(moved)?);
+    }
+    let moved = match solid {
+        Some(_) => Shape::from(Solid::new(moved_shells)?),
+        None if moved_shells.len() == 1 => Shape::from(moved_shells.remove(0)),
+        None => Shape::from(Compound::new(
+            moved_shells.into_iter().map(Shape::from).collect(),
+        )),
+    };
+    let edges = edges
+        .into_iter()
+        .map(|(id, (edge, _))| (id, edge))
+        .collect();
+    Ok((moved, edges))
+}
+
+/// 曲面を法線の向きに `s` だけずらした曲面と、元のパラメータからの pcurve のずれを返す
+///
+/// 解析曲面は同じ種類の曲面にし、パラメータ `(u, v)` の点を法線の向きにずらした点が
+/// ずらした曲面の `(u, v) + ずれ` になるようにする。それ以外の曲面は [`OffsetSurface`] を
+/// 格子で補間した B-スプライン曲面にし、ずれは `None`（パラメータは保たない）を返す。
+fn offset_surface(surface: &GeomSurface, s: f64) -> Result<(GeomSurface, Option<Vector2>)> {
+    let degenerate = || {
+        OcctKrsError::DegenerateGeometry(format!(
+            "ずらした曲面が退化します（距離 {}）: {:?}",
+            s, surface
+        ))
+    };
+    // 半径が増える向きと曲面の法線の向きが同じなら 1、逆なら -1
+    let sign = |grow: Vector3| {
+        let n = surface
+            .normal_at(0.0, 0.0)
+            .map_or(Vector3::ZERO, Dir::to_vector);
+        if n.dot(grow) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        }
+    };
+    let radius = |r: f64| {
+        if r > precision::confusion() {
+            Ok(r)
+        } else {
+            Err(degenerate())
+        }
+    };
+    let zero = Some(Vector2::new(0.0, 0.0));
+    Ok(match surface {
+        GeomSurface::Plane(plane) => {
+            let t = Transform::from_translation(plane.normal().to_vector() * s);
+            (GeomSurface::Plane(plane.transformed(&t)), zero)
+        }
+        GeomSurface::Cylinder(c) => {
+            let p = c.position();
+            let r = radius(c.radius() + sign(p.x_direction().to_vector()) * s)?;
+            (CylindricalSurface::new(p, r)?.into(), zero)
+        }
+        GeomSurface::Sphere(c) => {
+            let p = c.position();
+            let r = radius(c.radius() + sign(p.x_direction().to_vector()) * s)?;
+            (SphericalSurface::new(p, r)?.into(), zero)
+        }
+        GeomSurface::Torus(c) => {
+            let p = c.position();
+            let r = radius(c.minor_radius() + sign(p.x_direction().to_vector()) * s)?;
+            (ToroidalSurface::new(p, c.major_radius(), r)?.into(), zero)
+        }
+        GeomSurface::Cone(c) => {
+            // 母線に垂直に σs ずらすと、基準の半径は σs / cos A 増え、v は σs tan A 減る
+            let p = c.position();
+            let a = c.semi_angle();
+            let grow = p.x_direction().to_vector() * a.cos() - p.direction().to_vector() * a.sin();
+            let t = sign(grow) * s;
+            let cone = ConicalSurface::new(p, a, c.ref_radius() + t / a.cos())
+                .map_err(|_| degenerate())?;
+            (cone.into(), Some(Vector2::new(0.0, -t * a.tan())))
+        }
+        other => {
+            let ((u0, u1), (v0, v1)) = (other.u_range(), other.v_range());
+            if ![u0, u1, v0, v1].iter().all(|x| x.is_finite()) {
+                return Err(OcctKrsError::InvalidInput(
+                    "パラメータ範囲が無限の自由曲面はずらせません".to_string(),
+                ));
+            }
+            let offset = OffsetSurface::new(other.clone(), s)?;
+            let n = OFFSET_SAMPLES;
+            let at = |a: f64, b: f64, k: usize| a + (b - a) * k as f64 / n as f64;
+            let grid: Vec<Vec<Point3>> = (0..=n)
+                .map(|i| {
+                    (0..=n)
+                        .map(|j| offset.point_at(at(u0, u1, i), at(v0, v1, j)))
+                        .collect()
+                })
+                .collect();
+            (BSplineSurface::interpolate(&grid, 3, 3)?.into(), None)
+        }
+    })
+}
+
+/// 移したエッジのパラメータと元のエッジのパラメータの対応
+enum ParameterMap {
+    /// 同じパラメータが対応する
+    Same,
+    /// 元の区間 `.0` と移した区間 `.1` が線形に対応する（直線のエッジ）
+    Linear((f64, f64), (f64, f64)),
+    /// 対応しない（pcurve は引き継がない）
+    Lost,
+}
+
+/// エッジを、隣り合うフェイスをずらした曲面 `surfaces` の交わりに移す
+///
+/// 移したエッジと、元のエッジとのパラメータの対応を返す。
+fn offset_edge(
+    edge: &Edge,
+    surfaces: &[&GeomSurface],
+    vertices: &HashMap<ShapeId, Vertex>,
+) -> Result<(Edge, ParameterMap)> {
+    let vertex = |v: Vertex| {
+        vertices
+            .get(&v.id())
+            .cloned()
+            .ok_or_else(|| OcctKrsError::InvalidInput("エッジの頂点が立体にありません".to_string()))
+    };
+    let (start, end) = (vertex(edge.start_vertex())?, vertex(edge.end_vertex())?);
+    let range = edge.range();
+    let Some(curve) = edge.located_geometry() else {
+        return Ok((Edge::degenerate(start, range)?, ParameterMap::Same));
+    };
+    let (t0, t1) = range;
+    let point = |t: f64| {
+        let p = curve.point_at(t);
+        intersection_point(surfaces, p, Some(curve.derivative_at(t)))
+    };
+    let exact = match curve.as_ref() {
+        GeomCurve::Line(line) => {
+            // 向きが変わらなければ平行移動し、パラメータ区間は移した頂点から決める
+            let (a, b) = (point(t0)?, point(t1)?);
+            let parallel = (b - a).cross(line.dir.to_vector()).length()
+                <= precision::angular() * (b - a).length();
+            let moved = if parallel {
+                line.transformed(&Transform::from_translation(a - curve.point_at(t0)))
+            } else {
+                Line::from_points(a, b)?
+            };
+            let moved_range = (
+                moved.parameter_of(start.point()),
+                moved.parameter_of(end.point()),
+            );
+            Some((GeomCurve::Line(moved), moved_range))
+        }
+        GeomCurve::Circle(circle) => {
+            let samples = if edge.is_closed() {
+                [t0, t0 + (t1 - t0) / 3.0, t0 + 2.0 * (t1 - t0) / 3.0]
+            } else {
+                [t0, 0.5 * (t0 + t1), t1]
+            };
+            let [a, b, c] = [point(samples[0])?, point(samples[1])?, point(samples[2])?];
+            coaxial_circle(circle, [a, b, c]).map(|moved| {
+                let moved_range = if edge.is_closed() {
+                    range
+                } else {
+                    let s0 = moved.parameter_of(start.point());
+                    let s0 = s0 - TAU * ((s0 - t0) / TAU).round();
+                    let s1 = moved.parameter_of(end.point());
+                    (s0, s1 + TAU * ((s0 + (t1 - t0) - s1) / TAU).round())
+                };
+                (GeomCurve::Circle(moved), moved_range)
+            })
+        }
+        _ => None,
+    };
+    let (moved, new_range, mapping) = match exact {
+        Some((c, new_range)) => {
+            let same = (new_range.0 - t0).abs() <= precision::confusion()
+                && (new_range.1 - t1).abs() <= precision::confusion();
+            let mapping = match (same, curve.as_ref()) {
+                (true, _) => ParameterMap::Same,
+                (false, GeomCurve::Line(_)) => ParameterMap::Linear(range, new_range),
+                (false, _) => ParameterMap::Lost,
+            };
+            (c, new_range, mapping)
+        }
+        None => {
+            let closed = edge.is_closed();
+            let n = EDGE_SAMPLES;
+            let count = if closed { n } else { n + 1 };
+            let points = (0..count)
+                .map(|k| point(t0 + (t1 - t0) * k as f64 / n as f64))
+                .collect::<Result<Vec<_>>>()?;
+            let conditions = if closed {
+                EndConditions::Periodic
+            } else {
+                EndConditions::Natural
+            };
+            let c = BSplineCurve::interpolate(&points, conditions)?;
+            let new_range = (c.first_parameter(), c.last_parameter());
+            (GeomCurve::BSpline(c), new_range, ParameterMap::Lost)
+        }
+    };
+    if !edge.is_closed() {
+        let before = edge.end_point() - edge.start_point();
+        let after = end.point() - start.point();
+        if after.dot(before) <= precision::confusion() * before.length() {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "ずらしたエッジがつぶれて向きが反転します".to_string(),
+            ));
+        }
+    }
+    // 曲線の端と頂点のずれ、補間した曲線では標本の間での曲面からのずれをエッジの許容誤差にする
+    let mut gap = moved
+        .point_at(new_range.0)
+        .distance(start.point())
+        .max(moved.point_at(new_range.1).distance(end.point()));
+    if matches!(mapping, ParameterMap::Lost) {
+        let (s0, s1) = new_range;
+        for k in 0..EDGE_SAMPLES {
+            let p = moved.point_at(s0 + (s1 - s0) * (k as f64 + 0.5) / EDGE_SAMPLES as f64);
+            for surface in surfaces {
+                if let Some(q) = surface.project(p) {
+                    gap = gap.max(q.point.distance(p));
+                }
+            }
+        }
+    }
+    let new = Edge::new(moved, new_range, start, end)?;
+    let new = if gap > 0.5 * precision::confusion() {
+        new.with_tolerance(2.0 * gap)?
+    } else {
+        new
+    };
+    Ok((new, mapping))
+}
+
+/// 元のエッジの pcurve から、移したエッジの曲面 `(u, v) + shift` での pcurve を作る
+fn transfer_pcurve(
+    pcurve: &BSplineCurve2,
+    mapping: &ParameterMap,
+    shift: Vector2,
+) -> Result<Option<BSplineCurve2>> {
+    match mapping {
+        ParameterMap::Same => translated(pcurve, shift).map(Some),
+        ParameterMap::Linear((a0, a1), (b0, b1)) => {
+            // 直線のエッジの pcurve は解析曲面の上で直線なので、端を延ばして作り直す
+            let (p0, p1) = (pcurve.point_at(*a0), pcurve.point_at(*a1));
+            let at = |t: f64| p0 + (p1 - p0) * ((t - a0) / (a1 - a0)) + shift;
+            let (lo, hi) = (b0.min(*b1), b0.max(*b1));
+            let line =
+                BSplineCurve2::from_flat_knots(1, vec![at(lo), at(hi)], vec![lo, lo, hi, hi])?;
+            Ok(Some(line))
+        }
+        ParameterMap::Lost => Ok(None),
+    }
+}
+
+/// 引き継いだ pcurve `guess` を確かめ、移したエッジに合わなければ作り直す
+///
+/// 隣のフェイスとの交わりとして移したエッジは、このフェイスの法線の向きにずらした位置からずれることがある
+/// （円柱の底の円が軸の向きに動くなど）。その場合はエッジの両端を曲面に射影して `guess` の近くに
+/// 周期をそろえ、パラメータ空間の線分で表せるときだけそれを返す。
+fn fit_on(guess: BSplineCurve2, edge: &Edge, surface: &GeomSurface) -> Option<BSplineCurve2> {
+    if lies_on(&guess, edge, surface) {
+        return Some(guess);
+    }
+    let curve = edge.located_geometry()?;
+    let period = |(a, b): (f64, f64), closed: bool| closed.then_some(b - a);
+    let periods = [
+        period(surface.u_range(), surface.is_u_closed()),
+        period(surface.v_range(), surface.is_v_closed()),
+    ];
+    let near = |x: f64, prev: f64, period: Option<f64>| match period {
+        Some(period) => x + period * ((prev - x) / period).round(),
+        None => x,
+    };
+    let uv = |t: f64| {
+        let g = guess.point_at(t);
+        let q = surface.project(curve.point_at(t))?;
+        Some(Vector2::new(
+            near(q.u, g.x, periods[0]),
+            near(q.v, g.y, periods[1]),
+        ))
+    };
+    let (t0, t1) = edge.range();
+    let line = BSplineCurve2::from_flat_knots(1, vec![uv(t0)?, uv(t1)?], vec![t0, t0, t1, t1]);
+    line.ok().filter(|line| lies_on(line, edge, surface))
+}
+
+/// pcurve を曲面に写した曲線がエッジの曲線に端と中点で重なるかどうか
+fn lies_on(pcurve: &BSplineCurve2, edge: &Edge, surface: &GeomSurface) -> bool {
+    let Some(curve) = edge.located_geometry() else {
+        return true;
+    };
+    let (t0, t1) = edge.range();
+    [t0, 0.5 * (t0 + t1), t1].iter().all(|&t| {
+        let uv = pcurve.point_at(t);
+        surface.point_at(uv.x, uv.y).distance(curve.point_at(t)) <= edge.tolerance()
+    })
+}
+
+/// 3点を通る円が元の円と同じ軸の上にあれば、元の円と同じ向きとパラメータの円を返す
+fn coaxial_circle(circle: &Circle, points: [Point3; 3]) -> Option<Circle> {
+    let [a, b, c] = points;
+    let through = Circle::from_three_points(a, b, c).ok()?;
+    let position = circle.position();
+    let axis = position.direction().to_vector();
+    let center = through.center();
+    let offset = center - position.location();
+    let parallel = through
+        .position()
+        .direction()
+        .to_vector()
+        .cross(axis)
+        .length();
+    if parallel > precision::angular() || offset.cross(axis).length() > precision::confusion() {
+        return None;
+    }
+    let moved = Axis2::new(center, position.direction(), position.x_direction()).ok()?;
+    Circle::new(moved, through.radius()).ok()
+}
+
+/// `p` の近くで、曲面 `surfaces` のすべてに乗る点を求める
+///
+/// 各曲面の `p` に最も近い点での接平面の交点へ移る反復を行う。向きの異なる接平面が2枚だけなら
+/// `tangent`（なければ2枚の法線の外積）に垂直で `p` を通る平面を加え、1枚だけなら曲面への射影になる。
+fn intersection_point(
+    surfaces: &[&GeomSurface],
+    p: Point3,
+    tangent: Option<Vector3>,
+) -> Result<Point3> {
+    let failed = || {
+        OcctKrsError::DegenerateGeometry(
+            "ずらしたフェイスの曲面の交わりが求められません".to_string(),
+        )
+    };
+    let mut x = p;
+    for _ in 0..MAX_ITERATIONS {
+        let mut rows: Vec<(Vector3, f64)> = Vec::with_capacity(3);
+        for surface in surfaces {
+            let q = surface.project(x).ok_or_else(failed)?;
+            let n = surface.normal_at(q.u, q.v).ok_or_else(failed)?.to_vector();
+            if rows
+                .iter()
+                .any(|(m, _)| m.cross(n).length() <= precision::angular())
+            {
+                continue;
+            }
+            rows.push((n, n.dot(q.point.to_vector())));
+        }
+        let next = match rows.len() {
+            0 => return Err(failed()),
+            1 => {
+                let (n, d) = rows[0];
+                x + n * (d - n.dot(x.to_vector()))
+            }
+            2 => {
+                let t = tangent.unwrap_or_else(|| rows[0].0.cross(rows[1].0));
+                rows.push((t, t.dot(p.to_vector())));
+                solve_planes(&rows)?
+            }
+            _ => solve_planes(&rows[..3])?,
+        };
+        let step = next.distance(x);
+        x = next;
+        if step <= 1e-3 * precision::confusion() {
+            break;
+        }
+    }
+    for surface in surfaces {
+        let q = surface.project(x).ok_or_else(failed)?;
+        if q.point.distance(x) > MAX_GAP {
+            return Err(failed());
+        }
+    }
+    Ok(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use std::f64::consts::PI;
+
+    fn make_block(size: (f64, f64, f64)) -> Shape {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        Shape::from(make_box(position, size.0, size.1, size.2).unwrap())
+    }
+
+    fn top_face(shape: &Shape, z: f64) -> Face {
+        shape
+            .faces()
+            .into_iter()
+            .find(
+                |f| matches!(f.surface(), GeomSurface::Plane(p) if (p.origin().z - z).abs() < 1e-9),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_offset_shells() {
+        let block = make_block((2.0, 1.0, 1.0));
+        let grown = offset_shells(&block, |_| 0.1).unwrap();
+        let volume = checked_volume(&grown);
+        assert!(
+            (volume / (2.2 * 1.2 * 1.2) - 1.0).abs() < 1e-6,
+            "{}",
+            volume
+        );
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let shrunk = offset_shells(&cylinder, |_| -0.25).unwrap();
+        let volume = checked_volume(&shrunk);
+        let expected = PI * 0.75 * 0.75 * 1.5;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_offset_shape() {
+        let (a, b, c) = (2.0, 1.0, 1.0);
+        let block = make_block((a, b, c));
+        let d = 0.1;
+        let sharp = offset_shape(&block, d, JoinType::Intersection).unwrap();
+        let volume = checked_volume(&sharp);
+        let expected = (a + 2.0 * d) * (b + 2.0 * d) * (c + 2.0 * d);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 丸めると球で転がした範囲（シュタイナーの公式）になる
+        let round = offset_shape(&block, d, JoinType::Arc).unwrap();
+        let volume = checked_volume(&round);
+        let expected = a * b * c
+            + 2.0 * (a * b + b * c + c * a) * d
+            + PI * (a + b + c) * d * d
+            + 4.0 / 3.0 * PI * d * d * d;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 内へ細らせる直方体には凹の稜線がないので、つなぎ方によらない
+        let inner = offset_shape(&block, -d, JoinType::Arc).unwrap();
+        let volume = checked_volume(&inner);
+        let expected = (a - 2.0 * d) * (b - 2.0 * d) * (c - 2.0 * d);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        assert!(offset_shape(&block, 0.0, JoinType::Arc)
+            .unwrap()
+            .is_same(&block));
+        assert!(offset_shape(&block, f64::NAN, JoinType::Arc).is_err());
+    }
+
+    #[test]
+    fn test_offset_shape_collapse() {
+        // 幅 1 の溝のある立体を 0.6 太らせると溝がつぶれて直方体になる
+        let block = make_block((3.0, 1.0, 2.0));
+        let position = Axis2::new(Point3::new(1.0, -1.0, 1.0), Dir::Z, Dir::X).unwrap();
+        let slot = Shape::from(make_box(position, 1.0, 3.0, 2.0).unwrap());
+        let slotted = boolean::cut(&block, &slot).unwrap();
+        let d = 0.6;
+        let result = offset_shape(&slotted, d, JoinType::Intersection).unwrap();
+        let volume = checked_volume(&result);
+        let expected = 4.2 * 2.2 * 3.2;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // つぶれない距離では位相を保つ
+        let d = 0.3;
+        let result = offset_shape(&slotted, d, JoinType::Intersection).unwrap();
+        assert_eq!(result.faces().len(), slotted.faces().len());
+        let volume = checked_volume(&result);
+        let expected = 3.6 * 1.6 * 2.6 - 0.4 * 1.6 * 1.0;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 厚さ 0.2 の板は 0.15 細らせると消える
+        let position = Axis2::new(Point3::new(1.4, 0.0, 1.0), Dir::Z, Dir::X).unwrap();
+        let fin = Shape::from(make_box(position, 0.2, 1.0, 1.0).unwrap());
+        let base = make_block((3.0, 1.0, 1.0));
+        let finned = boolean::fuse(&base, &fin).unwrap();
+        let result = offset_shape(&finned, -0.15, JoinType::Intersection).unwrap();
+        let volume = checked_volume(&result);
+        let expected = 2.7 * 0.7 * 0.7;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_shell_box() {
+        let block = make_block((2.0, 1.0, 1.0));
+        let top = top_face(&block, 1.0);
+        let t = 0.1;
+        let result = shell(&block, &[top], t).unwrap();
+        // 外側の5面、内側の5面、穴のあいた上面
+        assert_eq!(result.faces().len(), 11);
+        let volume = checked_volume(&result);
+        let expected = 2.0 - (2.0 - 2.0 * t) * (1.0 - 2.0 * t) * (1.0 - t);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 取り除くフェイスがなければ空洞のある立体になる
+        let result = shell(&block, &[], t).unwrap();
+        assert_eq!(result.explore(ShapeKind::Shell).unique().count(), 2);
+        let volume = checked_volume(&result);
+        let expected = 2.0 - (2.0 - 2.0 * t) * (1.0 - 2.0 * t) * (1.0 - 2.0 * t);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_shell_cylinder() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let top = top_face(&cylinder, 2.0);
+        let t = 0.2;
+        let result = shell(&cylinder, &[top], t).unwrap();
+        let volume = checked_volume(&result);
+        let expected = PI * 2.0 - PI * (1.0 - t) * (1.0 - t) * (2.0 - t);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_thicken() {
+        let block = make_block((2.0, 1.0, 1.0));
+        let top = top_face(&block, 1.0);
+        for t in [0.1, -0.1] {
+            let solid = thicken(&Shape::from(top.clone()), t).unwrap();
+            assert_eq!(solid.kind(), ShapeKind::Solid);
+            assert_eq!(solid.faces().len(), 6);
+            let volume = checked_volume(&solid);
+            assert!((volume / 0.2 - 1.0).abs() < 1e-6, "{}", volume);
+        }
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let side = cylinder
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Cylinder(_)))
+            .unwrap();
+        let tube = thicken(&Shape::from(side), 0.3).unwrap();
+        let volume = checked_volume(&tube);
+        let expected = PI * (1.3 * 1.3 - 1.0) * 2.0;
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+    }
+
+    #[test]
+    fn test_thicken_lofted_sheet() {
+        let section = |y: f64, z: f64| {
+            let edge = Edge::from_points(Point3::new(0.0, y, z), Point3::new(1.0, y, z)).unwrap();
+            Wire::new(vec![edge]).unwrap()
+        };
+        let sheet = loft(
+            &[section(0.0, 0.0), section(1.0, 0.3), section(2.0, 0.0)],
+            false,
+            false,
+        )
+        .unwrap();
+        let t = 0.05;
+        let solid = thicken(&sheet, t).unwrap();
+        let volume = checked_volume(&solid).abs();
+        // 面積は弦の長さ（約 2.1）程度
+        assert!(volume > 2.0 * t && volume < 2.3 * t, "{}", volume);
+    }
+
+    #[test]
+    fn test_thicken_errors() {
+        let block = make_block((1.0, 1.0, 1.0));
+        let top = Shape::from(top_face(&block, 1.0));
+        assert!(thicken(&top, 0.0).is_err());
+        assert!(thicken(&top, f64::INFINITY).is_err());
+        assert!(thicken(&block, 0.1).is_err());
+        let vertex = Shape::from(Vertex::new(Point3::new(0.0, 0.0, 0.0)));
+        assert!(thicken(&vertex, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_shell_errors() {
+        let block = make_block((1.0, 1.0, 1.0));
+        let top = top_face(&block, 1.0);
+        assert!(shell(&block, std::slice::from_ref(&top), 0.0).is_err());
+        let other = top_face(&make_block((1.0, 1.0, 1.0)), 1.0);
+        assert!(shell(&block, &[other], 0.1).is_err());
+        assert!(shell(&Shape::from(top.clone()), &[top], 0.1).is_err());
+    }
+}
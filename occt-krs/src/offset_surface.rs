@@ -0,0 +1,232 @@
+use crate::precision;
+use crate::{OcctKrsError, Point3, Result, Surface, Vector3};
+
+/// 自己交差・退化を調べるためにパラメータ範囲を各方向に分割する区間数
+const VALIDATION_SAMPLES: usize = 16;
+
+/// 基の曲面を法線方向に一定距離だけずらした曲面（OCCT の `Geom_OffsetSurface` 相当）
+///
+/// `P(u, v) = S(u, v) + d N(u, v)`。`N` は基の曲面の `normal_at` で、`d` が負なら裏側へずらす。
+/// パラメータは基の曲面のものをそのまま使う。
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetSurface<S> {
+    basis: S,
+    distance: f64,
+}
+
+impl<S: Surface> OffsetSurface<S> {
+    /// 基の曲面を距離 `distance` だけずらした曲面を生成する
+    ///
+    /// パラメータ範囲を格子状に分割した点で次の場合を調べ、見つかればエラーを返す
+    /// （範囲が無限の方向は長さ1の部分区間で調べる）。
+    /// - 法線が定まらない点がある
+    /// - 曲率半径が `|distance|` 以下で、ずらした面が尖る・裏返る（局所的な退化）
+    /// - ずらした点から基の曲面までの距離が `|distance|` より短く、面の別の部分同士が交わる（大域的な自己交差）
+    pub fn new(basis: S, distance: f64) -> Result<Self> {
+        if !distance.is_finite() {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "オフセット距離が不正です: {}",
+                distance
+            )));
+        }
+        let surface = Self { basis, distance };
+        surface.validate()?;
+        Ok(surface)
+    }
+
+    /// 基の曲面を返す
+    pub fn basis(&self) -> &S {
+        &self.basis
+    }
+
+    /// オフセット距離を返す
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// パラメータ `(u, v)` における点を返す（法線が定まらない点では基の曲面の点）
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        let p = self.basis.point_at(u, v);
+        match self.basis.normal_at(u, v) {
+            Some(n) => p + n.to_vector() * self.distance,
+            None => p,
+        }
+    }
+
+    /// パラメータ `(u, v)` で、ずらした面が局所的に正則なら `true` を返す
+    ///
+    /// 法線が定まり、両方の主曲率 `k` について `1 - d k` が正であることを調べる。
+    pub fn is_regular_at(&self, u: f64, v: f64) -> bool {
+        self.basis.curvature_at(u, v).is_some_and(|c| {
+            let factor = |k: f64| 1.0 - self.distance * k;
+            factor(c.max_curvature).min(factor(c.min_curvature)) > precision::confusion()
+        })
+    }
+
+    /// 基の曲面の単位法線とその u, v 方向の微分を返す（法線が定まらない点では `None`）
+    pub(crate) fn normal_derivatives(&self, u: f64, v: f64) -> Option<(Vector3, Vector3, Vector3)> {
+        let normal = self.basis.normal_at(u, v)?.to_vector();
+        let (su, sv) = (
+            self.basis.derivative_u_at(u, v),
+            self.basis.derivative_v_at(u, v),
+        );
+        let (suu, suv, svv) = self.basis.second_derivatives_at(u, v);
+        let n = su.cross(sv);
+        let len = n.length();
+        if len <= f64::MIN_POSITIVE {
+            return None;
+        }
+        // normal_at は Su × Sv と逆向きのことがあるので、向きを合わせて微分する
+        let sign = if n.dot(normal) < 0.0 { -1.0 } else { 1.0 };
+        let derivative = |dn: Vector3| (dn - normal * normal.dot(dn)) * (sign / len);
+        Some((
+            normal,
+            derivative(suu.cross(sv) + su.cross(suv)),
+            derivative(suv.cross(sv) + su.cross(svv)),
+        ))
+    }
+
+    /// 格子点で法線・局所的な退化・大域的な自己交差を調べる
+    fn validate(&self) -> Result<()> {
+        let d = self.distance.abs();
+        if d == 0.0 {
+            return Ok(());
+        }
+        let (us, vs) = (
+            sample_parameters(self.basis.u_range()),
+            sample_parameters(self.basis.v_range()),
+        );
+        let tolerance = precision::confusion().max(d * 1e-6);
+        for &u in &us {
+            for &v in &vs {
+                if self.basis.normal_at(u, v).is_none() {
+                    return Err(OcctKrsError::DegenerateGeometry(format!(
+                        "法線が定まらない点があります: (u, v) = ({}, {})",
+                        u, v
+                    )));
+                }
+                if !self.is_regular_at(u, v) {
+                    return Err(OcctKrsError::DegenerateGeometry(format!(
+                        "曲率半径がオフセット距離以下で、面が退化します: (u, v) = ({}, {})",
+                        u, v
+                    )));
+                }
+                let p = self.point_at(u, v);
+                if let Some(q) = self.basis.project(p) {
+                    if q.distance < d - tolerance {
+                        return Err(OcctKrsError::DegenerateGeometry(format!(
+                            "オフセットした面が自己交差します: (u, v) = ({}, {})",
+                            u, v
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// パラメータ範囲を分割した標本値を返す（無限の場合は長さ1の部分区間を使う）
+fn sample_parameters((a, b): (f64, f64)) -> Vec<f64> {
+    let (a, b) = match (a.is_finite(), b.is_finite()) {
+        (true, true) => (a, b),
+        (true, false) => (a, a + 1.0),
+        (false, true) => (b - 1.0, b),
+        (false, false) => (-1.0, 1.0),
+    };
+    (0..=VALIDATION_SAMPLES)
+        .map(|i| a + (b - a) * i as f64 / VALIDATION_SAMPLES as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Axis3, BSplineCurve, CylindricalSurface, Dir, EndConditions, ExtrudedSurface, Plane,
+        SphericalSurface, ToroidalSurface,
+    };
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn sphere(r: f64) -> SphericalSurface {
+        SphericalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), r).unwrap()
+    }
+
+    #[test]
+    fn test_sphere_offset() {
+        let outer = OffsetSurface::new(sphere(2.0), 0.5).unwrap();
+        let inner = OffsetSurface::new(sphere(2.0), -1.5).unwrap();
+        for &(u, v) in &[(0.0, 0.0), (1.0, 0.5), (4.0, -1.2)] {
+            assert!((outer.point_at(u, v).distance(Point3::ORIGIN) - 2.5).abs() < 1e-12);
+            assert!((inner.point_at(u, v).distance(Point3::ORIGIN) - 0.5).abs() < 1e-12);
+            assert_point_eq(outer.point_at(u, v), sphere(2.5).point_at(u, v));
+        }
+        assert_eq!(outer.distance(), 0.5);
+        // 中心を越えてずらすと裏返る
+        assert!(OffsetSurface::new(sphere(2.0), -2.0).is_err());
+        assert!(OffsetSurface::new(sphere(2.0), -3.0).is_err());
+        assert!(OffsetSurface::new(sphere(2.0), f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_derivatives_match_finite_differences() {
+        let torus =
+            ToroidalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 3.0, 1.0).unwrap();
+        let s = OffsetSurface::new(torus, 0.4).unwrap();
+        let (u, v, h) = (0.7, 2.1, 1e-6);
+        let du = (s.point_at(u + h, v) - s.point_at(u - h, v)) / (2.0 * h);
+        let dv = (s.point_at(u, v + h) - s.point_at(u, v - h)) / (2.0 * h);
+        assert!((Surface::derivative_u_at(&s, u, v) - du).length() < 1e-7);
+        assert!((Surface::derivative_v_at(&s, u, v) - dv).length() < 1e-7);
+        // 法線は基の曲面と同じ向き
+        let n = s.normal_at(u, v).unwrap();
+        assert!(n.dot(s.basis().normal_at(u, v).unwrap()) > 1.0 - 1e-12);
+    }
+
+    #[test]
+    fn test_plane_and_cylinder_offsets() {
+        let plane = OffsetSurface::new(Plane::xy(), -2.0).unwrap();
+        assert_point_eq(plane.point_at(1.0, 2.0), Point3::new(1.0, 2.0, -2.0));
+        let proj = Surface::project(&plane, Point3::new(3.0, 4.0, 5.0)).unwrap();
+        assert!((proj.distance - 7.0).abs() < 1e-12);
+        assert_point_eq(proj.point, Point3::new(3.0, 4.0, -2.0));
+
+        let cylinder =
+            CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        let thick = OffsetSurface::new(cylinder, 0.25).unwrap();
+        assert!(thick.is_u_closed());
+        let k = thick.curvature_at(0.3, 0.0).unwrap();
+        assert!((k.min_curvature + 0.8).abs() < 1e-5);
+        assert!(OffsetSurface::new(cylinder, -1.5).is_err());
+    }
+
+    #[test]
+    fn test_global_self_intersection() {
+        // 脚の間隔が 1 の Ω 形の曲線を押し出した面。内側へ 0.7 ずらすと、
+        // 曲率半径は十分大きいが脚同士が交差する
+        let points: Vec<Point3> = [
+            (0.5, -3.0),
+            (0.5, -1.0),
+            (2.0, 1.0),
+            (0.0, 3.0),
+            (-2.0, 1.0),
+            (-0.5, -1.0),
+            (-0.5, -3.0),
+        ]
+        .iter()
+        .map(|&(x, y)| Point3::new(x, y, 0.0))
+        .collect();
+        let curve = BSplineCurve::interpolate(&points, EndConditions::Natural).unwrap();
+        let surface = ExtrudedSurface::new(curve, Dir::Z);
+        // 曲線は反時計回りなので、Su × Sv は外側（右手側）を向く
+        let n = surface.normal_at(0.0, 0.0).unwrap();
+        assert!(n.x() > 0.9);
+        assert!(OffsetSurface::new(surface.clone(), 0.3).is_ok());
+        assert!(OffsetSurface::new(surface.clone(), -0.3).is_ok());
+        let err = OffsetSurface::new(surface, -0.7).unwrap_err();
+        assert!(err.to_string().contains("自己交差"), "{}", err);
+    }
+}
@@ -0,0 +1,164 @@
+//! 形状を規則的に並べた配列（直線状・円周状のパターン）の生成
+//!
+//! 各インスタンスは元の形状と中身を共有し、配置（[`Location`](crate::Location)）だけが異なる。
+//! 最初のインスタンスは元の形状そのもので、結果は全インスタンスを含む複合形状として返す。
+
+use std::f64::consts::TAU;
+
+use crate::precision;
+use crate::{Axis1, Compound, Dir, OcctKrsError, Result, Shape, Transform};
+
+/// 形状を方向 `direction` に間隔 `spacing` で `count` 個並べる
+///
+/// 個数が 0 の場合、間隔が有限値でない場合はエラーを返す。
+pub fn linear(shape: &Shape, direction: Dir, count: usize, spacing: f64) -> Result<Compound> {
+    check_count(count)?;
+    if !spacing.is_finite() {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "パターンの間隔が不正です: {}",
+            spacing
+        )));
+    }
+    let step = direction.to_vector() * spacing;
+    Ok(instances(shape, count, |i| {
+        Transform::from_translation(step * i as f64)
+    }))
+}
+
+/// 形状を軸 `axis` のまわりに角度 `total_angle`（ラジアン）の範囲で `count` 個並べる
+///
+/// 角度が一周（2π）なら一周を `count` 等分し、最後のインスタンスが最初と重ならないようにする。
+/// それ以外は最初と最後のインスタンスが `total_angle` だけ離れるように等分する。
+/// 個数が 0 の場合、角度が有限値でない場合はエラーを返す。
+pub fn circular(shape: &Shape, axis: &Axis1, count: usize, total_angle: f64) -> Result<Compound> {
+    check_count(count)?;
+    if !total_angle.is_finite() {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "パターンの角度が不正です: {}",
+            total_angle
+        )));
+    }
+    let full = (total_angle.abs() - TAU).abs() <= precision::angular();
+    let step = if full || count == 1 {
+        total_angle / count as f64
+    } else {
+        total_angle / (count - 1) as f64
+    };
+    Ok(instances(shape, count, |i| {
+        Transform::rotation(axis, step * i as f64)
+    }))
+}
+
+/// 個数が 1 以上であることを確かめる
+fn check_count(count: usize) -> Result<()> {
+    if count == 0 {
+        return Err(OcctKrsError::InvalidInput(
+            "パターンの個数は 1 以上が必要です".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `i` 番目のインスタンスを `transform(i)` で移した複合形状（0 番目は元の形状）
+fn instances(shape: &Shape, count: usize, transform: impl Fn(usize) -> Transform) -> Compound {
+    let shapes = (0..count)
+        .map(|i| {
+            if i == 0 {
+                shape.clone()
+            } else {
+                shape.moved(&transform(i))
+            }
+        })
+        .collect();
+    Compound::new(shapes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::make_box;
+    use crate::{Axis2, Point3, ShapeKind, Vector3, Vertex};
+    use std::f64::consts::FRAC_PI_2;
+
+    /// 各インスタンスの頂点の重心
+    fn centers(compound: &Compound) -> Vec<Point3> {
+        compound
+            .shapes()
+            .iter()
+            .map(|s| {
+                let points: Vec<Point3> = s
+                    .explore(ShapeKind::Vertex)
+                    .unique()
+                    .filter_map(|v| Vertex::try_from(v).ok())
+                    .map(|v| v.point())
+                    .collect();
+                let sum = points
+                    .iter()
+                    .fold(Vector3::ZERO, |acc, p| acc + p.to_vector());
+                Point3::from(sum / points.len() as f64)
+            })
+            .collect()
+    }
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_linear() {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let block = Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap());
+        let rail = linear(&block, Dir::X, 4, 1.5).unwrap();
+        assert_eq!(rail.shapes().len(), 4);
+        for (i, c) in centers(&rail).into_iter().enumerate() {
+            assert_point_eq(c, Point3::new(0.5 + 1.5 * i as f64, 0.5, 0.5));
+        }
+        // 中身は共有し、配置だけが異なる
+        let shapes = rail.shapes();
+        assert!(shapes[0].is_same(&block));
+        let faces = |s: &Shape| s.faces()[0].clone();
+        assert!(faces(&shapes[3]).is_partner(&faces(&block)));
+        assert!(!shapes[3].is_same(&block));
+        assert_eq!(Shape::from(rail).explore(ShapeKind::Solid).count(), 4);
+    }
+
+    #[test]
+    fn test_circular() {
+        let position = Axis2::new(Point3::new(1.75, -0.25, 0.0), Dir::Z, Dir::X).unwrap();
+        let boss = Shape::from(make_box(position, 0.5, 0.5, 1.0).unwrap());
+        let axis = Axis1 {
+            location: Point3::origin(),
+            direction: Dir::Z,
+        };
+        // 一周なら等分して最初と最後が重ならない（ボルト円）
+        let bolts = circular(&boss, &axis, 6, TAU).unwrap();
+        for (i, c) in centers(&bolts).into_iter().enumerate() {
+            let a = TAU / 6.0 * i as f64;
+            assert_point_eq(c, Point3::new(2.0 * a.cos(), 2.0 * a.sin(), 0.5));
+        }
+        // 一周でなければ両端を含めて等分する
+        let arc = circular(&boss, &axis, 3, FRAC_PI_2).unwrap();
+        let c = centers(&arc);
+        assert_point_eq(
+            c[1],
+            Point3::new(2.0 * 0.5f64.sqrt(), 2.0 * 0.5f64.sqrt(), 0.5),
+        );
+        assert_point_eq(c[2], Point3::new(0.0, 2.0, 0.5));
+        let single = circular(&boss, &axis, 1, FRAC_PI_2).unwrap();
+        assert_eq!(single.shapes().len(), 1);
+    }
+
+    #[test]
+    fn test_pattern_errors() {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let block = Shape::from(make_box(position, 1.0, 1.0, 1.0).unwrap());
+        let axis = Axis1 {
+            location: Point3::origin(),
+            direction: Dir::Z,
+        };
+        assert!(linear(&block, Dir::X, 0, 1.0).is_err());
+        assert!(linear(&block, Dir::X, 2, f64::NAN).is_err());
+        assert!(circular(&block, &axis, 0, TAU).is_err());
+        assert!(circular(&block, &axis, 2, f64::INFINITY).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::bspline_fit::fit_least_squares;
+use crate::projection::refine_surface_projection;
+use crate::{BSplineCurve2, Curve2, Curve3, OcctKrsError, Point3, Result, Surface, Vector2};
+
+/// 最初に標本化する区間数
+const INITIAL_SAMPLES: usize = 16;
+
+/// 標本化する区間数の上限
+const MAX_SAMPLES: usize = 1024;
+
+/// 曲面のパラメータ空間上の曲線を曲面に載せた3次元曲線（OCCT の `Adaptor3d_CurveOnSurface` 相当）
+///
+/// 点は `S(c(t))`。パラメータは pcurve のものをそのまま使う。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveOnSurface<C, S> {
+    pcurve: C,
+    surface: S,
+}
+
+impl<C: Curve2, S: Surface> CurveOnSurface<C, S> {
+    /// pcurve と曲面から生成する
+    pub fn new(pcurve: C, surface: S) -> Self {
+        Self { pcurve, surface }
+    }
+
+    /// パラメータ空間上の曲線を返す
+    pub fn pcurve(&self) -> &C {
+        &self.pcurve
+    }
+
+    /// 曲面を返す
+    pub fn surface(&self) -> &S {
+        &self.surface
+    }
+
+    /// パラメータ `t` における点を返す
+    pub fn point_at(&self, t: f64) -> Point3 {
+        let uv = self.pcurve.point_at(t);
+        self.surface.point_at(uv.x, uv.y)
+    }
+}
+
+/// 3次元曲線を曲面へ射影して得た pcurve
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedCurve {
+    /// 元の曲線と同じパラメータを持つパラメータ空間上の曲線
+    pub pcurve: BSplineCurve2,
+    /// 標本点での、曲面に載せた pcurve と射影点との最大距離
+    pub max_error: f64,
+}
+
+/// 3次元曲線を曲面へ射影し、パラメータ空間上の曲線（pcurve）を求める（OCCT の `GeomProjLib::Curve2d` 相当）
+///
+/// 曲線上の標本点を曲面へ最近点射影し、得られた (u, v) を曲線と同じパラメータで B-スプライン近似する。
+/// 閉じた方向では継ぎ目で値が跳ばないようにパラメータを周期分ずらしてつなぐので、
+/// pcurve は曲面のパラメータ範囲の外に出ることがある。
+/// 曲面に載せた pcurve と射影点との距離が `tolerance` 以下になるまで標本点と制御点を増やし、
+/// 満たせない場合は `ToleranceExceeded` を返す。
+/// 曲線のパラメータ範囲が有限でない場合や、射影が求まらない場合はエラーを返す。
+pub fn project_curve_onto_surface(
+    curve: &(impl Curve3 + ?Sized),
+    surface: &(impl Surface + ?Sized),
+    tolerance: f64,
+) -> Result<ProjectedCurve> {
+    if !(tolerance > 0.0 && tolerance.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "許容誤差が不正です: {}",
+            tolerance
+        )));
+    }
+    let (a, b) = (curve.first_parameter(), curve.last_parameter());
+    if !(a.is_finite() && b.is_finite() && a < b) {
+        return Err(OcctKrsError::InvalidInput(
+            "射影する曲線のパラメータ範囲は有限である必要があります".to_string(),
+        ));
+    }
+    let mut samples = INITIAL_SAMPLES;
+    loop {
+        // 偶数番目の標本で近似し、奇数番目を含めたすべての標本で誤差を測る
+        let params: Vec<f64> = (0..=2 * samples)
+            .map(|i| a + (b - a) * i as f64 / (2 * samples) as f64)
+            .collect();
+        let uv = project_samples(curve, surface, &params)?;
+        let targets: Vec<Point3> = uv.iter().map(|q| surface.point_at(q.x, q.y)).collect();
+        let fit_points: Vec<Point3> = uv
+            .iter()
+            .step_by(2)
+            .map(|q| Point3::new(q.x, q.y, 0.0))
+            .collect();
+        let fit_params: Vec<f64> = params.iter().step_by(2).copied().collect();
+        let degree = 3.min(samples);
+        let mut count = degree + 1;
+        let mut max_error;
+        loop {
+            let pcurve = BSplineCurve2::from_planar(fit_least_squares(
+                &fit_points,
+                &fit_params,
+                degree,
+                count,
+            )?);
+            max_error = params
+                .iter()
+                .zip(&targets)
+                .map(|(&t, q)| {
+                    let p = pcurve.point_at(t);
+                    surface.point_at(p.x, p.y).distance(*q)
+                })
+                .fold(0.0, f64::max);
+            if max_error <= tolerance {
+                return Ok(ProjectedCurve { pcurve, max_error });
+            }
+            if count == fit_points.len() {
+                break;
+            }
+            count = (count + count.div_ceil(2)).min(fit_points.len());
+        }
+        if samples >= MAX_SAMPLES {
+            return Err(OcctKrsError::ToleranceExceeded {
+                tolerance,
+                deviation: max_error,
+            });
+        }
+        samples *= 2;
+    }
+}
+
+/// 曲線上の各パラメータの点を曲面へ射影し、継ぎ目で跳ばないようにつないだ (u, v) 列を返す
+fn project_samples(
+    curve: &(impl Curve3 + ?Sized),
+    surface: &(impl Surface + ?Sized),
+    params: &[f64],
+) -> Result<Vec<Vector2>> {
+    let first = surface.project(curve.point_at(params[0])).ok_or_else(|| {
+        OcctKrsError::DegenerateGeometry("曲線の始点を曲面へ射影できません".to_string())
+    })?;
+    let period = |closed: bool, (lo, hi): (f64, f64)| if closed { Some(hi - lo) } else { None };
+    let periods = [
+        period(surface.is_u_closed(), surface.u_range()),
+        period(surface.is_v_closed(), surface.v_range()),
+    ];
+    let unwrap = |x: f64, prev: f64, period: Option<f64>| match period {
+        Some(t) => x + t * ((prev - x) / t).round(),
+        None => x,
+    };
+    let mut result = vec![Vector2::new(first.u, first.v)];
+    for &t in &params[1..] {
+        let prev = *result.last().unwrap();
+        let (u, v) = refine_surface_projection(surface, curve.point_at(t), prev.x, prev.y);
+        result.push(Vector2::new(
+            unwrap(u, prev.x, periods[0]),
+            unwrap(v, prev.y, periods[1]),
+        ));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Arc, Axis2, Axis3, Circle, CylindricalSurface, Dir, Handedness, Helix, Plane, Segment,
+        SphericalSurface,
+    };
+    use std::f64::consts::{PI, TAU};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn cylinder() -> CylindricalSurface {
+        CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 3.0).unwrap()
+    }
+
+    #[test]
+    fn test_circle_on_cylinder() {
+        let circle =
+            Circle::new(Axis2::from_normal(Point3::new(0.0, 0.0, 2.0), Dir::Z), 3.0).unwrap();
+        let result = project_curve_onto_surface(&circle, &cylinder(), 1e-7).unwrap();
+        assert!(result.max_error <= 1e-7);
+        // u は曲線のパラメータ（角度）と一致し、v は高さで一定
+        for i in 0..=10 {
+            let t = TAU * i as f64 / 10.0;
+            let uv = result.pcurve.point_at(t);
+            assert!((uv.x - t).abs() < 1e-6, "{} != {}", uv.x, t);
+            assert!((uv.y - 2.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_helix_unwraps_across_turns() {
+        let helix = Helix::new(
+            Axis2::from_normal(Point3::ORIGIN, Dir::Z),
+            3.0,
+            1.0,
+            2.5,
+            Handedness::Right,
+        )
+        .unwrap();
+        let result = project_curve_onto_surface(&helix, &cylinder(), 1e-6).unwrap();
+        let end = result.pcurve.point_at(helix.last_parameter());
+        assert!((end.x - 2.5 * TAU).abs() < 1e-5);
+        assert!((end.y - 2.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_arc_across_seam_is_continuous() {
+        let circle = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Z), 3.0).unwrap();
+        let arc = Arc::new(circle, -1.0, 1.0).unwrap();
+        let result = project_curve_onto_surface(&arc, &cylinder(), 1e-7).unwrap();
+        let (s, e) = (result.pcurve.point_at(-1.0), result.pcurve.point_at(1.0));
+        assert!((e.x - s.x - 2.0).abs() < 1e-6);
+        let on_surface = CurveOnSurface::new(result.pcurve, cylinder());
+        assert!(
+            on_surface
+                .point_at(0.0)
+                .distance(Point3::new(3.0, 0.0, 0.0))
+                < 1e-7
+        );
+    }
+
+    #[test]
+    fn test_segment_above_plane() {
+        // 平面から離れた曲線は射影した像の pcurve になる
+        let seg = Segment::new(Point3::new(1.0, 2.0, 5.0), Point3::new(4.0, -2.0, 1.0));
+        let result = project_curve_onto_surface(&seg, &Plane::xy(), 1e-9).unwrap();
+        assert!(result.max_error <= 1e-9);
+        assert_eq!(result.pcurve.first_parameter(), 0.0);
+        assert_eq!(result.pcurve.last_parameter(), 1.0);
+        let on_surface = CurveOnSurface::new(result.pcurve, Plane::xy());
+        assert_point_eq(on_surface.point_at(0.0), Point3::new(1.0, 2.0, 0.0));
+        assert_point_eq(on_surface.point_at(0.5), Point3::new(2.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_curve_on_surface_derivatives() {
+        let sphere =
+            SphericalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 2.0).unwrap();
+        let pcurve = BSplineCurve2::from_flat_knots(
+            2,
+            vec![
+                Vector2::new(0.1, -0.5),
+                Vector2::new(1.0, 0.8),
+                Vector2::new(PI, 0.2),
+            ],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        )
+        .unwrap();
+        let c = CurveOnSurface::new(pcurve, sphere);
+        let (t, h) = (0.4, 1e-5);
+        let d1 = (Curve3::point_at(&c, t + h) - Curve3::point_at(&c, t - h)) / (2.0 * h);
+        assert!((c.derivative_at(t) - d1).length() < 1e-8);
+        let d2 = (c.derivative_at(t + h) - c.derivative_at(t - h)) / (2.0 * h);
+        assert!((c.second_derivative_at(t) - d2).length() < 1e-6);
+        assert_eq!(c.last_parameter(), 1.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let seg = Segment::new(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0));
+        assert!(project_curve_onto_surface(&seg, &Plane::xy(), 0.0).is_err());
+        let line = crate::Line::new(Point3::ORIGIN, Dir::X);
+        assert!(project_curve_onto_surface(&line, &Plane::xy(), 1e-6).is_err());
+    }
+}
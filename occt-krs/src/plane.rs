@@ -0,0 +1,246 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Axis3, Dir, Line, OcctKrsError, Point3, Quaternion, Result, Segment, Transform, Vector3,
+};
+
+/// 平面（OCCT の `gp_Pln` 相当）
+///
+/// 局所座標系 `position` の原点を通り、主方向を法線とする。
+/// 平面上の点は局所座標系の X・Y 方向を用いて `(u, v)` で表される。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Plane {
+    position: Axis3,
+}
+
+impl Plane {
+    /// 平面上の1点と法線から平面を生成する
+    pub fn new(origin: Point3, normal: Dir) -> Self {
+        Self::from_axis(Axis3::from_normal(origin, normal))
+    }
+
+    /// 局所座標系から平面を生成する
+    pub fn from_axis(position: Axis3) -> Self {
+        Self { position }
+    }
+
+    /// 方程式 `ax + by + cz + d = 0` の係数から平面を生成する
+    /// 法線 `(a, b, c)` がゼロの場合はエラーを返す
+    pub fn from_coefficients(a: f64, b: f64, c: f64, d: f64) -> Result<Self> {
+        let n = Vector3::new(a, b, c);
+        let normal = Dir::from_vector(n)?;
+        let origin = Point3::from(n * (-d / n.dot(n)));
+        Ok(Self::new(origin, normal))
+    }
+
+    /// 3点を通る平面を生成する（法線は `(b - a) × (c - a)` の向き）
+    /// 3点が同一直線上にある場合はエラーを返す
+    pub fn from_points(a: Point3, b: Point3, c: Point3) -> Result<Self> {
+        let normal = Dir::from_vector((b - a).cross(c - a)).map_err(|_| {
+            OcctKrsError::DegenerateGeometry("3点が同一直線上にあります".to_string())
+        })?;
+        Ok(Self::new(a, normal))
+    }
+
+    /// XY平面を返す
+    pub fn xy() -> Self {
+        Self::new(Point3::origin(), Dir::Z)
+    }
+
+    /// 局所座標系を返す
+    pub fn position(&self) -> Axis3 {
+        self.position
+    }
+
+    /// 平面の原点を返す
+    pub fn origin(&self) -> Point3 {
+        self.position.location()
+    }
+
+    /// 法線を返す
+    pub fn normal(&self) -> Dir {
+        self.position.direction()
+    }
+
+    /// 方程式 `ax + by + cz + d = 0` の係数 `[a, b, c, d]` を返す（`(a, b, c)` は単位ベクトル）
+    pub fn coefficients(&self) -> [f64; 4] {
+        let n = self.normal().to_vector();
+        [n.x, n.y, n.z, -n.dot(self.origin().to_vector())]
+    }
+
+    /// 点までの符号付き距離を計算する（法線の向きの側が正）
+    pub fn signed_distance(&self, p: Point3) -> f64 {
+        (p - self.origin()).dot(self.normal().to_vector())
+    }
+
+    /// 点までの距離を計算する
+    pub fn distance(&self, p: Point3) -> f64 {
+        self.signed_distance(p).abs()
+    }
+
+    /// 点を平面に正射影する
+    pub fn project(&self, p: Point3) -> Point3 {
+        p - self.normal().to_vector() * self.signed_distance(p)
+    }
+
+    /// 点が平面上（距離が `tol` 以下）にあるか判定する
+    pub fn contains(&self, p: Point3, tol: f64) -> bool {
+        self.distance(p) <= tol
+    }
+
+    /// 局所座標 `(u, v)` の点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.position.point_at(u, v, 0.0)
+    }
+
+    /// 点を平面に射影したときの局所座標 `(u, v)` を返す
+    pub fn parameters_of(&self, p: Point3) -> (f64, f64) {
+        let d = p - self.origin();
+        (
+            d.dot(self.position.x_direction().to_vector()),
+            d.dot(self.position.y_direction().to_vector()),
+        )
+    }
+
+    /// 直線との交点を返す
+    /// 直線が平面と平行な場合は `None` を返す
+    pub fn intersect_line(&self, line: &Line) -> Option<Point3> {
+        let n = self.normal().to_vector();
+        let denom = line.dir.to_vector().dot(n);
+        if denom.abs() <= crate::precision::angular() {
+            return None;
+        }
+        Some(line.point_at(-self.signed_distance(line.origin) / denom))
+    }
+
+    /// 線分との交点を返す
+    /// 交わらない、または線分が平面と平行な場合は `None` を返す
+    pub fn intersect_segment(&self, segment: &Segment) -> Option<Point3> {
+        let da = self.signed_distance(segment.start);
+        let db = self.signed_distance(segment.end);
+        if da == db || da * db > 0.0 {
+            return None;
+        }
+        Some(segment.point_at(da / (da - db)))
+    }
+
+    /// この平面に関する鏡映変換を返す
+    ///
+    /// 法線まわりの半回転と原点に関する点対称の合成として表す。
+    pub fn mirror_transform(&self) -> Transform {
+        let n = self.normal().to_vector();
+        Transform::new(
+            Quaternion::from_axis_angle(n, std::f64::consts::PI),
+            n * (2.0 * n.dot(self.origin().to_vector())),
+            -1.0,
+        )
+    }
+
+    /// 変換を適用した平面を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self::from_axis(self.position.transformed(t))
+    }
+
+    /// u の向きを反転した平面を返す
+    ///
+    /// 局所座標系の X 方向を反転するので、元の `(u, v)` の点は `(-u, v)` の点になり、右手系と左手系が入れ替わる。
+    pub fn u_reversed(&self) -> Self {
+        let mut position = self.position;
+        position.x_reverse();
+        Self::from_axis(position)
+    }
+
+    /// 法線を反転した平面を返す
+    pub fn reversed(&self) -> Self {
+        let mut position = self.position;
+        position.z_reverse();
+        Self::from_axis(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_distance_and_projection() {
+        let pl = Plane::new(Point3::new(0.0, 0.0, 2.0), Dir::Z);
+        let p = Point3::new(1.0, -3.0, 5.0);
+        assert_eq!(pl.signed_distance(p), 3.0);
+        assert_eq!(pl.signed_distance(Point3::origin()), -2.0);
+        assert_eq!(pl.reversed().signed_distance(p), -3.0);
+        assert_point_eq(pl.project(p), Point3::new(1.0, -3.0, 2.0));
+        assert!(pl.contains(pl.project(p), 1e-12));
+        let (u, v) = pl.parameters_of(p);
+        assert_point_eq(pl.point_at(u, v), pl.project(p));
+    }
+
+    #[test]
+    fn test_coefficients_roundtrip() {
+        let pl = Plane::from_coefficients(0.0, 3.0, 4.0, -10.0).unwrap();
+        // 原点からの距離は 10 / 5 = 2
+        assert!((pl.signed_distance(Point3::origin()) + 2.0).abs() < 1e-12);
+        let [a, b, c, d] = pl.coefficients();
+        assert!((a - 0.0).abs() < 1e-12 && (b - 0.6).abs() < 1e-12 && (c - 0.8).abs() < 1e-12);
+        assert!((d + 2.0).abs() < 1e-12);
+        assert!(Plane::from_coefficients(0.0, 0.0, 0.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_from_points() {
+        let pl = Plane::from_points(
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+        )
+        .unwrap();
+        let n = pl.normal().to_vector();
+        assert!((n - Vector3::new(1.0, 1.0, 1.0).normalized()).length() < 1e-12);
+        let err = Plane::from_points(
+            Point3::origin(),
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(2.0, 2.0, 2.0),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OcctKrsError::DegenerateGeometry(_)));
+    }
+
+    #[test]
+    fn test_intersections() {
+        let pl = Plane::xy();
+        let line =
+            Line::from_points(Point3::new(1.0, 1.0, 2.0), Point3::new(2.0, 1.0, 1.0)).unwrap();
+        assert_point_eq(
+            pl.intersect_line(&line).unwrap(),
+            Point3::new(3.0, 1.0, 0.0),
+        );
+        let parallel = Line::new(Point3::new(0.0, 0.0, 1.0), Dir::X);
+        assert!(pl.intersect_line(&parallel).is_none());
+
+        let s = Segment::new(Point3::new(0.0, 0.0, -1.0), Point3::new(0.0, 2.0, 3.0));
+        assert_point_eq(
+            pl.intersect_segment(&s).unwrap(),
+            Point3::new(0.0, 0.5, 0.0),
+        );
+        let above = Segment::new(Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 0.0, 3.0));
+        assert!(pl.intersect_segment(&above).is_none());
+    }
+
+    #[test]
+    fn test_mirror() {
+        let pl = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::new(0.0, 1.0, 1.0).unwrap());
+        let m = pl.mirror_transform();
+        let p = Point3::new(0.3, 2.0, -1.0);
+        let q = m * p;
+        // 鏡映点は平面に関して反対側の等距離にある
+        assert!((pl.signed_distance(q) + pl.signed_distance(p)).abs() < 1e-12);
+        assert_point_eq(pl.project(q), pl.project(p));
+        assert_point_eq(m * q, p);
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::{ParseVectorError, Vector3};
+
+/// 3次元空間上の点を表す構造体
+///
+/// 点と変位ベクトルを型で区別するため、`Vector3` とは別の型として扱う。
+/// 点 - 点 = ベクトル、点 ± ベクトル = 点 は許容するが、点 + 点 は定義しない。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Point3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point3 {
+    /// 原点
+    pub const ORIGIN: Point3 = Point3::new(0.0, 0.0, 0.0);
+
+    /// 新しい点を生成する
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// 原点を返す
+    pub const fn origin() -> Self {
+        Self::ORIGIN
+    }
+
+    /// 原点からこの点への位置ベクトルを返す
+    pub fn to_vector(self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    /// 2点間の距離を計算する
+    pub fn distance(self, other: Point3) -> f64 {
+        (self - other).length()
+    }
+
+    /// 2点間の距離の2乗を計算する
+    pub fn distance_squared(self, other: Point3) -> f64 {
+        let d = self - other;
+        d.dot(d)
+    }
+
+    /// 成分ごとの最小値からなる点を返す（境界ボックスの計算に利用する）
+    pub fn component_min(self, other: Point3) -> Point3 {
+        Point3::from(self.to_vector().component_min(other.to_vector()))
+    }
+
+    /// 成分ごとの最大値からなる点を返す（境界ボックスの計算に利用する）
+    pub fn component_max(self, other: Point3) -> Point3 {
+        Point3::from(self.to_vector().component_max(other.to_vector()))
+    }
+
+    /// `a` と `b` を線形補間する（t = 0 で a、t = 1 で b）
+    pub fn lerp(a: Point3, b: Point3, t: f64) -> Point3 {
+        a + (b - a) * t
+    }
+
+    /// 2点の中点を返す
+    pub fn midpoint(self, other: Point3) -> Point3 {
+        self + (other - self) * 0.5
+    }
+}
+
+/// 位置ベクトルから点への変換
+impl From<Vector3> for Point3 {
+    fn from(v: Vector3) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+/// 点から位置ベクトルへの変換
+impl From<Point3> for Vector3 {
+    fn from(p: Point3) -> Self {
+        p.to_vector()
+    }
+}
+
+/// `(x, y, z)` の形式で表示する
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_vector().fmt(f)
+    }
+}
+
+/// 文字列からの読み取り（`Vector3` と同じ形式を受け付ける）
+impl FromStr for Point3 {
+    type Err = ParseVectorError;
+    fn from_str(s: &str) -> Result<Self, ParseVectorError> {
+        s.parse::<Vector3>().map(Point3::from)
+    }
+}
+
+/// 点 + ベクトル = 点
+impl Add<Vector3> for Point3 {
+    type Output = Point3;
+    fn add(self, v: Vector3) -> Point3 {
+        Point3::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+
+/// 点 - ベクトル = 点
+impl Sub<Vector3> for Point3 {
+    type Output = Point3;
+    fn sub(self, v: Vector3) -> Point3 {
+        Point3::new(self.x - v.x, self.y - v.y, self.z - v.z)
+    }
+}
+
+/// 点 - 点 = ベクトル
+impl Sub for Point3 {
+    type Output = Vector3;
+    fn sub(self, other: Point3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// 点 += ベクトル
+impl AddAssign<Vector3> for Point3 {
+    fn add_assign(&mut self, v: Vector3) {
+        *self = *self + v;
+    }
+}
+
+/// 点 -= ベクトル
+impl SubAssign<Vector3> for Point3 {
+    fn sub_assign(&mut self, v: Vector3) {
+        *self = *self - v;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = Point3::new(1.0, 2.0, 3.0);
+        let q = Point3::new(4.0, 6.0, 3.0);
+
+        // 点 - 点 はベクトルになる
+        let d: Vector3 = q - p;
+        assert_eq!(d, Vector3::new(3.0, 4.0, 0.0));
+
+        // 点 + ベクトル は点になる
+        let r: Point3 = p + d;
+        assert_eq!(r, q);
+        assert_eq!(q - d, p);
+    }
+
+    #[test]
+    fn test_distance_and_midpoint() {
+        let p = Point3::origin();
+        let q = Point3::new(3.0, 4.0, 0.0);
+        assert!((p.distance(q) - 5.0).abs() < 1e-10);
+        assert!((p.distance_squared(q) - 25.0).abs() < 1e-10);
+        assert_eq!(p.midpoint(q), Point3::new(1.5, 2.0, 0.0));
+        assert_eq!(Point3::lerp(p, q, 0.5), p.midpoint(q));
+        assert_eq!(
+            Point3::new(1.0, 5.0, -1.0).component_min(Point3::new(2.0, 4.0, 0.0)),
+            Point3::new(1.0, 4.0, -1.0)
+        );
+        assert_eq!(
+            Point3::new(1.0, 5.0, -1.0).component_max(Point3::new(2.0, 4.0, 0.0)),
+            Point3::new(2.0, 5.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let v = Vector3::new(1.0, -2.0, 0.5);
+        let p = Point3::from(v);
+        assert_eq!(Vector3::from(p), v);
+        assert_eq!(p.to_vector(), v);
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut p = Point3::new(1.0, 1.0, 1.0);
+        p += Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(p, Point3::new(2.0, 3.0, 4.0));
+        p -= Vector3::new(2.0, 3.0, 4.0);
+        assert_eq!(p, Point3::origin());
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        let p = Point3::new(1.5, 0.0, -2.0);
+        assert_eq!(p.to_string(), "(1.5, 0, -2)");
+        assert_eq!("(1.5, 0, -2)".parse::<Point3>().unwrap(), p);
+    }
+}
@@ -0,0 +1,419 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{BSplineCurve, BoundingBox, OcctKrsError, Point3, Result, Segment, Transform};
+
+/// 3次元の折れ線（開いた折れ線、または閉じた多角形）
+///
+/// 閉じた折れ線では終点から始点へ戻る辺を暗黙に含み、始点を末尾に重ねて保持しない。
+/// パラメータ `t` は整数部が辺の番号、小数部が辺上の位置に対応し、範囲は `[0, 辺の数]`。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline3 {
+    points: Vec<Point3>,
+    closed: bool,
+}
+
+impl Polyline3 {
+    /// 頂点列から折れ線を生成する
+    /// 開いた折れ線は2点以上、閉じた折れ線は3点以上が必要
+    pub fn new(points: Vec<Point3>, closed: bool) -> Result<Self> {
+        let required = required_points(closed);
+        if points.len() < required {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "折れ線には {} 個以上の頂点が必要です（{} 個）",
+                required,
+                points.len()
+            )));
+        }
+        Ok(Self { points, closed })
+    }
+
+    /// 頂点列を返す
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// 閉じた折れ線なら `true` を返す
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// 辺の数を返す
+    pub fn segment_count(&self) -> usize {
+        if self.closed {
+            self.points.len()
+        } else {
+            self.points.len() - 1
+        }
+    }
+
+    /// `i` 番目の辺を返す
+    pub fn segment(&self, i: usize) -> Segment {
+        Segment::new(self.points[i], self.points[(i + 1) % self.points.len()])
+    }
+
+    /// 辺を順に返す
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        (0..self.segment_count()).map(|i| self.segment(i))
+    }
+
+    /// 全長を計算する
+    pub fn length(&self) -> f64 {
+        self.segments().map(|s| s.length()).sum()
+    }
+
+    /// パラメータ `t` における点を返す（範囲外は端の辺を延長する）
+    pub fn point_at(&self, t: f64) -> Point3 {
+        let (i, local) = self.locate(t);
+        self.segment(i).point_at(local)
+    }
+
+    /// パラメータを辺の番号と辺上のパラメータに分解する
+    pub(crate) fn locate(&self, t: f64) -> (usize, f64) {
+        let last = self.segment_count() - 1;
+        let i = if t.is_nan() {
+            0
+        } else {
+            (t.floor().max(0.0) as usize).min(last)
+        };
+        (i, t - i as f64)
+    }
+
+    /// パラメータ 0 から `t` までの長さを返す
+    pub(crate) fn length_to(&self, t: f64) -> f64 {
+        let (i, local) = self.locate(t);
+        self.segments().take(i).map(|s| s.length()).sum::<f64>() + local * self.segment(i).length()
+    }
+
+    /// 始点からの長さが `s` となる点を返す
+    /// `s` が 0 から全長の範囲外の場合は `None` を返す
+    pub fn point_at_length(&self, s: f64) -> Option<Point3> {
+        if !(0.0..=self.length()).contains(&s) {
+            return None;
+        }
+        let mut rest = s;
+        for seg in self.segments() {
+            let l = seg.length();
+            if rest <= l {
+                return Some(if l > 0.0 {
+                    seg.point_at(rest / l)
+                } else {
+                    seg.start
+                });
+            }
+            rest -= l;
+        }
+        Some(self.segment(self.segment_count() - 1).end)
+    }
+
+    /// 長さに沿って等間隔に `count` 個の頂点を取り直した折れ線を返す
+    ///
+    /// 開いた折れ線は両端点を含み、閉じた折れ線は始点から1周を `count` 等分する。
+    /// 頂点数が足りない、または全長がゼロの場合はエラーを返す。
+    pub fn resample(&self, count: usize) -> Result<Polyline3> {
+        let total = self.length();
+        if total <= 0.0 {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "長さがゼロの折れ線は再分割できません".to_string(),
+            ));
+        }
+        let divisions = if self.closed { count } else { count.max(1) - 1 };
+        if divisions == 0 {
+            return Polyline3::new(Vec::new(), self.closed);
+        }
+        let step = total / divisions as f64;
+        let mut points = Vec::with_capacity(count);
+        let mut target = 0.0;
+        let mut walked = 0.0;
+        for seg in self.segments() {
+            let l = seg.length();
+            while points.len() < count && target <= walked + l {
+                points.push(if l > 0.0 {
+                    seg.point_at((target - walked) / l)
+                } else {
+                    seg.start
+                });
+                target = step * points.len() as f64;
+            }
+            walked += l;
+        }
+        // 丸め誤差で最後の点が取りこぼされた場合は終点で補う
+        if points.len() < count {
+            points.push(self.segment(self.segment_count() - 1).end);
+        }
+        Polyline3::new(points, self.closed)
+    }
+
+    /// Douglas–Peucker 法で、元の折れ線からのずれが `tolerance` 以内になるよう頂点を間引く
+    ///
+    /// 開いた折れ線の両端点は必ず残る。閉じた折れ線は始点と、始点から最も遠い頂点を残す。
+    pub fn simplify(&self, tolerance: f64) -> Polyline3 {
+        let n = self.points.len();
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        if self.closed {
+            let far = farthest_from(&self.points, self.points[0]);
+            keep[far] = true;
+            douglas_peucker(&self.points, 0, far, tolerance, &mut keep);
+            // 終点から始点へ戻る部分は、始点を末尾に重ねた列として扱う
+            let mut tail: Vec<Point3> = self.points[far..].to_vec();
+            tail.push(self.points[0]);
+            let mut tail_keep = vec![false; tail.len()];
+            douglas_peucker(&tail, 0, tail.len() - 1, tolerance, &mut tail_keep);
+            for (i, k) in tail_keep.iter().enumerate().take(tail.len() - 1) {
+                keep[far + i] |= *k;
+            }
+        } else {
+            keep[n - 1] = true;
+            douglas_peucker(&self.points, 0, n - 1, tolerance, &mut keep);
+        }
+        let mut points: Vec<Point3> = self
+            .points
+            .iter()
+            .zip(&keep)
+            .filter(|(_, k)| **k)
+            .map(|(p, _)| *p)
+            .collect();
+        if self.closed && points.len() < 3 {
+            // 多角形として残すため、始点と最遠点を結ぶ線分から最も遠い頂点を加える
+            let chord = Segment::new(points[0], points[points.len() - 1]);
+            let extra = (0..n)
+                .max_by(|&i, &j| {
+                    chord
+                        .distance(self.points[i])
+                        .total_cmp(&chord.distance(self.points[j]))
+                })
+                .unwrap();
+            points = (0..n)
+                .filter(|&i| keep[i] || i == extra)
+                .map(|i| self.points[i])
+                .collect();
+        }
+        if points.len() < required_points(self.closed) {
+            // すべての頂点が一直線上にある等、多角形として残せない場合は元の折れ線を返す
+            return self.clone();
+        }
+        Self {
+            points,
+            closed: self.closed,
+        }
+    }
+
+    /// 頂点を囲む境界ボックスを返す
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_points(self.points.iter().copied()).unwrap()
+    }
+
+    /// 向きを反転した折れ線を返す（閉じた折れ線は始点を保つ）
+    pub fn reversed(&self) -> Self {
+        let mut points = self.points.clone();
+        points.reverse();
+        if self.closed {
+            points.rotate_right(1);
+        }
+        Self {
+            points,
+            closed: self.closed,
+        }
+    }
+
+    /// 変換を適用した折れ線を返す
+    pub fn transformed(&self, t: &Transform) -> Self {
+        Self {
+            points: self.points.iter().map(|p| t.transform_point(*p)).collect(),
+            closed: self.closed,
+        }
+    }
+
+    /// 1次の B-スプライン曲線に変換する（パラメータは折れ線と同じく辺の番号に対応する）
+    pub fn to_nurbs(&self) -> BSplineCurve {
+        let mut points = self.points.clone();
+        if self.closed {
+            points.push(self.points[0]);
+        }
+        let n = points.len() - 1;
+        let mut knots = vec![0.0];
+        knots.extend((0..=n).map(|i| i as f64));
+        knots.push(n as f64);
+        BSplineCurve::from_flat_knots(1, points, knots).unwrap()
+    }
+}
+
+/// 折れ線に必要な頂点の数を返す
+fn required_points(closed: bool) -> usize {
+    if closed {
+        3
+    } else {
+        2
+    }
+}
+
+/// `points` の中で `p` から最も遠い頂点の番号を返す
+fn farthest_from(points: &[Point3], p: Point3) -> usize {
+    (0..points.len())
+        .max_by(|&i, &j| {
+            points[i]
+                .distance_squared(p)
+                .total_cmp(&points[j].distance_squared(p))
+        })
+        .unwrap()
+}
+
+/// `first` と `last` の間で残すべき頂点に印を付ける
+fn douglas_peucker(
+    points: &[Point3],
+    first: usize,
+    last: usize,
+    tolerance: f64,
+    keep: &mut [bool],
+) {
+    if last <= first + 1 {
+        return;
+    }
+    let chord = Segment::new(points[first], points[last]);
+    let (index, deviation) = (first + 1..last)
+        .map(|i| (i, chord.distance(points[i])))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    if deviation > tolerance {
+        keep[index] = true;
+        douglas_peucker(points, first, index, tolerance, keep);
+        douglas_peucker(points, index, last, tolerance, keep);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Curve3, Vector3};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn square(closed: bool) -> Polyline3 {
+        Polyline3::new(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(2.0, 0.0, 0.0),
+                Point3::new(2.0, 2.0, 0.0),
+                Point3::new(0.0, 2.0, 0.0),
+            ],
+            closed,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_requires_enough_points() {
+        assert!(Polyline3::new(vec![Point3::ORIGIN], false).is_err());
+        assert!(Polyline3::new(vec![Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)], true).is_err());
+        assert!(Polyline3::new(vec![Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)], false).is_ok());
+    }
+
+    #[test]
+    fn test_length_and_evaluation() {
+        let open = square(false);
+        let closed = square(true);
+        assert_eq!(open.segment_count(), 3);
+        assert_eq!(closed.segment_count(), 4);
+        assert!((open.length() - 6.0).abs() < 1e-12);
+        assert!((closed.length() - 8.0).abs() < 1e-12);
+        assert_point_eq(open.point_at(1.5), Point3::new(2.0, 1.0, 0.0));
+        assert_point_eq(closed.point_at(3.5), Point3::new(0.0, 1.0, 0.0));
+        assert_point_eq(
+            closed.point_at_length(7.0).unwrap(),
+            Point3::new(0.0, 1.0, 0.0),
+        );
+        assert!(open.point_at_length(6.5).is_none());
+
+        assert!(Curve3::is_closed(&closed));
+        assert!(!Curve3::is_closed(&open));
+        assert!((closed.arc_length() - 8.0).abs() < 1e-12);
+        assert!((closed.arc_length_between(0.5, 2.5) - 4.0).abs() < 1e-12);
+        assert_eq!(closed.derivative_at(3.2), Vector3::new(0.0, -2.0, 0.0));
+        let t = closed.parameter_at_length(5.0).unwrap();
+        assert_point_eq(closed.point_at(t), Point3::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_resample() {
+        let open = square(false).resample(7).unwrap();
+        assert_eq!(open.points().len(), 7);
+        assert_point_eq(open.points()[0], Point3::ORIGIN);
+        assert_point_eq(open.points()[3], Point3::new(2.0, 1.0, 0.0));
+        assert_point_eq(open.points()[6], Point3::new(0.0, 2.0, 0.0));
+
+        let closed = square(true).resample(8).unwrap();
+        assert!(closed.is_closed());
+        assert_eq!(closed.points().len(), 8);
+        for seg in closed.segments() {
+            assert!((seg.length() - 1.0).abs() < 1e-12);
+        }
+        assert!(square(false).resample(1).is_err());
+        let degenerate = Polyline3::new(vec![Point3::ORIGIN; 3], false).unwrap();
+        assert!(degenerate.resample(5).is_err());
+    }
+
+    #[test]
+    fn test_simplify_open() {
+        // ほぼ直線上のノイズを含む点列
+        let points: Vec<Point3> = (0..=20)
+            .map(|k| {
+                let x = k as f64;
+                let noise = if k % 2 == 0 { 0.01 } else { -0.01 };
+                let y = if k <= 10 { noise } else { x - 10.0 + noise };
+                Point3::new(x, y, 0.0)
+            })
+            .collect();
+        let line = Polyline3::new(points.clone(), false).unwrap();
+        let simple = line.simplify(0.05);
+        assert_eq!(simple.points().len(), 3);
+        assert_eq!(simple.points()[0], points[0]);
+        assert_eq!(simple.points()[1], points[10]);
+        assert_eq!(simple.points()[2], points[20]);
+        for p in &points {
+            assert!(simple.segments().any(|s| s.distance(*p) <= 0.05));
+        }
+        // 許容誤差ゼロではノイズの頂点も残る
+        assert_eq!(line.simplify(0.0).points().len(), points.len());
+    }
+
+    #[test]
+    fn test_simplify_closed() {
+        let polygon = square(true).resample(40).unwrap();
+        let simple = polygon.simplify(1e-9);
+        assert!(simple.is_closed());
+        assert_eq!(simple.points().len(), 4);
+        assert!((simple.length() - 8.0).abs() < 1e-9);
+
+        // 大きな許容誤差でも多角形として3頂点以上を保つ
+        let coarse = polygon.simplify(10.0);
+        assert!(coarse.points().len() >= 3);
+    }
+
+    #[test]
+    fn test_reversed_and_bounding_box() {
+        let closed = square(true);
+        let r = closed.reversed();
+        assert_eq!(r.points()[0], closed.points()[0]);
+        assert_eq!(r.points()[1], closed.points()[3]);
+        assert!((r.length() - closed.length()).abs() < 1e-12);
+        let b = closed.bounding_box();
+        assert_eq!(b.min, Point3::ORIGIN);
+        assert_eq!(b.max, Point3::new(2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn test_to_nurbs() {
+        for closed in [false, true] {
+            let p = square(closed);
+            let c = p.to_nurbs();
+            assert_eq!(c.last_parameter(), p.segment_count() as f64);
+            for t in [0.0, 0.5, 1.25, 2.9, p.segment_count() as f64] {
+                assert_point_eq(c.point_at(t), p.point_at(t));
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! 許容誤差（トレランス）に関する定数と比較関数
+//!
+//! OCCT の `Precision` パッケージに倣い、距離・角度・パラメータの標準的な許容誤差を提供する。
+//! 異なる許容誤差を使いたい場合は [`Tolerances`] を生成して持ち回す。
+
+use std::f64::consts::PI;
+
+use crate::{Point3, Vector2, Vector3};
+
+/// 2点を同一とみなす距離の許容誤差（OCCT の `Precision::Confusion()` 相当）
+pub fn confusion() -> f64 {
+    1e-7
+}
+
+/// 2つの方向を同一とみなす角度の許容誤差（OCCT の `Precision::Angular()` 相当）
+pub fn angular() -> f64 {
+    1e-12
+}
+
+/// パラメータ空間での許容誤差（OCCT の `Precision::PConfusion()` 相当）
+pub fn parametric() -> f64 {
+    confusion() * 0.01
+}
+
+/// 許容誤差の組をまとめたコンテキスト
+///
+/// `Default` は標準の許容誤差（[`confusion`], [`angular`], [`parametric`]）になる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerances {
+    pub confusion: f64,
+    pub angular: f64,
+    pub parametric: f64,
+}
+
+impl Tolerances {
+    /// 各許容誤差を指定して生成する
+    pub fn new(confusion: f64, angular: f64, parametric: f64) -> Self {
+        Self {
+            confusion,
+            angular,
+            parametric,
+        }
+    }
+
+    /// 2点が距離の許容誤差内で一致するか判定する
+    pub fn points_equal(&self, a: Point3, b: Point3) -> bool {
+        a.approx_eq(&b, self.confusion)
+    }
+
+    /// 2つのベクトルが距離の許容誤差内で一致するか判定する
+    pub fn vectors_equal(&self, a: Vector3, b: Vector3) -> bool {
+        a.approx_eq(&b, self.confusion)
+    }
+
+    /// 2つの角度が角度の許容誤差内で一致するか判定する
+    pub fn angles_equal(&self, a: f64, b: f64) -> bool {
+        angle_approx_eq(a, b, self.angular)
+    }
+
+    /// 2つのパラメータが許容誤差内で一致するか判定する
+    pub fn parameters_equal(&self, a: f64, b: f64) -> bool {
+        approx_eq(a, b, self.parametric)
+    }
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Self::new(confusion(), angular(), parametric())
+    }
+}
+
+/// 許容誤差付きで比較できる型
+pub trait ApproxEq {
+    /// 差（距離）が `tol` 以下であれば `true` を返す
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        (self - other).abs() <= tol
+    }
+}
+
+impl ApproxEq for Vector2 {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        (*self - *other).length() <= tol
+    }
+}
+
+impl ApproxEq for Vector3 {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        (*self - *other).length() <= tol
+    }
+}
+
+impl ApproxEq for Point3 {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.distance(*other) <= tol
+    }
+}
+
+/// 浮動小数点数の比較（許容誤差 tol 以内なら同一とみなす）
+pub fn approx_eq<T: ApproxEq>(a: T, b: T, tol: f64) -> bool {
+    a.approx_eq(&b, tol)
+}
+
+/// 角度の比較（2πの整数倍の差は同一とみなす）
+pub fn angle_approx_eq(a: f64, b: f64, tol: f64) -> bool {
+    let d = (a - b).rem_euclid(2.0 * PI);
+    d <= tol || 2.0 * PI - d <= tol
+}
+
+/// 2つの浮動小数点数の間にある表現可能な値の個数（ULP 距離）を返す
+///
+/// `+0.0` と `-0.0` の距離は 0 とする。どちらかが NaN の場合は `None` を返す。
+pub fn ulps_distance(a: f64, b: f64) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    // 符号付きの表現を単調な整数列に並べ替える
+    let ordered = |x: f64| {
+        let i = x.to_bits() as i64;
+        if i < 0 {
+            i64::MIN - i
+        } else {
+            i
+        }
+    };
+    let d = ordered(a) as i128 - ordered(b) as i128;
+    Some(d.unsigned_abs().min(u64::MAX as u128) as u64)
+}
+
+/// ULP 距離が `max_ulps` 以下であれば同一とみなす
+///
+/// 値の大きさによらず有効桁の一致を判定できる。0 付近では ULP 距離が極端に大きくなるので、
+/// [`relative_eq`] と同じく差の絶対値が `f64::EPSILON` 以下であれば同一とみなす。
+pub fn almost_eq_ulps(a: f64, b: f64, max_ulps: u64) -> bool {
+    (a - b).abs() <= f64::EPSILON || ulps_distance(a, b).is_some_and(|d| d <= max_ulps)
+}
+
+/// 絶対誤差と相対誤差を組み合わせた比較
+///
+/// `|a - b| <= max(abs_tol, rel_tol * max(|a|, |b|))` を満たせば同一とみなす。
+pub fn relative_eq(a: f64, b: f64, abs_tol: f64, rel_tol: f64) -> bool {
+    if a == b {
+        return true;
+    }
+    let diff = (a - b).abs();
+    diff <= abs_tol || diff <= rel_tol * a.abs().max(b.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_tolerances() {
+        assert_eq!(confusion(), 1e-7);
+        assert_eq!(angular(), 1e-12);
+        assert!(parametric() < confusion());
+        let t = Tolerances::default();
+        assert_eq!(t.confusion, confusion());
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        assert!(approx_eq(1.0, 1.0 + 1e-8, confusion()));
+        assert!(!approx_eq(1.0, 1.0 + 1e-6, confusion()));
+        assert!(approx_eq(
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(1.0, 2.0, 3.0 + 1e-9),
+            confusion()
+        ));
+        assert!(!approx_eq(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 1e-3, 0.0),
+            confusion()
+        ));
+    }
+
+    #[test]
+    fn test_angle_approx_eq() {
+        assert!(angle_approx_eq(0.0, 2.0 * PI, 1e-12));
+        assert!(angle_approx_eq(-PI, PI, 1e-12));
+        assert!(angle_approx_eq(1e-13, 2.0 * PI - 1e-13, 1e-12));
+        assert!(!angle_approx_eq(0.0, 0.1, 1e-12));
+    }
+
+    #[test]
+    fn test_ulps() {
+        assert_eq!(ulps_distance(1.0, 1.0), Some(0));
+        assert_eq!(ulps_distance(1.0, 1.0 + f64::EPSILON), Some(1));
+        assert_eq!(ulps_distance(0.0, -0.0), Some(0));
+        // 0 をまたぐ場合も連続して数える
+        assert_eq!(
+            ulps_distance(f64::from_bits(1), -f64::from_bits(1)),
+            Some(2)
+        );
+        assert_eq!(ulps_distance(f64::NAN, 1.0), None);
+        assert_eq!(ulps_distance(f64::MAX, f64::INFINITY), Some(1));
+
+        // 大きさが異なっても同じ ULP 数で判定できる
+        for scale in [1.0_f64, 1e8, 1e300] {
+            let a = scale * 1.234;
+            let b = f64::from_bits(a.to_bits() + 3);
+            assert!(almost_eq_ulps(a, b, 4));
+            assert!(!almost_eq_ulps(a, b, 2));
+        }
+        assert!(!almost_eq_ulps(f64::NAN, f64::NAN, u64::MAX));
+
+        // 0 付近は絶対誤差で判定する
+        assert!(almost_eq_ulps(0.0, 1e-300, 0));
+        assert!(almost_eq_ulps(-1e-17, 1e-17, 0));
+        assert!(!almost_eq_ulps(0.0, 1e-15, 4));
+    }
+
+    #[test]
+    fn test_relative_eq() {
+        assert!(relative_eq(1e10, 1e10 + 1.0, 0.0, 1e-9));
+        assert!(!relative_eq(1e10, 1e10 + 100.0, 0.0, 1e-9));
+        assert!(relative_eq(1e-12, -1e-12, 1e-10, 1e-9));
+        assert!(!relative_eq(1e-12, 2e-12, 0.0, 1e-9));
+        assert!(relative_eq(f64::INFINITY, f64::INFINITY, 0.0, 0.0));
+        assert!(!relative_eq(f64::NAN, f64::NAN, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_custom_context() {
+        let loose = Tolerances::new(1e-3, 1e-3, 1e-3);
+        let p = Point3::new(0.0, 0.0, 0.0);
+        let q = Point3::new(0.0, 0.0, 5e-4);
+        assert!(loose.points_equal(p, q));
+        assert!(!Tolerances::default().points_equal(p, q));
+        assert!(loose.angles_equal(0.0, 5e-4));
+        assert!(loose.parameters_equal(1.0, 1.0005));
+    }
+}
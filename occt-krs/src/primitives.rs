@@ -0,0 +1,408 @@
+//! 基本立体（直方体・円柱・円錐・球・トーラス・くさび）の生成（OCCT の `BRepPrimAPI` 相当）
+//!
+//! いずれも座標系 `position`（[`Axis2`]）の局所座標で形を定め、閉じたシェル1つからなる立体を返す。
+//! フェイスの法線はすべて立体の外側を向き、曲面のフェイスには継ぎ目と極の退化エッジを含めて
+//! pcurve を設定する。
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use crate::precision;
+use crate::{
+    Axis2, Axis3, BSplineCurve2, Circle, ConicalSurface, CylindricalSurface, Edge, Face,
+    GeomSurface, Line, OcctKrsError, Point3, Result, Shell, Solid, SphericalSurface,
+    ToroidalSurface, Vector2, Vertex, Wire,
+};
+
+/// 直方体を生成する（OCCT の `BRepPrimAPI_MakeBox` 相当）
+///
+/// `position` の原点を角とし、X・Y・Z 方向にそれぞれ `dx`・`dy`・`dz` の大きさを持つ。
+/// 大きさが正の有限値でない場合はエラーを返す。
+pub fn make_box(position: Axis2, dx: f64, dy: f64, dz: f64) -> Result<Solid> {
+    check_dimension("直方体の大きさ", dx)?;
+    check_dimension("直方体の大きさ", dy)?;
+    check_dimension("直方体の大きさ", dz)?;
+    make_wedge(position, dx, dy, dz, dx)
+}
+
+/// 直角のくさびを生成する（OCCT の `BRepPrimAPI_MakeWedge(dx, dy, dz, ltx)` 相当）
+///
+/// 底面は `y = 0` の `0 ≤ x ≤ dx`, `0 ≤ z ≤ dz`、上面は `y = dy` の `0 ≤ x ≤ ltx`, `0 ≤ z ≤ dz` で、
+/// `ltx = dx` なら直方体、`ltx = 0` なら上面が稜線に縮んだ三角柱になる。
+/// `dx`・`dy`・`dz` が正の有限値でない場合や、`ltx` が負または有限でない場合はエラーを返す。
+pub fn make_wedge(position: Axis2, dx: f64, dy: f64, dz: f64, ltx: f64) -> Result<Solid> {
+    check_dimension("くさびの大きさ", dx)?;
+    check_dimension("くさびの大きさ", dy)?;
+    check_dimension("くさびの大きさ", dz)?;
+    if !(ltx >= 0.0 && ltx.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "くさびの上面の長さが不正です: {}",
+            ltx
+        )));
+    }
+    // 番号の各ビットが x・y・z の側を表す角
+    let corners: Vec<Point3> = (0..8)
+        .map(|i| {
+            let top = (i >> 1) & 1 == 1;
+            let x = if top { ltx } else { dx } * (i & 1) as f64;
+            let y = dy * ((i >> 1) & 1) as f64;
+            let z = dz * ((i >> 2) & 1) as f64;
+            position.point_at(x, y, z)
+        })
+        .collect();
+    // 外側から見て反時計回りに並べた各面の角
+    let faces = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    polyhedron(&corners, &faces)
+}
+
+/// 円柱を生成する（OCCT の `BRepPrimAPI_MakeCylinder` 相当）
+///
+/// 底面の中心を `position` の原点とし、主方向に高さ `height` だけ伸びる。
+/// 半径や高さが正の有限値でない場合はエラーを返す。
+pub fn make_cylinder(position: Axis2, radius: f64, height: f64) -> Result<Solid> {
+    check_dimension("円柱の半径", radius)?;
+    check_dimension("円柱の高さ", height)?;
+    frustum(position, radius, radius, height)
+}
+
+/// 円錐（円錐台）を生成する（OCCT の `BRepPrimAPI_MakeCone` 相当）
+///
+/// 底面の中心を `position` の原点とし、半径 `bottom_radius` の底面から主方向に高さ `height` の位置の
+/// 半径 `top_radius` の上面まで伸びる。どちらかの半径が 0 なら先端が頂点になる。
+/// 半径が負または有限でない場合、両方の半径が等しい場合、高さが正の有限値でない場合はエラーを返す。
+pub fn make_cone(
+    position: Axis2,
+    bottom_radius: f64,
+    top_radius: f64,
+    height: f64,
+) -> Result<Solid> {
+    for radius in [bottom_radius, top_radius] {
+        if !(radius >= 0.0 && radius.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "円錐の半径が不正です: {}",
+                radius
+            )));
+        }
+    }
+    if bottom_radius == top_radius {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "円錐の底面と上面の半径が等しくなっています: {}",
+            bottom_radius
+        )));
+    }
+    check_dimension("円錐の高さ", height)?;
+    frustum(position, bottom_radius, top_radius, height)
+}
+
+/// 球を生成する（OCCT の `BRepPrimAPI_MakeSphere` 相当）
+///
+/// 中心を `position` の原点とし、継ぎ目は X 方向の側の子午線、極は主方向の両端に置く。
+/// 半径が正の有限値でない場合はエラーを返す。
+pub fn make_sphere(position: Axis2, radius: f64) -> Result<Solid> {
+    check_dimension("球の半径", radius)?;
+    let surface = SphericalSurface::new(Axis3::from(position), radius)?;
+    let south = Vertex::new(position.point_at(0.0, 0.0, -radius));
+    let north = Vertex::new(position.point_at(0.0, 0.0, radius));
+    // 局所座標の XZ 平面上で、角度 t の点が R (cos t · X + sin t · Z) になる円
+    let meridian = Circle::new(
+        Axis2::new(
+            position.location(),
+            position.y_direction().reversed(),
+            position.x_direction(),
+        )?,
+        radius,
+    )?;
+    let seam = Edge::new(
+        meridian,
+        (-FRAC_PI_2, FRAC_PI_2),
+        south.clone(),
+        north.clone(),
+    )?;
+    let bottom = Edge::degenerate(south, (0.0, TAU))?;
+    let top = Edge::degenerate(north, (0.0, TAU))?;
+    let face = lateral_face(surface, &bottom, &seam, &top, (-FRAC_PI_2, FRAC_PI_2))?;
+    Solid::from_shells(vec![Shell::new(vec![face])?])
+}
+
+/// トーラスを生成する（OCCT の `BRepPrimAPI_MakeTorus` 相当）
+///
+/// 中心を `position` の原点とし、主方向のまわりに半径 `major_radius` の円に沿って
+/// 半径 `minor_radius` の円を回した形になる。
+/// 半径が正の有限値でない場合や、小半径が大半径以上で自己交差する場合はエラーを返す。
+pub fn make_torus(position: Axis2, major_radius: f64, minor_radius: f64) -> Result<Solid> {
+    check_dimension("トーラスの大半径", major_radius)?;
+    check_dimension("トーラスの小半径", minor_radius)?;
+    if minor_radius >= major_radius {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "トーラスの小半径 {} が大半径 {} 以上です",
+            minor_radius, major_radius
+        )));
+    }
+    let surface = ToroidalSurface::new(Axis3::from(position), major_radius, minor_radius)?;
+    let vertex = Vertex::new(position.point_at(major_radius + minor_radius, 0.0, 0.0));
+    // u = 0 の断面の円と、v = 0 の外周の円
+    let section = Circle::new(
+        Axis2::new(
+            position.point_at(major_radius, 0.0, 0.0),
+            position.y_direction().reversed(),
+            position.x_direction(),
+        )?,
+        minor_radius,
+    )?;
+    let outer = Circle::new(position, major_radius + minor_radius)?;
+    let seam = Edge::new(section, (0.0, TAU), vertex.clone(), vertex.clone())?;
+    let rim = Edge::new(outer, (0.0, TAU), vertex.clone(), vertex)?;
+    let face = lateral_face(surface, &rim, &seam, &rim, (0.0, TAU))?;
+    Solid::from_shells(vec![Shell::new(vec![face])?])
+}
+
+/// 大きさが正の有限値であることを確かめる
+fn check_dimension(name: &str, value: f64) -> Result<()> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(OcctKrsError::InvalidInput(format!(
+            "{}は正の有限値である必要があります: {}",
+            name, value
+        )))
+    }
+}
+
+/// 底面の半径 `r0`、上面の半径 `r1`、高さ `height` の円柱または円錐台（半径 0 の側は頂点）
+fn frustum(position: Axis2, r0: f64, r1: f64, height: f64) -> Result<Solid> {
+    let bottom_point = position.point_at(r0, 0.0, 0.0);
+    let top_point = position.point_at(r1, 0.0, height);
+    let (b, t) = (Vertex::new(bottom_point), Vertex::new(top_point));
+    let slant = bottom_point.distance(top_point);
+    let surface: GeomSurface = if r0 == r1 {
+        CylindricalSurface::new(Axis3::from(position), r0)?.into()
+    } else {
+        ConicalSurface::new(Axis3::from(position), ((r1 - r0) / height).atan(), r0)?.into()
+    };
+    // 半径 0 の側は退化エッジ、そうでなければ継ぎ目の端から回る円
+    let rim = |vertex: &Vertex, radius: f64, z: f64| -> Result<Edge> {
+        if radius == 0.0 {
+            return Edge::degenerate(vertex.clone(), (0.0, TAU));
+        }
+        let circle = Circle::new(
+            position.with_location(position.point_at(0.0, 0.0, z)),
+            radius,
+        )?;
+        Edge::new(circle, (0.0, TAU), vertex.clone(), vertex.clone())
+    };
+    let bottom = rim(&b, r0, 0.0)?;
+    let top = rim(&t, r1, height)?;
+    let line = Line::from_points(bottom_point, top_point)?;
+    let seam = Edge::new(line, (0.0, slant), b, t)?;
+    let mut faces = vec![lateral_face(surface, &bottom, &seam, &top, (0.0, slant))?];
+    let tolerance = precision::confusion();
+    if !bottom.is_degenerate() {
+        let wire = Wire::new(vec![bottom.reversed()])?;
+        faces.push(Face::from_planar_wires(wire, Vec::new(), tolerance)?);
+    }
+    if !top.is_degenerate() {
+        let wire = Wire::new(vec![top])?;
+        faces.push(Face::from_planar_wires(wire, Vec::new(), tolerance)?);
+    }
+    Solid::from_shells(vec![Shell::new(faces)?])
+}
+
+/// パラメータ空間の長方形 `[0, 2π] × [v0, v1]` 全体を覆う、u 方向に周期的な曲面のフェイス
+///
+/// `bottom`（`v = v0`）と `top`（`v = v1`）は u と同じパラメータ `[0, 2π]` を持ち、
+/// `seam`（`u = 0` と `u = 2π` の継ぎ目）は `v0` から `v1` へ v に比例したパラメータを持つ。
+/// トーラスのように `bottom` と `top` が同じエッジでもよい。
+fn lateral_face(
+    surface: impl Into<GeomSurface>,
+    bottom: &Edge,
+    seam: &Edge,
+    top: &Edge,
+    (v0, v1): (f64, f64),
+) -> Result<Face> {
+    let wire = Wire::new(vec![
+        bottom.clone(),
+        seam.clone(),
+        top.reversed(),
+        seam.reversed(),
+    ])?;
+    let pcurves = vec![vec![
+        Some(uv_line((0.0, v0), (TAU, v0), bottom.range())?),
+        Some(uv_line((TAU, v0), (TAU, v1), seam.range())?),
+        Some(uv_line((0.0, v1), (TAU, v1), top.range())?),
+        Some(uv_line((0.0, v0), (0.0, v1), seam.range())?),
+    ]];
+    Face::with_pcurves(surface, vec![wire], pcurves)
+}
+
+/// パラメータ区間 `range` で `a` から `b` へ進むパラメータ空間上の線分
+fn uv_line(a: (f64, f64), b: (f64, f64), range: (f64, f64)) -> Result<BSplineCurve2> {
+    BSplineCurve2::from_flat_knots(
+        1,
+        vec![Vector2::new(a.0, a.1), Vector2::new(b.0, b.1)],
+        vec![range.0, range.0, range.1, range.1],
+    )
+}
+
+/// 角の位置と、外側から見て反時計回りに角の番号を並べた各面から多面体を生成する
+///
+/// 同じ位置の角は1つにまとめ、角が3つ未満に縮んだ面は除く。辺は隣り合う面で共有する。
+pub(crate) fn polyhedron(corners: &[Point3], faces: &[[usize; 4]]) -> Result<Solid> {
+    let tolerance = precision::confusion();
+    // 同じ位置の角は最初の番号に寄せる
+    let index: Vec<usize> = (0..corners.len())
+        .map(|i| {
+            (0..i)
+                .find(|&j| corners[j].distance(corners[i]) <= tolerance)
+                .unwrap_or(i)
+        })
+        .collect();
+    let vertices: Vec<Vertex> = corners.iter().map(|&p| Vertex::new(p)).collect();
+    let mut edges: Vec<((usize, usize), Edge)> = Vec::new();
+    let mut result = Vec::with_capacity(faces.len());
+    for face in faces {
+        let mut loop_: Vec<usize> = face.iter().map(|&i| index[i]).collect();
+        loop_.dedup();
+        while loop_.len() > 1 && loop_[0] == loop_[loop_.len() - 1] {
+            loop_.pop();
+        }
+        if loop_.len() < 3 {
+            continue;
+        }
+        let mut wire = Vec::with_capacity(loop_.len());
+        for (k, &a) in loop_.iter().enumerate() {
+            let b = loop_[(k + 1) % loop_.len()];
+            let edge = match edges
+                .iter()
+                .find(|(key, _)| *key == (a, b) || *key == (b, a))
+            {
+                Some(((s, _), e)) if *s == a => e.clone(),
+                Some((_, e)) => e.reversed(),
+                None => {
+                    let line = Line::from_points(corners[a], corners[b])?;
+                    let length = corners[a].distance(corners[b]);
+                    let e = Edge::new(
+                        line,
+                        (0.0, length),
+                        vertices[a].clone(),
+                        vertices[b].clone(),
+                    )?;
+                    edges.push(((a, b), e.clone()));
+                    e
+                }
+            };
+            wire.push(edge);
+        }
+        result.push(Face::from_planar_wires(
+            Wire::new(wire)?,
+            Vec::new(),
+            tolerance,
+        )?);
+    }
+    Solid::from_shells(vec![Shell::new(result)?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::checked_volume;
+    use crate::{Dir, Shape, ShapeKind};
+    use std::f64::consts::PI;
+
+    /// 原点からずらして傾けた座標系
+    fn tilted() -> Axis2 {
+        let direction = Dir::from_vector(crate::Vector3::new(1.0, 2.0, 2.0)).unwrap();
+        Axis2::new(Point3::new(1.0, -2.0, 3.0), direction, Dir::X).unwrap()
+    }
+
+    fn count(solid: &Solid, kind: ShapeKind) -> usize {
+        Shape::from(solid.clone()).explore(kind).unique().count()
+    }
+
+    #[test]
+    fn test_make_box() {
+        let solid = make_box(tilted(), 1.0, 2.0, 3.0).unwrap();
+        let volume = checked_volume(&Shape::from(solid.clone()));
+        assert!((volume / 6.0 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(count(&solid, ShapeKind::Face), 6);
+        assert_eq!(count(&solid, ShapeKind::Edge), 12);
+        assert_eq!(count(&solid, ShapeKind::Vertex), 8);
+        let far = tilted().point_at(1.0, 2.0, 3.0);
+        assert!(Shape::from(solid)
+            .explore(ShapeKind::Vertex)
+            .any(|v| crate::Vertex::try_from(v).unwrap().point().distance(far) < 1e-12));
+        assert!(make_box(Axis2::world(), 0.0, 1.0, 1.0).is_err());
+        assert!(make_box(Axis2::world(), 1.0, f64::NAN, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_make_wedge() {
+        let solid = make_wedge(tilted(), 2.0, 1.0, 3.0, 1.0).unwrap();
+        let volume = checked_volume(&Shape::from(solid.clone()));
+        assert!((volume / 4.5 - 1.0).abs() < 1e-6, "{}", volume);
+        // 上面が稜線に縮むと三角柱になる
+        let prism = make_wedge(Axis2::world(), 2.0, 1.0, 3.0, 0.0).unwrap();
+        let volume = checked_volume(&Shape::from(prism.clone()));
+        assert!((volume / 3.0 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(count(&prism, ShapeKind::Face), 5);
+        assert_eq!(count(&prism, ShapeKind::Vertex), 6);
+        assert!(make_wedge(Axis2::world(), 2.0, 1.0, 3.0, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_make_cylinder() {
+        let solid = make_cylinder(tilted(), 2.0, 3.0).unwrap();
+        let volume = checked_volume(&Shape::from(solid.clone()));
+        assert!((volume / (PI * 12.0) - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(count(&solid, ShapeKind::Face), 3);
+        assert_eq!(count(&solid, ShapeKind::Edge), 3);
+        assert!(make_cylinder(Axis2::world(), -1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_make_cone() {
+        let frustum = make_cone(tilted(), 2.0, 1.0, 3.0).unwrap();
+        let volume = checked_volume(&Shape::from(frustum.clone()));
+        assert!((volume / (PI * 7.0) - 1.0).abs() < 1e-6, "{}", volume);
+        // 先端が頂点になる円錐は、底面と側面の2枚のフェイスになる
+        let cone = make_cone(Axis2::world(), 0.0, 2.0, 3.0).unwrap();
+        let volume = checked_volume(&Shape::from(cone.clone()));
+        assert!((volume / (PI * 4.0) - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(count(&cone, ShapeKind::Face), 2);
+        assert!(make_cone(Axis2::world(), 1.0, 1.0, 1.0).is_err());
+        assert!(make_cone(Axis2::world(), 1.0, -1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_make_sphere() {
+        let solid = make_sphere(tilted(), 2.0).unwrap();
+        let volume = checked_volume(&Shape::from(solid.clone()));
+        assert!(
+            (volume / (PI * 32.0 / 3.0) - 1.0).abs() < 1e-6,
+            "{}",
+            volume
+        );
+        assert_eq!(count(&solid, ShapeKind::Face), 1);
+        assert_eq!(count(&solid, ShapeKind::Vertex), 2);
+        assert!(make_sphere(Axis2::world(), 0.0).is_err());
+    }
+
+    #[test]
+    fn test_make_torus() {
+        let solid = make_torus(tilted(), 3.0, 1.0).unwrap();
+        let volume = checked_volume(&Shape::from(solid.clone()));
+        assert!(
+            (volume / (2.0 * PI * PI * 3.0) - 1.0).abs() < 1e-6,
+            "{}",
+            volume
+        );
+        assert_eq!(count(&solid, ShapeKind::Edge), 2);
+        assert_eq!(count(&solid, ShapeKind::Vertex), 1);
+        assert!(make_torus(Axis2::world(), 1.0, 1.0).is_err());
+    }
+}
@@ -0,0 +1,514 @@
+use crate::{precision, Curve3, Point3, Surface};
+
+/// 射影の初期値を探すためにパラメータ範囲を分割する区間数
+const SAMPLES: usize = 128;
+
+/// 射影の初期値を探すために曲面のパラメータ範囲を各方向に分割する区間数
+const SURFACE_SAMPLES: usize = 32;
+
+/// 精密化する初期値の最大数
+const SURFACE_SEEDS: usize = 8;
+
+/// 点から曲線への射影（曲線上の最近点）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveProjection {
+    /// 曲線上の点のパラメータ
+    pub parameter: f64,
+    /// 曲線上の点
+    pub point: Point3,
+    /// 元の点との距離
+    pub distance: f64,
+}
+
+/// 点からの距離が極小となる曲線上の点をパラメータ順にすべて返す
+///
+/// パラメータ範囲を等分した点で距離の極小を探し、ニュートン法で精密化する。
+/// パラメータ範囲が無限の場合は空を返す。
+pub(crate) fn project_local(curve: &(impl Curve3 + ?Sized), p: Point3) -> Vec<CurveProjection> {
+    let (a, b) = (curve.first_parameter(), curve.last_parameter());
+    if !(a.is_finite() && b.is_finite()) {
+        return Vec::new();
+    }
+    let step = (b - a) / SAMPLES as f64;
+    let u = |i: usize| a + step * i as f64;
+    let d: Vec<f64> = (0..=SAMPLES)
+        .map(|i| curve.point_at(u(i)).distance_squared(p))
+        .collect();
+    let mut result: Vec<CurveProjection> = Vec::new();
+    for i in 0..=SAMPLES {
+        // 距離が一定の区間では最初の標本点だけを採る
+        let left = i == 0 || d[i] < d[i - 1];
+        let right = i == SAMPLES || d[i] <= d[i + 1];
+        if !(left && right) {
+            continue;
+        }
+        let lo = u(i.saturating_sub(1));
+        let hi = u((i + 1).min(SAMPLES));
+        let t = refine_projection(curve, p, u(i), lo, hi);
+        let point = curve.point_at(t);
+        let found = CurveProjection {
+            parameter: t,
+            point,
+            distance: point.distance(p),
+        };
+        match result.last_mut() {
+            // 隣り合う標本点から同じ極小に収束した場合はまとめる
+            Some(last) if (last.parameter - t).abs() <= step => {
+                if found.distance < last.distance {
+                    *last = found;
+                }
+            }
+            _ => result.push(found),
+        }
+    }
+    // 閉曲線の始点と終点は同じ点なので、終点側を除く
+    if result.len() > 1 && curve.is_closed() {
+        let (first, last) = (result[0], result[result.len() - 1]);
+        if last.parameter - first.parameter >= (b - a) - step
+            && first.point.distance(last.point) <= precision::confusion()
+        {
+            result.pop();
+        }
+    }
+    result
+}
+
+/// 点に最も近い曲線上の点を返す（距離が等しい点が複数あればパラメータ順にすべて）
+pub(crate) fn project_point(curve: &(impl Curve3 + ?Sized), p: Point3) -> Vec<CurveProjection> {
+    let mut all = project_local(curve, p);
+    let Some(min) = all.iter().map(|x| x.distance).min_by(f64::total_cmp) else {
+        return all;
+    };
+    all.retain(|x| x.distance <= min + precision::confusion());
+    all
+}
+
+/// 初期値 `u` から、点との距離が極小となるパラメータを `[lo, hi]` の範囲で求める
+pub(crate) fn refine_projection(
+    curve: &(impl Curve3 + ?Sized),
+    p: Point3,
+    mut u: f64,
+    lo: f64,
+    hi: f64,
+) -> f64 {
+    for _ in 0..32 {
+        let f = curve.point_at(u) - p;
+        let d1 = curve.derivative_at(u);
+        let d2 = curve.second_derivative_at(u);
+        let g = f.dot(d1);
+        let mut h = d1.dot(d1) + f.dot(d2);
+        if h <= 0.0 {
+            // 曲率中心より外側では2階の項を落として勾配方向に進む
+            h = d1.dot(d1);
+            if h <= 0.0 {
+                break;
+            }
+        }
+        let next = (u - g / h).clamp(lo, hi);
+        let done = (next - u).abs() <= 1e-15 * (1.0 + u.abs());
+        u = next;
+        if done {
+            break;
+        }
+    }
+    u
+}
+
+/// 点から曲面への射影（曲面上の最近点）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceProjection {
+    /// 曲面上の点の u パラメータ
+    pub u: f64,
+    /// 曲面上の点の v パラメータ
+    pub v: f64,
+    /// 曲面上の点
+    pub point: Point3,
+    /// 元の点との距離
+    pub distance: f64,
+}
+
+/// パラメータ `(u, v)` の点を射影の結果として返す
+pub(crate) fn surface_projection_at(
+    surface: &(impl Surface + ?Sized),
+    p: Point3,
+    u: f64,
+    v: f64,
+) -> SurfaceProjection {
+    let point = surface.point_at(u, v);
+    SurfaceProjection {
+        u,
+        v,
+        point,
+        distance: point.distance(p),
+    }
+}
+
+/// 点に最も近い曲面上の点を返す
+///
+/// パラメータ範囲を格子状に分割した点で距離の極小を探し、近いものから順にニュートン法で精密化する。
+/// 閉じた方向では継ぎ目をまたいで探索する。パラメータ範囲が無限の場合は `None` を返す。
+pub(crate) fn project_onto_surface(
+    surface: &(impl Surface + ?Sized),
+    p: Point3,
+) -> Option<SurfaceProjection> {
+    let ((u0, u1), (v0, v1)) = (surface.u_range(), surface.v_range());
+    if ![u0, u1, v0, v1].iter().all(|x| x.is_finite()) {
+        return None;
+    }
+    let n = SURFACE_SAMPLES;
+    let at = |a: f64, b: f64, i: usize| a + (b - a) * i as f64 / n as f64;
+    let d: Vec<Vec<f64>> = (0..=n)
+        .map(|i| {
+            (0..=n)
+                .map(|j| {
+                    surface
+                        .point_at(at(u0, u1, i), at(v0, v1, j))
+                        .distance_squared(p)
+                })
+                .collect()
+        })
+        .collect();
+    let closed = [surface.is_u_closed(), surface.is_v_closed()];
+    // 閉じた方向では端の隣を反対側の端の手前とする
+    let neighbor = |i: usize, step: isize, closed: bool| -> Option<usize> {
+        let k = i as isize + step;
+        if (0..=n as isize).contains(&k) {
+            Some(k as usize)
+        } else if closed {
+            Some(if k < 0 { n - 1 } else { 1 })
+        } else {
+            None
+        }
+    };
+    let mut minima: Vec<(f64, usize, usize)> = Vec::new();
+    for i in 0..=n {
+        for j in 0..=n {
+            let is_min = (-1..=1).all(|di| {
+                (-1..=1).all(
+                    |dj| match (neighbor(i, di, closed[0]), neighbor(j, dj, closed[1])) {
+                        (Some(a), Some(b)) => d[i][j] <= d[a][b],
+                        _ => true,
+                    },
+                )
+            });
+            if is_min {
+                minima.push((d[i][j], i, j));
+            }
+        }
+    }
+    minima.sort_by(|a, b| a.0.total_cmp(&b.0));
+    nearest_from_seeds(
+        surface,
+        p,
+        minima
+            .iter()
+            .take(SURFACE_SEEDS)
+            .map(|&(_, i, j)| (at(u0, u1, i), at(v0, v1, j))),
+    )
+}
+
+/// 各初期値から精密化した点のうち最も近いものを返す
+pub(crate) fn nearest_from_seeds(
+    surface: &(impl Surface + ?Sized),
+    p: Point3,
+    seeds: impl IntoIterator<Item = (f64, f64)>,
+) -> Option<SurfaceProjection> {
+    seeds
+        .into_iter()
+        .map(|(u, v)| {
+            let (u, v) = refine_surface_projection(surface, p, u, v);
+            surface_projection_at(surface, p, u, v)
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
+
+/// 初期値 `(u, v)` から、点との距離が極小となるパラメータをニュートン法で求める
+///
+/// 閉じた方向ではパラメータを範囲内に折り返し、それ以外の方向では範囲内に制限する。
+pub(crate) fn refine_surface_projection(
+    surface: &(impl Surface + ?Sized),
+    p: Point3,
+    u: f64,
+    v: f64,
+) -> (f64, f64) {
+    let ((u0, u1), (v0, v1)) = (surface.u_range(), surface.v_range());
+    let closed = [surface.is_u_closed(), surface.is_v_closed()];
+    let fit = |x: f64, lo: f64, hi: f64, closed: bool| {
+        if closed {
+            lo + (x - lo).rem_euclid(hi - lo)
+        } else {
+            x.clamp(lo, hi)
+        }
+    };
+    let (mut u, mut v) = (u, v);
+    let mut dist = surface.point_at(u, v).distance_squared(p);
+    for _ in 0..64 {
+        let f = surface.point_at(u, v) - p;
+        let (su, sv) = (surface.derivative_u_at(u, v), surface.derivative_v_at(u, v));
+        let (suu, suv, svv) = surface.second_derivatives_at(u, v);
+        let g = [f.dot(su), f.dot(sv)];
+        let mut h = [
+            su.dot(su) + f.dot(suu),
+            su.dot(sv) + f.dot(suv),
+            sv.dot(sv) + f.dot(svv),
+        ];
+        if !(h[0] > 0.0 && h[0] * h[2] - h[1] * h[1] > 0.0) {
+            // ヘッセ行列が正定値でない場合は2階の項を落とす（ガウス・ニュートン法）
+            h = [su.dot(su), su.dot(sv), sv.dot(sv)];
+        }
+        let det = h[0] * h[2] - h[1] * h[1];
+        let (du, dv) = if det > 1e-14 * (h[0] * h[2]).max(f64::MIN_POSITIVE) {
+            (
+                (h[2] * g[0] - h[1] * g[1]) / det,
+                (h[0] * g[1] - h[1] * g[0]) / det,
+            )
+        } else {
+            // 極などで一方の偏微分が消える場合は方向ごとに進む
+            let step = |g: f64, h: f64| if h > f64::MIN_POSITIVE { g / h } else { 0.0 };
+            (step(g[0], h[0]), step(g[1], h[2]))
+        };
+        // 距離が減るまで歩幅を縮める
+        let mut scale = 1.0;
+        let mut moved = false;
+        for _ in 0..16 {
+            let nu = fit(u - du * scale, u0, u1, closed[0]);
+            let nv = fit(v - dv * scale, v0, v1, closed[1]);
+            let nd = surface.point_at(nu, nv).distance_squared(p);
+            if nd <= dist {
+                let small = (nu - u).abs() <= 1e-15 * (1.0 + u.abs())
+                    && (nv - v).abs() <= 1e-15 * (1.0 + v.abs());
+                (u, v, dist) = (nu, nv, nd);
+                moved = !small;
+                break;
+            }
+            scale *= 0.5;
+        }
+        if !moved {
+            break;
+        }
+    }
+    (u, v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Axis1, Axis2, Axis3, BSplineCurve, BSplineSurface, Circle, ConicalSurface, Dir, Ellipse,
+        ExtrudedSurface, Line, Plane, Polyline3, RevolvedSurface, Segment, SphericalSurface,
+        ToroidalSurface,
+    };
+    use std::f64::consts::{FRAC_PI_2, TAU};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_project_onto_spline() {
+        let c = BSplineCurve::clamped_uniform(
+            3,
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 0.0),
+                Point3::new(3.0, 2.0, 1.0),
+                Point3::new(4.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let p = Point3::new(2.0, 3.0, 2.0);
+        let proj = c.project(p);
+        assert_eq!(proj.len(), 1);
+        let r = proj[0];
+        assert_point_eq(r.point, c.point_at(r.parameter));
+        // 最近点では差ベクトルが接線と直交する
+        assert!((r.point - p).dot(c.derivative_at(r.parameter)).abs() < 1e-10);
+        assert!((r.distance - r.point.distance(p)).abs() < 1e-15);
+        // 標本点のどれよりも近い
+        for i in 0..=100 {
+            assert!(c.point_at(i as f64 / 100.0).distance(p) >= r.distance - 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_project_clamps_to_ends() {
+        let s = Segment::new(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0));
+        let r = s.project(Point3::new(2.0, 1.0, 0.0));
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].parameter, 1.0);
+        assert!((r[0].distance - 2.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_project_equidistant_points() {
+        // 楕円の中心からは短軸の両端が同じ距離で最も近い
+        let e = Ellipse::new(Axis2::world(), 3.0, 1.0).unwrap();
+        let r = e.project(Point3::new(0.0, 0.0, 0.5));
+        assert_eq!(r.len(), 2);
+        assert_point_eq(r[0].point, Point3::new(0.0, 1.0, 0.0));
+        assert_point_eq(r[1].point, Point3::new(0.0, -1.0, 0.0));
+
+        // 閉曲線の継ぎ目は1点として扱う
+        let square = Polyline3::new(
+            vec![
+                Point3::new(1.0, -1.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(-1.0, 1.0, 0.0),
+                Point3::new(-1.0, -1.0, 0.0),
+            ],
+            true,
+        )
+        .unwrap();
+        let r = square.project(Point3::new(1.0, -1.0, 3.0));
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].parameter, 0.0);
+    }
+
+    #[test]
+    fn test_analytic_projections() {
+        let line = Line::new(Point3::new(0.0, 1.0, 0.0), Dir::X);
+        let r = line.project(Point3::new(-5.0, 3.0, 0.0));
+        assert_eq!(r.len(), 1);
+        assert!((r[0].parameter + 5.0).abs() < 1e-12);
+        assert!((r[0].distance - 2.0).abs() < 1e-12);
+
+        let circle = Circle::new(Axis2::world(), 2.0).unwrap();
+        let r = circle.project(Point3::new(0.0, 5.0, 0.0));
+        assert!((r[0].parameter - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_point_eq(r[0].point, Point3::new(0.0, 2.0, 0.0));
+        assert!((r[0].distance - 3.0).abs() < 1e-12);
+        // 一般の実装と一致する
+        let generic = project_point(&circle, Point3::new(1.0, 5.0, 1.0));
+        let exact = circle.project(Point3::new(1.0, 5.0, 1.0));
+        assert!((generic[0].parameter - exact[0].parameter).abs() < 1e-10);
+        assert!(generic[0].point.distance(exact[0].point) < 1e-10);
+    }
+
+    #[test]
+    fn test_project_local_finds_all_minima() {
+        // 円の外側の点からは最も近い点だけが極小（最も遠い点は極大）
+        let circle = Circle::new(Axis2::world(), 1.0).unwrap();
+        let minima = project_local(&circle, Point3::new(3.0, 0.0, 0.0));
+        assert_eq!(minima.len(), 1);
+        assert_point_eq(minima[0].point, Point3::new(1.0, 0.0, 0.0));
+
+        // 波打つ曲線では複数の極小が見つかる
+        let wave = BSplineCurve::clamped_uniform(
+            2,
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, -2.0, 0.0),
+                Point3::new(2.0, 2.0, 0.0),
+                Point3::new(3.0, -2.0, 0.0),
+                Point3::new(4.0, 0.0, 0.0),
+            ],
+        )
+        .unwrap();
+        let minima = project_local(&wave, Point3::new(2.0, -10.0, 0.0));
+        assert_eq!(minima.len(), 2);
+        assert_eq!(project_point(&wave, Point3::new(2.0, -10.0, 0.0)).len(), 2);
+    }
+
+    #[test]
+    fn test_project_onto_surface_across_seam() {
+        // 継ぎ目のすぐ手前の点は u ≈ 2π 付近に射影される
+        let seg = Segment::new(Point3::new(2.0, 0.0, 0.0), Point3::new(2.0, 0.0, 3.0));
+        let s = RevolvedSurface::new(seg, Axis1::oz());
+        let angle: f64 = -0.01;
+        let p = Point3::new(3.0 * angle.cos(), 3.0 * angle.sin(), 1.5);
+        let r = s.project(p).unwrap();
+        assert!((r.u - (TAU + angle)).abs() < 1e-9);
+        assert!((r.v - 0.5).abs() < 1e-9);
+        assert!((r.distance - 1.0).abs() < 1e-9);
+        // 継ぎ目の反対側から探索を始めても継ぎ目を越えて収束する
+        let (u, _) = refine_surface_projection(&s, p, 0.2, 0.5);
+        assert!((u - (TAU + angle)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_numeric_projection_matches_analytic() {
+        let axis = Axis3::new(
+            Point3::new(0.5, -1.0, 2.0),
+            Dir::new(0.3, 0.2, 1.0).unwrap(),
+            Dir::x_axis(),
+        )
+        .unwrap();
+        let torus = ToroidalSurface::new(axis, 3.0, 1.0).unwrap();
+        let sphere = SphericalSurface::new(axis, 2.0).unwrap();
+        for p in [
+            Point3::new(4.0, 1.0, 2.0),
+            Point3::new(-2.0, -3.0, 3.5),
+            Point3::new(0.6, -0.9, 4.1),
+        ] {
+            let exact = torus.project(p).unwrap();
+            let numeric = project_onto_surface(&torus, p).unwrap();
+            assert!((exact.distance - numeric.distance).abs() < 1e-9);
+            assert_point_eq(exact.point, numeric.point);
+            let exact = sphere.project(p).unwrap();
+            let numeric = project_onto_surface(&sphere, p).unwrap();
+            assert!((exact.distance - numeric.distance).abs() < 1e-9);
+        }
+        // 極の近くの点
+        let p = sphere.center() + axis.direction().to_vector() * 3.0;
+        let r = project_onto_surface(&sphere, p).unwrap();
+        assert!((r.distance - 1.0).abs() < 1e-9);
+        assert!((r.v - FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_project_onto_cone_uses_nearer_generator() {
+        let axis = Axis3::from_normal(Point3::ORIGIN, Dir::z_axis());
+        let cone = ConicalSurface::new(axis, 0.5, 0.0).unwrap();
+        // 頂点より下の点は反対側の母線（v < 0）に近い
+        let p = Point3::new(0.1, 0.0, -2.0);
+        let r = cone.project(p).unwrap();
+        let numeric = (0..=3600)
+            .flat_map(|i| (0..=200).map(move |j| (i as f64 * TAU / 3600.0, -4.0 + j as f64 * 0.04)))
+            .map(|(u, v)| cone.point_at(u, v).distance(p))
+            .fold(f64::INFINITY, f64::min);
+        assert!(r.distance <= numeric + 1e-12);
+        assert!(r.v < 0.0);
+    }
+
+    #[test]
+    fn test_project_onto_extrusion_and_plane() {
+        let circle = Circle::new(Axis2::world(), 2.0).unwrap();
+        let s = ExtrudedSurface::new(circle, Dir::z_axis());
+        let r = s.project(Point3::new(0.0, 5.0, 100.0)).unwrap();
+        assert_point_eq(r.point, Point3::new(0.0, 2.0, 100.0));
+        assert!((r.distance - 3.0).abs() < 1e-9);
+
+        let plane = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::z_axis());
+        let r = Surface::project(&plane, Point3::new(3.0, 4.0, 5.0)).unwrap();
+        assert_point_eq(r.point, Point3::new(3.0, 4.0, 1.0));
+        assert!((r.distance - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_project_onto_bspline_surface() {
+        let grid = (0..4)
+            .map(|i| {
+                (0..4)
+                    .map(|j| {
+                        let (x, y) = (i as f64, j as f64);
+                        Point3::new(x, y, (x - 1.5).powi(2) * 0.3 - (y - 1.5).powi(2) * 0.2)
+                    })
+                    .collect()
+            })
+            .collect();
+        let knots = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+        let s = BSplineSurface::from_flat_knots(3, 3, grid, knots.clone(), knots).unwrap();
+        let p = Point3::new(1.2, 2.1, 1.5);
+        let r = s.project(p).unwrap();
+        // 最近点では差ベクトルが接平面に直交する
+        let f = r.point - p;
+        assert!(f.dot(s.derivative_u_at(r.u, r.v)).abs() < 1e-9);
+        assert!(f.dot(s.derivative_v_at(r.u, r.v)).abs() < 1e-9);
+        for i in 0..=20 {
+            for j in 0..=20 {
+                let q = s.point_at(i as f64 / 20.0, j as f64 / 20.0);
+                assert!(q.distance(p) >= r.distance - 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul};
+
+use crate::{Matrix3, Vector3};
+
+/// 回転を表す四元数（クォータニオン）
+///
+/// `w + xi + yj + zk` の形で保持する。回転として扱う場合は単位四元数であることを前提とする。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// 新しい四元数を生成する
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// 恒等回転を表す単位四元数を返す
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// 回転軸と角度（ラジアン）から回転を表す四元数を生成する
+    /// ※軸がゼロ長の場合はpanicするので注意
+    pub fn from_axis_angle(axis: Vector3, angle: f64) -> Self {
+        let a = axis.normalized();
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::new(c, a.x * s, a.y * s, a.z * s)
+    }
+
+    /// 回転軸（単位ベクトル）と角度（0〜π）を取り出す
+    /// 恒等回転の場合、軸は X 軸とする
+    pub fn to_axis_angle(self) -> (Vector3, f64) {
+        let q = if self.w < 0.0 { -1.0 * self } else { self }.normalized();
+        let s = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        if s < 1e-15 {
+            return (Vector3::new(1.0, 0.0, 0.0), 0.0);
+        }
+        let angle = 2.0 * s.atan2(q.w);
+        (Vector3::new(q.x / s, q.y / s, q.z / s), angle)
+    }
+
+    /// ベクトル部を返す
+    pub fn vector_part(self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// ノルムを計算する
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// 正規化（単位四元数化）する
+    /// ※ノルムがゼロの場合はpanicするので注意
+    pub fn normalized(self) -> Quaternion {
+        let len = self.length();
+        if len == 0.0 {
+            panic!("ゼロ四元数は正規化できません");
+        }
+        self * (1.0 / len)
+    }
+
+    /// 共役四元数を返す
+    pub fn conjugate(self) -> Quaternion {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// 逆四元数を返す
+    /// ノルムがゼロの場合は `None` を返す
+    pub fn inverse(self) -> Option<Quaternion> {
+        let n2 = self.dot(self);
+        if n2 == 0.0 {
+            return None;
+        }
+        Some(self.conjugate() * (1.0 / n2))
+    }
+
+    /// 球面線形補間（slerp）を行う（t = 0 で a、t = 1 で b）
+    ///
+    /// 回転として最短経路になるよう、内積が負の場合は `b` の符号を反転して補間する。
+    /// 2つの回転がほぼ一致する場合は正規化付き線形補間で代用する。
+    pub fn slerp(a: Quaternion, b: Quaternion, t: f64) -> Quaternion {
+        let a = a.normalized();
+        let mut b = b.normalized();
+        let mut cos = a.dot(b);
+        if cos < 0.0 {
+            b = -1.0 * b;
+            cos = -cos;
+        }
+        if cos > 1.0 - 1e-10 {
+            let q = a * (1.0 - t) + b * t;
+            return q.normalized();
+        }
+        let theta = cos.acos();
+        let sin = theta.sin();
+        let wa = ((1.0 - t) * theta).sin() / sin;
+        let wb = (t * theta).sin() / sin;
+        a * wa + b * wb
+    }
+
+    /// ベクトルを回転する（単位四元数であることを前提とする）
+    pub fn rotate(self, v: Vector3) -> Vector3 {
+        // v' = v + 2w(q×v) + 2q×(q×v)
+        let q = self.vector_part();
+        let t = 2.0 * q.cross(v);
+        v + self.w * t + q.cross(t)
+    }
+
+    /// 回転行列に変換する（単位四元数であることを前提とする）
+    pub fn to_matrix3(self) -> Matrix3 {
+        let Quaternion { w, x, y, z } = self;
+        Matrix3::from_rows([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+
+    /// 回転行列から四元数を生成する（w ≥ 0 に正規化して返す）
+    pub fn from_matrix3(m: &Matrix3) -> Self {
+        let m = &m.m;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        // 数値的に安定な成分を基準に計算する（Shepperd の方法）
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                0.25 * s,
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            Self::new(
+                (m[2][1] - m[1][2]) / s,
+                0.25 * s,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            Self::new(
+                (m[0][2] - m[2][0]) / s,
+                (m[0][1] + m[1][0]) / s,
+                0.25 * s,
+                (m[1][2] + m[2][1]) / s,
+            )
+        } else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            Self::new(
+                (m[1][0] - m[0][1]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                0.25 * s,
+            )
+        };
+        let q = q.normalized();
+        if q.w < 0.0 {
+            -1.0 * q
+        } else {
+            q
+        }
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 四元数同士の積（ハミルトン積）の実装
+/// `a * b` は「b の回転の後に a の回転」を表す
+impl Mul for Quaternion {
+    type Output = Self;
+    fn mul(self, o: Self) -> Self {
+        Self::new(
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+        )
+    }
+}
+
+/// 四元数同士の加算の実装
+impl Add for Quaternion {
+    type Output = Self;
+    fn add(self, o: Self) -> Self {
+        Self::new(self.w + o.w, self.x + o.x, self.y + o.y, self.z + o.z)
+    }
+}
+
+/// 四元数のスカラー倍の実装
+impl Mul<f64> for Quaternion {
+    type Output = Self;
+    fn mul(self, s: f64) -> Self {
+        Self::new(self.w * s, self.x * s, self.y * s, self.z * s)
+    }
+}
+
+/// スカラー倍の右側に四元数を許容するための実装
+impl Mul<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn mul(self, q: Quaternion) -> Quaternion {
+        q * self
+    }
+}
+
+/// 四元数によるベクトルの回転
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+    fn mul(self, v: Vector3) -> Vector3 {
+        self.rotate(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_vec_eq(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_rotate_vector() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+        assert_vec_eq(q * Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        // 逆回転で元に戻る
+        let v = Vector3::new(0.3, -1.2, 2.0);
+        assert_vec_eq(q.inverse().unwrap() * (q * v), v);
+        assert_vec_eq(q.conjugate() * (q * v), v);
+    }
+
+    #[test]
+    fn test_composition() {
+        let a = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 0.4);
+        let b = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 1.0), -1.1);
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_vec_eq((a * b) * v, a * (b * v));
+        assert!(((a * b).length() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matrix_roundtrip() {
+        let axes = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 2.0, -3.0),
+        ];
+        for axis in axes {
+            for angle in [0.0, 0.5, FRAC_PI_2, 3.0, PI] {
+                let q = Quaternion::from_axis_angle(axis, angle);
+                let m = q.to_matrix3();
+                let v = Vector3::new(-0.7, 0.2, 1.9);
+                assert_vec_eq(m * v, q * v);
+                let back = Quaternion::from_matrix3(&m);
+                assert_vec_eq(back * v, q * v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_axis_angle_roundtrip() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 3.0, 4.0), 1.2);
+        let (axis, angle) = q.to_axis_angle();
+        assert_vec_eq(axis, Vector3::new(0.0, 0.6, 0.8));
+        assert!((angle - 1.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zero_quaternion() {
+        assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inverse().is_none());
+    }
+
+    #[test]
+    fn test_slerp() {
+        let axis = Vector3::new(0.0, 0.0, 1.0);
+        let a = Quaternion::identity();
+        let b = Quaternion::from_axis_angle(axis, FRAC_PI_2);
+        let mid = Quaternion::slerp(a, b, 0.5);
+        let expected = Quaternion::from_axis_angle(axis, FRAC_PI_2 / 2.0);
+        let v = Vector3::new(1.0, 0.0, 0.0);
+        assert_vec_eq(mid * v, expected * v);
+        assert_vec_eq(Quaternion::slerp(a, b, 0.0) * v, v);
+        assert_vec_eq(Quaternion::slerp(a, b, 1.0) * v, b * v);
+        // 符号が反転した四元数（同じ回転）との補間でも最短経路になる
+        let mid2 = Quaternion::slerp(a, -1.0 * b, 0.5);
+        assert_vec_eq(mid2 * v, expected * v);
+        // ほぼ同じ回転同士でも破綻しない
+        let near = Quaternion::slerp(b, b, 0.3);
+        assert!((near.length() - 1.0).abs() < 1e-12);
+    }
+}
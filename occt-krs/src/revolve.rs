@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use crate::extrude::{area_vector, translated, uv_segment, wire_points};
+use crate::precision;
+use crate::topo::ShapeId;
+use crate::{
+    Axis1, Axis3, BSplineCurve2, Circle, ConicalSurface, CylindricalSurface, Dir, Edge, Face,
+    GeomCurve, GeomSurface, OcctKrsError, Orientation, Point3, Result, RevolvedSurface, Shape,
+    Shell, Solid, SphericalSurface, ToroidalSurface, Transform, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 形状を軸 `axis` のまわりに角度 `angle` だけ回転させる（OCCT の `BRepPrimAPI_MakeRevol` 相当）
+///
+/// エッジからはフェイス、ワイヤからはシェル、フェイスからは立体を作る。
+/// 角度の絶対値が 2π なら一周させて元のエッジを継ぎ目にし、そうでなければ元のフェイスと
+/// それを回転したフェイスで立体の両端に蓋をする。フェイスの法線はすべて外側を向く。
+/// 側面の曲面は、軸と同じ平面上にある直線のエッジでは平面・円柱面・円錐面、
+/// 軸を含む平面上の円のエッジでは球面（中心が軸上の場合）かトーラス面、
+/// それ以外のエッジでは曲線を回転した回転面にする。軸上にある直線のエッジからは側面を作らない。
+/// `angle` が負なら軸のまわりに逆向きに回転させる。
+///
+/// 角度が 0 の場合や絶対値が 2π を超える場合、エッジ・ワイヤ・フェイス以外の形状の場合、
+/// フェイスが回転する向きと平行な場合、軸と交わる直線のエッジや、
+/// 軸を中心として軸に垂直な円のエッジがある場合はエラーを返す。
+pub fn revolve(profile: &Shape, axis: Axis1, angle: f64) -> Result<Shape> {
+    if !(angle != 0.0 && angle.abs() <= TAU + precision::angular()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "回転角が不正です: {}",
+            angle
+        )));
+    }
+    let axis = if angle < 0.0 { axis.reversed() } else { axis };
+    let mut rotation = Rotation::new(axis, angle.abs());
+    match profile {
+        Shape::Edge(edge) => rotation
+            .side_face(edge, false)?
+            .map(Shape::from)
+            .ok_or_else(|| {
+                OcctKrsError::DegenerateGeometry(
+                    "退化エッジや軸上のエッジは回転できません".to_string(),
+                )
+            }),
+        Shape::Wire(wire) => Ok(Shell::new(rotation.side_faces(wire, false)?)?.into()),
+        Shape::Face(face) => rotation.solid(face).map(Shape::from),
+        other => Err(OcctKrsError::InvalidInput(format!(
+            "{:?} は回転できません（エッジ・ワイヤ・フェイスのみ）",
+            other.kind()
+        ))),
+    }
+}
+
+/// 回転の途中で作った頂点・エッジを、元の形状ごとに共有するための表
+struct Rotation {
+    axis: Axis1,
+    angle: f64,
+    /// 一周させる場合は `true`（回転した先の頂点・エッジは元のものになる）
+    full: bool,
+    turn: Transform,
+    /// 元の頂点から、回転した先の頂点と、元の頂点からそこへ回る稜線への対応
+    vertices: HashMap<ShapeId, (Vertex, Edge)>,
+    /// 元のエッジ（`Forward`）から、回転した先のエッジへの対応
+    edges: HashMap<ShapeId, Edge>,
+}
+
+impl Rotation {
+    fn new(axis: Axis1, angle: f64) -> Self {
+        let full = angle >= TAU - precision::angular();
+        let angle = if full { TAU } else { angle };
+        Self {
+            axis,
+            angle,
+            full,
+            turn: Transform::rotation(&axis, angle),
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    /// 軸から点へ向かう、軸に垂直なベクトル
+    fn radial(&self, p: Point3) -> Vector3 {
+        let w = p - self.axis.location;
+        let a = self.axis.direction.to_vector();
+        w - a * w.dot(a)
+    }
+
+    /// 点の軸に沿った高さ
+    fn height(&self, p: Point3) -> f64 {
+        (p - self.axis.location).dot(self.axis.direction.to_vector())
+    }
+
+    fn on_axis(&self, p: Point3) -> bool {
+        self.radial(p).length() <= precision::confusion()
+    }
+
+    /// 軸上の高さ `h` を原点とし、主方向を軸、X方向を `x` とする座標系
+    fn frame(&self, h: f64, x: Vector3) -> Result<Axis3> {
+        let origin = self.axis.location + self.axis.direction.to_vector() * h;
+        Axis3::new(origin, self.axis.direction, Dir::from_vector(x)?)
+    }
+
+    /// 点 `p` を通る向き `d` の直線が軸と同じ平面上にあれば `true`
+    fn coplanar(&self, p: Point3, d: Vector3) -> bool {
+        let n = self.axis.direction.to_vector().cross(d);
+        n.length() <= precision::angular()
+            || (p - self.axis.location).dot(n.normalized()).abs() <= precision::confusion()
+    }
+
+    /// 軸上にある直線のエッジなら `true`
+    fn lies_on_axis(&self, edge: &Edge, curve: &GeomCurve) -> bool {
+        matches!(curve, GeomCurve::Line(_))
+            && self.on_axis(edge.start_point())
+            && self.on_axis(edge.end_point())
+    }
+
+    /// 頂点を回転した先の頂点と、元の頂点からそこへ軸のまわりを正の向きに回る稜線
+    ///
+    /// 軸上の頂点は動かず、稜線は退化エッジになる。
+    fn lateral(&mut self, vertex: &Vertex) -> Result<(Vertex, Edge)> {
+        if let Some(found) = self.vertices.get(&vertex.id()) {
+            return Ok(found.clone());
+        }
+        let p = vertex.point();
+        let start = vertex.oriented(Orientation::Forward);
+        let r = self.radial(p);
+        let found = if r.length() <= precision::confusion() {
+            (start.clone(), Edge::degenerate(start, (0.0, self.angle))?)
+        } else {
+            let top = if self.full {
+                start.clone()
+            } else {
+                Vertex::new(self.turn.transform_point(p)).with_tolerance(vertex.tolerance())?
+            };
+            let circle = Circle::new(self.frame(self.height(p), r)?.to_axis2(), r.length())?;
+            let edge = Edge::new(circle, (0.0, self.angle), start, top.clone())?;
+            (top, edge)
+        };
+        self.vertices.insert(vertex.id(), found.clone());
+        Ok(found)
+    }
+
+    /// `Forward` のエッジを回転した先のエッジ（曲線とパラメータ区間は元のまま回転する）
+    ///
+    /// 一周させる場合や軸上のエッジでは元のエッジを返す。
+    fn top_edge(&mut self, edge: &Edge) -> Result<Edge> {
+        if self.full {
+            return Ok(edge.clone());
+        }
+        if let Some(found) = self.edges.get(&edge.id()) {
+            return Ok(found.clone());
+        }
+        let top = match edge.located_geometry() {
+            Some(curve) if self.lies_on_axis(edge, &curve) => edge.clone(),
+            Some(curve) => {
+                let (start, _) = self.lateral(&edge.start_vertex())?;
+                let (end, _) = self.lateral(&edge.end_vertex())?;
+                Edge::new(curve.transformed(&self.turn), edge.range(), start, end)?
+                    .with_tolerance(edge.tolerance())?
+            }
+            None => {
+                let (start, _) = self.lateral(&edge.start_vertex())?;
+                Edge::degenerate(start, edge.range())?.with_tolerance(edge.tolerance())?
+            }
+        };
+        self.edges.insert(edge.id(), top.clone());
+        Ok(top)
+    }
+
+    /// ワイヤの各エッジを回転した側面（退化エッジと軸上のエッジは除く）
+    fn side_faces(&mut self, wire: &Wire, flip: bool) -> Result<Vec<Face>> {
+        let mut faces = Vec::with_capacity(wire.len());
+        for edge in wire.edges() {
+            faces.extend(self.side_face(&edge, flip)?);
+        }
+        Ok(faces)
+    }
+
+    /// エッジを回転した側面（退化エッジと軸上のエッジでは `None`）
+    ///
+    /// 曲線の接線と回転する向きの外積の側を表にし、エッジが反転していれば裏返す。
+    /// `flip` が `true` なら、さらに裏返す。
+    fn side_face(&mut self, edge: &Edge, flip: bool) -> Result<Option<Face>> {
+        let Some(curve) = edge.located_geometry().map(|c| c.into_owned()) else {
+            return Ok(None);
+        };
+        let e = edge.oriented(Orientation::Forward);
+        if self.lies_on_axis(&e, &curve) {
+            return Ok(None);
+        }
+        let (_, start) = self.lateral(&e.start_vertex())?;
+        let (_, end) = self.lateral(&e.end_vertex())?;
+        let top = self.top_edge(&e)?;
+        let (face, reversed) = match self.side_surface(&e, &curve)? {
+            Some((surface, bottom, dv)) => {
+                let (t0, t1) = e.range();
+                let turn = Vector2::new(self.angle, 0.0);
+                let lateral = |t: f64| {
+                    let p = bottom.point_at(t);
+                    uv_segment(p, p + turn, (0.0, self.angle))
+                };
+                let pcurves = vec![vec![
+                    Some(bottom.clone()),
+                    Some(lateral(t1)?),
+                    Some(translated(&bottom, turn)?),
+                    Some(lateral(t0)?),
+                ]];
+                let wire = Wire::new(vec![e, end, top.reversed(), start.reversed()])?;
+                // v がエッジに沿って増える曲面では、パラメータ空間でワイヤが時計回りになる
+                let wire = if dv > 0.0 { wire.reversed() } else { wire };
+                (Face::with_pcurves(surface, vec![wire], pcurves)?, dv > 0.0)
+            }
+            None => (self.planar_face(&e, start, end, top)?, false),
+        };
+        let reversed = reversed ^ (edge.orientation() == Orientation::Reversed) ^ flip;
+        Ok(Some(if reversed { face.reversed() } else { face }))
+    }
+
+    /// 軸に垂直な直線の `Forward` のエッジを回転した平面のフェイス
+    ///
+    /// 一周させる場合は、両端の頂点が描く円を境界にした円板か円環にする。
+    fn planar_face(&self, edge: &Edge, start: Edge, end: Edge, top: Edge) -> Result<Face> {
+        let tolerance = precision::confusion();
+        let boundary = [edge.clone(), end, top.reversed(), start.reversed()];
+        if !self.full {
+            let edges = boundary
+                .into_iter()
+                .filter(|e| !e.is_degenerate())
+                .collect();
+            return Face::from_planar_wires(Wire::new(edges)?, Vec::new(), tolerance);
+        }
+        // 軸から遠ざかるエッジなら終点の円が外周になり、円の向きのまま反時計回りになる
+        let [_, end, _, start] = boundary;
+        let outward =
+            self.radial(edge.end_point()).length() > self.radial(edge.start_point()).length();
+        let (outer, inner) = if outward { (end, start) } else { (start, end) };
+        let inners = if inner.is_degenerate() {
+            Vec::new()
+        } else {
+            vec![Wire::new(vec![inner])?]
+        };
+        Face::from_planar_wires(Wire::new(vec![outer])?, inners, tolerance)
+    }
+
+    /// `Forward` のエッジを回転した曲面と、その曲面でのエッジの pcurve（u = 0）、
+    /// エッジに沿って進むときの v の増え方（`1` か `-1`）。平面になる場合は `None`
+    ///
+    /// どの曲面も u は回転角で、v はエッジのパラメータの1次式になる。
+    fn side_surface(
+        &self,
+        edge: &Edge,
+        curve: &GeomCurve,
+    ) -> Result<Option<(GeomSurface, BSplineCurve2, f64)>> {
+        let (t0, t1) = edge.range();
+        let a = self.axis.direction.to_vector();
+        let segment = |v0: f64, v1: f64| {
+            uv_segment(Vector2::new(0.0, v0), Vector2::new(0.0, v1), (t0, t1)).map(Some)
+        };
+        let (p0, p1) = (edge.point_at(t0), edge.point_at(t1));
+        let middle = self.radial(edge.point_at(0.5 * (t0 + t1)));
+        // 両端が中央と同じ側にあり、エッジが軸をまたがないか
+        let one_side = [p0, p1]
+            .iter()
+            .all(|&p| self.radial(p).dot(middle) >= -precision::confusion() * middle.length());
+        match curve {
+            GeomCurve::Line(line) if self.coplanar(p0, line.dir.to_vector()) => {
+                if !one_side {
+                    return Err(OcctKrsError::DegenerateGeometry(
+                        "回転軸と交わる直線のエッジがあります".to_string(),
+                    ));
+                }
+                let d = line.dir.to_vector();
+                let dh = d.dot(a);
+                if dh.abs() <= precision::angular() {
+                    return Ok(None);
+                }
+                let s = dh.signum();
+                if d.cross(a).length() <= precision::angular() {
+                    // v は軸に沿った高さ
+                    let surface =
+                        CylindricalSurface::new(self.frame(0.0, middle)?, middle.length())?;
+                    let (v0, v1) = (self.height(p0), self.height(p1));
+                    return Ok(segment(v0, v1)?.map(|pcurve| (surface.into(), pcurve, s)));
+                }
+                // v は始点から母線に沿って測った長さ（高さが増える向きが正）
+                let x = middle.normalized();
+                let surface = ConicalSurface::new(
+                    self.frame(self.height(p0), middle)?,
+                    (d.dot(x) / dh).atan(),
+                    self.radial(p0).dot(x).max(0.0),
+                )?;
+                return Ok(segment(0.0, s * (t1 - t0))?.map(|pcurve| (surface.into(), pcurve, s)));
+            }
+            GeomCurve::Circle(c) => {
+                let normal = c.position().direction().to_vector();
+                let center = self.radial(c.center());
+                if normal.dot(a).abs() >= 1.0 - precision::angular()
+                    && center.length() <= precision::confusion()
+                {
+                    return Err(OcctKrsError::DegenerateGeometry(
+                        "回転軸を中心とする軸に垂直な円のエッジがあります".to_string(),
+                    ));
+                }
+                let in_meridian = normal.dot(a).abs() <= precision::angular()
+                    && (c.center() - self.axis.location).dot(normal).abs()
+                        <= precision::confusion();
+                if in_meridian && one_side {
+                    // 軸と X 方向の平面で、角度 t の点は中心から角度 `phase + s t` の方向にある
+                    let torus = center.length() > precision::confusion();
+                    let x = if torus { center } else { middle }.normalized();
+                    let (cx, cy) = (
+                        c.position().x_direction().to_vector(),
+                        c.position().y_direction().to_vector(),
+                    );
+                    let phase = cx.dot(a).atan2(cx.dot(x));
+                    let s = cy.dot(a * phase.cos() - x * phase.sin()).signum();
+                    let v = |t: f64| phase + s * t;
+                    let frame = self.frame(self.height(c.center()), x)?;
+                    if torus {
+                        let shift = TAU * (v(t0) / TAU).floor();
+                        let surface = ToroidalSurface::new(frame, center.length(), c.radius())?;
+                        return Ok(segment(v(t0) - shift, v(t1) - shift)?
+                            .map(|pcurve| (surface.into(), pcurve, s)));
+                    }
+                    let shift = TAU * (v(0.5 * (t0 + t1)) / TAU).round();
+                    let (v0, v1) = (v(t0) - shift, v(t1) - shift);
+                    let limit = FRAC_PI_2 + precision::angular();
+                    if v0.abs() <= limit && v1.abs() <= limit {
+                        let surface = SphericalSurface::new(frame, c.radius())?;
+                        return Ok(segment(v0, v1)?.map(|pcurve| (surface.into(), pcurve, s)));
+                    }
+                }
+            }
+            _ => {}
+        }
+        let surface = RevolvedSurface::new(curve.clone(), self.axis);
+        Ok(segment(t0, t1)?.map(|pcurve| (surface.into(), pcurve, 1.0)))
+    }
+
+    /// フェイスを回転した立体
+    fn solid(&mut self, face: &Face) -> Result<Solid> {
+        // 外側のワイヤの面積ベクトルで、フェイスの表が回転する向きを向いているか調べる
+        let outer = face.outer_wire().ok_or_else(|| {
+            OcctKrsError::InvalidInput("境界を持たないフェイスは回転できません".to_string())
+        })?;
+        let points = wire_points(&outer);
+        let area = area_vector(&points);
+        let center = points.iter().fold(Vector3::ZERO, |s, &p| s + p) / points.len() as f64;
+        let motion = self
+            .axis
+            .direction
+            .to_vector()
+            .cross(self.radial(Point3::origin() + center));
+        let height = area.dot(motion);
+        if height.abs() <= precision::confusion() * area.length() * motion.length().max(1.0) {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "回転する向きがフェイスと平行です".to_string(),
+            ));
+        }
+        let flip = height < 0.0;
+        let mut faces = Vec::new();
+        for wire in face.wires() {
+            faces.extend(self.side_faces(&wire, flip)?);
+        }
+        if self.full {
+            return Solid::new(vec![Shell::new(faces)?]);
+        }
+        // 蓋はフェイスの向きによらず、格納された順とエッジの向きで作る
+        let cap = face.oriented(Orientation::Forward);
+        let mut top_wires = Vec::new();
+        let mut top_pcurves = Vec::new();
+        for wire in cap.wires() {
+            let mut edges = Vec::with_capacity(wire.len());
+            let mut pcurves = Vec::with_capacity(wire.len());
+            for edge in wire.edges() {
+                let top = self.top_edge(&edge.oriented(Orientation::Forward))?;
+                pcurves.push(cap.pcurve(&edge).cloned());
+                edges.push(top.oriented(edge.orientation()));
+            }
+            top_wires.push(Wire::new(edges)?);
+            top_pcurves.push(pcurves);
+        }
+        let surface = cap.located_geometry().transformed(&self.turn);
+        let top = Face::with_pcurves(surface, top_wires, top_pcurves)?
+            .with_tolerance(face.tolerance())?
+            .oriented(face.orientation());
+        // 元のフェイスの表が回転する向きを向いていれば、元のフェイスを裏返して蓋にする
+        let (bottom, top) = if flip {
+            (face.clone(), top.reversed())
+        } else {
+            (face.reversed(), top)
+        };
+        faces.insert(0, bottom);
+        faces.push(top);
+        Solid::new(vec![Shell::new(faces)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Ellipse, Line, ShapeKind};
+    use std::f64::consts::PI;
+
+    /// xz 平面上の点を順に結んだ多角形のフェイス
+    fn polygon(points: &[(f64, f64)]) -> Face {
+        let p = |(x, z): (f64, f64)| Point3::new(x, 0.0, z);
+        let edges = (0..points.len())
+            .map(|i| Edge::from_points(p(points[i]), p(points[(i + 1) % points.len()])).unwrap())
+            .collect();
+        Face::from_planar_wires(Wire::from_edges(edges, 1e-9).unwrap(), Vec::new(), 1e-9).unwrap()
+    }
+
+    /// xz 平面上で中心 `(x, 0, 0)` の円か楕円の内側のフェイス
+    fn disk(curve: impl Into<GeomCurve>) -> Face {
+        let wire = Wire::new(vec![Edge::from_curve(curve).unwrap()]).unwrap();
+        Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap()
+    }
+
+    fn xz_frame(x: f64) -> Axis2 {
+        Axis2::new(Point3::new(x, 0.0, 0.0), Dir::Y.reversed(), Dir::X).unwrap()
+    }
+
+    fn has_surface(shape: &Shape, matches: fn(&GeomSurface) -> bool) -> bool {
+        shape.faces().iter().any(|f| matches(f.surface()))
+    }
+
+    #[test]
+    fn test_revolve_rectangle() {
+        let rectangle = Shape::from(polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 2.0), (0.0, 2.0)]));
+        let solid = revolve(&rectangle, Axis1::oz(), TAU).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / (2.0 * PI) - 1.0).abs() < 1e-6, "{}", volume);
+        // 円柱面と上下の円板（軸上のエッジからは側面を作らない）
+        assert_eq!(solid.faces().len(), 3);
+        assert!(has_surface(&solid, |s| matches!(
+            s,
+            GeomSurface::Cylinder(_)
+        )));
+
+        for angle in [FRAC_PI_2, -FRAC_PI_2] {
+            let solid = revolve(&rectangle, Axis1::oz(), angle).unwrap();
+            let volume = checked_volume(&solid);
+            assert!((volume / (0.5 * PI) - 1.0).abs() < 1e-6, "{}", volume);
+            assert_eq!(solid.faces().len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_revolve_annulus_and_cone() {
+        let ring = Shape::from(polygon(&[(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)]));
+        let volume = checked_volume(&revolve(&ring, Axis1::oz(), TAU).unwrap());
+        assert!((volume / (3.0 * PI) - 1.0).abs() < 1e-6, "{}", volume);
+
+        let triangle = Shape::from(polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]));
+        for angle in [TAU, PI] {
+            let solid = revolve(&triangle, Axis1::oz(), angle).unwrap();
+            let volume = checked_volume(&solid);
+            let expected = PI / 3.0 * angle / TAU;
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+            assert!(has_surface(&solid, |s| matches!(s, GeomSurface::Cone(_))));
+        }
+    }
+
+    #[test]
+    fn test_revolve_half_disk_makes_sphere() {
+        let south = Vertex::new(Point3::new(0.0, 0.0, -1.0));
+        let north = Vertex::new(Point3::new(0.0, 0.0, 1.0));
+        // 角度 t の点が -cos t · Z + sin t · X になる円
+        let frame = Axis2::new(Point3::origin(), Dir::Y.reversed(), Dir::Z.reversed()).unwrap();
+        let meridian = Circle::new(frame, 1.0).unwrap();
+        let arc = Edge::new(meridian, (0.0, PI), south.clone(), north.clone());
+        let line = Line::from_points(north.point(), south.point()).unwrap();
+        let chord = Edge::new(line, (0.0, 2.0), north, south).unwrap();
+        let wire = Wire::new(vec![arc.unwrap(), chord]).unwrap();
+        let half = Shape::from(Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap());
+        for angle in [TAU, PI] {
+            let solid = revolve(&half, Axis1::oz(), angle).unwrap();
+            let volume = checked_volume(&solid);
+            let expected = 4.0 / 3.0 * PI * angle / TAU;
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+            assert!(has_surface(&solid, |s| matches!(s, GeomSurface::Sphere(_))));
+        }
+    }
+
+    #[test]
+    fn test_revolve_disk_makes_torus() {
+        let circle = Shape::from(disk(Circle::new(xz_frame(3.0), 1.0).unwrap()));
+        let solid = revolve(&circle, Axis1::oz(), TAU).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / (6.0 * PI * PI) - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(solid.faces().len(), 1);
+        assert!(has_surface(&solid, |s| matches!(s, GeomSurface::Torus(_))));
+
+        // 楕円は回転面になる
+        let ellipse = Shape::from(disk(Ellipse::new(xz_frame(3.0), 1.0, 0.5).unwrap()));
+        let solid = revolve(&ellipse, Axis1::oz(), FRAC_PI_2).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / (0.75 * PI * PI) - 1.0).abs() < 1e-6, "{}", volume);
+        assert!(has_surface(&solid, |s| matches!(
+            s,
+            GeomSurface::Revolution(_)
+        )));
+    }
+
+    #[test]
+    fn test_revolve_wire_and_edge() {
+        let face = polygon(&[(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)]);
+        let wire = face.outer_wire().unwrap();
+        let shell = revolve(&wire.clone().into(), Axis1::oz(), PI).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+        assert!(shell.check().is_valid());
+        // 元のワイヤと回転した先のワイヤが自由エッジになる
+        assert_eq!(shell.free_bounds().unwrap().free_edge_count(), 8);
+
+        let shell = revolve(&wire.into(), Axis1::oz(), TAU).unwrap();
+        assert!(shell.free_bounds().unwrap().is_empty());
+
+        let edge = Edge::from_points(Point3::new(1.0, 0.0, 0.0), Point3::new(2.0, 0.0, 1.0));
+        let face = revolve(&edge.unwrap().into(), Axis1::oz(), TAU).unwrap();
+        assert_eq!(face.kind(), ShapeKind::Face);
+        assert!(face.check().is_valid());
+    }
+
+    #[test]
+    fn test_revolve_errors() {
+        let square = Shape::from(polygon(&[(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)]));
+        assert!(revolve(&square, Axis1::oz(), 0.0).is_err());
+        assert!(revolve(&square, Axis1::oz(), 3.0 * PI).is_err());
+        assert!(revolve(&square, Axis1::oz(), f64::NAN).is_err());
+        // 回転軸がフェイスの平面に垂直
+        assert!(revolve(&square, Axis1::oy(), PI).is_err());
+        let vertex = Shape::from(Vertex::new(Point3::new(1.0, 0.0, 0.0)));
+        assert!(revolve(&vertex, Axis1::oz(), PI).is_err());
+        let axis_edge = Edge::from_points(Point3::origin(), Point3::new(0.0, 0.0, 1.0)).unwrap();
+        assert!(revolve(&axis_edge.into(), Axis1::oz(), PI).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use crate::check::edge_parameters;
+use crate::precision;
+use crate::shape_builder::{edge_pcurve, union_find_root};
+use crate::topo::{check_tolerance, ShapeId};
+use crate::{
+    Curve3, Edge, Face, OcctKrsError, Orientation, Point3, Result, Shape, ShapeKind, Shell, Solid,
+    Vertex, Wire,
+};
+
+/// 縫い合わせの結果（OCCT の `BRepBuilderAPI_Sewing` 相当）
+#[derive(Debug, Clone)]
+pub struct Sewing {
+    shells: Vec<Shell>,
+    free_edges: Vec<Edge>,
+}
+
+impl Sewing {
+    /// 共有するエッジでつながったフェイスごとのシェルを返す（フェイスの向きはそろえてある）
+    pub fn shells(&self) -> &[Shell] {
+        &self.shells
+    }
+
+    /// 閉じたシェルから、外向きにそろえた立体を返す
+    pub fn solids(&self) -> Result<Vec<Solid>> {
+        self.shells
+            .iter()
+            .filter(|s| s.is_closed())
+            .map(|s| Solid::new(vec![s.clone()])?.fix_orientation())
+            .collect()
+    }
+
+    /// 相手が見つからず、1枚のフェイスの境界にしか現れないエッジを返す
+    pub fn free_edges(&self) -> &[Edge] {
+        &self.free_edges
+    }
+
+    /// 自由エッジが残っていなければ `true` を返す
+    pub fn is_closed(&self) -> bool {
+        self.free_edges.is_empty()
+    }
+}
+
+/// 別々に作られたフェイスを、`tolerance` 以内で一致する頂点とエッジを共有させて縫い合わせる
+///
+/// 位置が `tolerance` 以内の頂点は1つにまとめ（許容誤差は元の頂点を含むように広げる）、
+/// 端の頂点が同じで曲線が `tolerance` 以内で重なる2本のエッジは1本にまとめる。
+/// 3本以上のエッジが重なる場合も、まとめるのは2本ずつにする。
+/// 縫い合わせたフェイスは配置を適用した曲面で作り直し、pcurve は必要に応じて求め直す。
+/// フェイスが空の場合や、許容誤差が正の有限値でない場合はエラーを返す。
+pub fn sew(faces: Vec<Face>, tolerance: f64) -> Result<Sewing> {
+    check_tolerance(tolerance)?;
+    if faces.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "縫い合わせるフェイスがありません".to_string(),
+        ));
+    }
+    let faces: Vec<Face> = faces
+        .iter()
+        .map(|f| f.oriented(Orientation::Forward))
+        .collect();
+    let compound = Shape::from(crate::Compound::new(
+        faces.iter().cloned().map(Shape::from).collect(),
+    ));
+    let vertices = merge_vertices(&compound, tolerance)?;
+
+    // 端の頂点の組ごとにエッジの候補を集める
+    let edge_faces = compound.ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+    let mut candidates: HashMap<(ShapeId, ShapeId), Vec<usize>> = HashMap::new();
+    let mut edges: Vec<Edge> = Vec::new();
+    let mut edge_face_ids: Vec<Vec<ShapeId>> = Vec::new();
+    for (shape, ancestors) in edge_faces.iter() {
+        let Ok(edge) = Edge::try_from(shape.clone()) else {
+            continue;
+        };
+        let edge = edge.oriented(Orientation::Forward);
+        if !edge.is_degenerate() {
+            let (a, b) = (
+                vertices[&edge.start_vertex().id()].id(),
+                vertices[&edge.end_vertex().id()].id(),
+            );
+            let key = if a <= b { (a, b) } else { (b, a) };
+            candidates.entry(key).or_default().push(edges.len());
+        }
+        edges.push(edge);
+        edge_face_ids.push(ancestors.iter().map(|f| f.id()).collect());
+    }
+    // 各エッジをまとめた先（番号）と、向きが逆かどうか
+    let mut target: Vec<(usize, bool)> = (0..edges.len()).map(|i| (i, false)).collect();
+    let mut deviation = vec![0.0_f64; edges.len()];
+    let mut keys: Vec<_> = candidates.keys().copied().collect();
+    keys.sort();
+    for key in keys {
+        let group = &candidates[&key];
+        let mut paired = vec![false; group.len()];
+        for a in 0..group.len() {
+            if paired[a] {
+                continue;
+            }
+            for b in a + 1..group.len() {
+                if paired[b] {
+                    continue;
+                }
+                let (i, j) = (group[a], group[b]);
+                // 同じフェイスの境界どうしはまとめない
+                if edge_face_ids[i]
+                    .iter()
+                    .any(|f| edge_face_ids[j].contains(f))
+                {
+                    continue;
+                }
+                if let Some((d, reversed)) = coincidence(&edges[i], &edges[j], tolerance) {
+                    target[j] = (i, reversed);
+                    deviation[i] = deviation[i].max(d);
+                    paired[a] = true;
+                    paired[b] = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    // まとめた先のエッジを、まとめた頂点で作り直す
+    let mut rebuilt: Vec<Option<Edge>> = vec![None; edges.len()];
+    for i in 0..edges.len() {
+        if target[i].0 != i {
+            continue;
+        }
+        let edge = &edges[i];
+        let (start, end) = (
+            vertices[&edge.start_vertex().id()].clone(),
+            vertices[&edge.end_vertex().id()].clone(),
+        );
+        let mut new = if start.is_same(&edge.start_vertex()) && end.is_same(&edge.end_vertex()) {
+            edge.clone()
+        } else {
+            edge.with_vertices(start, end)?
+        };
+        if deviation[i] > new.tolerance() {
+            new = new.with_tolerance(deviation[i])?;
+        }
+        rebuilt[i] = Some(new);
+    }
+    let index: HashMap<ShapeId, usize> =
+        edges.iter().enumerate().map(|(i, e)| (e.id(), i)).collect();
+
+    // フェイスを作り直す
+    let mut sewn = Vec::with_capacity(faces.len());
+    for face in &faces {
+        let surface = face.located_geometry().into_owned();
+        let mut wires = Vec::new();
+        let mut pcurves = Vec::new();
+        for wire in face.wires() {
+            let mut new_edges = Vec::with_capacity(wire.len());
+            let mut new_pcurves = Vec::with_capacity(wire.len());
+            for edge in wire.edges() {
+                let i = index[&edge.id()];
+                let (t, reversed) = target[i];
+                let mut orientation = edge.orientation();
+                if reversed {
+                    orientation = orientation.reversed();
+                }
+                let new = rebuilt[t]
+                    .as_ref()
+                    .map_or_else(|| edge.clone(), |e| e.oriented(orientation));
+                // 元のエッジの曲線と区間をそのまま使い（頂点だけ付け替えた場合も含む）、
+                // 配置もなければ pcurve を引き継ぐ（継ぎ目のエッジの2つの pcurve を保つ）
+                let pcurve = match face.pcurve(&edge) {
+                    Some(p)
+                        if face.location().is_identity() && (t == i || new.is_partner(&edge)) =>
+                    {
+                        Some(p.clone())
+                    }
+                    _ => edge_pcurve(&new, &surface, tolerance).unwrap_or(None),
+                };
+                new_edges.push(new);
+                new_pcurves.push(pcurve);
+            }
+            // 新しいワイヤは Forward なので、たどる順が格納順になる
+            wires.push(Wire::new(new_edges)?);
+            pcurves.push(new_pcurves);
+        }
+        sewn.push(Face::with_pcurves(surface, wires, pcurves)?.with_tolerance(face.tolerance())?);
+    }
+
+    // 共有するエッジでつながったフェイスをシェルにまとめる
+    let mut parent: Vec<usize> = (0..sewn.len()).collect();
+    let mut uses: HashMap<ShapeId, (usize, usize, Edge)> = HashMap::new();
+    for (f, face) in sewn.iter().enumerate() {
+        for wire in face.wires() {
+            for edge in wire.edges() {
+                if edge.is_degenerate() {
+                    continue;
+                }
+                let entry = uses.entry(edge.id()).or_insert((f, 0, edge.clone()));
+                entry.1 += 1;
+                let (a, b) = (
+                    union_find_root(&mut parent, entry.0),
+                    union_find_root(&mut parent, f),
+                );
+                parent[a] = b;
+            }
+        }
+    }
+    let mut groups: Vec<(usize, Vec<Face>)> = Vec::new();
+    for (f, face) in sewn.into_iter().enumerate() {
+        let root = union_find_root(&mut parent, f);
+        match groups.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, g)) => g.push(face),
+            None => groups.push((root, vec![face])),
+        }
+    }
+    let shells = groups
+        .into_iter()
+        .map(|(_, g)| Shell::new(g)?.fix_orientation())
+        .collect::<Result<Vec<_>>>()?;
+    let mut free: Vec<(usize, Edge)> = uses
+        .into_values()
+        .filter(|u| u.1 == 1)
+        .map(|u| (u.0, u.2.oriented(Orientation::Forward)))
+        .collect();
+    free.sort_by_key(|(f, e)| (*f, e.id()));
+    Ok(Sewing {
+        shells,
+        free_edges: free.into_iter().map(|(_, e)| e).collect(),
+    })
+}
+
+/// 位置が `tolerance` 以内の頂点をまとめ、元の頂点から新しい頂点への対応を返す
+fn merge_vertices(shape: &Shape, tolerance: f64) -> Result<HashMap<ShapeId, Vertex>> {
+    let vertices = shape.vertices();
+    let n = vertices.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+    // x 座標で並べ、近いものだけを比べる
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| vertices[a].point().x.total_cmp(&vertices[b].point().x));
+    for (k, &a) in order.iter().enumerate() {
+        let p = vertices[a].point();
+        for &b in &order[k + 1..] {
+            let q = vertices[b].point();
+            if q.x - p.x > tolerance {
+                break;
+            }
+            if p.distance(q) <= tolerance {
+                let (ra, rb) = (
+                    union_find_root(&mut parent, a),
+                    union_find_root(&mut parent, b),
+                );
+                parent[rb] = ra;
+            }
+        }
+    }
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..n {
+        let root = union_find_root(&mut parent, i);
+        clusters.entry(root).or_default().push(i);
+    }
+    let mut map = HashMap::new();
+    for members in clusters.values() {
+        let first = &vertices[members[0]];
+        let merged = if members.len() == 1 {
+            first.oriented(Orientation::Forward)
+        } else {
+            let p = first.point();
+            let tol = members
+                .iter()
+                .map(|&i| p.distance(vertices[i].point()) + vertices[i].tolerance())
+                .fold(first.tolerance(), f64::max);
+            Vertex::new(p).with_tolerance(tol)?
+        };
+        for &i in members {
+            map.insert(vertices[i].id(), merged.clone());
+        }
+    }
+    Ok(map)
+}
+
+/// エッジ `b` がエッジ `a` と `tolerance` 以内で重なるなら、最大のずれと向きが逆かどうかを返す
+pub(crate) fn coincidence(a: &Edge, b: &Edge, tolerance: f64) -> Option<(f64, bool)> {
+    let params = edge_parameters(b);
+    let n = params.len() - 1;
+    let mut deviation: f64 = 0.0;
+    for &t in &params {
+        deviation = deviation.max(distance_to_edge(a, b.point_at(t)).0);
+        if deviation.is_nan() || deviation > tolerance {
+            return None;
+        }
+    }
+    // b を前から順にたどったとき、a のパラメータが減っていくなら逆向き
+    let (t1, t3) = (params[n / 4], params[n - n / 4]);
+    let (t1, t3) = if t1 < t3 { (t1, t3) } else { (t3, t1) };
+    let reversed = distance_to_edge(a, b.point_at(t1)).1 > distance_to_edge(a, b.point_at(t3)).1;
+    Some((deviation, reversed))
+}
+
+/// 点からエッジまでの距離と、最も近い点のエッジの曲線のパラメータを返す
+fn distance_to_edge(edge: &Edge, p: Point3) -> (f64, f64) {
+    let (first, last) = edge.range();
+    let mut best = (edge.point_at(first).distance(p), first);
+    let end = edge.point_at(last).distance(p);
+    if end < best.0 {
+        best = (end, last);
+    }
+    let (Some(curve), Some(inverse)) = (edge.curve(), edge.location().inverse()) else {
+        return best;
+    };
+    let scale = edge.location().transform().scale.abs();
+    let margin = precision::parametric();
+    for proj in curve.project(inverse.apply(p)) {
+        if proj.parameter >= first - margin && proj.parameter <= last + margin {
+            let d = proj.distance * scale;
+            if d < best.0 {
+                best = (d, proj.parameter);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 頂点を共有しない、別々に作った立方体の6枚のフェイス（`gap` だけ頂点をずらす）
+    fn box_faces(gap: f64) -> Vec<Face> {
+        let corner =
+            |i: usize| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64);
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        quads
+            .iter()
+            .enumerate()
+            .map(|(k, q)| {
+                let shift = crate::Vector3::new(0.0, 0.0, gap * k as f64);
+                let p: Vec<Point3> = q.iter().map(|&i| corner(i) + shift).collect();
+                let edges = (0..4)
+                    .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+                    .collect();
+                let wire = Wire::from_edges(edges, 1e-9).unwrap();
+                Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sew_box_into_solid() {
+        let sewing = sew(box_faces(1e-5), 1e-4).unwrap();
+        assert!(sewing.is_closed());
+        assert_eq!(sewing.shells().len(), 1);
+        let shell = Shape::from(sewing.shells()[0].clone());
+        assert_eq!(shell.edges().len(), 12);
+        assert_eq!(shell.vertices().len(), 8);
+        let solids = sewing.solids().unwrap();
+        assert_eq!(solids.len(), 1);
+        let report = Shape::from(solids[0].clone()).check();
+        assert!(report.is_valid(), "{:?}", report.issues());
+        assert!(solids[0].shells()[0].is_closed());
+    }
+
+    #[test]
+    fn test_sew_reports_free_edges() {
+        // 隙間が許容誤差より大きい
+        let sewing = sew(box_faces(1e-3), 1e-4).unwrap();
+        assert!(!sewing.is_closed());
+        assert!(sewing.solids().unwrap().is_empty());
+
+        // 1枚欠けた箱は、欠けた面のまわりの4本が自由エッジになる
+        let mut faces = box_faces(0.0);
+        faces.remove(1);
+        let sewing = sew(faces, 1e-7).unwrap();
+        assert_eq!(sewing.shells().len(), 1);
+        assert_eq!(sewing.free_edges().len(), 4);
+        assert!(sewing
+            .free_edges()
+            .iter()
+            .all(|e| (e.start_point().z - 1.0).abs() < 1e-12
+                && (e.end_point().z - 1.0).abs() < 1e-12));
+        assert!(sew(Vec::new(), 1e-7).is_err());
+    }
+}
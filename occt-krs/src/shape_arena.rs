@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+
+use crate::topo::ShapeId;
+use crate::{
+    BSplineCurve2, Compound, Edge, Face, GeomCurve, GeomSurface, Location, OcctKrsError,
+    Orientation, Point3, Result, Shape, ShapeKind, Shell, Solid, Vertex, Wire,
+};
+
+/// [`ShapeArena`] に格納した形状の中身を指すハンドル
+///
+/// 番号と世代の組で、削除された形状を指す古いハンドルは無効になる（同じ番号が再利用されても区別できる）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShapeHandle {
+    index: u32,
+    generation: u32,
+}
+
+/// 形状の中身のハンドルに配置と向きを組にした参照（OCCT の `TopoDS_Shape` 相当）
+///
+/// 親から部分形状への参照と、[`ShapeArena::insert`] で格納した形状そのものを表す。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeLink {
+    handle: ShapeHandle,
+    orientation: Orientation,
+    location: Location,
+}
+
+impl ShapeLink {
+    /// 中身のハンドルを返す
+    pub fn handle(&self) -> ShapeHandle {
+        self.handle
+    }
+
+    /// 親に対する向きを返す
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// 親に対する配置を返す
+    pub fn location(&self) -> Location {
+        self.location
+    }
+}
+
+/// 形状の種類ごとの中身（配置を適用する前の幾何と許容誤差）
+#[derive(Debug, Clone)]
+enum Node {
+    Vertex {
+        point: Point3,
+        tolerance: f64,
+    },
+    /// `curve` が `None` なら退化エッジ
+    Edge {
+        curve: Option<GeomCurve>,
+        range: (f64, f64),
+        tolerance: f64,
+    },
+    Wire,
+    /// `pcurves[i][j]` は `i` 番目のワイヤに格納された `j` 番目のエッジの pcurve
+    Face {
+        surface: GeomSurface,
+        pcurves: Vec<Vec<Option<BSplineCurve2>>>,
+        tolerance: f64,
+    },
+    Shell,
+    Solid,
+    Compound,
+}
+
+impl Node {
+    fn kind(&self) -> ShapeKind {
+        match self {
+            Node::Vertex { .. } => ShapeKind::Vertex,
+            Node::Edge { .. } => ShapeKind::Edge,
+            Node::Wire => ShapeKind::Wire,
+            Node::Face { .. } => ShapeKind::Face,
+            Node::Shell => ShapeKind::Shell,
+            Node::Solid => ShapeKind::Solid,
+            Node::Compound => ShapeKind::Compound,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    node: Node,
+    /// 部分形状への参照（エッジは始点・終点、フェイスはワイヤを格納した順）
+    children: Vec<ShapeLink>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: u32,
+    entry: Option<Entry>,
+}
+
+/// 形状の位相と幾何を、世代付きの番号で指し合う配列に格納する保管庫
+///
+/// 頂点・エッジ・フェイスなどの中身（点・曲線・曲面・pcurve・許容誤差）を配列の要素として値で持ち、
+/// 親子関係は部分形状のハンドルと配置・向きの組（[`ShapeLink`]）で持つ。`Arc` の参照を持たないので、
+/// 保管庫の複製は配列の複製だけで済み、ハンドルでたどるときも割り当てをしない。
+/// 中身は値だけなので `Send + Sync` で、ハンドルで部分形状を分けて並列に読める。
+///
+/// [`Shape`] から [`ShapeArena::insert`] で格納し、[`ShapeArena::shape`] で [`Shape`] に戻す。
+#[derive(Debug, Clone, Default)]
+pub struct ShapeArena {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl ShapeArena {
+    /// 空の保管庫を生成する
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 形状とそのすべての部分形状の中身を格納し、形状への参照を返す
+    ///
+    /// 形状の中で共有されている部分形状（`is_partner`）は1つの中身にまとめて格納する。
+    /// 別々に格納した形状どうしは中身を共有しない。
+    pub fn insert(&mut self, shape: &Shape) -> ShapeLink {
+        let mut stored = HashMap::new();
+        ShapeLink {
+            handle: self.insert_stored(&stored_shape(shape), &mut stored),
+            orientation: shape.orientation(),
+            location: shape.location(),
+        }
+    }
+
+    /// 向き `Forward`・恒等な配置の形状の中身を格納する（`stored` は格納済みの中身のハンドル）
+    fn insert_stored(
+        &mut self,
+        shape: &Shape,
+        stored: &mut HashMap<ShapeId, ShapeHandle>,
+    ) -> ShapeHandle {
+        if let Some(&handle) = stored.get(&shape.id()) {
+            return handle;
+        }
+        let children = shape
+            .children()
+            .iter()
+            .map(|child| ShapeLink {
+                handle: self.insert_stored(&stored_shape(child), stored),
+                orientation: child.orientation(),
+                location: child.location(),
+            })
+            .collect();
+        let node = match shape {
+            Shape::Vertex(v) => Node::Vertex {
+                point: v.point(),
+                tolerance: v.tolerance(),
+            },
+            Shape::Edge(e) => Node::Edge {
+                curve: e.curve().cloned(),
+                range: e.range(),
+                tolerance: e.tolerance(),
+            },
+            Shape::Wire(_) => Node::Wire,
+            Shape::Face(f) => Node::Face {
+                surface: f.surface().clone(),
+                pcurves: f.stored_pcurves(),
+                tolerance: f.tolerance(),
+            },
+            Shape::Shell(_) => Node::Shell,
+            Shape::Solid(_) => Node::Solid,
+            Shape::Compound(_) => Node::Compound,
+        };
+        let handle = self.allocate(Entry { node, children });
+        stored.insert(shape.id(), handle);
+        handle
+    }
+
+    fn allocate(&mut self, entry: Entry) -> ShapeHandle {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.entry = Some(entry);
+                ShapeHandle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                ShapeHandle {
+                    index: (self.slots.len() - 1) as u32,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// 格納した中身から形状を組み立てる
+    ///
+    /// 保管庫の中で共有されている中身は、組み立てた形状の中でも共有する。
+    /// 参照が無効なハンドルを含む場合や、中身から形状を作れない場合はエラーを返す。
+    pub fn shape(&self, link: &ShapeLink) -> Result<Shape> {
+        let mut built = HashMap::new();
+        let shape = self.build(link.handle, &mut built)?;
+        Ok(shape.located(link.location).oriented(link.orientation))
+    }
+
+    /// 中身を向き `Forward`・恒等な配置の形状にする（`built` は組み立て済みの形状）
+    fn build(&self, handle: ShapeHandle, built: &mut HashMap<u32, Shape>) -> Result<Shape> {
+        if let Some(shape) = built.get(&handle.index) {
+            return Ok(shape.clone());
+        }
+        let entry = self.entry(handle).ok_or_else(|| {
+            OcctKrsError::InvalidInput("保管庫にない形状のハンドルです".to_string())
+        })?;
+        let mut children = Vec::with_capacity(entry.children.len());
+        for link in &entry.children {
+            let child = self.build(link.handle, built)?;
+            children.push(child.located(link.location).oriented(link.orientation));
+        }
+        let shape: Shape = match &entry.node {
+            Node::Vertex { point, tolerance } => {
+                Vertex::new(*point).with_tolerance(*tolerance)?.into()
+            }
+            Node::Edge {
+                curve,
+                range,
+                tolerance,
+            } => {
+                let vertices = typed::<Vertex>(children)?;
+                let [start, end] = &vertices[..] else {
+                    return Err(OcctKrsError::DegenerateGeometry(
+                        "エッジの頂点が2つではありません".to_string(),
+                    ));
+                };
+                let edge = match curve {
+                    Some(curve) => Edge::new(curve.clone(), *range, start.clone(), end.clone())?,
+                    None => Edge::degenerate(start.clone(), *range)?,
+                };
+                edge.with_tolerance(*tolerance)?.into()
+            }
+            Node::Wire => Wire::new(typed(children)?)?.into(),
+            Node::Face {
+                surface,
+                pcurves,
+                tolerance,
+            } => Face::with_pcurves(surface.clone(), typed(children)?, pcurves.clone())?
+                .with_tolerance(*tolerance)?
+                .into(),
+            Node::Shell => Shell::new(typed(children)?)?.into(),
+            Node::Solid => Solid::new(typed(children)?)?.into(),
+            Node::Compound => Compound::new(children).into(),
+        };
+        built.insert(handle.index, shape.clone());
+        Ok(shape)
+    }
+
+    /// 中身を削除する（ハンドルが無効なら `false`）
+    ///
+    /// 部分形状は削除しない。削除した中身を部分形状に持つ形状の参照は無効なハンドルを指す。
+    pub fn remove(&mut self, handle: ShapeHandle) -> bool {
+        if self.entry(handle).is_none() {
+            return false;
+        }
+        let slot = &mut self.slots[handle.index as usize];
+        slot.entry = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        self.len -= 1;
+        true
+    }
+
+    fn entry(&self, handle: ShapeHandle) -> Option<&Entry> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.entry.as_ref()
+    }
+
+    /// ハンドルが有効なら `true` を返す
+    pub fn contains(&self, handle: ShapeHandle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    /// ハンドルの指す中身の種類を返す
+    pub fn kind(&self, handle: ShapeHandle) -> Option<ShapeKind> {
+        self.entry(handle).map(|e| e.node.kind())
+    }
+
+    /// 部分形状への参照を返す（ハンドルが無効なら空）
+    ///
+    /// エッジの頂点は始点が `Forward`、終点が `Reversed` になる。
+    pub fn children(&self, handle: ShapeHandle) -> &[ShapeLink] {
+        self.entry(handle).map_or(&[], |e| &e.children)
+    }
+
+    /// 頂点の配置を適用する前の位置を返す（頂点でなければ `None`）
+    pub fn point(&self, handle: ShapeHandle) -> Option<Point3> {
+        match self.entry(handle)?.node {
+            Node::Vertex { point, .. } => Some(point),
+            _ => None,
+        }
+    }
+
+    /// エッジの配置を適用する前の曲線を返す（エッジでないか退化エッジなら `None`）
+    pub fn curve(&self, handle: ShapeHandle) -> Option<&GeomCurve> {
+        match &self.entry(handle)?.node {
+            Node::Edge { curve, .. } => curve.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// フェイスの配置を適用する前の曲面を返す（フェイスでなければ `None`）
+    pub fn surface(&self, handle: ShapeHandle) -> Option<&GeomSurface> {
+        match &self.entry(handle)?.node {
+            Node::Face { surface, .. } => Some(surface),
+            _ => None,
+        }
+    }
+
+    /// 頂点・エッジ・フェイスの許容誤差を返す（それ以外は `None`）
+    pub fn tolerance(&self, handle: ShapeHandle) -> Option<f64> {
+        match self.entry(handle)?.node {
+            Node::Vertex { tolerance, .. }
+            | Node::Edge { tolerance, .. }
+            | Node::Face { tolerance, .. } => Some(tolerance),
+            _ => None,
+        }
+    }
+
+    /// 中身に含まれる種類 `kind` の部分形状のハンドルを、重複なく深さ優先の順で返す
+    ///
+    /// 中身自身が `kind` の場合はそれだけを返す。
+    pub fn explore(&self, handle: ShapeHandle, kind: ShapeKind) -> Vec<ShapeHandle> {
+        let mut result = Vec::new();
+        let mut visited = vec![false; self.slots.len()];
+        let mut stack = vec![handle];
+        while let Some(h) = stack.pop() {
+            let Some(entry) = self.entry(h) else {
+                continue;
+            };
+            if std::mem::replace(&mut visited[h.index as usize], true) {
+                continue;
+            }
+            let k = entry.node.kind();
+            if k == kind {
+                result.push(h);
+            } else if k < kind {
+                stack.extend(entry.children.iter().rev().map(|c| c.handle));
+            }
+        }
+        result
+    }
+
+    /// 格納されている中身の数を返す
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 中身が格納されていなければ `true` を返す
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 格納されている中身のハンドルと種類を番号順に返す
+    pub fn iter(&self) -> impl Iterator<Item = (ShapeHandle, ShapeKind)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(i, s)| {
+            s.entry.as_ref().map(|e| {
+                let handle = ShapeHandle {
+                    index: i as u32,
+                    generation: s.generation,
+                };
+                (handle, e.node.kind())
+            })
+        })
+    }
+}
+
+/// 形状の中身を、向き `Forward`・恒等な配置で見たもの
+fn stored_shape(shape: &Shape) -> Shape {
+    shape
+        .located(Location::identity())
+        .oriented(Orientation::Forward)
+}
+
+/// 部分形状を種類 `T` にそろえる
+fn typed<T: TryFrom<Shape, Error = OcctKrsError>>(shapes: Vec<Shape>) -> Result<Vec<T>> {
+    shapes.into_iter().map(T::try_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::test_support::checked_volume;
+    use crate::{Axis2, Dir, Transform, Vector3};
+    use std::collections::HashSet;
+
+    fn square() -> Face {
+        let p = |x: f64, y: f64| Point3::new(x, y, 0.0);
+        let edges = vec![
+            Edge::from_points(p(0.0, 0.0), p(1.0, 0.0)).unwrap(),
+            Edge::from_points(p(1.0, 0.0), p(1.0, 1.0)).unwrap(),
+            Edge::from_points(p(1.0, 1.0), p(0.0, 1.0)).unwrap(),
+            Edge::from_points(p(0.0, 1.0), p(0.0, 0.0)).unwrap(),
+        ];
+        let wire = Wire::from_edges(edges, 1e-9).unwrap();
+        Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap()
+    }
+
+    #[test]
+    fn test_arena_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Shape>();
+        assert_send_sync::<ShapeArena>();
+        assert_send_sync::<ShapeLink>();
+    }
+
+    #[test]
+    fn test_insert_shares_sub_shapes() {
+        let face = Shape::from(square());
+        let compound = Shape::from(Compound::new(vec![face.clone(), face.reversed()]));
+        let mut arena = ShapeArena::new();
+        let root = arena.insert(&compound).handle();
+        // 複合形状・フェイス・ワイヤ・4本のエッジ・4個の頂点
+        assert_eq!(arena.len(), 11);
+        let links = arena.children(root);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].handle(), links[1].handle());
+        assert_eq!(links[0].orientation(), Orientation::Forward);
+        assert_eq!(links[1].orientation(), Orientation::Reversed);
+        let f = links[0].handle();
+        assert_eq!(arena.explore(root, ShapeKind::Edge).len(), 4);
+        assert_eq!(arena.explore(f, ShapeKind::Face), vec![f]);
+        let points: Vec<Point3> = arena
+            .explore(root, ShapeKind::Vertex)
+            .iter()
+            .filter_map(|&h| arena.point(h))
+            .collect();
+        assert_eq!(points.len(), 4);
+        assert!(points.contains(&Point3::new(1.0, 1.0, 0.0)));
+        assert!(arena.surface(f).is_some() && arena.curve(f).is_none());
+        assert_eq!(arena.iter().count(), 11);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let position = Axis2::new(Point3::new(0.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cube = Shape::from(make_box(position, 1.0, 2.0, 3.0).unwrap());
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let moved = cylinder.moved(&Transform::from_translation(Vector3::new(5.0, 0.0, 0.0)));
+        let mut arena = ShapeArena::new();
+        let links: Vec<ShapeLink> = [&cube, &moved].iter().map(|s| arena.insert(s)).collect();
+        // 保管庫の複製は元の保管庫と独立している
+        let copy = arena.clone();
+        arena.remove(links[0].handle());
+        for (link, original) in links.iter().zip([&cube, &moved]) {
+            let shape = copy.shape(link).unwrap();
+            assert_eq!(shape.location(), original.location());
+            assert_eq!(shape.faces().len(), original.faces().len());
+            let edges = |s: &Shape| s.explore(ShapeKind::Edge).unique().count();
+            assert_eq!(edges(&shape), edges(original));
+            let volume = checked_volume(&shape);
+            assert!(
+                (volume - checked_volume(original)).abs() < 1e-12,
+                "{}",
+                volume
+            );
+            let bounds = |s: &Shape| {
+                s.explore(ShapeKind::Vertex)
+                    .filter_map(|v| Vertex::try_from(v).ok())
+                    .map(|v| v.point())
+                    .fold(f64::NEG_INFINITY, |m, p| m.max(p.x))
+            };
+            assert_eq!(bounds(&shape), bounds(original));
+        }
+        assert!(arena.shape(&links[0]).is_err());
+        let ids: HashSet<ShapeHandle> = copy.iter().map(|(h, _)| h).collect();
+        assert_eq!(ids.len(), copy.len());
+    }
+
+    #[test]
+    fn test_removed_handle_is_stale() {
+        let face = Shape::from(square());
+        let mut arena = ShapeArena::new();
+        let f = arena.insert(&face).handle();
+        assert!(arena.remove(f));
+        assert!(!arena.contains(f) && arena.kind(f).is_none() && !arena.remove(f));
+        assert!(arena.children(f).is_empty());
+        assert_eq!(arena.len(), 9);
+        // 番号は再利用されるが、古いハンドルとは区別される
+        let again = arena.insert(&face).handle();
+        assert_ne!(again, f);
+        assert_eq!(arena.kind(again), Some(ShapeKind::Face));
+        assert_eq!(arena.len(), 19);
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::HashMap;
+
+use crate::precision;
+use crate::topo::{check_tolerance, ShapeId};
+use crate::{
+    project_curve_onto_surface, BSplineCurve2, Curve3, Dir, Edge, Face, GeomCurve, GeomSurface,
+    Line, OcctKrsError, Orientation, Plane, Point3, Result, Shape, ShapeKind, Shell, Solid,
+    Surface, TrimmedCurve, Vector2, Vector3, Vertex, Wire,
+};
+
+/// 平面性や穴の向きを調べるときの、曲線のエッジ1本あたりの分割数
+const EDGE_SAMPLES: usize = 8;
+
+impl Edge {
+    /// 曲線 `curve` のパラメータ範囲全体からエッジを生成する（OCCT の `BRepBuilderAPI_MakeEdge` 相当）
+    /// 範囲が有限でない場合はエラーを返す
+    pub fn from_curve(curve: impl Into<GeomCurve>) -> Result<Self> {
+        let curve = curve.into();
+        let range = (curve.first_parameter(), curve.last_parameter());
+        Self::from_curve_range(curve, range)
+    }
+
+    /// 曲線 `curve` のパラメータ区間 `range` からエッジを生成する
+    ///
+    /// 区間の両端に頂点を作り、両端が `precision::confusion()` 以内なら1つの頂点を共有する閉じたエッジにする。
+    pub fn from_curve_range(curve: impl Into<GeomCurve>, range: (f64, f64)) -> Result<Self> {
+        let curve = curve.into();
+        let (first, last) = range;
+        if !(first.is_finite() && last.is_finite() && first < last) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "エッジのパラメータ区間が不正です: [{}, {}]",
+                first, last
+            )));
+        }
+        let (p, q) = (curve.point_at(first), curve.point_at(last));
+        let start = Vertex::new(p);
+        let end = if p.distance(q) <= precision::confusion() {
+            start.clone()
+        } else {
+            Vertex::new(q)
+        };
+        Self::new(curve, range, start, end)
+    }
+
+    /// 2点を結ぶ線分のエッジを生成する（パラメータは始点からの距離）
+    /// 2点が `precision::confusion()` 以内の場合はエラーを返す
+    pub fn from_points(a: Point3, b: Point3) -> Result<Self> {
+        let length = a.distance(b);
+        if length <= precision::confusion() {
+            return Err(OcctKrsError::DegenerateGeometry(format!(
+                "線分の端点が一致しています: {:?}",
+                a
+            )));
+        }
+        Self::from_curve_range(Line::from_points(a, b)?, (0.0, length))
+    }
+
+    /// 頂点を `start`, `end`（向きに沿ってたどったときの始点・終点）に置き換えたエッジを返す
+    /// 曲線・パラメータ区間・許容誤差・向きはそのまま引き継ぐ
+    pub(crate) fn with_vertices(&self, start: Vertex, end: Vertex) -> Result<Edge> {
+        let edge = match self.located_geometry() {
+            None => Edge::degenerate(start, self.range())?,
+            Some(curve) => {
+                let (a, b) = if self.orientation() == Orientation::Reversed {
+                    (end, start)
+                } else {
+                    (start, end)
+                };
+                Edge::new(curve.into_owned(), self.range(), a, b)?
+            }
+        };
+        Ok(edge
+            .with_tolerance(self.tolerance())?
+            .oriented(self.orientation()))
+    }
+}
+
+impl Wire {
+    /// たどる順に並べたエッジから、端点を許容誤差 `tolerance` でつないだワイヤを生成する
+    /// （OCCT の `BRepBuilderAPI_MakeWire` 相当）
+    ///
+    /// 前のエッジの終点に近い側が始点になるように各エッジの向きを決め、
+    /// 離れている端点は許容誤差を広げた1つの頂点にまとめる（頂点を置き換えたエッジは作り直す）。
+    /// 最後のエッジの終点が最初のエッジの始点から `tolerance` 以内なら閉じたワイヤにする。
+    /// エッジが空の場合や、隣り合うエッジの端点が `tolerance` より離れている場合はエラーを返す。
+    pub fn from_edges(edges: Vec<Edge>, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        if edges.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "ワイヤには1本以上のエッジが必要です".to_string(),
+            ));
+        }
+        let n = edges.len();
+        let mut oriented: Vec<Edge> = Vec::with_capacity(n);
+        for (i, edge) in edges.iter().enumerate() {
+            let edge = match oriented.last() {
+                // 最初のエッジは次のエッジに近い側を終点にする
+                None if n > 1 => {
+                    let next = &edges[1];
+                    let gap = |p: Point3| {
+                        p.distance(next.start_point())
+                            .min(p.distance(next.end_point()))
+                    };
+                    if gap(edge.start_point()) < gap(edge.end_point()) {
+                        edge.reversed()
+                    } else {
+                        edge.clone()
+                    }
+                }
+                None => edge.clone(),
+                Some(prev) => {
+                    let p = prev.end_point();
+                    let (ds, de) = (p.distance(edge.start_point()), p.distance(edge.end_point()));
+                    if ds.min(de) > tolerance {
+                        return Err(OcctKrsError::InvalidInput(format!(
+                            "{} 番目のエッジが前のエッジの終点から {} 離れていて、つながりません（許容誤差 {}）",
+                            i,
+                            ds.min(de),
+                            tolerance
+                        )));
+                    }
+                    if de < ds {
+                        edge.reversed()
+                    } else {
+                        edge.clone()
+                    }
+                }
+            };
+            oriented.push(edge);
+        }
+        let closed = oriented[n - 1]
+            .end_point()
+            .distance(oriented[0].start_point())
+            <= tolerance;
+        // junctions[i] は i 番目のエッジの終点と次のエッジの始点をまとめた頂点
+        let mut junctions = Vec::with_capacity(n);
+        for i in 0..n {
+            let end = oriented[i].end_vertex();
+            if i + 1 == n && !closed {
+                junctions.push(end);
+            } else {
+                junctions.push(merge_vertices(&end, &oriented[(i + 1) % n].start_vertex())?);
+            }
+        }
+        let mut result = Vec::with_capacity(n);
+        for (i, edge) in oriented.iter().enumerate() {
+            let start = if i > 0 {
+                junctions[i - 1].clone()
+            } else if closed {
+                junctions[n - 1].clone()
+            } else {
+                edge.start_vertex()
+            };
+            let end = junctions[i].clone();
+            if edge.start_vertex().is_same(&start) && edge.end_vertex().is_same(&end) {
+                result.push(edge.clone());
+            } else {
+                result.push(edge.with_vertices(start, end)?);
+            }
+        }
+        Wire::new(result)
+    }
+}
+
+/// 2つの頂点を1つにまとめる（同じ頂点ならそのまま、そうでなければ両方を含む許容誤差の新しい頂点）
+fn merge_vertices(a: &Vertex, b: &Vertex) -> Result<Vertex> {
+    if a.is_same(b) {
+        return Ok(a.oriented(Orientation::Forward));
+    }
+    let gap = a.point().distance(b.point());
+    Vertex::new(a.point()).with_tolerance(a.tolerance().max(gap + b.tolerance()))
+}
+
+impl Face {
+    /// 同じ平面上にある閉じたワイヤから平面のフェイスを生成する（OCCT の `BRepBuilderAPI_MakeFace` 相当）
+    ///
+    /// 平面の法線は外側のワイヤ `outer` が反時計回りになる向きに取り、穴のワイヤ `inners` は
+    /// 時計回りになるように必要なら反転する。各エッジの pcurve も求めて設定する。
+    /// ワイヤが閉じていない場合や、平面から `tolerance` より離れた点がある場合はエラーを返す。
+    pub fn from_planar_wires(outer: Wire, inners: Vec<Wire>, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        let mut wires = vec![outer];
+        wires.extend(inners);
+        if let Some(i) = wires.iter().position(|w| !w.is_closed()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "{} 番目のワイヤが閉じていません",
+                i
+            )));
+        }
+        let samples: Vec<Vec<Point3>> = wires.iter().map(wire_samples).collect();
+        let plane = fit_plane(&samples[0])?;
+        for (i, points) in samples.iter().enumerate() {
+            let deviation = points
+                .iter()
+                .map(|&p| plane.distance(p))
+                .fold(0.0, f64::max);
+            if deviation > tolerance {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のワイヤが平面上にありません（平面からの距離 {}、許容誤差 {}）",
+                    i, deviation, tolerance
+                )));
+            }
+        }
+        for (wire, points) in wires.iter_mut().zip(&samples).skip(1) {
+            let uv: Vec<Vector2> = points
+                .iter()
+                .map(|&p| {
+                    let (u, v) = plane.parameters_of(p);
+                    Vector2::new(u, v)
+                })
+                .collect();
+            if signed_area(&uv) > 0.0 {
+                *wire = wire.reversed();
+            }
+        }
+        let surface = GeomSurface::from(plane);
+        let pcurves = wires
+            .iter()
+            .map(|w| wire_pcurves(w, &surface, tolerance))
+            .collect::<Result<_>>()?;
+        Face::with_pcurves(surface, wires, pcurves)
+    }
+
+    /// 曲面 `surface` 上にある閉じたワイヤからフェイスを生成する
+    ///
+    /// 外側のワイヤ `outer` は曲面のパラメータ空間で反時計回り（曲面の法線側から見て反時計回り）に、
+    /// 穴のワイヤ `inners` は時計回りになるように必要なら反転する。各エッジの pcurve も求めて設定する。
+    /// ワイヤが閉じていない場合、曲面から `tolerance` より離れた点がある場合、
+    /// ワイヤが曲面の継ぎ目をまたいでパラメータ空間で閉じない場合はエラーを返す。
+    pub fn from_wires_on_surface(
+        surface: impl Into<GeomSurface>,
+        outer: Wire,
+        inners: Vec<Wire>,
+        tolerance: f64,
+    ) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        let surface = surface.into();
+        let mut wires = vec![outer];
+        wires.extend(inners);
+        let mut pcurves = Vec::with_capacity(wires.len());
+        for (i, wire) in wires.iter_mut().enumerate() {
+            if !wire.is_closed() {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のワイヤが閉じていません",
+                    i
+                )));
+            }
+            let deviation = wire_samples(wire)
+                .into_iter()
+                .map(|p| surface.project(p).map_or(f64::INFINITY, |q| q.distance))
+                .fold(0.0, f64::max);
+            if deviation > tolerance {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のワイヤが曲面上にありません（曲面からの距離 {}、許容誤差 {}）",
+                    i, deviation, tolerance
+                )));
+            }
+            let wire_pcurves = wire_pcurves(wire, &surface, tolerance)?;
+            let area = uv_loop_area(wire, &wire_pcurves).ok_or_else(|| {
+                OcctKrsError::InvalidInput(format!(
+                    "{} 番目のワイヤが曲面のパラメータ空間で閉じていません",
+                    i
+                ))
+            })?;
+            // pcurve はエッジごとに格納順で持つので、ワイヤを反転しても並びは変わらない
+            if (i == 0) == (area < 0.0) {
+                *wire = wire.reversed();
+            }
+            pcurves.push(wire_pcurves);
+        }
+        Face::with_pcurves(surface, wires, pcurves)
+    }
+}
+
+/// 閉じたワイヤからフェイスを生成する（OCCT の `BRepBuilderAPI_MakeFace(wire)` 相当）
+///
+/// `support` が `None` ならワイヤが乗る平面を求めて [`Face::from_planar_wires`] で、
+/// 曲面を与えた場合はその上のフェイスとして [`Face::from_wires_on_surface`] で生成する。
+/// どちらも外側のワイヤ `outer` を反時計回り、穴 `inners` を時計回りにそろえる。
+pub fn make_face(
+    outer: Wire,
+    inners: Vec<Wire>,
+    support: Option<&GeomSurface>,
+    tolerance: f64,
+) -> Result<Face> {
+    match support {
+        Some(surface) => Face::from_wires_on_surface(surface.clone(), outer, inners, tolerance),
+        None => Face::from_planar_wires(outer, inners, tolerance),
+    }
+}
+
+/// ワイヤをたどった pcurve の点列が囲む符号付き面積（パラメータ空間で閉じていない場合は `None`）
+///
+/// `pcurves` はワイヤに格納された順のエッジの pcurve。
+fn uv_loop_area(wire: &Wire, pcurves: &[Option<BSplineCurve2>]) -> Option<f64> {
+    let mut order: Vec<usize> = (0..pcurves.len()).collect();
+    if wire.orientation() == Orientation::Reversed {
+        order.reverse();
+    }
+    let mut points = Vec::new();
+    let mut gaps = Vec::new();
+    for (edge, index) in wire.edges().iter().zip(order) {
+        let Some(pcurve) = &pcurves[index] else {
+            continue;
+        };
+        let (first, last) = edge.range();
+        let (a, b) = if edge.orientation() == Orientation::Reversed {
+            (last, first)
+        } else {
+            (first, last)
+        };
+        if let Some(&previous) = points.last() {
+            gaps.push(pcurve.point_at(a) - previous);
+        }
+        for k in 0..=EDGE_SAMPLES {
+            let s = k as f64 / EDGE_SAMPLES as f64;
+            points.push(pcurve.point_at(a + (b - a) * s));
+        }
+    }
+    let (&start, &end) = (points.first()?, points.last()?);
+    gaps.push(start - end);
+    // 継ぎ目をまたぐと周期分の大きな飛びが生じる
+    let (min, max) = points.iter().fold(
+        (
+            Vector2::new(f64::INFINITY, f64::INFINITY),
+            Vector2::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+        ),
+        |(lo, hi), p| {
+            (
+                Vector2::new(lo.x.min(p.x), lo.y.min(p.y)),
+                Vector2::new(hi.x.max(p.x), hi.y.max(p.y)),
+            )
+        },
+    );
+    let size = (max - min).length();
+    if gaps.iter().any(|g| g.length() > 0.25 * size) {
+        return None;
+    }
+    Some(signed_area(&points))
+}
+
+/// ワイヤの各エッジの pcurve を、ワイヤに格納された順に求める
+fn wire_pcurves(
+    wire: &Wire,
+    surface: &GeomSurface,
+    tolerance: f64,
+) -> Result<Vec<Option<BSplineCurve2>>> {
+    let mut pcurves = wire
+        .edges()
+        .iter()
+        .map(|e| edge_pcurve(e, surface, tolerance))
+        .collect::<Result<Vec<_>>>()?;
+    // 反転したワイヤはたどる順が格納順と逆になる
+    if wire.orientation() == Orientation::Reversed {
+        pcurves.reverse();
+    }
+    Ok(pcurves)
+}
+
+/// エッジの曲線を曲面のパラメータ空間へ移した pcurve を求める（退化エッジでは `None`）
+///
+/// 平面上の直線と B-スプライン曲線は厳密に、それ以外は射影と近似で求める。
+pub(crate) fn edge_pcurve(
+    edge: &Edge,
+    surface: &GeomSurface,
+    tolerance: f64,
+) -> Result<Option<BSplineCurve2>> {
+    let Some(curve) = edge.located_geometry() else {
+        return Ok(None);
+    };
+    let (first, last) = edge.range();
+    if let GeomSurface::Plane(plane) = surface {
+        let uv = |p: Point3| {
+            let (u, v) = plane.parameters_of(p);
+            Vector2::new(u, v)
+        };
+        match curve.as_ref() {
+            GeomCurve::Line(_) => {
+                let points = vec![uv(edge.point_at(first)), uv(edge.point_at(last))];
+                let knots = vec![first, first, last, last];
+                return BSplineCurve2::from_flat_knots(1, points, knots).map(Some);
+            }
+            GeomCurve::BSpline(c) => {
+                // 平面の座標への写像はアフィンなので、制御点を写せば同じパラメータの曲線になる
+                let points = c.control_points().iter().map(|&p| uv(p)).collect();
+                let pcurve =
+                    BSplineCurve2::from_flat_knots(c.degree(), points, c.flat_knots().to_vec())?;
+                return match c.weights() {
+                    Some(w) => pcurve.with_weights(w.to_vec()).map(Some),
+                    None => Ok(Some(pcurve)),
+                };
+            }
+            _ => {}
+        }
+    }
+    let trimmed = TrimmedCurve::new(curve.into_owned(), first, last)?;
+    let tolerance = tolerance.max(edge.tolerance()).max(precision::confusion());
+    Ok(Some(
+        project_curve_onto_surface(&trimmed, surface, tolerance)?.pcurve,
+    ))
+}
+
+/// ワイヤを向きに沿ってたどった点列を返す（直線のエッジは始点のみ、曲線のエッジは等分点）
+fn wire_samples(wire: &Wire) -> Vec<Point3> {
+    let mut points = Vec::new();
+    for edge in wire.edges() {
+        let Some(curve) = edge.curve() else {
+            continue;
+        };
+        let (first, last) = edge.range();
+        let n = if matches!(curve, GeomCurve::Line(_)) {
+            1
+        } else {
+            EDGE_SAMPLES
+        };
+        for k in 0..n {
+            let s = k as f64 / n as f64;
+            let t = if edge.orientation() == Orientation::Reversed {
+                last + (first - last) * s
+            } else {
+                first + (last - first) * s
+            };
+            points.push(edge.point_at(t));
+        }
+    }
+    points
+}
+
+/// 閉じた点列が反時計回りに見える向きの法線を持つ平面を、ニューエル法で求める
+fn fit_plane(points: &[Point3]) -> Result<Plane> {
+    let origin = points[0];
+    let mut normal = Vector3::ZERO;
+    for (i, &p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        normal += (p - origin).cross(q - origin);
+    }
+    let normal = Dir::from_vector(normal).map_err(|_| {
+        OcctKrsError::DegenerateGeometry("ワイヤが囲む面積がゼロで、平面が定まりません".to_string())
+    })?;
+    Ok(Plane::new(origin, normal))
+}
+
+/// union-find の木で `i` の根を返す（経路を半分に縮める）
+pub(crate) fn union_find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// 閉じた多角形の符号付き面積（反時計回りで正）
+pub(crate) fn signed_area(points: &[Vector2]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum::<f64>()
+        / 2.0
+}
+
+impl Shell {
+    /// 辺を共有してつながったフェイスからシェルを生成する
+    /// フェイスが空の場合や、共有するエッジでつながらないフェイスがある場合はエラーを返す
+    pub fn from_faces(faces: Vec<Face>) -> Result<Self> {
+        let shell = Shell::new(faces)?;
+        let faces = shell.faces();
+        let index: HashMap<ShapeId, usize> =
+            faces.iter().enumerate().map(|(i, f)| (f.id(), i)).collect();
+        let map = Shape::from(shell.clone()).ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+        // フェイスの隣接関係を union-find でまとめる
+        let mut parent: Vec<usize> = (0..faces.len()).collect();
+
+        for (_, adjacent) in map.iter() {
+            for pair in adjacent.windows(2) {
+                let (a, b) = (
+                    union_find_root(&mut parent, index[&pair[0].id()]),
+                    union_find_root(&mut parent, index[&pair[1].id()]),
+                );
+                parent[a] = b;
+            }
+        }
+        let first = union_find_root(&mut parent, 0);
+        if let Some(i) = (1..faces.len()).find(|&i| union_find_root(&mut parent, i) != first) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "{} 番目のフェイスが最初のフェイスと共有するエッジでつながっていません",
+                i
+            )));
+        }
+        Ok(shell)
+    }
+}
+
+impl Solid {
+    /// 閉じたシェル（最初が外側の境界、残りが空洞）から立体を生成する
+    /// シェルが空の場合や、閉じていないシェルがある場合は自由エッジ（1枚のフェイスにしか使われないエッジ）の数を添えてエラーを返す
+    pub fn from_shells(shells: Vec<Shell>) -> Result<Self> {
+        for (i, shell) in shells.iter().enumerate() {
+            if !shell.is_closed() {
+                let map = Shape::from(shell.clone()).ancestor_map(ShapeKind::Edge, ShapeKind::Face);
+                let free = map
+                    .iter()
+                    .filter(|(e, faces)| {
+                        faces.len() == 1
+                            && Edge::try_from((*e).clone()).is_ok_and(|e| !e.is_degenerate())
+                    })
+                    .count();
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のシェルが閉じていません（自由エッジ {} 本）",
+                    i, free
+                )));
+            }
+        }
+        Solid::new(shells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, Circle, Surface};
+
+    fn polygon(points: &[(f64, f64, f64)]) -> Wire {
+        let n = points.len();
+        let edges = (0..n)
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % n]);
+                Edge::from_points(Point3::new(a.0, a.1, a.2), Point3::new(b.0, b.1, b.2)).unwrap()
+            })
+            .collect();
+        Wire::from_edges(edges, 1e-6).unwrap()
+    }
+
+    #[test]
+    fn test_edge_constructors() {
+        let circle = Circle::new(Axis2::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        let e = Edge::from_curve(circle).unwrap();
+        assert!(e.is_closed());
+        let arc = Edge::from_curve_range(circle, (0.0, 1.0)).unwrap();
+        assert!(!arc.is_closed());
+        assert!(Edge::from_curve(Line::new(Point3::ORIGIN, Dir::X)).is_err());
+        let s = Edge::from_points(Point3::ORIGIN, Point3::new(0.0, 3.0, 4.0)).unwrap();
+        assert_eq!(s.range(), (0.0, 5.0));
+        assert_eq!(s.end_point(), Point3::new(0.0, 3.0, 4.0));
+        assert!(Edge::from_points(Point3::ORIGIN, Point3::ORIGIN).is_err());
+    }
+
+    #[test]
+    fn test_wire_connects_within_tolerance() {
+        // 向きがばらばらで、端点がわずかに離れたエッジ
+        let p = |x: f64, y: f64| Point3::new(x, y, 0.0);
+        let edges = vec![
+            Edge::from_points(p(1.0, 0.0), p(0.0, 0.0)).unwrap(),
+            Edge::from_points(p(1.0, 0.0), p(1.0, 1.0)).unwrap(),
+            Edge::from_points(p(0.0, 1.0), p(1.0, 1.0 + 1e-5)).unwrap(),
+            Edge::from_points(p(0.0, 1.0), p(0.0, 0.0)).unwrap(),
+        ];
+        let wire = Wire::from_edges(edges.clone(), 1e-4).unwrap();
+        assert!(wire.is_closed());
+        let e = wire.edges();
+        assert_eq!(e[0].start_point(), p(0.0, 0.0));
+        assert_eq!(e[2].orientation(), Orientation::Reversed);
+        assert!(e[1].end_vertex().is_same(&e[2].start_vertex()));
+        assert!(e[1].end_vertex().tolerance() >= 1e-5);
+        // 頂点を置き換える必要のないエッジはそのまま共有される
+        let again = Wire::from_edges(e.clone(), 1e-4).unwrap().edges();
+        assert!(again.iter().zip(&e).all(|(a, b)| a.is_same(b)));
+
+        let err = Wire::from_edges(edges.clone(), 1e-6).unwrap_err();
+        assert!(err.to_string().contains("2 番目"), "{}", err);
+        let open = Wire::from_edges(edges[..3].to_vec(), 1e-4).unwrap();
+        assert!(!open.is_closed());
+        assert!(Wire::from_edges(Vec::new(), 1e-4).is_err());
+    }
+
+    #[test]
+    fn test_planar_face_with_hole() {
+        let outer = polygon(&[
+            (0.0, 0.0, 1.0),
+            (4.0, 0.0, 1.0),
+            (4.0, 4.0, 1.0),
+            (0.0, 4.0, 1.0),
+        ]);
+        // 穴は外側と同じ向きで渡しても時計回りに直される
+        let hole = polygon(&[
+            (1.0, 1.0, 1.0),
+            (2.0, 1.0, 1.0),
+            (2.0, 2.0, 1.0),
+            (1.0, 2.0, 1.0),
+        ]);
+        let face = Face::from_planar_wires(outer.clone(), vec![hole], 1e-7).unwrap();
+        let normal = face.surface().normal_at(0.0, 0.0).unwrap();
+        assert!(normal.dot(Dir::Z) > 1.0 - 1e-12);
+        assert_eq!(face.inner_wires()[0].orientation(), Orientation::Reversed);
+        for wire in face.wires() {
+            for edge in wire.edges() {
+                let pcurve = face.pcurve(&edge).unwrap();
+                let (first, last) = edge.range();
+                for t in [first, (first + last) / 2.0, last] {
+                    let uv = pcurve.point_at(t);
+                    let p = face.surface().point_at(uv.x, uv.y);
+                    assert!(p.distance(edge.point_at(t)) < 1e-12);
+                }
+            }
+        }
+
+        // 円のワイヤ: pcurve は射影で近似する
+        let circle =
+            Circle::new(Axis2::from_normal(Point3::new(0.0, 0.0, 2.0), Dir::X), 1.5).unwrap();
+        let disk = Wire::from_edges(vec![Edge::from_curve(circle).unwrap()], 1e-7).unwrap();
+        let face = Face::from_planar_wires(disk, Vec::new(), 1e-7).unwrap();
+        let edge = face.outer_wire().unwrap().edges()[0].clone();
+        let uv = face.pcurve(&edge).unwrap().point_at(2.0);
+        assert!(
+            face.surface()
+                .point_at(uv.x, uv.y)
+                .distance(circle.point_at(2.0))
+                < 1e-6
+        );
+
+        let skew = polygon(&[
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (1.0, 1.0, 0.5),
+            (0.0, 1.0, 0.0),
+        ]);
+        assert!(Face::from_planar_wires(skew, Vec::new(), 1e-3).is_err());
+        let line = Wire::from_edges(
+            vec![Edge::from_points(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)).unwrap()],
+            1e-7,
+        )
+        .unwrap();
+        assert!(Face::from_planar_wires(line, Vec::new(), 1e-7).is_err());
+    }
+
+    #[test]
+    fn test_face_on_cylinder_with_hole() {
+        use crate::{Axis3, CylindricalSurface};
+        let axis = Axis3::from_normal(Point3::ORIGIN, Dir::Z);
+        let cylinder = GeomSurface::from(CylindricalSurface::new(axis, 1.0).unwrap());
+        // 円柱面上で角度 [a0, a1]、高さ [z0, z1] の範囲を囲むワイヤ
+        let patch = |a0: f64, a1: f64, z0: f64, z1: f64| {
+            let point = |a: f64, z: f64| Point3::new(a.cos(), a.sin(), z);
+            let arc = |z: f64| {
+                let circle = Circle::new(Axis2::from_normal(Point3::new(0.0, 0.0, z), Dir::Z), 1.0);
+                Edge::from_curve_range(circle.unwrap(), (a0, a1)).unwrap()
+            };
+            let edges = vec![
+                arc(z0),
+                Edge::from_points(point(a1, z0), point(a1, z1)).unwrap(),
+                arc(z1),
+                Edge::from_points(point(a0, z1), point(a0, z0)).unwrap(),
+            ];
+            Wire::from_edges(edges, 1e-7).unwrap()
+        };
+        let outer = patch(0.5, 2.0, 0.0, 2.0);
+        // 穴は外側と同じ向きで渡しても時計回りに直される
+        let hole = patch(1.0, 1.5, 0.5, 1.5);
+        let face = make_face(outer, vec![hole], Some(&cylinder), 1e-7).unwrap();
+        assert_eq!(face.wires().len(), 2);
+        let report = Shape::from(face.clone()).check();
+        assert!(report.is_valid(), "{:?}", report.issues());
+        let areas: Vec<f64> = face
+            .wires()
+            .iter()
+            .map(|w| {
+                let mut stored: Vec<_> =
+                    w.edges().iter().map(|e| face.pcurve(e).cloned()).collect();
+                if w.orientation() == Orientation::Reversed {
+                    stored.reverse();
+                }
+                uv_loop_area(w, &stored).unwrap()
+            })
+            .collect();
+        assert!(areas[0] > 0.0 && areas[1] < 0.0, "{:?}", areas);
+
+        // 曲面から離れたワイヤと、継ぎ目をまたいで一周するワイヤ
+        let off = polygon(&[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0)]);
+        assert!(make_face(off, Vec::new(), Some(&cylinder), 1e-7).is_err());
+        let around = patch(0.0, 2.0 * std::f64::consts::PI, 0.0, 1.0);
+        assert!(make_face(around, Vec::new(), Some(&cylinder), 1e-7).is_err());
+        // 曲面を与えなければ平面のフェイスになる
+        let square = polygon(&[
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (1.0, 1.0, 0.0),
+            (0.0, 1.0, 0.0),
+        ]);
+        let planar = make_face(square, Vec::new(), None, 1e-7).unwrap();
+        assert!(matches!(planar.surface(), GeomSurface::Plane(_)));
+    }
+
+    #[test]
+    fn test_shell_and_solid_assembly() {
+        // 8頂点・12エッジを共有する立方体
+        let v: Vec<Vertex> = (0..8)
+            .map(|i| {
+                Vertex::new(Point3::new(
+                    (i & 1) as f64,
+                    ((i >> 1) & 1) as f64,
+                    ((i >> 2) & 1) as f64,
+                ))
+            })
+            .collect();
+        let mut edges: HashMap<(usize, usize), Edge> = HashMap::new();
+        let mut edge = |a: usize, b: usize| {
+            let key = (a.min(b), a.max(b));
+            let e = edges
+                .entry(key)
+                .or_insert_with(|| {
+                    let (p, q) = (v[key.0].point(), v[key.1].point());
+                    Edge::new(
+                        Line::from_points(p, q).unwrap(),
+                        (0.0, 1.0),
+                        v[key.0].clone(),
+                        v[key.1].clone(),
+                    )
+                    .unwrap()
+                })
+                .clone();
+            if a < b {
+                e
+            } else {
+                e.reversed()
+            }
+        };
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let faces: Vec<Face> = quads
+            .iter()
+            .map(|q| {
+                let wire = Wire::new((0..4).map(|i| edge(q[i], q[(i + 1) % 4])).collect()).unwrap();
+                Face::from_planar_wires(wire, Vec::new(), 1e-7).unwrap()
+            })
+            .collect();
+        let shell = Shell::from_faces(faces.clone()).unwrap();
+        assert!(Solid::from_shells(vec![shell]).is_ok());
+
+        let open = Shell::from_faces(faces[..5].to_vec()).unwrap();
+        let err = Solid::from_shells(vec![open]).unwrap_err();
+        assert!(err.to_string().contains("自由エッジ 4 本"), "{}", err);
+        // 向かい合う2面は辺を共有しない
+        assert!(Shell::from_faces(vec![faces[0].clone(), faces[1].clone()]).is_err());
+    }
+}
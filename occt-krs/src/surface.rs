@@ -0,0 +1,912 @@
+use crate::elementary_surface::radial;
+use crate::precision;
+use crate::projection::{
+    nearest_from_seeds, project_onto_surface, surface_projection_at, SurfaceProjection,
+};
+use crate::{
+    Axis3, BSplineSurface, BoundingBox, ConicalSurface, Curve3, CylindricalSurface, Dir,
+    ExtrudedSurface, GeomSurface, OffsetSurface, Plane, Point3, RevolvedSurface, RuledSurface,
+    SphericalSurface, ToroidalSurface, TrimmedSurface, Vector3,
+};
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+/// 曲面上の点における曲率（OCCT の `GeomLProp_SLProps` 相当）
+///
+/// 曲率の符号は `normal_at` の法線を基準とし、法線の側へ曲がる場合に正となる
+/// （外向きの法線を持つ球面では負）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceCurvature {
+    /// 最大主曲率
+    pub max_curvature: f64,
+    /// 最小主曲率
+    pub min_curvature: f64,
+    /// 最大主曲率の方向
+    pub max_direction: Dir,
+    /// 最小主曲率の方向
+    pub min_direction: Dir,
+    /// ガウス曲率（主曲率の積）
+    pub gaussian: f64,
+    /// 平均曲率（主曲率の平均）
+    pub mean: f64,
+}
+
+/// 解析的に扱える曲面（OCCT の `GeomAbs_SurfaceType` 相当）
+///
+/// 交差計算などで曲面の種類に応じた厳密な計算を行うために用いる。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalyticSurface {
+    Plane(Plane),
+    Cylinder(CylindricalSurface),
+    Sphere(SphericalSurface),
+    Cone(ConicalSurface),
+    Torus(ToroidalSurface),
+}
+
+/// 3次元のパラメトリック曲面（OCCT の `Geom_Surface` 相当）
+///
+/// テッセレーション・射影・交差などのアルゴリズムは `&dyn Surface` を受け取るので、
+/// 利用側で独自の曲面を実装して渡すこともできる。
+pub trait Surface {
+    /// パラメータ `(u, v)` における点を返す
+    fn point_at(&self, u: f64, v: f64) -> Point3;
+
+    /// パラメータ `(u, v)` における u 方向の1階偏微分を返す
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3;
+
+    /// パラメータ `(u, v)` における v 方向の1階偏微分を返す
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3;
+
+    /// パラメータ `(u, v)` における2階偏微分 `(Suu, Suv, Svv)` を返す
+    ///
+    /// 既定の実装は1階偏微分の中心差分による近似。
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let (hu, hv) = (difference_step(u), difference_step(v));
+        let suu = (self.derivative_u_at(u + hu, v) - self.derivative_u_at(u - hu, v)) / (2.0 * hu);
+        let suv = (self.derivative_u_at(u, v + hv) - self.derivative_u_at(u, v - hv)) / (2.0 * hv);
+        let svv = (self.derivative_v_at(u, v + hv) - self.derivative_v_at(u, v - hv)) / (2.0 * hv);
+        (suu, suv, svv)
+    }
+
+    /// パラメータ `(u, v)` における単位法線 `Su × Sv` を返す
+    /// 偏微分が平行またはゼロ（特異点）の場合は `None` を返す
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        Dir::from_vector(self.derivative_u_at(u, v).cross(self.derivative_v_at(u, v))).ok()
+    }
+
+    /// u のパラメータ範囲 `(始まり, 終わり)` を返す（無限に延びる場合は無限大）
+    fn u_range(&self) -> (f64, f64);
+
+    /// v のパラメータ範囲 `(始まり, 終わり)` を返す（無限に延びる場合は無限大）
+    fn v_range(&self) -> (f64, f64);
+
+    /// u 方向に閉じている（u の始まりと終わりの v 等パラメータ線が一致する）場合は `true` を返す
+    fn is_u_closed(&self) -> bool {
+        let ((u0, u1), (v0, v1)) = (self.u_range(), self.v_range());
+        boundaries_match(u0, u1, v0, v1, |a, b| self.point_at(a, b))
+    }
+
+    /// v 方向に閉じている（v の始まりと終わりの u 等パラメータ線が一致する）場合は `true` を返す
+    fn is_v_closed(&self) -> bool {
+        let ((u0, u1), (v0, v1)) = (self.u_range(), self.v_range());
+        boundaries_match(v0, v1, u0, u1, |a, b| self.point_at(b, a))
+    }
+
+    /// パラメータ `(u, v)` における主曲率・主方向・ガウス曲率・平均曲率を返す
+    /// 法線が定まらない点では `None` を返す
+    ///
+    /// 臍点（主曲率が等しい点）では主方向が定まらないので、u 方向を最大主曲率の方向とする。
+    fn curvature_at(&self, u: f64, v: f64) -> Option<SurfaceCurvature> {
+        let normal = self.normal_at(u, v)?;
+        let (su, sv) = (self.derivative_u_at(u, v), self.derivative_v_at(u, v));
+        let (suu, suv, svv) = self.second_derivatives_at(u, v);
+        let n = normal.to_vector();
+        // 第1基本形式と第2基本形式の係数
+        let (e, f, g) = (su.dot(su), su.dot(sv), sv.dot(sv));
+        let (l, m, nn) = (suu.dot(n), suv.dot(n), svv.dot(n));
+        let det = e * g - f * f;
+        if det <= f64::MIN_POSITIVE {
+            return None;
+        }
+        let gaussian = (l * nn - m * m) / det;
+        let mean = (e * nn - 2.0 * f * m + g * l) / (2.0 * det);
+        let root = (mean * mean - gaussian).max(0.0).sqrt();
+        let (max_curvature, min_curvature) = (mean + root, mean - root);
+
+        // 最大主曲率の方向 (du, dv) は (II - k I) の零空間
+        let k = max_curvature;
+        let rows = [(l - k * e, m - k * f), (m - k * f, nn - k * g)];
+        let (a, b) = if rows[0].0.hypot(rows[0].1) >= rows[1].0.hypot(rows[1].1) {
+            rows[0]
+        } else {
+            rows[1]
+        };
+        let umbilic = root <= 1e-10 * (1.0 + mean.abs());
+        let direction = if umbilic { su } else { su * (-b) + sv * a };
+        let max_direction = Dir::from_vector(direction - n * direction.dot(n))
+            .or_else(|_| Dir::from_vector(su))
+            .ok()?;
+        let min_direction = normal.crossed(max_direction).ok()?;
+        Some(SurfaceCurvature {
+            max_curvature,
+            min_curvature,
+            max_direction,
+            min_direction,
+            gaussian,
+            mean,
+        })
+    }
+
+    /// 点に最も近い曲面上の点を返す
+    ///
+    /// 既定の実装はパラメータ範囲を格子状に分割して初期値を探し、ニュートン法で精密化する。
+    /// 閉じた方向では継ぎ目をまたいで探索する。パラメータ範囲が無限の場合は `None` を返す。
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        project_onto_surface(self, p)
+    }
+
+    /// 解析的に扱える曲面であればその種類と形状を返す（既定は `None`）
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        None
+    }
+
+    /// 曲面を囲む境界ボックスを返す
+    ///
+    /// 既定の実装はパラメータ範囲を格子状に分割した点から求める近似で、厳密に曲面全体を含むとは限らない。
+    fn bounding_box(&self) -> BoundingBox {
+        let ((u0, u1), (v0, v1)) = (self.u_range(), self.v_range());
+        if ![u0, u1, v0, v1].iter().all(|x| x.is_finite()) {
+            return BoundingBox::infinite();
+        }
+        BoundingBox::from_points(tessellate_grid(self, 16, 16).into_iter().flatten()).unwrap()
+    }
+}
+
+/// パラメータ範囲を `nu` × `nv` 等分した格子点を返す（`result[i][j]` は u の i 番目、v の j 番目）
+/// 分割数が 0 の方向は範囲の始まりのみを返す
+pub fn tessellate_grid(
+    surface: &(impl Surface + ?Sized),
+    nu: usize,
+    nv: usize,
+) -> Vec<Vec<Point3>> {
+    let ((u0, u1), (v0, v1)) = (surface.u_range(), surface.v_range());
+    let at = |a: f64, b: f64, i: usize, n: usize| {
+        if n == 0 {
+            a
+        } else {
+            a + (b - a) * i as f64 / n as f64
+        }
+    };
+    (0..=nu)
+        .map(|i| {
+            let u = at(u0, u1, i, nu);
+            (0..=nv)
+                .map(|j| surface.point_at(u, at(v0, v1, j, nv)))
+                .collect()
+        })
+        .collect()
+}
+
+/// 一方のパラメータの始まり `a0` と終わり `a1` で、もう一方のパラメータ `[b0, b1]` に沿った点が一致するか調べる
+/// `[b0, b1]` が無限の場合は有限な部分区間で調べる
+fn boundaries_match(
+    a0: f64,
+    a1: f64,
+    b0: f64,
+    b1: f64,
+    point_at: impl Fn(f64, f64) -> Point3,
+) -> bool {
+    if !(a0.is_finite() && a1.is_finite()) {
+        return false;
+    }
+    let (b0, b1) = match (b0.is_finite(), b1.is_finite()) {
+        (true, true) => (b0, b1),
+        (true, false) => (b0, b0 + 1.0),
+        (false, true) => (b1 - 1.0, b1),
+        (false, false) => (-1.0, 1.0),
+    };
+    (0..=8).all(|k| {
+        let b = b0 + (b1 - b0) * k as f64 / 8.0;
+        point_at(a0, b).distance(point_at(a1, b)) <= precision::confusion()
+    })
+}
+
+/// 中心差分の刻み幅（パラメータの大きさに応じて調整する）
+fn difference_step(t: f64) -> f64 {
+    1e-4 * t.abs().max(1.0)
+}
+
+impl Surface for Plane {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        Plane::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, _u: f64, _v: f64) -> Vector3 {
+        self.position().x_direction().to_vector()
+    }
+
+    fn derivative_v_at(&self, _u: f64, _v: f64) -> Vector3 {
+        self.position().y_direction().to_vector()
+    }
+
+    fn second_derivatives_at(&self, _u: f64, _v: f64) -> (Vector3, Vector3, Vector3) {
+        (Vector3::ZERO, Vector3::ZERO, Vector3::ZERO)
+    }
+
+    fn normal_at(&self, _u: f64, _v: f64) -> Option<Dir> {
+        Some(self.normal())
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        let (u, v) = self.parameters_of(p);
+        Some(surface_projection_at(self, p, u, v))
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        Some(AnalyticSurface::Plane(*self))
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+/// 座標系が右手系なら 1、左手系なら -1 を返す（法線 `Su × Sv` の向きの補正に用いる）
+fn orientation(position: &Axis3) -> f64 {
+    if position.is_direct() {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+impl Surface for CylindricalSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        CylindricalSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, _v: f64) -> Vector3 {
+        radial(&self.position(), u).1 * self.radius()
+    }
+
+    fn derivative_v_at(&self, _u: f64, _v: f64) -> Vector3 {
+        self.position().direction().to_vector()
+    }
+
+    fn second_derivatives_at(&self, u: f64, _v: f64) -> (Vector3, Vector3, Vector3) {
+        let (e, _) = radial(&self.position(), u);
+        (-e * self.radius(), Vector3::ZERO, Vector3::ZERO)
+    }
+
+    fn normal_at(&self, u: f64, _v: f64) -> Option<Dir> {
+        let (e, _) = radial(&self.position(), u);
+        Some(Dir::new_unchecked(e * orientation(&self.position())))
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        let (u, v) = self.parameters_of(p);
+        Some(surface_projection_at(self, p, u, v))
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        Some(AnalyticSurface::Cylinder(*self))
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl Surface for SphericalSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        SphericalSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        radial(&self.position(), u).1 * (self.radius() * v.cos())
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        (self.position().direction().to_vector() * c - e * s) * self.radius()
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let (e, de) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        let r = self.radius();
+        let z = self.position().direction().to_vector();
+        (-e * (r * c), -de * (r * s), -(e * c + z * s) * r)
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        // 極でも放射方向を法線とする
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        let n = e * c + self.position().direction().to_vector() * s;
+        Some(Dir::new_unchecked(n * orientation(&self.position())))
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        let (u, v) = self.parameters_of(p);
+        Some(surface_projection_at(self, p, u, v))
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        Some(AnalyticSurface::Sphere(*self))
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (-FRAC_PI_2, FRAC_PI_2)
+    }
+}
+
+impl Surface for ConicalSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        ConicalSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        radial(&self.position(), u).1 * self.radius_at(v)
+    }
+
+    fn derivative_v_at(&self, u: f64, _v: f64) -> Vector3 {
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = self.semi_angle().sin_cos();
+        e * s + self.position().direction().to_vector() * c
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let (e, de) = radial(&self.position(), u);
+        (
+            -e * self.radius_at(v),
+            de * self.semi_angle().sin(),
+            Vector3::ZERO,
+        )
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        // 頂点では法線が定まらない
+        let r = self.radius_at(v);
+        if r.abs() <= precision::confusion() {
+            return None;
+        }
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = self.semi_angle().sin_cos();
+        let n = e * c - self.position().direction().to_vector() * s;
+        Some(Dir::new_unchecked(
+            n * (r.signum() * orientation(&self.position())),
+        ))
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        // 軸と点を含む平面で切った2本の母線のうち近い方へ射影する
+        let (u, _) = self.parameters_of(p);
+        [u, (u + PI) % TAU]
+            .into_iter()
+            .map(|u| {
+                let v = (p - self.point_at(u, 0.0)).dot(self.derivative_v_at(u, 0.0));
+                surface_projection_at(self, p, u, v)
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        Some(AnalyticSurface::Cone(*self))
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl Surface for ToroidalSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        ToroidalSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        radial(&self.position(), u).1 * (self.major_radius() + self.minor_radius() * v.cos())
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        (self.position().direction().to_vector() * c - e * s) * self.minor_radius()
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let (e, de) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        let r = self.minor_radius();
+        let z = self.position().direction().to_vector();
+        (
+            -e * (self.major_radius() + r * c),
+            -de * (r * s),
+            -(e * c + z * s) * r,
+        )
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        let (e, _) = radial(&self.position(), u);
+        let (s, c) = v.sin_cos();
+        let n = e * c + self.position().direction().to_vector() * s;
+        Some(Dir::new_unchecked(n * orientation(&self.position())))
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        let (u, v) = self.parameters_of(p);
+        Some(surface_projection_at(self, p, u, v))
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        Some(AnalyticSurface::Torus(*self))
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+}
+
+impl Surface for BSplineSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        BSplineSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        self.derivatives_at(u, v, 1)[1][0]
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        self.derivatives_at(u, v, 1)[0][1]
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let d = self.derivatives_at(u, v, 2);
+        (d[2][0], d[1][1], d[0][2])
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        self.u_bounds()
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        self.v_bounds()
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        // 凸包性により制御点を囲む箱は曲面全体を含む
+        BoundingBox::from_points(self.control_points().iter().flatten().copied()).unwrap()
+    }
+}
+
+impl<C1: Curve3, C2: Curve3> Surface for RuledSurface<C1, C2> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        RuledSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        let (s, k) = self.second_parameter(u);
+        let a = self.first_curve().derivative_at(u);
+        a + (self.second_curve().derivative_at(s) * k - a) * v
+    }
+
+    fn derivative_v_at(&self, u: f64, _v: f64) -> Vector3 {
+        let (s, _) = self.second_parameter(u);
+        self.second_curve().point_at(s) - self.first_curve().point_at(u)
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let (s, k) = self.second_parameter(u);
+        let (a1, b1) = (
+            self.first_curve().derivative_at(u),
+            self.second_curve().derivative_at(s) * k,
+        );
+        let (a2, b2) = (
+            self.first_curve().second_derivative_at(u),
+            self.second_curve().second_derivative_at(s) * (k * k),
+        );
+        (a2 + (b2 - a2) * v, b1 - a1, Vector3::ZERO)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (
+            self.first_curve().first_parameter(),
+            self.first_curve().last_parameter(),
+        )
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+impl<C: Curve3> Surface for RevolvedSurface<C> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        RevolvedSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        let w = self.basis_curve().point_at(v) - self.axis().location;
+        self.rotate_derivative(w, u)
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        self.rotate(self.basis_curve().derivative_at(v), u)
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        let w = self.basis_curve().point_at(v) - self.axis().location;
+        let suu = self.rotate_second_derivative(w, u);
+        let suv = self.rotate_derivative(self.basis_curve().derivative_at(v), u);
+        let svv = self.rotate(self.basis_curve().second_derivative_at(v), u);
+        (suu, suv, svv)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (0.0, TAU)
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (
+            self.basis_curve().first_parameter(),
+            self.basis_curve().last_parameter(),
+        )
+    }
+}
+
+impl<C: Curve3> Surface for ExtrudedSurface<C> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        ExtrudedSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, _v: f64) -> Vector3 {
+        self.basis_curve().derivative_at(u)
+    }
+
+    fn derivative_v_at(&self, _u: f64, _v: f64) -> Vector3 {
+        self.direction().to_vector()
+    }
+
+    fn second_derivatives_at(&self, u: f64, _v: f64) -> (Vector3, Vector3, Vector3) {
+        (
+            self.basis_curve().second_derivative_at(u),
+            Vector3::ZERO,
+            Vector3::ZERO,
+        )
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        // 押し出し方向に沿って最も近い位置を v の初期値とし、曲線のパラメータだけを分割して探す
+        let (a, b) = self.u_range();
+        if !(a.is_finite() && b.is_finite()) {
+            return None;
+        }
+        let d = self.direction().to_vector();
+        let n = 64;
+        let seeds = (0..=n).map(|i| {
+            let u = a + (b - a) * i as f64 / n as f64;
+            (u, (p - self.basis_curve().point_at(u)).dot(d))
+        });
+        nearest_from_seeds(self, p, seeds)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        (
+            self.basis_curve().first_parameter(),
+            self.basis_curve().last_parameter(),
+        )
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+}
+
+impl<S: Surface> Surface for OffsetSurface<S> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        OffsetSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        let su = self.basis().derivative_u_at(u, v);
+        match self.normal_derivatives(u, v) {
+            Some((_, nu, _)) => su + nu * self.distance(),
+            None => su,
+        }
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        let sv = self.basis().derivative_v_at(u, v);
+        match self.normal_derivatives(u, v) {
+            Some((_, _, nv)) => sv + nv * self.distance(),
+            None => sv,
+        }
+    }
+
+    /// 正則な点では法線は基の曲面と同じ
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        self.basis().normal_at(u, v)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        self.basis().u_range()
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        self.basis().v_range()
+    }
+
+    /// 基の曲面への最近点と同じパラメータの点が最近点になる
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        let q = self.basis().project(p)?;
+        Some(surface_projection_at(self, p, q.u, q.v))
+    }
+}
+
+impl GeomSurface {
+    /// 保持している曲面を `Surface` として返す
+    fn as_surface(&self) -> &dyn Surface {
+        match self {
+            GeomSurface::Plane(s) => s,
+            GeomSurface::Cylinder(s) => s,
+            GeomSurface::Sphere(s) => s,
+            GeomSurface::Cone(s) => s,
+            GeomSurface::Torus(s) => s,
+            GeomSurface::BSpline(s) => s,
+            GeomSurface::Extrusion(s) => s,
+            GeomSurface::Revolution(s) => s,
+        }
+    }
+}
+
+/// 各メソッドは保持している曲面の実装に委ねる
+impl Surface for GeomSurface {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.as_surface().point_at(u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        self.as_surface().derivative_u_at(u, v)
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        self.as_surface().derivative_v_at(u, v)
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        self.as_surface().second_derivatives_at(u, v)
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        self.as_surface().normal_at(u, v)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        self.as_surface().u_range()
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        self.as_surface().v_range()
+    }
+
+    fn is_u_closed(&self) -> bool {
+        self.as_surface().is_u_closed()
+    }
+
+    fn is_v_closed(&self) -> bool {
+        self.as_surface().is_v_closed()
+    }
+
+    fn curvature_at(&self, u: f64, v: f64) -> Option<SurfaceCurvature> {
+        self.as_surface().curvature_at(u, v)
+    }
+
+    fn project(&self, p: Point3) -> Option<SurfaceProjection> {
+        self.as_surface().project(p)
+    }
+
+    fn as_analytic(&self) -> Option<AnalyticSurface> {
+        self.as_surface().as_analytic()
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.as_surface().bounding_box()
+    }
+}
+
+/// 点や微分は基の曲面のものをそのまま返す。パラメータ範囲は外側の境界を囲む矩形
+impl<S: Surface> Surface for TrimmedSurface<S> {
+    fn point_at(&self, u: f64, v: f64) -> Point3 {
+        TrimmedSurface::point_at(self, u, v)
+    }
+
+    fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+        self.basis().derivative_u_at(u, v)
+    }
+
+    fn derivative_v_at(&self, u: f64, v: f64) -> Vector3 {
+        self.basis().derivative_v_at(u, v)
+    }
+
+    fn second_derivatives_at(&self, u: f64, v: f64) -> (Vector3, Vector3, Vector3) {
+        self.basis().second_derivatives_at(u, v)
+    }
+
+    fn normal_at(&self, u: f64, v: f64) -> Option<Dir> {
+        self.basis().normal_at(u, v)
+    }
+
+    fn u_range(&self) -> (f64, f64) {
+        self.bounds().0
+    }
+
+    fn v_range(&self) -> (f64, f64) {
+        self.bounds().1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, TAU};
+
+    /// 利用側で定義する独自の曲面の例（半径 1 の円柱の側面）
+    struct Tube;
+
+    impl Surface for Tube {
+        fn point_at(&self, u: f64, v: f64) -> Point3 {
+            Point3::new(u.cos(), u.sin(), v)
+        }
+        fn derivative_u_at(&self, u: f64, _v: f64) -> Vector3 {
+            Vector3::new(-u.sin(), u.cos(), 0.0)
+        }
+        fn derivative_v_at(&self, _u: f64, _v: f64) -> Vector3 {
+            Vector3::Z
+        }
+        fn u_range(&self) -> (f64, f64) {
+            (0.0, TAU)
+        }
+        fn v_range(&self) -> (f64, f64) {
+            (0.0, 2.0)
+        }
+    }
+
+    #[test]
+    fn test_custom_surface_defaults() {
+        let s = Tube;
+        let n = s.normal_at(0.3, 1.0).unwrap();
+        assert!((n.to_vector() - Vector3::new(0.3_f64.cos(), 0.3_f64.sin(), 0.0)).length() < 1e-12);
+        let (suu, suv, svv) = s.second_derivatives_at(0.3, 1.0);
+        assert!((suu + Vector3::new(0.3_f64.cos(), 0.3_f64.sin(), 0.0)).length() < 1e-7);
+        assert!(suv.length() < 1e-12);
+        assert!(svv.length() < 1e-12);
+        assert!(s.is_u_closed());
+        assert!(!s.is_v_closed());
+
+        let b = s.bounding_box();
+        assert!((b.min.x + 1.0).abs() < 1e-12 && (b.max.z - 2.0).abs() < 1e-12);
+        let dyn_surface: &dyn Surface = &s;
+        assert_eq!(tessellate_grid(dyn_surface, 4, 2).len(), 5);
+        assert_eq!(tessellate_grid(dyn_surface, 4, 2)[0].len(), 3);
+    }
+
+    #[test]
+    fn test_plane_surface() {
+        let p = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::Z);
+        assert_eq!(Surface::point_at(&p, 2.0, 3.0), Point3::new(2.0, 3.0, 1.0));
+        assert_eq!(p.normal_at(5.0, -1.0), Some(Dir::Z));
+        let su = p.derivative_u_at(0.0, 0.0);
+        let sv = p.derivative_v_at(0.0, 0.0);
+        assert!((su.cross(sv) - Vector3::Z).length() < 1e-15);
+        assert!(!p.is_u_closed() && !p.is_v_closed());
+        assert!(Surface::bounding_box(&p).is_infinite());
+    }
+
+    #[test]
+    fn test_singular_normal() {
+        // 頂点が特異点となる円錐
+        struct Cone;
+        impl Surface for Cone {
+            fn point_at(&self, u: f64, v: f64) -> Point3 {
+                Point3::new(v * u.cos(), v * u.sin(), v)
+            }
+            fn derivative_u_at(&self, u: f64, v: f64) -> Vector3 {
+                Vector3::new(-v * u.sin(), v * u.cos(), 0.0)
+            }
+            fn derivative_v_at(&self, u: f64, _v: f64) -> Vector3 {
+                Vector3::new(u.cos(), u.sin(), 1.0)
+            }
+            fn u_range(&self) -> (f64, f64) {
+                (0.0, TAU)
+            }
+            fn v_range(&self) -> (f64, f64) {
+                (0.0, 1.0)
+            }
+        }
+        assert!(Cone.normal_at(1.0, 0.0).is_none());
+        assert!(Cone.normal_at(1.0, 0.5).is_some());
+    }
+
+    #[test]
+    fn test_curvature_of_saddle() {
+        // z = x² - y² の原点では主曲率 ±2、主方向は X と Y
+        struct Saddle;
+        impl Surface for Saddle {
+            fn point_at(&self, u: f64, v: f64) -> Point3 {
+                Point3::new(u, v, u * u - v * v)
+            }
+            fn derivative_u_at(&self, u: f64, _v: f64) -> Vector3 {
+                Vector3::new(1.0, 0.0, 2.0 * u)
+            }
+            fn derivative_v_at(&self, _u: f64, v: f64) -> Vector3 {
+                Vector3::new(0.0, 1.0, -2.0 * v)
+            }
+            fn u_range(&self) -> (f64, f64) {
+                (-1.0, 1.0)
+            }
+            fn v_range(&self) -> (f64, f64) {
+                (-1.0, 1.0)
+            }
+        }
+        let c = Saddle.curvature_at(0.0, 0.0).unwrap();
+        assert!((c.max_curvature - 2.0).abs() < 1e-6);
+        assert!((c.min_curvature + 2.0).abs() < 1e-6);
+        assert!((c.gaussian + 4.0).abs() < 1e-5);
+        assert!(c.mean.abs() < 1e-6);
+        assert!(c.max_direction.to_vector().x.abs() > 1.0 - 1e-9);
+        assert!(c.min_direction.to_vector().y.abs() > 1.0 - 1e-9);
+    }
+
+    #[test]
+    fn test_curvature_of_elementary_surfaces() {
+        let axis = Axis3::from_normal(Point3::ORIGIN, Dir::z_axis());
+        // 外向きの法線に対して球面の曲率は -1/R で、すべての点が臍点
+        let sphere = SphericalSurface::new(axis, 2.0).unwrap();
+        let c = sphere.curvature_at(0.7, 0.4).unwrap();
+        assert!((c.max_curvature + 0.5).abs() < 1e-12);
+        assert!((c.min_curvature + 0.5).abs() < 1e-12);
+        assert!((c.gaussian - 0.25).abs() < 1e-12);
+        let n = sphere.normal_at(0.7, 0.4).unwrap();
+        assert!(c.max_direction.dot(n).abs() < 1e-12);
+
+        let cylinder = CylindricalSurface::new(axis, 2.0).unwrap();
+        let c = cylinder.curvature_at(1.0, 3.0).unwrap();
+        assert!(c.max_curvature.abs() < 1e-12);
+        assert!((c.min_curvature + 0.5).abs() < 1e-12);
+        assert!(c.gaussian.abs() < 1e-12);
+        assert!((c.mean + 0.25).abs() < 1e-12);
+        assert!(c.max_direction.dot(Dir::z_axis()).abs() > 1.0 - 1e-12);
+
+        // トーラスの外側はガウス曲率が正、内側は負
+        let torus = ToroidalSurface::new(axis, 3.0, 1.0).unwrap();
+        let outer = torus.curvature_at(0.0, 0.0).unwrap();
+        let inner = torus.curvature_at(0.0, PI).unwrap();
+        assert!((outer.gaussian - 1.0 / 4.0).abs() < 1e-12);
+        assert!((inner.gaussian + 1.0 / 2.0).abs() < 1e-12);
+
+        let plane = Plane::new(Point3::ORIGIN, Dir::z_axis());
+        let c = plane.curvature_at(1.0, 2.0).unwrap();
+        assert_eq!((c.max_curvature, c.min_curvature), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,302 @@
+use crate::bspline::{basis_function_derivatives, find_span};
+use crate::bspline_fit::{fit_least_squares, solve_dense};
+use crate::{BSplineSurface, OcctKrsError, Point3, Result};
+
+/// 曲面の最小二乗近似の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceApproximation {
+    /// 近似曲面
+    pub surface: BSplineSurface,
+    /// 各格子点と、その点に対応するパラメータでの曲面上の点との距離の最大値
+    pub max_error: f64,
+}
+
+impl BSplineSurface {
+    /// 格子状に並んだ点を通る B-スプライン曲面を生成する（The NURBS Book 9.2.5）
+    ///
+    /// `points[i][j]` が u 方向 i 番目・v 方向 j 番目の点で、`points[i][j]` を `(u_i, v_j)` で通る。
+    /// パラメータは各行・各列の弦長パラメータを平均したもので、範囲は `[0, 1]`。
+    /// 各方向の次数は指定値と `点の数 - 1` の小さい方。
+    /// 格子が長方形でない、点が不足している、隣り合う行や列が一致する場合はエラーを返す。
+    pub fn interpolate(
+        points: &[Vec<Point3>],
+        u_degree: usize,
+        v_degree: usize,
+    ) -> Result<BSplineSurface> {
+        let (nu, nv) = check_grid(points, u_degree, v_degree)?;
+        let (p, q) = (u_degree.min(nu - 1), v_degree.min(nv - 1));
+        let (u_params, v_params) = grid_parameters(points)?;
+        let (u_knots, v_knots) = (averaged_knots(&u_params, p), averaged_knots(&v_params, q));
+        let u_matrix = interpolation_matrix(&u_params, &u_knots, p);
+        let v_matrix = interpolation_matrix(&v_params, &v_knots, q);
+        let singular = || OcctKrsError::DegenerateGeometry("補間の方程式が解けません".to_string());
+
+        // 各行を u 方向に補間し、得られた制御点を列ごとに v 方向へ補間する
+        let mut rows = Vec::with_capacity(nv);
+        for j in 0..nv {
+            let rhs = points.iter().map(|row| row[j].to_vector()).collect();
+            rows.push(solve_dense(u_matrix.clone(), rhs).ok_or_else(singular)?);
+        }
+        let mut control_points = Vec::with_capacity(nu);
+        for i in 0..nu {
+            let rhs = rows.iter().map(|r| r[i]).collect();
+            let column = solve_dense(v_matrix.clone(), rhs).ok_or_else(singular)?;
+            control_points.push(column.into_iter().map(Point3::from).collect());
+        }
+        BSplineSurface::from_flat_knots(p, q, control_points, u_knots, v_knots)
+    }
+
+    /// 格子状に並んだ点を許容誤差以内で近似する B-スプライン曲面を最小二乗法で生成する
+    ///
+    /// 各方向の次数は `max_degree` と `点の数 - 1` の小さい方。パラメータは `interpolate` と同じ。
+    /// 行ごとに u 方向、続いて列ごとに v 方向の最小二乗近似を行い（4隅の点は厳密に通る）、
+    /// 制御点を増やしながら誤差が `tolerance` 以下になるまで繰り返す。
+    /// 制御点数が点の数に達しても満たせない場合は `ToleranceExceeded` を返す。
+    pub fn approximate(
+        points: &[Vec<Point3>],
+        max_degree: usize,
+        tolerance: f64,
+    ) -> Result<SurfaceApproximation> {
+        if !(tolerance >= 0.0 && tolerance.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "許容誤差が不正です: {}",
+                tolerance
+            )));
+        }
+        let (nu, nv) = check_grid(points, max_degree, max_degree)?;
+        let (p, q) = (max_degree.min(nu - 1), max_degree.min(nv - 1));
+        let (u_params, v_params) = grid_parameters(points)?;
+        let (mut count_u, mut count_v) = (p + 1, q + 1);
+        loop {
+            let mut rows = Vec::with_capacity(nv);
+            for j in 0..nv {
+                let row: Vec<Point3> = points.iter().map(|r| r[j]).collect();
+                rows.push(fit_least_squares(&row, &u_params, p, count_u)?);
+            }
+            let mut control_points = Vec::with_capacity(count_u);
+            let mut v_knots = Vec::new();
+            for i in 0..count_u {
+                let column: Vec<Point3> = rows.iter().map(|r| r.control_points()[i]).collect();
+                let curve = fit_least_squares(&column, &v_params, q, count_v)?;
+                v_knots = curve.flat_knots().to_vec();
+                control_points.push(curve.control_points().to_vec());
+            }
+            let surface = BSplineSurface::from_flat_knots(
+                p,
+                q,
+                control_points,
+                rows[0].flat_knots().to_vec(),
+                v_knots,
+            )?;
+            let max_error = points
+                .iter()
+                .zip(&u_params)
+                .flat_map(|(row, &u)| {
+                    let surface = &surface;
+                    row.iter()
+                        .zip(&v_params)
+                        .map(move |(pt, &v)| surface.point_at(u, v).distance(*pt))
+                })
+                .fold(0.0, f64::max);
+            if max_error <= tolerance {
+                return Ok(SurfaceApproximation { surface, max_error });
+            }
+            if count_u == nu && count_v == nv {
+                return Err(OcctKrsError::ToleranceExceeded {
+                    tolerance,
+                    deviation: max_error,
+                });
+            }
+            count_u = (count_u + count_u.div_ceil(2)).min(nu);
+            count_v = (count_v + count_v.div_ceil(2)).min(nv);
+        }
+    }
+}
+
+/// 格子の形と次数を調べ、u・v 方向の点の数を返す
+fn check_grid(points: &[Vec<Point3>], u_degree: usize, v_degree: usize) -> Result<(usize, usize)> {
+    if u_degree == 0 || v_degree == 0 {
+        return Err(OcctKrsError::InvalidInput(
+            "次数は1以上である必要があります".to_string(),
+        ));
+    }
+    let nu = points.len();
+    let nv = points.first().map_or(0, |row| row.len());
+    if nu < 2 || nv < 2 {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "格子には各方向に2点以上が必要です（{} × {}）",
+            nu, nv
+        )));
+    }
+    if points.iter().any(|row| row.len() != nv) {
+        return Err(OcctKrsError::InvalidInput(
+            "格子の各行の点の数が揃っていません".to_string(),
+        ));
+    }
+    Ok((nu, nv))
+}
+
+/// 格子の u・v 方向のパラメータを、各列・各行の弦長パラメータの平均として求める
+fn grid_parameters(points: &[Vec<Point3>]) -> Result<(Vec<f64>, Vec<f64>)> {
+    let nv = points[0].len();
+    let columns: Vec<Vec<Point3>> = (0..nv)
+        .map(|j| points.iter().map(|row| row[j]).collect())
+        .collect();
+    Ok((averaged_parameters(&columns)?, averaged_parameters(points)?))
+}
+
+/// 点列それぞれの正規化した弦長パラメータを平均する（長さがゼロの点列は除く）
+///
+/// 点列はすべて同じ数の点を持つこと。結果は `0` から `1` へ狭義単調増加する。
+pub(crate) fn averaged_parameters(lines: &[Vec<Point3>]) -> Result<Vec<f64>> {
+    let m = lines[0].len();
+    let mut sum = vec![0.0; m];
+    let mut count = 0;
+    for line in lines {
+        let lengths: Vec<f64> = line.windows(2).map(|w| w[0].distance(w[1])).collect();
+        let total: f64 = lengths.iter().sum();
+        if total <= f64::MIN_POSITIVE {
+            continue;
+        }
+        let mut acc = 0.0;
+        for (s, d) in sum[1..].iter_mut().zip(&lengths) {
+            acc += d;
+            *s += acc / total;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "点列がすべて1点に縮退しています".to_string(),
+        ));
+    }
+    let mut params: Vec<f64> = sum.iter().map(|s| s / count as f64).collect();
+    params[m - 1] = 1.0;
+    if params.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(OcctKrsError::DegenerateGeometry(
+            "隣り合う点列が一致しています".to_string(),
+        ));
+    }
+    Ok(params)
+}
+
+/// 補間のパラメータから、平均法でフラットノットを求める（The NURBS Book 式 9.8）
+pub(crate) fn averaged_knots(params: &[f64], degree: usize) -> Vec<f64> {
+    let m = params.len();
+    let mut knots = vec![params[0]; degree + 1];
+    for j in 1..m - degree {
+        knots.push(params[j..j + degree].iter().sum::<f64>() / degree as f64);
+    }
+    knots.extend(std::iter::repeat_n(params[m - 1], degree + 1));
+    knots
+}
+
+/// 各パラメータでの基底関数の値を並べた補間の係数行列を返す
+pub(crate) fn interpolation_matrix(params: &[f64], knots: &[f64], degree: usize) -> Vec<Vec<f64>> {
+    let m = params.len();
+    let mut matrix = vec![vec![0.0; m]; m];
+    for (row, &t) in matrix.iter_mut().zip(params) {
+        let span = find_span(knots, m - 1, degree, t);
+        let basis = &basis_function_derivatives(knots, span, t, degree, 0)[0];
+        row[span - degree..=span].copy_from_slice(basis);
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Surface;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    /// z = sin(x) cos(y) の高さ場を格子状に標本化する
+    fn height_field(nu: usize, nv: usize) -> Vec<Vec<Point3>> {
+        (0..nu)
+            .map(|i| {
+                (0..nv)
+                    .map(|j| {
+                        let (x, y) = (
+                            3.0 * i as f64 / (nu - 1) as f64,
+                            2.0 * j as f64 / (nv - 1) as f64,
+                        );
+                        Point3::new(x, y, x.sin() * y.cos())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_interpolate_passes_through_grid() {
+        let grid = height_field(7, 5);
+        let s = BSplineSurface::interpolate(&grid, 3, 3).unwrap();
+        assert_eq!((s.u_degree(), s.v_degree()), (3, 3));
+        assert_eq!(s.control_points().len(), 7);
+        assert_eq!(s.control_points()[0].len(), 5);
+        let (u_params, v_params) = grid_parameters(&grid).unwrap();
+        for (row, &u) in grid.iter().zip(&u_params) {
+            for (pt, &v) in row.iter().zip(&v_params) {
+                assert_point_eq(s.point_at(u, v), *pt);
+            }
+        }
+        assert_eq!(s.u_range(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_interpolate_reduces_degree_for_small_grids() {
+        let grid = vec![
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+            vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0)],
+        ];
+        let s = BSplineSurface::interpolate(&grid, 3, 3).unwrap();
+        assert_eq!((s.u_degree(), s.v_degree()), (1, 1));
+        assert_point_eq(s.point_at(0.5, 0.5), Point3::new(0.5, 0.5, 0.25));
+    }
+
+    #[test]
+    fn test_invalid_grids() {
+        let ragged = vec![
+            vec![Point3::ORIGIN, Point3::new(0.0, 1.0, 0.0)],
+            vec![Point3::new(1.0, 0.0, 0.0)],
+        ];
+        assert!(BSplineSurface::interpolate(&ragged, 2, 2).is_err());
+        assert!(BSplineSurface::interpolate(&height_field(1, 3), 2, 2).is_err());
+        assert!(BSplineSurface::interpolate(&height_field(3, 3), 0, 2).is_err());
+        let repeated = vec![height_field(2, 3)[0].clone(); 3];
+        assert!(BSplineSurface::interpolate(&repeated, 2, 2).is_err());
+    }
+
+    #[test]
+    fn test_approximate_within_tolerance() {
+        let grid = height_field(40, 30);
+        let result = BSplineSurface::approximate(&grid, 3, 1e-4).unwrap();
+        assert!(result.max_error <= 1e-4);
+        // 点の数より十分少ない制御点で近似できる
+        assert!(result.surface.control_points().len() < 40);
+        assert!(result.surface.control_points()[0].len() < 30);
+        assert_point_eq(result.surface.point_at(0.0, 0.0), grid[0][0]);
+        assert_point_eq(result.surface.point_at(1.0, 1.0), grid[39][29]);
+        assert!(BSplineSurface::approximate(&grid, 3, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_approximate_noisy_scan_is_smooth() {
+        // 平面 z = 0 に ±1e-3 のノイズを載せた点群は、少ない制御点で許容誤差内に収まる
+        let grid: Vec<Vec<Point3>> = (0..20)
+            .map(|i| {
+                (0..20)
+                    .map(|j| {
+                        let noise = 1e-3 * (((i * 7 + j * 13) % 5) as f64 - 2.0) / 2.0;
+                        Point3::new(i as f64, j as f64, noise)
+                    })
+                    .collect()
+            })
+            .collect();
+        let result = BSplineSurface::approximate(&grid, 3, 2e-3).unwrap();
+        assert!(result.max_error <= 2e-3);
+        assert_eq!(result.surface.control_points().len(), 4);
+    }
+}
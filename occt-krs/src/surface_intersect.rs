@@ -0,0 +1,1040 @@
+use crate::precision;
+use crate::projection::refine_surface_projection;
+use crate::{
+    AnalyticSurface, Axis2, BoundingBox, Circle, ConicalSurface, CylindricalSurface, Dir, Ellipse,
+    Line, OcctKrsError, Plane, Point3, Polyline3, Result, SphericalSurface, Surface, Vector3,
+};
+
+/// 交線の初期値を探すためにパラメータ範囲を各方向に分割する区間数（曲がりの大きい方向はさらに細かくする）
+const SAMPLES: usize = 16;
+
+/// 交線の追跡で打ち切る最大の点数
+const MAX_STEPS: usize = 100_000;
+
+/// 2曲面の交差
+#[derive(Debug, Clone, PartialEq)]
+pub enum SurfaceIntersection {
+    /// 直線の交線
+    Line(Line),
+    /// 円の交線
+    Circle(Circle),
+    /// 楕円の交線
+    Ellipse(Ellipse),
+    /// 2曲面が1点で接している
+    Point(Point3),
+    /// 追跡で求めた交線の近似
+    Walked(WalkedCurve),
+    /// 2曲面が一致している
+    Coincident,
+}
+
+/// 交線を追跡して得た点列と、各点での両曲面のパラメータ
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkedCurve {
+    /// 交線を近似する折れ線（閉じた交線では閉じた折れ線）
+    pub curve: Polyline3,
+    /// 各点での1つ目の曲面のパラメータ `(u, v)`
+    pub parameters1: Vec<(f64, f64)>,
+    /// 各点での2つ目の曲面のパラメータ `(u, v)`
+    pub parameters2: Vec<(f64, f64)>,
+}
+
+/// 2曲面の交線を求める
+///
+/// 平面どうし、平面と球面・円柱面・円錐面（軸に垂直な場合）、球面どうしは解析的に求める。
+/// それ以外は、パラメータ範囲を格子状に分割して交点の初期値を探し、交線を追跡して
+/// 弦の偏差が `tolerance` 程度の折れ線で近似する。接しているだけの交差は追跡では報告しない。
+///
+/// 追跡ではパラメータ範囲が無限の曲面は、もう一方の曲面の境界ボックスを射影した範囲に制限する。
+/// 両方のパラメータ範囲が無限、または `tolerance` が正でない場合はエラーを返す。
+pub fn intersect_surfaces(
+    s1: &(impl Surface + ?Sized),
+    s2: &(impl Surface + ?Sized),
+    tolerance: f64,
+) -> Result<Vec<SurfaceIntersection>> {
+    if !(tolerance > 0.0 && tolerance.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "許容誤差が不正です: {}",
+            tolerance
+        )));
+    }
+    if let (Some(a), Some(b)) = (s1.as_analytic(), s2.as_analytic()) {
+        if let Some(result) = intersect_analytic(&a, &b, tolerance) {
+            return Ok(result);
+        }
+    }
+    march(s1, s2, tolerance)
+}
+
+/// 解析的に求められる組み合わせであれば交線を返す
+fn intersect_analytic(
+    a: &AnalyticSurface,
+    b: &AnalyticSurface,
+    tol: f64,
+) -> Option<Vec<SurfaceIntersection>> {
+    use AnalyticSurface as A;
+    match (a, b) {
+        (A::Plane(p), A::Plane(q)) => Some(plane_plane(p, q, tol)),
+        (A::Plane(p), A::Sphere(s)) | (A::Sphere(s), A::Plane(p)) => Some(plane_sphere(p, s, tol)),
+        (A::Plane(p), A::Cylinder(c)) | (A::Cylinder(c), A::Plane(p)) => {
+            Some(plane_cylinder(p, c, tol))
+        }
+        (A::Plane(p), A::Cone(c)) | (A::Cone(c), A::Plane(p)) => plane_cone(p, c, tol),
+        (A::Sphere(s), A::Sphere(t)) => Some(sphere_sphere(s, t, tol)),
+        _ => None,
+    }
+}
+
+fn plane_plane(p: &Plane, q: &Plane, tol: f64) -> Vec<SurfaceIntersection> {
+    let (n1, n2) = (p.normal().to_vector(), q.normal().to_vector());
+    let c = n1.cross(n2);
+    if c.length() <= precision::angular() {
+        return if p.distance(q.origin()) <= tol {
+            vec![SurfaceIntersection::Coincident]
+        } else {
+            Vec::new()
+        };
+    }
+    let direction = Dir::new_unchecked(c.normalized());
+    // n1·x = h1, n2·x = h2 を満たす点
+    let (h1, h2) = (
+        n1.dot(p.origin().to_vector()),
+        n2.dot(q.origin().to_vector()),
+    );
+    let origin = Point3::from((n2.cross(c) * h1 + c.cross(n1) * h2) / c.dot(c));
+    vec![SurfaceIntersection::Line(Line::new(origin, direction))]
+}
+
+fn plane_sphere(p: &Plane, s: &SphericalSurface, tol: f64) -> Vec<SurfaceIntersection> {
+    let d = p.signed_distance(s.center());
+    let foot = s.center() - p.normal().to_vector() * d;
+    let r = s.radius();
+    if d.abs() > r + tol {
+        Vec::new()
+    } else if d.abs() >= r - tol {
+        vec![SurfaceIntersection::Point(foot)]
+    } else {
+        let circle = Circle::from_center_normal(foot, p.normal(), (r * r - d * d).sqrt());
+        circle
+            .map(SurfaceIntersection::Circle)
+            .into_iter()
+            .collect()
+    }
+}
+
+fn plane_cylinder(p: &Plane, c: &CylindricalSurface, tol: f64) -> Vec<SurfaceIntersection> {
+    let axis = c.position().direction();
+    let n = p.normal();
+    let cos = n.dot(axis);
+    let r = c.radius();
+    if cos.abs() <= precision::angular() {
+        // 軸に平行な平面：母線に沿った 0〜2 本の直線
+        let d = p.signed_distance(c.position().location());
+        let foot = c.position().location() - n.to_vector() * d;
+        if d.abs() > r + tol {
+            return Vec::new();
+        }
+        if d.abs() >= r - tol {
+            return vec![SurfaceIntersection::Line(Line::new(foot, axis))];
+        }
+        let side = n.to_vector().cross(axis.to_vector()).normalized() * (r * r - d * d).sqrt();
+        return vec![
+            SurfaceIntersection::Line(Line::new(foot - side, axis)),
+            SurfaceIntersection::Line(Line::new(foot + side, axis)),
+        ];
+    }
+    let center = axis_plane_point(p, c.position().location(), axis);
+    if 1.0 - cos.abs() <= precision::angular() {
+        let circle = Circle::from_center_normal(center, axis, r);
+        return circle
+            .map(SurfaceIntersection::Circle)
+            .into_iter()
+            .collect();
+    }
+    // 斜めの平面：短軸は軸と平面の法線の両方に垂直、長半径は r / |cos|
+    let minor = axis.crossed(n).ok();
+    let major = minor.and_then(|m| n.crossed(m).ok());
+    major
+        .and_then(|m| Axis2::new(center, n, m).ok())
+        .and_then(|position| Ellipse::new(position, r / cos.abs(), r).ok())
+        .map(SurfaceIntersection::Ellipse)
+        .into_iter()
+        .collect()
+}
+
+/// 軸に垂直な平面との交線のみ解析的に求め、それ以外は `None` を返す
+fn plane_cone(p: &Plane, c: &ConicalSurface, tol: f64) -> Option<Vec<SurfaceIntersection>> {
+    let axis = c.position().direction();
+    if 1.0 - p.normal().dot(axis).abs() > precision::angular() {
+        return None;
+    }
+    let center = axis_plane_point(p, c.position().location(), axis);
+    let height = (center - c.position().location()).dot(axis.to_vector());
+    let radius = c.radius_at(height / c.semi_angle().cos()).abs();
+    if radius <= tol {
+        return Some(vec![SurfaceIntersection::Point(c.apex())]);
+    }
+    let circle = Circle::from_center_normal(center, axis, radius);
+    Some(
+        circle
+            .map(SurfaceIntersection::Circle)
+            .into_iter()
+            .collect(),
+    )
+}
+
+fn sphere_sphere(s: &SphericalSurface, t: &SphericalSurface, tol: f64) -> Vec<SurfaceIntersection> {
+    let (r1, r2) = (s.radius(), t.radius());
+    let v = t.center() - s.center();
+    let d = v.length();
+    let Ok(dir) = Dir::from_vector(v) else {
+        return if (r1 - r2).abs() <= tol {
+            vec![SurfaceIntersection::Coincident]
+        } else {
+            Vec::new()
+        };
+    };
+    if d > r1 + r2 + tol || d < (r1 - r2).abs() - tol {
+        return Vec::new();
+    }
+    let a = (d * d + r1 * r1 - r2 * r2) / (2.0 * d);
+    let center = s.center() + dir.to_vector() * a;
+    if (d - (r1 + r2)).abs() <= tol || (d - (r1 - r2).abs()).abs() <= tol {
+        return vec![SurfaceIntersection::Point(center)];
+    }
+    let circle = Circle::from_center_normal(center, dir, (r1 * r1 - a * a).max(0.0).sqrt());
+    circle
+        .map(SurfaceIntersection::Circle)
+        .into_iter()
+        .collect()
+}
+
+/// 平面と軸の交点（軸が平面に平行でないこと）
+fn axis_plane_point(p: &Plane, location: Point3, axis: Dir) -> Point3 {
+    let t = -p.signed_distance(location) / p.normal().dot(axis);
+    location + axis.to_vector() * t
+}
+
+/// 追跡に用いる曲面のパラメータ範囲
+struct Domain {
+    u: (f64, f64),
+    v: (f64, f64),
+    closed: [bool; 2],
+}
+
+impl Domain {
+    /// 無限の方向は `other` の境界ボックスの角を射影した範囲に制限する
+    fn new(surface: &(impl Surface + ?Sized), other: Option<&BoundingBox>) -> Result<Self> {
+        let (mut u, mut v) = (surface.u_range(), surface.v_range());
+        if ![u.0, u.1, v.0, v.1].iter().all(|x| x.is_finite()) {
+            let window = other
+                .and_then(|b| projected_window(surface, b))
+                .ok_or_else(|| {
+                    OcctKrsError::InvalidInput(
+                    "交線の追跡には少なくとも一方の曲面のパラメータ範囲が有限である必要があります"
+                        .to_string(),
+                )
+                })?;
+            let fit = |range: (f64, f64), window: (f64, f64)| {
+                if range.0.is_finite() && range.1.is_finite() {
+                    range
+                } else {
+                    (range.0.max(window.0), range.1.min(window.1))
+                }
+            };
+            u = fit(u, window.0);
+            v = fit(v, window.1);
+            if !(u.0 < u.1 && v.0 < v.1) {
+                return Err(OcctKrsError::InvalidInput(
+                    "交線の追跡範囲が空です".to_string(),
+                ));
+            }
+        }
+        Ok(Self {
+            u,
+            v,
+            closed: [surface.is_u_closed(), surface.is_v_closed()],
+        })
+    }
+
+    /// 閉じた方向は範囲内に折り返し、それ以外は範囲内に制限する
+    fn fit(&self, u: f64, v: f64) -> (f64, f64) {
+        let wrap = |x: f64, (lo, hi): (f64, f64), closed: bool| {
+            if closed {
+                lo + (x - lo).rem_euclid(hi - lo)
+            } else {
+                x.clamp(lo, hi)
+            }
+        };
+        (
+            wrap(u, self.u, self.closed[0]),
+            wrap(v, self.v, self.closed[1]),
+        )
+    }
+
+    /// 閉じていない方向で範囲外に出ていないか
+    fn contains(&self, u: f64, v: f64) -> bool {
+        let inside = |x: f64, (lo, hi): (f64, f64), closed: bool| {
+            closed || (x >= lo - 1e-12 * (1.0 + lo.abs()) && x <= hi + 1e-12 * (1.0 + hi.abs()))
+        };
+        inside(u, self.u, self.closed[0]) && inside(v, self.v, self.closed[1])
+    }
+
+    /// 各方向を `counts` 等分した格子の `(i, j)` 番目の点のパラメータ
+    fn at(&self, i: usize, j: usize, counts: [usize; 2]) -> (f64, f64) {
+        let t = |(lo, hi): (f64, f64), k: usize, n: usize| lo + (hi - lo) * k as f64 / n as f64;
+        (t(self.u, i, counts[0]), t(self.v, j, counts[1]))
+    }
+}
+
+/// 境界ボックスの角を曲面に射影したパラメータを囲む範囲（1割の余裕を持たせる）
+fn projected_window(
+    surface: &(impl Surface + ?Sized),
+    b: &BoundingBox,
+) -> Option<((f64, f64), (f64, f64))> {
+    if b.is_infinite() {
+        return None;
+    }
+    let mut u = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut v = (f64::INFINITY, f64::NEG_INFINITY);
+    for k in 0..8 {
+        let corner = Point3::new(
+            if k & 1 == 0 { b.min.x } else { b.max.x },
+            if k & 2 == 0 { b.min.y } else { b.max.y },
+            if k & 4 == 0 { b.min.z } else { b.max.z },
+        );
+        let r = surface.project(corner)?;
+        u = (u.0.min(r.u), u.1.max(r.u));
+        v = (v.0.min(r.v), v.1.max(r.v));
+    }
+    let grow = |(lo, hi): (f64, f64)| {
+        let m = 0.1 * (hi - lo) + 1.0;
+        (lo - m, hi + m)
+    };
+    Some((grow(u), grow(v)))
+}
+
+/// 交線上の点（位置と両曲面のパラメータ）
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    point: Point3,
+    x: [f64; 4],
+}
+
+/// 2曲面と各パラメータ範囲
+struct Pair<'a, S1: ?Sized, S2: ?Sized> {
+    s1: &'a S1,
+    s2: &'a S2,
+    d1: Domain,
+    d2: Domain,
+    tol: f64,
+}
+
+impl<S1: Surface + ?Sized, S2: Surface + ?Sized> Pair<'_, S1, S2> {
+    fn fit(&self, x: [f64; 4]) -> [f64; 4] {
+        let (u1, v1) = self.d1.fit(x[0], x[1]);
+        let (u2, v2) = self.d2.fit(x[2], x[3]);
+        [u1, v1, u2, v2]
+    }
+
+    fn contains(&self, x: &[f64; 4]) -> bool {
+        self.d1.contains(x[0], x[1]) && self.d2.contains(x[2], x[3])
+    }
+
+    fn gap(&self, x: &[f64; 4]) -> Vector3 {
+        self.s1.point_at(x[0], x[1]) - self.s2.point_at(x[2], x[3])
+    }
+
+    fn node(&self, x: [f64; 4]) -> Node {
+        let p1 = self.s1.point_at(x[0], x[1]);
+        let p2 = self.s2.point_at(x[2], x[3]);
+        Node {
+            point: p1 + (p2 - p1) * 0.5,
+            x,
+        }
+    }
+
+    /// 交線の接線方向（両曲面の法線の外積）を返す。接している場合は `None`
+    fn tangent(&self, x: &[f64; 4]) -> Option<Vector3> {
+        let n1 = self.s1.normal_at(x[0], x[1])?;
+        let n2 = self.s2.normal_at(x[2], x[3])?;
+        let t = n1.to_vector().cross(n2.to_vector());
+        (t.length() > 1e-6).then(|| t.normalized())
+    }
+
+    /// 4変数の最小ノルムのニュートン法で交線上の点に収束させる
+    fn converge(&self, mut x: [f64; 4]) -> Option<[f64; 4]> {
+        for _ in 0..32 {
+            let f = self.gap(&x);
+            if f.length() <= self.tol * 1e-3 {
+                return Some(x);
+            }
+            let j = [
+                self.s1.derivative_u_at(x[0], x[1]),
+                self.s1.derivative_v_at(x[0], x[1]),
+                -self.s2.derivative_u_at(x[2], x[3]),
+                -self.s2.derivative_v_at(x[2], x[3]),
+            ];
+            // J Jᵀ y = f、Δx = -Jᵀ y
+            let mut jj = [[0.0; 3]; 3];
+            for (r, row) in jj.iter_mut().enumerate() {
+                for (c, value) in row.iter_mut().enumerate() {
+                    *value = j.iter().map(|v| v.to_array()[r] * v.to_array()[c]).sum();
+                }
+            }
+            let y = solve(jj, f.to_array())?;
+            let y = Vector3::new(y[0], y[1], y[2]);
+            let step = j.map(|v| v.dot(y));
+            x = self.fit([
+                x[0] - step[0],
+                x[1] - step[1],
+                x[2] - step[2],
+                x[3] - step[3],
+            ]);
+        }
+        (self.gap(&x).length() <= self.tol).then_some(x)
+    }
+
+    /// 交互に射影して交線上の点を探す（接近して法線がほぼ平行な場合の予備）
+    ///
+    /// 距離の縮む割合が続いても残りの反復で許容誤差に届かない場合は打ち切る。
+    fn alternate(&self, mut x: [f64; 4]) -> Option<[f64; 4]> {
+        const ITERATIONS: i32 = 64;
+        let mut last = f64::INFINITY;
+        for k in 1..=ITERATIONS {
+            let p1 = self.s1.point_at(x[0], x[1]);
+            let (u2, v2) = refine_surface_projection(self.s2, p1, x[2], x[3]);
+            let p2 = self.s2.point_at(u2, v2);
+            let (u1, v1) = refine_surface_projection(self.s1, p2, x[0], x[1]);
+            x = [u1, v1, u2, v2];
+            let gap = self.gap(&x).length();
+            if gap <= self.tol * 1e-3 || gap * (gap / last).powi(ITERATIONS - k) > self.tol {
+                break;
+            }
+            last = gap;
+        }
+        (self.gap(&x).length() <= self.tol).then_some(x)
+    }
+
+    /// 予測点 `target` を通り接線 `t` に垂直な平面上で交線上の点を求める
+    fn correct(&self, mut x: [f64; 4], target: Point3, t: Vector3) -> Option<[f64; 4]> {
+        for _ in 0..16 {
+            let f = self.gap(&x);
+            let p1 = self.s1.point_at(x[0], x[1]);
+            let g = (p1 - target).dot(t);
+            let (a, b) = (
+                self.s1.derivative_u_at(x[0], x[1]),
+                self.s1.derivative_v_at(x[0], x[1]),
+            );
+            let (c, d) = (
+                -self.s2.derivative_u_at(x[2], x[3]),
+                -self.s2.derivative_v_at(x[2], x[3]),
+            );
+            let (fa, ca) = (f.to_array(), [a, b, c, d].map(|v| v.to_array()));
+            let mut m = [[0.0; 4]; 4];
+            for (r, row) in m.iter_mut().take(3).enumerate() {
+                *row = ca.map(|column| column[r]);
+            }
+            m[3] = [a.dot(t), b.dot(t), 0.0, 0.0];
+            let dx = solve(m, [fa[0], fa[1], fa[2], g])?;
+            x = self.fit([x[0] - dx[0], x[1] - dx[1], x[2] - dx[2], x[3] - dx[3]]);
+            if dx.iter().map(|v| v.abs()).sum::<f64>() <= 1e-14 {
+                break;
+            }
+        }
+        (self.gap(&x).length() <= self.tol * 0.1).then_some(x)
+    }
+
+    /// 1つ目の曲面上で空間の変位 `delta` に対応するパラメータの変位を最小二乗で求めて予測する
+    fn predict(&self, x: &[f64; 4], delta: Vector3) -> [f64; 4] {
+        let shift = |su: Vector3, sv: Vector3| {
+            let (a, b, c) = (su.dot(su), su.dot(sv), sv.dot(sv));
+            let (p, q) = (su.dot(delta), sv.dot(delta));
+            let det = a * c - b * b;
+            if det.abs() <= f64::MIN_POSITIVE {
+                (0.0, 0.0)
+            } else {
+                ((c * p - b * q) / det, (a * q - b * p) / det)
+            }
+        };
+        let (du1, dv1) = shift(
+            self.s1.derivative_u_at(x[0], x[1]),
+            self.s1.derivative_v_at(x[0], x[1]),
+        );
+        let (du2, dv2) = shift(
+            self.s2.derivative_u_at(x[2], x[3]),
+            self.s2.derivative_v_at(x[2], x[3]),
+        );
+        [x[0] + du1, x[1] + dv1, x[2] + du2, x[3] + dv2]
+    }
+
+    /// 交線上の点 `x` から長さ `h` だけ方向 `t` に進んだ点を求める
+    /// 閉じていない方向の範囲を越える場合は、範囲内に収まる最大の歩幅まで縮める
+    fn step(&self, x: &[f64; 4], t: Vector3, h: f64) -> Option<([f64; 4], bool)> {
+        let try_step = |h: f64| -> Option<[f64; 4]> {
+            let predicted = self.predict(x, t * h);
+            let target = self.s1.point_at(x[0], x[1]) + t * h;
+            self.correct(self.fit(predicted), target, t)
+                .filter(|y| self.contains(y))
+                .filter(|y| {
+                    let moved = self.s1.point_at(y[0], y[1]) - self.s1.point_at(x[0], x[1]);
+                    moved.dot(t) > 0.0
+                })
+        };
+        if let Some(y) = try_step(h) {
+            if !self.at_boundary(&y) {
+                return Some((y, false));
+            }
+        }
+        // 範囲の境界までの歩幅を二分法で求める
+        let (mut lo, mut hi) = (0.0, h);
+        let mut best = None;
+        for _ in 0..40 {
+            let mid = 0.5 * (lo + hi);
+            match try_step(mid) {
+                Some(y) => {
+                    lo = mid;
+                    best = Some(y);
+                }
+                None => hi = mid,
+            }
+        }
+        best.filter(|_| lo > 0.0).map(|y| (y, true))
+    }
+
+    /// 閉じていない方向でパラメータが範囲の端にあるか
+    fn at_boundary(&self, x: &[f64; 4]) -> bool {
+        let on = |value: f64, (lo, hi): (f64, f64), closed: bool| {
+            let eps = 1e-12 * (1.0 + lo.abs().max(hi.abs()));
+            !closed && ((value - lo).abs() <= eps || (value - hi).abs() <= eps)
+        };
+        on(x[0], self.d1.u, self.d1.closed[0])
+            || on(x[1], self.d1.v, self.d1.closed[1])
+            || on(x[2], self.d2.u, self.d2.closed[0])
+            || on(x[3], self.d2.v, self.d2.closed[1])
+    }
+
+    /// 始点から一方向へ交線を追跡する。始点に戻った場合は `true` を返す
+    fn trace(&self, start: Node, forward: bool, h_max: f64) -> (Vec<Node>, bool) {
+        let sign = if forward { 1.0 } else { -1.0 };
+        let h_min = h_max * 1e-4;
+        let mut nodes = vec![start];
+        let mut h = h_max * 0.25;
+        let Some(mut t) = self.tangent(&start.x).map(|t| t * sign) else {
+            return (nodes, false);
+        };
+        while nodes.len() < MAX_STEPS {
+            let last = *nodes.last().unwrap();
+            let Some((y, boundary)) = self.step(&last.x, t, h) else {
+                if h <= h_min {
+                    break;
+                }
+                h *= 0.5;
+                continue;
+            };
+            let Some(mut t_new) = self.tangent(&y) else {
+                // 接する点に達した
+                nodes.push(self.node(y));
+                break;
+            };
+            if t_new.dot(t) < 0.0 {
+                t_new = -t_new;
+            }
+            let angle = t.cross(t_new).length().atan2(t.dot(t_new));
+            let chord = self.node(y).point.distance(last.point);
+            // 弦の偏差はおよそ 弦長 × 角度 / 8
+            if (chord * angle / 8.0 > self.tol || angle > 0.3) && h > h_min {
+                h *= 0.5;
+                continue;
+            }
+            let node = self.node(y);
+            // 始点の近くへ戻ってきたら閉じた交線とする
+            if nodes.len() > 2 {
+                let to_start = start.point - last.point;
+                if to_start.length() <= chord.max(self.tol) && to_start.dot(t) > 0.0 {
+                    return (nodes, true);
+                }
+            }
+            nodes.push(node);
+            if boundary {
+                break;
+            }
+            t = t_new;
+            if chord * angle / 8.0 < 0.25 * self.tol && angle < 0.1 {
+                h = (h * 1.5).min(h_max);
+            }
+        }
+        (nodes, false)
+    }
+}
+
+/// 交線の追跡で交線を求める
+fn march(
+    s1: &(impl Surface + ?Sized),
+    s2: &(impl Surface + ?Sized),
+    tol: f64,
+) -> Result<Vec<SurfaceIntersection>> {
+    let b1 = finite_box(s1);
+    let b2 = finite_box(s2);
+    let pair = Pair {
+        s1,
+        s2,
+        d1: Domain::new(s1, b2.as_ref())?,
+        d2: Domain::new(s2, b1.as_ref())?,
+        tol,
+    };
+    let grid1 = Grid::new(s1, &pair.d1, tol);
+    let grid2 = Grid::new(s2, &pair.d2, tol);
+    let diagonal = grid1.extent.min(grid2.extent).max(tol);
+    let h_max = diagonal / 32.0;
+
+    // 交差する格子の組から交線上の点を集める
+    let mut seeds: Vec<Node> = Vec::new();
+    for (i1, box1) in grid1.boxes.iter().enumerate() {
+        for (i2, box2) in grid2.boxes.iter().enumerate() {
+            if !box1.intersects(box2) {
+                continue;
+            }
+            let (u1, v1) = grid1.center(i1, &pair.d1);
+            let (u2, v2) = grid2.center(i2, &pair.d2);
+            let start = [u1, v1, u2, v2];
+            let Some(x) = pair.converge(start).or_else(|| pair.alternate(start)) else {
+                continue;
+            };
+            let node = pair.node(x);
+            if !seeds.iter().any(|s| s.point.distance(node.point) <= tol) {
+                seeds.push(node);
+            }
+        }
+    }
+
+    let mut curves: Vec<Vec<Node>> = Vec::new();
+    let mut closed_flags = Vec::new();
+    for seed in seeds {
+        if pair.tangent(&seed.x).is_none() {
+            continue;
+        }
+        if curves
+            .iter()
+            .zip(&closed_flags)
+            .any(|(c, &closed)| distance_to_nodes(c, closed, seed.point) <= 4.0 * tol)
+        {
+            continue;
+        }
+        let (forward, closed) = pair.trace(seed, true, h_max);
+        let nodes = if closed {
+            forward
+        } else {
+            let (mut backward, _) = pair.trace(seed, false, h_max);
+            backward.reverse();
+            backward.pop();
+            backward.extend(forward);
+            backward
+        };
+        curves.push(nodes);
+        closed_flags.push(closed);
+    }
+
+    Ok(curves
+        .into_iter()
+        .zip(closed_flags)
+        .filter_map(|(nodes, closed)| {
+            let closed = closed && nodes.len() >= 3;
+            let curve = Polyline3::new(nodes.iter().map(|n| n.point).collect(), closed).ok()?;
+            Some(SurfaceIntersection::Walked(WalkedCurve {
+                curve,
+                parameters1: nodes.iter().map(|n| (n.x[0], n.x[1])).collect(),
+                parameters2: nodes.iter().map(|n| (n.x[2], n.x[3])).collect(),
+            }))
+        })
+        .collect())
+}
+
+/// 曲面の境界ボックス（無限の場合は `None`）
+fn finite_box(s: &(impl Surface + ?Sized)) -> Option<BoundingBox> {
+    let b = s.bounding_box();
+    (!b.is_infinite()).then_some(b)
+}
+
+/// 点列（閉じている場合は終点と始点を結ぶ辺も含む）までの距離
+fn distance_to_nodes(nodes: &[Node], closed: bool, p: Point3) -> f64 {
+    let segment = |a: Point3, b: Point3| {
+        let v = b - a;
+        let len2 = v.dot(v);
+        let t = if len2 > 0.0 {
+            ((p - a).dot(v) / len2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        p.distance(a + v * t)
+    };
+    let mut d = nodes
+        .windows(2)
+        .map(|w| segment(w[0].point, w[1].point))
+        .fold(f64::INFINITY, f64::min);
+    if let [first, .., last] = nodes {
+        if closed {
+            d = d.min(segment(last.point, first.point));
+        }
+    }
+    if nodes.len() == 1 {
+        d = p.distance(nodes[0].point);
+    }
+    d
+}
+
+/// パラメータ範囲を格子に分けた各区画の境界ボックス
+///
+/// 各方向の区間数は `SAMPLES` から始め、区間の中央の弦からのずれが大きい方向
+/// （螺旋に沿う方向など）は区画の境界ボックスが緩くならないよう最大4倍まで細かくする。
+struct Grid {
+    boxes: Vec<BoundingBox>,
+    extent: f64,
+    /// 各方向の区間数
+    counts: [usize; 2],
+}
+
+impl Grid {
+    fn new(surface: &(impl Surface + ?Sized), domain: &Domain, tol: f64) -> Self {
+        let coarse = [SAMPLES, SAMPLES];
+        let sample = |counts: [usize; 2]| -> Vec<Vec<Point3>> {
+            (0..=counts[0])
+                .map(|i| {
+                    (0..=counts[1])
+                        .map(|j| {
+                            let (u, v) = domain.at(i, j, counts);
+                            surface.point_at(u, v)
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        let points = sample(coarse);
+        let all = BoundingBox::from_points(points.iter().flatten().copied()).unwrap();
+        let extent = all.size().length();
+        // 方向ごとに、区間の中央の点と両端の中点との距離の最大値
+        let mut sags = [0.0f64; 2];
+        for i in 0..=SAMPLES {
+            for j in 0..=SAMPLES {
+                if i < SAMPLES {
+                    let (u, v) = domain.at(2 * i + 1, 2 * j, [2 * SAMPLES, 2 * SAMPLES]);
+                    let chord = points[i][j] + (points[i + 1][j] - points[i][j]) * 0.5;
+                    sags[0] = sags[0].max(surface.point_at(u, v).distance(chord));
+                }
+                if j < SAMPLES {
+                    let (u, v) = domain.at(2 * i, 2 * j + 1, [2 * SAMPLES, 2 * SAMPLES]);
+                    let chord = points[i][j] + (points[i][j + 1] - points[i][j]) * 0.5;
+                    sags[1] = sags[1].max(surface.point_at(u, v).distance(chord));
+                }
+            }
+        }
+        let counts = sags.map(|mut sag| {
+            let mut n = SAMPLES;
+            while sag > extent / 128.0 && n < 4 * SAMPLES {
+                n *= 2;
+                sag /= 4.0;
+            }
+            n
+        });
+        let points = if counts == coarse {
+            points
+        } else {
+            sample(counts)
+        };
+        let mut boxes = Vec::with_capacity(counts[0] * counts[1]);
+        for i in 0..counts[0] {
+            for j in 0..counts[1] {
+                let corners = [
+                    points[i][j],
+                    points[i + 1][j],
+                    points[i][j + 1],
+                    points[i + 1][j + 1],
+                ];
+                let (u0, v0) = domain.at(i, j, counts);
+                let (u1, v1) = domain.at(i + 1, j + 1, counts);
+                let mid = surface.point_at(0.5 * (u0 + u1), 0.5 * (v0 + v1));
+                let average = Point3::from(
+                    corners
+                        .iter()
+                        .fold(Vector3::ZERO, |acc, p| acc + p.to_vector())
+                        * 0.25,
+                );
+                // 区画内で最も離れる位置は中央とは限らないので余裕を持たせる
+                let sag = 2.0 * mid.distance(average);
+                let mut b = BoundingBox::from_points(corners).unwrap();
+                b.add_point(mid);
+                boxes.push(b.enlarged(sag + tol));
+            }
+        }
+        Self {
+            boxes,
+            extent,
+            counts,
+        }
+    }
+
+    /// k 番目の区画の中央のパラメータ
+    fn center(&self, k: usize, domain: &Domain) -> (f64, f64) {
+        let (i, j) = (k / self.counts[1], k % self.counts[1]);
+        let (u0, v0) = domain.at(i, j, self.counts);
+        let (u1, v1) = domain.at(i + 1, j + 1, self.counts);
+        (0.5 * (u0 + u1), 0.5 * (v0 + v1))
+    }
+}
+
+/// 部分ピボット選択付きのガウスの消去法で小さな連立方程式を解く
+fn solve<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..N {
+            let f = a[row][col] / a[col][col];
+            let pivot_row = a[col];
+            for (x, y) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= f * y;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let s: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - s) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arc, Axis1, Axis3, BSplineSurface, RevolvedSurface, ToroidalSurface, Transform};
+    use std::f64::consts::PI;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn z_axis() -> Axis3 {
+        Axis3::from_normal(Point3::ORIGIN, Dir::z_axis())
+    }
+
+    /// 交線の各点が両曲面上にあることを確かめる
+    fn assert_on_both(w: &WalkedCurve, s1: &dyn Surface, s2: &dyn Surface, tol: f64) {
+        assert_eq!(w.curve.points().len(), w.parameters1.len());
+        for ((p, a), b) in w
+            .curve
+            .points()
+            .iter()
+            .zip(&w.parameters1)
+            .zip(&w.parameters2)
+        {
+            assert!(s1.point_at(a.0, a.1).distance(*p) <= tol);
+            assert!(s2.point_at(b.0, b.1).distance(*p) <= tol);
+        }
+    }
+
+    #[test]
+    fn test_plane_plane() {
+        let p = Plane::xy();
+        let q = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::new(1.0, 0.0, 1.0).unwrap());
+        let r = intersect_surfaces(&p, &q, 1e-7).unwrap();
+        let [SurfaceIntersection::Line(line)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!(p.distance(line.origin) < 1e-12 && q.distance(line.origin) < 1e-12);
+        assert!(line.dir.dot(Dir::y_axis()).abs() > 1.0 - 1e-12);
+
+        let parallel = Plane::new(Point3::new(0.0, 0.0, 2.0), Dir::z_axis());
+        assert!(intersect_surfaces(&p, &parallel, 1e-7).unwrap().is_empty());
+        assert_eq!(
+            intersect_surfaces(&p, &p.reversed(), 1e-7).unwrap(),
+            vec![SurfaceIntersection::Coincident]
+        );
+    }
+
+    #[test]
+    fn test_plane_sphere_and_sphere_sphere() {
+        let s = SphericalSurface::new(z_axis(), 2.0).unwrap();
+        let p = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::z_axis());
+        let r = intersect_surfaces(&p, &s, 1e-7).unwrap();
+        let [SurfaceIntersection::Circle(c)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!((c.radius() - 3.0_f64.sqrt()).abs() < 1e-12);
+        assert_point_eq(c.center(), Point3::new(0.0, 0.0, 1.0));
+
+        let tangent = Plane::new(Point3::new(0.0, 0.0, 2.0), Dir::z_axis());
+        assert_eq!(
+            intersect_surfaces(&s, &tangent, 1e-7).unwrap(),
+            vec![SurfaceIntersection::Point(Point3::new(0.0, 0.0, 2.0))]
+        );
+
+        let moved = s.transformed(&Transform::from_translation(Vector3::new(2.0, 0.0, 0.0)));
+        let r = intersect_surfaces(&s, &moved, 1e-7).unwrap();
+        let [SurfaceIntersection::Circle(c)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert_point_eq(c.center(), Point3::new(1.0, 0.0, 0.0));
+        assert!((c.radius() - 3.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_plane_cylinder() {
+        let cyl = CylindricalSurface::new(z_axis(), 1.0).unwrap();
+        // 軸に平行な平面では2本の直線
+        let p = Plane::new(Point3::new(0.5, 0.0, 0.0), Dir::x_axis());
+        let r = intersect_surfaces(&p, &cyl, 1e-7).unwrap();
+        assert_eq!(r.len(), 2);
+        for x in &r {
+            let SurfaceIntersection::Line(line) = x else {
+                panic!("{:?}", x);
+            };
+            assert!((line.origin.x - 0.5).abs() < 1e-12);
+            assert!((line.origin.y.abs() - 0.75_f64.sqrt()).abs() < 1e-12);
+        }
+        // 斜めの平面では楕円
+        let p = Plane::new(Point3::new(0.0, 0.0, 1.0), Dir::new(0.0, 1.0, 1.0).unwrap());
+        let r = intersect_surfaces(&cyl, &p, 1e-7).unwrap();
+        let [SurfaceIntersection::Ellipse(e)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!((e.major_radius() - 2.0_f64.sqrt()).abs() < 1e-12);
+        for k in 0..8 {
+            let q = e.point_at(k as f64);
+            assert!(p.distance(q) < 1e-12);
+            assert!((q.x.hypot(q.y) - 1.0).abs() < 1e-12);
+        }
+        // 軸に垂直な平面では円
+        let r = intersect_surfaces(&cyl, &Plane::xy(), 1e-7).unwrap();
+        assert!(
+            matches!(r.as_slice(), [SurfaceIntersection::Circle(c)] if (c.radius() - 1.0).abs() < 1e-12)
+        );
+    }
+
+    #[test]
+    fn test_plane_cone_perpendicular() {
+        let cone = ConicalSurface::new(z_axis(), PI / 4.0, 1.0).unwrap();
+        let p = Plane::new(Point3::new(0.0, 0.0, 2.0), Dir::z_axis());
+        let r = intersect_surfaces(&p, &cone, 1e-7).unwrap();
+        let [SurfaceIntersection::Circle(c)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!((c.radius() - 3.0).abs() < 1e-12);
+        // 頂点を通る平面では1点
+        let apex = Plane::new(cone.apex(), Dir::z_axis());
+        assert_eq!(
+            intersect_surfaces(&apex, &cone, 1e-7).unwrap(),
+            vec![SurfaceIntersection::Point(cone.apex())]
+        );
+    }
+
+    #[test]
+    fn test_marching_plane_torus() {
+        // 軸に垂直な平面はトーラスを2つの円で切る（解析解を持たない組み合わせ）
+        let torus = ToroidalSurface::new(z_axis(), 3.0, 1.0).unwrap();
+        let plane = Plane::new(Point3::new(0.0, 0.0, 0.5), Dir::z_axis());
+        let tol = 1e-4;
+        let r = intersect_surfaces(&plane, &torus, tol).unwrap();
+        assert_eq!(r.len(), 2);
+        let mut radii = Vec::new();
+        for x in &r {
+            let SurfaceIntersection::Walked(w) = x else {
+                panic!("{:?}", x);
+            };
+            assert!(w.curve.is_closed());
+            assert_on_both(w, &plane, &torus, 1e-6);
+            let rho: Vec<f64> = w.curve.points().iter().map(|p| p.x.hypot(p.y)).collect();
+            assert!(rho.iter().all(|r| (r - rho[0]).abs() < 1e-6));
+            radii.push(rho[0]);
+            // 弦の偏差は許容誤差程度
+            let chord = w.curve.segments().map(|s| s.length()).fold(0.0, f64::max);
+            assert!(chord * chord / (8.0 * rho[0]) < 2.0 * tol);
+        }
+        radii.sort_by(f64::total_cmp);
+        let h = 0.75_f64.sqrt();
+        assert!((radii[0] - (3.0 - h)).abs() < 1e-6);
+        assert!((radii[1] - (3.0 + h)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_marching_open_curve_ends_on_boundary() {
+        let knots = vec![0.0, 0.0, 1.0, 1.0];
+        let flat = BSplineSurface::from_flat_knots(
+            1,
+            1,
+            vec![
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+                vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+            ],
+            knots.clone(),
+            knots.clone(),
+        )
+        .unwrap();
+        let wall = BSplineSurface::from_flat_knots(
+            1,
+            1,
+            vec![
+                vec![Point3::new(-0.5, 0.2, -1.0), Point3::new(-0.5, 0.2, 1.0)],
+                vec![Point3::new(1.5, 0.6, -1.0), Point3::new(1.5, 0.6, 1.0)],
+            ],
+            knots.clone(),
+            knots,
+        )
+        .unwrap();
+        let r = intersect_surfaces(&flat, &wall, 1e-6).unwrap();
+        let [SurfaceIntersection::Walked(w)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!(!w.curve.is_closed());
+        assert_on_both(w, &flat, &wall, 1e-6);
+        let points = w.curve.points();
+        let (a, b) = (points[0], points[points.len() - 1]);
+        let (a, b) = if a.x < b.x { (a, b) } else { (b, a) };
+        assert!(a.distance(Point3::new(0.0, 0.3, 0.0)) < 1e-6);
+        assert!(b.distance(Point3::new(1.0, 0.5, 0.0)) < 1e-6);
+    }
+
+    #[test]
+    fn test_marching_across_seam() {
+        // 半円を回転した球面と、ずらした球面（一方が解析的でないので追跡になる）
+        let arc = Arc::new(
+            Circle::new(
+                Axis2::new(Point3::ORIGIN, -Dir::y_axis(), Dir::x_axis()).unwrap(),
+                2.0,
+            )
+            .unwrap(),
+            -PI / 2.0,
+            PI / 2.0,
+        )
+        .unwrap();
+        let revolved = RevolvedSurface::new(arc, Axis1::oz());
+        let sphere = SphericalSurface::new(
+            Axis3::from_normal(Point3::new(1.0, 0.0, 0.0), Dir::z_axis()),
+            2.0,
+        )
+        .unwrap();
+        let r = intersect_surfaces(&revolved, &sphere, 1e-5).unwrap();
+        let [SurfaceIntersection::Walked(w)] = r.as_slice() else {
+            panic!("{:?}", r);
+        };
+        assert!(w.curve.is_closed());
+        assert_on_both(w, &revolved, &sphere, 1e-6);
+        for p in w.curve.points() {
+            assert!((p.x - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let p = Plane::xy();
+        assert!(intersect_surfaces(&p, &p, 0.0).is_err());
+        let cone = ConicalSurface::new(z_axis(), 0.3, 1.0).unwrap();
+        let tilted = Plane::new(Point3::ORIGIN, Dir::new(1.0, 0.0, 1.0).unwrap());
+        // 両方とも無限の曲面で解析解がない組み合わせ
+        assert!(intersect_surfaces(&tilted, &cone, 1e-6).is_err());
+    }
+}
@@ -0,0 +1,780 @@
+use std::collections::HashMap;
+
+use crate::bspline_fit::solve_dense;
+use crate::extrude::{area_vector, uv_segment, wire_points};
+use crate::loft::Profile;
+use crate::precision;
+use crate::surface_fit::{averaged_knots, interpolation_matrix};
+use crate::topo::ShapeId;
+use crate::{
+    Arc, Axis2, Axis3, BSplineCurve, BSplineSurface, Circle, Curve3, Dir, Edge, Face, GeomCurve,
+    GeomSurface, OcctKrsError, Orientation, Point3, Result, Shape, Shell, Solid, Transform,
+    Vector2, Vector3, Vertex, Wire,
+};
+
+/// 直線でない経路を分割する区間の数
+const SPANS: usize = 16;
+
+/// 回転を最小にする標構を求めるときの、区間あたりの刻みの数
+const SUBSTEPS: usize = 8;
+
+/// 掃引で断面を運ぶ座標系の決め方（OCCT の `GeomFill_Trihedron` 相当）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameMode {
+    /// 接線・主法線・従法線からなるフレネ標構（曲率が 0 の所では直前の法線を引き継ぐ）
+    ///
+    /// 空間曲線では経路の捩れに合わせて断面も接線のまわりに回る。
+    Frenet,
+    /// 接線のまわりの回転を最小にした標構（OCCT の `GeomFill_IsCorrectedFrenet` 相当）
+    ///
+    /// 始点ではフレネ標構と一致し、経路が捩れても断面がねじれない。
+    CorrectedFrenet,
+}
+
+/// 形状を経路 `path` に沿って掃引する（OCCT の `BRepOffsetAPI_MakePipe` 相当）
+///
+/// エッジからはフェイス、ワイヤからはシェル、平面のフェイスからは両端に蓋をした立体を作る。
+/// 形状は置かれた位置のまま、経路の始点での座標系から各点での座標系へ剛体変換で運ぶ。
+/// 座標系は経路の接線を主方向とし、法線の決め方を `mode` で選ぶ。
+/// 側面は断面の曲線を経路上の点で厳密に通る B-スプライン曲面で、u は断面の曲線を
+/// 有理 B-スプラインにしたときのパラメータ、v は経路のパラメータになる。
+/// 元の形状のエッジは B-スプライン曲線のエッジに置き換える（頂点は元のものを使う）。
+///
+/// 経路が退化エッジや接線が 0 になる点を持つ場合、エッジ・ワイヤ・平面のフェイス以外の形状の場合、
+/// 経路がフェイスと平行な場合、双曲線や螺旋のエッジがある場合はエラーを返す。
+pub fn sweep(profile: &Shape, path: &Edge, mode: FrameMode) -> Result<Shape> {
+    let mut pipe = Pipe::new(path, mode)?;
+    match profile {
+        Shape::Edge(edge) => pipe
+            .side_face(edge, false)?
+            .map(Shape::from)
+            .ok_or_else(|| {
+                OcctKrsError::DegenerateGeometry("退化エッジは掃引できません".to_string())
+            }),
+        Shape::Wire(wire) => Ok(Shell::new(pipe.side_faces(wire, false)?)?.into()),
+        Shape::Face(face) => pipe.solid(face).map(Shape::from),
+        other => Err(OcctKrsError::InvalidInput(format!(
+            "{:?} は掃引できません（エッジ・ワイヤ・フェイスのみ）",
+            other.kind()
+        ))),
+    }
+}
+
+/// 複数の断面を、経路 `path` に沿って形を変えながら掃引する（OCCT の `BRepOffsetAPI_MakePipeShell` 相当）
+///
+/// 断面は置かれた位置のまま使い、重心に最も近い経路上の点をその断面の位置とする。経路上の各点では、
+/// 前後の断面を始点の座標系へ戻して対応する制御点を同次座標で線形に補間し、その点の座標系へ運んだ
+/// 断面を作る（最初の断面より前と最後の断面より後では、その断面をそのまま運ぶ）。座標系の決め方は
+/// [`sweep`] と同じ。作った断面は [`loft`](crate::loft) と同じように滑らかな B-スプライン曲面で結び、
+/// エッジの数が揃わない断面は長いエッジから順に半分に分割して揃える。
+/// `solid` が `true` なら両端に平面の蓋をして立体にし、`false` ならシェルを返す。
+///
+/// 断面がない場合、断面が経路に沿って順に並んでいない場合、閉じた断面と開いた断面が混ざる場合、
+/// 経路が退化エッジや接線が 0 になる点を持つ場合、`solid` が `true` で断面が開いているか平面でない場合、
+/// 双曲線や螺旋のエッジがある場合はエラーを返す。
+pub fn sweep_sections(
+    profiles: &[Wire],
+    path: &Edge,
+    mode: FrameMode,
+    solid: bool,
+) -> Result<Shape> {
+    if profiles.is_empty() {
+        return Err(OcctKrsError::InvalidInput(
+            "掃引する断面がありません".to_string(),
+        ));
+    }
+    let periodic = profiles[0].is_closed();
+    if profiles.iter().any(|w| w.is_closed() != periodic) {
+        return Err(OcctKrsError::InvalidInput(
+            "閉じた断面と開いた断面が混ざっています".to_string(),
+        ));
+    }
+    let curve = path
+        .located_geometry()
+        .ok_or_else(|| OcctKrsError::InvalidInput("退化エッジは経路にできません".to_string()))?;
+    let range = path.range();
+    let positions: Vec<f64> = profiles
+        .iter()
+        .map(|w| {
+            let points = wire_points(w);
+            let centroid = points.iter().fold(Vector3::ZERO, |s, &p| s + p) / points.len() as f64;
+            nearest_parameter(&curve, range, Point3::from(centroid))
+        })
+        .collect();
+    if positions.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(OcctKrsError::InvalidInput(
+            "断面が経路に沿って順に並んでいません".to_string(),
+        ));
+    }
+
+    // 等分した点に断面の位置を加えたステーション（断面の位置に近すぎる等分点は除く）
+    let (s0, s1) = range;
+    let gap = (s1 - s0) / (4 * SPANS) as f64;
+    let mut params: Vec<f64> = (0..=SPANS)
+        .map(|k| s0 + (s1 - s0) * k as f64 / SPANS as f64)
+        .filter(|s| positions.iter().all(|p| (s - p).abs() > gap))
+        .chain(positions.iter().copied())
+        .collect();
+    params.sort_by(f64::total_cmp);
+    let frames = frames(&curve, &params, mode)?;
+    let moves: Vec<Transform> = frames
+        .iter()
+        .map(|f| Transform::displacement(&frames[0], f))
+        .collect();
+
+    // 断面を始点の座標系へ戻し、エッジの数と曲線の形を揃える
+    let mut shapes = profiles
+        .iter()
+        .map(|w| Profile::new(w, periodic))
+        .collect::<Result<Vec<_>>>()?;
+    let count = shapes.iter().map(|p| p.curves.len()).max().unwrap_or(0);
+    let mut locals: Vec<Vec<BSplineCurve>> = vec![Vec::new(); count];
+    for (shape, position) in shapes.iter_mut().zip(&positions) {
+        shape.split_to(count)?;
+        let k = params.iter().position(|s| s == position).unwrap_or(0);
+        let back = moves[k].inverse().ok_or_else(|| {
+            OcctKrsError::DegenerateGeometry("経路の座標系が求まりません".to_string())
+        })?;
+        for (i, c) in shape.curves.iter().enumerate() {
+            locals[i].push(c.transformed(&back));
+        }
+    }
+    let locals = locals
+        .iter()
+        .map(|curves| BSplineCurve::make_compatible(curves))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut sections = Vec::with_capacity(params.len());
+    for (s, m) in params.iter().zip(&moves) {
+        // 前後の断面と、後ろの断面の割合
+        let next = positions
+            .iter()
+            .position(|p| p > s)
+            .unwrap_or(positions.len());
+        let (a, b, t) = match next {
+            0 => (0, 0, 0.0),
+            n if n == positions.len() => (n - 1, n - 1, 0.0),
+            n => (
+                n - 1,
+                n,
+                (s - positions[n - 1]) / (positions[n] - positions[n - 1]),
+            ),
+        };
+        let curves = locals
+            .iter()
+            .map(|c| Ok(blend(&c[a], &c[b], t)?.transformed(m)))
+            .collect::<Result<Vec<_>>>()?;
+        sections.push(section_wire(curves, periodic)?);
+    }
+    crate::loft(&sections, false, solid)
+}
+
+/// 互換な2本の曲線の制御点を、同次座標で `a` から `b` へ割合 `t` だけ補間した曲線
+fn blend(a: &BSplineCurve, b: &BSplineCurve, t: f64) -> Result<BSplineCurve> {
+    let n = a.control_points().len();
+    let mut points = Vec::with_capacity(n);
+    let mut weights = Vec::with_capacity(n);
+    for j in 0..n {
+        let (wa, wb) = (a.weight(j) * (1.0 - t), b.weight(j) * t);
+        let w = wa + wb;
+        points.push(Point3::from(
+            (a.control_points()[j].to_vector() * wa + b.control_points()[j].to_vector() * wb) / w,
+        ));
+        weights.push(w);
+    }
+    let curve = BSplineCurve::from_flat_knots(a.degree(), points, a.flat_knots().to_vec())?;
+    if a.is_rational() || b.is_rational() {
+        curve.with_weights(weights)
+    } else {
+        Ok(curve)
+    }
+}
+
+/// 曲線を順につないだ断面のワイヤ（`periodic` なら最後の曲線の終点を最初の頂点にする）
+fn section_wire(curves: Vec<BSplineCurve>, periodic: bool) -> Result<Wire> {
+    let mut vertices = Vec::with_capacity(curves.len() + 1);
+    for (i, c) in curves.iter().enumerate() {
+        // 補間した端点は前の曲線の終点とわずかにずれうるので、その分を頂点の許容誤差に含める
+        let gap = match i.checked_sub(1) {
+            Some(prev) => curves[prev].end_point().distance(c.start_point()),
+            None if periodic => curves[curves.len() - 1]
+                .end_point()
+                .distance(c.start_point()),
+            None => 0.0,
+        };
+        vertices
+            .push(Vertex::new(c.start_point()).with_tolerance(gap.max(precision::confusion()))?);
+    }
+    if !periodic {
+        let end = curves[curves.len() - 1].end_point();
+        vertices.push(Vertex::new(end));
+    }
+    let n = vertices.len();
+    let edges = curves
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| {
+            Edge::new(
+                c,
+                (0.0, 1.0),
+                vertices[i].clone(),
+                vertices[(i + 1) % n].clone(),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Wire::new(edges)
+}
+
+/// 点 `p` に最も近い、曲線のパラメータ区間 `(s0, s1)` 内のパラメータ
+fn nearest_parameter(curve: &GeomCurve, (s0, s1): (f64, f64), p: Point3) -> f64 {
+    let mut best = (curve.point_at(s0).distance(p), s0);
+    let end = curve.point_at(s1).distance(p);
+    if end < best.0 {
+        best = (end, s1);
+    }
+    for proj in curve.project(p) {
+        if proj.parameter >= s0 && proj.parameter <= s1 && proj.distance < best.0 {
+            best = (proj.distance, proj.parameter);
+        }
+    }
+    best.1
+}
+
+/// 経路上の点（ステーション）と、そこへ断面を運ぶ変換、および掃引の途中で作った頂点・エッジの表
+struct Pipe {
+    /// ステーションでの経路のパラメータ（曲面の v）
+    params: Vec<f64>,
+    /// v 方向の次数・フラットノットと、ステーションで補間する係数行列
+    degree: usize,
+    knots: Vec<f64>,
+    matrix: Vec<Vec<f64>>,
+    /// 始点の座標系から各ステーションの座標系への変換
+    moves: Vec<Transform>,
+    /// 始点での経路の接線
+    tangent: Vector3,
+    /// 元の頂点から、運んだ先の頂点と、元の頂点からそこへ伸びる稜線への対応
+    vertices: HashMap<ShapeId, (Vertex, Edge)>,
+    /// 元のエッジ（`Forward`）から、置き換えたエッジと運んだ先のエッジへの対応
+    edges: HashMap<ShapeId, (Edge, Edge)>,
+}
+
+impl Pipe {
+    fn new(path: &Edge, mode: FrameMode) -> Result<Self> {
+        let curve = path.located_geometry().ok_or_else(|| {
+            OcctKrsError::InvalidInput("退化エッジは経路にできません".to_string())
+        })?;
+        let (s0, s1) = path.range();
+        // 螺旋は1巻きごとに `SPANS` 区間に分ける
+        let spans = match *curve {
+            GeomCurve::Line(_) => 1,
+            GeomCurve::Helix(h) => SPANS * (h.turns().ceil() as usize).max(1),
+            _ => SPANS,
+        };
+        let params: Vec<f64> = (0..=spans)
+            .map(|k| s0 + (s1 - s0) * k as f64 / spans as f64)
+            .collect();
+        let frames = frames(&curve, &params, mode)?;
+        let moves = frames
+            .iter()
+            .map(|f| Transform::displacement(&frames[0], f))
+            .collect();
+        let degree = 3.min(spans);
+        let knots = averaged_knots(&params, degree);
+        let matrix = interpolation_matrix(&params, &knots, degree);
+        Ok(Self {
+            params,
+            degree,
+            knots,
+            matrix,
+            moves,
+            tangent: frames[0].direction().to_vector(),
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+        })
+    }
+
+    /// 終点のステーションへ運ぶ変換
+    fn last_move(&self) -> &Transform {
+        &self.moves[self.moves.len() - 1]
+    }
+
+    /// 各ステーションへ運んだ点 `p` を通る、v 方向の制御点列
+    fn column(&self, p: Point3) -> Result<Vec<Point3>> {
+        let rhs = self
+            .moves
+            .iter()
+            .map(|m| m.transform_point(p).to_vector())
+            .collect();
+        let poles = solve_dense(self.matrix.clone(), rhs).ok_or_else(|| {
+            OcctKrsError::DegenerateGeometry("補間の方程式が解けません".to_string())
+        })?;
+        Ok(poles.into_iter().map(Point3::from).collect())
+    }
+
+    /// 断面の曲線を各ステーションへ運んだ位置で通る曲面
+    ///
+    /// 同じ制御点の列は重みが一定なので、運んだ制御点をそのまま v 方向に補間すれば有理曲面でも厳密に通る。
+    fn surface(&self, section: &BSplineCurve) -> Result<BSplineSurface> {
+        let grid = section
+            .control_points()
+            .iter()
+            .map(|&p| self.column(p))
+            .collect::<Result<Vec<_>>>()?;
+        let surface = BSplineSurface::from_flat_knots(
+            section.degree(),
+            self.degree,
+            grid,
+            section.flat_knots().to_vec(),
+            self.knots.clone(),
+        )?;
+        match section.weights() {
+            Some(w) => {
+                surface.with_weights(w.iter().map(|&w| vec![w; self.params.len()]).collect())
+            }
+            None => Ok(surface),
+        }
+    }
+
+    /// 頂点を運んだ先の頂点と、元の頂点からそこへ伸びる稜線
+    fn lateral(&mut self, vertex: &Vertex) -> Result<(Vertex, Edge)> {
+        if let Some(found) = self.vertices.get(&vertex.id()) {
+            return Ok(found.clone());
+        }
+        let p = vertex.point();
+        let top =
+            Vertex::new(self.last_move().transform_point(p)).with_tolerance(vertex.tolerance())?;
+        let curve =
+            BSplineCurve::from_flat_knots(self.degree, self.column(p)?, self.knots.clone())?;
+        let range = (self.params[0], self.params[self.params.len() - 1]);
+        let edge = Edge::new(
+            curve,
+            range,
+            vertex.oriented(Orientation::Forward),
+            top.clone(),
+        )?;
+        self.vertices
+            .insert(vertex.id(), (top.clone(), edge.clone()));
+        Ok((top, edge))
+    }
+
+    /// `Forward` のエッジを置き換えた B-スプライン曲線のエッジと、それを終点へ運んだエッジ
+    fn section_edges(&mut self, edge: &Edge) -> Result<(Edge, Edge)> {
+        if let Some(found) = self.edges.get(&edge.id()) {
+            return Ok(found.clone());
+        }
+        let (top_start, _) = self.lateral(&edge.start_vertex())?;
+        let (top_end, _) = self.lateral(&edge.end_vertex())?;
+        let tolerance = edge.tolerance();
+        let found = match edge.located_geometry() {
+            Some(curve) => {
+                let section = bspline_section(&curve, edge.range())?;
+                let range = (section.first_parameter(), section.last_parameter());
+                let top = section.transformed(self.last_move());
+                (
+                    Edge::new(section, range, edge.start_vertex(), edge.end_vertex())?,
+                    Edge::new(top, range, top_start, top_end)?,
+                )
+            }
+            None => (
+                Edge::degenerate(edge.start_vertex(), edge.range())?,
+                Edge::degenerate(top_start, edge.range())?,
+            ),
+        };
+        let found = (
+            found.0.with_tolerance(tolerance)?,
+            found.1.with_tolerance(tolerance)?,
+        );
+        self.edges.insert(edge.id(), found.clone());
+        Ok(found)
+    }
+
+    /// ワイヤの各エッジを掃引した側面（退化エッジは除く）
+    fn side_faces(&mut self, wire: &Wire, flip: bool) -> Result<Vec<Face>> {
+        let mut faces = Vec::with_capacity(wire.len());
+        for edge in wire.edges() {
+            faces.extend(self.side_face(&edge, flip)?);
+        }
+        Ok(faces)
+    }
+
+    /// エッジを掃引した側面（退化エッジでは `None`）
+    ///
+    /// 曲線の接線と経路の接線の外積の側を表にし、エッジが反転していれば裏返す。
+    /// `flip` が `true` なら、さらに裏返す。
+    fn side_face(&mut self, edge: &Edge, flip: bool) -> Result<Option<Face>> {
+        if edge.is_degenerate() {
+            return Ok(None);
+        }
+        let e = edge.oriented(Orientation::Forward);
+        let (bottom, top) = self.section_edges(&e)?;
+        let (_, start) = self.lateral(&e.start_vertex())?;
+        let (_, end) = self.lateral(&e.end_vertex())?;
+        let section = match bottom.located_geometry().as_deref() {
+            Some(GeomCurve::BSpline(c)) => c.clone(),
+            _ => {
+                return Err(OcctKrsError::DegenerateGeometry(
+                    "断面の曲線が B-スプラインではありません".to_string(),
+                ))
+            }
+        };
+        let surface = self.surface(&section)?;
+        let (u0, u1) = bottom.range();
+        let (v0, v1) = (self.params[0], self.params[self.params.len() - 1]);
+        let uv = |u: f64, v: f64| Vector2::new(u, v);
+        let pcurves = vec![vec![
+            Some(uv_segment(uv(u0, v0), uv(u1, v0), (u0, u1))?),
+            Some(uv_segment(uv(u1, v0), uv(u1, v1), (v0, v1))?),
+            Some(uv_segment(uv(u0, v1), uv(u1, v1), (u0, u1))?),
+            Some(uv_segment(uv(u0, v0), uv(u0, v1), (v0, v1))?),
+        ]];
+        let wire = Wire::new(vec![bottom, end, top.reversed(), start.reversed()])?;
+        let face = Face::with_pcurves(surface, vec![wire], pcurves)?;
+        let reversed = (edge.orientation() == Orientation::Reversed) ^ flip;
+        Ok(Some(if reversed { face.reversed() } else { face }))
+    }
+
+    /// 平面のフェイスを掃引した立体
+    fn solid(&mut self, face: &Face) -> Result<Solid> {
+        if !matches!(face.surface(), GeomSurface::Plane(_)) {
+            return Err(OcctKrsError::InvalidInput(
+                "掃引できるのは平面のフェイスだけです".to_string(),
+            ));
+        }
+        // 外側のワイヤの面積ベクトルで、フェイスの表が経路の向きを向いているか調べる
+        let outer = face.outer_wire().ok_or_else(|| {
+            OcctKrsError::InvalidInput("境界を持たないフェイスは掃引できません".to_string())
+        })?;
+        let area = area_vector(&wire_points(&outer));
+        let height = area.dot(self.tangent);
+        if height.abs() <= precision::confusion() * area.length() {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "掃引する経路がフェイスと平行です".to_string(),
+            ));
+        }
+        let flip = height < 0.0;
+        let mut faces = Vec::new();
+        for wire in face.wires() {
+            faces.extend(self.side_faces(&wire, flip)?);
+        }
+        // 蓋はフェイスの向きによらず、格納された順とエッジの向きで作る
+        let cap = face.oriented(Orientation::Forward);
+        let mut bottom_wires = Vec::new();
+        let mut top_wires = Vec::new();
+        for wire in cap.wires() {
+            let mut bottom = Vec::with_capacity(wire.len());
+            let mut top = Vec::with_capacity(wire.len());
+            for edge in wire.edges() {
+                let (b, t) = self.section_edges(&edge.oriented(Orientation::Forward))?;
+                bottom.push(b.oriented(edge.orientation()));
+                top.push(t.oriented(edge.orientation()));
+            }
+            bottom_wires.push(Wire::new(bottom)?);
+            top_wires.push(Wire::new(top)?);
+        }
+        let surface = cap.located_geometry();
+        let cap_face = |surface: GeomSurface, wires: Vec<Wire>| -> Result<Face> {
+            Ok(Face::new(surface, wires)?
+                .with_tolerance(face.tolerance())?
+                .oriented(face.orientation()))
+        };
+        let bottom = cap_face(surface.clone().into_owned(), bottom_wires)?;
+        let top = cap_face(surface.transformed(self.last_move()), top_wires)?;
+        // 元のフェイスの表が経路の向きを向いていれば、始点の蓋を裏返す
+        let (bottom, top) = if flip {
+            (bottom, top.reversed())
+        } else {
+            (bottom.reversed(), top)
+        };
+        faces.insert(0, bottom);
+        faces.push(top);
+        Solid::new(vec![Shell::new(faces)?])
+    }
+}
+
+/// エッジの曲線のパラメータ区間 `range` を厳密に表す B-スプライン曲線
+///
+/// 円と楕円は有理 B-スプラインにするので、パラメータは元の曲線と区間の端でのみ一致する。
+pub(crate) fn bspline_section(curve: &GeomCurve, (t0, t1): (f64, f64)) -> Result<BSplineCurve> {
+    match curve {
+        GeomCurve::Line(l) => l.to_nurbs(t0, t1),
+        GeomCurve::Circle(c) => Ok(Arc::new(*c, t0, t1)?.to_nurbs()),
+        GeomCurve::Ellipse(e) => {
+            // 単位円の円弧を楕円の軸の長さに拡大する（アフィン変換は制御点に適用すればよい）
+            let unit = Arc::new(Circle::new(Axis2::world(), 1.0)?, t0, t1)?.to_nurbs();
+            let position = e.position();
+            let (a, b) = (e.major_radius(), e.minor_radius());
+            let points = unit
+                .control_points()
+                .iter()
+                .map(|p| position.point_at(a * p.x, b * p.y, 0.0))
+                .collect();
+            let curve =
+                BSplineCurve::from_flat_knots(unit.degree(), points, unit.flat_knots().to_vec())?;
+            match unit.weights() {
+                Some(w) => curve.with_weights(w.to_vec()),
+                None => Ok(curve),
+            }
+        }
+        GeomCurve::Parabola(p) => p.to_nurbs(t0, t1),
+        GeomCurve::BSpline(c) => c.trim(t0, t1),
+        other => Err(OcctKrsError::InvalidInput(format!(
+            "掃引できない曲線のエッジです: {:?}",
+            other
+        ))),
+    }
+}
+
+/// 経路のパラメータ `params` での座標系（原点は経路上の点、主方向は接線、X方向は法線）
+fn frames(curve: &GeomCurve, params: &[f64], mode: FrameMode) -> Result<Vec<Axis3>> {
+    let tangent = |s: f64| {
+        Dir::from_vector(curve.derivative_at(s)).map_err(|_| {
+            OcctKrsError::DegenerateGeometry(format!(
+                "経路の接線がパラメータ {} で 0 になります",
+                s
+            ))
+        })
+    };
+    // 接線に垂直な成分を法線にする（垂直な成分がなければ `None`）
+    let normal = |t: Dir, v: Vector3| {
+        let n = v - t.to_vector() * v.dot(t.to_vector());
+        Dir::from_vector(n).ok()
+    };
+    let frenet = |s: f64, t: Dir| normal(t, curve.second_derivative_at(s));
+    // ベクトル `w` を `v` に垂直な平面で鏡映する（`v` が 0 なら何もしない）
+    let reflect = |w: Vector3, v: Vector3| {
+        let c = v.dot(v);
+        if c > 0.0 {
+            w - v * (2.0 * v.dot(w) / c)
+        } else {
+            w
+        }
+    };
+    let t0 = tangent(params[0])?;
+    let mut n = frenet(params[0], t0)
+        .unwrap_or_else(|| Axis2::from_normal(Point3::origin(), t0).x_direction());
+    let mut frames = vec![Axis3::new(curve.point_at(params[0]), t0, n)?];
+    let (mut s, mut t) = (params[0], t0);
+    for &next in &params[1..] {
+        match mode {
+            FrameMode::Frenet => {
+                t = tangent(next)?;
+                n = frenet(next, t)
+                    .or_else(|| normal(t, n.to_vector()))
+                    .unwrap_or(n);
+            }
+            // 二重反射法（Wang ほか 2008）で刻みごとに法線を運ぶ
+            FrameMode::CorrectedFrenet => {
+                let step = (next - s) / SUBSTEPS as f64;
+                for k in 0..SUBSTEPS {
+                    let (a, b) = (s + step * k as f64, s + step * (k + 1) as f64);
+                    let t1 = tangent(b)?;
+                    let chord = curve.point_at(b) - curve.point_at(a);
+                    let (r, tl) = (reflect(n.to_vector(), chord), reflect(t.to_vector(), chord));
+                    let r = reflect(r, t1.to_vector() - tl);
+                    n = normal(t1, r).unwrap_or(n);
+                    t = t1;
+                }
+            }
+        }
+        s = next;
+        frames.push(Axis3::new(curve.point_at(next), t, n)?);
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::checked_volume;
+    use crate::{Handedness, Helix, ShapeKind};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    /// xy 平面上で原点を中心とする半径 `r` の円板
+    fn disk(r: f64) -> Face {
+        let circle = Circle::new(Axis2::world(), r).unwrap();
+        let wire = Wire::new(vec![Edge::from_curve(circle).unwrap()]).unwrap();
+        Face::from_planar_wires(wire, Vec::new(), 1e-9).unwrap()
+    }
+
+    /// xy 平面上で原点を中心とする一辺 `a` の正方形
+    fn square(a: f64) -> Face {
+        let h = a / 2.0;
+        let p = [(-h, -h), (h, -h), (h, h), (-h, h)].map(|(x, y)| Point3::new(x, y, 0.0));
+        let edges = (0..4)
+            .map(|i| Edge::from_points(p[i], p[(i + 1) % 4]).unwrap())
+            .collect();
+        Face::from_planar_wires(Wire::from_edges(edges, 1e-9).unwrap(), Vec::new(), 1e-9).unwrap()
+    }
+
+    /// 原点から +z 方向に出て、xz 平面上を半径 `r` で 90° 曲がる円弧の経路
+    fn bend(r: f64) -> Edge {
+        let axis = Axis2::new(Point3::new(r, 0.0, 0.0), Dir::Y, Dir::X.reversed()).unwrap();
+        let circle = Circle::new(axis, r).unwrap();
+        Edge::new(
+            circle,
+            (0.0, FRAC_PI_2),
+            Vertex::new(Point3::origin()),
+            Vertex::new(Point3::new(r, 0.0, r)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sweep_along_line() {
+        let path = Edge::from_points(Point3::origin(), Point3::new(0.0, 0.0, 3.0)).unwrap();
+        let solid = sweep(&disk(1.0).into(), &path, FrameMode::Frenet).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / (3.0 * PI) - 1.0).abs() < 1e-6, "{}", volume);
+        // 裏返したフェイスでも外向きの立体になる
+        let solid = sweep(&square(1.0).reversed().into(), &path, FrameMode::Frenet).unwrap();
+        let volume = checked_volume(&solid);
+        assert!((volume / 3.0 - 1.0).abs() < 1e-6, "{}", volume);
+        assert_eq!(solid.faces().len(), 6);
+    }
+
+    #[test]
+    fn test_sweep_along_bend() {
+        // パップスの定理で、体積は断面積と断面の重心が動く長さの積
+        for mode in [FrameMode::Frenet, FrameMode::CorrectedFrenet] {
+            let solid = sweep(&disk(0.5).into(), &bend(2.0), mode).unwrap();
+            let volume = checked_volume(&solid);
+            let expected = PI * 0.25 * PI;
+            assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+            // 終点の蓋は x 方向を向く
+            let faces = solid.faces();
+            let top = faces.last().unwrap();
+            let p = top.outer_wire().unwrap().edges()[0].start_point();
+            assert!((p.x - 2.0).abs() < 1e-9, "{:?}", p);
+        }
+    }
+
+    #[test]
+    fn test_corrected_frame_does_not_twist() {
+        let helix = Helix::new(Axis2::world(), 2.0, 4.0, 2.0, Handedness::Right).unwrap();
+        let curve = GeomCurve::from(helix);
+        let params: Vec<f64> = (0..=SPANS)
+            .map(|k| 4.0 * PI * k as f64 / SPANS as f64)
+            .collect();
+        let frenet = frames(&curve, &params, FrameMode::Frenet).unwrap();
+        let corrected = frames(&curve, &params, FrameMode::CorrectedFrenet).unwrap();
+        assert_eq!(frenet[0].x_direction(), corrected[0].x_direction());
+        // フレネ標構は接線のまわりに捩率 τ の速さで回るので、終点では回転を最小にする標構が
+        // フレネ標構から -τL だけ遅れる（L は経路の長さ）
+        let (r, c) = (2.0, 4.0 / (2.0 * PI));
+        let torsion = c / (r * r + c * c);
+        let length = 4.0 * PI * (r * r + c * c).sqrt();
+        let (f, g) = (frenet[SPANS], corrected[SPANS]);
+        let (nf, nc) = (f.x_direction().to_vector(), g.x_direction().to_vector());
+        let angle = nf
+            .cross(nc)
+            .dot(f.direction().to_vector())
+            .atan2(nf.dot(nc));
+        let expected = -torsion * length;
+        let diff = (angle - expected + PI).rem_euclid(2.0 * PI) - PI;
+        assert!(diff.abs() < 1e-2, "{} {}", angle, expected);
+
+        // 始点で経路に垂直に置いた正方形を掃引する
+        let start = Transform::displacement(&Axis3::from(Axis2::world()), &corrected[0]);
+        let profile = Shape::from(square(0.5).moved(&start));
+        let path = Edge::from_curve(helix).unwrap();
+        let solid = sweep(&profile, &path, FrameMode::CorrectedFrenet).unwrap();
+        // 側面は標構を補間した近似曲面なので、体積もその誤差の分だけずれる
+        let volume = checked_volume(&solid);
+        assert!((volume / (0.25 * length) - 1.0).abs() < 1e-3, "{}", volume);
+    }
+
+    #[test]
+    fn test_sweep_wire_and_edge() {
+        let wire = square(1.0).outer_wire().unwrap();
+        let shell = sweep(&wire.into(), &bend(3.0), FrameMode::CorrectedFrenet).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+        assert!(shell.check().is_valid());
+        assert_eq!(shell.free_bounds().unwrap().free_edge_count(), 8);
+
+        let edge = Edge::from_points(Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let face = sweep(&edge.unwrap().into(), &bend(3.0), FrameMode::Frenet).unwrap();
+        assert_eq!(face.kind(), ShapeKind::Face);
+        assert!(face.check().is_valid());
+    }
+
+    #[test]
+    fn test_sweep_sections_nozzle() {
+        // 半径 1 から 0.5 へ細くなる円錐台
+        let path = Edge::from_points(Point3::origin(), Point3::new(0.0, 0.0, 4.0)).unwrap();
+        let top = Transform::from_translation(Vector3::new(0.0, 0.0, 4.0));
+        let profiles = [
+            disk(1.0).outer_wire().unwrap(),
+            disk(0.5).outer_wire().unwrap().moved(&top),
+        ];
+        let solid = sweep_sections(&profiles, &path, FrameMode::Frenet, true).unwrap();
+        let volume = checked_volume(&solid);
+        let expected = PI * 4.0 / 3.0 * (1.0 + 0.5 + 0.25);
+        assert!((volume / expected - 1.0).abs() < 1e-6, "{}", volume);
+        // 断面が1つなら通常の掃引と同じ
+        let shell = sweep_sections(&profiles[..1], &path, FrameMode::Frenet, false).unwrap();
+        assert_eq!(shell.kind(), ShapeKind::Shell);
+    }
+
+    #[test]
+    fn test_sweep_sections_morph_along_bend() {
+        // 始点の正方形から、途中の円を経て、終点の小さな正方形へ変わるダクト
+        let path = bend(2.0);
+        let curve = path.located_geometry().unwrap().into_owned();
+        let place = |s: f64| {
+            let frame = frames(&curve, &[s], FrameMode::CorrectedFrenet).unwrap()[0];
+            Transform::displacement(&Axis3::from(Axis2::world()), &frame)
+        };
+        let profiles = [
+            square(1.0).outer_wire().unwrap().moved(&place(0.0)),
+            disk(0.5).outer_wire().unwrap().moved(&place(FRAC_PI_4)),
+            square(0.6).outer_wire().unwrap().moved(&place(FRAC_PI_2)),
+        ];
+        let solid = sweep_sections(&profiles, &path, FrameMode::CorrectedFrenet, true).unwrap();
+        let volume = checked_volume(&solid);
+        assert!(volume > 0.0);
+        // 両端の蓋は元の断面の位置にある
+        let faces = solid.faces();
+        for (cap, corner) in [
+            (&faces[0], Point3::new(0.5, 0.5, 0.0)),
+            (faces.last().unwrap(), Point3::new(2.0, 0.3, 2.3)),
+        ] {
+            let nearest = cap
+                .outer_wire()
+                .unwrap()
+                .edges()
+                .iter()
+                .map(|e| e.start_point().distance(corner))
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest < 1e-9, "{}", nearest);
+        }
+    }
+
+    #[test]
+    fn test_sweep_sections_errors() {
+        let path = Edge::from_points(Point3::origin(), Point3::new(0.0, 0.0, 4.0)).unwrap();
+        assert!(sweep_sections(&[], &path, FrameMode::Frenet, true).is_err());
+        let up = Transform::from_translation(Vector3::new(0.0, 0.0, 2.0));
+        let wire = disk(1.0).outer_wire().unwrap();
+        // 経路の順に並んでいない
+        let reversed = [wire.moved(&up), wire.clone()];
+        assert!(sweep_sections(&reversed, &path, FrameMode::Frenet, true).is_err());
+        let open = Wire::new(vec![Edge::from_points(
+            Point3::new(-1.0, 0.0, 2.0),
+            Point3::new(1.0, 0.0, 2.0),
+        )
+        .unwrap()])
+        .unwrap();
+        assert!(sweep_sections(&[wire, open], &path, FrameMode::Frenet, false).is_err());
+    }
+
+    #[test]
+    fn test_sweep_errors() {
+        let path = Edge::from_points(Point3::origin(), Point3::new(1.0, 0.0, 0.0)).unwrap();
+        // 経路がフェイスと平行
+        assert!(sweep(&square(1.0).into(), &path, FrameMode::Frenet).is_err());
+        let vertex = Shape::from(Vertex::new(Point3::origin()));
+        assert!(sweep(&vertex, &path, FrameMode::Frenet).is_err());
+        let degenerate = Edge::degenerate(Vertex::new(Point3::origin()), (0.0, 1.0)).unwrap();
+        assert!(sweep(&square(1.0).into(), &degenerate, FrameMode::Frenet).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Axis1, Curve3, Dir, Point3, Vector3};
+
+/// 曲線を軸のまわりに回転して得られる回転面（OCCT の `Geom_SurfaceOfRevolution` 相当）
+///
+/// `P(u, v)` は母線 `C(v)` を軸のまわりに角度 `u` だけ回転した点。
+/// u の範囲は 0〜2π、v の範囲は母線のパラメータ範囲となる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RevolvedSurface<C> {
+    curve: C,
+    axis: Axis1,
+}
+
+/// 曲線を一定方向に掃引して得られる押し出し面（OCCT の `Geom_SurfaceOfLinearExtrusion` 相当）
+///
+/// `P(u, v) = C(u) + v · D`。u の範囲は曲線のパラメータ範囲、v は無限となる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtrudedSurface<C> {
+    curve: C,
+    direction: Dir,
+}
+
+impl<C: Curve3> RevolvedSurface<C> {
+    /// 母線と回転軸から回転面を生成する
+    pub fn new(curve: C, axis: Axis1) -> Self {
+        Self { curve, axis }
+    }
+
+    /// 母線を返す
+    pub fn basis_curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// 回転軸を返す
+    pub fn axis(&self) -> Axis1 {
+        self.axis
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.axis.location + self.rotate(self.curve.point_at(v) - self.axis.location, u)
+    }
+
+    /// ベクトルを回転軸のまわりに角度 `u` だけ回転する
+    pub(crate) fn rotate(&self, w: Vector3, u: f64) -> Vector3 {
+        let (axial, radial, normal) = self.decompose(w);
+        let (s, c) = u.sin_cos();
+        axial + radial * c + normal * s
+    }
+
+    /// `rotate` の角度 `u` についての微分を返す
+    pub(crate) fn rotate_derivative(&self, w: Vector3, u: f64) -> Vector3 {
+        let (_, radial, normal) = self.decompose(w);
+        let (s, c) = u.sin_cos();
+        normal * c - radial * s
+    }
+
+    /// `rotate` の角度 `u` についての2階微分を返す
+    pub(crate) fn rotate_second_derivative(&self, w: Vector3, u: f64) -> Vector3 {
+        let (_, radial, normal) = self.decompose(w);
+        let (s, c) = u.sin_cos();
+        -(radial * c + normal * s)
+    }
+
+    /// ベクトルを軸方向成分・軸に垂直な成分・それを軸まわりに 90° 回転したものに分解する
+    fn decompose(&self, w: Vector3) -> (Vector3, Vector3, Vector3) {
+        let d = self.axis.direction.to_vector();
+        let axial = d * w.dot(d);
+        (axial, w - axial, d.cross(w))
+    }
+}
+
+impl<C: Curve3> ExtrudedSurface<C> {
+    /// 曲線と押し出し方向から押し出し面を生成する
+    pub fn new(curve: C, direction: Dir) -> Self {
+        Self { curve, direction }
+    }
+
+    /// 押し出す曲線を返す
+    pub fn basis_curve(&self) -> &C {
+        &self.curve
+    }
+
+    /// 押し出し方向を返す
+    pub fn direction(&self) -> Dir {
+        self.direction
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.curve.point_at(u) + self.direction.to_vector() * v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, Axis3, Circle, CylindricalSurface, Segment, Surface, ToroidalSurface};
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!((a - b).length() < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_revolved_segment_is_cylinder() {
+        let seg = Segment::new(Point3::new(2.0, 0.0, 0.0), Point3::new(2.0, 0.0, 3.0));
+        let s = RevolvedSurface::new(seg, Axis1::oz());
+        let cyl = CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::z_axis()), 2.0)
+            .unwrap();
+        for &(u, v) in &[(0.0, 0.0), (1.2, 0.5), (4.0, 1.0)] {
+            assert_point_eq(s.point_at(u, v), cyl.point_at(u, 3.0 * v));
+            let n = s.normal_at(u, v).unwrap();
+            assert!((n.to_vector() - cyl.normal_at(u, v).unwrap().to_vector()).length() < 1e-12);
+        }
+        assert!(s.is_u_closed() && !s.is_v_closed());
+        assert_eq!(s.v_range(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_revolved_circle_is_torus() {
+        let circle = Circle::new(
+            Axis2::new(Point3::new(3.0, 0.0, 0.0), -Dir::y_axis(), Dir::x_axis()).unwrap(),
+            1.0,
+        )
+        .unwrap();
+        let s = RevolvedSurface::new(circle, Axis1::oz());
+        let torus =
+            ToroidalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::z_axis()), 3.0, 1.0)
+                .unwrap();
+        for &(u, v) in &[(0.3, 0.2), (2.0, 4.0)] {
+            assert_point_eq(s.point_at(u, v), torus.point_at(u, v));
+        }
+        assert!(s.is_u_closed() && s.is_v_closed());
+    }
+
+    #[test]
+    fn test_extruded_circle_is_cylinder() {
+        let circle = Circle::new(Axis2::world(), 2.0).unwrap();
+        let s = ExtrudedSurface::new(circle, Dir::z_axis());
+        assert_point_eq(
+            s.point_at(0.5, 4.0),
+            Point3::new(2.0 * 0.5_f64.cos(), 2.0 * 0.5_f64.sin(), 4.0),
+        );
+        assert!(s.is_u_closed() && !s.is_v_closed());
+        assert!(s.bounding_box().is_infinite());
+    }
+
+    #[test]
+    fn test_exact_derivatives_match_differences() {
+        let arc = Circle::new(
+            Axis2::new(
+                Point3::new(3.0, 1.0, 0.5),
+                Dir::new(0.2, -1.0, 0.3).unwrap(),
+                Dir::x_axis(),
+            )
+            .unwrap(),
+            1.0,
+        )
+        .unwrap();
+        let axis = Axis1::new(Point3::new(0.0, 0.5, 0.0), Dir::new(0.1, 0.2, 1.0).unwrap());
+        let surfaces: Vec<Box<dyn Surface>> = vec![
+            Box::new(RevolvedSurface::new(arc, axis)),
+            Box::new(ExtrudedSurface::new(arc, Dir::new(1.0, 0.0, 1.0).unwrap())),
+        ];
+        let (u, v, h) = (0.8, 0.3, 1e-5);
+        for s in &surfaces {
+            let du = (s.point_at(u + h, v) - s.point_at(u - h, v)) / (2.0 * h);
+            let dv = (s.point_at(u, v + h) - s.point_at(u, v - h)) / (2.0 * h);
+            assert!((du - s.derivative_u_at(u, v)).length() < 1e-8);
+            assert!((dv - s.derivative_v_at(u, v)).length() < 1e-8);
+            let (suu, suv, svv) = s.second_derivatives_at(u, v);
+            let duu = (s.derivative_u_at(u + h, v) - s.derivative_u_at(u - h, v)) / (2.0 * h);
+            let duv = (s.derivative_u_at(u, v + h) - s.derivative_u_at(u, v - h)) / (2.0 * h);
+            let dvv = (s.derivative_v_at(u, v + h) - s.derivative_v_at(u, v - h)) / (2.0 * h);
+            assert!((suu - duu).length() < 1e-8);
+            assert!((suv - duv).length() < 1e-8);
+            assert!((svv - dvv).length() < 1e-8);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! テストで共通に使う補助関数
+
+use crate::fix::signed_volume;
+use crate::{Shape, ShapeKind, Shell};
+
+/// 形状が妥当で自由エッジがなく、シェルがすべて閉じていることを確かめ、シェルの符号付き体積の合計を返す
+pub(crate) fn checked_volume(shape: &Shape) -> f64 {
+    let report = shape.check();
+    assert!(report.is_valid(), "{:?}", report.issues());
+    assert!(shape.free_bounds().unwrap().is_empty());
+    let shells: Vec<Shell> = shape
+        .explore(ShapeKind::Shell)
+        .unique()
+        .filter_map(|s| Shell::try_from(s).ok())
+        .collect();
+    assert!(!shells.is_empty());
+    shells
+        .iter()
+        .map(|shell| {
+            assert!(shell.is_closed());
+            signed_volume(shell)
+        })
+        .sum()
+}
@@ -0,0 +1,323 @@
+use crate::boolean::{cut, fuse};
+use crate::extrude::wire_points;
+use crate::precision;
+use crate::{
+    sweep, Axis2, CylindricalSurface, Dir, Edge, Face, FrameMode, GeomSurface, Handedness, Helix,
+    OcctKrsError, Orientation, Point3, Result, Shape, Surface, Transform, Vector3, Wire,
+};
+
+/// ISO メートル並目ねじの呼び径とピッチ（ISO 261 の第1選択）
+const ISO_METRIC_COARSE: [(f64, f64); 22] = [
+    (1.0, 0.25),
+    (1.2, 0.25),
+    (1.6, 0.35),
+    (2.0, 0.4),
+    (2.5, 0.45),
+    (3.0, 0.5),
+    (4.0, 0.7),
+    (5.0, 0.8),
+    (6.0, 1.0),
+    (8.0, 1.25),
+    (10.0, 1.5),
+    (12.0, 1.75),
+    (14.0, 2.0),
+    (16.0, 2.0),
+    (20.0, 2.5),
+    (24.0, 3.0),
+    (30.0, 3.5),
+    (36.0, 4.0),
+    (42.0, 4.5),
+    (48.0, 5.0),
+    (56.0, 5.5),
+    (64.0, 6.0),
+];
+
+/// ISO メートル並目ねじのピッチを呼び径から返す（M1〜M64 の第1選択と M14）
+///
+/// 表にない呼び径の場合はエラーを返す。
+pub fn iso_metric_pitch(nominal_diameter: f64) -> Result<f64> {
+    ISO_METRIC_COARSE
+        .iter()
+        .find(|(d, _)| (d - nominal_diameter).abs() <= precision::confusion())
+        .map(|&(_, pitch)| pitch)
+        .ok_or_else(|| {
+            OcctKrsError::InvalidInput(format!(
+                "ISO メートル並目ねじにない呼び径です: {}",
+                nominal_diameter
+            ))
+        })
+}
+
+/// 円柱面のフェイス `face` に切る、ISO メートルねじの基本山形の溝の断面を返す
+///
+/// 断面は円柱の軸と継ぎ目（曲面の X 方向）を含む半平面上の台形で、フェイスの外向きの法線が軸から
+/// 離れる向き（おねじ）なら軸の側へ、軸へ向かう向き（めねじ）なら外側へ、とがり山の高さ
+/// `H = √3/2 · pitch` の 5/8 の深さまで掘る。面の外側へも `H/16` だけはみ出させて、ブーリアン演算で
+/// 面どうしが重ならないようにする。
+///
+/// フェイスが円柱面でない場合、ピッチが正の有限値でない場合、溝が軸に届く場合はエラーを返す。
+pub fn iso_thread_profile(face: &Face, pitch: f64) -> Result<Face> {
+    check_pitch(pitch)?;
+    let (cylinder, outward) = cylinder_of(face)?;
+    let position = cylinder.position();
+    let (z0, z1) = axial_range(face, &cylinder);
+    let radius = cylinder.radius();
+    let h = 3f64.sqrt() / 2.0 * pitch;
+    // (半径, 幅) の組で、溝の底と面の外側の辺を表す
+    let (inner, outer) = if outward {
+        (
+            (radius - 5.0 * h / 8.0, pitch / 4.0),
+            (radius + h / 16.0, pitch * 15.0 / 16.0),
+        )
+    } else {
+        (
+            (radius - h / 16.0, pitch * 13.0 / 16.0),
+            (radius + 5.0 * h / 8.0, pitch / 8.0),
+        )
+    };
+    if inner.0 <= precision::confusion() {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "ねじの溝が円柱の軸に届きます（半径 {}、ピッチ {}）",
+            radius, pitch
+        )));
+    }
+    let z = (z0 + z1) / 2.0;
+    let x = position.x_direction().to_vector();
+    let d = position.direction().to_vector();
+    let at = |r: f64, dz: f64| position.location() + x * r + d * (z + dz);
+    let corners = [
+        at(inner.0, -inner.1 / 2.0),
+        at(outer.0, -outer.1 / 2.0),
+        at(outer.0, outer.1 / 2.0),
+        at(inner.0, inner.1 / 2.0),
+    ];
+    let edges = (0..4)
+        .map(|i| Edge::from_points(corners[i], corners[(i + 1) % 4]))
+        .collect::<Result<Vec<_>>>()?;
+    let tolerance = precision::confusion();
+    Face::from_planar_wires(Wire::from_edges(edges, tolerance)?, Vec::new(), tolerance)
+}
+
+/// 立体 `shape` の円柱面のフェイス `face` にねじを切る
+///
+/// 断面 `profile`（円柱の軸を含む平面上のフェイス）を、その重心を通り軸方向に `pitch` ずつ進む
+/// 螺旋に沿ってフレネ標構で掃引し、断面の重心が立体の内側（フェイスの外向きの法線と反対の側）に
+/// あれば立体から取り除き、外側にあれば立体に加える。断面は軸方向の位置だけを螺旋の始点へ移して使い、
+/// 軸まわりの位置は断面の置かれた所のまま使う（ねじの位相になる）。取り除く場合は、溝が端面を
+/// 抜けるように、断面の軸方向の幅の半分と 1/4 ピッチだけ螺旋をフェイスの両端から延ばす。
+///
+/// フェイスが立体のものでないか円柱面でない場合、ピッチが正の有限値でない場合、断面の重心が軸上にある場合、
+/// 掃引やブーリアン演算に失敗した場合はエラーを返す。
+pub fn thread(
+    shape: &Shape,
+    face: &Face,
+    profile: &Face,
+    pitch: f64,
+    handedness: Handedness,
+) -> Result<Shape> {
+    check_pitch(pitch)?;
+    if !shape.faces().iter().any(|f| f.is_same(face)) {
+        return Err(OcctKrsError::InvalidInput(
+            "ねじを切るフェイスが立体にありません".to_string(),
+        ));
+    }
+    let (cylinder, outward) = cylinder_of(face)?;
+    let position = cylinder.position();
+    let (origin, d) = (position.location(), position.direction().to_vector());
+    let outer = profile.outer_wire().ok_or_else(|| {
+        OcctKrsError::InvalidInput("境界を持たない断面ではねじを切れません".to_string())
+    })?;
+    let points = wire_points(&outer);
+    let centroid = points.iter().fold(Vector3::ZERO, |s, &p| s + p) / points.len() as f64;
+    let w = Point3::from(centroid) - origin;
+    let height = w.dot(d);
+    // 断面の軸方向の幅の半分
+    let half_width = points
+        .iter()
+        .map(|&p| ((Point3::from(p) - origin).dot(d) - height).abs())
+        .fold(0.0, f64::max);
+    let radial = w - d * height;
+    let r = radial.length();
+    let radial = Dir::from_vector(radial).map_err(|_| {
+        OcctKrsError::InvalidInput("ねじの断面の重心が円柱の軸上にあります".to_string())
+    })?;
+    let removing = (r < cylinder.radius()) == outward;
+
+    let (z0, z1) = axial_range(face, &cylinder);
+    let (z0, z1) = if removing {
+        let margin = half_width + pitch / 4.0;
+        (z0 - margin, z1 + margin)
+    } else {
+        (z0, z1)
+    };
+    let start = Axis2::new(origin + d * z0, position.direction(), radial)?;
+    let helix = Helix::new(start, r, pitch, (z1 - z0) / pitch, handedness)?;
+    let section = profile.moved(&Transform::from_translation(d * (z0 - height)));
+    let tool = sweep(
+        &Shape::from(section),
+        &Edge::from_curve(helix)?,
+        FrameMode::Frenet,
+    )?;
+    if removing {
+        cut(shape, &tool)
+    } else {
+        fuse(shape, &tool)
+    }
+}
+
+/// 立体 `shape` の円柱面のフェイス `face` に、呼び径 `nominal_diameter` の ISO メートル並目ねじを切る
+///
+/// ピッチを [`iso_metric_pitch`] で選び、[`iso_thread_profile`] の溝を [`thread`] で取り除く。
+/// おねじではフェイスの直径を呼び径に、めねじでは谷の径を呼び径に合わせておくこと。
+pub fn iso_metric_thread(
+    shape: &Shape,
+    face: &Face,
+    nominal_diameter: f64,
+    handedness: Handedness,
+) -> Result<Shape> {
+    let pitch = iso_metric_pitch(nominal_diameter)?;
+    let profile = iso_thread_profile(face, pitch)?;
+    thread(shape, face, &profile, pitch, handedness)
+}
+
+fn check_pitch(pitch: f64) -> Result<()> {
+    if !(pitch > 0.0 && pitch.is_finite()) {
+        return Err(OcctKrsError::InvalidInput(format!(
+            "ねじのピッチが不正です: {}",
+            pitch
+        )));
+    }
+    Ok(())
+}
+
+/// 円柱面のフェイスの曲面（配置を適用したもの）と、外向きの法線が軸から離れる向きかどうか
+fn cylinder_of(face: &Face) -> Result<(CylindricalSurface, bool)> {
+    let surface = face.located_geometry();
+    let GeomSurface::Cylinder(cylinder) = surface.as_ref() else {
+        return Err(OcctKrsError::InvalidInput(
+            "ねじを切れるのは円柱面のフェイスだけです".to_string(),
+        ));
+    };
+    let radial = cylinder.position().x_direction().to_vector();
+    let normal = surface
+        .normal_at(0.0, 0.0)
+        .map_or(radial, |n| n.to_vector());
+    let away = normal.dot(radial) > 0.0;
+    Ok((
+        *cylinder,
+        away == (face.orientation() != Orientation::Reversed),
+    ))
+}
+
+/// フェイスの境界が円柱の軸方向に占める範囲
+fn axial_range(face: &Face, cylinder: &CylindricalSurface) -> (f64, f64) {
+    let position = cylinder.position();
+    let d = position.direction().to_vector();
+    face.wires()
+        .iter()
+        .flat_map(wire_points)
+        .map(|p| (Point3::from(p) - position.location()).dot(d))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), z| {
+            (lo.min(z), hi.max(z))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::make_cylinder;
+    use crate::test_support::checked_volume;
+    use std::f64::consts::PI;
+
+    fn side_face(shape: &Shape) -> Face {
+        shape
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Cylinder(_)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_iso_metric_pitch() {
+        assert_eq!(iso_metric_pitch(6.0).unwrap(), 1.0);
+        assert_eq!(iso_metric_pitch(10.0).unwrap(), 1.5);
+        assert!(iso_metric_pitch(7.0).is_err());
+    }
+
+    /// エッジに沿って軸（Z）まわりの角度が増えるとき高さも増える分の合計（右ねじなら正、左ねじなら負）
+    fn winding(shape: &Shape) -> f64 {
+        let mut sum = 0.0;
+        for edge in shape.edges() {
+            let (a, b) = edge.range();
+            let points: Vec<Point3> = (0..=16)
+                .map(|k| edge.point_at(a + (b - a) * k as f64 / 16.0))
+                .collect();
+            for w in points.windows(2) {
+                let turn =
+                    (w[1].y.atan2(w[1].x) - w[0].y.atan2(w[0].x) + PI).rem_euclid(2.0 * PI) - PI;
+                sum += turn * (w[1].z - w[0].z);
+            }
+        }
+        sum
+    }
+
+    /// 半径 3、高さ 1 の丸棒に M6 のねじを切り、体積と巻く向きを確かめる
+    fn check_m6_thread(handedness: Handedness) {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let rod = Shape::from(make_cylinder(position, 3.0, 1.0).unwrap());
+        let face = side_face(&rod);
+        let threaded = iso_metric_thread(&rod, &face, 6.0, handedness).unwrap();
+        let volume = checked_volume(&threaded);
+        // 溝の円柱内の部分（台形）の面積に、その重心が回る長さと巻き数を掛けたものだけ減る
+        let (p, h) = (1.0, 3f64.sqrt() / 2.0);
+        let depth = 5.0 * h / 8.0;
+        let (a, b) = (p / 4.0, 7.0 * p / 8.0);
+        let area = (a + b) / 2.0 * depth;
+        let centroid = 3.0 - depth + depth * (a + 2.0 * b) / (3.0 * (a + b));
+        let removed = area * 2.0 * PI * centroid / p;
+        let expected = PI * 9.0 - removed;
+        assert!(
+            (volume / expected - 1.0).abs() < 1e-4,
+            "{} {}",
+            volume,
+            expected
+        );
+        // 溝の螺旋のエッジは、右ねじなら軸まわりに回るほど上がり、左ねじなら下がる
+        let turn = winding(&threaded);
+        match handedness {
+            Handedness::Right => assert!(turn > 0.1, "{}", turn),
+            Handedness::Left => assert!(turn < -0.1, "{}", turn),
+        }
+    }
+
+    #[test]
+    fn test_iso_metric_thread() {
+        check_m6_thread(Handedness::Right);
+    }
+
+    #[test]
+    fn test_iso_metric_thread_left() {
+        check_m6_thread(Handedness::Left);
+    }
+
+    #[test]
+    fn test_thread_errors() {
+        let position = Axis2::new(Point3::origin(), Dir::Z, Dir::X).unwrap();
+        let rod = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let face = side_face(&rod);
+        let cap = rod
+            .faces()
+            .into_iter()
+            .find(|f| matches!(f.surface(), GeomSurface::Plane(_)))
+            .unwrap();
+        assert!(iso_thread_profile(&cap, 0.5).is_err());
+        assert!(iso_thread_profile(&face, 0.0).is_err());
+        // 溝が軸に届く
+        assert!(iso_thread_profile(&face, 2.0).is_err());
+        let profile = iso_thread_profile(&face, 0.25).unwrap();
+        assert!(thread(&rod, &face, &profile, f64::NAN, Handedness::Left).is_err());
+        // 別の立体のフェイス
+        let other = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        assert!(thread(&other, &face, &profile, 0.25, Handedness::Right).is_err());
+    }
+}
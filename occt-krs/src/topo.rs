@@ -0,0 +1,1757 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync;
+
+use crate::precision;
+use crate::{
+    Axis1, BSplineCurve2, Curve3, GeomCurve, GeomSurface, Location, OcctKrsError, Plane, Point3,
+    Result, Surface, Transform, Vector3,
+};
+
+/// 形状を識別する値（中身の所在と配置の組。同じ形状を指す間は一定）
+pub(crate) type ShapeId = (usize, Option<[u64; 8]>);
+
+/// 形状の種類（OCCT の `TopAbs_ShapeEnum` 相当）
+///
+/// 包含関係の上位ほど小さく、`Compound < Solid < Shell < Face < Wire < Edge < Vertex` の順に並ぶ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShapeKind {
+    Compound,
+    Solid,
+    Shell,
+    Face,
+    Wire,
+    Edge,
+    Vertex,
+}
+
+/// 親の形状に対する向き（OCCT の `TopAbs_Orientation` 相当）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Orientation {
+    /// 幾何と同じ向き（エッジは曲線の進行方向、フェイスは曲面の法線側が外）
+    Forward,
+    /// 幾何と逆向き
+    Reversed,
+    /// 領域の内部にある（両側が内部）
+    Internal,
+    /// 領域の外部にある（両側が外部）
+    External,
+}
+
+impl Orientation {
+    /// `Forward` と `Reversed` を入れ替えた向きを返す（`Internal`, `External` はそのまま）
+    pub fn reversed(self) -> Self {
+        match self {
+            Orientation::Forward => Orientation::Reversed,
+            Orientation::Reversed => Orientation::Forward,
+            o => o,
+        }
+    }
+
+    /// この向きの親の中で、向き `child` を持つ子の実際の向きを返す（OCCT の `TopAbs::Compose` 相当）
+    pub fn compose(self, child: Orientation) -> Self {
+        match self {
+            Orientation::Forward => child,
+            Orientation::Reversed => child.reversed(),
+            o => o,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VertexData {
+    point: Point3,
+    tolerance: f64,
+}
+
+#[derive(Debug, Clone)]
+struct EdgeData {
+    /// `None` は曲面の極などで長さを持たない退化エッジ
+    curve: Option<sync::Arc<GeomCurve>>,
+    range: (f64, f64),
+    start: Vertex,
+    end: Vertex,
+    tolerance: f64,
+}
+
+#[derive(Debug, Clone)]
+struct WireData {
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone)]
+struct FaceData {
+    surface: sync::Arc<GeomSurface>,
+    wires: Vec<Wire>,
+    /// `wires[i]` の `j` 番目のエッジのパラメータ空間上の曲線
+    pcurves: Vec<Vec<Option<sync::Arc<BSplineCurve2>>>>,
+    tolerance: f64,
+}
+
+#[derive(Debug, Clone)]
+struct ShellData {
+    faces: Vec<Face>,
+}
+
+#[derive(Debug, Clone)]
+struct SolidData {
+    shells: Vec<Shell>,
+}
+
+#[derive(Debug, Clone)]
+struct CompoundData {
+    shapes: Vec<Shape>,
+}
+
+/// 頂点（OCCT の `TopoDS_Vertex` 相当）
+///
+/// 位置と許容誤差を持つ。複製しても中身は共有され、`is_same` で同一の頂点か判定できる。
+#[derive(Debug, Clone)]
+pub struct Vertex {
+    data: sync::Arc<VertexData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 稜線（OCCT の `TopoDS_Edge` 相当）
+///
+/// 曲線のパラメータ区間 `[first, last]` と、その両端の頂点からなる。
+/// 向きが `Reversed` のときは曲線を逆にたどり、始点と終点が入れ替わる。
+#[derive(Debug, Clone)]
+pub struct Edge {
+    data: sync::Arc<EdgeData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// エッジを端点でつないだ列（OCCT の `TopoDS_Wire` 相当）
+#[derive(Debug, Clone)]
+pub struct Wire {
+    data: sync::Arc<WireData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 曲面を閉じたワイヤで囲んだ面（OCCT の `TopoDS_Face` 相当）
+///
+/// 最初のワイヤが外側の境界、残りが穴の境界。ワイヤを持たない面は曲面のパラメータ範囲全体を表す。
+/// 向きが `Forward` のときは曲面の法線の側が立体の外側になる。
+#[derive(Debug, Clone)]
+pub struct Face {
+    data: sync::Arc<FaceData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 辺でつながったフェイスの集まり（OCCT の `TopoDS_Shell` 相当）
+#[derive(Debug, Clone)]
+pub struct Shell {
+    data: sync::Arc<ShellData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 閉じたシェルで囲まれた立体（OCCT の `TopoDS_Solid` 相当）
+///
+/// 最初のシェルが外側の境界、残りが内部の空洞の境界。
+#[derive(Debug, Clone)]
+pub struct Solid {
+    data: sync::Arc<SolidData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 任意の形状の集まり（OCCT の `TopoDS_Compound` 相当）
+#[derive(Debug, Clone)]
+pub struct Compound {
+    data: sync::Arc<CompoundData>,
+    orientation: Orientation,
+    location: Location,
+}
+
+/// 種類を問わない形状（OCCT の `TopoDS_Shape` 相当）
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Vertex(Vertex),
+    Edge(Edge),
+    Wire(Wire),
+    Face(Face),
+    Shell(Shell),
+    Solid(Solid),
+    Compound(Compound),
+}
+
+/// 各形状に共通する向きの操作と、同一性の判定を実装する
+macro_rules! impl_shape_common {
+    ($($ty:ident),*) => {
+        $(
+            impl $ty {
+                /// 親の形状に対する向きを返す
+                pub fn orientation(&self) -> Orientation {
+                    self.orientation
+                }
+
+                /// 配置を返す
+                pub fn location(&self) -> Location {
+                    self.location
+                }
+
+                /// 中身を共有したまま向きを `orientation` にした形状を返す
+                pub fn oriented(&self, orientation: Orientation) -> Self {
+                    Self {
+                        orientation,
+                        ..self.clone()
+                    }
+                }
+
+                /// 中身を共有したまま向きを反転した形状を返す
+                pub fn reversed(&self) -> Self {
+                    self.oriented(self.orientation.reversed())
+                }
+
+                /// 中身を共有したまま配置を `location` にした形状を返す
+                pub fn located(&self, location: Location) -> Self {
+                    Self {
+                        location,
+                        ..self.clone()
+                    }
+                }
+
+                /// 中身を共有したまま、今の配置の後に `transform` を適用した形状を返す
+                /// （OCCT の `Moved` 相当。幾何は複製しない）
+                pub fn moved(&self, transform: &Transform) -> Self {
+                    self.located(Location::new(*transform) * self.location)
+                }
+
+                /// 向きを問わず、同じ中身を同じ配置で指している場合に `true` を返す
+                /// （OCCT の `IsSame` 相当）
+                pub fn is_same(&self, other: &Self) -> bool {
+                    self.is_partner(other) && self.location == other.location
+                }
+
+                /// 向きと配置を問わず中身を共有している場合に `true` を返す（OCCT の `IsPartner` 相当）
+                pub fn is_partner(&self, other: &Self) -> bool {
+                    sync::Arc::ptr_eq(&self.data, &other.data)
+                }
+
+                /// 形状を識別する値（`is_same` な形状どうしで等しい）
+                pub(crate) fn id(&self) -> ShapeId {
+                    (sync::Arc::as_ptr(&self.data) as usize, self.location.key())
+                }
+
+                                /// この形状を、向き `orientation`・配置 `location` の親の部分形状として見たものを返す
+                fn composed(&self, orientation: Orientation, location: Location) -> Self {
+                    Self {
+                        data: self.data.clone(),
+                        orientation: orientation.compose(self.orientation),
+                        location: location * self.location,
+                    }
+                }
+            }
+
+            /// 同じ中身を同じ配置・同じ向きで指している場合に等しい（OCCT の `IsEqual` 相当）
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    self.id() == other.id() && self.orientation == other.orientation
+                }
+            }
+
+            impl Eq for $ty {}
+
+            impl std::hash::Hash for $ty {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.id().hash(state);
+                    self.orientation.hash(state);
+                }
+            }
+
+
+            impl From<$ty> for Shape {
+                fn from(s: $ty) -> Self {
+                    Shape::$ty(s)
+                }
+            }
+
+            impl TryFrom<Shape> for $ty {
+                type Error = OcctKrsError;
+
+                fn try_from(s: Shape) -> Result<Self> {
+                    match s {
+                        Shape::$ty(s) => Ok(s),
+                        s => Err(OcctKrsError::InvalidInput(format!(
+                            "{:?} を {} に変換できません",
+                            s.kind(),
+                            stringify!($ty)
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_shape_common!(Vertex, Edge, Wire, Face, Shell, Solid, Compound);
+
+/// 許容誤差が正の有限値か調べる
+pub(crate) fn check_tolerance(tolerance: f64) -> Result<()> {
+    if tolerance > 0.0 && tolerance.is_finite() {
+        Ok(())
+    } else {
+        Err(OcctKrsError::InvalidInput(format!(
+            "許容誤差が不正です: {}",
+            tolerance
+        )))
+    }
+}
+
+impl Vertex {
+    /// 点 `point` に許容誤差 `precision::confusion()` の頂点を生成する
+    pub fn new(point: Point3) -> Self {
+        Self {
+            data: sync::Arc::new(VertexData {
+                point,
+                tolerance: precision::confusion(),
+            }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        }
+    }
+
+    /// 許容誤差を `tolerance` にした頂点を返す
+    /// 中身を他の形状と共有している場合は複製するので、以後は別の頂点になる。
+    /// 許容誤差が正の有限値でない場合はエラーを返す
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        sync::Arc::make_mut(&mut self.data).tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// 配置を適用した位置を返す
+    pub fn point(&self) -> Point3 {
+        self.location.apply(self.data.point)
+    }
+
+    /// 許容誤差（この距離以内の点を同じ頂点とみなす）を返す
+    pub fn tolerance(&self) -> f64 {
+        self.data.tolerance
+    }
+}
+
+impl Edge {
+    /// 曲線 `curve` のパラメータ区間 `range` を、始点 `start`、終点 `end` の頂点で区切ったエッジを生成する
+    ///
+    /// 区間が有限でない・空の場合や、区間の端の曲線上の点が頂点から
+    /// 頂点の許容誤差（`precision::confusion()` 未満なら `precision::confusion()`）より離れている場合はエラーを返す。
+    pub fn new(
+        curve: impl Into<GeomCurve>,
+        range: (f64, f64),
+        start: Vertex,
+        end: Vertex,
+    ) -> Result<Self> {
+        let curve = curve.into();
+        let (first, last) = range;
+        if !(first.is_finite() && last.is_finite() && first < last) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "エッジのパラメータ区間が不正です: [{}, {}]",
+                first, last
+            )));
+        }
+        for (vertex, t, name) in [(&start, first, "始点"), (&end, last, "終点")] {
+            let gap = curve.point_at(t).distance(vertex.point());
+            if gap > vertex.tolerance().max(precision::confusion()) {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "エッジの{}の頂点が曲線から離れています（距離 {}）",
+                    name, gap
+                )));
+            }
+        }
+        Ok(Self::from_data(EdgeData {
+            curve: Some(sync::Arc::new(curve)),
+            range,
+            start: start.oriented(Orientation::Forward),
+            end: end.oriented(Orientation::Reversed),
+            tolerance: precision::confusion(),
+        }))
+    }
+
+    /// 頂点 `vertex` に縮退した、パラメータ区間 `range` の退化エッジを生成する
+    ///
+    /// 球面の極や円錐の頂点のように、曲面のパラメータ空間では長さを持つが3次元では点になる境界に使う。
+    pub fn degenerate(vertex: Vertex, range: (f64, f64)) -> Result<Self> {
+        let (first, last) = range;
+        if !(first.is_finite() && last.is_finite() && first < last) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "エッジのパラメータ区間が不正です: [{}, {}]",
+                first, last
+            )));
+        }
+        Ok(Self::from_data(EdgeData {
+            curve: None,
+            range,
+            start: vertex.oriented(Orientation::Forward),
+            end: vertex.oriented(Orientation::Reversed),
+            tolerance: precision::confusion(),
+        }))
+    }
+
+    fn from_data(data: EdgeData) -> Self {
+        Self {
+            data: sync::Arc::new(data),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        }
+    }
+
+    /// 許容誤差を `tolerance` にしたエッジを返す
+    /// 中身を他の形状と共有している場合は複製するので、以後は別のエッジになる。
+    /// 許容誤差が正の有限値でない場合はエラーを返す
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        sync::Arc::make_mut(&mut self.data).tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// 配置を適用する前の曲線を返す（退化エッジでは `None`）
+    pub fn curve(&self) -> Option<&GeomCurve> {
+        self.data.curve.as_deref()
+    }
+
+    /// 配置を適用した曲線を返す（退化エッジでは `None`）
+    ///
+    /// 配置が恒等変換なら複製せずに共有している曲線を返す。
+    /// スケールを含む配置では曲線によってパラメータが変わるので（[`GeomCurve::transformed`]）、
+    /// エッジのパラメータで点を求めるには `point_at` を使う。
+    pub fn located_geometry(&self) -> Option<Cow<'_, GeomCurve>> {
+        let curve = self.data.curve.as_deref()?;
+        Some(if self.location.is_identity() {
+            Cow::Borrowed(curve)
+        } else {
+            Cow::Owned(curve.transformed(&self.location.transform()))
+        })
+    }
+
+    /// 曲線のパラメータ区間 `(first, last)` を返す（向きによらない）
+    pub fn range(&self) -> (f64, f64) {
+        self.data.range
+    }
+
+    /// 許容誤差（曲線と、面上の曲線や頂点とのずれの上限）を返す
+    pub fn tolerance(&self) -> f64 {
+        self.data.tolerance
+    }
+
+    /// 退化エッジなら `true` を返す
+    pub fn is_degenerate(&self) -> bool {
+        self.data.curve.is_none()
+    }
+
+    /// 始点と終点が同じ頂点なら `true` を返す
+    pub fn is_closed(&self) -> bool {
+        self.data.start.is_same(&self.data.end)
+    }
+
+    /// 向きに沿ってたどったときの始点の頂点を返す
+    pub fn start_vertex(&self) -> Vertex {
+        let v = if self.orientation == Orientation::Reversed {
+            &self.data.end
+        } else {
+            &self.data.start
+        };
+        v.composed(self.orientation, self.location)
+    }
+
+    /// 向きに沿ってたどったときの終点の頂点を返す
+    pub fn end_vertex(&self) -> Vertex {
+        let v = if self.orientation == Orientation::Reversed {
+            &self.data.start
+        } else {
+            &self.data.end
+        };
+        v.composed(self.orientation, self.location)
+    }
+
+    /// 向きに沿ってたどったときの始点を返す
+    pub fn start_point(&self) -> Point3 {
+        self.start_vertex().point()
+    }
+
+    /// 向きに沿ってたどったときの終点を返す
+    pub fn end_point(&self) -> Point3 {
+        self.end_vertex().point()
+    }
+
+    /// 曲線のパラメータ `t` における点を、配置を適用して返す（退化エッジでは頂点の位置）
+    pub fn point_at(&self, t: f64) -> Point3 {
+        match &self.data.curve {
+            Some(c) => self.location.apply(c.point_at(t)),
+            None => self.start_vertex().point(),
+        }
+    }
+}
+
+impl Wire {
+    /// たどる順に並べたエッジからワイヤを生成する
+    ///
+    /// エッジが空の場合や、各エッジの終点の頂点が次のエッジの始点の頂点と同じでない場合はエラーを返す。
+    /// 許容誤差の範囲で離れた頂点をつなぐ場合は、先に頂点を共有したエッジを作っておく。
+    pub fn new(edges: Vec<Edge>) -> Result<Self> {
+        if edges.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "ワイヤには1本以上のエッジが必要です".to_string(),
+            ));
+        }
+        for (i, pair) in edges.windows(2).enumerate() {
+            if !pair[0].end_vertex().is_same(&pair[1].start_vertex()) {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目のエッジの終点が次のエッジの始点とつながっていません",
+                    i
+                )));
+            }
+        }
+        Ok(Self {
+            data: sync::Arc::new(WireData { edges }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        })
+    }
+
+    /// 向きに沿ってたどる順にエッジを返す
+    ///
+    /// ワイヤが `Reversed` の場合は逆順に並べ、各エッジの向きも反転する。
+    pub fn edges(&self) -> Vec<Edge> {
+        let edges = self
+            .data
+            .edges
+            .iter()
+            .map(|e| e.composed(self.orientation, self.location));
+        if self.orientation == Orientation::Reversed {
+            edges.rev().collect()
+        } else {
+            edges.collect()
+        }
+    }
+
+    /// エッジの数を返す
+    pub fn len(&self) -> usize {
+        self.data.edges.len()
+    }
+
+    /// エッジを持たない場合に `true` を返す（`new` で生成したワイヤでは常に `false`）
+    pub fn is_empty(&self) -> bool {
+        self.data.edges.is_empty()
+    }
+
+    /// 最後のエッジの終点が最初のエッジの始点と同じ頂点なら `true` を返す
+    pub fn is_closed(&self) -> bool {
+        let edges = &self.data.edges;
+        edges[edges.len() - 1]
+            .end_vertex()
+            .is_same(&edges[0].start_vertex())
+    }
+}
+
+impl Face {
+    /// 曲面 `surface` をワイヤ `wires`（最初が外側の境界、残りが穴）で囲んだフェイスを生成する
+    /// 閉じていないワイヤがある場合はエラーを返す
+    pub fn new(surface: impl Into<GeomSurface>, wires: Vec<Wire>) -> Result<Self> {
+        let pcurves = wires.iter().map(|w| vec![None; w.len()]).collect();
+        Self::with_pcurves(surface, wires, pcurves)
+    }
+
+    /// 境界を持たず、曲面のパラメータ範囲全体を表すフェイスを生成する
+    pub fn from_surface(surface: impl Into<GeomSurface>) -> Self {
+        Self {
+            data: sync::Arc::new(FaceData {
+                surface: sync::Arc::new(surface.into()),
+                wires: Vec::new(),
+                pcurves: Vec::new(),
+                tolerance: precision::confusion(),
+            }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        }
+    }
+
+    /// 各エッジのパラメータ空間上の曲線（pcurve）を指定してフェイスを生成する
+    ///
+    /// `pcurves[i][j]` は `wires[i]` に格納された `j` 番目のエッジの pcurve で、
+    /// エッジの曲線と同じパラメータを持つ（`None` は未設定）。
+    /// 閉じていないワイヤがある場合や、`pcurves` の個数がワイヤのエッジ数と合わない場合はエラーを返す。
+    pub fn with_pcurves(
+        surface: impl Into<GeomSurface>,
+        wires: Vec<Wire>,
+        pcurves: Vec<Vec<Option<BSplineCurve2>>>,
+    ) -> Result<Self> {
+        if let Some(i) = wires.iter().position(|w| !w.is_closed()) {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "{} 番目のワイヤが閉じていません",
+                i
+            )));
+        }
+        if pcurves.len() != wires.len()
+            || pcurves.iter().zip(&wires).any(|(p, w)| p.len() != w.len())
+        {
+            return Err(OcctKrsError::InvalidInput(
+                "pcurve の個数がワイヤのエッジ数と一致しません".to_string(),
+            ));
+        }
+        Ok(Self {
+            data: sync::Arc::new(FaceData {
+                surface: sync::Arc::new(surface.into()),
+                wires,
+                pcurves: pcurves
+                    .into_iter()
+                    .map(|p| p.into_iter().map(|p| p.map(sync::Arc::new)).collect())
+                    .collect(),
+                tolerance: precision::confusion(),
+            }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        })
+    }
+
+    /// 許容誤差を `tolerance` にしたフェイスを返す
+    /// 中身を他の形状と共有している場合は複製するので、以後は別のフェイスになる。
+    /// 許容誤差が正の有限値でない場合はエラーを返す
+    pub fn with_tolerance(mut self, tolerance: f64) -> Result<Self> {
+        check_tolerance(tolerance)?;
+        sync::Arc::make_mut(&mut self.data).tolerance = tolerance;
+        Ok(self)
+    }
+
+    /// 配置を適用する前の曲面を返す（pcurve はこの曲面のパラメータ空間の曲線）
+    pub fn surface(&self) -> &GeomSurface {
+        &self.data.surface
+    }
+
+    /// 配置を適用した曲面を返す（配置が恒等変換なら複製しない）
+    ///
+    /// スケールを含む配置では曲面によってパラメータが変わるので（[`GeomSurface::transformed`]）、
+    /// pcurve のパラメータで点を求めるには `point_at` を使う。
+    pub fn located_geometry(&self) -> Cow<'_, GeomSurface> {
+        if self.location.is_identity() {
+            Cow::Borrowed(&self.data.surface)
+        } else {
+            Cow::Owned(self.data.surface.transformed(&self.location.transform()))
+        }
+    }
+
+    /// 曲面のパラメータ `(u, v)` における点を、配置を適用して返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.location.apply(self.data.surface.point_at(u, v))
+    }
+
+    /// 許容誤差を返す
+    pub fn tolerance(&self) -> f64 {
+        self.data.tolerance
+    }
+
+    /// 境界のワイヤを返す（最初が外側の境界。向きはフェイスの向きと合成したもの）
+    pub fn wires(&self) -> Vec<Wire> {
+        self.data
+            .wires
+            .iter()
+            .map(|w| w.composed(self.orientation, self.location))
+            .collect()
+    }
+
+    /// 外側の境界のワイヤを返す（境界を持たない場合は `None`）
+    pub fn outer_wire(&self) -> Option<Wire> {
+        self.wires().into_iter().next()
+    }
+
+    /// 穴の境界のワイヤを返す
+    pub fn inner_wires(&self) -> Vec<Wire> {
+        self.wires().into_iter().skip(1).collect()
+    }
+
+    /// このフェイスの境界にあるエッジ `edge` の pcurve を返す（OCCT の `BRep_Tool::CurveOnSurface` 相当）
+    ///
+    /// 継ぎ目のエッジのように同じエッジが両方の向きで現れる場合は、`edge` の向きに対応するものを返す。
+    /// エッジが境界にない場合や pcurve が未設定の場合は `None` を返す。
+    pub fn pcurve(&self, edge: &Edge) -> Option<&BSplineCurve2> {
+        // フェイスが反転していれば、格納時のエッジの向きも反転して見える
+        let orientation = if self.orientation == Orientation::Reversed {
+            edge.orientation.reversed()
+        } else {
+            edge.orientation
+        };
+        let mut fallback = None;
+        for (wire, pcurves) in self.data.wires.iter().zip(&self.data.pcurves) {
+            let wire = wire.composed(Orientation::Forward, self.location);
+            for (e, p) in wire.data.edges.iter().zip(pcurves) {
+                let e = e.composed(wire.orientation, wire.location);
+                if !e.is_same(edge) {
+                    continue;
+                }
+                if e.orientation == orientation {
+                    return p.as_deref();
+                }
+                fallback = fallback.or(p.as_deref());
+            }
+        }
+        fallback
+    }
+
+    /// 格納したワイヤのエッジの順に並べた pcurve（`wires()` を向き `Forward` で見たときの順）
+    pub(crate) fn stored_pcurves(&self) -> Vec<Vec<Option<BSplineCurve2>>> {
+        self.data
+            .pcurves
+            .iter()
+            .map(|p| p.iter().map(|c| c.as_deref().cloned()).collect())
+            .collect()
+    }
+}
+
+impl Shell {
+    /// フェイスの集まりからシェルを生成する（フェイスが空の場合はエラー）
+    pub fn new(faces: Vec<Face>) -> Result<Self> {
+        if faces.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "シェルには1枚以上のフェイスが必要です".to_string(),
+            ));
+        }
+        Ok(Self {
+            data: sync::Arc::new(ShellData { faces }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        })
+    }
+
+    /// フェイスを返す（向きはシェルの向きと合成したもの）
+    pub fn faces(&self) -> Vec<Face> {
+        self.data
+            .faces
+            .iter()
+            .map(|f| f.composed(self.orientation, self.location))
+            .collect()
+    }
+
+    /// 退化エッジ以外のすべてのエッジが、ちょうど2回ずつフェイスの境界に現れる場合に `true` を返す
+    ///
+    /// 継ぎ目のエッジは1枚のフェイスに2回現れる。幾何的な閉じ方や向きの整合性は調べない。
+    pub fn is_closed(&self) -> bool {
+        let mut uses: HashMap<ShapeId, usize> = HashMap::new();
+        for face in self.faces() {
+            for wire in face.wires() {
+                for edge in wire.edges() {
+                    if !edge.is_degenerate() {
+                        *uses.entry(edge.id()).or_default() += 1;
+                    }
+                }
+            }
+        }
+        uses.values().all(|&n| n == 2)
+    }
+}
+
+impl Solid {
+    /// シェル（最初が外側の境界、残りが空洞）から立体を生成する（シェルが空の場合はエラー）
+    pub fn new(shells: Vec<Shell>) -> Result<Self> {
+        if shells.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "立体には1つ以上のシェルが必要です".to_string(),
+            ));
+        }
+        Ok(Self {
+            data: sync::Arc::new(SolidData { shells }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        })
+    }
+
+    /// シェルを返す（向きは立体の向きと合成したもの）
+    pub fn shells(&self) -> Vec<Shell> {
+        self.data
+            .shells
+            .iter()
+            .map(|s| s.composed(self.orientation, self.location))
+            .collect()
+    }
+}
+
+impl Compound {
+    /// 形状の集まりから複合形状を生成する（空でもよい）
+    pub fn new(shapes: Vec<Shape>) -> Self {
+        Self {
+            data: sync::Arc::new(CompoundData { shapes }),
+            orientation: Orientation::Forward,
+            location: Location::identity(),
+        }
+    }
+
+    /// 含まれる形状を返す（向きは複合形状の向きと合成したもの）
+    pub fn shapes(&self) -> Vec<Shape> {
+        self.data
+            .shapes
+            .iter()
+            .map(|s| s.composed(self.orientation, self.location))
+            .collect()
+    }
+}
+
+impl Shape {
+    /// 形状の種類を返す
+    pub fn kind(&self) -> ShapeKind {
+        match self {
+            Shape::Vertex(_) => ShapeKind::Vertex,
+            Shape::Edge(_) => ShapeKind::Edge,
+            Shape::Wire(_) => ShapeKind::Wire,
+            Shape::Face(_) => ShapeKind::Face,
+            Shape::Shell(_) => ShapeKind::Shell,
+            Shape::Solid(_) => ShapeKind::Solid,
+            Shape::Compound(_) => ShapeKind::Compound,
+        }
+    }
+
+    /// 親の形状に対する向きを返す
+    pub fn orientation(&self) -> Orientation {
+        match self {
+            Shape::Vertex(s) => s.orientation(),
+            Shape::Edge(s) => s.orientation(),
+            Shape::Wire(s) => s.orientation(),
+            Shape::Face(s) => s.orientation(),
+            Shape::Shell(s) => s.orientation(),
+            Shape::Solid(s) => s.orientation(),
+            Shape::Compound(s) => s.orientation(),
+        }
+    }
+
+    /// 中身を共有したまま向きを `orientation` にした形状を返す
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        match self {
+            Shape::Vertex(s) => s.oriented(orientation).into(),
+            Shape::Edge(s) => s.oriented(orientation).into(),
+            Shape::Wire(s) => s.oriented(orientation).into(),
+            Shape::Face(s) => s.oriented(orientation).into(),
+            Shape::Shell(s) => s.oriented(orientation).into(),
+            Shape::Solid(s) => s.oriented(orientation).into(),
+            Shape::Compound(s) => s.oriented(orientation).into(),
+        }
+    }
+
+    /// 中身を共有したまま向きを反転した形状を返す
+    pub fn reversed(&self) -> Self {
+        self.oriented(self.orientation().reversed())
+    }
+
+    /// 配置を返す
+    pub fn location(&self) -> Location {
+        match self {
+            Shape::Vertex(s) => s.location(),
+            Shape::Edge(s) => s.location(),
+            Shape::Wire(s) => s.location(),
+            Shape::Face(s) => s.location(),
+            Shape::Shell(s) => s.location(),
+            Shape::Solid(s) => s.location(),
+            Shape::Compound(s) => s.location(),
+        }
+    }
+
+    /// 中身を共有したまま配置を `location` にした形状を返す
+    pub fn located(&self, location: Location) -> Self {
+        match self {
+            Shape::Vertex(s) => s.located(location).into(),
+            Shape::Edge(s) => s.located(location).into(),
+            Shape::Wire(s) => s.located(location).into(),
+            Shape::Face(s) => s.located(location).into(),
+            Shape::Shell(s) => s.located(location).into(),
+            Shape::Solid(s) => s.located(location).into(),
+            Shape::Compound(s) => s.located(location).into(),
+        }
+    }
+
+    /// 中身を共有したまま、今の配置の後に `transform` を適用した形状を返す（幾何は複製しない）
+    pub fn moved(&self, transform: &Transform) -> Self {
+        self.located(Location::new(*transform) * self.location())
+    }
+
+    /// 向きを問わず、同じ中身を同じ配置で指している場合に `true` を返す（OCCT の `IsSame` 相当）
+    pub fn is_same(&self, other: &Shape) -> bool {
+        self.kind() == other.kind() && self.id() == other.id()
+    }
+
+    /// 形状を識別する値（`is_same` な形状どうしで等しい）
+    pub(crate) fn id(&self) -> ShapeId {
+        match self {
+            Shape::Vertex(s) => s.id(),
+            Shape::Edge(s) => s.id(),
+            Shape::Wire(s) => s.id(),
+            Shape::Face(s) => s.id(),
+            Shape::Shell(s) => s.id(),
+            Shape::Solid(s) => s.id(),
+            Shape::Compound(s) => s.id(),
+        }
+    }
+
+    /// この形状を、向き `orientation`・配置 `location` の親の部分形状として見たものを返す
+    fn composed(&self, orientation: Orientation, location: Location) -> Self {
+        match self {
+            Shape::Vertex(s) => s.composed(orientation, location).into(),
+            Shape::Edge(s) => s.composed(orientation, location).into(),
+            Shape::Wire(s) => s.composed(orientation, location).into(),
+            Shape::Face(s) => s.composed(orientation, location).into(),
+            Shape::Shell(s) => s.composed(orientation, location).into(),
+            Shape::Solid(s) => s.composed(orientation, location).into(),
+            Shape::Compound(s) => s.composed(orientation, location).into(),
+        }
+    }
+
+    /// 許容誤差を返す（頂点・エッジ・フェイス以外は `None`）
+    pub fn tolerance(&self) -> Option<f64> {
+        match self {
+            Shape::Vertex(s) => Some(s.tolerance()),
+            Shape::Edge(s) => Some(s.tolerance()),
+            Shape::Face(s) => Some(s.tolerance()),
+            _ => None,
+        }
+    }
+
+    /// 直下の部分形状を返す（向きと配置はこの形状のものと合成したもの）
+    ///
+    /// エッジの頂点は始点が `Forward`、終点が `Reversed` になる。
+    pub fn children(&self) -> Vec<Shape> {
+        match self {
+            Shape::Vertex(_) => Vec::new(),
+            Shape::Edge(e) => vec![
+                e.data.start.composed(e.orientation, e.location).into(),
+                e.data.end.composed(e.orientation, e.location).into(),
+            ],
+            Shape::Wire(w) => w.edges().into_iter().map(Shape::from).collect(),
+            Shape::Face(f) => f.wires().into_iter().map(Shape::from).collect(),
+            Shape::Shell(s) => s.faces().into_iter().map(Shape::from).collect(),
+            Shape::Solid(s) => s.shells().into_iter().map(Shape::from).collect(),
+            Shape::Compound(c) => c.shapes(),
+        }
+    }
+
+    /// 中身を複製した形状を返す（OCCT の `BRepBuilderAPI_Copy` 相当）
+    ///
+    /// 複製した形状は元の形状と `is_partner` にならない。元の形状の中で共有されている部分形状は
+    /// 複製の中でも共有する。`deep_geometry` が `false` なら曲線・曲面は元の形状と共有し、
+    /// `true` なら曲線・曲面も複製する。向きと配置は元の形状と同じになる。
+    pub fn copy(&self, deep_geometry: bool) -> Shape {
+        ShapeCopier::new(deep_geometry).shape(self)
+    }
+
+    /// 変換 `transform` を適用した形状を返す（OCCT の `BRepBuilderAPI_Transform` 相当）
+    ///
+    /// `copy_geometry` が `false` なら中身を共有したまま配置に変換を加える（[`Shape::moved`]）。
+    /// `true` なら頂点・曲線・曲面・pcurve に変換を焼き込んだ複製を返し、元の形状とは中身を共有しない
+    /// （配置が恒等変換でない部分形状は、変換と共役な配置を保つ）。
+    /// 鏡映を含む変換（負のスケール）では `copy_geometry` によらず焼き込んだ複製を返し、曲面の局所座標系を
+    /// 右手系に保つためにパラメータの向きを1つ反転する。pcurve とワイヤのたどる向きもそれに合わせるので、
+    /// フェイスの向きと立体の外向きは変わらない。
+    ///
+    /// スケールが 0 または有限でない場合はエラーを返す。
+    pub fn transformed(&self, transform: &Transform, copy_geometry: bool) -> Result<Shape> {
+        let inverse = match transform.inverse() {
+            Some(inverse) if transform.scale.is_finite() => inverse,
+            _ => {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "逆変換のない変換です: {:?}",
+                    transform
+                )))
+            }
+        };
+        if copy_geometry || transform.scale < 0.0 {
+            return Ok(ShapeCopier::baking(*transform, inverse).shape(self));
+        }
+        Ok(self.moved(transform))
+    }
+
+    /// 平面 `plane` に関して鏡映した形状を返す（`copy_geometry` とエラーは [`Shape::transformed`] と同じ）
+    pub fn mirrored(&self, plane: &Plane, copy_geometry: bool) -> Result<Shape> {
+        self.transformed(&plane.mirror_transform(), copy_geometry)
+    }
+
+    /// `offset` だけ平行移動した形状を返す（`copy_geometry` とエラーは [`Shape::transformed`] と同じ）
+    pub fn translated(&self, offset: Vector3, copy_geometry: bool) -> Result<Shape> {
+        self.transformed(&Transform::from_translation(offset), copy_geometry)
+    }
+
+    /// 軸 `axis` まわりに角度 `angle`（ラジアン）だけ回転した形状を返す
+    /// （`copy_geometry` とエラーは [`Shape::transformed`] と同じ）
+    pub fn rotated(&self, axis: &Axis1, angle: f64, copy_geometry: bool) -> Result<Shape> {
+        self.transformed(&Transform::rotation(axis, angle), copy_geometry)
+    }
+
+    /// 点 `center` を中心に `factor` 倍した形状を返す（`copy_geometry` は [`Shape::transformed`] と同じ）
+    ///
+    /// 負の倍率は `center` に関する点対称を含む。倍率が 0 または有限でない場合はエラーを返す。
+    pub fn scaled(&self, center: Point3, factor: f64, copy_geometry: bool) -> Result<Shape> {
+        self.transformed(&Transform::scale_about(center, factor), copy_geometry)
+    }
+}
+
+/// 同じ中身を同じ配置・同じ向きで指している場合に等しい（OCCT の `IsEqual` 相当）
+impl PartialEq for Shape {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same(other) && self.orientation() == other.orientation()
+    }
+}
+
+impl Eq for Shape {}
+
+impl std::hash::Hash for Shape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+        self.id().hash(state);
+        self.orientation().hash(state);
+    }
+}
+
+/// [`Shape::copy`] と [`Shape::transformed`] で、元の中身（所在で識別）から複製した中身への対応を持つ
+struct ShapeCopier {
+    deep: bool,
+    /// 幾何に焼き込む変換とその逆変換
+    transform: Option<(Transform, Transform)>,
+    vertices: HashMap<usize, sync::Arc<VertexData>>,
+    edges: HashMap<usize, sync::Arc<EdgeData>>,
+    wires: HashMap<usize, sync::Arc<WireData>>,
+    faces: HashMap<usize, sync::Arc<FaceData>>,
+    shells: HashMap<usize, sync::Arc<ShellData>>,
+    solids: HashMap<usize, sync::Arc<SolidData>>,
+    compounds: HashMap<usize, sync::Arc<CompoundData>>,
+}
+
+impl ShapeCopier {
+    fn new(deep: bool) -> Self {
+        Self {
+            deep,
+            transform: None,
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+            wires: HashMap::new(),
+            faces: HashMap::new(),
+            shells: HashMap::new(),
+            solids: HashMap::new(),
+            compounds: HashMap::new(),
+        }
+    }
+
+    /// 幾何に変換 `transform`（逆変換は `inverse`）を焼き込んで複製する
+    fn baking(transform: Transform, inverse: Transform) -> Self {
+        Self {
+            transform: Some((transform, inverse)),
+            ..Self::new(true)
+        }
+    }
+
+    /// 部分形状の配置を、焼き込む変換と共役にする（恒等変換はそのまま）
+    fn location(&self, location: Location) -> Location {
+        match self.transform {
+            Some((t, inverse)) if !location.is_identity() => {
+                Location::new(t) * location * Location::new(inverse)
+            }
+            _ => location,
+        }
+    }
+
+    /// 許容誤差に変換のスケールを掛ける
+    fn tolerance(&self, tolerance: f64) -> f64 {
+        match self.transform {
+            Some((t, _)) => tolerance * t.scale.abs(),
+            None => tolerance,
+        }
+    }
+
+    /// 曲線・曲面を、深い複製なら複製し、そうでなければ共有する
+    fn geometry<T: Clone>(&self, geometry: &sync::Arc<T>) -> sync::Arc<T> {
+        if self.deep {
+            sync::Arc::new(T::clone(geometry))
+        } else {
+            geometry.clone()
+        }
+    }
+
+    /// エッジ `edge` の曲面 `surface` 上の pcurve を複製する
+    ///
+    /// 変換を焼き込む場合は、曲線と曲面のパラメータの倍率に合わせて pcurve を伸縮する。
+    fn pcurve(
+        &self,
+        pcurve: &sync::Arc<BSplineCurve2>,
+        edge: &Edge,
+        surface: &GeomSurface,
+    ) -> sync::Arc<BSplineCurve2> {
+        let Some((t, _)) = self.transform else {
+            return self.geometry(pcurve);
+        };
+        let k = edge.curve().map_or(1.0, |c| c.parametric_scale(&t));
+        let (ku, kv) = surface.parametric_scale(&t);
+        if k == 1.0 && ku == 1.0 && kv == 1.0 {
+            return self.geometry(pcurve);
+        }
+        sync::Arc::new(pcurve.scaled(k, ku, kv))
+    }
+
+    fn shape(&mut self, shape: &Shape) -> Shape {
+        match shape {
+            Shape::Vertex(s) => self.vertex(s).into(),
+            Shape::Edge(s) => self.edge(s).into(),
+            Shape::Wire(s) => self.wire(s).into(),
+            Shape::Face(s) => self.face(s).into(),
+            Shape::Shell(s) => self.shell(s).into(),
+            Shape::Solid(s) => self.solid(s).into(),
+            Shape::Compound(s) => self.compound(s).into(),
+        }
+    }
+
+    fn vertex(&mut self, vertex: &Vertex) -> Vertex {
+        let key = sync::Arc::as_ptr(&vertex.data) as usize;
+        let data = self
+            .vertices
+            .entry(key)
+            .or_insert_with(|| {
+                let d = &vertex.data;
+                sync::Arc::new(match self.transform {
+                    Some((t, _)) => VertexData {
+                        point: t.transform_point(d.point),
+                        tolerance: d.tolerance * t.scale.abs(),
+                    },
+                    None => VertexData::clone(d),
+                })
+            })
+            .clone();
+        Vertex {
+            data,
+            location: self.location(vertex.location),
+            ..*vertex
+        }
+    }
+
+    fn edge(&mut self, edge: &Edge) -> Edge {
+        let key = sync::Arc::as_ptr(&edge.data) as usize;
+        let data = match self.edges.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let d = &edge.data;
+                let (curve, range) = match (self.transform, &d.curve) {
+                    (Some((t, _)), Some(c)) => {
+                        let k = c.parametric_scale(&t);
+                        let range = (d.range.0 * k, d.range.1 * k);
+                        (Some(sync::Arc::new(c.transformed(&t))), range)
+                    }
+                    _ => (d.curve.as_ref().map(|c| self.geometry(c)), d.range),
+                };
+                let data = sync::Arc::new(EdgeData {
+                    curve,
+                    range,
+                    start: self.vertex(&d.start),
+                    end: self.vertex(&d.end),
+                    tolerance: self.tolerance(d.tolerance),
+                });
+                self.edges.insert(key, data.clone());
+                data
+            }
+        };
+        Edge {
+            data,
+            location: self.location(edge.location),
+            ..*edge
+        }
+    }
+
+    fn wire(&mut self, wire: &Wire) -> Wire {
+        let key = sync::Arc::as_ptr(&wire.data) as usize;
+        let data = match self.wires.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let edges = wire.data.edges.iter().map(|e| self.edge(e)).collect();
+                let data = sync::Arc::new(WireData { edges });
+                self.wires.insert(key, data.clone());
+                data
+            }
+        };
+        Wire {
+            data,
+            location: self.location(wire.location),
+            ..*wire
+        }
+    }
+
+    fn face(&mut self, face: &Face) -> Face {
+        let key = sync::Arc::as_ptr(&face.data) as usize;
+        let data = match self.faces.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let d = &face.data;
+                // 鏡映では曲面のパラメータの向きを1つ反転して、法線 Su × Sv を外向きに保つ
+                let (surface, flip) = match self.transform {
+                    Some((t, _)) if t.scale < 0.0 => {
+                        let (surface, flip) = d.surface.mirror_transformed(&t);
+                        (sync::Arc::new(surface), Some(flip))
+                    }
+                    Some((t, _)) => (sync::Arc::new(d.surface.transformed(&t)), None),
+                    None => (self.geometry(&d.surface), None),
+                };
+                let mut pcurves: Vec<Vec<_>> = d
+                    .pcurves
+                    .iter()
+                    .zip(&d.wires)
+                    .map(|(p, wire)| {
+                        p.iter()
+                            .zip(&wire.data.edges)
+                            .map(|(c, edge)| {
+                                c.as_ref().map(|c| {
+                                    let pcurve = self.pcurve(c, edge, &d.surface);
+                                    match flip {
+                                        Some(flip) => sync::Arc::new(flip.apply(&pcurve)),
+                                        None => pcurve,
+                                    }
+                                })
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let mut wires: Vec<Wire> = d.wires.iter().map(|w| self.wire(w)).collect();
+                if flip.is_some() {
+                    // パラメータ空間で境界の回る向きも反転するので、ワイヤを逆にたどって元の向きに戻す
+                    for (wire, pcurves) in wires.iter_mut().zip(&mut pcurves) {
+                        let edges = wire.data.edges.iter().rev().map(Edge::reversed).collect();
+                        wire.data = sync::Arc::new(WireData { edges });
+                        pcurves.reverse();
+                    }
+                }
+                let data = sync::Arc::new(FaceData {
+                    surface,
+                    wires,
+                    pcurves,
+                    tolerance: self.tolerance(d.tolerance),
+                });
+                self.faces.insert(key, data.clone());
+                data
+            }
+        };
+        Face {
+            data,
+            location: self.location(face.location),
+            ..*face
+        }
+    }
+
+    fn shell(&mut self, shell: &Shell) -> Shell {
+        let key = sync::Arc::as_ptr(&shell.data) as usize;
+        let data = match self.shells.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let faces = shell.data.faces.iter().map(|f| self.face(f)).collect();
+                let data = sync::Arc::new(ShellData { faces });
+                self.shells.insert(key, data.clone());
+                data
+            }
+        };
+        Shell {
+            data,
+            location: self.location(shell.location),
+            ..*shell
+        }
+    }
+
+    fn solid(&mut self, solid: &Solid) -> Solid {
+        let key = sync::Arc::as_ptr(&solid.data) as usize;
+        let data = match self.solids.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let shells = solid.data.shells.iter().map(|s| self.shell(s)).collect();
+                let data = sync::Arc::new(SolidData { shells });
+                self.solids.insert(key, data.clone());
+                data
+            }
+        };
+        Solid {
+            data,
+            location: self.location(solid.location),
+            ..*solid
+        }
+    }
+
+    fn compound(&mut self, compound: &Compound) -> Compound {
+        let key = sync::Arc::as_ptr(&compound.data) as usize;
+        let data = match self.compounds.get(&key) {
+            Some(data) => data.clone(),
+            None => {
+                let shapes = compound.data.shapes.iter().map(|s| self.shape(s)).collect();
+                let data = sync::Arc::new(CompoundData { shapes });
+                self.compounds.insert(key, data.clone());
+                data
+            }
+        };
+        Compound {
+            data,
+            location: self.location(compound.location),
+            ..*compound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis2, Axis3, Circle, CylindricalSurface, Dir, Line, Plane, Vector2};
+
+    fn segment_edge(a: &Vertex, b: &Vertex) -> Edge {
+        let line = Line::from_points(a.point(), b.point()).unwrap();
+        let length = a.point().distance(b.point());
+        Edge::new(line, (0.0, length), a.clone(), b.clone()).unwrap()
+    }
+
+    /// 頂点・エッジを共有する四面体のシェル
+    fn tetrahedron() -> (Vec<Vertex>, Vec<Edge>, Shell) {
+        let v: Vec<Vertex> = [
+            (0.0, 0.0, 0.0),
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vertex::new(Point3::new(x, y, z)))
+        .collect();
+        let pairs = [(0, 1), (1, 2), (2, 0), (0, 3), (1, 3), (2, 3)];
+        let e: Vec<Edge> = pairs
+            .iter()
+            .map(|&(a, b)| segment_edge(&v[a], &v[b]))
+            .collect();
+        // 各面を (エッジ番号, 反転するか) の列で表す
+        let loops = [
+            [(0, true), (2, true), (1, true)],
+            [(0, false), (4, false), (3, true)],
+            [(1, false), (5, false), (4, true)],
+            [(2, false), (3, false), (5, true)],
+        ];
+        let faces = loops
+            .iter()
+            .map(|l| {
+                let edges: Vec<Edge> = l
+                    .iter()
+                    .map(|&(i, rev)| if rev { e[i].reversed() } else { e[i].clone() })
+                    .collect();
+                let p: Vec<Point3> = edges.iter().map(|e| e.start_point()).collect();
+                let plane = Plane::from_points(p[0], p[1], p[2]).unwrap();
+                Face::new(plane, vec![Wire::new(edges).unwrap()]).unwrap()
+            })
+            .collect();
+        (v, e, Shell::new(faces).unwrap())
+    }
+
+    #[test]
+    fn test_orientation_compose() {
+        use Orientation::*;
+        assert_eq!(Forward.compose(Reversed), Reversed);
+        assert_eq!(Reversed.compose(Reversed), Forward);
+        assert_eq!(Reversed.compose(Internal), Internal);
+        assert_eq!(Internal.compose(Forward), Internal);
+        assert_eq!(External.reversed(), External);
+        assert!(ShapeKind::Solid < ShapeKind::Face && ShapeKind::Edge < ShapeKind::Vertex);
+    }
+
+    #[test]
+    fn test_edge_orientation_and_tolerance() {
+        let (a, b) = (
+            Vertex::new(Point3::ORIGIN),
+            Vertex::new(Point3::new(2.0, 0.0, 0.0)),
+        );
+        let e = segment_edge(&a, &b);
+        let r = e.reversed();
+        assert!(r.is_same(&e));
+        assert!(r.start_vertex().is_same(&b));
+        assert_eq!(r.end_point(), Point3::ORIGIN);
+        assert_eq!(r.range(), (0.0, 2.0));
+        assert_eq!(e.tolerance(), precision::confusion());
+        assert!(!e.is_closed());
+
+        // 頂点が曲線から離れているとエラー。頂点の許容誤差を広げれば受け入れる
+        let far = Vertex::new(Point3::new(2.0, 0.01, 0.0));
+        let line = Line::new(Point3::ORIGIN, Dir::X);
+        assert!(Edge::new(line, (0.0, 2.0), a.clone(), far.clone()).is_err());
+        let loose = far.with_tolerance(0.02).unwrap();
+        assert_eq!(loose.tolerance(), 0.02);
+        let e = Edge::new(line, (0.0, 2.0), a.clone(), loose).unwrap();
+        assert_eq!(e.with_tolerance(0.05).unwrap().tolerance(), 0.05);
+        assert!(Edge::new(line, (1.0, 1.0), a.clone(), a.clone()).is_err());
+        assert!(a.with_tolerance(-1.0).is_err());
+
+        let d = Edge::degenerate(Vertex::new(Point3::ORIGIN), (0.0, 1.0)).unwrap();
+        assert!(d.is_degenerate() && d.is_closed());
+        assert_eq!(d.point_at(0.5), Point3::ORIGIN);
+    }
+
+    #[test]
+    fn test_wire_connectivity() {
+        let v: Vec<Vertex> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| Vertex::new(Point3::new(x, y, 0.0)))
+            .collect();
+        let e01 = segment_edge(&v[0], &v[1]);
+        let e12 = segment_edge(&v[1], &v[2]);
+        let e20 = segment_edge(&v[2], &v[0]);
+        let open = Wire::new(vec![e01.clone(), e12.clone()]).unwrap();
+        assert!(!open.is_closed());
+        let closed = Wire::new(vec![e01.clone(), e12.clone(), e20.clone()]).unwrap();
+        assert!(closed.is_closed());
+        assert!(Wire::new(vec![e01.clone(), e20.clone()]).is_err());
+        assert!(Wire::new(Vec::new()).is_err());
+        // 反転したワイヤは逆順・逆向きにたどる
+        let r = closed.reversed().edges();
+        assert!(r[0].is_same(&e20) && r[0].orientation() == Orientation::Reversed);
+        assert_eq!(r[0].start_point(), v[0].point());
+        assert!(Face::new(Plane::xy(), vec![open]).is_err());
+    }
+
+    #[test]
+    fn test_shell_shares_sub_shapes() {
+        let (v, e, shell) = tetrahedron();
+        assert!(shell.is_closed());
+        let faces = shell.faces();
+        assert_eq!(faces.len(), 4);
+        // エッジは2枚のフェイスに逆向きで現れる
+        let uses: Vec<Edge> = faces
+            .iter()
+            .flat_map(|f| f.outer_wire().unwrap().edges())
+            .filter(|x| x.is_same(&e[4]))
+            .collect();
+        assert_eq!(uses.len(), 2);
+        assert_eq!(uses[0].orientation(), uses[1].orientation().reversed());
+        assert!(uses[0].start_vertex().is_same(&v[1]) || uses[0].start_vertex().is_same(&v[3]));
+
+        let open = Shell::new(faces[..3].to_vec()).unwrap();
+        assert!(!open.is_closed());
+        let solid = Solid::new(vec![shell.clone()]).unwrap();
+        let shape = Shape::from(solid.reversed());
+        assert_eq!(shape.kind(), ShapeKind::Solid);
+        let children = shape.children();
+        assert_eq!(children[0].orientation(), Orientation::Reversed);
+        assert!(children[0].is_same(&Shape::from(shell)));
+        assert!(Face::try_from(shape.clone()).is_err());
+        assert!(Solid::try_from(shape).is_ok());
+        assert!(Shell::new(Vec::new()).is_err());
+        assert!(Solid::new(Vec::new()).is_err());
+
+        let compound = Compound::new(vec![v[0].clone().into(), e[0].clone().into()]);
+        let children = Shape::from(compound).children();
+        assert_eq!(children[1].kind(), ShapeKind::Edge);
+        assert_eq!(children[1].children().len(), 2);
+    }
+
+    #[test]
+    fn test_face_pcurves_on_seam() {
+        // 円柱の側面: 上下の円と、両方の向きで現れる継ぎ目の直線
+        let cylinder =
+            CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        let (b, t) = (
+            Vertex::new(Point3::new(1.0, 0.0, 0.0)),
+            Vertex::new(Point3::new(1.0, 0.0, 2.0)),
+        );
+        let circle = |z: f64| {
+            Circle::new(Axis2::from_normal(Point3::new(0.0, 0.0, z), Dir::Z), 1.0).unwrap()
+        };
+        let bottom = Edge::new(
+            circle(0.0),
+            (0.0, std::f64::consts::TAU),
+            b.clone(),
+            b.clone(),
+        )
+        .unwrap();
+        let top = Edge::new(
+            circle(2.0),
+            (0.0, std::f64::consts::TAU),
+            t.clone(),
+            t.clone(),
+        )
+        .unwrap();
+        let seam = segment_edge(&b, &t);
+        let wire = Wire::new(vec![
+            bottom.clone(),
+            seam.clone(),
+            top.reversed(),
+            seam.reversed(),
+        ])
+        .unwrap();
+        let uv = |a: (f64, f64), b: (f64, f64), t1: f64| {
+            BSplineCurve2::from_flat_knots(
+                1,
+                vec![Vector2::new(a.0, a.1), Vector2::new(b.0, b.1)],
+                vec![0.0, 0.0, t1, t1],
+            )
+            .unwrap()
+        };
+        let tau = std::f64::consts::TAU;
+        let pcurves = vec![vec![
+            Some(uv((0.0, 0.0), (tau, 0.0), tau)),
+            Some(uv((tau, 0.0), (tau, 2.0), 2.0)),
+            Some(uv((0.0, 2.0), (tau, 2.0), tau)),
+            Some(uv((0.0, 0.0), (0.0, 2.0), 2.0)),
+        ]];
+        let face = Face::with_pcurves(cylinder, vec![wire.clone()], pcurves).unwrap();
+        let p = face.pcurve(&seam).unwrap();
+        assert_eq!(p.point_at(0.0), Vector2::new(tau, 0.0));
+        let p = face.pcurve(&seam.reversed()).unwrap();
+        assert_eq!(p.point_at(0.0), Vector2::new(0.0, 0.0));
+        // 反転したフェイスでは境界のエッジの向きも反転して見える
+        let r = face.reversed();
+        let p = r.pcurve(&seam.reversed()).unwrap();
+        assert_eq!(p.point_at(0.0), Vector2::new(tau, 0.0));
+        assert!(face
+            .pcurve(&segment_edge(&b, &Vertex::new(Point3::ORIGIN)))
+            .is_none());
+        assert!(Face::with_pcurves(cylinder, vec![wire], vec![vec![None]]).is_err());
+        let whole = Face::from_surface(Plane::xy());
+        assert!(whole.outer_wire().is_none());
+    }
+
+    #[test]
+    fn test_moved_instances_share_data() {
+        let (v, e, shell) = tetrahedron();
+        let t = Transform::from_translation(crate::Vector3::new(5.0, 0.0, 0.0));
+        let moved = shell.moved(&t);
+        assert!(moved.is_partner(&shell) && !moved.is_same(&shell));
+        assert_eq!(moved.location().transform(), t);
+        assert!(moved.is_closed());
+        // 部分形状には配置が合成される
+        let face = &moved.faces()[1];
+        let edge = &face.outer_wire().unwrap().edges()[0];
+        assert!(edge.is_partner(&e[0]) && !edge.is_same(&e[0]));
+        assert_eq!(edge.start_point(), Point3::new(5.0, 0.0, 0.0));
+        assert_eq!(edge.point_at(0.5), Point3::new(5.5, 0.0, 0.0));
+        let curve = edge.located_geometry().unwrap();
+        assert!(matches!(curve, Cow::Owned(_)));
+        assert_eq!(curve.point_at(1.0), Point3::new(6.0, 0.0, 0.0));
+        assert!(matches!(e[0].located_geometry(), Some(Cow::Borrowed(_))));
+        let surface = face.located_geometry();
+        assert!(
+            surface
+                .project(Point3::new(5.5, 0.0, 3.0))
+                .unwrap()
+                .distance
+                < 1e-12
+        );
+
+        let compound = Shape::from(Compound::new(vec![
+            shell.clone().into(),
+            moved.clone().into(),
+            moved.clone().into(),
+        ]));
+        // 配置の異なる複製は別の形状として数え、同じ配置の複製は1つにまとまる
+        assert_eq!(compound.explore(ShapeKind::Face).unique().count(), 8);
+        assert_eq!(compound.vertices().len(), 8);
+        assert!(compound
+            .vertices()
+            .iter()
+            .any(|x| x.point() == Point3::new(5.0, 0.0, 1.0)));
+        assert_eq!(Shape::from(moved.clone()).location(), moved.location());
+        let back = moved.moved(&t.inverse().unwrap()).faces()[0]
+            .outer_wire()
+            .unwrap();
+        let start = shell.faces()[0].outer_wire().unwrap().edges()[0].start_point();
+        assert!(back.edges()[0].start_point().distance(start) < 1e-12);
+        assert!(v[0].is_same(
+            &v[0]
+                .moved(&Transform::identity())
+                .located(Location::identity())
+        ));
+    }
+
+    #[test]
+    fn test_pcurve_lookup_on_located_face() {
+        let square: Vec<Vertex> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| Vertex::new(Point3::new(x, y, 0.0)))
+            .collect();
+        let edges: Vec<Edge> = (0..4)
+            .map(|i| segment_edge(&square[i], &square[(i + 1) % 4]))
+            .collect();
+        let pcurves = vec![edges
+            .iter()
+            .map(|e| {
+                let (a, b) = (e.start_point(), e.end_point());
+                Some(
+                    BSplineCurve2::from_flat_knots(
+                        1,
+                        vec![Vector2::new(a.x, a.y), Vector2::new(b.x, b.y)],
+                        vec![0.0, 0.0, 1.0, 1.0],
+                    )
+                    .unwrap(),
+                )
+            })
+            .collect()];
+        let face = Face::with_pcurves(Plane::xy(), vec![Wire::new(edges).unwrap()], pcurves)
+            .unwrap()
+            .moved(&Transform::from_translation(crate::Vector3::new(
+                0.0, 0.0, 2.0,
+            )));
+        for edge in face.outer_wire().unwrap().edges() {
+            let uv = face.pcurve(&edge).unwrap().point_at(0.5);
+            assert!(face.point_at(uv.x, uv.y).distance(edge.point_at(0.5)) < 1e-12);
+        }
+        // 配置の異なるエッジ（元のエッジ）には対応しない
+        let original = segment_edge(&square[0], &square[1]);
+        assert!(face.pcurve(&original).is_none());
+    }
+
+    #[test]
+    fn test_copy_keeps_sharing() {
+        let (_, _, shell) = tetrahedron();
+        let solid = Shape::from(Solid::new(vec![shell]).unwrap()).moved(
+            &Transform::from_translation(crate::Vector3::new(1.0, 2.0, 3.0)),
+        );
+        for deep in [false, true] {
+            let copy = solid.copy(deep);
+            assert!(!copy.is_same(&solid));
+            assert_eq!(copy.location(), solid.location());
+            assert_eq!(copy.explore(ShapeKind::Face).unique().count(), 4);
+            assert_eq!(copy.explore(ShapeKind::Edge).unique().count(), 6);
+            assert_eq!(copy.explore(ShapeKind::Vertex).unique().count(), 4);
+            let edge = |s: &Shape| Edge::try_from(s.explore(ShapeKind::Edge).next().unwrap());
+            let (a, b) = (edge(&solid).unwrap(), edge(&copy).unwrap());
+            assert!(Solid::try_from(copy).unwrap().shells()[0].is_closed());
+            assert!(!a.is_partner(&b));
+            assert!(a.start_point().distance(b.start_point()) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_copy_shares_or_duplicates_geometry() {
+        let (_, _, shell) = tetrahedron();
+        let face = shell.faces()[0].clone();
+        let edge = face.outer_wire().unwrap().edges()[0].clone();
+        let shallow = Face::try_from(Shape::from(face.clone()).copy(false)).unwrap();
+        let deep = Face::try_from(Shape::from(face.clone()).copy(true)).unwrap();
+        assert!(sync::Arc::ptr_eq(&face.data.surface, &shallow.data.surface));
+        assert!(!sync::Arc::ptr_eq(&face.data.surface, &deep.data.surface));
+        let curve = |f: &Face| {
+            f.outer_wire().unwrap().edges()[0]
+                .data
+                .curve
+                .clone()
+                .unwrap()
+        };
+        assert!(sync::Arc::ptr_eq(
+            edge.data.curve.as_ref().unwrap(),
+            &curve(&shallow)
+        ));
+        assert!(!sync::Arc::ptr_eq(
+            edge.data.curve.as_ref().unwrap(),
+            &curve(&deep)
+        ));
+        assert_eq!(deep.orientation(), face.orientation());
+    }
+
+    #[test]
+    fn test_transformed_shapes() {
+        use crate::fix::signed_volume;
+        use crate::primitives::make_cylinder;
+        use crate::{Axis2, Vector3};
+        let position = Axis2::new(Point3::new(1.0, 0.0, 0.0), Dir::Z, Dir::X).unwrap();
+        let cylinder = Shape::from(make_cylinder(position, 1.0, 2.0).unwrap());
+        let volume = |s: &Shape| -> f64 {
+            let report = s.check();
+            assert!(report.is_valid(), "{:?}", report.issues());
+            s.explore(ShapeKind::Shell)
+                .filter_map(|s| Shell::try_from(s).ok())
+                .map(|shell| signed_volume(&shell))
+                .sum()
+        };
+        let base = volume(&cylinder);
+        let mirror = Plane::new(Point3::new(3.0, 0.0, 0.0), Dir::X);
+        let axis = Axis1 {
+            location: Point3::new(0.0, 0.0, 0.0),
+            direction: Dir::Y,
+        };
+        for copy in [false, true] {
+            let results = [
+                (
+                    cylinder.mirrored(&mirror, copy).unwrap(),
+                    1.0,
+                    Point3::new(5.0, 0.0, 1.0),
+                    true,
+                ),
+                (
+                    cylinder
+                        .translated(Vector3::new(0.0, 0.0, 5.0), copy)
+                        .unwrap(),
+                    1.0,
+                    Point3::new(1.0, 0.0, 6.0),
+                    false,
+                ),
+                (
+                    cylinder
+                        .rotated(&axis, std::f64::consts::FRAC_PI_2, copy)
+                        .unwrap(),
+                    1.0,
+                    Point3::new(1.0, 0.0, -1.0),
+                    false,
+                ),
+                (
+                    cylinder.scaled(Point3::origin(), 2.0, copy).unwrap(),
+                    8.0,
+                    Point3::new(2.0, 0.0, 2.0),
+                    false,
+                ),
+                (
+                    cylinder.scaled(Point3::origin(), -0.5, copy).unwrap(),
+                    0.125,
+                    Point3::new(-0.5, 0.0, -0.5),
+                    true,
+                ),
+            ];
+            for (shape, ratio, axis_point, mirror) in results {
+                // 鏡映（負の倍率）は常に焼き込んだ複製になる
+                let shared = !copy && !mirror;
+                assert_eq!(shape.faces()[0].is_partner(&cylinder.faces()[0]), shared);
+                assert_eq!(shape.location().is_identity(), !shared);
+                assert!((volume(&shape) / base - ratio).abs() < 1e-9);
+                // 軸の中点から側面までの距離は半径に倍率を掛けたものになる
+                let face = shape
+                    .faces()
+                    .into_iter()
+                    .find(|f| matches!(f.surface(), GeomSurface::Cylinder(_)))
+                    .unwrap();
+                if let GeomSurface::Cylinder(c) = face.located_geometry().as_ref() {
+                    assert!(c.position().is_direct());
+                }
+                let radius = ratio.cbrt();
+                let d = face
+                    .located_geometry()
+                    .project(axis_point)
+                    .map(|p| p.distance)
+                    .unwrap();
+                assert!((d - radius).abs() < 1e-9, "{} {}", d, radius);
+            }
+        }
+        assert!(cylinder.scaled(Point3::origin(), 0.0, true).is_err());
+    }
+
+    #[test]
+    fn test_shape_equality_and_hash() {
+        let (v, e, _) = tetrahedron();
+        let mut set = std::collections::HashSet::new();
+        assert!(set.insert(e[0].clone()));
+        assert!(!set.insert(e[0].clone()));
+        assert!(set.insert(e[0].reversed()));
+        assert!(set.insert(segment_edge(&v[0], &v[1])));
+        assert_eq!(set.len(), 3);
+        let moved = e[0].moved(&Transform::from_translation(crate::Vector3::new(
+            0.0, 0.0, 1.0,
+        )));
+        assert_ne!(moved, e[0]);
+        assert_eq!(
+            moved,
+            e[0].moved(&Transform::from_translation(crate::Vector3::new(
+                0.0, 0.0, 1.0
+            )))
+        );
+
+        let shapes: std::collections::HashSet<Shape> = [
+            Shape::from(e[0].clone()),
+            e[0].clone().into(),
+            v[0].clone().into(),
+        ]
+        .into();
+        assert_eq!(shapes.len(), 2);
+        assert_ne!(Shape::from(e[0].clone()), Shape::from(e[0].reversed()));
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+use crate::{Matrix3, Matrix4, Point3, Quaternion, Vector3};
+
+/// 回転・平行移動・一様スケールからなる変換（OCCT の `gp_Trsf` 相当）
+///
+/// 点 `p` は `scale * rotation(p) + translation` に写される。
+/// `scale` が負の場合は原点に関する点対称を含む（鏡映の表現に利用する）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transform {
+    pub rotation: Quaternion,
+    pub translation: Vector3,
+    pub scale: f64,
+}
+
+impl Transform {
+    /// 各成分を指定して変換を生成する
+    pub fn new(rotation: Quaternion, translation: Vector3, scale: f64) -> Self {
+        Self {
+            rotation,
+            translation,
+            scale,
+        }
+    }
+
+    /// 恒等変換を返す
+    pub fn identity() -> Self {
+        Self::new(Quaternion::identity(), Vector3::new(0.0, 0.0, 0.0), 1.0)
+    }
+
+    /// 平行移動を生成する
+    pub fn from_translation(t: Vector3) -> Self {
+        Self::new(Quaternion::identity(), t, 1.0)
+    }
+
+    /// 原点まわりの回転を生成する
+    pub fn from_rotation(rotation: Quaternion) -> Self {
+        Self::new(rotation, Vector3::new(0.0, 0.0, 0.0), 1.0)
+    }
+
+    /// 原点を中心とする一様スケールを生成する
+    pub fn from_scale(scale: f64) -> Self {
+        Self::new(Quaternion::identity(), Vector3::new(0.0, 0.0, 0.0), scale)
+    }
+
+    /// 指定した点を中心とする一様スケールを生成する
+    pub fn scale_about(center: Point3, scale: f64) -> Self {
+        let c = center.to_vector();
+        Self::new(Quaternion::identity(), c - c * scale, scale)
+    }
+
+    /// 点を変換する
+    pub fn transform_point(&self, p: Point3) -> Point3 {
+        Point3::from(self.transform_vector(p.to_vector()) + self.translation)
+    }
+
+    /// ベクトルを変換する（平行移動は影響しない）
+    pub fn transform_vector(&self, v: Vector3) -> Vector3 {
+        self.rotation.rotate(v) * self.scale
+    }
+
+    /// 逆変換を返す
+    /// スケールがゼロの場合は `None` を返す
+    pub fn inverse(&self) -> Option<Self> {
+        if self.scale == 0.0 {
+            return None;
+        }
+        let rotation = self.rotation.conjugate();
+        let scale = 1.0 / self.scale;
+        let translation = rotation.rotate(self.translation) * -scale;
+        Some(Self::new(rotation, translation, scale))
+    }
+
+    /// 平行移動を除いた線形部分（スケールを含む）を行列として返す
+    pub fn linear_matrix(&self) -> Matrix3 {
+        self.rotation.to_matrix3() * self.scale
+    }
+
+    /// 4x4の同次変換行列に変換する
+    pub fn to_matrix4(&self) -> Matrix4 {
+        let mut m = self.linear_matrix().to_matrix4();
+        m.m[0][3] = self.translation.x;
+        m.m[1][3] = self.translation.y;
+        m.m[2][3] = self.translation.z;
+        m
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// 変換の合成の実装
+/// `a * b` は「b を適用した後に a を適用する」変換を表す
+impl Mul for Transform {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.rotation * other.rotation,
+            self.transform_vector(other.translation) + self.translation,
+            self.scale * other.scale,
+        )
+    }
+}
+
+/// 変換による点の写像
+impl Mul<Point3> for Transform {
+    type Output = Point3;
+    fn mul(self, p: Point3) -> Point3 {
+        self.transform_point(p)
+    }
+}
+
+/// 変換によるベクトルの写像
+impl Mul<Vector3> for Transform {
+    type Output = Vector3;
+    fn mul(self, v: Vector3) -> Vector3 {
+        self.transform_vector(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_point_eq(a: Point3, b: Point3) {
+        assert!(a.distance(b) < 1e-10, "{:?} != {:?}", a, b);
+    }
+
+    fn sample() -> Transform {
+        Transform::new(
+            Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0), 0.8),
+            Vector3::new(1.0, -2.0, 3.0),
+            2.5,
+        )
+    }
+
+    #[test]
+    fn test_apply() {
+        let t = Transform::from_translation(Vector3::new(1.0, 2.0, 3.0))
+            * Transform::from_rotation(Quaternion::from_axis_angle(
+                Vector3::new(0.0, 0.0, 1.0),
+                FRAC_PI_2,
+            ));
+        assert_point_eq(t * Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 3.0, 3.0));
+        let v = t * Vector3::new(1.0, 0.0, 0.0);
+        assert!((v - Vector3::new(0.0, 1.0, 0.0)).length() < 1e-10);
+    }
+
+    #[test]
+    fn test_scale_about() {
+        let t = Transform::scale_about(Point3::new(1.0, 1.0, 1.0), 2.0);
+        assert_point_eq(t * Point3::new(1.0, 1.0, 1.0), Point3::new(1.0, 1.0, 1.0));
+        assert_point_eq(t * Point3::new(2.0, 1.0, 1.0), Point3::new(3.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let a = sample();
+        let b = Transform::new(
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), -0.3),
+            Vector3::new(0.5, 0.0, -1.0),
+            -0.5,
+        );
+        let p = Point3::new(0.3, 0.7, -1.1);
+        assert_point_eq((a * b) * p, a * (b * p));
+
+        let inv = a.inverse().unwrap();
+        assert_point_eq(inv * (a * p), p);
+        assert_point_eq((a * inv) * p, p);
+        assert!(Transform::from_scale(0.0).inverse().is_none());
+    }
+
+    #[test]
+    fn test_matrix_agrees() {
+        let t = sample();
+        let p = Point3::new(-1.0, 4.0, 2.0);
+        assert_point_eq(t.to_matrix4().transform_point(p), t * p);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let t = Transform::new(
+            Quaternion::new(0.5, 0.5, -0.5, 0.5),
+            Vector3::new(1.0, -2.0, 3.0),
+            2.5,
+        );
+        let json = serde_json::to_string_pretty(&t).unwrap();
+        let back: Transform = serde_json::from_str(&json).unwrap();
+        assert_eq!(t, back);
+    }
+}
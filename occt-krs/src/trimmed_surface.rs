@@ -0,0 +1,451 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::precision;
+use crate::{BSplineCurve2, OcctKrsError, Point3, Result, Surface, Vector2};
+
+/// 境界の曲線を折れ線で近似するときの、次数2以上の曲線のノット区間あたりの分割数
+const SEGMENTS_PER_SPAN: usize = 16;
+
+/// 点とトリム領域の位置関係（OCCT の `TopAbs_State` 相当）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PointClassification {
+    /// 領域の内部
+    Inside,
+    /// 領域の外部
+    Outside,
+    /// 境界上（許容誤差以内）
+    OnBoundary,
+}
+
+/// 曲面のパラメータ空間上の閉じた境界ループ（OCCT の `Wire` の pcurve 列に相当）
+///
+/// 曲線を順につなぎ、最後の曲線の終点が最初の曲線の始点に戻る。
+/// 内外判定には各曲線を折れ線で近似した多角形を用いる。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "Vec<BSplineCurve2>", into = "Vec<BSplineCurve2>")
+)]
+pub struct UvLoop {
+    curves: Vec<BSplineCurve2>,
+    polygon: Vec<Vector2>,
+}
+
+impl UvLoop {
+    /// 曲線の列から境界ループを生成する
+    /// 曲線が空の場合や、隣り合う曲線の端点が `precision::confusion()` 以内でつながっていない場合はエラーを返す
+    pub fn new(curves: Vec<BSplineCurve2>) -> Result<Self> {
+        if curves.is_empty() {
+            return Err(OcctKrsError::InvalidInput(
+                "境界ループには1本以上の曲線が必要です".to_string(),
+            ));
+        }
+        for (i, c) in curves.iter().enumerate() {
+            let next = &curves[(i + 1) % curves.len()];
+            let gap =
+                (c.point_at(c.last_parameter()) - next.point_at(next.first_parameter())).length();
+            if gap > precision::confusion() {
+                return Err(OcctKrsError::InvalidInput(format!(
+                    "{} 番目の曲線の終点が次の曲線の始点とつながっていません（距離 {}）",
+                    i, gap
+                )));
+            }
+        }
+        let polygon = curves.iter().flat_map(sample_curve).collect();
+        Ok(Self { curves, polygon })
+    }
+
+    /// 多角形の頂点列から、各辺を1次の曲線とした境界ループを生成する（始点を末尾に重ねない）
+    /// 頂点が3個未満の場合や、同じ頂点が連続する場合はエラーを返す
+    pub fn from_polygon(points: &[Vector2]) -> Result<Self> {
+        if points.len() < 3 {
+            return Err(OcctKrsError::InvalidInput(format!(
+                "多角形には 3 個以上の頂点が必要です（{} 個）",
+                points.len()
+            )));
+        }
+        let curves = (0..points.len())
+            .map(|i| {
+                let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                if (b - a).length() <= precision::confusion() {
+                    return Err(OcctKrsError::DegenerateGeometry(format!(
+                        "{} 番目の辺の長さがゼロです",
+                        i
+                    )));
+                }
+                BSplineCurve2::from_flat_knots(1, vec![a, b], vec![0.0, 0.0, 1.0, 1.0])
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(curves)
+    }
+
+    /// 境界を構成する曲線を返す
+    pub fn curves(&self) -> &[BSplineCurve2] {
+        &self.curves
+    }
+
+    /// 内外判定に用いる近似多角形の頂点列を返す（始点を末尾に重ねない）
+    pub fn polygon(&self) -> &[Vector2] {
+        &self.polygon
+    }
+
+    /// 近似多角形の符号付き面積を返す（反時計回りなら正）
+    pub fn signed_area(&self) -> f64 {
+        self.edges().map(|(a, b)| a.perp_dot(b)).sum::<f64>() / 2.0
+    }
+
+    /// 向きを反転したループを返す
+    pub fn reversed(&self) -> UvLoop {
+        let mut polygon = self.polygon.clone();
+        polygon.reverse();
+        polygon.rotate_right(1);
+        Self {
+            curves: self.curves.iter().rev().map(|c| c.reversed()).collect(),
+            polygon,
+        }
+    }
+
+    /// 点のまわりの回転数（反時計回りに囲めば 1）を返す
+    pub fn winding_number(&self, p: Vector2) -> i32 {
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = (b - a).perp_dot(p - a);
+            if a.y <= p.y {
+                if b.y > p.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// 点から近似多角形までの距離を返す
+    pub fn distance(&self, p: Vector2) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let ab = b - a;
+                let len2 = ab.dot(ab);
+                let t = if len2 > 0.0 {
+                    ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                (p - (a + ab * t)).length()
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// 点と、ループで囲まれた領域との位置関係を返す（ループの向きによらない）
+    pub fn classify(&self, p: Vector2, tolerance: f64) -> PointClassification {
+        if self.distance(p) <= tolerance {
+            PointClassification::OnBoundary
+        } else if self.winding_number(p) != 0 {
+            PointClassification::Inside
+        } else {
+            PointClassification::Outside
+        }
+    }
+
+    /// パラメータ空間での範囲 `((u_min, u_max), (v_min, v_max))` を返す
+    fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        self.polygon.iter().fold(
+            (
+                (f64::INFINITY, f64::NEG_INFINITY),
+                (f64::INFINITY, f64::NEG_INFINITY),
+            ),
+            |((u0, u1), (v0, v1)), p| ((u0.min(p.x), u1.max(p.x)), (v0.min(p.y), v1.max(p.y))),
+        )
+    }
+
+    /// 近似多角形の辺を順に返す
+    fn edges(&self) -> impl Iterator<Item = (Vector2, Vector2)> + '_ {
+        let n = self.polygon.len();
+        (0..n).map(move |i| (self.polygon[i], self.polygon[(i + 1) % n]))
+    }
+}
+
+/// 曲線を折れ線で近似した点列を返す（終点は次の曲線の始点と重なるので含めない）
+fn sample_curve(curve: &BSplineCurve2) -> Vec<Vector2> {
+    let knots = curve.flat_knots();
+    let (a, b) = (curve.first_parameter(), curve.last_parameter());
+    let per_span = if curve.degree() == 1 && !curve.is_rational() {
+        1
+    } else {
+        SEGMENTS_PER_SPAN
+    };
+    let mut params = Vec::new();
+    for w in knots.windows(2) {
+        let (k0, k1) = (w[0].max(a), w[1].min(b));
+        if k0 < k1 {
+            params.extend((0..per_span).map(|i| k0 + (k1 - k0) * i as f64 / per_span as f64));
+        }
+    }
+    params.into_iter().map(|t| curve.point_at(t)).collect()
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Vec<BSplineCurve2>> for UvLoop {
+    type Error = OcctKrsError;
+    fn try_from(curves: Vec<BSplineCurve2>) -> Result<Self> {
+        UvLoop::new(curves)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<UvLoop> for Vec<BSplineCurve2> {
+    fn from(l: UvLoop) -> Self {
+        l.curves
+    }
+}
+
+/// パラメータ空間上の境界ループで切り取った曲面（BRep の面の幾何に相当）
+///
+/// 外側の境界は反時計回り、穴の境界は時計回りに揃えて保持するので、
+/// パラメータ空間で見て領域は常に境界の進行方向の左側にある。
+/// パラメータは基の曲面のものをそのまま使い、範囲は外側の境界を囲む矩形になる。
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrimmedSurface<S> {
+    basis: S,
+    outer: UvLoop,
+    inners: Vec<UvLoop>,
+}
+
+impl<S: Surface> TrimmedSurface<S> {
+    /// 基の曲面と外側・穴の境界ループから生成する（ループの向きは自動で揃える）
+    /// 外側の境界が面積を持たない場合や、穴の境界が外側の境界の内部にない場合はエラーを返す
+    pub fn new(basis: S, outer: UvLoop, inners: Vec<UvLoop>) -> Result<Self> {
+        let area = outer.signed_area();
+        if area.abs() <= precision::parametric() {
+            return Err(OcctKrsError::DegenerateGeometry(
+                "外側の境界ループが面積を持ちません".to_string(),
+            ));
+        }
+        let outer = if area < 0.0 { outer.reversed() } else { outer };
+        let inners = inners
+            .into_iter()
+            .enumerate()
+            .map(|(i, inner)| {
+                let inside = inner
+                    .polygon()
+                    .iter()
+                    .all(|p| outer.winding_number(*p) != 0);
+                if !inside {
+                    return Err(OcctKrsError::InvalidInput(format!(
+                        "{} 番目の穴の境界が外側の境界の内部にありません",
+                        i
+                    )));
+                }
+                Ok(if inner.signed_area() > 0.0 {
+                    inner.reversed()
+                } else {
+                    inner
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            basis,
+            outer,
+            inners,
+        })
+    }
+
+    /// 基の曲面のパラメータ範囲全体を領域とする（範囲が有限の場合のみ）
+    pub fn from_bounds(basis: S) -> Result<Self> {
+        let ((u0, u1), (v0, v1)) = (basis.u_range(), basis.v_range());
+        if ![u0, u1, v0, v1].iter().all(|x| x.is_finite()) {
+            return Err(OcctKrsError::InvalidInput(
+                "パラメータ範囲が無限の曲面は境界ループを指定する必要があります".to_string(),
+            ));
+        }
+        let outer = UvLoop::from_polygon(&[
+            Vector2::new(u0, v0),
+            Vector2::new(u1, v0),
+            Vector2::new(u1, v1),
+            Vector2::new(u0, v1),
+        ])?;
+        Self::new(basis, outer, Vec::new())
+    }
+
+    /// 基の曲面を返す
+    pub fn basis(&self) -> &S {
+        &self.basis
+    }
+
+    /// 外側の境界ループを返す
+    pub fn outer(&self) -> &UvLoop {
+        &self.outer
+    }
+
+    /// 穴の境界ループを返す
+    pub fn inners(&self) -> &[UvLoop] {
+        &self.inners
+    }
+
+    /// パラメータ `(u, v)` における点を返す
+    pub fn point_at(&self, u: f64, v: f64) -> Point3 {
+        self.basis.point_at(u, v)
+    }
+
+    /// パラメータ `(u, v)` と領域との位置関係を返す
+    ///
+    /// 境界は折れ線で近似するため、`tolerance` は近似誤差より大きく取ること。
+    pub fn classify(&self, u: f64, v: f64, tolerance: f64) -> PointClassification {
+        let p = Vector2::new(u, v);
+        match self.outer.classify(p, tolerance) {
+            PointClassification::Inside => {}
+            state => return state,
+        }
+        for inner in &self.inners {
+            match inner.classify(p, tolerance) {
+                PointClassification::Inside => return PointClassification::Outside,
+                PointClassification::OnBoundary => return PointClassification::OnBoundary,
+                PointClassification::Outside => {}
+            }
+        }
+        PointClassification::Inside
+    }
+
+    /// パラメータ空間での範囲 `((u_min, u_max), (v_min, v_max))` を返す
+    pub(crate) fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        self.outer.bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Axis3, CylindricalSurface, Dir, Plane};
+    use std::f64::consts::TAU;
+
+    fn square(c: Vector2, h: f64) -> UvLoop {
+        UvLoop::from_polygon(&[
+            Vector2::new(c.x - h, c.y - h),
+            Vector2::new(c.x + h, c.y - h),
+            Vector2::new(c.x + h, c.y + h),
+            Vector2::new(c.x - h, c.y + h),
+        ])
+        .unwrap()
+    }
+
+    fn circle_loop(c: Vector2, r: f64) -> UvLoop {
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let pts = [
+            (1.0, 0.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+            (-1.0, 1.0),
+            (-1.0, 0.0),
+            (-1.0, -1.0),
+            (0.0, -1.0),
+            (1.0, -1.0),
+            (1.0, 0.0),
+        ];
+        let curve = BSplineCurve2::from_flat_knots(
+            2,
+            pts.iter()
+                .map(|&(x, y)| Vector2::new(c.x + r * x, c.y + r * y))
+                .collect(),
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0],
+        )
+        .unwrap()
+        .with_weights(vec![1.0, w, 1.0, w, 1.0, w, 1.0, w, 1.0])
+        .unwrap();
+        UvLoop::new(vec![curve]).unwrap()
+    }
+
+    #[test]
+    fn test_loop_validation() {
+        assert!(UvLoop::new(Vec::new()).is_err());
+        let open = BSplineCurve2::from_flat_knots(
+            1,
+            vec![Vector2::ZERO, Vector2::X],
+            vec![0.0, 0.0, 1.0, 1.0],
+        )
+        .unwrap();
+        assert!(UvLoop::new(vec![open]).is_err());
+        assert!(UvLoop::from_polygon(&[Vector2::ZERO, Vector2::X]).is_err());
+        let l = square(Vector2::ZERO, 1.0);
+        assert!((l.signed_area() - 4.0).abs() < 1e-12);
+        assert!((l.reversed().signed_area() + 4.0).abs() < 1e-12);
+        assert_eq!(l.winding_number(Vector2::ZERO), 1);
+        assert_eq!(l.reversed().winding_number(Vector2::ZERO), -1);
+        assert_eq!(l.winding_number(Vector2::new(2.0, 0.0)), 0);
+    }
+
+    #[test]
+    fn test_plane_with_hole() {
+        let face = TrimmedSurface::new(
+            Plane::xy(),
+            square(Vector2::ZERO, 2.0).reversed(),
+            vec![circle_loop(Vector2::ZERO, 1.0)],
+        )
+        .unwrap();
+        // 向きは外側が反時計回り、穴が時計回りに揃う
+        assert!(face.outer().signed_area() > 0.0);
+        assert!(face.inners()[0].signed_area() < 0.0);
+        assert!((face.inners()[0].signed_area() + std::f64::consts::PI).abs() < 1e-2);
+        let tol = 1e-6;
+        assert_eq!(face.classify(1.5, 1.5, tol), PointClassification::Inside);
+        assert_eq!(face.classify(0.2, -0.3, tol), PointClassification::Outside);
+        assert_eq!(face.classify(3.0, 0.0, tol), PointClassification::Outside);
+        assert_eq!(
+            face.classify(2.0, 0.5, tol),
+            PointClassification::OnBoundary
+        );
+        assert_eq!(
+            face.classify(1.0, 0.0, tol),
+            PointClassification::OnBoundary
+        );
+        assert_eq!(
+            face.classify(0.0, 1.0 + 1e-3, tol),
+            PointClassification::Inside
+        );
+        assert_eq!(face.bounds(), ((-2.0, 2.0), (-2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_invalid_hole_is_rejected() {
+        let result = TrimmedSurface::new(
+            Plane::xy(),
+            square(Vector2::ZERO, 1.0),
+            vec![square(Vector2::new(3.0, 0.0), 0.5)],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_bounds() {
+        let cylinder =
+            CylindricalSurface::new(Axis3::from_normal(Point3::ORIGIN, Dir::Z), 1.0).unwrap();
+        assert!(TrimmedSurface::from_bounds(cylinder).is_err());
+        let face = TrimmedSurface::new(
+            cylinder,
+            UvLoop::from_polygon(&[
+                Vector2::new(0.0, 0.0),
+                Vector2::new(TAU, 0.0),
+                Vector2::new(TAU, 2.0),
+                Vector2::new(0.0, 2.0),
+            ])
+            .unwrap(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(face.classify(1.0, 1.0, 1e-9), PointClassification::Inside);
+        assert_eq!(face.classify(1.0, 3.0, 1e-9), PointClassification::Outside);
+        assert!(face.point_at(0.0, 1.0).distance(Point3::new(1.0, 0.0, 1.0)) < 1e-12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_loop_serde_round_trip() {
+        let l = circle_loop(Vector2::new(1.0, 2.0), 0.5);
+        let json = serde_json::to_string(&l).unwrap();
+        let back: UvLoop = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, l);
+    }
+}
@@ -0,0 +1,253 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{NormalizeError, Vector3};
+
+/// 2次元ベクトルを表す構造体
+///
+/// スケッチ平面上の計算など、平面幾何で利用する。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vector2 {
+    /// ゼロベクトル
+    pub const ZERO: Vector2 = Vector2::new(0.0, 0.0);
+    /// 全成分が1のベクトル
+    pub const ONE: Vector2 = Vector2::new(1.0, 1.0);
+    /// X軸方向の単位ベクトル
+    pub const X: Vector2 = Vector2::new(1.0, 0.0);
+    /// Y軸方向の単位ベクトル
+    pub const Y: Vector2 = Vector2::new(0.0, 1.0);
+
+    /// 新しいベクトルを生成する
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Vector2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 2次元の外積（perp-dot）を計算する
+    /// `other` が `self` から反時計回り側にあれば正になる
+    pub fn perp_dot(self, other: Vector2) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// 反時計回りに90度回転したベクトルを返す
+    pub fn perp(self) -> Vector2 {
+        Vector2::new(-self.y, self.x)
+    }
+
+    /// ベクトルの長さ（ノルム）を計算する
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// 正規化（単位ベクトル化）する
+    /// ※長さがゼロの場合はpanicするので注意。ライブラリ内部では `try_normalized` を使うこと
+    pub fn normalized(self) -> Vector2 {
+        match self.try_normalized() {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// 正規化（単位ベクトル化）する
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn try_normalized(self) -> Result<Vector2, NormalizeError> {
+        let len = self.length();
+        if !len.is_finite() || len <= f64::MIN_POSITIVE {
+            return Err(NormalizeError);
+        }
+        Ok(Vector2::new(self.x / len, self.y / len))
+    }
+
+    /// 正規化（単位ベクトル化）する
+    /// 正規化できない場合は `default` を返す
+    pub fn normalize_or(self, default: Vector2) -> Vector2 {
+        self.try_normalized().unwrap_or(default)
+    }
+
+    /// z成分を与えて3次元ベクトルに拡張する
+    pub fn extend(self, z: f64) -> Vector3 {
+        Vector3::new(self.x, self.y, z)
+    }
+}
+
+/// z成分を捨てて3次元ベクトルから変換する
+impl From<Vector3> for Vector2 {
+    fn from(v: Vector3) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+/// z = 0 として3次元ベクトルに変換する
+impl From<Vector2> for Vector3 {
+    fn from(v: Vector2) -> Self {
+        v.extend(0.0)
+    }
+}
+
+/// Vector2同士の加算の実装
+impl Add for Vector2 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Vector2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+/// Vector2同士の減算の実装
+impl Sub for Vector2 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Vector2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Vector2のスカラー倍の実装 (ベクトル * スカラー)
+impl Mul<f64> for Vector2 {
+    type Output = Self;
+    fn mul(self, scalar: f64) -> Self {
+        Vector2::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+/// スカラー倍の右側にベクトルを許容するための実装 (スカラー * ベクトル)
+impl Mul<Vector2> for f64 {
+    type Output = Vector2;
+    fn mul(self, vector: Vector2) -> Vector2 {
+        vector * self
+    }
+}
+
+/// Vector2の符号反転の実装
+impl Neg for Vector2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
+/// Vector2のスカラー除算の実装 (ベクトル / スカラー)
+impl Div<f64> for Vector2 {
+    type Output = Self;
+    fn div(self, scalar: f64) -> Self {
+        Vector2::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+/// 加算代入の実装
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// 減算代入の実装
+impl SubAssign for Vector2 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// スカラー倍代入の実装
+impl MulAssign<f64> for Vector2 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+/// スカラー除算代入の実装
+impl DivAssign<f64> for Vector2 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_and_perp_dot() {
+        let a = Vector2::new(1.0, 0.0);
+        let b = Vector2::new(0.0, 1.0);
+        assert!(a.dot(b).abs() < 1e-10);
+        // b は a から反時計回り側にあるので正
+        assert!((a.perp_dot(b) - 1.0).abs() < 1e-10);
+        assert!((b.perp_dot(a) + 1.0).abs() < 1e-10);
+        assert_eq!(a.perp(), b);
+    }
+
+    #[test]
+    fn test_length_and_normalization() {
+        let v = Vector2::new(3.0, 4.0);
+        assert!((v.length() - 5.0).abs() < 1e-10);
+        assert!((v.normalized().length() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "ゼロ長ベクトルは正規化できません")]
+    fn test_normalize_zero_vector() {
+        let _ = Vector2::new(0.0, 0.0).normalized();
+    }
+
+    #[test]
+    fn test_try_normalized() {
+        assert_eq!(Vector2::new(0.0, 0.0).try_normalized(), Err(NormalizeError));
+        let fallback = Vector2::new(1.0, 0.0);
+        assert_eq!(Vector2::new(0.0, 0.0).normalize_or(fallback), fallback);
+        assert_eq!(
+            Vector2::new(0.0, -2.0).try_normalized(),
+            Ok(Vector2::new(0.0, -1.0))
+        );
+    }
+
+    #[test]
+    fn test_operators_and_conversions() {
+        let a = Vector2::new(1.0, 2.0);
+        let b = Vector2::new(3.0, -1.0);
+        assert_eq!(a + b, Vector2::new(4.0, 1.0));
+        assert_eq!(a - b, Vector2::new(-2.0, 3.0));
+        assert_eq!(2.0 * a, Vector2::new(2.0, 4.0));
+
+        let v3 = Vector3::from(a);
+        assert_eq!(v3, Vector3::new(1.0, 2.0, 0.0));
+        assert_eq!(
+            Vector2::from(Vector3::new(5.0, 6.0, 7.0)),
+            Vector2::new(5.0, 6.0)
+        );
+        assert_eq!(a.extend(3.0), Vector3::new(1.0, 2.0, 3.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let v = Vector2::new(1.5, -2.0);
+        let json = serde_json::to_string(&v).unwrap();
+        let back: Vector2 = serde_json::from_str(&json).unwrap();
+        assert_eq!(v, back);
+    }
+
+    #[test]
+    fn test_neg_div_and_assign_ops() {
+        let v = Vector2::new(2.0, -4.0);
+        assert_eq!(-v, Vector2::new(-2.0, 4.0));
+        assert_eq!(v / 2.0, Vector2::new(1.0, -2.0));
+
+        let mut w = v;
+        w += Vector2::new(1.0, 1.0);
+        w -= Vector2::new(0.0, 2.0);
+        assert_eq!(w, Vector2::new(3.0, -5.0));
+        w *= 2.0;
+        w /= 4.0;
+        assert_eq!(w, Vector2::new(1.5, -2.5));
+    }
+}
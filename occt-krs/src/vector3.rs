@@ -0,0 +1,806 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+use crate::{Dir, NormalizeError, ParseVectorError};
+
+/// 3次元ベクトルを表す構造体
+///
+/// `[f64; 3]` と同じメモリ配置になるよう `repr(C)` を指定している。
+/// デシリアライズでは `{"x":..}`・`{"X":..}` 形式のオブジェクトと `[x, y, z]` 形式の配列を受け付ける。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vector3Repr"))]
+#[repr(C)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// デシリアライズ時に受け付ける `Vector3` の表現
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Vector3Repr {
+    Array([f64; 3]),
+    Object {
+        #[serde(alias = "X")]
+        x: f64,
+        #[serde(alias = "Y")]
+        y: f64,
+        #[serde(alias = "Z")]
+        z: f64,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl From<Vector3Repr> for Vector3 {
+    fn from(r: Vector3Repr) -> Self {
+        match r {
+            Vector3Repr::Array([x, y, z]) => Vector3::new(x, y, z),
+            Vector3Repr::Object { x, y, z } => Vector3::new(x, y, z),
+        }
+    }
+}
+
+impl Vector3 {
+    /// ゼロベクトル
+    pub const ZERO: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+    /// 全成分が1のベクトル
+    pub const ONE: Vector3 = Vector3::new(1.0, 1.0, 1.0);
+    /// X軸方向の単位ベクトル
+    pub const X: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+    /// Y軸方向の単位ベクトル
+    pub const Y: Vector3 = Vector3::new(0.0, 1.0, 0.0);
+    /// Z軸方向の単位ベクトル
+    pub const Z: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
+    /// 新しいベクトルを生成する
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Vector3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// 外積を計算する
+    pub fn cross(self, other: Vector3) -> Vector3 {
+        Vector3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// ベクトルの長さ（ノルム）を計算する
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// `a` と `b` を線形補間する（t = 0 で a、t = 1 で b）
+    pub fn lerp(a: Vector3, b: Vector3, t: f64) -> Vector3 {
+        a + (b - a) * t
+    }
+
+    /// 2つのベクトルのなす角（0〜π、ラジアン）を計算する
+    /// どちらかがゼロベクトルの場合は 0 を返す
+    pub fn angle_between(self, other: Vector3) -> f64 {
+        // 小さな角度や π に近い角度でも精度を保つため atan2 を用いる
+        self.cross(other).length().atan2(self.dot(other))
+    }
+
+    /// `other` 方向への正射影を計算する
+    /// `other` がゼロベクトルの場合はエラーを返す
+    pub fn project_onto(self, other: Vector3) -> Result<Vector3, NormalizeError> {
+        let d = other.try_normalized()?;
+        Ok(d * self.dot(d))
+    }
+
+    /// `other` に直交する成分（正射影を除いた残り）を計算する
+    /// `other` がゼロベクトルの場合はエラーを返す
+    pub fn reject_from(self, other: Vector3) -> Result<Vector3, NormalizeError> {
+        Ok(self - self.project_onto(other)?)
+    }
+
+    /// 法線 `normal` を持つ面で反射したベクトルを返す
+    pub fn reflect(self, normal: Dir) -> Vector3 {
+        let n = normal.to_vector();
+        self - n * (2.0 * self.dot(n))
+    }
+
+    /// 原点を通る軸 `axis` まわりに角度 `angle`（ラジアン）だけ回転したベクトルを返す
+    ///
+    /// ロドリゲスの回転公式 `v cosθ + (k×v) sinθ + k (k·v)(1 - cosθ)` による。
+    pub fn rotated_about(self, axis: Dir, angle: f64) -> Vector3 {
+        let k = axis.to_vector();
+        let (s, c) = angle.sin_cos();
+        self * c + k.cross(self) * s + k * (k.dot(self) * (1.0 - c))
+    }
+
+    /// スカラー三重積 `a · (b × c)` を計算する
+    /// 3つのベクトルが張る平行六面体の符号付き体積に等しい
+    pub fn triple(a: Vector3, b: Vector3, c: Vector3) -> f64 {
+        a.dot(b.cross(c))
+    }
+
+    /// 2つのベクトルが平行（同じ向きまたは逆向き）か判定する
+    ///
+    /// OCCT の `gp_Vec::IsParallel` と同様に、なす角が `angular_tol` 以内、
+    /// または π との差が `angular_tol` 以内であれば平行とみなす。
+    /// 標準の許容誤差には `precision::angular()` を用いる。
+    /// どちらかがゼロベクトルの場合は `false` を返す。
+    pub fn is_parallel(self, other: Vector3, angular_tol: f64) -> bool {
+        if self.try_normalized().is_err() || other.try_normalized().is_err() {
+            return false;
+        }
+        let angle = self.angle_between(other);
+        angle <= angular_tol || std::f64::consts::PI - angle <= angular_tol
+    }
+
+    /// 2つのベクトルが逆向きか判定する（OCCT の `gp_Vec::IsOpposite` 相当）
+    /// どちらかがゼロベクトルの場合は `false` を返す
+    pub fn is_opposite(self, other: Vector3, angular_tol: f64) -> bool {
+        if self.try_normalized().is_err() || other.try_normalized().is_err() {
+            return false;
+        }
+        std::f64::consts::PI - self.angle_between(other) <= angular_tol
+    }
+
+    /// 2つのベクトルが直交するか判定する（OCCT の `gp_Vec::IsNormal` 相当）
+    /// どちらかがゼロベクトルの場合は `false` を返す
+    pub fn is_perpendicular(self, other: Vector3, angular_tol: f64) -> bool {
+        if self.try_normalized().is_err() || other.try_normalized().is_err() {
+            return false;
+        }
+        (std::f64::consts::FRAC_PI_2 - self.angle_between(other)).abs() <= angular_tol
+    }
+
+    /// 球座標からベクトルを生成する
+    ///
+    /// `r` は長さ、`theta` は +Z 軸からの極角（0〜π）、`phi` は XY 平面上で +X 軸から測った方位角。
+    pub fn from_spherical(r: f64, theta: f64, phi: f64) -> Vector3 {
+        let (st, ct) = theta.sin_cos();
+        let (sp, cp) = phi.sin_cos();
+        Vector3::new(r * st * cp, r * st * sp, r * ct)
+    }
+
+    /// 球座標 `(r, theta, phi)` に変換する（`from_spherical` と同じ規約）
+    /// ゼロベクトルでは `(0, 0, 0)` を返し、Z軸上では `phi = 0` とする
+    pub fn to_spherical(self) -> (f64, f64, f64) {
+        let r = self.length();
+        let rho = self.x.hypot(self.y);
+        (r, rho.atan2(self.z), self.y.atan2(self.x))
+    }
+
+    /// 円柱座標からベクトルを生成する
+    ///
+    /// `rho` は Z 軸からの距離、`phi` は +X 軸から測った方位角、`z` は高さ。
+    pub fn from_cylindrical(rho: f64, phi: f64, z: f64) -> Vector3 {
+        let (sp, cp) = phi.sin_cos();
+        Vector3::new(rho * cp, rho * sp, z)
+    }
+
+    /// 円柱座標 `(rho, phi, z)` に変換する（`from_cylindrical` と同じ規約）
+    pub fn to_cylindrical(self) -> (f64, f64, f64) {
+        (self.x.hypot(self.y), self.y.atan2(self.x), self.z)
+    }
+
+    /// 成分ごとの最小値からなるベクトルを返す
+    pub fn component_min(self, other: Vector3) -> Vector3 {
+        Vector3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// 成分ごとの最大値からなるベクトルを返す
+    pub fn component_max(self, other: Vector3) -> Vector3 {
+        Vector3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// 各成分を `min` と `max` の対応する成分の範囲に収める
+    /// ※`min` のいずれかの成分が `max` を上回る場合はpanicするので注意
+    pub fn clamp(self, min: Vector3, max: Vector3) -> Vector3 {
+        Vector3::new(
+            self.x.clamp(min.x, max.x),
+            self.y.clamp(min.y, max.y),
+            self.z.clamp(min.z, max.z),
+        )
+    }
+
+    /// 各成分の絶対値からなるベクトルを返す
+    pub fn abs(self) -> Vector3 {
+        Vector3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// 成分ごとの積（アダマール積）を計算する
+    pub fn component_mul(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+
+    /// 成分ごとの商を計算する
+    pub fn component_div(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x / other.x, self.y / other.y, self.z / other.z)
+    }
+
+    /// 最小の成分を返す
+    pub fn min_element(self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// 最大の成分を返す
+    pub fn max_element(self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// 成分を配列として返す
+    pub fn to_array(self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// 成分をスライスとして参照する
+    pub fn as_slice(&self) -> &[f64] {
+        // SAFETY: repr(C) で f64 が3つ連続して並ぶため [f64; 3] と同じ配置になる
+        unsafe { std::slice::from_raw_parts(self as *const Vector3 as *const f64, 3) }
+    }
+
+    /// 成分を可変スライスとして参照する
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        // SAFETY: repr(C) で f64 が3つ連続して並ぶため [f64; 3] と同じ配置になる
+        unsafe { std::slice::from_raw_parts_mut(self as *mut Vector3 as *mut f64, 3) }
+    }
+
+    /// 成分 (x, y, z) を順に返すイテレータ
+    pub fn iter(&self) -> std::slice::Iter<'_, f64> {
+        self.as_slice().iter()
+    }
+
+    /// 正規化（単位ベクトル化）する  
+    /// ※長さがゼロの場合はpanicするので注意。ライブラリ内部では `try_normalized` を使うこと
+    pub fn normalized(self) -> Vector3 {
+        match self.try_normalized() {
+            Ok(v) => v,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// 正規化（単位ベクトル化）する  
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn try_normalized(self) -> Result<Vector3, NormalizeError> {
+        let len = self.length();
+        if !len.is_finite() || len <= f64::MIN_POSITIVE {
+            return Err(NormalizeError);
+        }
+        Ok(Vector3 {
+            x: self.x / len,
+            y: self.y / len,
+            z: self.z / len,
+        })
+    }
+
+    /// 正規化（単位ベクトル化）する  
+    /// 正規化できない場合は `default` を返す
+    pub fn normalize_or(self, default: Vector3) -> Vector3 {
+        self.try_normalized().unwrap_or(default)
+    }
+}
+
+/// Vector3同士の加算の実装
+impl Add for Vector3 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+/// Vector3同士の減算の実装
+impl Sub for Vector3 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// Vector3のスカラー倍の実装 (ベクトル * スカラー)
+impl Mul<f64> for Vector3 {
+    type Output = Self;
+    fn mul(self, scalar: f64) -> Self {
+        Vector3::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+/// スカラー倍の右側にベクトルを許容するための実装 (スカラー * ベクトル)
+impl Mul<Vector3> for f64 {
+    type Output = Vector3;
+    fn mul(self, vector: Vector3) -> Vector3 {
+        vector * self
+    }
+}
+
+/// Vector3の符号反転の実装
+impl Neg for Vector3 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Vector3のスカラー除算の実装 (ベクトル / スカラー)
+impl Div<f64> for Vector3 {
+    type Output = Self;
+    fn div(self, scalar: f64) -> Self {
+        Vector3::new(self.x / scalar, self.y / scalar, self.z / scalar)
+    }
+}
+
+/// 加算代入の実装
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// 減算代入の実装
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// スカラー倍代入の実装
+impl MulAssign<f64> for Vector3 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+/// スカラー除算代入の実装
+impl DivAssign<f64> for Vector3 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+/// `(x, y, z)` の形式で表示する
+/// 精度指定（`{:.3}` など）は各成分に適用される
+impl fmt::Display for Vector3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "({:.*}, {:.*}, {:.*})", p, self.x, p, self.y, p, self.z),
+            None => write!(f, "({}, {}, {})", self.x, self.y, self.z),
+        }
+    }
+}
+
+/// 文字列からの読み取り
+///
+/// `(1, 2, 3)`、`[1, 2, 3]`、`1,2,3`、`1 2 3` のいずれの形式も受け付ける。
+impl FromStr for Vector3 {
+    type Err = ParseVectorError;
+    fn from_str(s: &str) -> Result<Self, ParseVectorError> {
+        let a = parse_components::<3>(s)?;
+        Ok(Vector3::new(a[0], a[1], a[2]))
+    }
+}
+
+/// 括弧で囲まれた（または囲まれていない）カンマ・空白区切りの数値を N 個読み取る
+pub(crate) fn parse_components<const N: usize>(s: &str) -> Result<[f64; N], ParseVectorError> {
+    let t = s.trim();
+    let t = match (t.chars().next(), t.chars().last()) {
+        (Some('('), Some(')')) | (Some('['), Some(']')) => &t[1..t.len() - 1],
+        _ => t,
+    };
+    let parts: Vec<&str> = t
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.len() != N {
+        return Err(ParseVectorError::new(format!(
+            "{}個の成分が必要ですが{}個でした: {:?}",
+            N,
+            parts.len(),
+            s
+        )));
+    }
+    let mut out = [0.0; N];
+    for (o, p) in out.iter_mut().zip(parts) {
+        *o = p
+            .parse()
+            .map_err(|_| ParseVectorError::new(format!("数値ではありません: {:?}", p)))?;
+    }
+    Ok(out)
+}
+
+/// 添字による成分アクセス（0: x, 1: y, 2: z）
+impl Index<usize> for Vector3 {
+    type Output = f64;
+    fn index(&self, i: usize) -> &f64 {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vector3 のインデックスが範囲外です: {}", i),
+        }
+    }
+}
+
+/// 添字による成分の書き換え（0: x, 1: y, 2: z）
+impl IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, i: usize) -> &mut f64 {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vector3 のインデックスが範囲外です: {}", i),
+        }
+    }
+}
+
+/// 配列からの変換
+impl From<[f64; 3]> for Vector3 {
+    fn from(a: [f64; 3]) -> Self {
+        Vector3::new(a[0], a[1], a[2])
+    }
+}
+
+/// 配列への変換
+impl From<Vector3> for [f64; 3] {
+    fn from(v: Vector3) -> Self {
+        v.to_array()
+    }
+}
+
+/// 成分を順に取り出すイテレータへの変換
+impl IntoIterator for Vector3 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 3>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_array().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Vector3 {
+    type Item = &'a f64;
+    type IntoIter = std::slice::Iter<'a, f64>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_product() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let b = Vector3::new(0.0, 1.0, 0.0);
+        // 直交するので内積は0
+        assert!((a.dot(b) - 0.0).abs() < 1e-10);
+
+        let c = Vector3::new(1.0, 2.0, 3.0);
+        let d = Vector3::new(4.0, -5.0, 6.0);
+        let dot = c.dot(d);
+        // 計算結果：1*4 + 2*(-5) + 3*6 = 4 - 10 + 18 = 12
+        assert!((dot - 12.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cross_product() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let b = Vector3::new(0.0, 1.0, 0.0);
+        let cross = a.cross(b);
+        // 右手系で外積は (0, 0, 1)
+        let expected = Vector3::new(0.0, 0.0, 1.0);
+        assert!((cross.x - expected.x).abs() < 1e-10);
+        assert!((cross.y - expected.y).abs() < 1e-10);
+        assert!((cross.z - expected.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_length_and_normalization() {
+        let v = Vector3::new(3.0, 4.0, 0.0);
+        let len = v.length();
+        // 3-4-5の三角形なので長さは5
+        assert!((len - 5.0).abs() < 1e-10);
+
+        let normalized = v.normalized();
+        // 正規化後は長さが1
+        assert!((normalized.length() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "ゼロ長ベクトルは正規化できません")]
+    fn test_normalize_zero_vector() {
+        let zero = Vector3::new(0.0, 0.0, 0.0);
+        // ゼロベクトルの正規化はpanicするのでテストが成功するはず
+        let _ = zero.normalized();
+    }
+
+    #[test]
+    fn test_try_normalized() {
+        let v = Vector3::new(0.0, 0.0, 2.0).try_normalized().unwrap();
+        assert_eq!(v, Vector3::new(0.0, 0.0, 1.0));
+
+        let zero = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(zero.try_normalized(), Err(NormalizeError));
+        assert!(Vector3::new(f64::INFINITY, 0.0, 0.0)
+            .try_normalized()
+            .is_err());
+
+        let fallback = Vector3::new(1.0, 0.0, 0.0);
+        assert_eq!(zero.normalize_or(fallback), fallback);
+        assert_eq!(
+            Vector3::new(0.0, 3.0, 0.0).normalize_or(fallback),
+            Vector3::new(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_neg_div_and_assign_ops() {
+        let v = Vector3::new(1.0, -2.0, 4.0);
+        assert_eq!(-v, Vector3::new(-1.0, 2.0, -4.0));
+        assert_eq!(v / 2.0, Vector3::new(0.5, -1.0, 2.0));
+
+        let mut w = v;
+        w += Vector3::new(1.0, 1.0, 1.0);
+        assert_eq!(w, Vector3::new(2.0, -1.0, 5.0));
+        w -= Vector3::new(2.0, 0.0, 1.0);
+        assert_eq!(w, Vector3::new(0.0, -1.0, 4.0));
+        w *= 3.0;
+        assert_eq!(w, Vector3::new(0.0, -3.0, 12.0));
+        w /= 3.0;
+        assert_eq!(w, Vector3::new(0.0, -1.0, 4.0));
+    }
+
+    #[test]
+    fn test_index_and_arrays() {
+        let mut v = Vector3::from([1.0, 2.0, 3.0]);
+        assert_eq!(v[0], 1.0);
+        assert_eq!(v[2], 3.0);
+        v[1] = 5.0;
+        assert_eq!(v.y, 5.0);
+
+        let a: [f64; 3] = v.into();
+        assert_eq!(a, [1.0, 5.0, 3.0]);
+        assert_eq!(v.as_slice(), &[1.0, 5.0, 3.0]);
+        v.as_mut_slice()[0] = -1.0;
+        assert_eq!(v.x, -1.0);
+    }
+
+    #[test]
+    fn test_component_iteration() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let sum: f64 = v.iter().sum();
+        assert_eq!(sum, 6.0);
+        let doubled: Vec<f64> = v.into_iter().map(|c| c * 2.0).collect();
+        assert_eq!(doubled, vec![2.0, 4.0, 6.0]);
+        for (i, c) in (&v).into_iter().enumerate() {
+            assert_eq!(*c, v[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Vector3 のインデックスが範囲外です: 3")]
+    fn test_index_out_of_range() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        let _ = v[3];
+    }
+
+    #[test]
+    fn test_component_wise_utilities() {
+        let a = Vector3::new(1.0, -5.0, 3.0);
+        let b = Vector3::new(2.0, -6.0, 0.0);
+        assert_eq!(a.component_min(b), Vector3::new(1.0, -6.0, 0.0));
+        assert_eq!(a.component_max(b), Vector3::new(2.0, -5.0, 3.0));
+        assert_eq!(a.abs(), Vector3::new(1.0, 5.0, 3.0));
+        assert_eq!(a.component_mul(b), Vector3::new(2.0, 30.0, 0.0));
+        assert_eq!(
+            a.component_div(Vector3::new(2.0, 5.0, 3.0)),
+            Vector3::new(0.5, -1.0, 1.0)
+        );
+        assert_eq!(a.min_element(), -5.0);
+        assert_eq!(a.max_element(), 3.0);
+
+        let lo = Vector3::new(0.0, 0.0, 0.0);
+        let hi = Vector3::new(1.0, 1.0, 1.0);
+        assert_eq!(a.clamp(lo, hi), Vector3::new(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let x = Vector3::new(2.0, 0.0, 0.0);
+        let y = Vector3::new(0.0, 3.0, 0.0);
+        assert!((x.angle_between(y) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((x.angle_between(-x) - std::f64::consts::PI).abs() < 1e-12);
+        assert!(x.angle_between(x).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_project_and_reject() {
+        let v = Vector3::new(3.0, 4.0, 5.0);
+        let axis = Vector3::new(0.0, 0.0, 2.0);
+        assert_eq!(v.project_onto(axis).unwrap(), Vector3::new(0.0, 0.0, 5.0));
+        assert_eq!(v.reject_from(axis).unwrap(), Vector3::new(3.0, 4.0, 0.0));
+        let zero = Vector3::new(0.0, 0.0, 0.0);
+        assert_eq!(v.project_onto(zero), Err(NormalizeError));
+        assert!(v.reject_from(zero).is_err());
+    }
+
+    #[test]
+    fn test_reflect() {
+        let v = Vector3::new(1.0, -1.0, 0.0);
+        let r = v.reflect(Dir::y_axis());
+        assert_eq!(r, Vector3::new(1.0, 1.0, 0.0));
+        // 反射で長さは変わらない
+        assert!((r.length() - v.length()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = Vector3::new(0.0, 0.0, 0.0);
+        let b = Vector3::new(2.0, 4.0, -6.0);
+        assert_eq!(Vector3::lerp(a, b, 0.0), a);
+        assert_eq!(Vector3::lerp(a, b, 1.0), b);
+        assert_eq!(Vector3::lerp(a, b, 0.5), Vector3::new(1.0, 2.0, -3.0));
+        // 範囲外の t では外挿になる
+        assert_eq!(Vector3::lerp(a, b, 2.0), Vector3::new(4.0, 8.0, -12.0));
+    }
+
+    #[test]
+    fn test_rotated_about() {
+        let v = Vector3::new(1.0, 0.0, 0.0);
+        let r = v.rotated_about(Dir::z_axis(), std::f64::consts::FRAC_PI_2);
+        assert!((r - Vector3::new(0.0, 1.0, 0.0)).length() < 1e-12);
+
+        // 軸方向の成分は変わらない
+        let axis = Dir::new(1.0, 1.0, 1.0).unwrap();
+        let w = Vector3::new(0.3, -2.0, 0.7);
+        let r = w.rotated_about(axis, 1.3);
+        assert!((r.dot(axis.to_vector()) - w.dot(axis.to_vector())).abs() < 1e-12);
+        assert!((r.length() - w.length()).abs() < 1e-12);
+        // 3回の 2π/3 回転で元に戻る
+        let third = 2.0 * std::f64::consts::PI / 3.0;
+        let back = w
+            .rotated_about(axis, third)
+            .rotated_about(axis, third)
+            .rotated_about(axis, third);
+        assert!((back - w).length() < 1e-12);
+    }
+
+    #[test]
+    fn test_triple_product() {
+        let x = Vector3::new(1.0, 0.0, 0.0);
+        let y = Vector3::new(0.0, 1.0, 0.0);
+        let z = Vector3::new(0.0, 0.0, 1.0);
+        assert_eq!(Vector3::triple(x, y, z), 1.0);
+        assert_eq!(Vector3::triple(y, x, z), -1.0);
+        assert_eq!(Vector3::triple(x, x, z), 0.0);
+    }
+
+    #[test]
+    fn test_parallel_and_perpendicular() {
+        use crate::precision;
+        let tol = precision::angular();
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        assert!(a.is_parallel(a * 2.5, tol));
+        assert!(a.is_parallel(-a, tol));
+        assert!(a.is_opposite(-a, tol));
+        assert!(!a.is_opposite(a, tol));
+        assert!(!a.is_parallel(Vector3::new(1.0, 2.0, 3.001), tol));
+        // 角度の許容誤差を緩めれば平行とみなす
+        assert!(a.is_parallel(Vector3::new(1.0, 2.0, 3.001), 1e-3));
+
+        let b = Vector3::new(3.0, 0.0, -1.0);
+        assert!(a.is_perpendicular(b, tol));
+        assert!(!a.is_perpendicular(a, tol));
+
+        let zero = Vector3::new(0.0, 0.0, 0.0);
+        assert!(!zero.is_parallel(a, tol));
+        assert!(!zero.is_perpendicular(a, tol));
+    }
+
+    #[test]
+    fn test_display() {
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.to_string(), "(1, 2, 3)");
+        assert_eq!(
+            format!("{:.2}", Vector3::new(0.5, -1.0, 1.0 / 3.0)),
+            "(0.50, -1.00, 0.33)"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let expected = Vector3::new(1.0, -2.5, 3e2);
+        for s in [
+            "(1, -2.5, 3e2)",
+            "[1,-2.5,3e2]",
+            " 1 -2.5 300 ",
+            "1, -2.5, 300",
+        ] {
+            assert_eq!(s.parse::<Vector3>().unwrap(), expected, "{}", s);
+        }
+        // Display の出力を読み戻せる
+        let v = Vector3::new(0.1, 0.2, 0.3);
+        assert_eq!(v.to_string().parse::<Vector3>().unwrap(), v);
+
+        assert!("(1, 2)".parse::<Vector3>().is_err());
+        assert!("(1, 2, x)".parse::<Vector3>().is_err());
+        assert!("".parse::<Vector3>().is_err());
+    }
+
+    #[test]
+    fn test_constants() {
+        // static で軸のテーブルを定義できる
+        static AXES: [Vector3; 3] = [Vector3::X, Vector3::Y, Vector3::Z];
+        assert_eq!(AXES[0].cross(AXES[1]), AXES[2]);
+        assert_eq!(Vector3::ZERO.length(), 0.0);
+        assert_eq!(Vector3::ONE, Vector3::new(1.0, 1.0, 1.0));
+        const HALF: Vector3 = Vector3::new(0.5, 0.5, 0.5);
+        assert_eq!(HALF * 2.0, Vector3::ONE);
+    }
+
+    #[test]
+    fn test_spherical_coordinates() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+        let v = Vector3::from_spherical(2.0, FRAC_PI_2, FRAC_PI_2);
+        assert!((v - Vector3::new(0.0, 2.0, 0.0)).length() < 1e-12);
+        let z = Vector3::from_spherical(1.0, 0.0, 1.0);
+        assert!((z - Vector3::Z).length() < 1e-12);
+
+        let w = Vector3::new(1.0, -2.0, 0.5);
+        let (r, theta, phi) = w.to_spherical();
+        assert!((Vector3::from_spherical(r, theta, phi) - w).length() < 1e-12);
+        assert!((0.0..=std::f64::consts::PI).contains(&theta));
+
+        let (r, theta, phi) = Vector3::new(1.0, 1.0, 0.0).to_spherical();
+        assert!((r - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((theta - FRAC_PI_2).abs() < 1e-12);
+        assert!((phi - FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(Vector3::ZERO.to_spherical(), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_cylindrical_coordinates() {
+        let v = Vector3::from_cylindrical(3.0, std::f64::consts::PI, 5.0);
+        assert!((v - Vector3::new(-3.0, 0.0, 5.0)).length() < 1e-12);
+        let w = Vector3::new(-1.0, -1.0, 2.0);
+        let (rho, phi, z) = w.to_cylindrical();
+        assert!((Vector3::from_cylindrical(rho, phi, z) - w).length() < 1e-12);
+        assert!((rho - 2.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_layouts() {
+        let expected = Vector3::new(1.0, -2.0, 3.5);
+        for json in [
+            r#"{"x": 1.0, "y": -2.0, "z": 3.5}"#,
+            r#"{"X": 1.0, "Y": -2.0, "Z": 3.5}"#,
+            r#"[1.0, -2.0, 3.5]"#,
+        ] {
+            let v: Vector3 = serde_json::from_str(json).unwrap();
+            assert_eq!(v, expected);
+        }
+        // 出力は従来どおり小文字のオブジェクト形式
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#"{"x":1.0,"y":-2.0,"z":3.5}"#
+        );
+        assert!(serde_json::from_str::<Vector3>("[1.0, 2.0]").is_err());
+        assert!(serde_json::from_str::<Vector3>(r#"{"x": 1.0, "y": 2.0}"#).is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! 単精度（f32）版のベクトル・点型
+//!
+//! メッシュやレンダリング向けのパイプラインでは f32 を、モデリングでは f64 を使うことを想定し、
+//! f64 版の型と相互に変換できる単精度の型を提供する。
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+use crate::{NormalizeError, Point3, Vector2, Vector3};
+
+/// 単精度の2次元ベクトル
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Vector2f32 {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// 単精度の3次元ベクトル
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Vector3f32 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// 単精度の3次元の点
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Point3f32 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vector2f32 {
+    /// 新しいベクトルを生成する
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 2次元の外積（perp-dot）を計算する
+    pub fn perp_dot(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// ベクトルの長さ（ノルム）を計算する
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// 正規化（単位ベクトル化）する
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn try_normalized(self) -> Result<Self, NormalizeError> {
+        let len = self.length();
+        if !len.is_finite() || len <= f32::MIN_POSITIVE {
+            return Err(NormalizeError);
+        }
+        Ok(self / len)
+    }
+
+    /// 倍精度のベクトルに変換する
+    pub fn to_f64(self) -> Vector2 {
+        Vector2::new(self.x as f64, self.y as f64)
+    }
+}
+
+impl Vector3f32 {
+    /// 新しいベクトルを生成する
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// 内積を計算する
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// 外積を計算する
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// ベクトルの長さ（ノルム）を計算する
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// 正規化（単位ベクトル化）する
+    /// 長さがほぼゼロ、または有限でない場合はエラーを返す
+    pub fn try_normalized(self) -> Result<Self, NormalizeError> {
+        let len = self.length();
+        if !len.is_finite() || len <= f32::MIN_POSITIVE {
+            return Err(NormalizeError);
+        }
+        Ok(self / len)
+    }
+
+    /// 成分を配列として返す
+    pub fn to_array(self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// 成分をスライスとして参照する
+    pub fn as_slice(&self) -> &[f32] {
+        // SAFETY: repr(C) で f32 が3つ連続して並ぶため [f32; 3] と同じ配置になる
+        unsafe { std::slice::from_raw_parts(self as *const Self as *const f32, 3) }
+    }
+
+    /// 倍精度のベクトルに変換する
+    pub fn to_f64(self) -> Vector3 {
+        Vector3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+}
+
+impl Point3f32 {
+    /// 新しい点を生成する
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// 2点間の距離を計算する
+    pub fn distance(self, other: Self) -> f32 {
+        (self - other).length()
+    }
+
+    /// 成分を配列として返す
+    pub fn to_array(self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// 倍精度の点に変換する
+    pub fn to_f64(self) -> Point3 {
+        Point3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+}
+
+impl Vector2 {
+    /// 単精度のベクトルに変換する（精度が落ちる）
+    pub fn to_f32(self) -> Vector2f32 {
+        Vector2f32::new(self.x as f32, self.y as f32)
+    }
+}
+
+impl Vector3 {
+    /// 単精度のベクトルに変換する（精度が落ちる）
+    pub fn to_f32(self) -> Vector3f32 {
+        Vector3f32::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+}
+
+impl Point3 {
+    /// 単精度の点に変換する（精度が落ちる）
+    pub fn to_f32(self) -> Point3f32 {
+        Point3f32::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+}
+
+/// 倍精度の点列を単精度にまとめて変換する
+pub fn points_to_f32(points: &[Point3]) -> Vec<Point3f32> {
+    points.iter().map(|p| p.to_f32()).collect()
+}
+
+/// 単精度の点列を倍精度にまとめて変換する
+pub fn points_to_f64(points: &[Point3f32]) -> Vec<Point3> {
+    points.iter().map(|p| p.to_f64()).collect()
+}
+
+/// 単精度から倍精度への変換は精度を落とさないので `From` を実装する
+impl From<Vector2f32> for Vector2 {
+    fn from(v: Vector2f32) -> Self {
+        v.to_f64()
+    }
+}
+
+impl From<Vector3f32> for Vector3 {
+    fn from(v: Vector3f32) -> Self {
+        v.to_f64()
+    }
+}
+
+impl From<Point3f32> for Point3 {
+    fn from(p: Point3f32) -> Self {
+        p.to_f64()
+    }
+}
+
+impl From<[f32; 3]> for Vector3f32 {
+    fn from(a: [f32; 3]) -> Self {
+        Self::new(a[0], a[1], a[2])
+    }
+}
+
+impl From<Vector3f32> for [f32; 3] {
+    fn from(v: Vector3f32) -> Self {
+        v.to_array()
+    }
+}
+
+impl Index<usize> for Vector3f32 {
+    type Output = f32;
+    fn index(&self, i: usize) -> &f32 {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vector3f32 のインデックスが範囲外です: {}", i),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vector3f32 {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vector3f32 のインデックスが範囲外です: {}", i),
+        }
+    }
+}
+
+/// 成分ごとの演算子をまとめて実装する
+macro_rules! impl_vector_ops {
+    ($t:ident { $($f:ident),+ }) => {
+        impl Add for $t {
+            type Output = Self;
+            fn add(self, o: Self) -> Self {
+                Self { $($f: self.$f + o.$f),+ }
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+            fn sub(self, o: Self) -> Self {
+                Self { $($f: self.$f - o.$f),+ }
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+            fn mul(self, s: f32) -> Self {
+                Self { $($f: self.$f * s),+ }
+            }
+        }
+
+        impl Mul<$t> for f32 {
+            type Output = $t;
+            fn mul(self, v: $t) -> $t {
+                v * self
+            }
+        }
+
+        impl Div<f32> for $t {
+            type Output = Self;
+            fn div(self, s: f32) -> Self {
+                Self { $($f: self.$f / s),+ }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self { $($f: -self.$f),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, o: Self) {
+                *self = *self + o;
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, o: Self) {
+                *self = *self - o;
+            }
+        }
+
+        impl MulAssign<f32> for $t {
+            fn mul_assign(&mut self, s: f32) {
+                *self = *self * s;
+            }
+        }
+
+        impl DivAssign<f32> for $t {
+            fn div_assign(&mut self, s: f32) {
+                *self = *self / s;
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Vector2f32 { x, y });
+impl_vector_ops!(Vector3f32 { x, y, z });
+
+/// 点 + ベクトル = 点
+impl Add<Vector3f32> for Point3f32 {
+    type Output = Point3f32;
+    fn add(self, v: Vector3f32) -> Point3f32 {
+        Point3f32::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+
+/// 点 - ベクトル = 点
+impl Sub<Vector3f32> for Point3f32 {
+    type Output = Point3f32;
+    fn sub(self, v: Vector3f32) -> Point3f32 {
+        Point3f32::new(self.x - v.x, self.y - v.y, self.z - v.z)
+    }
+}
+
+/// 点 - 点 = ベクトル
+impl Sub for Point3f32 {
+    type Output = Vector3f32;
+    fn sub(self, o: Point3f32) -> Vector3f32 {
+        Vector3f32::new(self.x - o.x, self.y - o.y, self.z - o.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_vector_ops() {
+        let a = Vector3f32::new(1.0, 0.0, 0.0);
+        let b = Vector3f32::new(0.0, 1.0, 0.0);
+        assert_eq!(a.cross(b), Vector3f32::new(0.0, 0.0, 1.0));
+        assert_eq!(a.dot(b), 0.0);
+        let mut c = a + b * 2.0;
+        c -= a;
+        c /= 2.0;
+        assert_eq!(c, b);
+        assert_eq!(-c, Vector3f32::new(0.0, -1.0, 0.0));
+        assert!(Vector3f32::default().try_normalized().is_err());
+        assert_eq!(
+            Vector2f32::new(1.0, 0.0).perp_dot(Vector2f32::new(0.0, 1.0)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_precision_conversion() {
+        let v = Vector3::new(1.0, 2.5, -3.0);
+        assert_eq!(v.to_f32().to_f64(), v);
+        assert_eq!(Vector3::from(v.to_f32()), v);
+        let p = Point3::new(0.1, 0.2, 0.3);
+        // 単精度への変換では誤差が生じるが、f32 の精度内で一致する
+        assert!(p.to_f32().to_f64().distance(p) < 1e-7);
+
+        let pts = vec![Point3::new(1.0, 2.0, 3.0), Point3::new(4.0, 5.0, 6.0)];
+        let single = points_to_f32(&pts);
+        assert_eq!(single[1], Point3f32::new(4.0, 5.0, 6.0));
+        assert_eq!(points_to_f64(&single), pts);
+    }
+
+    #[test]
+    fn test_f32_layout() {
+        let v = Vector3f32::from([1.0, 2.0, 3.0]);
+        assert_eq!(v.as_slice(), &[1.0, 2.0, 3.0]);
+        assert_eq!(v[1], 2.0);
+        let p = Point3f32::new(1.0, 1.0, 1.0) + v;
+        assert_eq!(p - Point3f32::new(1.0, 1.0, 1.0), v);
+    }
+}
@@ -3,6 +3,7 @@
 use std::fs::File;
 use std::io::Read;
 
+use occt_krs::precision::approx_eq;
 use occt_krs::Vector3; // ここは実際のクレート名に合わせて変更してください
 use serde::Deserialize;
 
@@ -27,11 +28,6 @@ struct PythonResults {
     v1_normalized: Vector3,
 }
 
-/// 浮動小数点数の比較（許容誤差 tol 以内なら同一とみなす）
-fn approx_eq(a: f64, b: f64, tol: f64) -> bool {
-    (a - b).abs() < tol
-}
-
 #[test]
 fn test_vector_operations_against_python_results() {
     // プロジェクトのルートディレクトリから result ディレクトリのパスを生成する